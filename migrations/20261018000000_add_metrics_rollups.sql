@@ -0,0 +1,18 @@
+-- Nodes metrics averaged over fixed-size time buckets (60 = 1-minute, 3600 = 1-hour buckets)
+CREATE TABLE IF NOT EXISTS nodes_metrics_rollups (
+    node_id TEXT NOT NULL,
+    resolution INTEGER NOT NULL,
+    timestamp INTEGER NOT NULL,
+    key TEXT NOT NULL,
+    value REAL NOT NULL
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_nodes_metrics_rollups
+    ON nodes_metrics_rollups(node_id, resolution, key, timestamp);
+
+-- How long (in hours) to keep raw metrics samples.
+ALTER TABLE settings ADD COLUMN metrics_raw_retention_hours INTEGER NOT NULL DEFAULT 24;
+-- How long (in days) to keep 1-minute averaged metrics.
+ALTER TABLE settings ADD COLUMN metrics_minute_retention_days INTEGER NOT NULL DEFAULT 7;
+-- How long (in days) to keep 1-hour averaged metrics.
+ALTER TABLE settings ADD COLUMN metrics_hourly_retention_days INTEGER NOT NULL DEFAULT 180;
@@ -0,0 +1,26 @@
+-- Nodes metrics samples with numeric values, replacing the untyped table
+CREATE TABLE IF NOT EXISTS nodes_metrics_typed (
+    node_id TEXT NOT NULL,
+    timestamp INTEGER NOT NULL,
+    key TEXT NOT NULL,
+    value REAL NOT NULL
+);
+
+-- Convert existing samples, discarding those which are incomplete or not numeric,
+-- since casting any other text to REAL would silently turn it into 0.0
+INSERT INTO nodes_metrics_typed (node_id, timestamp, key, value)
+    SELECT node_id, timestamp, key, CAST(value AS REAL)
+    FROM nodes_metrics
+    WHERE timestamp IS NOT NULL AND key IS NOT NULL AND value IS NOT NULL
+        AND (typeof(value) IN ('integer', 'real')
+            OR (trim(value) GLOB '*[0-9]*' AND trim(value) NOT GLOB '*[^0-9.eE+-]*'));
+
+DROP TABLE nodes_metrics;
+ALTER TABLE nodes_metrics_typed RENAME TO nodes_metrics;
+
+-- Used when querying the metrics of a node
+CREATE INDEX IF NOT EXISTS idx_nodes_metrics_node_id_timestamp
+    ON nodes_metrics(node_id, timestamp);
+-- Used when rolling up and pruning metrics of all nodes
+CREATE INDEX IF NOT EXISTS idx_nodes_metrics_timestamp
+    ON nodes_metrics(timestamp);
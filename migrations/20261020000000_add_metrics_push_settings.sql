@@ -0,0 +1,10 @@
+-- Push metrics to an InfluxDB server using the line protocol over HTTP.
+ALTER TABLE settings ADD COLUMN influxdb_enabled INTEGER NOT NULL DEFAULT 0;
+-- InfluxDB write endpoint URL.
+ALTER TABLE settings ADD COLUMN influxdb_url TEXT NOT NULL DEFAULT 'http://localhost:8086/api/v2/write?org=formicaio&bucket=formicaio';
+-- Optional InfluxDB API token.
+ALTER TABLE settings ADD COLUMN influxdb_token TEXT NOT NULL DEFAULT '';
+-- Push metrics to a Graphite server using the plaintext protocol over TCP.
+ALTER TABLE settings ADD COLUMN graphite_enabled INTEGER NOT NULL DEFAULT 0;
+-- Address (host:port) of the Graphite server.
+ALTER TABLE settings ADD COLUMN graphite_addr TEXT NOT NULL DEFAULT 'localhost:2003';
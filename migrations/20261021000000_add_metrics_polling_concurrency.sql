@@ -0,0 +1,2 @@
+-- Maximum number of nodes whose metrics are fetched concurrently.
+ALTER TABLE settings ADD COLUMN nodes_metrics_polling_concurrency INTEGER NOT NULL DEFAULT 10;
@@ -0,0 +1,13 @@
+-- Periodic snapshots of the aggregated stats of all nodes
+CREATE TABLE IF NOT EXISTS stats_history (
+    timestamp INTEGER PRIMARY KEY NOT NULL,
+    total_nodes INTEGER NOT NULL,
+    active_nodes INTEGER NOT NULL,
+    connected_peers INTEGER NOT NULL,
+    shunned_count INTEGER NOT NULL,
+    estimated_net_size INTEGER NOT NULL,
+    stored_records INTEGER NOT NULL,
+    relevant_records INTEGER NOT NULL,
+    used_disk_space INTEGER NOT NULL,
+    available_disk_space INTEGER NOT NULL
+);
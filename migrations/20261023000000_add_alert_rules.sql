@@ -0,0 +1,20 @@
+-- User-defined threshold-based alert rules
+CREATE TABLE IF NOT EXISTS alert_rules (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    metric TEXT NOT NULL,
+    operator TEXT NOT NULL,
+    threshold REAL NOT NULL,
+    hysteresis REAL NOT NULL DEFAULT 0,
+    for_secs INTEGER NOT NULL DEFAULT 0,
+    severity TEXT NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT 1,
+    silenced_until INTEGER
+);
+
+-- Set of rules available out of the box
+INSERT INTO alert_rules (name, metric, operator, threshold, hysteresis, for_secs, severity) VALUES
+    ('Node exited', 'node_exited', '>', 0.5, 0, 0, 'critical'),
+    ('Low connected peers', 'node_connected_peers', '<', 5, 2, 600, 'warning'),
+    ('Low free disk space', 'free_disk_percent', '<', 10, 2, 0, 'critical'),
+    ('No earnings', 'hours_since_last_earning', '>', 24, 0, 0, 'warning');
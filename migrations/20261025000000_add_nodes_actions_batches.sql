@@ -0,0 +1,14 @@
+-- Nodes actions batches with their per-node progress, kept as history once finished
+CREATE TABLE IF NOT EXISTS nodes_actions_batches (
+    id INTEGER PRIMARY KEY,
+    batch_type TEXT NOT NULL,
+    interval_secs INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    complete INTEGER NOT NULL DEFAULT 0,
+    items TEXT NOT NULL DEFAULT '[]',
+    created_at INTEGER NOT NULL,
+    started_at INTEGER,
+    finished_at INTEGER
+);
+
+CREATE INDEX IF NOT EXISTS idx_nodes_actions_batches_finished_at ON nodes_actions_batches (finished_at);
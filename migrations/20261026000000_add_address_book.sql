@@ -0,0 +1,7 @@
+-- Labels, notes and colours assigned by the user to rewards addresses
+CREATE TABLE IF NOT EXISTS address_book (
+    address TEXT PRIMARY KEY,
+    label TEXT NOT NULL,
+    note TEXT NOT NULL DEFAULT '',
+    color TEXT NOT NULL DEFAULT '#6366f1'
+);
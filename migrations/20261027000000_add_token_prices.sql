@@ -0,0 +1,16 @@
+-- Fiat currency reward payments are valued in when exporting earnings.
+ALTER TABLE settings ADD COLUMN fiat_currency TEXT NOT NULL DEFAULT 'usd';
+-- URL of the HTTP API the daily token price is retrieved from ('{date}' and '{currency}' are replaced).
+ALTER TABLE settings ADD COLUMN token_price_api_url TEXT NOT NULL DEFAULT 'https://api.coingecko.com/api/v3/coins/autonomi/history?date={date}&localization=false';
+
+-- Daily price of the rewards token in each fiat currency, as retrieved from the price API
+CREATE TABLE IF NOT EXISTS token_prices (
+    -- Day (UTC) in 'YYYY-MM-DD' format
+    date TEXT NOT NULL,
+    currency TEXT NOT NULL,
+    price REAL NOT NULL,
+    PRIMARY KEY (date, currency)
+);
+
+CREATE INDEX IF NOT EXISTS idx_earnings_timestamp
+    ON earnings(timestamp);
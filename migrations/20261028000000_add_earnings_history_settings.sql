@@ -0,0 +1,12 @@
+-- Comma-separated lengths (in hours) of the periods earnings are analysed over.
+ALTER TABLE settings ADD COLUMN earnings_periods_hours TEXT NOT NULL DEFAULT '48,168,720,2160';
+-- How long (in days) reward payments history is kept in the DB.
+ALTER TABLE settings ADD COLUMN earnings_retention_days INTEGER NOT NULL DEFAULT 210;
+-- Optional block number or day ('YYYY-MM-DD') to track earnings history since.
+ALTER TABLE settings ADD COLUMN earnings_history_start TEXT;
+
+-- Earliest block the earnings history of each rewards address has been fetched from
+CREATE TABLE IF NOT EXISTS earnings_sync (
+    address TEXT PRIMARY KEY,
+    from_block INTEGER NOT NULL
+);
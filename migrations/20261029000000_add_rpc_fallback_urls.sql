@@ -0,0 +1,2 @@
+-- Comma-separated URLs of the L2 network RPC endpoints to fail over to, in priority order.
+ALTER TABLE settings ADD COLUMN l2_network_rpc_fallback_urls TEXT NOT NULL DEFAULT '';
@@ -0,0 +1,3 @@
+-- Comma-separated contract addresses of the ERC-20 tokens, other than the rewards token,
+-- which balances are also tracked for each rewards address.
+ALTER TABLE settings ADD COLUMN additional_token_contracts TEXT NOT NULL DEFAULT '';
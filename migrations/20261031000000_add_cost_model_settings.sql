@@ -0,0 +1,8 @@
+-- Price of the electricity per kWh, in the fiat currency.
+ALTER TABLE settings ADD COLUMN electricity_price_per_kwh REAL NOT NULL DEFAULT 0.2;
+-- Power (in watts) drawn by the host running the nodes, 0 to estimate it from the nodes CPU usage.
+ALTER TABLE settings ADD COLUMN host_power_watts REAL NOT NULL DEFAULT 0;
+-- Estimated power (in watts) drawn per each CPU usage percentage point of a node.
+ALTER TABLE settings ADD COLUMN watts_per_cpu_percent REAL NOT NULL DEFAULT 0.3;
+-- Cost of each GB of disk space used per month, in the fiat currency.
+ALTER TABLE settings ADD COLUMN disk_cost_per_gb_month REAL NOT NULL DEFAULT 0.02;
@@ -0,0 +1,29 @@
+-- Increases of the rewards reported by each node through its metrics, pending to be
+-- correlated with the on-chain payments received by the node's rewards address.
+CREATE TABLE IF NOT EXISTS nodes_rewards_deltas (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    node_id TEXT NOT NULL,
+    -- Increase of the rewards, in attos
+    amount TEXT NOT NULL,
+    -- UNIX timestamp (millis) of the metrics sample the increase was observed in
+    timestamp INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_nodes_rewards_deltas_timestamp
+    ON nodes_rewards_deltas(timestamp);
+
+-- Share of each reward payment estimated to have been earned by each node
+CREATE TABLE IF NOT EXISTS nodes_earnings (
+    node_id TEXT NOT NULL,
+    address TEXT NOT NULL,
+    -- Amount attributed to the node, in attos
+    amount TEXT NOT NULL,
+    block_number INTEGER NOT NULL,
+    -- UNIX timestamp (seconds) of the block the payment was included in
+    timestamp INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_nodes_earnings_node_id
+    ON nodes_earnings(node_id);
+CREATE INDEX IF NOT EXISTS idx_nodes_earnings_block_number
+    ON nodes_earnings(address, block_number);
@@ -0,0 +1,13 @@
+-- Monthly traffic (in GB) allowed by the host's connection, 0 means no cap
+ALTER TABLE settings ADD COLUMN monthly_traffic_cap_gb INTEGER NOT NULL DEFAULT 0;
+
+-- Traffic received and transmitted by the host's network interfaces each month
+CREATE TABLE IF NOT EXISTS network_traffic (
+    -- Calendar month (UTC) in 'YYYY-MM' format
+    month TEXT PRIMARY KEY,
+    received_bytes INTEGER NOT NULL DEFAULT 0,
+    transmitted_bytes INTEGER NOT NULL DEFAULT 0
+);
+
+INSERT INTO alert_rules (name, metric, operator, threshold, hysteresis, for_secs, severity) VALUES
+    ('Monthly traffic cap', 'monthly_traffic_cap_percent', '>', 80, 5, 0, 'warning');
@@ -0,0 +1,21 @@
+-- Users allowed to log in to the web UI and server API
+CREATE TABLE IF NOT EXISTS users (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    username TEXT NOT NULL UNIQUE,
+    -- Argon2 hash of the password, in PHC string format
+    password_hash TEXT NOT NULL,
+    -- UNIX timestamp (seconds) the user was created at
+    created INTEGER NOT NULL
+);
+
+-- Sessions of the users logged in
+CREATE TABLE IF NOT EXISTS sessions (
+    -- SHA-256 hash of the session token, hex-encoded, so tokens can't be taken from the DB
+    token_hash TEXT PRIMARY KEY,
+    user_id INTEGER NOT NULL,
+    -- UNIX timestamps (seconds) the session was created at and expires at
+    created INTEGER NOT NULL,
+    expires INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_sessions_expires ON sessions(expires);
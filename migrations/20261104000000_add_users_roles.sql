@@ -0,0 +1,3 @@
+-- Role of each user: 'viewer', 'operator' or 'admin'.
+-- Users created before roles existed keep full access.
+ALTER TABLE users ADD COLUMN role TEXT NOT NULL DEFAULT 'admin';
@@ -0,0 +1,5 @@
+-- Retrieve the token prices of a whole range of days with a single request to CoinGecko,
+-- unless the user set a price API other than the default one.
+UPDATE settings
+SET token_price_api_url = 'https://api.coingecko.com/api/v3/coins/autonomi/market_chart/range?vs_currency={currency}&from={from}&to={to}'
+WHERE token_price_api_url = 'https://api.coingecko.com/api/v3/coins/autonomi/history?date={date}&localization=false';
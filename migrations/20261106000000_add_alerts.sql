@@ -0,0 +1,14 @@
+-- Alerts fired by the alert rules, so they survive restarts without firing again
+CREATE TABLE IF NOT EXISTS alerts (
+    id INTEGER PRIMARY KEY,
+    rule_id INTEGER NOT NULL,
+    rule_name TEXT NOT NULL,
+    severity TEXT NOT NULL,
+    node_id TEXT,
+    value REAL NOT NULL,
+    message TEXT NOT NULL,
+    fired_at INTEGER NOT NULL,
+    resolved_at INTEGER,
+    acked_at INTEGER,
+    silenced BOOLEAN NOT NULL DEFAULT 0
+);
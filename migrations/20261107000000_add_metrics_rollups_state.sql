@@ -0,0 +1,14 @@
+-- Oldest timestamp (in millis) of each rollup resolution whose bucket still needs to be
+-- averaged, so buckets which get samples after being rolled up are averaged again
+CREATE TABLE IF NOT EXISTS metrics_rollups_state (
+    resolution INTEGER PRIMARY KEY,
+    unrolled_from INTEGER NOT NULL
+);
+
+-- Start from the bucket following the last one rolled up of each resolution
+INSERT OR IGNORE INTO metrics_rollups_state (resolution, unrolled_from)
+    SELECT 60, COALESCE(MAX(timestamp) + 60000, 0)
+    FROM nodes_metrics_rollups WHERE resolution = 60;
+INSERT OR IGNORE INTO metrics_rollups_state (resolution, unrolled_from)
+    SELECT 3600, COALESCE(MAX(timestamp) + 3600000, 0)
+    FROM nodes_metrics_rollups WHERE resolution = 3600;
@@ -0,0 +1,13 @@
+-- Token prices are cached per price API they were retrieved from, so changing it in the
+-- settings doesn't keep serving the prices of the previous one. The prices cached so far
+-- are discarded since the API they were retrieved from is unknown.
+DROP TABLE IF EXISTS token_prices;
+CREATE TABLE IF NOT EXISTS token_prices (
+    -- Day (UTC) in 'YYYY-MM-DD' format
+    date TEXT NOT NULL,
+    currency TEXT NOT NULL,
+    -- URL template of the price API the price was retrieved from
+    source TEXT NOT NULL,
+    price REAL NOT NULL,
+    PRIMARY KEY (date, currency, source)
+);
@@ -1,4 +1,4 @@
-use crate::types::AppSettings;
+use crate::types::{AddressBook, AppSettings};
 
 #[cfg(feature = "ssr")]
 pub use super::app_context::AppContext;
@@ -9,13 +9,17 @@ use super::node_mgr::NodeManager;
 use super::server_api::{get_new_agent_events, get_settings, nodes_instances};
 use super::{
     error_template::{AppError, ErrorTemplate},
-    types::{NodeId, NodeInstanceInfo, NodesActionsBatch, NodesSortStrategy, Stats},
-    views::{HomeScreenView, Notification, about::AboutView, terminal::TerminalView},
+    server_api::{address_book, auth_status},
+    types::{
+        AuthStatus, CurrentUser, NodeId, NodeInstanceInfo, NodesActionsBatch, NodesSortStrategy,
+        Permission, Stats,
+    },
+    views::{HomeScreenView, LoginView, Notification, about::AboutView, terminal::TerminalView},
 };
 
 #[cfg(feature = "hydrate")]
 use super::{
-    types::AgentEventType,
+    types::{AgentEvent, AgentEventType, NodesInstancesInfo, ServerEvent},
     views::{show_error_alert_msg, show_warning_alert_msg},
 };
 
@@ -34,18 +38,24 @@ use leptos_router::{
 };
 use std::collections::{HashMap, HashSet};
 use wasm_bindgen::{JsValue, prelude::*};
+#[cfg(feature = "hydrate")]
+use web_sys::{EventSource, MessageEvent};
 
 #[wasm_bindgen(module = "/public/metamask.js")]
 extern "C" {
     pub async fn get_addr_from_metamask() -> JsValue;
 }
 
-// Maximum number of metrics data points to be kept per node on DB cache.
+// Maximum number of metrics data points per series to be kept by the metrics charts.
 pub const METRICS_MAX_SIZE_PER_NODE: usize = 5_000;
 /// Number of days to retain agent events in the DB before pruning.
 pub const AGENT_EVENTS_MAX_AGE_DAYS: u32 = 30;
-// How often we poll the backend to retrieve an up to date list of node instances.
+// How often we poll the backend to retrieve an up to date list of node instances,
+// only when the live events stream is not available.
 pub const NODES_LIST_POLLING_FREQ_MILLIS: u64 = 5_500;
+// URL of the live events stream served by the backend.
+#[cfg(feature = "hydrate")]
+const SERVER_EVENTS_URL: &str = "/api/events";
 
 /// Global server-side state shared across the application, available only when running with SSR (server-side rendering).
 #[cfg(feature = "ssr")]
@@ -99,6 +109,19 @@ pub struct ClientGlobalState {
     pub current_page: RwSignal<usize>,
     // Current values of the app settings
     pub app_settings: RwSignal<AppSettings>,
+    // Labels, notes and colours assigned to rewards addresses
+    pub address_book: RwSignal<AddressBook>,
+    // User logged in, along with its role
+    pub current_user: RwSignal<Option<CurrentUser>>,
+}
+
+impl ClientGlobalState {
+    /// Whether the user logged in is allowed the given permission,
+    /// used to hide the actions its role can't perform.
+    pub fn can(&self, permission: Permission) -> bool {
+        self.current_user
+            .with(|user| user.as_ref().is_some_and(|user| user.can(permission)))
+    }
 }
 
 pub fn shell(options: LeptosOptions) -> impl IntoView {
@@ -142,14 +165,10 @@ pub fn App() -> impl IntoView {
         nodes_sort_strategy: RwSignal::new(NodesSortStrategy::default()),
         current_page: RwSignal::new(0usize),
         app_settings: RwSignal::new(AppSettings::default()),
+        address_book: RwSignal::new(AddressBook::default()),
+        current_user: RwSignal::new(None),
     });
 
-    // spawn poller tasks only on client side
-    #[cfg(feature = "hydrate")]
-    spawn_nodes_list_polling();
-    #[cfg(feature = "hydrate")]
-    spawn_agent_events_polling();
-
     view! {
         <Router>
             <main>
@@ -163,44 +182,273 @@ pub fn App() -> impl IntoView {
 
                 <Title text="Formicaio" />
 
-                <Routes fallback=|| {
-                    let mut outside_errors = Errors::default();
-                    outside_errors.insert_with_default_key(AppError::NotFound);
-                    view! { <ErrorTemplate outside_errors /> }.into_view()
-                }>
-                    <Route path=StaticSegment("/") view=HomeScreenView />
-                    <Route path=StaticSegment("/about") view=AboutView />
-                    <Route path=StaticSegment("/terminal") view=TerminalView />
-                </Routes>
+                <AuthGate>
+                    <Routes fallback=|| {
+                        let mut outside_errors = Errors::default();
+                        outside_errors.insert_with_default_key(AppError::NotFound);
+                        view! { <ErrorTemplate outside_errors /> }.into_view()
+                    }>
+                        <Route path=StaticSegment("/") view=HomeScreenView />
+                        <Route path=StaticSegment("/about") view=AboutView />
+                        <Route path=StaticSegment("/terminal") view=TerminalView />
+                    </Routes>
+                </AuthGate>
             </main>
         </Router>
     }
 }
 
+// Renders its children only if the client is logged in, showing the login form otherwise,
+// or the form to create the first user if none has been set up yet.
+#[component]
+fn AuthGate(children: ChildrenFn) -> impl IntoView {
+    let context = expect_context::<ClientGlobalState>();
+    let status = Resource::new(|| (), |_| auth_status());
+    let children = StoredValue::new(children);
+
+    view! {
+        <Suspense fallback=|| ()>
+            {move || Suspend::new(async move {
+                match status.await {
+                    Ok(AuthStatus::Authenticated(user)) => {
+                        context.current_user.set(Some(user));
+                        // spawn tasks which keep the state up to date only on client side
+                        #[cfg(feature = "hydrate")]
+                        spawn_server_updates();
+                        children.read_value()().into_any()
+                    }
+                    Ok(AuthStatus::SetupRequired) => view! { <LoginView setup=true /> }.into_any(),
+                    Ok(AuthStatus::LoginRequired) => view! { <LoginView setup=false /> }.into_any(),
+                    Err(err) => {
+                        view! {
+                            <p class="p-8 text-rose-400">
+                                {format!("Failed to check authentication status: {err}")}
+                            </p>
+                        }
+                            .into_any()
+                    }
+                }
+            })}
+        </Suspense>
+    }
+}
+
+// Spawns a task which subscribes to the live events stream of the server to keep the
+// information of nodes instances up to date, falling back to polling the server if
+// the stream is not available, e.g. behind a proxy which doesn't support it.
+#[cfg(feature = "hydrate")]
+fn spawn_server_updates() {
+    spawn_local(async {
+        let context = expect_context::<ClientGlobalState>();
+        refresh_app_settings(context).await;
+        refresh_address_book(context).await;
+        if let Err(err) = subscribe_server_events(context) {
+            logging::log!("[Task] Failed to subscribe to server events, polling instead: {err:?}");
+            spawn_nodes_list_polling();
+            spawn_agent_events_polling();
+        }
+    });
+}
+
+// Subscribe to the live events stream of the server, applying each event received to the context.
+#[cfg(feature = "hydrate")]
+fn subscribe_server_events(context: ClientGlobalState) -> Result<(), JsValue> {
+    let events_source = EventSource::new(SERVER_EVENTS_URL)?;
+
+    // Track the last agent error shown to suppress repeated identical errors.
+    let mut last_error_shown: Option<String> = None;
+    let on_message = Closure::<dyn FnMut(MessageEvent)>::new(move |msg: MessageEvent| {
+        let Some(data) = msg.data().as_string() else {
+            return;
+        };
+        match serde_json::from_str::<ServerEvent>(&data) {
+            Ok(event) => apply_server_event(context, event, &mut last_error_shown),
+            Err(err) => logging::log!("[Task] Failed to parse event received from server: {err}"),
+        }
+    });
+    events_source.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+    on_message.forget();
+
+    // settings changed while disconnected are not notified, so we retrieve them upon (re)connection
+    let on_open = Closure::<dyn FnMut()>::new(move || {
+        context.is_online.set(true);
+        spawn_local(refresh_app_settings(context));
+    });
+    events_source.set_onopen(Some(on_open.as_ref().unchecked_ref()));
+    on_open.forget();
+
+    let source = events_source.clone();
+    let on_error = Closure::<dyn FnMut()>::new(move || {
+        context.is_online.set(false);
+        // the browser keeps reconnecting by itself unless the stream was rejected
+        if source.ready_state() == EventSource::CLOSED {
+            logging::log!("[Task] Server events stream was closed, polling the server instead");
+            source.set_onmessage(None);
+            source.set_onopen(None);
+            source.set_onerror(None);
+            spawn_nodes_list_polling();
+            spawn_agent_events_polling();
+        }
+    });
+    events_source.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+    on_error.forget();
+
+    Ok(())
+}
+
+// Update the client global state with an event received from the server.
+#[cfg(feature = "hydrate")]
+fn apply_server_event(
+    context: ClientGlobalState,
+    event: ServerEvent,
+    last_error_shown: &mut Option<String>,
+) {
+    match event {
+        ServerEvent::Snapshot(info) => apply_nodes_info(context, *info),
+        ServerEvent::NodeUpdated(info) => {
+            context.is_action_triggered.set(ActionTriggered::None);
+            let cx_node = context
+                .nodes
+                .with_untracked(|(_, nodes)| nodes.get(&info.node_id).cloned());
+            match cx_node {
+                Some(cn) => cn.set(*info),
+                None => context.nodes.update(|(_, nodes)| {
+                    let _ = nodes.insert(info.node_id.clone(), RwSignal::new(*info));
+                }),
+            }
+        }
+        ServerEvent::NodeRemoved(node_id) => {
+            context.nodes.update(|(_, nodes)| {
+                let _ = nodes.remove(&node_id);
+            });
+            fit_current_page(context);
+        }
+        ServerEvent::StatsUpdated(stats) => context.stats.set(*stats),
+        ServerEvent::BatchesUpdated(batches) => {
+            context.is_action_triggered.set(ActionTriggered::None);
+            apply_batches(context, batches);
+        }
+        ServerEvent::AgentEvent(event) => show_agent_event(&event, last_error_shown),
+        ServerEvent::NewBinVersion(version) => context.latest_bin_version.set(Some(version)),
+        ServerEvent::SettingsUpdated => spawn_local(refresh_app_settings(context)),
+    }
+}
+
+// Retrieve the app settings from the server and update them in the client global state.
+#[cfg(feature = "hydrate")]
+async fn refresh_app_settings(context: ClientGlobalState) {
+    let app_settings = get_settings().await.unwrap_or_default();
+    apply_app_settings(&context, app_settings);
+}
+
+// Retrieve the rewards addresses book from the server and update it in the client global state.
+pub async fn refresh_address_book(context: ClientGlobalState) {
+    match address_book().await {
+        Ok(book) => context.address_book.set(book),
+        Err(err) => leptos::logging::log!("Failed to retrieve rewards addresses book: {err}"),
+    }
+}
+
+// Update the app settings in the client global state, only if there were changes.
+pub fn apply_app_settings(context: &ClientGlobalState, app_settings: AppSettings) {
+    context.app_settings.update(|s| {
+        // update node list mode only if it was changed on the backend
+        context.tile_mode.update(|m| {
+            let updated_tile_mode = app_settings.node_list_mode == 0;
+            let context_tile_mode = s.node_list_mode == 0;
+            if updated_tile_mode != context_tile_mode && m != &updated_tile_mode {
+                *m = updated_tile_mode;
+            }
+        });
+
+        // update app settings in context only if there were changes
+        if s != &app_settings {
+            *s = app_settings;
+        }
+    });
+}
+
+// Update the client global state with the full list of nodes, stats and batches.
+#[cfg(feature = "hydrate")]
+fn apply_nodes_info(context: ClientGlobalState, info: NodesInstancesInfo) {
+    // if we received info about new binary version then update context
+    if info.latest_bin_version.is_some() {
+        context.latest_bin_version.set(info.latest_bin_version);
+    }
+
+    context.stats.update(|s| *s = info.stats);
+
+    // update info about node action batches in progress
+    apply_batches(context, info.scheduled_batches);
+
+    context.is_action_triggered.set(ActionTriggered::None);
+
+    // first let's get rid of those removed remotely
+    context.nodes.update(|(loaded, cx_nodes)| {
+        *loaded = true;
+        cx_nodes.retain(|id, _| info.nodes.contains_key(id))
+    });
+    // let's now update those with new values
+    context.nodes.with_untracked(|(_, cx_nodes)| {
+        for (id, cn) in cx_nodes {
+            if let Some(updated) = info.nodes.get(id)
+                && cn.read_untracked() != *updated
+            {
+                cn.update(|cn| *cn = updated.clone());
+            }
+        }
+    });
+    // we can add any new node created remotely, perhaps by another instance of the app
+    info.nodes
+        .into_iter()
+        .filter(|(id, _)| !context.nodes.read_untracked().1.contains_key(id))
+        .for_each(|(id, new_node)| {
+            context.nodes.update(|(_, nodes)| {
+                let _ = nodes.insert(id.clone(), RwSignal::new(new_node));
+            })
+        });
+
+    fit_current_page(context);
+}
+
+// Update the client global state with the list of node action batches.
+#[cfg(feature = "hydrate")]
+fn apply_batches(context: ClientGlobalState, batches: Vec<NodesActionsBatch>) {
+    context.scheduled_batches.update(|cx_batches| {
+        // first let's get rid of those removed remotely
+        cx_batches.retain(|cx_b| batches.iter().any(|b| b.id == cx_b.read_untracked().id));
+        // now update and/or add those which are new
+        batches.into_iter().enumerate().for_each(|(index, batch)| {
+            if let Some(cx_batch) = cx_batches.get(index) {
+                if cx_batch.read_untracked().id == batch.id {
+                    cx_batch.update(|b| *b = batch);
+                } else {
+                    cx_batches.insert(index, RwSignal::new(batch));
+                }
+            } else {
+                cx_batches.insert(index, RwSignal::new(batch));
+            }
+        });
+    });
+}
+
+// Make sure our pagination is not overflowing the number of nodes.
+#[cfg(feature = "hydrate")]
+fn fit_current_page(context: ClientGlobalState) {
+    let count = context.nodes.with_untracked(|(_, nodes)| nodes.len());
+    if count > 0 && context.current_page.get_untracked() >= count {
+        context.current_page.update(|c| *c = count - 1);
+    }
+}
+
 // Spawns a task which polls the server to obtain up to date information of nodes instances.
 #[cfg(feature = "hydrate")]
 fn spawn_nodes_list_polling() {
     spawn_local(async {
         let context = expect_context::<ClientGlobalState>();
         loop {
-            let app_settings = get_settings().await.unwrap_or_default();
-            let node_list_page_size = app_settings.node_list_page_size as usize;
-
-            context.app_settings.update(|s| {
-                // update node list mode only if it was changed on the backend
-                context.tile_mode.update(|m| {
-                    let updated_tile_mode = app_settings.node_list_mode == 0;
-                    let context_tile_mode = s.node_list_mode == 0;
-                    if updated_tile_mode != context_tile_mode && m != &updated_tile_mode {
-                        *m = updated_tile_mode;
-                    }
-                });
-
-                // update app settings in context only if there were changes
-                if s != &app_settings {
-                    *s = app_settings;
-                }
-            });
+            refresh_app_settings(context).await;
+            let node_list_page_size = context.app_settings.read_untracked().node_list_page_size;
 
             let delay_millis = match nodes_instances(None).await {
                 Err(err) => {
@@ -212,73 +460,11 @@ fn spawn_nodes_list_polling() {
                 }
                 Ok(info) => {
                     context.is_online.set(true);
-
-                    // if we received info about new binary version then update context
-                    if info.latest_bin_version.is_some() {
-                        context.latest_bin_version.set(info.latest_bin_version);
-                    }
-
-                    context.stats.update(|s| *s = info.stats);
-
-                    // update info about node action batches in progress
-                    context.scheduled_batches.update(|cx_batches| {
-                        // first let's get rid of those removed remotely
-                        cx_batches.retain(|cx_b| {
-                            info.scheduled_batches
-                                .iter()
-                                .any(|b| b.id == cx_b.read_untracked().id)
-                        });
-                        // now update and/or add those which are new
-                        info.scheduled_batches.into_iter().enumerate().for_each(
-                            |(index, batch)| {
-                                if let Some(cx_batch) = cx_batches.get(index) {
-                                    if cx_batch.read_untracked().id == batch.id {
-                                        cx_batch.update(|b| *b = batch);
-                                    } else {
-                                        cx_batches.insert(index, RwSignal::new(batch));
-                                    }
-                                } else {
-                                    cx_batches.insert(index, RwSignal::new(batch));
-                                }
-                            },
-                        );
-                    });
-
-                    context.is_action_triggered.set(ActionTriggered::None);
-
-                    // first let's get rid of those removed remotely
-                    context.nodes.update(|(loaded, cx_nodes)| {
-                        *loaded = true;
-                        cx_nodes.retain(|id, _| info.nodes.contains_key(id))
-                    });
-                    // let's now update those with new values
-                    context.nodes.with_untracked(|(_, cx_nodes)| {
-                        for (id, cn) in cx_nodes {
-                            if let Some(updated) = info.nodes.get(id)
-                                && cn.read_untracked() != *updated
-                            {
-                                cn.update(|cn| *cn = updated.clone());
-                            }
-                        }
-                    });
-                    // we can add any new node created remotely, perhaps by another instance of the app
-                    info.nodes
-                        .into_iter()
-                        .filter(|(id, _)| !context.nodes.read_untracked().1.contains_key(id))
-                        .for_each(|(id, new_node)| {
-                            context.nodes.update(|(_, nodes)| {
-                                let _ = nodes.insert(id.clone(), RwSignal::new(new_node));
-                            })
-                        });
-
-                    // make sure our pagination is not overflowing the number of nodes
-                    let count = context.nodes.with_untracked(|(_, nodes)| nodes.len());
-                    if count > 0 && context.current_page.get_untracked() >= count {
-                        context.current_page.update(|c| *c = count - 1);
-                    }
+                    apply_nodes_info(context, info);
 
                     // the larger the number of nodes, the longer the delay
-                    (count / node_list_page_size) as u64 * 1_000
+                    let count = context.nodes.with_untracked(|(_, nodes)| nodes.len());
+                    (count / node_list_page_size as usize) as u64 * 1_000
                 }
             };
 
@@ -319,23 +505,7 @@ fn spawn_agent_events_polling() {
                     // Skip notifications on the very first poll — it just syncs the watermark
                     if !is_first_poll {
                         for event in events {
-                            let msg =
-                                agent_event_notification(&event.event_type, &event.description);
-                            match &event.event_type {
-                                AgentEventType::AnomalyDetected => {
-                                    last_error_shown = None; // reset dedup on successful actions
-                                    show_warning_alert_msg(msg);
-                                }
-                                AgentEventType::Error => {
-                                    // Suppress consecutive identical errors so a persistent
-                                    // backend outage doesn't spam the bell every 15 seconds.
-                                    if last_error_shown.as_deref() != Some(&msg) {
-                                        last_error_shown = Some(msg.clone());
-                                        show_error_alert_msg(msg);
-                                    }
-                                }
-                                AgentEventType::ActionTaken | AgentEventType::Info => {} // skip — informational only
-                            }
+                            show_agent_event(&event, &mut last_error_shown);
                         }
                     }
 
@@ -349,6 +519,27 @@ fn spawn_agent_events_polling() {
     });
 }
 
+// Push an autonomous agent event to the alert bell, if it's worth notifying.
+#[cfg(feature = "hydrate")]
+fn show_agent_event(event: &AgentEvent, last_error_shown: &mut Option<String>) {
+    let msg = agent_event_notification(&event.event_type, &event.description);
+    match &event.event_type {
+        AgentEventType::AnomalyDetected => {
+            *last_error_shown = None; // reset dedup on successful actions
+            show_warning_alert_msg(msg);
+        }
+        AgentEventType::Error => {
+            // Suppress consecutive identical errors so a persistent
+            // backend outage doesn't spam the bell every 15 seconds.
+            if last_error_shown.as_deref() != Some(&msg) {
+                *last_error_shown = Some(msg.clone());
+                show_error_alert_msg(msg);
+            }
+        }
+        AgentEventType::ActionTaken | AgentEventType::Info => {} // skip — informational only
+    }
+}
+
 // Produce a concise, human-readable notification string for an autonomous agent event.
 // ActionTaken descriptions have the format "Called {tool}: {json_result}" which can be
 // very long — we map the tool name to a short label and drop the result payload.
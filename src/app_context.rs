@@ -1,7 +1,10 @@
 use super::{
-    bg_tasks::{AgentContext, BgTasksCmds, ImmutableNodeStatus, NodeActionsBatches, NodesMetrics},
+    bg_tasks::{
+        AgentContext, AlertsEngine, BgTasksCmds, ImmutableNodeStatus, MetricPoint,
+        NodeActionsBatches, NodesMetrics, RpcEndpoints,
+    },
     db_client::DbClient,
-    types::Stats,
+    types::{NotificationEvent, ServerEvent, Stats},
 };
 
 use axum::extract::FromRef;
@@ -22,14 +25,24 @@ pub struct AppContext {
     pub node_status_locked: ImmutableNodeStatus,
     /// Channel for sending task commands to the background tasks handler.
     pub bg_tasks_cmds_tx: broadcast::Sender<BgTasksCmds>,
+    /// Channel for sending collected metrics points to be pushed to the configured sinks.
+    pub metrics_push_tx: broadcast::Sender<Vec<MetricPoint>>,
+    /// Bus of live events pushed to the subscribers of the events stream.
+    pub events_tx: broadcast::Sender<ServerEvent>,
+    /// Channel for sending events to be delivered through the notification channels.
+    pub notifications_tx: broadcast::Sender<NotificationEvent>,
     /// Batches of node actions currently being processed or scheduled.
     pub node_action_batches: NodeActionsBatches,
     /// Global statistics of all the node instances, shared and mutable across threads.
     pub stats: Arc<RwLock<Stats>>,
     /// This is used to determine if the MCP server is active and can be interacted with.
     pub mcp_status: Arc<RwLock<Option<String>>>,
+    /// Alerts fired by the user-defined alert rules, and the state of their evaluation.
+    pub alerts: Arc<RwLock<AlertsEngine>>,
     /// Shared context for the local AI agent (settings, autonomous mode flag, command channel).
     pub agent_ctx: AgentContext,
+    /// L2 network RPC endpoints, with their health, used to query balances and earnings.
+    pub rpc_endpoints: RpcEndpoints,
 }
 
 impl AppContext {
@@ -39,17 +52,55 @@ impl AppContext {
         let nodes_metrics = Arc::new(RwLock::new(NodesMetrics::new(db_client.clone())));
         let (bg_tasks_cmds_tx, _rx) = broadcast::channel::<BgTasksCmds>(1_000);
         let agent_settings = db_client.get_settings().await;
+        let rpc_endpoints = RpcEndpoints::new(&agent_settings.l2_network_rpc_urls());
         let agent_ctx = AgentContext::new(agent_settings);
+        let alerts = AlertsEngine::new(db_client.get_alerts().await);
         Self {
             db_client,
             latest_bin_version: Arc::new(RwLock::new(None)),
             nodes_metrics,
             node_status_locked: ImmutableNodeStatus::default(),
             bg_tasks_cmds_tx,
+            metrics_push_tx: broadcast::channel(100).0,
+            events_tx: broadcast::channel(1_000).0,
+            notifications_tx: broadcast::channel(100).0,
             node_action_batches: Arc::new(RwLock::new((broadcast::channel(3).0, Vec::new()))),
             stats: Arc::new(RwLock::new(Stats::default())),
             mcp_status: Arc::new(RwLock::new(None)),
+            alerts: Arc::new(RwLock::new(alerts)),
             agent_ctx,
+            rpc_endpoints,
         }
     }
+
+    /// Set the latest version of the node binary, publishing it onto the events bus if it's a new one.
+    pub async fn set_latest_bin_version(&self, version: Option<semver::Version>) {
+        let mut latest = self.latest_bin_version.write().await;
+        if let Some(ref v) = version
+            && latest.as_ref() != Some(v)
+        {
+            let _ = self
+                .events_tx
+                .send(ServerEvent::NewBinVersion(v.to_string()));
+        }
+        *latest = version;
+    }
+
+    /// Apply the given update to the global stats, publishing them onto the events bus if they changed.
+    pub async fn update_stats<R>(&self, update: impl FnOnce(&mut Stats) -> R) -> R {
+        let mut stats = self.stats.write().await;
+        let prev = stats.clone();
+        let res = update(&mut stats);
+        // the duration of the polling cycle changes on every cycle, so it's not a change on its own
+        let changed = Stats {
+            metrics_polling_cycle_millis: prev.metrics_polling_cycle_millis,
+            ..stats.clone()
+        } != prev;
+        if changed {
+            let _ = self
+                .events_tx
+                .send(ServerEvent::StatsUpdated(Box::new(stats.clone())));
+        }
+        res
+    }
 }
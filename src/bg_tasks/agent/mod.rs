@@ -7,8 +7,12 @@ pub use tool_executor::{ToolExecutor, summarize_result};
 use crate::{
     app_context::AppContext,
     node_mgr::NodeManager,
-    types::{AgentEventType, AppSettings, ChatMessage, StreamChunk},
+    types::{
+        AgentEventType, AppSettings, ChatMessage, NotificationEvent, NotificationEventKind,
+        ServerEvent, StreamChunk, UserRole,
+    },
 };
+use tool_executor::AUTONOMOUS_AGENT_ROLE;
 
 use bytes::Bytes;
 use futures_util::StreamExt;
@@ -178,11 +182,13 @@ fn build_system_prompt(custom_suffix: &str) -> String {
 /// Process a single chat turn: stream tokens + tool calls to `tx`.
 /// Loads prior conversation history from the in-memory session store by `session_id`,
 /// and saves the updated history (user + assistant turn) back on completion.
+/// The agent is only offered the tools allowed to the `role` of the user chatting with it.
 pub async fn process_chat_turn(
     user_message: String,
     session_id: String,
     app_ctx: AppContext,
     node_manager: NodeManager,
+    role: UserRole,
     tx: tokio::sync::mpsc::Sender<Bytes>,
 ) {
     let settings = app_ctx.agent_ctx.settings.read().await.clone();
@@ -216,8 +222,8 @@ pub async fn process_chat_turn(
         &settings.llm_model,
         &settings.llm_api_key,
     );
-    let tool_defs = ToolExecutor::tool_definitions();
-    let executor = ToolExecutor::new(app_ctx.clone(), node_manager.clone());
+    let tool_defs = ToolExecutor::tool_definitions(role);
+    let executor = ToolExecutor::new(app_ctx.clone(), node_manager.clone(), role);
 
     // Agentic loop: keep going until the LLM stops requesting tool calls
     let mut full_assistant_content = String::new();
@@ -434,7 +440,7 @@ async fn run_monitoring_cycle(app_ctx: &AppContext, node_manager: &NodeManager)
     // Restrict to read-only + start_node_instance only.
     // Destructive tools (delete, recycle, upgrade, create) are intentionally excluded.
     let tool_defs = ToolExecutor::autonomous_tool_definitions();
-    let executor = ToolExecutor::new(app_ctx.clone(), node_manager.clone());
+    let executor = ToolExecutor::new(app_ctx.clone(), node_manager.clone(), AUTONOMOUS_AGENT_ROLE);
 
     let messages = vec![
         LlmMessage::system(build_system_prompt(AUTONOMOUS_MODE_PROMPT)),
@@ -448,13 +454,12 @@ async fn run_monitoring_cycle(app_ctx: &AppContext, node_manager: &NodeManager)
     loop {
         if actions_taken >= max_actions {
             logging::log!("[Agent] Max actions per cycle ({max_actions}) reached.");
-            app_ctx
-                .db_client
-                .insert_agent_event(
-                    &AgentEventType::Info,
-                    &format!("Monitoring cycle: max actions ({max_actions}) reached"),
-                )
-                .await;
+            record_agent_event(
+                app_ctx,
+                AgentEventType::Info,
+                &format!("Monitoring cycle: max actions ({max_actions}) reached"),
+            )
+            .await;
             break;
         }
 
@@ -462,13 +467,13 @@ async fn run_monitoring_cycle(app_ctx: &AppContext, node_manager: &NodeManager)
             Ok(s) => s,
             Err(e) => {
                 logging::error!("[ERROR][Agent] Autonomous cycle LLM error: {e}");
-                app_ctx
-                    .db_client
-                    .insert_agent_event(
-                        &AgentEventType::Error,
-                        &format!("LLM error during monitoring: {e}"),
-                    )
-                    .await;
+                let description = format!("LLM error during monitoring: {e}");
+                record_agent_event(app_ctx, AgentEventType::Error, &description).await;
+                let _ = app_ctx.notifications_tx.send(NotificationEvent::new(
+                    NotificationEventKind::AgentError,
+                    "AI agent error",
+                    &description,
+                ));
                 return;
             }
         };
@@ -513,10 +518,7 @@ async fn run_monitoring_cycle(app_ctx: &AppContext, node_manager: &NodeManager)
 
         if !text.is_empty() {
             logging::log!("[Agent] Autonomous summary: {text}");
-            app_ctx
-                .db_client
-                .insert_agent_event(&AgentEventType::Info, &text)
-                .await;
+            record_agent_event(app_ctx, AgentEventType::Info, &text).await;
         }
 
         if pending.is_empty() {
@@ -556,13 +558,12 @@ async fn run_monitoring_cycle(app_ctx: &AppContext, node_manager: &NodeManager)
                 "[Agent] Autonomous action '{name}': {}",
                 summarize_result(&result)
             );
-            app_ctx
-                .db_client
-                .insert_agent_event(
-                    &AgentEventType::ActionTaken,
-                    &format!("Called {name}: {result}"),
-                )
-                .await;
+            record_agent_event(
+                app_ctx,
+                AgentEventType::ActionTaken,
+                &format!("Called {name}: {result}"),
+            )
+            .await;
 
             current_messages.push(LlmMessage::tool_result(id, &result));
             actions_taken += 1;
@@ -574,6 +575,17 @@ async fn run_monitoring_cycle(app_ctx: &AppContext, node_manager: &NodeManager)
 
 // ─── Helpers ────
 
+// Record an agent event, publishing it onto the live events bus once stored.
+async fn record_agent_event(app_ctx: &AppContext, event_type: AgentEventType, description: &str) {
+    if let Some(event) = app_ctx
+        .db_client
+        .insert_agent_event(&event_type, description)
+        .await
+    {
+        let _ = app_ctx.events_tx.send(ServerEvent::AgentEvent(event));
+    }
+}
+
 async fn send_chunk(tx: &tokio::sync::mpsc::Sender<Bytes>, chunk: &StreamChunk) {
     if let Ok(mut json) = serde_json::to_string(chunk) {
         json.push('\n');
@@ -1,10 +1,15 @@
 use super::llm_client::{FunctionDefinition, LlmToolCall, ToolDefinition};
-use crate::{app_context::AppContext, bg_tasks::mcp_tools::*, node_mgr::NodeManager};
+use crate::{
+    app_context::AppContext, bg_tasks::mcp_tools::*, node_mgr::NodeManager, types::UserRole,
+};
 
 use leptos::logging;
 use rust_mcp_sdk::schema::Tool as McpTool;
 use serde_json::{Value, json};
 
+/// Role the autonomous agent acts on behalf of, which allows starting nodes.
+pub const AUTONOMOUS_AGENT_ROLE: UserRole = UserRole::Operator;
+
 impl From<McpTool> for ToolDefinition {
     fn from(tool: McpTool) -> Self {
         let parameters = serde_json::to_value(&tool.input_schema)
@@ -22,16 +27,19 @@ impl From<McpTool> for ToolDefinition {
 
 /// Dispatches LLM tool calls directly to the existing `mcp_tools` functions,
 /// avoiding any HTTP round-trip through the external MCP server.
+/// Only the tools allowed to the role the agent acts on behalf of can be executed.
 pub struct ToolExecutor {
     app_ctx: AppContext,
     node_manager: NodeManager,
+    role: UserRole,
 }
 
 impl ToolExecutor {
-    pub fn new(app_ctx: AppContext, node_manager: NodeManager) -> Self {
+    pub fn new(app_ctx: AppContext, node_manager: NodeManager, role: UserRole) -> Self {
         Self {
             app_ctx,
             node_manager,
+            role,
         }
     }
 
@@ -62,6 +70,17 @@ impl ToolExecutor {
             return err;
         }
 
+        if !self.role.can(tool_permission(name)) {
+            logging::warn!(
+                "[Agent] Tool '{name}' rejected, not allowed to the '{}' role",
+                self.role.as_str()
+            );
+            return json_error(&format!(
+                "Permission denied: the '{}' role is not allowed to use the '{name}' tool",
+                self.role.as_str()
+            ));
+        }
+
         let result = match name.as_str() {
             "fetch_stats" => {
                 let tool = FetchStats {};
@@ -70,6 +89,13 @@ impl ToolExecutor {
                     Err(e) => json_error(&e.to_string()),
                 }
             }
+            "fetch_host_stats" => match serde_json::from_value::<FetchHostStats>(args) {
+                Ok(tool) => match tool.call_tool(&self.app_ctx).await {
+                    Ok(r) => extract_text_or_ok(&r),
+                    Err(e) => json_error(&e.to_string()),
+                },
+                Err(e) => json_error(&format!("bad args: {e}")),
+            },
             "nodes_instances" => {
                 let tool = NodeInstances {};
                 match tool.call_tool(&self.app_ctx, &self.node_manager).await {
@@ -77,8 +103,18 @@ impl ToolExecutor {
                     Err(e) => json_error(&e.to_string()),
                 }
             }
+            "fetch_profitability" => match serde_json::from_value::<FetchProfitability>(args) {
+                Ok(tool) => match tool.call_tool(&self.app_ctx, &self.node_manager).await {
+                    Ok(r) => extract_text_or_ok(&r),
+                    Err(e) => json_error(&e.to_string()),
+                },
+                Err(e) => json_error(&format!("bad args: {e}")),
+            },
             "create_node_instance" => match build_create_node_instance(&args) {
-                Ok(tool) => match tool.call_tool(&self.node_manager).await {
+                Ok(tool) => match tool
+                    .call_tool(&self.app_ctx, &self.node_manager, self.role)
+                    .await
+                {
                     Ok(r) => extract_text_or_ok(&r),
                     Err(e) => json_error(&e.to_string()),
                 },
@@ -135,9 +171,11 @@ impl ToolExecutor {
     /// Returns the OpenAI-format tool definitions to pass in each chat request.
     /// Derived automatically from the MCP tool structs — descriptions and parameter
     /// schemas are the single source of truth in `mcp_tools.rs`.
-    pub fn tool_definitions() -> Vec<ToolDefinition> {
+    /// Only the tools allowed to the given role are included.
+    pub fn tool_definitions(role: UserRole) -> Vec<ToolDefinition> {
         FormicaioTools::tools()
             .into_iter()
+            .filter(|t| role.can(tool_permission(&t.name)))
             .map(ToolDefinition::from)
             .collect()
     }
@@ -146,12 +184,16 @@ impl ToolExecutor {
     /// Only read-only and safe-start tools are allowed — destructive operations
     /// (delete, recycle, upgrade, create) must not be available to the autonomous agent.
     pub fn autonomous_tool_definitions() -> Vec<ToolDefinition> {
-        Self::tool_definitions()
+        Self::tool_definitions(AUTONOMOUS_AGENT_ROLE)
             .into_iter()
             .filter(|t| {
                 matches!(
                     t.function.name.as_str(),
-                    "fetch_stats" | "nodes_instances" | "start_node_instance"
+                    "fetch_stats"
+                        | "fetch_host_stats"
+                        | "nodes_instances"
+                        | "fetch_profitability"
+                        | "start_node_instance"
                 )
             })
             .collect()
@@ -210,6 +252,7 @@ fn transform_nodes_for_llm(raw: &str) -> String {
                 "connected_peers": v.get("connected_peers"),
                 "records": v.get("records"),
                 "balance": balance,
+                "estimated_earnings": v.get("estimated_earnings"),
                 "bin_version": v.get("bin_version"),
             })
         })
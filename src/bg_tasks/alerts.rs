@@ -0,0 +1,558 @@
+use crate::{
+    app::AppContext,
+    types::{
+        Alert, AlertMetric, AlertRule, AlertState, NodeId, NodeInstanceInfo, NotificationEvent,
+        NotificationEventKind, Stats,
+    },
+};
+
+use chrono::Utc;
+use leptos::logging;
+use std::collections::{HashMap, HashSet};
+
+// Maximum number of resolved alerts kept in the list, the oldest ones are dropped first.
+const MAX_RESOLVED_ALERTS: usize = 50;
+
+// Values the alert rules are evaluated against.
+pub struct AlertInputs {
+    pub nodes: Vec<NodeInstanceInfo>,
+    pub stats: Stats,
+    pub hours_since_last_earning: Option<f64>,
+}
+
+impl AlertInputs {
+    // Values of the metric for each subject it can be evaluated on, i.e. for each node
+    // for node metrics, or a single value (with no node id) for the aggregated stats.
+    fn values(&self, metric: AlertMetric) -> Vec<(Option<NodeId>, f64)> {
+        if metric.is_node_metric() {
+            return self
+                .nodes
+                .iter()
+                .filter_map(|info| {
+                    let value = if metric == AlertMetric::NodeExited {
+                        let exited = info.status.is_exited()
+                            || info.status.is_start_failed()
+                            || info.status.is_inactive_unknown();
+                        Some(if exited { 1.0 } else { 0.0 })
+                    } else if info.status.is_active() && !info.is_status_unknown {
+                        match metric {
+                            AlertMetric::NodeConnectedPeers => {
+                                info.connected_peers.map(|v| v as f64)
+                            }
+                            AlertMetric::NodeShunnedCount => info.shunned_count.map(|v| v as f64),
+                            AlertMetric::NodeStoredRecords => info.records.map(|v| v as f64),
+                            AlertMetric::NodeCpuUsage => info.cpu_usage,
+                            AlertMetric::NodeMemUsedMb => info.mem_used,
+                            _ => None,
+                        }
+                    } else {
+                        // metrics of inactive nodes are meaningless
+                        None
+                    };
+                    value.map(|v| (Some(info.node_id.clone()), v))
+                })
+                .collect();
+        }
+
+        let stats = &self.stats;
+        let value = match metric {
+            AlertMetric::ActiveNodes => Some(stats.active_nodes as f64),
+            AlertMetric::InactiveNodes => Some(stats.inactive_nodes as f64),
+            AlertMetric::TotalConnectedPeers => Some(stats.connected_peers as f64),
+            AlertMetric::EstimatedNetSize => Some(stats.estimated_net_size as f64),
+            AlertMetric::FreeDiskPercent => (stats.total_disk_space > 0)
+                .then(|| stats.available_disk_space as f64 * 100.0 / stats.total_disk_space as f64),
+            AlertMetric::HoursSinceLastEarning => self.hours_since_last_earning,
+            AlertMetric::MonthlyTrafficCapPercent => stats
+                .monthly_traffic
+                .as_ref()
+                .and_then(|traffic| traffic.cap_usage()),
+            _ => None,
+        };
+        value.map(|v| (None, v)).into_iter().collect()
+    }
+}
+
+// Keeps track of the alerts fired by the rules, evaluating them every time new values are provided.
+#[derive(Debug, Default)]
+pub struct AlertsEngine {
+    next_id: u64,
+    // Timestamp since when each rule has been breached by each subject, without firing yet.
+    pending: HashMap<(i64, Option<NodeId>), i64>,
+    alerts: Vec<Alert>,
+    // Ids of the alerts which changed, and of those dropped, since they were last persisted.
+    changed: HashSet<u64>,
+    removed: Vec<u64>,
+}
+
+impl AlertsEngine {
+    // Create the engine with the alerts restored from the DB, so the ones still firing
+    // are not fired (and notified) again.
+    pub fn new(alerts: Vec<Alert>) -> Self {
+        Self {
+            next_id: alerts.iter().map(|a| a.id).max().unwrap_or_default(),
+            alerts,
+            ..Default::default()
+        }
+    }
+
+    // List of alerts, firing ones first, and the most recent first.
+    pub fn alerts(&self) -> Vec<Alert> {
+        let mut alerts = self.alerts.clone();
+        alerts.sort_by_key(|a| {
+            (
+                !a.is_firing(),
+                std::cmp::Reverse(a.severity),
+                std::cmp::Reverse(a.resolved_at.unwrap_or(a.fired_at)),
+            )
+        });
+        alerts
+    }
+
+    // Acknowledge an alert, returning false if it was not found.
+    pub fn acknowledge(&mut self, alert_id: u64, now: i64) -> bool {
+        match self.alerts.iter_mut().find(|a| a.id == alert_id) {
+            Some(alert) => {
+                alert.acked_at.get_or_insert(now);
+                self.changed.insert(alert_id);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Take the alerts which changed, and the ids of those dropped, since the last call.
+    pub fn take_changes(&mut self) -> (Vec<Alert>, Vec<u64>) {
+        let changed = std::mem::take(&mut self.changed);
+        let alerts = self
+            .alerts
+            .iter()
+            .filter(|a| changed.contains(&a.id))
+            .cloned()
+            .collect();
+        (alerts, std::mem::take(&mut self.removed))
+    }
+
+    // Evaluate the rules against the provided values, updating the state of the alerts.
+    // It returns the alerts which have just fired, and whose rules are not silenced.
+    pub fn evaluate(&mut self, rules: &[AlertRule], inputs: &AlertInputs, now: i64) -> Vec<Alert> {
+        let enabled = rules
+            .iter()
+            .filter(|r| r.enabled)
+            .map(|r| r.id)
+            .collect::<HashSet<_>>();
+
+        // resolve alerts, and forget about breaches, of rules which were disabled or removed
+        self.pending
+            .retain(|(rule_id, _), _| enabled.contains(rule_id));
+        self.alerts
+            .iter_mut()
+            .filter(|a| a.is_firing() && !enabled.contains(&a.rule_id))
+            .for_each(|a| resolve(a, now, &mut self.changed));
+
+        let mut fired = Vec::new();
+        for rule in rules.iter().filter(|r| r.enabled) {
+            let silenced = rule.is_silenced(now);
+            let values = inputs.values(rule.metric);
+            let subjects = values.iter().map(|(s, _)| s).collect::<HashSet<_>>();
+
+            // subjects which cannot be evaluated anymore, e.g. nodes removed or stopped
+            self.pending
+                .retain(|(rule_id, s), _| *rule_id != rule.id || subjects.contains(s));
+            self.alerts
+                .iter_mut()
+                .filter(|a| a.is_firing() && a.rule_id == rule.id && !subjects.contains(&a.node_id))
+                .for_each(|a| resolve(a, now, &mut self.changed));
+
+            for (subject, value) in values {
+                if let Some(alert) = self
+                    .alerts
+                    .iter_mut()
+                    .find(|a| a.is_firing() && a.rule_id == rule.id && a.node_id == subject)
+                {
+                    let unsilenced = alert.silenced && !silenced;
+                    if alert.value != value
+                        || alert.silenced != silenced
+                        || alert.severity != rule.severity
+                        || alert.rule_name != rule.name
+                    {
+                        alert.value = value;
+                        alert.silenced = silenced;
+                        alert.severity = rule.severity;
+                        alert.rule_name = rule.name.clone();
+                        self.changed.insert(alert.id);
+                    }
+                    if rule
+                        .operator
+                        .is_recovered(value, rule.threshold, rule.hysteresis)
+                    {
+                        resolve(alert, now, &mut self.changed);
+                    } else if unsilenced && alert.acked_at.is_none() {
+                        // it's notified once the silence is lifted if it's still firing
+                        fired.push(alert.clone());
+                    }
+                    continue;
+                }
+
+                let key = (rule.id, subject.clone());
+                if !rule.operator.is_breached(value, rule.threshold) {
+                    self.pending.remove(&key);
+                    continue;
+                }
+
+                let since = *self.pending.entry(key.clone()).or_insert(now);
+                if now - since >= rule.for_secs as i64 {
+                    self.pending.remove(&key);
+                    self.next_id += 1;
+                    let alert = Alert {
+                        id: self.next_id,
+                        rule_id: rule.id,
+                        rule_name: rule.name.clone(),
+                        severity: rule.severity,
+                        message: alert_message(rule, subject.as_ref(), value),
+                        node_id: subject,
+                        value,
+                        state: AlertState::Firing,
+                        fired_at: now,
+                        resolved_at: None,
+                        acked_at: None,
+                        silenced,
+                    };
+                    if !silenced {
+                        fired.push(alert.clone());
+                    }
+                    self.changed.insert(alert.id);
+                    self.alerts.push(alert);
+                }
+            }
+        }
+
+        // keep only the most recent resolved alerts
+        let num_resolved = self.alerts.iter().filter(|a| !a.is_firing()).count();
+        if let Some(excess) = num_resolved.checked_sub(MAX_RESOLVED_ALERTS) {
+            let mut resolved = self
+                .alerts
+                .iter()
+                .filter(|a| !a.is_firing())
+                .map(|a| (a.resolved_at, a.id))
+                .collect::<Vec<_>>();
+            resolved.sort();
+            let dropped = resolved
+                .into_iter()
+                .take(excess)
+                .map(|(_, id)| id)
+                .collect::<HashSet<_>>();
+            self.alerts.retain(|a| !dropped.contains(&a.id));
+            self.changed.retain(|id| !dropped.contains(id));
+            self.removed.extend(dropped);
+        }
+
+        fired
+    }
+}
+
+fn resolve(alert: &mut Alert, now: i64, changed: &mut HashSet<u64>) {
+    alert.state = AlertState::Resolved;
+    alert.resolved_at = Some(now);
+    changed.insert(alert.id);
+}
+
+fn alert_message(rule: &AlertRule, node_id: Option<&NodeId>, value: f64) -> String {
+    let subject = node_id
+        .map(|id| format!(" on node {}", id.short_node_id()))
+        .unwrap_or_default();
+    format!(
+        "{}{subject}: {} is {value:.2} (threshold {} {})",
+        rule.name,
+        rule.metric.label(),
+        rule.operator.as_str(),
+        rule.threshold
+    )
+}
+
+// Evaluate the alert rules stored in the DB against the latest nodes metrics, stats and earnings.
+pub async fn evaluate_alert_rules(app_ctx: AppContext) {
+    let rules = app_ctx.db_client.get_alert_rules().await;
+    if !rules.iter().any(|r| r.enabled) && app_ctx.alerts.read().await.alerts.is_empty() {
+        return;
+    }
+
+    let now = Utc::now().timestamp();
+    let mut nodes = app_ctx
+        .db_client
+        .get_nodes_list()
+        .await
+        .into_values()
+        .collect::<Vec<_>>();
+    {
+        let nodes_metrics = app_ctx.nodes_metrics.read().await;
+        nodes
+            .iter_mut()
+            .for_each(|info| nodes_metrics.update_node_info(info));
+    }
+    let inputs = AlertInputs {
+        nodes,
+        stats: app_ctx.stats.read().await.clone(),
+        hours_since_last_earning: app_ctx
+            .db_client
+            .get_last_earning_timestamp()
+            .await
+            .map(|ts| (now - ts) as f64 / 3_600.0),
+    };
+
+    let (fired, (changed, removed)) = {
+        let mut alerts = app_ctx.alerts.write().await;
+        let fired = alerts.evaluate(&rules, &inputs, now);
+        (fired, alerts.take_changes())
+    };
+    let _ = app_ctx.db_client.store_alerts(&changed, &removed).await;
+
+    for alert in fired {
+        logging::warn!(
+            "[WARN][BgTask] Alert fired ({:?}): {}",
+            alert.severity,
+            alert.message
+        );
+        let mut event = NotificationEvent::new(
+            NotificationEventKind::AlertFired,
+            &format!("[{}] {}", alert.severity.as_str(), alert.rule_name),
+            &alert.message,
+        );
+        event.node_id = alert.node_id;
+        let _ = app_ctx.notifications_tx.send(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{AlertOperator, AlertSeverity, NodeStatus};
+
+    fn low_peers_rule() -> AlertRule {
+        AlertRule {
+            id: 1,
+            name: "Low peers".to_string(),
+            metric: AlertMetric::NodeConnectedPeers,
+            operator: AlertOperator::Below,
+            threshold: 5.0,
+            hysteresis: 2.0,
+            for_secs: 600,
+            severity: AlertSeverity::Warning,
+            enabled: true,
+            silenced_until: None,
+        }
+    }
+
+    fn inputs_with_peers(node_id: &NodeId, peers: usize) -> AlertInputs {
+        let mut info = NodeInstanceInfo::new(node_id.clone());
+        info.status = NodeStatus::Active;
+        info.connected_peers = Some(peers);
+        AlertInputs {
+            nodes: vec![info],
+            stats: Stats::default(),
+            hours_since_last_earning: None,
+        }
+    }
+
+    #[test]
+    fn test_alert_fires_after_breach_duration_and_resolves_with_hysteresis() {
+        let node_id = NodeId::new("aaaaaaaaaaaa").unwrap();
+        let rules = [low_peers_rule()];
+        let mut engine = AlertsEngine::default();
+
+        assert!(
+            engine
+                .evaluate(&rules, &inputs_with_peers(&node_id, 3), 0)
+                .is_empty()
+        );
+        assert!(
+            engine
+                .evaluate(&rules, &inputs_with_peers(&node_id, 3), 599)
+                .is_empty()
+        );
+        assert!(engine.alerts().is_empty());
+
+        let fired = engine.evaluate(&rules, &inputs_with_peers(&node_id, 2), 600);
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].node_id, Some(node_id.clone()));
+        assert_eq!(fired[0].state, AlertState::Firing);
+
+        // back above the threshold but within the hysteresis margin
+        assert!(
+            engine
+                .evaluate(&rules, &inputs_with_peers(&node_id, 6), 630)
+                .is_empty()
+        );
+        assert!(engine.alerts()[0].is_firing());
+        assert_eq!(engine.alerts()[0].value, 6.0);
+
+        engine.evaluate(&rules, &inputs_with_peers(&node_id, 7), 660);
+        let alerts = engine.alerts();
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].state, AlertState::Resolved);
+        assert_eq!(alerts[0].resolved_at, Some(660));
+    }
+
+    #[test]
+    fn test_pending_breach_is_reset_when_value_recovers() {
+        let node_id = NodeId::new("aaaaaaaaaaaa").unwrap();
+        let rules = [low_peers_rule()];
+        let mut engine = AlertsEngine::default();
+
+        engine.evaluate(&rules, &inputs_with_peers(&node_id, 3), 0);
+        engine.evaluate(&rules, &inputs_with_peers(&node_id, 5), 300);
+        assert!(
+            engine
+                .evaluate(&rules, &inputs_with_peers(&node_id, 3), 600)
+                .is_empty()
+        );
+        assert_eq!(
+            engine
+                .evaluate(&rules, &inputs_with_peers(&node_id, 3), 1_200)
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_silenced_and_disabled_rules() {
+        let node_id = NodeId::new("aaaaaaaaaaaa").unwrap();
+        let mut rule = low_peers_rule();
+        rule.for_secs = 0;
+        rule.silenced_until = Some(100);
+        let mut engine = AlertsEngine::default();
+
+        // silenced alerts are tracked but not reported as fired
+        assert!(
+            engine
+                .evaluate(&[rule.clone()], &inputs_with_peers(&node_id, 1), 0)
+                .is_empty()
+        );
+        assert!(engine.alerts()[0].silenced);
+        assert!(engine.acknowledge(engine.alerts()[0].id, 5));
+        assert_eq!(engine.alerts()[0].acked_at, Some(5));
+
+        rule.enabled = false;
+        engine.evaluate(&[rule], &inputs_with_peers(&node_id, 1), 10);
+        assert_eq!(engine.alerts()[0].state, AlertState::Resolved);
+    }
+
+    #[test]
+    fn test_silenced_alert_fires_when_silence_is_lifted() {
+        let (node_a, node_b) = (
+            NodeId::new("aaaaaaaaaaaa").unwrap(),
+            NodeId::new("bbbbbbbbbbbb").unwrap(),
+        );
+        let mut rule = low_peers_rule();
+        rule.for_secs = 0;
+        rule.silenced_until = Some(100);
+        let mut inputs = inputs_with_peers(&node_a, 1);
+        inputs.nodes.extend(inputs_with_peers(&node_b, 1).nodes);
+        let mut engine = AlertsEngine::default();
+
+        assert!(engine.evaluate(&[rule.clone()], &inputs, 0).is_empty());
+        // acknowledged ones are not notified when the silence is lifted
+        let acked = engine
+            .alerts()
+            .iter()
+            .find(|a| a.node_id == Some(node_b.clone()))
+            .unwrap()
+            .id;
+        assert!(engine.acknowledge(acked, 5));
+        assert!(engine.evaluate(&[rule.clone()], &inputs, 50).is_empty());
+
+        let fired = engine.evaluate(&[rule.clone()], &inputs, 100);
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].node_id, Some(node_a));
+        assert!(!fired[0].silenced);
+        // it's notified only once
+        assert!(engine.evaluate(&[rule], &inputs, 150).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_alerts_restored_from_db_do_not_fire_again() {
+        let db_client = crate::db_client::test_db_client().await;
+        let node_id = NodeId::new("aaaaaaaaaaaa").unwrap();
+        let rules = [AlertRule {
+            for_secs: 0,
+            ..low_peers_rule()
+        }];
+
+        let mut engine = AlertsEngine::default();
+        assert_eq!(
+            engine
+                .evaluate(&rules, &inputs_with_peers(&node_id, 1), 0)
+                .len(),
+            1
+        );
+        let (changed, removed) = engine.take_changes();
+        db_client.store_alerts(&changed, &removed).await.unwrap();
+        let alert_id = engine.alerts()[0].id;
+        assert!(engine.acknowledge(alert_id, 5));
+        db_client.acknowledge_alert(alert_id, 5).await.unwrap();
+
+        // restarting with the condition still breached neither fires nor loses the ack
+        let mut engine = AlertsEngine::new(db_client.get_alerts().await);
+        assert_eq!(engine.alerts().len(), 1);
+        assert_eq!(engine.alerts()[0].acked_at, Some(5));
+        assert!(
+            engine
+                .evaluate(&rules, &inputs_with_peers(&node_id, 2), 30)
+                .is_empty()
+        );
+        let (changed, removed) = engine.take_changes();
+        db_client.store_alerts(&changed, &removed).await.unwrap();
+        let alerts = db_client.get_alerts().await;
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].id, alert_id);
+        assert_eq!(alerts[0].value, 2.0);
+        assert_eq!(alerts[0].acked_at, Some(5));
+        assert!(alerts[0].is_firing());
+
+        engine.evaluate(&rules, &inputs_with_peers(&node_id, 8), 60);
+        let (changed, removed) = engine.take_changes();
+        db_client.store_alerts(&changed, &removed).await.unwrap();
+        let alerts = db_client.get_alerts().await;
+        assert_eq!(alerts[0].resolved_at, Some(60));
+        assert_eq!(AlertsEngine::new(alerts).next_id, alert_id);
+    }
+
+    #[test]
+    fn test_stats_rules_and_exited_nodes() {
+        let node_id = NodeId::new("aaaaaaaaaaaa").unwrap();
+        let mut inputs = inputs_with_peers(&node_id, 10);
+        inputs.nodes[0].status =
+            NodeStatus::Inactive(crate::types::InactiveReason::Exited("killed".to_string()));
+        inputs.stats.total_disk_space = 1_000;
+        inputs.stats.available_disk_space = 50;
+
+        let exited = AlertRule {
+            id: 1,
+            metric: AlertMetric::NodeExited,
+            operator: AlertOperator::Above,
+            threshold: 0.5,
+            enabled: true,
+            ..Default::default()
+        };
+        let disk = AlertRule {
+            id: 2,
+            metric: AlertMetric::FreeDiskPercent,
+            operator: AlertOperator::Below,
+            threshold: 10.0,
+            enabled: true,
+            ..Default::default()
+        };
+        let peers = AlertRule {
+            id: 3,
+            for_secs: 0,
+            ..low_peers_rule()
+        };
+
+        let mut engine = AlertsEngine::default();
+        let fired = engine.evaluate(&[exited, disk, peers], &inputs, 0);
+        assert_eq!(fired.len(), 2);
+        assert_eq!(fired[0].node_id, Some(node_id));
+        assert_eq!(fired[1].node_id, None);
+        assert_eq!(fired[1].value, 5.0);
+    }
+}
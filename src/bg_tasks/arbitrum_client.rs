@@ -1,16 +1,16 @@
-use crate::db_client::DbClient;
+use super::rpc_endpoints::{RpcEndpoints, RpcEndpointsError};
+use crate::{db_client::DbClient, types::EarningsHistoryStart};
 use alloy::{
     primitives::{Address, B256, U256, keccak256},
-    providers::{Provider, ProviderBuilder},
+    providers::Provider,
     rpc::types::{BlockNumberOrTag, Filter, eth::Log},
 };
-use chrono::{DateTime, TimeZone, Utc};
+use chrono::{DateTime, NaiveTime, TimeZone, Utc};
 use leptos::logging;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use thiserror::Error;
 use tokio::time::{Duration, sleep};
-use url::Url;
 
 // Arbitrum One produces approximately 288,000 blocks per day.
 // That's based on a block time of roughly 0.25–0.3 seconds — about 12,000 blocks
@@ -19,10 +19,8 @@ use url::Url;
 // to sequence Arbitrum, so the actual number fluctuates. But under normal
 // activity levels, ~288K/day is the standard estimate used in practice.
 const BLOCKS_PER_DAY: u64 = 300_000;
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
 const GET_LOGS_CHUNK_SIZE: u64 = 100_000;
-// Number of days of earnings history to track. We need to compare the
-// last 3 months with previous 3 months, i.e. at least 6 months of historic records.
-const NUM_DAYS_TO_TRACK_EARNINGS: u64 = 210;
 // Maximum number of blocks to process per fetch call to throttle RPC usage.
 // Remaining blocks are fetched on the next scheduled invocation.
 const MAX_BLOCKS_PER_FETCH_CALL: u64 = 1_000_000;
@@ -33,13 +31,11 @@ const INTER_CHUNK_DELAY: Duration = Duration::from_millis(500);
 #[derive(Debug, Error)]
 pub enum ArbitrumClientError {
     #[error("RPC error: {0}")]
-    RpcError(String),
+    RpcError(#[from] RpcEndpointsError),
     #[error("Failed to parse data: {0}")]
     ParseError(String),
     #[error("Invalid address: {0}")]
     InvalidAddress(String),
-    #[error("Invalid configuration: {0}")]
-    ConfigError(String),
 }
 
 /// Container for payment records for a specific address
@@ -66,92 +62,103 @@ pub struct PaymentRecord {
 
 /// Client for querying payment data from Arbitrum L2
 pub struct ArbitrumClient {
-    /// RPC endpoint URL
-    endpoint: Url,
+    /// RPC endpoints to send the queries to
+    rpc: RpcEndpoints,
     /// Contract address to query transactions from
     contract_address: Address,
     /// Rewards addresses to monitor (transaction destinations)
     rewards_addresses: Vec<Address>,
     /// Database client for caching earnings
     db_client: DbClient,
+    /// Number of days of earnings history to track
+    retention_days: u64,
+    /// Optional block or day to track earnings history since, overriding the retention period
+    history_start: Option<EarningsHistoryStart>,
 }
 
 impl ArbitrumClient {
     /// Creates a new Arbitrum client
     ///
     /// # Arguments
-    /// * `endpoint` - Arbitrum L2 RPC endpoint URL (e.g., "https://arb1.arbitrum.io/rpc")
+    /// * `rpc` - Arbitrum L2 RPC endpoints (e.g., "https://arb1.arbitrum.io/rpc"), in priority order
     /// * `contract_address` - The contract address to query transactions from (as string with 0x prefix)
     /// * `rewards_addresses` - List of destination addresses to monitor (as strings with 0x prefix)
-    /// * `days_to_track` - Number of days of payment history to retrieve
+    /// * `retention_days` - Number of days of payment history to retrieve
+    /// * `history_start` - Optional block or day to retrieve payment history since instead
     ///
     /// # Errors
     /// Returns `ArbitrumClientError::InvalidAddress` if contract address is malformed
-    /// Returns `ArbitrumClientError::ConfigError` if RPC URL is invalid
     pub fn new<'a>(
-        endpoint: &str,
+        rpc: RpcEndpoints,
         contract_address: &str,
         rewards_addresses: impl Iterator<Item = &'a Address>,
         db_client: DbClient,
+        retention_days: u64,
+        history_start: Option<EarningsHistoryStart>,
     ) -> Result<Self, ArbitrumClientError> {
         let contract_addr = contract_address
             .parse::<Address>()
             .map_err(|_| ArbitrumClientError::InvalidAddress(contract_address.to_string()))?;
 
         Ok(Self {
-            endpoint: endpoint.parse().map_err(|_| {
-                ArbitrumClientError::ConfigError("Invalid RPC endpoint".to_string())
-            })?,
+            rpc,
             contract_address: contract_addr,
             rewards_addresses: rewards_addresses.cloned().collect(),
             db_client,
+            retention_days,
+            history_start,
         })
     }
 
-    /// Prune old earnings history records based on the predefined number of days to track
+    /// Prune earnings history records older than the tracked history, i.e. older than
+    /// the history start if set, or otherwise older than the retention period.
     pub async fn prune_history(
-        endpoint: &str,
+        rpc: &RpcEndpoints,
         db_client: &DbClient,
+        retention_days: u64,
+        history_start: Option<EarningsHistoryStart>,
     ) -> Result<(), ArbitrumClientError> {
-        let endpoint = endpoint
-            .parse()
-            .map_err(|_| ArbitrumClientError::ConfigError("Invalid RPC endpoint".to_string()))?;
-
-        let provider = ProviderBuilder::new().connect_http(endpoint);
-        let current_block = provider
-            .get_block_number()
-            .await
-            .map_err(|e| ArbitrumClientError::RpcError(e.to_string()))?;
-        let blocks_to_fetch = NUM_DAYS_TO_TRACK_EARNINGS * BLOCKS_PER_DAY;
-        let default_from = current_block.saturating_sub(blocks_to_fetch);
-
-        db_client.delete_old_earnings(default_from).await;
+        let current_block = rpc
+            .call(|provider| async move { provider.get_block_number().await })
+            .await?;
+        let from_block = history_start_block(
+            current_block,
+            Utc::now().timestamp(),
+            retention_days,
+            history_start,
+        );
+
+        if from_block > 0 {
+            db_client.delete_old_earnings(from_block - 1).await;
+        }
         Ok(())
     }
 
     /// Fetches incoming payments for all monitored rewards addresses
-    /// First retrieves cached payments from database (if available), then fetches new ones from RPC.
-    /// To avoid overwhelming the RPC server, processing is capped at MAX_BLOCKS_PER_FETCH_CALL
-    /// blocks per call. The second element of the returned tuple indicates whether all blocks
-    /// up to the current chain tip have been processed (true = fully synced).
+    /// First retrieves cached payments from database (if available), then fetches new ones from RPC,
+    /// and finally backfills the history older than the one already fetched, if the tracked history
+    /// was extended. To avoid overwhelming the RPC server, processing is capped at
+    /// MAX_BLOCKS_PER_FETCH_CALL blocks per call. The second element of the returned tuple is the
+    /// percentage of the tracked history that has been processed (100 = fully synced).
     ///
     /// # Errors
     /// Returns `ArbitrumClientError` if the RPC call fails
     pub async fn fetch_incoming_payments(
         &self,
-    ) -> Result<(Vec<AddressPayments>, bool), ArbitrumClientError> {
-        let provider = ProviderBuilder::new().connect_http(self.endpoint.clone());
-
+    ) -> Result<(Vec<AddressPayments>, u8), ArbitrumClientError> {
         // Phase 1: collect cached payments and missing ranges per address
         let mut cached_per_addr: HashMap<Address, HashSet<PaymentRecord>> = HashMap::new();
         let mut missing_ranges: Vec<(u64, u64)> = Vec::new();
+        // Earliest block fetched so far for the addresses whose history needs to be backfilled
+        let mut backfill_synced_from: HashMap<Address, u64> = HashMap::new();
 
-        let current_block = provider
-            .get_block_number()
-            .await
-            .map_err(|e| ArbitrumClientError::RpcError(e.to_string()))?;
-        let blocks_to_fetch = NUM_DAYS_TO_TRACK_EARNINGS * BLOCKS_PER_DAY;
-        let default_from = current_block.saturating_sub(blocks_to_fetch);
+        let current_block = self
+            .rpc
+            .call(|provider| async move { provider.get_block_number().await })
+            .await?;
+        let now = Utc::now().timestamp();
+        let default_from =
+            history_start_block(current_block, now, self.retention_days, self.history_start);
 
         for address in &self.rewards_addresses {
             logging::log!("[Arbitrum] Collecting cached earnings for address: {address}");
@@ -170,6 +177,32 @@ impl ArbitrumClient {
                 missing_ranges.push((requested_from, requested_to));
             }
 
+            let synced_from = match (
+                max_cached,
+                self.db_client.get_earnings_synced_from(address).await,
+            ) {
+                (Some(_), Some(synced_from)) => synced_from,
+                (Some(_), None) => {
+                    // history fetched before its start was kept track of covers the retention period
+                    let synced_from =
+                        history_start_block(current_block, now, self.retention_days, None);
+                    self.db_client
+                        .set_earnings_synced_from(address, synced_from)
+                        .await;
+                    synced_from
+                }
+                (None, _) => {
+                    // nothing cached yet, the whole history is in the missing range
+                    self.db_client
+                        .set_earnings_synced_from(address, default_from)
+                        .await;
+                    default_from
+                }
+            };
+            if synced_from > default_from {
+                backfill_synced_from.insert(*address, synced_from);
+            }
+
             cached_per_addr.insert(*address, cached);
         }
 
@@ -199,12 +232,14 @@ impl ArbitrumClient {
             .map(|addr| addr.into_word())
             .collect();
         let mut remaining_budget = MAX_BLOCKS_PER_FETCH_CALL;
-        let mut fully_synced = true;
+        // Number of blocks of the tracked history still to be processed
+        let mut blocks_left = 0u64;
 
-        'outer: for (from_block, to_block) in merged_ranges {
+        for (from_block, to_block) in merged_ranges {
+            let range_len = to_block - from_block + 1;
             if remaining_budget == 0 {
-                fully_synced = false;
-                break;
+                blocks_left += range_len;
+                continue;
             }
 
             logging::log!(
@@ -220,25 +255,51 @@ impl ArbitrumClient {
                 .to_block(BlockNumberOrTag::Number(to_block));
 
             match self
-                .get_logs_chunked(&provider, filter, &mut cached_per_addr, remaining_budget)
+                .get_logs_chunked(filter, &mut cached_per_addr, remaining_budget)
                 .await
             {
                 Ok((blocks_processed, completed)) => {
-                    remaining_budget = remaining_budget.saturating_sub(blocks_processed);
-                    if !completed {
-                        fully_synced = false;
-                        break 'outer;
+                    if completed {
+                        remaining_budget = remaining_budget.saturating_sub(blocks_processed);
+                    } else {
+                        // stopped by the budget or by an RPC error, leave the rest for next call
+                        blocks_left += range_len.saturating_sub(blocks_processed);
+                        remaining_budget = 0;
                     }
                 }
                 Err(err) => {
                     logging::error!(
                         "[ERROR][Arbitrum] Failed to fetch merged logs for range {from_block}-{to_block}: {err}"
                     );
-                    fully_synced = false;
+                    blocks_left += range_len;
                 }
             }
         }
 
+        // Phase 2b: backfill the history older than the one already fetched,
+        // from the newest to the oldest block, with the remaining budget.
+        if let Some(to_block) = backfill_synced_from.values().max().map(|b| b - 1) {
+            if remaining_budget == 0 {
+                blocks_left += to_block - default_from + 1;
+            } else {
+                logging::log!(
+                    "[Arbitrum] Backfilling earnings history from block #{to_block} back to #{default_from} ..."
+                );
+                let base_filter = Filter::new()
+                    .address(self.contract_address)
+                    .event_signature(transfer_signature);
+                blocks_left += self
+                    .backfill_logs(
+                        base_filter,
+                        (default_from, to_block),
+                        &mut backfill_synced_from,
+                        &mut cached_per_addr,
+                        remaining_budget,
+                    )
+                    .await;
+            }
+        }
+
         // Phase 3: assemble final per-address payments
         let all_payments = cached_per_addr
             .into_iter()
@@ -249,15 +310,17 @@ impl ArbitrumClient {
             })
             .collect();
 
-        Ok((all_payments, fully_synced))
+        let tracked_blocks = current_block.saturating_sub(default_from) + 1;
+        Ok((all_payments, sync_progress(tracked_blocks, blocks_left)))
     }
 
-    // Fetch logs in chunks of GET_LOGS_CHUNK_SIZE blocks, up to max_blocks total.
+    // Fetch logs in chunks of GET_LOGS_CHUNK_SIZE blocks, up to max_blocks total, halving the
+    // chunks whenever the request for one of them is rejected, e.g. for exceeding the block range
+    // or result size limits of the RPC endpoint.
     // Returns (blocks_processed, completed) where completed=true means the full
     // range was consumed (i.e. we were not stopped by the max_blocks budget).
     async fn get_logs_chunked(
         &self,
-        provider: &impl Provider,
         base_filter: Filter,
         cached_per_addr: &mut HashMap<Address, HashSet<PaymentRecord>>,
         max_blocks: u64,
@@ -267,34 +330,41 @@ impl ArbitrumClient {
             _ => return Err(ArbitrumClientError::ParseError("Invalid from_block".into())),
         };
 
-        let to_block = provider
-            .get_block_number()
-            .await
-            .map_err(|e| ArbitrumClientError::RpcError(e.to_string()))?;
+        let to_block = self
+            .rpc
+            .call(|provider| async move { provider.get_block_number().await })
+            .await?;
 
         let mut current = from_block;
         let mut latest_cached_bn = 0u64;
         let mut blocks_fetched = 0u64;
+        let mut chunk_size = GET_LOGS_CHUNK_SIZE;
 
         while current <= to_block && blocks_fetched < max_blocks {
             let remaining_budget = max_blocks - blocks_fetched;
-            let end = (current + GET_LOGS_CHUNK_SIZE.min(remaining_budget) - 1).min(to_block);
+            let end = (current + chunk_size.min(remaining_budget) - 1).min(to_block);
 
             let filter = base_filter
                 .clone()
                 .from_block(BlockNumberOrTag::Number(current))
                 .to_block(BlockNumberOrTag::Number(end));
 
-            match provider.get_logs(&filter).await {
+            match self.get_logs(&filter).await {
                 Ok(logs) => {
                     // cache in db
-                    self.cache_logs(logs, provider, cached_per_addr).await;
+                    self.cache_logs(logs, cached_per_addr).await;
                     latest_cached_bn = end;
                     blocks_fetched += end - current + 1;
                     current = end + 1;
                     // Throttle to avoid overwhelming the RPC server
                     sleep(INTER_CHUNK_DELAY).await;
                 }
+                Err(err @ RpcEndpointsError::RequestRejected { .. }) if end > current => {
+                    chunk_size = (end - current).div_ceil(2);
+                    logging::warn!(
+                        "[WARN][Arbitrum] Failed to get blocks from {current} to {end}, retrying in chunks of {chunk_size} blocks: {err}"
+                    );
+                }
                 Err(err) => {
                     logging::error!(
                         "[ERROR][Arbitrum] Failed to get blocks from {current} to {end}: {err}"
@@ -319,10 +389,102 @@ impl ArbitrumClient {
         Ok((blocks_fetched, completed))
     }
 
+    // Fetch logs of the given range backwards, from its newest to its oldest block, in chunks of
+    // GET_LOGS_CHUNK_SIZE blocks up to max_blocks total, halving the chunks whenever the request
+    // for one of them is rejected. Each chunk is requested only for the addresses whose history
+    // doesn't cover it yet, and the earliest block fetched for them is stored after each chunk
+    // so backfilling resumes from there on the next call.
+    // Returns the number of blocks of the range left to be fetched.
+    async fn backfill_logs(
+        &self,
+        base_filter: Filter,
+        (from_block, to_block): (u64, u64),
+        synced_from: &mut HashMap<Address, u64>,
+        cached_per_addr: &mut HashMap<Address, HashSet<PaymentRecord>>,
+        max_blocks: u64,
+    ) -> u64 {
+        let mut top = to_block;
+        let mut blocks_fetched = 0u64;
+        let mut chunk_size = GET_LOGS_CHUNK_SIZE;
+
+        while blocks_fetched < max_blocks {
+            let size = chunk_size.min(max_blocks - blocks_fetched);
+            let mut bottom = from_block.max(top.saturating_sub(size - 1));
+            // don't go below the history already fetched for other addresses within this chunk,
+            // they'll be included in the request for the next chunk
+            if let Some(synced) = synced_from
+                .values()
+                .filter(|b| **b > bottom && **b <= top)
+                .max()
+            {
+                bottom = *synced;
+            }
+            let addrs = synced_from
+                .iter()
+                .filter(|(_, b)| **b > top)
+                .map(|(addr, _)| *addr)
+                .collect::<Vec<_>>();
+            if addrs.is_empty() {
+                return 0;
+            }
+
+            let filter = base_filter
+                .clone()
+                .topic2(
+                    addrs
+                        .iter()
+                        .map(|addr| addr.into_word())
+                        .collect::<Vec<B256>>(),
+                )
+                .from_block(BlockNumberOrTag::Number(bottom))
+                .to_block(BlockNumberOrTag::Number(top));
+
+            match self.get_logs(&filter).await {
+                Ok(logs) => {
+                    self.cache_logs(logs, cached_per_addr).await;
+                    for addr in addrs {
+                        self.db_client.set_earnings_synced_from(&addr, bottom).await;
+                        synced_from.insert(addr, bottom);
+                    }
+                    blocks_fetched += top - bottom + 1;
+                    if bottom == from_block {
+                        logging::log!(
+                            "[Arbitrum] Successfully backfilled earnings history in DB back to block #{from_block}"
+                        );
+                        return 0;
+                    }
+                    top = bottom - 1;
+                    // Throttle to avoid overwhelming the RPC server
+                    sleep(INTER_CHUNK_DELAY).await;
+                }
+                Err(err @ RpcEndpointsError::RequestRejected { .. }) if top > bottom => {
+                    chunk_size = (top - bottom).div_ceil(2);
+                    logging::warn!(
+                        "[WARN][Arbitrum] Failed to get blocks from {bottom} to {top}, retrying in chunks of {chunk_size} blocks: {err}"
+                    );
+                }
+                Err(err) => {
+                    logging::error!(
+                        "[ERROR][Arbitrum] Failed to get blocks from {bottom} to {top}: {err}"
+                    );
+                    break;
+                }
+            }
+        }
+
+        top - from_block + 1
+    }
+
+    // Get logs matching the filter, failing over to other RPC endpoints if needed.
+    async fn get_logs(&self, filter: &Filter) -> Result<Vec<Log>, RpcEndpointsError> {
+        self.rpc
+            .call(|provider| async move { provider.get_logs(filter).await })
+            .await
+    }
+
     async fn cache_logs(
         &self,
         logs: Vec<Log>,
-        provider: &impl Provider,
         cached_per_addr: &mut HashMap<Address, HashSet<PaymentRecord>>,
     ) {
         // Pre-filter logs to only those relevant to monitored addresses, and collect
@@ -371,8 +533,13 @@ impl ArbitrumClient {
         // Fetch each unique block once to get its timestamp.
         let mut block_timestamps: HashMap<u64, DateTime<Utc>> = HashMap::new();
         for bn in unique_blocks {
-            let block = match provider
-                .get_block_by_number(BlockNumberOrTag::Number(bn))
+            let block = match self
+                .rpc
+                .call(|provider| async move {
+                    provider
+                        .get_block_by_number(BlockNumberOrTag::Number(bn))
+                        .await
+                })
                 .await
             {
                 Ok(Some(b)) => b,
@@ -410,3 +577,92 @@ impl ArbitrumClient {
         }
     }
 }
+
+// First block of the earnings history to track: the history start if set, either a block
+// number or estimated from the number of days since the given day, or otherwise the first
+// block within the retention period.
+fn history_start_block(
+    current_block: u64,
+    now: i64,
+    retention_days: u64,
+    history_start: Option<EarningsHistoryStart>,
+) -> u64 {
+    match history_start {
+        Some(EarningsHistoryStart::Block(block_number)) => block_number.min(current_block),
+        Some(EarningsHistoryStart::Date(date)) => {
+            let secs = now - date.and_time(NaiveTime::MIN).and_utc().timestamp();
+            let blocks = (secs.max(0) as u64).saturating_mul(BLOCKS_PER_DAY) / SECS_PER_DAY;
+            current_block.saturating_sub(blocks)
+        }
+        None => current_block.saturating_sub(retention_days.saturating_mul(BLOCKS_PER_DAY)),
+    }
+}
+
+// Percentage of the tracked blocks already processed, only reporting 100% when none is left.
+fn sync_progress(tracked_blocks: u64, blocks_left: u64) -> u8 {
+    if blocks_left == 0 || tracked_blocks == 0 {
+        return 100;
+    }
+    let processed = tracked_blocks.saturating_sub(blocks_left);
+    ((processed as u128 * 100 / tracked_blocks as u128) as u8).min(99)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_history_start_block() {
+        let current_block = 400_000_000;
+        // 2026-03-11 12:00:00 UTC
+        let now = 1_773_230_400;
+        assert_eq!(
+            history_start_block(current_block, now, 210, None),
+            current_block - 210 * BLOCKS_PER_DAY
+        );
+        assert_eq!(
+            history_start_block(current_block, now, 1, Some(EarningsHistoryStart::Block(5))),
+            5
+        );
+        assert_eq!(
+            history_start_block(10, now, 1, Some(EarningsHistoryStart::Block(50))),
+            10
+        );
+
+        // ten and a half days ago
+        let date = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        assert_eq!(
+            history_start_block(
+                current_block,
+                now,
+                1,
+                Some(EarningsHistoryStart::Date(date))
+            ),
+            current_block - 10 * BLOCKS_PER_DAY - BLOCKS_PER_DAY / 2
+        );
+        // days in the future
+        let date = NaiveDate::from_ymd_opt(2026, 4, 1).unwrap();
+        assert_eq!(
+            history_start_block(
+                current_block,
+                now,
+                1,
+                Some(EarningsHistoryStart::Date(date))
+            ),
+            current_block
+        );
+        assert_eq!(history_start_block(1_000, now, 210, None), 0);
+    }
+
+    #[test]
+    fn test_sync_progress() {
+        assert_eq!(sync_progress(1_000, 0), 100);
+        assert_eq!(sync_progress(0, 0), 100);
+        assert_eq!(sync_progress(1_000, 1_000), 0);
+        assert_eq!(sync_progress(1_000, 250), 75);
+        // never reported as fully synced while there are blocks left
+        assert_eq!(sync_progress(1_000, 1), 99);
+        assert_eq!(sync_progress(1_000, 5_000), 0);
+    }
+}
@@ -0,0 +1,382 @@
+use crate::{
+    app::AppContext,
+    types::{CurrentUser, Permission, SESSION_COOKIE_NAME, normalized_address},
+};
+
+use argon2::{Argon2, PasswordHash, PasswordHasher, PasswordVerifier, password_hash::SaltString};
+use axum::{
+    extract::{Request, State},
+    http::{HeaderMap, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use leptos::logging;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+
+/// How long (in seconds) a session lasts after logging in.
+pub const SESSION_DURATION_SECS: i64 = 30 * 24 * 3_600;
+// Prefix of the paths of the server API.
+const API_PATH_PREFIX: &str = "/api/";
+// Path of the Prometheus metrics exporter, which requires a session as the server API does.
+const METRICS_PATH: &str = "/metrics";
+// Paths of the server API which can be used without being authenticated. The stats widget
+// is polled by the dashboards of home servers, e.g. UmbrelOS, which cannot log in.
+const PUBLIC_API_PATHS: [&str; 4] = [
+    "/api/auth/status",
+    "/api/auth/login",
+    "/api/auth/setup",
+    "/api/stats_widget",
+];
+
+/// Hash a password with Argon2, returning it in PHC string format.
+pub fn hash_password(password: &str) -> Result<String, argon2::password_hash::Error> {
+    let salt = SaltString::encode_b64(&rand::random::<[u8; 16]>())?;
+    Ok(Argon2::default()
+        .hash_password(password.as_bytes(), &salt)?
+        .to_string())
+}
+
+/// Check a password matches the Argon2 hash, in PHC string format, of the user's password.
+pub fn verify_password(password: &str, password_hash: &str) -> bool {
+    PasswordHash::new(password_hash).is_ok_and(|hash| {
+        Argon2::default()
+            .verify_password(password.as_bytes(), &hash)
+            .is_ok()
+    })
+}
+
+/// Generate a new random session token, hex-encoded.
+pub fn new_session_token() -> String {
+    hex::encode(rand::random::<[u8; 32]>())
+}
+
+/// Hash a session token, which is how sessions are stored in the DB.
+pub fn hash_session_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// Value of the Set-Cookie header which keeps the session token in the browser.
+/// An empty token with a zero max age removes the cookie.
+pub fn session_cookie(token: &str, max_age_secs: i64) -> String {
+    format!(
+        "{SESSION_COOKIE_NAME}={token}; Path=/; HttpOnly; SameSite=Strict; Max-Age={max_age_secs}"
+    )
+}
+
+/// Retrieve the session token sent with a request, either as a bearer token,
+/// as sent by the CLI, or in the session cookie, as sent by the browser.
+pub fn session_token(headers: &HeaderMap) -> Option<String> {
+    let bearer = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(|token| token.trim().to_string());
+
+    bearer.or_else(|| {
+        headers
+            .get_all(header::COOKIE)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(';'))
+            .filter_map(|cookie| cookie.trim().split_once('='))
+            .find(|(name, _)| *name == SESSION_COOKIE_NAME)
+            .map(|(_, token)| token.to_string())
+    })
+}
+
+/// Retrieve the user whose session token was sent with a request.
+pub async fn authenticated_user(app_ctx: &AppContext, headers: &HeaderMap) -> Option<CurrentUser> {
+    let token = session_token(headers).filter(|token| !token.is_empty())?;
+    app_ctx
+        .db_client
+        .get_session(&hash_session_token(&token))
+        .await
+        .map(|(user, _)| user)
+}
+
+/// Permission required to create nodes paying rewards to the given addresses. Only admins can
+/// set addresses which are neither used by existing nodes nor in the address book, otherwise
+/// operators could have new nodes paying to a wallet of their own.
+pub async fn rewards_addrs_permission(app_ctx: &AppContext, addrs: &[String]) -> Permission {
+    let address_book = app_ctx.db_client.get_address_book().await;
+    let nodes_addrs = app_ctx
+        .db_client
+        .get_nodes_list()
+        .await
+        .into_values()
+        .filter_map(|info| info.rewards_addr.as_deref().and_then(normalized_address))
+        .collect::<HashSet<_>>();
+    let all_known = addrs.iter().all(|addr| {
+        address_book.get(addr).is_some()
+            || normalized_address(addr).is_some_and(|addr| nodes_addrs.contains(&addr))
+    });
+    if all_known {
+        Permission::OperateNodes
+    } else {
+        Permission::ManageSettings
+    }
+}
+
+/// Permission required to use each path of the server API, or None if it's unknown.
+pub fn endpoint_permission(path: &str) -> Option<Permission> {
+    if path == METRICS_PATH {
+        return Some(Permission::View);
+    }
+    let endpoint = path.strip_prefix(API_PATH_PREFIX)?;
+    let permission = match endpoint {
+        "events"
+        | "auth/logout"
+        | "stats"
+        | "stats/history"
+        | "nodes/list"
+        | "nodes/logs_stream"
+        | "nodes/metrics"
+        | "host/stats"
+        | "host/history"
+        | "settings/get"
+        | "settings/rpc_health"
+        | "mcp/info"
+        | "batch/list"
+        | "batch/history"
+        | "alerts/list"
+        | "alerts/rules/list"
+        | "addresses/book/list"
+        | "addresses/summaries"
+        | "earnings/export"
+        | "earnings/history"
+        | "earnings/profitability"
+        | "agent/history"
+        | "agent/sessions"
+        | "agent/events"
+        | "agent/events/new" => Permission::View,
+        "nodes/create"
+        | "nodes/start"
+        | "nodes/stop"
+        | "nodes/upgrade"
+        | "nodes/recycle"
+        | "nodes/update"
+        | "batch/create"
+        | "batch/create_on_match"
+        | "batch/cancel"
+        | "batch/pause"
+        | "batch/resume"
+        | "batch/interval"
+        | "batch/retry"
+        | "alerts/ack"
+        | "agent/chat"
+        | "agent/history/clear" => Permission::OperateNodes,
+        "nodes/delete" => Permission::RemoveNodes,
+        "settings/set"
+        | "alerts/rules/save"
+        | "alerts/rules/silence"
+        | "alerts/rules/delete"
+        | "addresses/book/save"
+        | "addresses/book/delete"
+        | "notifications/channels/list"
+        | "notifications/channels/save"
+        | "notifications/channels/delete"
+        | "notifications/channels/test"
+        | "agent/autonomous"
+        | "agent/test" => Permission::ManageSettings,
+        "users/list" | "users/create" | "users/update" | "users/delete" => Permission::ManageUsers,
+        _ => return None,
+    };
+    Some(permission)
+}
+
+/// Middleware which rejects requests to the server API and the metrics exporter made without
+/// a valid session, except those needed to set up the first user and to log in, as well as
+/// those made by users whose role doesn't have the permission required by the endpoint.
+pub async fn require_auth(State(app_ctx): State<AppContext>, req: Request, next: Next) -> Response {
+    let path = req.uri().path();
+    let protected = path.starts_with(API_PATH_PREFIX) || path == METRICS_PATH;
+    if !protected || PUBLIC_API_PATHS.contains(&path) {
+        return next.run(req).await;
+    }
+
+    let Some(user) = authenticated_user(&app_ctx, req.headers()).await else {
+        logging::log!("[API] Rejected unauthenticated request to {path}");
+        return (StatusCode::UNAUTHORIZED, "Authentication required").into_response();
+    };
+
+    // endpoints not mapped to a permission are only allowed to admins
+    let permission = endpoint_permission(path).unwrap_or(Permission::ManageUsers);
+    if user.can(permission) {
+        next.run(req).await
+    } else {
+        logging::warn!(
+            "[API] Rejected request to {path} from user '{}' with role '{}'",
+            user.username,
+            user.role.as_str()
+        );
+        (
+            StatusCode::FORBIDDEN,
+            format!(
+                "Permission denied, the '{}' role is not allowed to perform this action",
+                user.role.as_str()
+            ),
+        )
+            .into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db_client::test_db_client,
+        types::{AddressBookEntry, NodeId, NodeInstanceInfo, UserRole},
+    };
+    use axum::http::HeaderValue;
+    use tower::ServiceExt;
+
+    #[test]
+    fn test_password_hash_verification() {
+        let hash = hash_password("s3cret-pass").expect("Failed to hash password");
+        assert!(verify_password("s3cret-pass", &hash));
+        assert!(!verify_password("wrong-pass", &hash));
+        assert!(!verify_password("s3cret-pass", "not-a-phc-string"));
+        // each hash is salted differently
+        assert_ne!(
+            hash,
+            hash_password("s3cret-pass").expect("Failed to hash password")
+        );
+    }
+
+    #[test]
+    fn test_session_token_from_headers() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(session_token(&headers), None);
+
+        headers.insert(
+            header::COOKIE,
+            HeaderValue::from_static("theme=dark; formicaio_session=abc123"),
+        );
+        assert_eq!(session_token(&headers), Some("abc123".to_string()));
+
+        // a bearer token takes precedence over the cookie
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer def456"),
+        );
+        assert_eq!(session_token(&headers), Some("def456".to_string()));
+    }
+
+    #[test]
+    fn test_all_endpoints_have_a_permission() {
+        let paths = leptos::server_fn::axum::server_fn_paths().collect::<Vec<_>>();
+        assert!(paths.iter().any(|(path, _)| *path == "/api/users/delete"));
+        for (path, _) in paths {
+            assert!(
+                PUBLIC_API_PATHS.contains(&path) || endpoint_permission(path).is_some(),
+                "No permission set for endpoint {path}"
+            );
+        }
+        assert_eq!(endpoint_permission("/api/events"), Some(Permission::View));
+        assert_eq!(
+            endpoint_permission("/api/nodes/delete"),
+            Some(Permission::RemoveNodes)
+        );
+        assert_eq!(endpoint_permission("/api/unknown"), None);
+        assert_eq!(endpoint_permission("/metrics"), Some(Permission::View));
+        assert_eq!(endpoint_permission("/metricsx"), None);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_rewards_addrs_require_admin() {
+        let app_ctx = AppContext::new(test_db_client().await).await;
+        let book_addr = "0x1111111111111111111111111111111111111111";
+        let node_addr = "0x2222222222222222222222222222222222222222";
+        let unknown_addr = "0x3333333333333333333333333333333333333333".to_string();
+        app_ctx
+            .db_client
+            .store_address_book_entry(&AddressBookEntry {
+                address: book_addr.to_string(),
+                label: "Main".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        app_ctx
+            .db_client
+            .insert_node_metadata(&NodeInstanceInfo {
+                node_id: NodeId::random(),
+                rewards_addr: Some(node_addr.to_string()),
+                ..Default::default()
+            })
+            .await;
+
+        let known = vec![
+            book_addr.to_string(),
+            node_addr.to_uppercase().replace("0X", "0x"),
+        ];
+        assert_eq!(
+            rewards_addrs_permission(&app_ctx, &known).await,
+            Permission::OperateNodes
+        );
+        let with_unknown = vec![book_addr.to_string(), unknown_addr];
+        let permission = rewards_addrs_permission(&app_ctx, &with_unknown).await;
+        assert!(!UserRole::Operator.can(permission));
+        assert!(UserRole::Admin.can(permission));
+    }
+
+    // Router serving the given paths behind the authentication middleware.
+    async fn app_with_auth(paths: &[&'static str]) -> axum::Router {
+        let app_ctx = AppContext::new(test_db_client().await).await;
+        paths
+            .iter()
+            .fold(axum::Router::new(), |app, path| {
+                app.route(path, axum::routing::get(|| async { "ok" }))
+            })
+            .layer(axum::middleware::from_fn_with_state(
+                app_ctx.clone(),
+                require_auth,
+            ))
+            .with_state(app_ctx)
+    }
+
+    fn request(uri: &str, token: Option<&str>) -> Request {
+        let mut req = Request::builder().uri(uri);
+        if let Some(token) = token {
+            req = req.header(header::AUTHORIZATION, format!("Bearer {token}"));
+        }
+        req.body(axum::body::Body::empty()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_metrics_requires_auth() {
+        let app = app_with_auth(&[METRICS_PATH, "/"]).await;
+
+        let resp = app
+            .clone()
+            .oneshot(request(METRICS_PATH, None))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        let resp = app
+            .clone()
+            .oneshot(request(METRICS_PATH, Some("invalid-token")))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        // the web UI pages are still served so the login form can be shown
+        let resp = app.oneshot(request("/", None)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_stats_widget_is_public() {
+        let app = app_with_auth(&["/api/stats_widget", "/api/stats"]).await;
+
+        let resp = app
+            .clone()
+            .oneshot(request("/api/stats_widget", None))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        // the full stats still require a session
+        let resp = app.oneshot(request("/api/stats", None)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    }
+}
@@ -1,15 +1,23 @@
 use crate::{
     app::AppContext,
-    node_mgr::NodeManager,
+    node_mgr::{NodeManager, NodeManagerError},
     server_api::parse_and_validate_addr,
-    types::{BatchStatus, BatchType, NodesActionsBatch},
+    types::{
+        BatchItemOutcome, BatchStatus, BatchType, NodeId, NodesActionsBatch, NotificationEvent,
+        NotificationEventKind, ServerEvent, distribute_rewards_addrs,
+    },
 };
 
 use leptos::logging;
 use rand::RngExt;
 use std::time::Duration;
 use thiserror::Error;
-use tokio::{select, time::sleep};
+use tokio::{select, sync::broadcast, time::sleep};
+
+// Maximum number of finished batches kept in the history.
+const BATCHES_HISTORY_MAX_LEN: u32 = 500;
+// How often the batches runner checks if any paused batch was resumed, when all are paused.
+const BATCH_PAUSED_CHECK_FREQ: Duration = Duration::from_secs(1);
 
 #[derive(Debug, Error)]
 pub enum ActionsBatchError {
@@ -17,6 +25,26 @@ pub enum ActionsBatchError {
     InvalidAddress(String),
     #[error("Cannot create batch {0}: No node IDs provided.")]
     MissingNodeId(BatchType),
+    #[error("Failed to signal the batch to be cancelled: {0}")]
+    CancelSignal(#[from] broadcast::error::SendError<u16>),
+    #[error("Batch {0} not found.")]
+    NotFound(u16),
+    #[error("Batch {0} cannot be {1} while its status is: {2}")]
+    InvalidState(u16, &'static str, BatchStatus),
+    #[error("Batch {0} has no failed actions to retry.")]
+    NothingToRetry(u16),
+    #[error("Batch {0} cannot be retried: {1}")]
+    NotRetriable(u16, String),
+    #[error("Cannot create batch {0}: No configuration changes provided.")]
+    MissingChanges(BatchType),
+    #[error("Cannot create batch {0}: Ports cannot be set to the same values on several nodes.")]
+    SamePortsOnManyNodes(BatchType),
+    #[error("Cannot create batch {0}: The weights of the rewards addresses add up to zero.")]
+    ZeroRewardsAddrsWeights(BatchType),
+    #[error(
+        "Cannot create batch {0}: The ports assigned to the nodes exceed the maximum port number."
+    )]
+    PortsOutOfRange(BatchType),
 }
 
 // Helper to prepare a node actions batch
@@ -28,10 +56,47 @@ pub async fn prepare_node_action_batch(
     node_manager: &NodeManager,
 ) -> Result<u16, ActionsBatchError> {
     match &batch_type {
-        BatchType::Create { node_opts, .. } => {
-            // validate rewards address before accepting the batch
+        BatchType::Create {
+            node_opts,
+            count,
+            rewards_addrs,
+        } => {
+            // each node is assigned the ports following the previous node's ones
+            let last = count.saturating_sub(1);
+            if node_opts.port.checked_add(last).is_none()
+                || node_opts.metrics_port.checked_add(last).is_none()
+            {
+                return Err(ActionsBatchError::PortsOutOfRange(batch_type));
+            }
+            // validate rewards addresses before accepting the batch
             parse_and_validate_addr(&node_opts.rewards_addr)
                 .map_err(ActionsBatchError::InvalidAddress)?;
+            for addr in rewards_addrs.iter() {
+                parse_and_validate_addr(&addr.address)
+                    .map_err(ActionsBatchError::InvalidAddress)?;
+            }
+            if !rewards_addrs.is_empty() && rewards_addrs.iter().all(|a| a.weight == 0) {
+                return Err(ActionsBatchError::ZeroRewardsAddrsWeights(batch_type));
+            }
+        }
+        BatchType::Update {
+            node_ids: l,
+            changes,
+        } => {
+            if l.is_empty() {
+                return Err(ActionsBatchError::MissingNodeId(batch_type));
+            }
+            if changes.is_empty() {
+                return Err(ActionsBatchError::MissingChanges(batch_type));
+            }
+            if l.len() > 1 && changes.changes_ports() {
+                return Err(ActionsBatchError::SamePortsOnManyNodes(batch_type));
+            }
+            if let Some(addr) = &changes.rewards_addr {
+                parse_and_validate_addr(addr).map_err(ActionsBatchError::InvalidAddress)?;
+            }
+
+            lock_batched_nodes(app_ctx, l, interval_secs).await;
         }
         BatchType::Start(l)
         | BatchType::Stop(l)
@@ -46,179 +111,746 @@ pub async fn prepare_node_action_batch(
 
             // let's lock all nodes which are part of the batch,
             // so the user cannot action on it till the batch is completed or cancelled.
-            let duration = Duration::from_secs((interval_secs + 2) * l.len() as u64);
-            for node_id in l.iter() {
-                app_ctx.db_client.set_node_status_to_locked(node_id).await;
-
-                // let's also prevent the backend from updating its status
-                app_ctx
-                    .node_status_locked
-                    .lock(node_id.clone(), duration)
-                    .await;
-            }
+            lock_batched_nodes(app_ctx, l, interval_secs).await;
         }
     }
 
-    let batch_id = rand::rng().random_range(0..=u16::MAX);
+    let batch_id = new_batch_id(app_ctx).await;
     let batch_info = NodesActionsBatch::new(batch_id, batch_type, interval_secs);
     logging::log!("[Batches] Creating new batch with ID {batch_id}: {batch_info:?}");
 
+    // the batch still runs if it fails to be stored, it won't be resumed upon restart though
+    let _ = app_ctx.db_client.store_batch(&batch_info).await;
+    schedule_batch(app_ctx, node_manager, batch_info).await;
+
+    Ok(batch_id)
+}
+
+// Resume the batches which were not finished when the app was stopped,
+// locking again the nodes they haven't actioned yet, unless they were paused.
+pub async fn resume_node_action_batches(app_ctx: &AppContext, node_manager: &NodeManager) {
+    for batch_info in app_ctx.db_client.get_unfinished_batches().await {
+        logging::log!(
+            "[Batches] Resuming batch {} with {}/{} actions already performed ...",
+            batch_info.id,
+            batch_info.items.len(),
+            batch_info.total_actions()
+        );
+        if batch_info.status != BatchStatus::Paused {
+            lock_batched_nodes(
+                app_ctx,
+                &batch_info.pending_node_ids(),
+                batch_info.interval_secs,
+            )
+            .await;
+        }
+        schedule_batch(app_ctx, node_manager, batch_info).await;
+    }
+}
+
+// Cancel a batch if it's scheduled or running, or dismiss it if it already finished.
+pub async fn cancel_node_action_batch(
+    app_ctx: &AppContext,
+    batch_id: u16,
+) -> Result<(), ActionsBatchError> {
+    let mut guard = app_ctx.node_action_batches.write().await;
+    let Some(index) = guard.1.iter().position(|b| b.id == batch_id) else {
+        return Ok(());
+    };
+
+    match guard.1[index].status {
+        // finished batch — just remove it (dismiss), it's kept in the history
+        ref status if status.is_finished() => {
+            guard.1.remove(index);
+            drop(guard);
+            publish_batches(app_ctx).await;
+        }
+        // not started yet — no need to involve the runner
+        BatchStatus::Scheduled => {
+            let batch_info = guard.1.remove(index);
+            drop(guard);
+            publish_batches(app_ctx).await;
+            record_cancelled_batch(app_ctx, batch_info).await;
+        }
+        // still running — signal the runner to cancel
+        _ => {
+            guard.0.send(batch_id)?;
+        }
+    }
+
+    Ok(())
+}
+
+// Pause a running batch, the action being performed, if any, is completed first.
+// The nodes it hasn't actioned yet are unlocked, so the user can act on them meanwhile.
+pub async fn pause_node_action_batch(
+    app_ctx: &AppContext,
+    batch_id: u16,
+) -> Result<(), ActionsBatchError> {
+    logging::log!("[Batches] Pausing batch {batch_id} ...");
+    let batch_info = update_batch(app_ctx, batch_id, |b| {
+        if !b.status.is_running() {
+            return Err(ActionsBatchError::InvalidState(
+                batch_id,
+                "paused",
+                b.status.clone(),
+            ));
+        }
+        b.status = BatchStatus::Paused;
+        Ok(())
+    })
+    .await?;
+    unlock_batched_nodes(app_ctx, &batch_info.pending_node_ids()).await;
+    Ok(())
+}
+
+// Resume a paused batch, locking again the nodes it hasn't actioned yet.
+pub async fn resume_node_action_batch(
+    app_ctx: &AppContext,
+    batch_id: u16,
+) -> Result<(), ActionsBatchError> {
+    logging::log!("[Batches] Resuming batch {batch_id} ...");
+    let batch_info = update_batch(app_ctx, batch_id, |b| {
+        if b.status != BatchStatus::Paused {
+            return Err(ActionsBatchError::InvalidState(
+                batch_id,
+                "resumed",
+                b.status.clone(),
+            ));
+        }
+        b.status = b.running_status();
+        Ok(())
+    })
+    .await?;
+    lock_batched_nodes(
+        app_ctx,
+        &batch_info.pending_node_ids(),
+        batch_info.interval_secs,
+    )
+    .await;
+    Ok(())
+}
+
+// Change the interval between the actions of a batch not finished yet.
+pub async fn set_node_action_batch_interval(
+    app_ctx: &AppContext,
+    batch_id: u16,
+    interval_secs: u64,
+) -> Result<(), ActionsBatchError> {
+    logging::log!("[Batches] Setting interval of batch {batch_id} to {interval_secs} secs. ...");
+    let batch_info = update_batch(app_ctx, batch_id, |b| {
+        if b.status.is_finished() {
+            return Err(ActionsBatchError::InvalidState(
+                batch_id,
+                "updated",
+                b.status.clone(),
+            ));
+        }
+        b.interval_secs = interval_secs;
+        Ok(())
+    })
+    .await?;
+    // the nodes lock expiration depends on the interval, paused batches' nodes are not locked
+    if batch_info.status != BatchStatus::Paused {
+        lock_batched_nodes(app_ctx, &batch_info.pending_node_ids(), interval_secs).await;
+    }
+    Ok(())
+}
+
+// Find a batch, either among the current ones or in the history.
+pub async fn find_node_action_batch(
+    app_ctx: &AppContext,
+    batch_id: u16,
+) -> Result<NodesActionsBatch, ActionsBatchError> {
+    let batch_info = app_ctx
+        .node_action_batches
+        .read()
+        .await
+        .1
+        .iter()
+        .find(|b| b.id == batch_id)
+        .cloned();
+    match batch_info {
+        Some(b) => Ok(b),
+        None => app_ctx
+            .db_client
+            .get_batch(batch_id)
+            .await
+            .ok_or(ActionsBatchError::NotFound(batch_id)),
+    }
+}
+
+// Replace the id of a node in the current batches, e.g. after it got a new one when
+// its configuration was updated, so they keep referencing the same node.
+// Batches in the history are updated by the DB client when the node id is changed.
+pub async fn replace_batched_node_id(app_ctx: &AppContext, node_id: &NodeId, new_node_id: &NodeId) {
+    let updated = {
+        let batches = &mut app_ctx.node_action_batches.write().await.1;
+        batches
+            .iter_mut()
+            .filter(|b| {
+                b.batch_type.ids().contains(node_id)
+                    || b.items.iter().any(|i| i.node_id.as_ref() == Some(node_id))
+            })
+            .map(|b| {
+                b.batch_type.replace_node_id(node_id, new_node_id);
+                for item in b.items.iter_mut() {
+                    if item.node_id.as_ref() == Some(node_id) {
+                        item.node_id = Some(new_node_id.clone());
+                    }
+                }
+                b.clone()
+            })
+            .collect::<Vec<_>>()
+    };
+    if !updated.is_empty() {
+        publish_batches(app_ctx).await;
+    }
+    for batch_info in updated {
+        let _ = app_ctx.db_client.store_batch(&batch_info).await;
+    }
+}
+
+// Create a new batch which retries only the actions which failed in a finished batch.
+// The finished batch is removed from the list, but it's still kept in the history.
+pub async fn retry_node_action_batch(
+    app_ctx: &AppContext,
+    node_manager: &NodeManager,
+    batch_id: u16,
+) -> Result<u16, ActionsBatchError> {
+    let batch_info = find_node_action_batch(app_ctx, batch_id).await?;
+
+    if !batch_info.status.is_finished() {
+        return Err(ActionsBatchError::InvalidState(
+            batch_id,
+            "retried",
+            batch_info.status,
+        ));
+    }
+    let batch_type = batch_info
+        .retry_batch_type()
+        .map_err(|err| ActionsBatchError::NotRetriable(batch_id, err))?
+        .ok_or(ActionsBatchError::NothingToRetry(batch_id))?;
+
+    logging::log!(
+        "[Batches] Retrying {} failed actions of batch {batch_id} ...",
+        batch_info.failures()
+    );
+    let new_batch_id =
+        prepare_node_action_batch(batch_type, batch_info.interval_secs, app_ctx, node_manager)
+            .await?;
+    app_ctx
+        .node_action_batches
+        .write()
+        .await
+        .1
+        .retain(|b| b.id != batch_id);
+    publish_batches(app_ctx).await;
+
+    Ok(new_batch_id)
+}
+
+// Apply a change to a batch in the list, storing it with the change
+async fn update_batch<F>(
+    app_ctx: &AppContext,
+    batch_id: u16,
+    update: F,
+) -> Result<NodesActionsBatch, ActionsBatchError>
+where
+    F: FnOnce(&mut NodesActionsBatch) -> Result<(), ActionsBatchError>,
+{
+    let batch_info = {
+        let batches = &mut app_ctx.node_action_batches.write().await.1;
+        let batch = batches
+            .iter_mut()
+            .find(|b| b.id == batch_id)
+            .ok_or(ActionsBatchError::NotFound(batch_id))?;
+        update(batch)?;
+        batch.clone()
+    };
+    publish_batches(app_ctx).await;
+    let _ = app_ctx.db_client.store_batch(&batch_info).await;
+    Ok(batch_info)
+}
+
+// Publish the list of batches onto the events bus, e.g. after any of them was added,
+// removed or made some progress.
+async fn publish_batches(app_ctx: &AppContext) {
+    let batches = app_ctx.node_action_batches.read().await.1.clone();
+    let _ = app_ctx.events_tx.send(ServerEvent::BatchesUpdated(batches));
+}
+
+// Generate a random ID which is not used by any other batch, either running or in the history
+async fn new_batch_id(app_ctx: &AppContext) -> u16 {
+    loop {
+        let batch_id = rand::rng().random_range(0..=u16::MAX);
+        let is_running = app_ctx
+            .node_action_batches
+            .read()
+            .await
+            .1
+            .iter()
+            .any(|b| b.id == batch_id);
+        if !is_running && !app_ctx.db_client.is_batch_stored(batch_id).await {
+            return batch_id;
+        }
+    }
+}
+
+// Add the batch to the list of batches to run, spawning the runner if it's not running yet
+async fn schedule_batch(
+    app_ctx: &AppContext,
+    node_manager: &NodeManager,
+    batch_info: NodesActionsBatch,
+) {
     let len = {
         let batches = &mut app_ctx.node_action_batches.write().await.1;
         batches.push(batch_info);
-        batches.iter().filter(|b| !b.status.is_failed()).count()
+        batches.iter().filter(|b| !b.status.is_finished()).count()
     };
+    publish_batches(app_ctx).await;
 
     // spawn a task if there was no other tasks already batched
     if len == 1 {
         tokio::spawn(run_batches(app_ctx.clone(), node_manager.clone()));
     }
-
-    Ok(batch_id)
 }
 
 async fn run_batches(app_ctx: AppContext, node_manager: NodeManager) {
     let mut cancel_rx = app_ctx.node_action_batches.read().await.0.subscribe();
 
     loop {
-        let batch_info = if let Some(next_batch) = app_ctx
-            .node_action_batches
-            .write()
-            .await
-            .1
-            .iter_mut()
-            .find(|b| !b.status.is_failed())
-        {
-            let mut batch = next_batch.clone();
-            batch.status = BatchStatus::InProgress;
-            *next_batch = batch.clone();
-            batch
-        } else {
-            return;
+        let next_batch = {
+            let batches = &mut app_ctx.node_action_batches.write().await.1;
+            if batches.iter().all(|b| b.status.is_finished()) {
+                return;
+            }
+            // paused batches are skipped so the ones queued behind them can run meanwhile
+            batches
+                .iter_mut()
+                .find(|b| !b.status.is_finished() && b.status != BatchStatus::Paused)
+                .map(|next_batch| {
+                    // a resumed batch keeps the status it had, e.g. with its failures
+                    if next_batch.status == BatchStatus::Scheduled {
+                        next_batch.status = BatchStatus::InProgress;
+                    }
+                    next_batch
+                        .started_at
+                        .get_or_insert_with(|| chrono::Utc::now().timestamp());
+                    next_batch.clone()
+                })
         };
-
-        match batch_info.batch_type {
-            BatchType::Create {
-                ref node_opts,
-                count,
-            } => {
-                logging::log!(
-                    "[Batches] Started node instances creation batch of {count} nodes ..."
-                );
-                let mut i = 0;
-                loop {
-                    select! {
-                        batch_id = cancel_rx.recv() => {
-                            if matches!(batch_id, Ok(id) if id == batch_info.id) {
-                                unlock_batched_nodes(&app_ctx, &batch_info.batch_type).await;
-                                break;
-                            }
-                        },
-                        _ = sleep(Duration::from_secs(batch_info.interval_secs)) => {
-                            let mut node_opts_clone = node_opts.clone();
-                            node_opts_clone.port += i;
-                            node_opts_clone.metrics_port += i;
-                            let res = node_manager.create_node_instance(node_opts_clone).await;
-                            update_batch_status(&res, &app_ctx, &batch_info, i.into(), count.into()).await;
-                            i += 1;
-                            if i == count {
-                                break;
-                            }
-                        }
+        let Some(batch_info) = next_batch else {
+            // all the batches left are paused, wait till any of them is resumed or cancelled
+            select! {
+                batch_id = cancel_rx.recv() => {
+                    if let Ok(id) = batch_id {
+                        cancel_batch(&app_ctx, id).await;
                     }
-                }
+                },
+                _ = sleep(BATCH_PAUSED_CHECK_FREQ) => {}
+            }
+            continue;
+        };
+        publish_batches(&app_ctx).await;
+        let _ = app_ctx.db_client.store_batch(&batch_info).await;
+
+        let count = batch_info.total_actions();
+        logging::log!(
+            "[Batches] Starting {} actions batch {} for {count} nodes ...",
+            batch_info.batch_type,
+            batch_info.id
+        );
+        // a resumed batch continues from the first action not performed yet
+        let mut i = batch_info.items.len();
+        while i < count {
+            // the interval and pause can be changed by the user while the batch runs
+            let Some((interval_secs, is_paused)) = batch_pace(&app_ctx, batch_info.id).await else {
+                break;
+            };
+            if is_paused {
+                logging::log!("[Batches] Batch {} paused", batch_info.id);
+                break;
             }
-            BatchType::Start(ref nodes)
-            | BatchType::Stop(ref nodes)
-            | BatchType::Upgrade(ref nodes)
-            | BatchType::Recycle(ref nodes)
-            | BatchType::Remove(ref nodes) => {
-                let count = nodes.len();
-                logging::log!("[Batches] Starting actions batch for {count} nodes ...");
-                let mut i = 0;
-                loop {
-                    select! {
-                        batch_id = cancel_rx.recv() => {
-                            if matches!(batch_id, Ok(id) if id == batch_info.id) {
-                                unlock_batched_nodes(&app_ctx, &batch_info.batch_type).await;
-                                break;
-                            }
-                        },
-                        _ = sleep(Duration::from_secs(batch_info.interval_secs)) => {
-                            let node_id = nodes[i].clone();
-                            app_ctx.node_status_locked.remove(&node_id).await;
-                            app_ctx.db_client.unlock_node_status(&node_id).await;
-                            let res = match batch_info.batch_type {
-                                BatchType::Start(_) => node_manager.start_node_instance(node_id).await,
-                                BatchType::Stop(_) => node_manager.stop_node_instance(node_id).await,
-                                BatchType::Upgrade(_) => node_manager.upgrade_node_instance(&node_id).await,
-                                BatchType::Recycle(_) => node_manager.recycle_node_instance(node_id).await,
-                                BatchType::Remove(_) => node_manager.delete_node_instance(node_id).await,
-                                BatchType::Create {..} => Ok(())
-                            };
-                            update_batch_status(&res, &app_ctx, &batch_info, i, count).await;
-                            i += 1;
-                            if i == count {
-                                break;
-                            }
+
+            select! {
+                batch_id = cancel_rx.recv() => {
+                    match batch_id {
+                        Ok(id) if id == batch_info.id => {
+                            cancel_batch(&app_ctx, id).await;
+                            break;
                         }
+                        // a paused batch, or one queued behind this one, was cancelled
+                        Ok(id) => cancel_batch(&app_ctx, id).await,
+                        Err(_) => {}
+                    }
+                },
+                _ = sleep(Duration::from_secs(interval_secs)) => {
+                    // it may have been paused, or removed, while waiting
+                    if !matches!(batch_pace(&app_ctx, batch_info.id).await, Some((_, false))) {
+                        continue;
                     }
+                    let (node_id, res) =
+                        run_batch_action(&app_ctx, &node_manager, &batch_info.batch_type, i).await;
+                    update_batch_status(&app_ctx, batch_info.id, node_id, res).await;
+                    i += 1;
                 }
             }
         }
 
+        app_ctx.node_action_batches.write().await.1.retain(|batch| {
+            // paused batches are picked again once resumed, failed ones are kept to be retried
+            batch.id != batch_info.id || !batch.status.is_finished() || batch.status.is_failed()
+        });
+        publish_batches(&app_ctx).await;
+    }
+}
+
+// Current interval of a batch and whether it's paused, if it's still in the list
+async fn batch_pace(app_ctx: &AppContext, batch_id: u16) -> Option<(u64, bool)> {
+    app_ctx
+        .node_action_batches
+        .read()
+        .await
+        .1
+        .iter()
+        .find(|b| b.id == batch_id)
+        .map(|b| (b.interval_secs, b.status == BatchStatus::Paused))
+}
+
+// Perform the action of the batch with the given index, returning the node actioned
+// along with the result. For creation batches the node is known only upon success.
+async fn run_batch_action(
+    app_ctx: &AppContext,
+    node_manager: &NodeManager,
+    batch_type: &BatchType,
+    index: usize,
+) -> (Option<NodeId>, Result<(), NodeManagerError>) {
+    let nodes = match batch_type {
+        BatchType::Create {
+            node_opts,
+            count,
+            rewards_addrs,
+        } => {
+            let mut node_opts_clone = node_opts.clone();
+            node_opts_clone.port += index as u16;
+            node_opts_clone.metrics_port += index as u16;
+            if let Some(addr) = distribute_rewards_addrs(rewards_addrs, *count).get(index) {
+                node_opts_clone.rewards_addr = addr.clone();
+            }
+            return match node_manager.create_node_instance(node_opts_clone).await {
+                Ok(info) => (Some(info.node_id), Ok(())),
+                Err(err) => (None, Err(err)),
+            };
+        }
+        BatchType::Start(nodes)
+        | BatchType::Stop(nodes)
+        | BatchType::Upgrade(nodes)
+        | BatchType::Recycle(nodes)
+        | BatchType::Remove(nodes)
+        | BatchType::Update {
+            node_ids: nodes, ..
+        } => nodes,
+    };
+
+    let node_id = nodes[index].clone();
+    app_ctx.node_status_locked.remove(&node_id).await;
+    app_ctx.db_client.unlock_node_status(&node_id).await;
+    let res = match batch_type {
+        BatchType::Start(_) => node_manager.start_node_instance(node_id.clone()).await,
+        BatchType::Stop(_) => node_manager.stop_node_instance(node_id.clone()).await,
+        BatchType::Upgrade(_) => node_manager.upgrade_node_instance(&node_id).await,
+        BatchType::Recycle(_) => node_manager.recycle_node_instance(node_id.clone()).await,
+        BatchType::Remove(_) => node_manager.delete_node_instance(node_id.clone()).await,
+        BatchType::Update { changes, .. } => {
+            // the node may get a new id when its configuration is updated
+            return match node_manager
+                .update_node_instance(node_id.clone(), changes.clone())
+                .await
+            {
+                Ok(info) => (Some(info.node_id), Ok(())),
+                Err(err) => (Some(node_id), Err(err)),
+            };
+        }
+        BatchType::Create { .. } => Ok(()),
+    };
+    (Some(node_id), res)
+}
+
+// Lock the nodes which are part of a batch, preventing also
+// the backend from updating their status till they are actioned.
+async fn lock_batched_nodes(app_ctx: &AppContext, node_ids: &[NodeId], interval_secs: u64) {
+    let duration = Duration::from_secs((interval_secs + 2) * node_ids.len() as u64);
+    for node_id in node_ids.iter() {
+        app_ctx.db_client.set_node_status_to_locked(node_id).await;
         app_ctx
-            .node_action_batches
-            .write()
-            .await
-            .1
-            .retain(|batch| batch.id != batch_info.id || batch.status.is_failed());
+            .node_status_locked
+            .lock(node_id.clone(), duration)
+            .await;
     }
 }
 
-async fn unlock_batched_nodes(app_ctx: &AppContext, batch_type: &BatchType) {
-    for node_id in batch_type.ids().iter() {
+async fn unlock_batched_nodes(app_ctx: &AppContext, node_ids: &[NodeId]) {
+    for node_id in node_ids.iter() {
         app_ctx.node_status_locked.remove(node_id).await;
         app_ctx.db_client.unlock_node_status(node_id).await;
     }
 }
 
+// Cancel a batch being run, or paused, removing it from the list and recording it with its latest progress
+async fn cancel_batch(app_ctx: &AppContext, batch_id: u16) {
+    let batch_info = {
+        let batches = &mut app_ctx.node_action_batches.write().await.1;
+        batches
+            .iter()
+            .position(|b| b.id == batch_id)
+            .map(|index| batches.remove(index))
+    };
+    if let Some(batch_info) = batch_info {
+        publish_batches(app_ctx).await;
+        record_cancelled_batch(app_ctx, batch_info).await;
+    }
+}
+
+async fn record_cancelled_batch(app_ctx: &AppContext, mut batch_info: NodesActionsBatch) {
+    logging::log!("[Batches] Batch {} cancelled", batch_info.id);
+    unlock_batched_nodes(app_ctx, &batch_info.batch_type.ids()).await;
+    batch_info.status = BatchStatus::Cancelled;
+    batch_info.finished_at = Some(chrono::Utc::now().timestamp());
+    record_batch_progress(app_ctx, &batch_info).await;
+}
+
+// Store the batch progress, pruning the history if the batch has finished
+async fn record_batch_progress(app_ctx: &AppContext, batch_info: &NodesActionsBatch) {
+    let _ = app_ctx.db_client.store_batch(batch_info).await;
+    if batch_info.status.is_finished() {
+        app_ctx
+            .db_client
+            .prune_batches_history(BATCHES_HISTORY_MAX_LEN)
+            .await;
+    }
+}
+
 // Helper to update the batch status after each node action completion
-async fn update_batch_status<R, E: std::fmt::Display>(
-    res: &Result<R, E>,
+async fn update_batch_status<E: std::fmt::Display>(
     app_ctx: &AppContext,
-    batch_info: &NodesActionsBatch,
-    action_index: usize,
-    total_actions: usize,
+    batch_id: u16,
+    node_id: Option<NodeId>,
+    res: Result<(), E>,
 ) {
-    let batch_id = batch_info.id;
-    if let Some(ref mut b) = app_ctx
-        .node_action_batches
-        .write()
-        .await
-        .1
-        .iter_mut()
-        .find(|batch| batch.id == batch_id)
-    {
-        match res {
+    let batch_info = {
+        let batches = &mut app_ctx.node_action_batches.write().await.1;
+        let Some(b) = batches.iter_mut().find(|batch| batch.id == batch_id) else {
+            return;
+        };
+
+        let error = match res {
             Err(err) => {
                 logging::error!(
                     "[ERROR][Batches] Node action failed on node instance as part of batch {batch_id}: {err}"
                 );
-                if let BatchStatus::InProgressWithFailures(count, _) = &b.status {
-                    b.status = BatchStatus::InProgressWithFailures(count + 1, err.to_string());
-                } else {
-                    b.status = BatchStatus::InProgressWithFailures(1, err.to_string());
-                }
+                Some(err.to_string())
             }
-            Ok(_) => b.complete += 1,
+            Ok(()) => {
+                b.complete += 1;
+                None
+            }
+        };
+        let now = chrono::Utc::now().timestamp();
+        b.items.push(BatchItemOutcome {
+            node_id,
+            error: error.clone(),
+            timestamp: now,
+        });
+        // a paused batch stays paused, the failure shows up once it's resumed
+        if error.is_some() && b.status != BatchStatus::Paused {
+            b.status = b.running_status();
         }
 
-        if action_index + 1 == total_actions
-            && let BatchStatus::InProgressWithFailures(_, err) = &b.status
-        {
-            unlock_batched_nodes(app_ctx, &batch_info.batch_type).await;
-            b.status = BatchStatus::Failed(err.clone());
+        if b.items.len() >= b.total_actions() {
+            b.finished_at = Some(now);
+            b.status = match b.running_status() {
+                BatchStatus::InProgressWithFailures(_, err) => BatchStatus::Failed(err),
+                _ => BatchStatus::Completed,
+            };
+        }
+        b.clone()
+    };
+    publish_batches(app_ctx).await;
+
+    if let BatchStatus::Failed(err) = &batch_info.status {
+        unlock_batched_nodes(app_ctx, &batch_info.batch_type.ids()).await;
+        let _ = app_ctx.notifications_tx.send(NotificationEvent::new(
+            NotificationEventKind::BatchFailed,
+            &format!("Batch {batch_id} failed"),
+            &format!(
+                "{} batch {batch_id} finished with {}/{} failed actions, last error: {err}",
+                batch_info.batch_type,
+                batch_info.failures(),
+                batch_info.total_actions()
+            ),
+        ));
+    }
+
+    record_batch_progress(app_ctx, &batch_info).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{db_client::test_db_client, types::NodeInstanceInfo};
+
+    #[cfg(not(feature = "native"))]
+    async fn test_node_manager(app_ctx: &AppContext) -> NodeManager {
+        NodeManager::new(app_ctx.clone()).await.unwrap()
+    }
+
+    #[cfg(feature = "native")]
+    async fn test_node_manager(app_ctx: &AppContext) -> NodeManager {
+        let data_dir = std::env::temp_dir().join(format!(
+            "formicaio-test-{}",
+            hex::encode(rand::random::<[u8; 8]>())
+        ));
+        NodeManager::new(app_ctx.clone(), Some(data_dir), true, None)
+            .await
+            .unwrap()
+    }
+
+    async fn stored_nodes(app_ctx: &AppContext, count: usize) -> Vec<NodeId> {
+        let mut ids = Vec::new();
+        for _ in 0..count {
+            let node_info = NodeInstanceInfo::new(NodeId::random());
+            app_ctx.db_client.insert_node_metadata(&node_info).await;
+            ids.push(node_info.node_id);
+        }
+        ids
+    }
+
+    async fn locked_nodes(app_ctx: &AppContext, ids: &[NodeId]) -> Vec<bool> {
+        let mut locked = Vec::new();
+        for node_id in ids {
+            let is_batched = app_ctx.db_client.check_node_is_not_batched(node_id).await;
+            locked.push(is_batched.is_err());
+        }
+        locked
+    }
+
+    // Wait till the batches runner picks the batch, the actions themselves are
+    // not performed within the tests as long as the batch's interval is long enough.
+    async fn wait_till_running(app_ctx: &AppContext, batch_id: u16) -> NodesActionsBatch {
+        loop {
+            let batch_info = find_node_action_batch(app_ctx, batch_id).await.unwrap();
+            if batch_info.status.is_running() {
+                return batch_info;
+            }
+            sleep(Duration::from_millis(10)).await;
         }
     }
+
+    #[tokio::test]
+    async fn test_retry_only_failed_actions() {
+        let app_ctx = AppContext::new(test_db_client().await).await;
+        let node_manager = test_node_manager(&app_ctx).await;
+        let ids = stored_nodes(&app_ctx, 3).await;
+
+        let outcome = |node_id: &NodeId, error: Option<&str>| BatchItemOutcome {
+            node_id: Some(node_id.clone()),
+            error: error.map(str::to_string),
+            timestamp: 1,
+        };
+        let mut failed = NodesActionsBatch::new(1, BatchType::Stop(ids.clone()), 3_600);
+        failed.items = vec![
+            outcome(&ids[0], None),
+            outcome(&ids[1], Some("failed")),
+            outcome(&ids[2], None),
+        ];
+        failed.status = BatchStatus::Failed("failed".to_string());
+        let mut completed = NodesActionsBatch::new(2, BatchType::Start(ids.clone()), 3_600);
+        completed.items = ids.iter().map(|id| outcome(id, None)).collect();
+        completed.status = BatchStatus::Completed;
+        app_ctx
+            .node_action_batches
+            .write()
+            .await
+            .1
+            .extend([failed, completed]);
+
+        assert!(matches!(
+            retry_node_action_batch(&app_ctx, &node_manager, 2).await,
+            Err(ActionsBatchError::NothingToRetry(2))
+        ));
+
+        let retry_id = retry_node_action_batch(&app_ctx, &node_manager, 1)
+            .await
+            .unwrap();
+        let retry = wait_till_running(&app_ctx, retry_id).await;
+        assert_eq!(retry.batch_type, BatchType::Stop(vec![ids[1].clone()]));
+        assert_eq!(retry.interval_secs, 3_600);
+        assert!(retry.items.is_empty());
+        // the retried batch is replaced by the new one, only the failed node is locked by it
+        let batch_ids = app_ctx
+            .node_action_batches
+            .read()
+            .await
+            .1
+            .iter()
+            .map(|b| b.id)
+            .collect::<Vec<_>>();
+        assert_eq!(batch_ids, vec![2, retry_id]);
+        assert_eq!(locked_nodes(&app_ctx, &ids).await, vec![false, true, false]);
+
+        // a batch not finished yet cannot be retried
+        assert!(matches!(
+            retry_node_action_batch(&app_ctx, &node_manager, retry_id).await,
+            Err(ActionsBatchError::InvalidState(..))
+        ));
+        cancel_node_action_batch(&app_ctx, retry_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_interval_and_pause_of_running_batch() {
+        let app_ctx = AppContext::new(test_db_client().await).await;
+        let node_manager = test_node_manager(&app_ctx).await;
+        let ids = stored_nodes(&app_ctx, 2).await;
+        let batch_id =
+            prepare_node_action_batch(BatchType::Stop(ids.clone()), 3_600, &app_ctx, &node_manager)
+                .await
+                .unwrap();
+        wait_till_running(&app_ctx, batch_id).await;
+        assert_eq!(locked_nodes(&app_ctx, &ids).await, vec![true, true]);
+
+        set_node_action_batch_interval(&app_ctx, batch_id, 7_200)
+            .await
+            .unwrap();
+        let batch_info = find_node_action_batch(&app_ctx, batch_id).await.unwrap();
+        assert_eq!(batch_info.interval_secs, 7_200);
+        assert_eq!(locked_nodes(&app_ctx, &ids).await, vec![true, true]);
+
+        // the nodes are released while the batch is paused, even if its interval is changed
+        pause_node_action_batch(&app_ctx, batch_id).await.unwrap();
+        assert_eq!(locked_nodes(&app_ctx, &ids).await, vec![false, false]);
+        set_node_action_batch_interval(&app_ctx, batch_id, 3_600)
+            .await
+            .unwrap();
+        let batch_info = find_node_action_batch(&app_ctx, batch_id).await.unwrap();
+        assert_eq!(batch_info.status, BatchStatus::Paused);
+        assert_eq!(batch_info.interval_secs, 3_600);
+        assert_eq!(locked_nodes(&app_ctx, &ids).await, vec![false, false]);
+        assert!(matches!(
+            pause_node_action_batch(&app_ctx, batch_id).await,
+            Err(ActionsBatchError::InvalidState(..))
+        ));
+
+        resume_node_action_batch(&app_ctx, batch_id).await.unwrap();
+        assert!(
+            find_node_action_batch(&app_ctx, batch_id)
+                .await
+                .unwrap()
+                .status
+                .is_running()
+        );
+        assert_eq!(locked_nodes(&app_ctx, &ids).await, vec![true, true]);
+
+        cancel_node_action_batch(&app_ctx, batch_id).await.unwrap();
+    }
 }
@@ -1,28 +1,23 @@
 use super::arbitrum_client::PaymentRecord;
-use crate::types::EarningsStats;
+use crate::types::{
+    EarningsBucket, EarningsBucketStats, EarningsExportRow, EarningsHistory, EarningsStats,
+};
 
 use alloy_primitives::U256;
+use chrono::NaiveDate;
+use std::collections::BTreeMap;
 
-pub fn calc_earnings_stats(now: i64, payments: &[PaymentRecord]) -> EarningsStats {
-    let earnings_stats = EarningsStats::default();
-    let mut periods = vec![
-        (
-            period_windows(now, earnings_stats.period_1.length_hours.into()),
-            earnings_stats.period_1,
-        ),
-        (
-            period_windows(now, earnings_stats.period_2.length_hours.into()),
-            earnings_stats.period_2,
-        ),
-        (
-            period_windows(now, earnings_stats.period_3.length_hours.into()),
-            earnings_stats.period_3,
-        ),
-        (
-            period_windows(now, earnings_stats.period_4.length_hours.into()),
-            earnings_stats.period_4,
-        ),
-    ];
+pub fn calc_earnings_stats(
+    now: i64,
+    periods_hours: &[u32],
+    payments: &[PaymentRecord],
+) -> EarningsStats {
+    let mut earnings_stats = EarningsStats::new(periods_hours);
+    let mut periods = earnings_stats
+        .periods
+        .iter_mut()
+        .map(|p| (period_windows(now, p.length_hours.into()), p))
+        .collect::<Vec<_>>();
 
     for (((start, end), (prev_start, prev_end)), period_stats) in &mut periods {
         let amts = payments_in_window(payments, *start, *end);
@@ -55,12 +50,64 @@ pub fn calc_earnings_stats(now: i64, payments: &[PaymentRecord]) -> EarningsStat
         period_stats.change_amount = change_amount;
     }
 
-    EarningsStats {
-        period_1: periods[0].1.clone(),
-        period_2: periods[1].1.clone(),
-        period_3: periods[2].1.clone(),
-        period_4: periods[3].1.clone(),
+    earnings_stats
+}
+
+/// Group the payments into buckets of time, from the oldest bucket charted up to the one
+/// of the given day, both for all addresses together (listed first) and for each of them.
+pub fn calc_earnings_history(
+    today: NaiveDate,
+    bucket: EarningsBucket,
+    payments: &[EarningsExportRow],
+) -> Vec<EarningsHistory> {
+    let first_start = bucket.first_bucket_start(today);
+    let mut starts = vec![first_start];
+    while let Some(next) = starts
+        .last()
+        .map(|s| bucket.next_bucket_start(*s))
+        .filter(|next| *next <= today)
+    {
+        starts.push(next);
+    }
+
+    let mut per_address = BTreeMap::<String, Vec<&EarningsExportRow>>::new();
+    for payment in payments.iter().filter(|p| p.date() >= first_start) {
+        per_address
+            .entry(payment.address.clone())
+            .or_default()
+            .push(payment);
     }
+
+    let history_of = |address: Option<String>, payments: &[&EarningsExportRow]| {
+        let mut cumulative = U256::ZERO;
+        let buckets = starts
+            .iter()
+            .map(|start| {
+                let in_bucket = payments
+                    .iter()
+                    .filter(|p| bucket.bucket_start(p.date()) == *start)
+                    .collect::<Vec<_>>();
+                let earned = in_bucket.iter().map(|p| p.amount).sum();
+                cumulative += earned;
+                EarningsBucketStats {
+                    start: *start,
+                    earned,
+                    num_payments: in_bucket.len(),
+                    cumulative,
+                }
+            })
+            .collect();
+        EarningsHistory { address, buckets }
+    };
+
+    let all = per_address.values().flatten().copied().collect::<Vec<_>>();
+    let mut history = vec![history_of(None, &all)];
+    history.extend(
+        per_address
+            .iter()
+            .map(|(addr, payments)| history_of(Some(addr.clone()), payments)),
+    );
+    history
 }
 
 fn payments_in_window(payments: &[PaymentRecord], start: i64, end: i64) -> Vec<U256> {
@@ -97,3 +144,116 @@ fn period_windows(now: i64, period_hours: i64) -> ((i64, i64), (i64, i64)) {
     let prev_start = prev_end - period_secs + 1;
     ((start, end), (prev_start, prev_end))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payment(address: &str, day: NaiveDate, amount: u64) -> EarningsExportRow {
+        EarningsExportRow {
+            address: address.to_string(),
+            timestamp: day.and_hms_opt(12, 0, 0).unwrap().and_utc().timestamp(),
+            block_number: 1,
+            amount: U256::from(amount),
+            price: None,
+        }
+    }
+
+    #[test]
+    fn test_calc_earnings_history() {
+        let day = |m, d| NaiveDate::from_ymd_opt(2026, m, d).unwrap();
+        // Wednesday
+        let today = day(3, 18);
+        let payments = vec![
+            // too old to be charted
+            payment("0xA", NaiveDate::from_ymd_opt(2025, 6, 1).unwrap(), 1_000),
+            payment("0xA", day(1, 5), 100),
+            payment("0xA", day(3, 2), 5),
+            payment("0xB", day(3, 4), 7),
+            payment("0xA", day(3, 16), 3),
+            payment("0xA", day(3, 18), 1),
+        ];
+
+        let weekly = calc_earnings_history(today, EarningsBucket::Week, &payments);
+        assert_eq!(weekly.len(), 3);
+        let total = &weekly[0];
+        assert_eq!(total.address, None);
+        assert_eq!(total.buckets.len(), 26);
+        let last = total.buckets.last().unwrap();
+        assert_eq!(last.start, day(3, 16));
+        assert_eq!(last.earned, U256::from(4));
+        assert_eq!(last.num_payments, 2);
+        assert_eq!(last.cumulative, U256::from(116));
+        // week starting on Monday 2nd of March
+        let week = &total.buckets[23];
+        assert_eq!(week.start, day(3, 2));
+        assert_eq!(week.earned, U256::from(12));
+        assert_eq!(weekly[2].address.as_deref(), Some("0xB"));
+        assert_eq!(weekly[2].buckets.last().unwrap().cumulative, U256::from(7));
+
+        let monthly = calc_earnings_history(today, EarningsBucket::Month, &payments);
+        assert_eq!(monthly[0].buckets.len(), 7);
+        assert_eq!(
+            monthly[0].buckets[0].start,
+            NaiveDate::from_ymd_opt(2025, 9, 1).unwrap()
+        );
+        // payments of January are within the charted months
+        assert_eq!(monthly[0].buckets[4].earned, U256::from(100));
+        assert_eq!(monthly[0].buckets[6].earned, U256::from(16));
+
+        let daily = calc_earnings_history(today, EarningsBucket::Day, &[]);
+        assert_eq!(daily.len(), 1);
+        assert_eq!(daily[0].buckets.len(), 30);
+        assert_eq!(daily[0].buckets[29].start, today);
+    }
+
+    #[test]
+    fn test_calc_earnings_stats_custom_periods() {
+        use chrono::{TimeZone, Utc};
+        let now = 1_773_230_400;
+        let payment = |hours_ago: i64, amount: u64| PaymentRecord {
+            timestamp: Utc.timestamp_opt(now - hours_ago * 3600, 0).unwrap(),
+            amount: U256::from(amount),
+            block_number: 1,
+        };
+        let payments = vec![
+            payment(1, 10),
+            payment(5, 20),
+            payment(30, 30),
+            payment(50, 40),
+        ];
+
+        // periods are sorted and duplicates ignored
+        let stats = calc_earnings_stats(now, &[24, 6, 24], &payments);
+        assert_eq!(stats.periods.len(), 2);
+
+        let last_6h = &stats.periods[0];
+        assert_eq!(last_6h.label, "Last 6 Hours");
+        assert_eq!(last_6h.num_payments, 2);
+        assert_eq!(last_6h.total_earned, U256::from(30));
+        assert_eq!(last_6h.median_payment, U256::from(15));
+        assert_eq!(last_6h.total_earned_prev, U256::ZERO);
+
+        let last_day = &stats.periods[1];
+        assert_eq!(last_day.label, "Last 24 Hours");
+        assert_eq!(last_day.total_earned, U256::from(30));
+        assert_eq!(last_day.total_earned_prev, U256::from(30));
+        assert_eq!(last_day.largest_payment, U256::from(20));
+
+        let labels = EarningsStats::new(&[1, 72, 336, 720, 2160])
+            .periods
+            .into_iter()
+            .map(|p| p.label)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            labels,
+            [
+                "Last Hour",
+                "Last 3 Days",
+                "Last 2 Weeks",
+                "Last Month",
+                "Last 3 Months"
+            ]
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use crate::{
+    app::AppContext,
+    node_mgr::{NodeManager, NodeManagerError},
+    types::{NodeFilter, NodesInstancesInfo, ServerEvent},
+};
+
+use axum::{
+    extract::State,
+    response::sse::{Event, KeepAlive, Sse},
+};
+use futures_util::{Stream, StreamExt};
+use leptos::logging;
+use tokio::sync::broadcast;
+
+// Obtain the list of existing nodes instances with their info, the stats and batches.
+pub async fn nodes_instances_info(
+    app_ctx: &AppContext,
+    node_manager: &NodeManager,
+    filter: Option<NodeFilter>,
+) -> Result<NodesInstancesInfo, NodeManagerError> {
+    let latest_bin_version = app_ctx
+        .latest_bin_version
+        .read()
+        .await
+        .clone()
+        .map(|v| v.to_string());
+    let stats = app_ctx.stats.read().await.clone();
+
+    let nodes = node_manager
+        .filtered_nodes_list(filter, app_ctx.nodes_metrics.clone())
+        .await?;
+
+    let scheduled_batches = app_ctx.node_action_batches.read().await.1.clone();
+
+    Ok(NodesInstancesInfo {
+        latest_bin_version,
+        nodes,
+        stats,
+        scheduled_batches,
+    })
+}
+
+// Stream of events for a subscriber, starting with a snapshot of the current state.
+// A new snapshot is sent whenever the subscriber lagged behind and missed some events.
+fn subscriber_events(
+    app_ctx: AppContext,
+    node_manager: NodeManager,
+) -> impl Stream<Item = ServerEvent> {
+    let mut events_rx = app_ctx.events_tx.subscribe();
+    async_stream::stream! {
+        let mut send_snapshot = true;
+        loop {
+            if send_snapshot {
+                send_snapshot = false;
+                match nodes_instances_info(&app_ctx, &node_manager, None).await {
+                    Ok(info) => yield ServerEvent::Snapshot(Box::new(info)),
+                    Err(err) => {
+                        logging::warn!("[WARN][API] Failed to get nodes list for events stream: {err}");
+                        break;
+                    }
+                }
+            }
+
+            match events_rx.recv().await {
+                Ok(event) => yield event,
+                Err(broadcast::error::RecvError::Lagged(_)) => send_snapshot = true,
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    }
+}
+
+// Handler which streams, as Server-Sent Events, the live events published onto the events bus.
+// Each event is sent as a JSON object with its type and data.
+pub async fn events_stream(
+    State(app_ctx): State<AppContext>,
+    State(node_manager): State<NodeManager>,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+    logging::log!("[API] New subscriber to the live events stream");
+    let events =
+        subscriber_events(app_ctx, node_manager).map(|event| Event::default().json_data(event));
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{db_client::test_db_client, types::Stats};
+
+    #[tokio::test]
+    async fn test_stats_and_bin_version_changes_are_published() {
+        let app_ctx = AppContext::new(test_db_client().await).await;
+        let mut events_rx = app_ctx.events_tx.subscribe();
+
+        app_ctx.update_stats(|stats| stats.active_nodes = 2).await;
+        // updates which don't change anything are not published
+        app_ctx.update_stats(|stats| stats.active_nodes = 2).await;
+        let version = semver::Version::new(0, 4, 1);
+        app_ctx.set_latest_bin_version(Some(version.clone())).await;
+        app_ctx.set_latest_bin_version(Some(version)).await;
+
+        let stats = Stats {
+            active_nodes: 2,
+            ..Default::default()
+        };
+        assert_eq!(
+            events_rx.try_recv(),
+            Ok(ServerEvent::StatsUpdated(Box::new(stats)))
+        );
+        assert_eq!(
+            events_rx.try_recv(),
+            Ok(ServerEvent::NewBinVersion("0.4.1".to_string()))
+        );
+        assert_eq!(
+            events_rx.try_recv(),
+            Err(broadcast::error::TryRecvError::Empty)
+        );
+    }
+}
@@ -0,0 +1,170 @@
+use crate::{
+    app::AppContext,
+    db_client::DbClient,
+    types::{
+        AppSettings, HostNetworkStats, HostSnapshot, HostStats, Metrics, MetricsResolution,
+        MonthlyTraffic, StatsHistoryRange,
+    },
+};
+
+use chrono::Utc;
+use std::collections::HashSet;
+use sysinfo::{
+    CpuRefreshKind, DiskRefreshKind, Disks, MemoryRefreshKind, Networks, RefreshKind, System,
+};
+use tokio::time::Instant;
+
+// Name of the loopback network interface, which is not reported.
+const LOOPBACK_INTERFACE: &str = "lo";
+// Format of the months the host traffic is accounted in.
+const TRAFFIC_MONTH_FORMAT: &str = "%Y-%m";
+const BYTES_PER_GB: u64 = 1_000_000_000;
+
+/// Sampler of the host resources usage. Network and disk I/O counters are
+/// reported by the OS as bytes since the previous refresh, so the same instance
+/// is kept across samples to calculate their rates.
+pub struct HostMonitor {
+    system: System,
+    networks: Networks,
+    disks: Disks,
+    last_refresh: Instant,
+}
+
+impl HostMonitor {
+    pub fn new() -> Self {
+        let system = System::new_with_specifics(
+            RefreshKind::nothing()
+                .with_cpu(CpuRefreshKind::nothing().with_cpu_usage())
+                .with_memory(MemoryRefreshKind::everything()),
+        );
+        Self {
+            system,
+            networks: Networks::new_with_refreshed_list(),
+            disks: Disks::new_with_refreshed_list_specifics(
+                DiskRefreshKind::nothing().with_io_usage(),
+            ),
+            last_refresh: Instant::now(),
+        }
+    }
+
+    /// Refresh the host information and return the resources usage since the previous sample.
+    pub fn sample(&mut self) -> HostStats {
+        self.system.refresh_cpu_usage();
+        self.system.refresh_memory();
+        self.networks.refresh(true);
+        self.disks
+            .refresh_specifics(true, DiskRefreshKind::nothing().with_io_usage());
+        let elapsed_secs = self.last_refresh.elapsed().as_secs_f64().max(1.0);
+        self.last_refresh = Instant::now();
+
+        let mut networks = self
+            .networks
+            .list()
+            .iter()
+            // interfaces which never had any traffic, e.g. down or unused virtual ones, are skipped
+            .filter(|(name, data)| {
+                name.as_str() != LOOPBACK_INTERFACE
+                    && data.total_received() + data.total_transmitted() > 0
+            })
+            .map(|(name, data)| HostNetworkStats {
+                interface: name.clone(),
+                received_rate: data.received() as f64 / elapsed_secs,
+                transmitted_rate: data.transmitted() as f64 / elapsed_secs,
+            })
+            .collect::<Vec<_>>();
+        networks.sort_by(|a, b| a.interface.cmp(&b.interface));
+
+        // the same device can be mounted more than once, its I/O is only accounted once
+        let mut devices = HashSet::new();
+        let (read_bytes, written_bytes) = self
+            .disks
+            .list()
+            .iter()
+            .filter(|disk| devices.insert(disk.name().to_os_string()))
+            .fold((0u64, 0u64), |(read, written), disk| {
+                let usage = disk.usage();
+                (read + usage.read_bytes, written + usage.written_bytes)
+            });
+
+        let load_avg = System::load_average();
+        HostStats {
+            timestamp: Utc::now().timestamp_millis(),
+            cpus: self.system.cpus().len(),
+            cpu_usage: self.system.global_cpu_usage() as f64,
+            mem_used: self.system.used_memory(),
+            mem_total: self.system.total_memory(),
+            swap_used: self.system.used_swap(),
+            swap_total: self.system.total_swap(),
+            load_avg: [load_avg.one, load_avg.five, load_avg.fifteen],
+            disk_read_rate: read_bytes as f64 / elapsed_secs,
+            disk_write_rate: written_bytes as f64 / elapsed_secs,
+            networks,
+        }
+    }
+
+    /// Bytes received and transmitted by all network interfaces, except the
+    /// loopback one, between the last two samples.
+    pub fn traffic(&self) -> (u64, u64) {
+        self.networks
+            .list()
+            .iter()
+            .filter(|(name, _)| name.as_str() != LOOPBACK_INTERFACE)
+            .fold((0, 0), |(rx, tx), (_, data)| {
+                (rx + data.received(), tx + data.transmitted())
+            })
+    }
+}
+
+/// Sample the host resources usage, making it available in the stats and storing
+/// it in the DB as part of the metrics history. The traffic of the network interfaces
+/// is also added to the one accounted for the current month.
+pub async fn update_host_stats(
+    app_ctx: &AppContext,
+    settings: &AppSettings,
+    monitor: &mut HostMonitor,
+) {
+    let host_stats = monitor.sample();
+    app_ctx
+        .db_client
+        .store_host_metrics(&host_stats.metrics())
+        .await;
+
+    let month = Utc::now().format(TRAFFIC_MONTH_FORMAT).to_string();
+    let (rx, tx) = monitor.traffic();
+    if rx + tx > 0 {
+        app_ctx.db_client.add_network_traffic(&month, rx, tx).await;
+    }
+    let (received, transmitted) = app_ctx.db_client.get_network_traffic(&month).await;
+    let monthly_traffic = MonthlyTraffic {
+        month,
+        received,
+        transmitted,
+        cap: (settings.monthly_traffic_cap_gb > 0)
+            .then(|| settings.monthly_traffic_cap_gb * BYTES_PER_GB),
+    };
+
+    app_ctx
+        .update_stats(|stats| {
+            stats.host = Some(host_stats);
+            stats.monthly_traffic = Some(monthly_traffic);
+        })
+        .await;
+}
+
+/// Retrieve the history of the host resources usage within the given time range,
+/// averaged over the buckets of the range, oldest first.
+pub async fn host_metrics_history(
+    db_client: &DbClient,
+    settings: &AppSettings,
+    range: StatsHistoryRange,
+) -> Vec<HostSnapshot> {
+    let now = Utc::now().timestamp_millis();
+    let since = now - range.span_millis();
+    let mut metrics = Metrics::new();
+    for (resolution, from, to) in MetricsResolution::windows_for_range(Some(since), now, settings) {
+        for (key, points) in db_client.get_host_metrics(resolution, from, to).await {
+            metrics.entry(key).or_default().extend(points);
+        }
+    }
+    HostSnapshot::from_metrics(&metrics, range.bucket_millis())
+}
@@ -1,12 +1,14 @@
-use super::mcp_tools::*;
-use crate::{app_context::AppContext, node_mgr::NodeManager};
+use super::{auth::hash_session_token, mcp_tools::*};
+use crate::{app_context::AppContext, node_mgr::NodeManager, types::UserRole};
 
 use async_trait::async_trait;
 use leptos::logging;
 use rust_mcp_axum::{AxumServerOptions, create_axum_server};
 use rust_mcp_sdk::{
     McpServer, ToMcpServerHandler,
+    auth::{AuthInfo, AuthProvider, AuthenticationError, OauthEndpoint},
     event_store::InMemoryEventStore,
+    mcp_http::{GenericBody, GenericBodyExt, McpAppState, McpHttpError, http},
     mcp_icon,
     mcp_server::ServerHandler,
     schema::{
@@ -15,7 +17,76 @@ use rust_mcp_sdk::{
         ServerCapabilities, ServerCapabilitiesTools, schema_utils::CallToolError,
     },
 };
-use std::{net::SocketAddr, sync::Arc, time::Duration};
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::Arc,
+    time::{Duration, UNIX_EPOCH},
+};
+
+// Key of the auth info's extra data where the role of the user is kept.
+const AUTH_INFO_ROLE_KEY: &str = "role";
+
+// Authenticates MCP clients with the session tokens of the users, sent as bearer
+// tokens, the same way the CLI does, keeping the user's role in the auth info.
+struct SessionAuthProvider {
+    app_ctx: AppContext,
+}
+
+#[async_trait]
+impl AuthProvider for SessionAuthProvider {
+    async fn verify_token(&self, access_token: String) -> Result<AuthInfo, AuthenticationError> {
+        let token_hash = hash_session_token(&access_token);
+        let Some((user, expires)) = self.app_ctx.db_client.get_session(&token_hash).await else {
+            return Err(AuthenticationError::InvalidToken {
+                description: "Invalid or expired session token",
+            });
+        };
+
+        let extra = serde_json::Map::from_iter([(
+            AUTH_INFO_ROLE_KEY.to_string(),
+            user.role.as_str().into(),
+        )]);
+        Ok(AuthInfo {
+            token_unique_id: token_hash,
+            client_id: None,
+            user_id: Some(user.username),
+            scopes: None,
+            expires_at: Some(UNIX_EPOCH + Duration::from_secs(expires.max(0) as u64)),
+            audience: None,
+            extra: Some(extra),
+        })
+    }
+
+    fn auth_endpoints(&self) -> Option<&HashMap<String, OauthEndpoint>> {
+        None
+    }
+
+    // Only called for the OAuth endpoints, none of which are provided.
+    async fn handle_request(
+        &self,
+        _request: http::Request<&str>,
+        _state: Arc<McpAppState>,
+    ) -> Result<http::Response<GenericBody>, McpHttpError> {
+        Ok(GenericBody::create_404_response())
+    }
+
+    fn protected_resource_metadata_url(&self) -> Option<&str> {
+        None
+    }
+}
+
+// Role of the user the MCP client authenticated as.
+async fn client_role(runtime: &Arc<dyn McpServer>) -> Option<UserRole> {
+    runtime
+        .auth_info_cloned()
+        .await?
+        .extra?
+        .get(AUTH_INFO_ROLE_KEY)?
+        .as_str()?
+        .parse()
+        .ok()
+}
 
 pub struct McpServerHandler {
     app_ctx: AppContext,
@@ -25,24 +96,45 @@ pub struct McpServerHandler {
 #[async_trait]
 impl ServerHandler for McpServerHandler {
     // Handle ListToolsRequest, return list of available tools as ListToolsResult
+    // Only the tools allowed to the role of the authenticated user are listed.
     async fn handle_list_tools_request(
         &self,
         _params: Option<PaginatedRequestParams>,
-        _runtime: Arc<dyn McpServer>,
+        runtime: Arc<dyn McpServer>,
     ) -> std::result::Result<ListToolsResult, RpcError> {
+        let role = client_role(&runtime).await;
         Ok(ListToolsResult {
             meta: None,
             next_cursor: None,
-            tools: FormicaioTools::tools(),
+            tools: FormicaioTools::tools()
+                .into_iter()
+                .filter(|t| role.is_some_and(|role| role.can(tool_permission(&t.name))))
+                .collect(),
         })
     }
 
-    /// Handles incoming CallToolRequest and processes it using the appropriate tool.
+    /// Handles incoming CallToolRequest and processes it using the appropriate tool,
+    /// as long as the role of the authenticated user is allowed to use it.
     async fn handle_call_tool_request(
         &self,
         params: CallToolRequestParams,
-        _runtime: Arc<dyn McpServer>,
+        runtime: Arc<dyn McpServer>,
     ) -> std::result::Result<CallToolResult, CallToolError> {
+        let role = match client_role(&runtime).await {
+            Some(role) if role.can(tool_permission(&params.name)) => role,
+            role => {
+                logging::warn!(
+                    "[MCP] Tool '{}' rejected, not allowed to the '{}' role",
+                    params.name,
+                    role.map_or("unknown", |role| role.as_str())
+                );
+                return Err(CallToolError::from_message(format!(
+                    "Permission denied: not allowed to use the '{}' tool",
+                    params.name
+                )));
+            }
+        };
+
         // Attempt to convert request parameters into FormicaioTools enum
         let tool_params: FormicaioTools =
             FormicaioTools::try_from(params).map_err(CallToolError::new)?;
@@ -50,10 +142,17 @@ impl ServerHandler for McpServerHandler {
         // Match the tool variant and execute its corresponding logic
         match tool_params {
             FormicaioTools::FetchStats(tool) => tool.call_tool(&self.app_ctx).await,
+            FormicaioTools::FetchHostStats(tool) => tool.call_tool(&self.app_ctx).await,
             FormicaioTools::NodeInstances(tool) => {
                 tool.call_tool(&self.app_ctx, &self.node_manager).await
             }
-            FormicaioTools::CreateNodeInstance(tool) => tool.call_tool(&self.node_manager).await,
+            FormicaioTools::FetchProfitability(tool) => {
+                tool.call_tool(&self.app_ctx, &self.node_manager).await
+            }
+            FormicaioTools::CreateNodeInstance(tool) => {
+                tool.call_tool(&self.app_ctx, &self.node_manager, role)
+                    .await
+            }
             FormicaioTools::StartNodeInstance(tool) => tool.call_tool(&self.node_manager).await,
             FormicaioTools::StopNodeInstance(tool) => tool.call_tool(&self.node_manager).await,
             FormicaioTools::DeleteNodeInstance(tool) => tool.call_tool(&self.node_manager).await,
@@ -107,6 +206,9 @@ pub fn start_mcp_server(addr: SocketAddr, app_ctx: AppContext, node_manager: Nod
             sse_support: false,
             ping_interval: Duration::from_secs(5),
             event_store: Some(Arc::new(InMemoryEventStore::default())), // enable resumability
+            auth: Some(Arc::new(SessionAuthProvider {
+                app_ctx: app_ctx.clone(),
+            })),
             ..Default::default()
         },
     );
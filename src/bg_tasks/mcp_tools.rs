@@ -1,8 +1,12 @@
 use crate::{
     app_context::AppContext,
+    bg_tasks::{host_metrics_history, profitability, rewards_addrs_permission},
     node_mgr::NodeManager,
     server_api::parse_and_validate_addr,
-    types::{NodeId, NodeOpts},
+    types::{
+        DEFAULT_PROFITABILITY_PERIOD_HOURS, NodeId, NodeOpts, Permission, StatsHistoryRange,
+        UserRole,
+    },
 };
 
 use rust_mcp_sdk::{
@@ -22,6 +26,23 @@ fn serialise_to_tool_response<T: serde::Serialize>(
     }
 }
 
+/// Permission required to use the tool with the given name.
+pub fn tool_permission(name: &str) -> Permission {
+    match name {
+        "fetch_stats" | "fetch_host_stats" | "nodes_instances" | "fetch_profitability" => {
+            Permission::View
+        }
+        "create_node_instance"
+        | "start_node_instance"
+        | "stop_node_instance"
+        | "upgrade_node_instance"
+        | "recycle_node_instance" => Permission::OperateNodes,
+        "delete_node_instance" => Permission::RemoveNodes,
+        // tools not mapped to a permission are only allowed to admins
+        _ => Permission::ManageUsers,
+    }
+}
+
 fn parse_node_id(node_id: &str) -> Result<NodeId, CallToolError> {
     NodeId::from_str(node_id).map_err(CallToolError::from_message)
 }
@@ -41,6 +62,51 @@ impl FetchStats {
     }
 }
 
+#[mcp_tool(
+    name = "fetch_host_stats",
+    description = "Return the current resources usage of the host the nodes run on \
+(CPU usage, memory and swap usage, load average, disk I/O and network throughput per \
+interface) and its network traffic during the current month against the monthly cap, \
+optionally with its history, to correlate nodes issues with host saturation."
+)]
+#[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
+pub struct FetchHostStats {
+    /// Include the history over the last "day", "week" or "month", averaged over 5-minute,
+    /// 30-minute and 2-hour buckets respectively
+    pub history: Option<String>,
+}
+impl FetchHostStats {
+    pub async fn call_tool(&self, app_ctx: &AppContext) -> Result<CallToolResult, CallToolError> {
+        let (current, monthly_traffic) = {
+            let stats = app_ctx.stats.read().await;
+            (stats.host.clone(), stats.monthly_traffic.clone())
+        };
+        let range = match self.history.as_deref().map(str::to_lowercase).as_deref() {
+            None => None,
+            Some("day") => Some(StatsHistoryRange::Day),
+            Some("week") => Some(StatsHistoryRange::Week),
+            Some("month") => Some(StatsHistoryRange::Month),
+            Some(other) => {
+                return Err(CallToolError::from_message(format!(
+                    "Invalid history range '{other}', expected 'day', 'week' or 'month'"
+                )));
+            }
+        };
+        let history = match range {
+            Some(range) => {
+                let settings = app_ctx.db_client.get_settings().await;
+                Some(host_metrics_history(&app_ctx.db_client, &settings, range).await)
+            }
+            None => None,
+        };
+        serialise_to_tool_response(&serde_json::json!({
+            "current": current,
+            "monthly_traffic": monthly_traffic,
+            "history": history,
+        }))
+    }
+}
+
 #[mcp_tool(
     name = "nodes_instances",
     description = "Retrieve the list of all node instances and their current state \
@@ -64,6 +130,49 @@ impl NodeInstances {
     }
 }
 
+#[mcp_tool(
+    name = "fetch_profitability",
+    description = "Return the estimated running costs (energy and disk) of each node, and of \
+all of them together, compared with the value of the rewards they earned over a period, \
+with the ROI, cost per record and the token break-even price."
+)]
+#[derive(Debug, ::serde::Deserialize, ::serde::Serialize, JsonSchema)]
+pub struct FetchProfitability {
+    /// Length in hours of the period up to now to calculate the profitability over (default 720)
+    pub period_hours: Option<u32>,
+}
+impl FetchProfitability {
+    pub async fn call_tool(
+        &self,
+        app_ctx: &AppContext,
+        node_manager: &NodeManager,
+    ) -> Result<CallToolResult, CallToolError> {
+        let period_hours = self
+            .period_hours
+            .unwrap_or(DEFAULT_PROFITABILITY_PERIOD_HOURS);
+        if period_hours == 0 {
+            return Err(CallToolError::from_message("Period cannot be 0 hours long"));
+        }
+        let nodes = node_manager
+            .filtered_nodes_list(None, app_ctx.nodes_metrics.clone())
+            .await
+            .map_err(|err| CallToolError::from_message(err.to_string()))?;
+        let settings = app_ctx.db_client.get_settings().await;
+        match profitability(
+            &app_ctx.db_client,
+            &settings,
+            &nodes,
+            period_hours,
+            chrono::Utc::now(),
+        )
+        .await
+        {
+            Ok(res) => serialise_to_tool_response(&res),
+            Err(err) => Err(CallToolError::from_message(err.to_string())),
+        }
+    }
+}
+
 #[mcp_tool(
     name = "create_node_instance",
     description = "Create and optionally start a new node instance. \
@@ -93,12 +202,23 @@ pub struct CreateNodeInstance {
 impl CreateNodeInstance {
     pub async fn call_tool(
         &self,
+        app_ctx: &AppContext,
         node_manager: &NodeManager,
+        role: UserRole,
     ) -> Result<CallToolResult, CallToolError> {
         // validate rewards address before proceeding
         if let Err(err) = parse_and_validate_addr(&self.rewards_addr) {
             return Err(CallToolError::from_message(err.to_string()));
         }
+        if !role
+            .can(rewards_addrs_permission(app_ctx, std::slice::from_ref(&self.rewards_addr)).await)
+        {
+            return Err(CallToolError::from_message(format!(
+                "Permission denied: the '{}' role is not allowed to create nodes paying to a \
+                rewards address which is neither used by other nodes nor in the address book",
+                role.as_str()
+            )));
+        }
 
         let node_opts = NodeOpts {
             ipv4_only: self.ipv4_only,
@@ -234,7 +354,9 @@ tool_box!(
     FormicaioTools,
     [
         FetchStats,
+        FetchHostStats,
         NodeInstances,
+        FetchProfitability,
         CreateNodeInstance,
         StartNodeInstance,
         StopNodeInstance,
@@ -1,3 +1,4 @@
+use super::NodeRewardsDelta;
 use crate::{
     db_client::DbClient,
     types::{NodeId, NodeInstanceInfo, ReachabilityCheckStatus, metrics::*},
@@ -10,6 +11,7 @@ use std::{
     collections::HashMap,
     net::{IpAddr, Ipv4Addr},
     str::FromStr,
+    time::Duration,
 };
 use thiserror::Error;
 
@@ -35,7 +37,12 @@ const NODE_METRICS_TO_COLLECT: [(&str, &str); 11] = [
 ];
 
 // Predefined set of historic metrics to store in DB.
-const NODE_METRICS_TO_STORE_IN_DB: [&str; 2] = [METRIC_KEY_MEM_USED_MB, METRIC_KEY_CPU_USAGE];
+const NODE_METRICS_TO_STORE_IN_DB: [&str; 4] = [
+    METRIC_KEY_MEM_USED_MB,
+    METRIC_KEY_CPU_USAGE,
+    METRIC_KEY_NET_RX_RATE,
+    METRIC_KEY_NET_TX_RATE,
+];
 // Env var to enable the use of a metrics proxy service by providing its IP and port number.
 const METRICS_PROXY_ADDR: &str = "METRICS_PROXY_ADDR";
 
@@ -49,12 +56,13 @@ pub enum MetricsClientError {
 
 // Client to query metrics from nodes
 pub struct NodeMetricsClient {
+    http_client: reqwest::Client,
     endpoint: String,
     metrics_to_collect: HashMap<String, String>,
 }
 
 impl NodeMetricsClient {
-    pub fn new(port: u16) -> Self {
+    pub fn new(http_client: reqwest::Client, port: u16) -> Self {
         let endpoint = match std::env::var(METRICS_PROXY_ADDR) {
             Ok(addr) => format!("http://{addr}/{port}"),
             Err(_) => format!("http://{DEFAULT_NODES_METRICS_HOST}:{port}/metrics"),
@@ -66,6 +74,7 @@ impl NodeMetricsClient {
             .collect();
 
         Self {
+            http_client,
             metrics_to_collect,
             endpoint,
         }
@@ -73,7 +82,13 @@ impl NodeMetricsClient {
 
     // Fetch, filter, and return the predefined type of metrics.
     pub async fn fetch_metrics(&self) -> Result<Vec<NodeMetric>, MetricsClientError> {
-        let response = reqwest::get(&self.endpoint).await?.text().await?;
+        let response = self
+            .http_client
+            .get(&self.endpoint)
+            .send()
+            .await?
+            .text()
+            .await?;
         self.parse_and_filter_metrics(&response).await
     }
 
@@ -121,6 +136,17 @@ impl NodeMetricsClient {
     }
 }
 
+// Health of the nodes metrics polling performed by the backend.
+#[derive(Clone, Debug, Default)]
+pub struct MetricsPollingHealth {
+    // Number of polling cycles completed since the backend was started.
+    pub cycles: u64,
+    // Time it took to complete the last polling cycle.
+    pub last_cycle_duration: Duration,
+    // Number of failed attempts to fetch metrics from nodes since the backend was started.
+    pub fetch_failures: u64,
+}
+
 // Structure to keep track of all nodes metrics. These metrics
 // are collected periodically at the backend in a background task,
 // and consumed by the frontend through a server api.
@@ -128,6 +154,17 @@ impl NodeMetricsClient {
 pub struct NodesMetrics {
     // Cache of the last metrics for each node indexed by their node id.
     data: HashMap<NodeId, HashMap<String, NodeMetric>>,
+    // Historic metrics collected during the current polling cycle, pending to be stored in DB.
+    pending: Vec<(NodeId, NodeMetric)>,
+    // Increases of the rewards reported by nodes during the current polling cycle,
+    // pending to be stored in DB so they can be correlated with the payments received.
+    pending_rewards: Vec<NodeRewardsDelta>,
+    // Total earnings estimated to have been earned by each node.
+    earnings: HashMap<NodeId, U256>,
+    // Last traffic counters (timestamp in millis, bytes received, bytes transmitted) read for each node.
+    net_counters: HashMap<NodeId, (i64, u64, u64)>,
+    // Health of the metrics polling cycles.
+    polling_health: MetricsPollingHealth,
     // DB client to store all metrics collected overtime
     db_client: DbClient,
 }
@@ -136,21 +173,35 @@ impl NodesMetrics {
     pub fn new(db_client: DbClient) -> Self {
         Self {
             data: HashMap::new(),
+            pending: Vec::new(),
+            pending_rewards: Vec::new(),
+            earnings: HashMap::new(),
+            net_counters: HashMap::new(),
+            polling_health: MetricsPollingHealth::default(),
             db_client,
         }
     }
 
-    // Store a data point for the specified node id.
-    pub async fn store(&mut self, node_id: &NodeId, metrics: &[NodeMetric]) {
-        // store into our DB cache those we keep as logs/historic values
-        self.db_client
-            .store_node_metrics(
-                node_id,
-                metrics
-                    .iter()
-                    .filter(|m| NODE_METRICS_TO_STORE_IN_DB.contains(&m.key.as_str())),
-            )
-            .await;
+    // Store a data point for the specified node id. The last rewards seen for the node are
+    // the ones persisted in DB, so increases are tracked across restarts of the backend and
+    // changes of the node id, rather than only since the previous sample cached in memory.
+    pub fn store(&mut self, node_id: &NodeId, metrics: &[NodeMetric], last_rewards: Option<U256>) {
+        // keep those we store in DB as logs/historic values until the polling cycle is finished
+        self.pending.extend(
+            metrics
+                .iter()
+                .filter(|m| NODE_METRICS_TO_STORE_IN_DB.contains(&m.key.as_str()))
+                .map(|m| (node_id.clone(), m.clone())),
+        );
+
+        if let Some((amount, timestamp)) = rewards_increase(last_rewards, metrics) {
+            self.pending_rewards.push(NodeRewardsDelta {
+                id: 0,
+                node_id: node_id.clone(),
+                amount,
+                timestamp,
+            });
+        }
 
         // let's now update our in-memory cache with new metrics values
         let metrics: HashMap<String, NodeMetric> =
@@ -158,6 +209,80 @@ impl NodesMetrics {
         let _ = self.data.insert(node_id.clone(), metrics.clone());
     }
 
+    // Store the traffic counters read for the specified node id, returning its throughput
+    // since the previous read unless the counters were reset, e.g. the node was restarted.
+    pub fn store_network_usage(
+        &mut self,
+        node_id: &NodeId,
+        rx_bytes: u64,
+        tx_bytes: u64,
+    ) -> Option<(f64, f64)> {
+        let timestamp = Utc::now().timestamp_millis();
+        let prev = self
+            .net_counters
+            .insert(node_id.clone(), (timestamp, rx_bytes, tx_bytes));
+        let (prev_ts, prev_rx, prev_tx) = prev?;
+        let elapsed_secs = (timestamp - prev_ts) as f64 / 1_000.0;
+        if elapsed_secs <= 0.0 || rx_bytes < prev_rx || tx_bytes < prev_tx {
+            return None;
+        }
+
+        let rx_rate = (rx_bytes - prev_rx) as f64 / elapsed_secs;
+        let tx_rate = (tx_bytes - prev_tx) as f64 / elapsed_secs;
+        let metrics = [
+            (METRIC_KEY_NET_RX_RATE, rx_rate),
+            (METRIC_KEY_NET_TX_RATE, tx_rate),
+        ]
+        .map(|(key, rate)| NodeMetric {
+            key: key.to_string(),
+            value: rate.to_string(),
+            timestamp,
+        });
+        self.pending
+            .extend(metrics.iter().map(|m| (node_id.clone(), m.clone())));
+        let cached = self.data.entry(node_id.clone()).or_default();
+        for m in metrics {
+            cached.insert(m.key.clone(), m);
+        }
+        Some((rx_rate, tx_rate))
+    }
+
+    // Take all historic metrics collected since last call, so they can be stored in DB.
+    pub fn take_pending(&mut self) -> Vec<(NodeId, NodeMetric)> {
+        std::mem::take(&mut self.pending)
+    }
+
+    // Take all increases of the rewards reported since last call, so they can be stored in DB.
+    pub fn take_pending_rewards(&mut self) -> Vec<NodeRewardsDelta> {
+        std::mem::take(&mut self.pending_rewards)
+    }
+
+    // Set the total earnings estimated to have been earned by each node.
+    pub fn set_nodes_earnings(&mut self, earnings: HashMap<NodeId, U256>) {
+        self.earnings = earnings;
+    }
+
+    // Return the in-memory cached metrics for the given node.
+    pub fn cached_node_metrics(&self, node_id: &NodeId) -> Option<&HashMap<String, NodeMetric>> {
+        self.data.get(node_id)
+    }
+
+    // Record a failed attempt to fetch metrics from a node.
+    pub fn record_fetch_failure(&mut self) {
+        self.polling_health.fetch_failures += 1;
+    }
+
+    // Record the completion of a polling cycle and how long it took.
+    pub fn record_polling_cycle(&mut self, duration: Duration) {
+        self.polling_health.cycles += 1;
+        self.polling_health.last_cycle_duration = duration;
+    }
+
+    // Return the health of the metrics polling cycles.
+    pub fn polling_health(&self) -> &MetricsPollingHealth {
+        &self.polling_health
+    }
+
     // Clear only the in-memory cache for the given node, leaving DB history intact.
     pub fn clear_node_cache(&mut self, node_id: &NodeId) {
         self.data.remove(node_id);
@@ -167,15 +292,37 @@ impl NodesMetrics {
     pub async fn remove_node_metrics(&mut self, node_id: &NodeId) {
         self.db_client.delete_node_metrics(node_id).await;
         let _ = self.data.remove(node_id);
+        let _ = self.net_counters.remove(node_id);
     }
 
-    // Return all the metrics for the specified node id with given filters
+    // Return all the metrics for the specified node id with given filters. Older data points
+    // are provided with a coarser resolution as per the retention configured for each of them.
     pub async fn get_node_metrics(&self, node_id: NodeId, since: Option<i64>) -> Metrics {
-        self.db_client.get_node_metrics(node_id, since).await
+        let settings = self.db_client.get_settings().await;
+        let windows =
+            MetricsResolution::windows_for_range(since, Utc::now().timestamp_millis(), &settings);
+
+        let mut metrics = Metrics::new();
+        for (resolution, from, to) in windows {
+            let window_metrics = self
+                .db_client
+                .get_node_metrics(node_id.clone(), resolution, from, to)
+                .await;
+            for (key, values) in window_metrics {
+                metrics.entry(key).or_default().extend(values);
+            }
+        }
+        metrics
     }
 
     // Update given node instance info with in-memory cached metrics
     pub fn update_node_info(&self, info: &mut NodeInstanceInfo) {
+        info.estimated_earnings = self.earnings.get(&info.node_id).copied();
+        if let Some((_, rx_bytes, tx_bytes)) = self.net_counters.get(&info.node_id) {
+            info.net_rx_bytes = Some(*rx_bytes);
+            info.net_tx_bytes = Some(*tx_bytes);
+        }
+
         if let Some(metrics) = self.data.get(&info.node_id) {
             if let Some(metric) = metrics.get(METRIC_KEY_BALANCE) {
                 info.rewards = U256::from_str(&metric.value).ok();
@@ -189,6 +336,14 @@ impl NodesMetrics {
                 info.cpu_usage = metric.value.parse::<f64>().ok();
             }
 
+            if let Some(metric) = metrics.get(METRIC_KEY_NET_RX_RATE) {
+                info.net_rx_rate = metric.value.parse::<f64>().ok();
+            }
+
+            if let Some(metric) = metrics.get(METRIC_KEY_NET_TX_RATE) {
+                info.net_tx_rate = metric.value.parse::<f64>().ok();
+            }
+
             if let Some(metric) = metrics.get(METRIC_KEY_RECORDS) {
                 info.records = metric.value.parse::<usize>().ok();
             }
@@ -247,6 +402,16 @@ impl NodesMetrics {
     }
 }
 
+// Increase of the rewards reported in the given metrics since the last rewards seen for the node,
+// along with the timestamp of the sample. A decrease means the node was restarted and its rewards
+// were reset, while no last rewards means the node hasn't reported any yet.
+fn rewards_increase(last_rewards: Option<U256>, metrics: &[NodeMetric]) -> Option<(U256, i64)> {
+    let last = last_rewards?;
+    let metric = metrics.iter().find(|m| m.key == METRIC_KEY_BALANCE)?;
+    let current = U256::from_str(&metric.value).ok()?;
+    (current > last).then(|| (current - last, metric.timestamp))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -254,7 +419,7 @@ mod test {
 
     #[tokio::test]
     async fn test_metrics_parse_invalid() {
-        let client = NodeMetricsClient::new(8080);
+        let client = NodeMetricsClient::new(reqwest::Client::new(), 8080);
         let invalid_data = "not openmetrics format";
         let result = client.parse_and_filter_metrics(invalid_data).await;
         assert!(matches!(result, Err(MetricsClientError::ParseError(_))));
@@ -262,7 +427,7 @@ mod test {
 
     #[tokio::test]
     async fn test_metrics_parse_valid() {
-        let client = NodeMetricsClient::new(8080);
+        let client = NodeMetricsClient::new(reqwest::Client::new(), 8080);
         let data = NODE_METRICS_TO_COLLECT
             .iter()
             .enumerate()
@@ -302,4 +467,22 @@ mod test {
             assert_eq!(metric.timestamp, timestamp);
         });
     }
+
+    #[test]
+    fn test_rewards_increase() {
+        let metrics = [NodeMetric {
+            key: METRIC_KEY_BALANCE.to_string(),
+            value: "150".to_string(),
+            timestamp: 1_000,
+        }];
+        assert_eq!(
+            rewards_increase(Some(U256::from(100)), &metrics),
+            Some((U256::from(50), 1_000))
+        );
+        assert_eq!(rewards_increase(Some(U256::from(150)), &metrics), None);
+        // the node was restarted and its rewards reset
+        assert_eq!(rewards_increase(Some(U256::from(200)), &metrics), None);
+        assert_eq!(rewards_increase(None, &metrics), None);
+        assert_eq!(rewards_increase(Some(U256::from(100)), &[]), None);
+    }
 }
@@ -0,0 +1,443 @@
+use crate::{
+    app::AppContext,
+    types::{
+        BatchStatus, NodeInstanceInfo, NodesActionsBatch, PeriodStats, Stats,
+        metrics::{METRIC_KEY_REACHABILITY, NodeMetric},
+    },
+};
+
+use alloy_primitives::{U256, utils::format_units};
+use axum::{
+    extract::State,
+    http::header::CONTENT_TYPE,
+    response::{IntoResponse, Response},
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::{Display, Write},
+};
+
+// Content type of the OpenMetrics text exposition format.
+const OPENMETRICS_CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+// Labels and value of a single sample within a metrics family.
+type Sample = (Vec<(&'static str, String)>, String);
+
+// Handler which exposes, in OpenMetrics format, the metrics collected from all nodes
+// by the backend, together with aggregated stats and Formicaio's own health metrics.
+// Nodes are not scraped by this handler, all values are taken from the backend cache.
+pub async fn metrics_exporter(State(app_ctx): State<AppContext>) -> Response {
+    let nodes = app_ctx.db_client.get_nodes_list().await;
+    let stats = app_ctx.stats.read().await.clone();
+    let batches = app_ctx.node_action_batches.read().await.1.clone();
+    let mcp_up = app_ctx.mcp_status.read().await.is_some();
+
+    let mut writer = OpenMetricsWriter::default();
+    {
+        let nodes_metrics = app_ctx.nodes_metrics.read().await;
+        let cached = nodes
+            .values()
+            .map(|info| (info, nodes_metrics.cached_node_metrics(&info.node_id)))
+            .collect::<Vec<_>>();
+        write_nodes_metrics(&mut writer, &cached);
+
+        let health = nodes_metrics.polling_health();
+        writer.family(
+            "formicaio_metrics_polling_cycles",
+            "counter",
+            "Number of nodes metrics polling cycles completed.",
+            [(vec![], health.cycles.to_string())],
+        );
+        writer.family(
+            "formicaio_metrics_polling_last_duration_seconds",
+            "gauge",
+            "Time it took to complete the last nodes metrics polling cycle.",
+            [(
+                vec![],
+                format_value(health.last_cycle_duration.as_secs_f64()),
+            )],
+        );
+        writer.family(
+            "formicaio_metrics_fetch_failures",
+            "counter",
+            "Number of failed attempts to fetch metrics from nodes.",
+            [(vec![], health.fetch_failures.to_string())],
+        );
+    }
+
+    write_stats(&mut writer, &stats);
+    write_batches(&mut writer, &batches);
+
+    writer.family(
+        "formicaio_build_info",
+        "gauge",
+        "Formicaio version information.",
+        [(
+            vec![("version", env!("CARGO_PKG_VERSION").to_string())],
+            "1".to_string(),
+        )],
+    );
+    writer.family(
+        "formicaio_mcp_server_up",
+        "gauge",
+        "Whether the MCP server is running.",
+        [(vec![], u8::from(mcp_up).to_string())],
+    );
+
+    ([(CONTENT_TYPE, OPENMETRICS_CONTENT_TYPE)], writer.finish()).into_response()
+}
+
+// Re-export the last metrics cached for each node, labelled with the node's information.
+fn write_nodes_metrics(
+    writer: &mut OpenMetricsWriter,
+    nodes: &[(&NodeInstanceInfo, Option<&HashMap<String, NodeMetric>>)],
+) {
+    let mut up = Vec::new();
+    let mut disk_usage = Vec::new();
+    let mut families = BTreeMap::<String, Vec<Sample>>::new();
+
+    for (info, metrics) in nodes {
+        let labels = node_labels(info);
+        up.push((
+            labels.clone(),
+            u8::from(info.status.is_active()).to_string(),
+        ));
+        if let Some(bytes) = info.disk_usage {
+            disk_usage.push((labels.clone(), bytes.to_string()));
+        }
+
+        for metric in metrics.iter().flat_map(|m| m.values()) {
+            let sample = if metric.key == METRIC_KEY_REACHABILITY {
+                // the value of this metric is the label of the reachability mode
+                let mut labels = labels.clone();
+                labels.push(("mode", metric.value.clone()));
+                (labels, "1".to_string())
+            } else if let Ok(value) = metric.value.parse::<f64>() {
+                (labels.clone(), format_value(value))
+            } else {
+                continue;
+            };
+            families.entry(metric.key.clone()).or_default().push(sample);
+        }
+    }
+
+    writer.family(
+        "formicaio_node_up",
+        "gauge",
+        "Whether the node is active.",
+        up,
+    );
+    writer.family(
+        "formicaio_node_disk_usage_bytes",
+        "gauge",
+        "Disk space used by the node.",
+        disk_usage,
+    );
+    for (key, samples) in families {
+        // counters are re-exported as such, their samples' name get the '_total' suffix back
+        match key.strip_suffix("_total") {
+            Some(name) => {
+                writer.family(name, "counter", "Metric collected from the node.", samples)
+            }
+            None => writer.family(&key, "gauge", "Metric collected from the node.", samples),
+        }
+    }
+}
+
+// Labels to identify a node in the exported metrics.
+fn node_labels(info: &NodeInstanceInfo) -> Vec<(&'static str, String)> {
+    let status = if info.status.is_inactive() {
+        "Inactive".to_string()
+    } else {
+        info.status.to_string()
+    };
+    vec![
+        ("node_id", info.node_id.to_string()),
+        ("peer_id", info.peer_id.clone().unwrap_or_default()),
+        (
+            "rewards_addr",
+            info.rewards_addr.clone().unwrap_or_default(),
+        ),
+        ("version", info.bin_version.clone().unwrap_or_default()),
+        ("status", status),
+    ]
+}
+
+// Export the aggregated stats, balances and earnings of all nodes.
+fn write_stats(writer: &mut OpenMetricsWriter, stats: &Stats) {
+    let gauges: [(&str, &str, String); 11] = [
+        (
+            "formicaio_nodes",
+            "Total number of node instances.",
+            stats.total_nodes.to_string(),
+        ),
+        (
+            "formicaio_nodes_active",
+            "Number of active node instances.",
+            stats.active_nodes.to_string(),
+        ),
+        (
+            "formicaio_nodes_inactive",
+            "Number of inactive node instances.",
+            stats.inactive_nodes.to_string(),
+        ),
+        (
+            "formicaio_connected_peers",
+            "Total number of peers connected across all nodes.",
+            stats.connected_peers.to_string(),
+        ),
+        (
+            "formicaio_shunned_count",
+            "Total number of peers which have shunned nodes.",
+            stats.shunned_count.to_string(),
+        ),
+        (
+            "formicaio_estimated_network_size",
+            "Estimated network size as the average of all nodes' observations.",
+            stats.estimated_net_size.to_string(),
+        ),
+        (
+            "formicaio_stored_records",
+            "Total number of records stored across all nodes.",
+            stats.stored_records.to_string(),
+        ),
+        (
+            "formicaio_relevant_records",
+            "Total number of relevant records stored across all nodes.",
+            stats.relevant_records.to_string(),
+        ),
+        (
+            "formicaio_disk_total_bytes",
+            "Total disk space.",
+            stats.total_disk_space.to_string(),
+        ),
+        (
+            "formicaio_disk_available_bytes",
+            "Available disk space.",
+            stats.available_disk_space.to_string(),
+        ),
+        (
+            "formicaio_disk_used_by_nodes_bytes",
+            "Disk space used by all nodes.",
+            stats.used_disk_space.to_string(),
+        ),
+    ];
+    for (name, help, value) in gauges {
+        writer.family(name, "gauge", help, [(vec![], value)]);
+    }
+
+    writer.family(
+        "formicaio_rewards_total_balance",
+        "gauge",
+        "Total balance of all rewards addresses, in tokens.",
+        [(vec![], tokens(stats.total_balance))],
+    );
+    writer.family(
+        "formicaio_rewards_balance",
+        "gauge",
+        "Balance of the rewards address, in tokens.",
+        stats
+            .balances
+            .iter()
+            .map(|(addr, balance)| (vec![("address", addr.clone())], tokens(*balance))),
+    );
+    writer.family(
+        "formicaio_rewards_eth_balance",
+        "gauge",
+        "Balance of the rewards address in the network's native gas token, in ETH.",
+        stats.other_balances.iter().filter_map(|(addr, others)| {
+            others
+                .eth
+                .map(|eth| (vec![("address", addr.clone())], tokens(eth)))
+        }),
+    );
+
+    let periods = stats
+        .earnings
+        .iter()
+        .flat_map(|(addr, e)| e.periods.iter().map(move |p| (addr, p)))
+        .collect::<Vec<_>>();
+    let period_labels = |addr: &String, p: &PeriodStats| {
+        vec![
+            ("address", addr.clone()),
+            ("period_hours", p.length_hours.to_string()),
+        ]
+    };
+    writer.family(
+        "formicaio_rewards_earned",
+        "gauge",
+        "Earnings of the rewards address within the period, in tokens.",
+        periods
+            .iter()
+            .map(|(addr, p)| (period_labels(addr, p), tokens(p.total_earned))),
+    );
+    writer.family(
+        "formicaio_rewards_payments",
+        "gauge",
+        "Number of payments received by the rewards address within the period.",
+        periods
+            .iter()
+            .map(|(addr, p)| (period_labels(addr, p), p.num_payments.to_string())),
+    );
+    writer.family(
+        "formicaio_rewards_earnings_syncing",
+        "gauge",
+        "Whether earnings history is still being fetched from the chain.",
+        [(
+            vec![],
+            u8::from(stats.earnings_sync_progress.is_some()).to_string(),
+        )],
+    );
+    writer.family(
+        "formicaio_rewards_earnings_sync_progress",
+        "gauge",
+        "Percentage of the tracked earnings history fetched from the chain so far.",
+        [(
+            vec![],
+            stats.earnings_sync_progress.unwrap_or(100).to_string(),
+        )],
+    );
+}
+
+// Export the number of nodes actions batches by their status.
+fn write_batches(writer: &mut OpenMetricsWriter, batches: &[NodesActionsBatch]) {
+    let mut counts = BTreeMap::from([
+        ("scheduled", 0),
+        ("in_progress", 0),
+        ("paused", 0),
+        ("failed", 0),
+    ]);
+    for batch in batches {
+        let status = match batch.status {
+            BatchStatus::Scheduled => "scheduled",
+            BatchStatus::InProgress | BatchStatus::InProgressWithFailures(_, _) => "in_progress",
+            BatchStatus::Paused => "paused",
+            BatchStatus::Failed(_) => "failed",
+            BatchStatus::Completed => "completed",
+            BatchStatus::Cancelled => "cancelled",
+        };
+        *counts.entry(status).or_default() += 1;
+    }
+    writer.family(
+        "formicaio_batches",
+        "gauge",
+        "Number of nodes actions batches by status.",
+        counts
+            .into_iter()
+            .map(|(status, count)| (vec![("status", status.to_string())], count.to_string())),
+    );
+}
+
+// Format a sample value, non-finite values are spelled as OpenMetrics requires
+// rather than as Rust does ('inf' and '-inf').
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value == f64::INFINITY {
+        "+Inf".to_string()
+    } else if value == f64::NEG_INFINITY {
+        "-Inf".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+// Convert a token amount into a decimal number of tokens.
+fn tokens(amount: U256) -> String {
+    format_units(amount, "ether").unwrap_or_else(|_| "NaN".to_string())
+}
+
+// Helper to build the body of an OpenMetrics response.
+#[derive(Default)]
+struct OpenMetricsWriter {
+    out: String,
+}
+
+impl OpenMetricsWriter {
+    // Write a metrics family with all its samples. Counters' samples get the '_total' suffix.
+    fn family(
+        &mut self,
+        name: &str,
+        metric_type: &str,
+        help: &str,
+        samples: impl IntoIterator<Item = Sample>,
+    ) {
+        let _ = writeln!(self.out, "# TYPE {name} {metric_type}");
+        let _ = writeln!(self.out, "# HELP {name} {help}");
+        let suffix = if metric_type == "counter" {
+            "_total"
+        } else {
+            ""
+        };
+        for (labels, value) in samples {
+            let _ = write!(self.out, "{name}{suffix}");
+            if !labels.is_empty() {
+                let labels = labels
+                    .iter()
+                    .map(|(k, v)| format!("{k}=\"{}\"", escape_label_value(v)))
+                    .collect::<Vec<_>>()
+                    .join(",");
+                let _ = write!(self.out, "{{{labels}}}");
+            }
+            let _ = writeln!(self.out, " {value}");
+        }
+    }
+
+    fn finish(mut self) -> String {
+        self.out.push_str("# EOF\n");
+        self.out
+    }
+}
+
+fn escape_label_value(value: impl Display) -> String {
+    value
+        .to_string()
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_family_is_written_in_openmetrics_format() {
+        let mut writer = OpenMetricsWriter::default();
+        writer.family(
+            "formicaio_test",
+            "counter",
+            "Test metric.",
+            [
+                (vec![], "1".to_string()),
+                (
+                    vec![
+                        ("node_id", "ab\"c".to_string()),
+                        ("status", "Active".to_string()),
+                    ],
+                    "2.5".to_string(),
+                ),
+            ],
+        );
+        assert_eq!(
+            writer.finish(),
+            "# TYPE formicaio_test counter\n\
+             # HELP formicaio_test Test metric.\n\
+             formicaio_test_total 1\n\
+             formicaio_test_total{node_id=\"ab\\\"c\",status=\"Active\"} 2.5\n\
+             # EOF\n"
+        );
+    }
+
+    #[test]
+    fn test_non_finite_values_are_formatted() {
+        assert_eq!(format_value(2.5), "2.5");
+        assert_eq!(format_value(-3.0), "-3");
+        assert_eq!(format_value(f64::INFINITY), "+Inf");
+        assert_eq!(format_value(f64::NEG_INFINITY), "-Inf");
+        assert_eq!(format_value(f64::NAN), "NaN");
+        // values reported by nodes as such are parsed into non-finite ones
+        assert_eq!(format_value("inf".parse().unwrap()), "+Inf");
+        assert_eq!(format_value("-inf".parse().unwrap()), "-Inf");
+    }
+}
@@ -0,0 +1,479 @@
+use super::BgTasksCmds;
+use crate::types::{AppSettings, NodeInstanceInfo, Stats};
+
+use alloy_primitives::{U256, utils::format_units};
+use leptos::logging;
+use std::collections::VecDeque;
+use thiserror::Error;
+use tokio::{
+    io::AsyncWriteExt,
+    net::TcpStream,
+    select,
+    sync::broadcast,
+    time::{Duration, Instant, interval, timeout},
+};
+
+// Maximum number of points buffered for each sink while they cannot be delivered.
+// When the limit is reached the oldest points are dropped.
+const MAX_BUFFERED_POINTS: usize = 50_000;
+// Maximum number of points sent to a sink in a single request.
+const MAX_BATCH_SIZE: usize = 5_000;
+// How often buffered points are pushed to the sinks.
+const FLUSH_FREQ: Duration = Duration::from_secs(10);
+// Maximum delay between retries when a sink keeps failing.
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(5 * 60);
+// Timeout when sending a batch of points to a sink.
+const SINK_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Error)]
+pub enum MetricsPushError {
+    #[error(transparent)]
+    HttpError(#[from] reqwest::Error),
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+    #[error("InfluxDB server responded with status {0}: {1}")]
+    InfluxDbError(reqwest::StatusCode, String),
+    #[error("Timeout ({SINK_REQUEST_TIMEOUT:?}) while sending metrics")]
+    Timeout,
+}
+
+// A data point to be pushed to the metrics sinks.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MetricPoint {
+    measurement: &'static str,
+    tags: Vec<(&'static str, String)>,
+    fields: Vec<(&'static str, f64)>,
+    // Timestamp in millis
+    timestamp: i64,
+}
+
+impl MetricPoint {
+    pub fn new(measurement: &'static str, timestamp: i64) -> Self {
+        Self {
+            measurement,
+            tags: Vec::new(),
+            fields: Vec::new(),
+            timestamp,
+        }
+    }
+
+    pub fn tag(mut self, key: &'static str, value: impl ToString) -> Self {
+        self.tags.push((key, value.to_string()));
+        self
+    }
+
+    pub fn field(mut self, key: &'static str, value: Option<f64>) -> Self {
+        if let Some(value) = value.filter(|v| v.is_finite()) {
+            self.fields.push((key, value));
+        }
+        self
+    }
+
+    // Format the point as an InfluxDB line protocol entry, with timestamp in nanoseconds.
+    fn to_influx_line(&self) -> String {
+        let escape = |s: &str| {
+            s.replace('\\', "\\\\")
+                .replace(',', "\\,")
+                .replace('=', "\\=")
+                .replace(' ', "\\ ")
+        };
+        let tags = self
+            .tags
+            .iter()
+            .filter(|(_, v)| !v.is_empty())
+            .map(|(k, v)| format!(",{}={}", escape(k), escape(v)))
+            .collect::<String>();
+        let fields = self
+            .fields
+            .iter()
+            .map(|(k, v)| format!("{}={v}", escape(k)))
+            .collect::<Vec<_>>()
+            .join(",");
+        format!(
+            "{}{tags} {fields} {}",
+            escape(self.measurement),
+            self.timestamp * 1_000_000
+        )
+    }
+
+    // Format the point as Graphite plaintext protocol entries, one per field,
+    // with the metric path built from the measurement, tag values and field name.
+    fn to_graphite_lines(&self) -> Vec<String> {
+        let sanitise = |s: &str| {
+            s.chars()
+                .map(|c| {
+                    if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                        c
+                    } else {
+                        '_'
+                    }
+                })
+                .collect::<String>()
+        };
+        let prefix = std::iter::once(self.measurement)
+            .chain(self.tags.iter().map(|(_, v)| v.as_str()))
+            .filter(|s| !s.is_empty())
+            .map(sanitise)
+            .collect::<Vec<_>>()
+            .join(".");
+        self.fields
+            .iter()
+            .map(|(k, v)| format!("{prefix}.{} {v} {}", sanitise(k), self.timestamp / 1_000))
+            .collect()
+    }
+}
+
+// Build a point with the metrics of the given node, if there is any.
+pub fn node_point(info: &NodeInstanceInfo, timestamp: i64) -> Option<MetricPoint> {
+    let point = MetricPoint::new("formicaio_node", timestamp)
+        .tag("node_id", &info.node_id)
+        .field("mem_used_mb", info.mem_used)
+        .field("cpu_usage_percent", info.cpu_usage)
+        .field("records", info.records.map(|v| v as f64))
+        .field("relevant_records", info.relevant_records.map(|v| v as f64))
+        .field("connected_peers", info.connected_peers.map(|v| v as f64))
+        .field("kbuckets_peers", info.kbuckets_peers.map(|v| v as f64))
+        .field("shunned_count", info.shunned_count.map(|v| v as f64))
+        .field("net_size", info.net_size.map(|v| v as f64))
+        .field("disk_usage_bytes", info.disk_usage.map(|v| v as f64))
+        .field("rewards", info.rewards.and_then(tokens));
+    (!point.fields.is_empty()).then_some(point)
+}
+
+// Build a point with the aggregated stats of all nodes.
+pub fn stats_point(stats: &Stats, timestamp: i64) -> MetricPoint {
+    MetricPoint::new("formicaio_stats", timestamp)
+        .field("total_nodes", Some(stats.total_nodes as f64))
+        .field("active_nodes", Some(stats.active_nodes as f64))
+        .field("inactive_nodes", Some(stats.inactive_nodes as f64))
+        .field("connected_peers", Some(stats.connected_peers as f64))
+        .field("shunned_count", Some(stats.shunned_count as f64))
+        .field("estimated_net_size", Some(stats.estimated_net_size as f64))
+        .field("stored_records", Some(stats.stored_records as f64))
+        .field("relevant_records", Some(stats.relevant_records as f64))
+        .field("total_disk_space", Some(stats.total_disk_space as f64))
+        .field(
+            "available_disk_space",
+            Some(stats.available_disk_space as f64),
+        )
+        .field("used_disk_space", Some(stats.used_disk_space as f64))
+}
+
+// Build the points with the current balance of each rewards address, and their total.
+pub fn balances_points(stats: &Stats, timestamp: i64) -> Vec<MetricPoint> {
+    stats
+        .balances
+        .iter()
+        .map(|(addr, balance)| {
+            MetricPoint::new("formicaio_rewards", timestamp)
+                .tag("address", addr)
+                .field("balance", tokens(*balance))
+        })
+        .chain(std::iter::once(
+            MetricPoint::new("formicaio_rewards_total", timestamp)
+                .field("balance", tokens(stats.total_balance)),
+        ))
+        .collect()
+}
+
+// Convert a token amount into a decimal number of tokens.
+fn tokens(amount: U256) -> Option<f64> {
+    format_units(amount, "ether").ok()?.parse().ok()
+}
+
+// Destination where metrics are pushed to.
+#[derive(Clone, Debug, PartialEq)]
+enum SinkTarget {
+    InfluxDb { url: String, token: String },
+    Graphite { addr: String },
+}
+
+impl SinkTarget {
+    fn influxdb(settings: &AppSettings) -> Option<Self> {
+        settings.influxdb_enabled.then(|| Self::InfluxDb {
+            url: settings.influxdb_url.clone(),
+            token: settings.influxdb_token.clone(),
+        })
+    }
+
+    fn graphite(settings: &AppSettings) -> Option<Self> {
+        settings.graphite_enabled.then(|| Self::Graphite {
+            addr: settings.graphite_addr.clone(),
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::InfluxDb { .. } => "InfluxDB",
+            Self::Graphite { .. } => "Graphite",
+        }
+    }
+
+    async fn send(
+        &self,
+        client: &reqwest::Client,
+        points: &[MetricPoint],
+    ) -> Result<(), MetricsPushError> {
+        match self {
+            Self::InfluxDb { url, token } => {
+                let body = points
+                    .iter()
+                    .map(|p| p.to_influx_line())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                let mut req = client.post(url).body(body);
+                if !token.is_empty() {
+                    req = req.header(reqwest::header::AUTHORIZATION, format!("Token {token}"));
+                }
+                let resp = req.send().await?;
+                let status = resp.status();
+                if !status.is_success() {
+                    let msg = resp.text().await.unwrap_or_default();
+                    return Err(MetricsPushError::InfluxDbError(status, msg));
+                }
+            }
+            Self::Graphite { addr } => {
+                let body = points
+                    .iter()
+                    .flat_map(|p| p.to_graphite_lines())
+                    .map(|l| l + "\n")
+                    .collect::<String>();
+                let mut stream = TcpStream::connect(addr).await?;
+                stream.write_all(body.as_bytes()).await?;
+                stream.shutdown().await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// A metrics sink with its buffer of points pending to be delivered.
+struct Sink {
+    target: SinkTarget,
+    buffer: VecDeque<MetricPoint>,
+    backoff: Duration,
+    next_attempt: Instant,
+}
+
+impl Sink {
+    fn new(target: SinkTarget) -> Self {
+        Self {
+            target,
+            buffer: VecDeque::new(),
+            backoff: Duration::ZERO,
+            next_attempt: Instant::now(),
+        }
+    }
+
+    fn enqueue(&mut self, points: &[MetricPoint]) {
+        self.buffer.extend(points.iter().cloned());
+        let overflow = self.buffer.len().saturating_sub(MAX_BUFFERED_POINTS);
+        if overflow > 0 {
+            self.buffer.drain(..overflow);
+            logging::warn!(
+                "[WARN][BgTask] Dropped {overflow} metrics points not yet pushed to {}, buffer is full.",
+                self.target.name()
+            );
+        }
+    }
+
+    // Push all buffered points in batches. Upon a failure the remaining points are kept
+    // to be retried on a later flush, backing off exponentially while the sink keeps failing.
+    async fn flush(&mut self, client: &reqwest::Client) {
+        if Instant::now() < self.next_attempt {
+            return;
+        }
+
+        while !self.buffer.is_empty() {
+            let batch_size = self.buffer.len().min(MAX_BATCH_SIZE);
+            let batch = self.buffer.range(..batch_size).cloned().collect::<Vec<_>>();
+            let res = match timeout(SINK_REQUEST_TIMEOUT, self.target.send(client, &batch)).await {
+                Ok(res) => res,
+                Err(_) => Err(MetricsPushError::Timeout),
+            };
+
+            match res {
+                Ok(()) => {
+                    self.buffer.drain(..batch_size);
+                    self.backoff = Duration::ZERO;
+                }
+                Err(err) => {
+                    self.backoff = (self.backoff * 2).clamp(FLUSH_FREQ, MAX_RETRY_BACKOFF);
+                    self.next_attempt = Instant::now() + self.backoff;
+                    logging::warn!(
+                        "[WARN][BgTask] Failed to push {} metrics points to {}, retrying in {:?}: {err}",
+                        self.buffer.len(),
+                        self.target.name(),
+                        self.backoff
+                    );
+                    return;
+                }
+            }
+        }
+    }
+}
+
+// Replace the sink if its target was changed in the settings, otherwise keep it with its buffer.
+fn update_sink(sink: &mut Option<Sink>, target: Option<SinkTarget>) {
+    if sink.as_ref().map(|s| &s.target) != target.as_ref() {
+        if let Some(ref target) = target {
+            logging::log!("[BgTask] Pushing metrics to {}", target.name());
+        }
+        *sink = target.map(Sink::new);
+    }
+}
+
+// Task which receives the metrics points collected by other tasks
+// and pushes them to the sinks enabled in the settings.
+pub async fn metrics_push_task(
+    settings: AppSettings,
+    mut bg_tasks_cmds_rx: broadcast::Receiver<BgTasksCmds>,
+    mut points_rx: broadcast::Receiver<Vec<MetricPoint>>,
+) {
+    let client = reqwest::Client::new();
+    let mut influxdb = None;
+    let mut graphite = None;
+    update_sink(&mut influxdb, SinkTarget::influxdb(&settings));
+    update_sink(&mut graphite, SinkTarget::graphite(&settings));
+    let mut flush = interval(FLUSH_FREQ);
+
+    loop {
+        select! {
+            cmd = bg_tasks_cmds_rx.recv() => {
+                if let Ok(BgTasksCmds::ApplySettings(s)) = cmd {
+                    update_sink(&mut influxdb, SinkTarget::influxdb(&s));
+                    update_sink(&mut graphite, SinkTarget::graphite(&s));
+                }
+            },
+            points = points_rx.recv() => {
+                match points {
+                    Ok(points) => {
+                        for sink in [&mut influxdb, &mut graphite].into_iter().flatten() {
+                            sink.enqueue(&points);
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(n)) => logging::warn!(
+                        "[WARN][BgTask] Dropped {n} batches of metrics points, sinks are lagging behind."
+                    ),
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            },
+            _ = flush.tick() => {
+                for sink in [&mut influxdb, &mut graphite].into_iter().flatten() {
+                    sink.flush(&client).await;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bg_tasks::stub_http_server::StubHttpServer;
+    use tokio::{io::AsyncReadExt, net::TcpListener};
+
+    fn test_point() -> MetricPoint {
+        MetricPoint::new("formicaio_node", 1_700_000_000_123)
+            .tag("node_id", "ab cd")
+            .tag("empty", "")
+            .field("mem_used_mb", Some(12.5))
+            .field("records", Some(3.0))
+            .field("missing", None)
+    }
+
+    #[test]
+    fn test_influx_line_format() {
+        assert_eq!(
+            test_point().to_influx_line(),
+            "formicaio_node,node_id=ab\\ cd mem_used_mb=12.5,records=3 1700000000123000000"
+        );
+    }
+
+    #[test]
+    fn test_graphite_lines_format() {
+        assert_eq!(
+            test_point().to_graphite_lines(),
+            vec![
+                "formicaio_node.ab_cd.mem_used_mb 12.5 1700000000",
+                "formicaio_node.ab_cd.records 3 1700000000"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_graphite_sink_pushes_points() -> Result<(), MetricsPushError> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?.to_string();
+        let mut sink = Sink::new(SinkTarget::Graphite { addr });
+        sink.enqueue(&[test_point()]);
+
+        let client = reqwest::Client::new();
+        let (flushed, received) = tokio::join!(sink.flush(&client), async {
+            let (mut stream, _) = listener.accept().await?;
+            let mut received = String::new();
+            stream.read_to_string(&mut received).await?;
+            Ok::<_, std::io::Error>(received)
+        });
+        let () = flushed;
+
+        assert_eq!(
+            received?,
+            "formicaio_node.ab_cd.mem_used_mb 12.5 1700000000\n\
+             formicaio_node.ab_cd.records 3 1700000000\n"
+        );
+        assert!(sink.buffer.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_influxdb_sink_pushes_points() -> Result<(), MetricsPushError> {
+        let server = StubHttpServer::bind().await?;
+        let mut sink = Sink::new(SinkTarget::InfluxDb {
+            url: server.url("/write?db=formicaio"),
+            token: "secret".to_string(),
+        });
+        sink.enqueue(&[test_point()]);
+
+        let client = reqwest::Client::new();
+        let (flushed, request) = tokio::join!(sink.flush(&client), server.respond(204, ""));
+        let () = flushed;
+
+        let request = request?;
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.target, "/write?db=formicaio");
+        assert_eq!(request.header("authorization"), Some("Token secret"));
+        assert_eq!(request.body, test_point().to_influx_line());
+        assert!(sink.buffer.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_failed_push_is_kept_for_retry() -> Result<(), MetricsPushError> {
+        // bind and drop a listener to get an address where nobody is listening
+        let addr = TcpListener::bind("127.0.0.1:0")
+            .await?
+            .local_addr()?
+            .to_string();
+        let mut sink = Sink::new(SinkTarget::Graphite { addr });
+        sink.enqueue(&[test_point(), test_point()]);
+
+        sink.flush(&reqwest::Client::new()).await;
+        assert_eq!(sink.buffer.len(), 2);
+        assert_eq!(sink.backoff, FLUSH_FREQ);
+        assert!(sink.next_attempt > Instant::now());
+        Ok(())
+    }
+
+    #[test]
+    fn test_buffer_is_bounded() {
+        let mut sink = Sink::new(SinkTarget::Graphite {
+            addr: "localhost:2003".to_string(),
+        });
+        let points = vec![test_point(); MAX_BUFFERED_POINTS];
+        sink.enqueue(&points);
+        sink.enqueue(&[test_point().tag("newest", "yes")]);
+        assert_eq!(sink.buffer.len(), MAX_BUFFERED_POINTS);
+        assert_eq!(sink.buffer.back(), Some(&test_point().tag("newest", "yes")));
+    }
+}
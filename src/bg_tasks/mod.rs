@@ -1,35 +1,74 @@
 pub mod agent;
+mod alerts;
 mod arbitrum_client;
+mod auth;
 mod batches;
 mod earnings;
+mod events_feed;
+mod host_metrics;
 #[cfg(not(feature = "lcd-disabled"))]
 mod lcd;
 mod mcp;
 pub(crate) mod mcp_tools;
 mod metrics_client;
+mod metrics_exporter;
+mod metrics_push;
+mod nodes_earnings;
+mod notifier;
+mod profitability;
+mod rpc_endpoints;
+#[cfg(test)]
+mod stub_http_server;
 mod tasks;
 mod tasks_ctx;
+mod token_prices;
 
 use super::{
     app::{AGENT_EVENTS_MAX_AGE_DAYS, AppContext},
     node_mgr::NodeManager,
-    types::{AppSettings, NodeId, NodeInstanceInfo, NodesActionsBatch},
+    types::{AppSettings, NodeId, NodeInstanceInfo, NodesActionsBatch, ServerEvent},
 };
 
 pub use agent::AgentContext;
+pub use alerts::AlertsEngine;
 pub(crate) use arbitrum_client::PaymentRecord;
-pub use batches::{ActionsBatchError, prepare_node_action_batch};
+pub use auth::{
+    SESSION_DURATION_SECS, authenticated_user, hash_password, hash_session_token,
+    new_session_token, require_auth, rewards_addrs_permission, session_cookie, session_token,
+    verify_password,
+};
+pub use batches::{
+    ActionsBatchError, cancel_node_action_batch, find_node_action_batch, pause_node_action_batch,
+    prepare_node_action_batch, replace_batched_node_id, resume_node_action_batch,
+    retry_node_action_batch, set_node_action_batch_interval,
+};
+pub use earnings::calc_earnings_history;
+pub use events_feed::{events_stream, nodes_instances_info};
+pub use host_metrics::host_metrics_history;
 pub use mcp::start_mcp_server;
 pub use metrics_client::NodesMetrics;
+pub use metrics_exporter::metrics_exporter;
+pub use metrics_push::MetricPoint;
+pub use nodes_earnings::attribute_nodes_earnings;
+pub(crate) use nodes_earnings::{NodeEarning, NodeRewardsDelta};
+pub use notifier::send_notification;
+pub(crate) use profitability::ResourceUsageSample;
+pub use profitability::profitability;
+pub use rpc_endpoints::RpcEndpoints;
+pub use token_prices::earnings_export;
 
+use alerts::evaluate_alert_rules;
 use alloy::sol;
+use host_metrics::{HostMonitor, update_host_stats};
 #[cfg(not(feature = "lcd-disabled"))]
 use lcd::display_stats_on_lcd;
 use leptos::logging;
+use metrics_push::metrics_push_task;
+use notifier::notifier_task;
 use std::{collections::HashMap, sync::Arc};
 use tasks::{
-    balance_checker_task, check_node_bin_version, prune_metrics, update_disks_usage,
-    update_nodes_info,
+    balance_checker_task, check_node_bin_version, prune_metrics, prune_stats_history,
+    rollup_metrics, snapshot_stats, update_disks_usage, update_nodes_info,
 };
 use tasks_ctx::TasksContext;
 use tokio::{
@@ -119,6 +158,13 @@ pub fn spawn_bg_tasks(app_ctx: AppContext, node_manager: NodeManager, settings:
         node_manager.clone(),
     ));
 
+    // Resume any nodes actions batch left unfinished when the app was stopped
+    tokio::spawn({
+        let app_ctx = app_ctx.clone();
+        let node_manager = node_manager.clone();
+        async move { batches::resume_node_action_batches(&app_ctx, &node_manager).await }
+    });
+
     let lcd_stats = Arc::new(RwLock::new(
         [(
             "Formicaio".to_string(),
@@ -146,8 +192,27 @@ pub fn spawn_bg_tasks(app_ctx: AppContext, node_manager: NodeManager, settings:
         lcd_stats.clone(),
     ));
 
+    // Spawn task which pushes collected metrics to the sinks enabled in settings
+    tokio::spawn(metrics_push_task(
+        ctx.app_settings.clone(),
+        app_ctx.bg_tasks_cmds_tx.subscribe(),
+        app_ctx.metrics_push_tx.subscribe(),
+    ));
+
+    // Spawn task which sends events through the notification channels subscribed to them
+    tokio::spawn(notifier_task(
+        app_ctx.db_client.clone(),
+        app_ctx.notifications_tx.subscribe(),
+    ));
+
     tokio::spawn(async move {
         let mut bg_tasks_cmds_rx = app_ctx.bg_tasks_cmds_tx.subscribe();
+        // HTTP client shared by all nodes metrics requests, so connections are kept alive and reused.
+        let metrics_http_client = reqwest::Client::new();
+        // Status of each node seen on the last polling cycle, to detect status transitions.
+        let mut nodes_last_status = HashMap::new();
+        // Sampler of the host resources usage, kept across samples to calculate I/O rates.
+        let mut host_monitor = HostMonitor::new();
         loop {
             select! {
                 cmd = bg_tasks_cmds_rx.recv() => {
@@ -172,11 +237,14 @@ pub fn spawn_bg_tasks(app_ctx: AppContext, node_manager: NodeManager, settings:
                                 agent::AgentCmd::SettingsChanged(Box::new(s.clone()))
                             );
                             ctx.apply_settings(s);
+                            // let the clients know, e.g. other users or tabs, so they retrieve them again
+                            let _ = app_ctx.events_tx.send(ServerEvent::SettingsUpdated);
                         }
                         Ok(BgTasksCmds::AgentAutonomousModeToggled(enabled)) => {
                             let _ = app_ctx.agent_ctx.cmds_tx.send(
                                 agent::AgentCmd::AutonomousModeToggled(enabled)
                             );
+                            let _ = app_ctx.events_tx.send(ServerEvent::SettingsUpdated);
                         }
                         _ => {}
                     }
@@ -194,9 +262,10 @@ pub fn spawn_bg_tasks(app_ctx: AppContext, node_manager: NodeManager, settings:
                     let _ = app_ctx.bg_tasks_cmds_tx.send(BgTasksCmds::PruneEarningsHistory);
                     let db_client = app_ctx.db_client.clone();
                     tokio::spawn(prune_metrics(
-                        node_manager.clone(),
-                        db_client.clone()
+                        db_client.clone(),
+                        ctx.app_settings.clone()
                     ));
+                    tokio::spawn(prune_stats_history(db_client.clone()));
                     tokio::spawn(async move {
                         logging::log!(
                             "[BgTask] Removing AI agent events older than {AGENT_EVENTS_MAX_AGE_DAYS} days from DB..."
@@ -204,9 +273,27 @@ pub fn spawn_bg_tasks(app_ctx: AppContext, node_manager: NodeManager, settings:
                         db_client.prune_agent_events(AGENT_EVENTS_MAX_AGE_DAYS).await;
                     });
                 },
+                _ = ctx.metrics_rollup.tick() => {
+                    tokio::spawn(rollup_metrics(app_ctx.db_client.clone()));
+                },
+                _ = ctx.stats_snapshot.tick() => {
+                    tokio::spawn(snapshot_stats(app_ctx.clone()));
+                },
+                _ = ctx.alerts_evaluation.tick() => {
+                    tokio::spawn(evaluate_alert_rules(app_ctx.clone()));
+                },
+                _ = ctx.host_metrics_sampling.tick() => {
+                    update_host_stats(&app_ctx, &ctx.app_settings, &mut host_monitor).await;
+                },
+                _ = ctx.rpc_health_check.tick() => {
+                    // the endpoints are only queried when rewards are being monitored
+                    if ctx.app_settings.rewards_monitoring_enabled {
+                        let rpc_endpoints = app_ctx.rpc_endpoints.clone();
+                        tokio::spawn(async move { rpc_endpoints.check_health().await });
+                    }
+                },
                 _ = ctx.nodes_metrics_polling.tick() => {
                     let query_bin_version = ctx.app_settings.lcd_display_enabled;
-                    let metrics_mode = ctx.app_settings.metrics_mode;
 
                     // we don't spawn a task for this one just in case it's taking
                     // too long to complete and we may start overwhelming the backend
@@ -215,7 +302,9 @@ pub fn spawn_bg_tasks(app_ctx: AppContext, node_manager: NodeManager, settings:
                         &node_manager,
                         app_ctx.clone(),
                         query_bin_version,
-                        metrics_mode,
+                        &ctx.app_settings,
+                        &metrics_http_client,
+                        &mut nodes_last_status,
                         &lcd_stats
                     ).await;
                     // reset interval to start next period from this instant,
@@ -0,0 +1,255 @@
+use crate::{
+    app::AppContext,
+    types::{EarningsExportRow, NodeId},
+};
+
+use alloy_primitives::{Address, U256};
+use chrono::Utc;
+use leptos::logging;
+use std::collections::{HashMap, HashSet};
+
+// Max time (in seconds) between a payment and the increase of the rewards reported
+// by a node for them to be correlated, allowing for the nodes metrics polling delay.
+const ATTRIBUTION_WINDOW_SECS: i64 = 900;
+// How far back (in seconds) payments which haven't been attributed yet are looked at.
+const ATTRIBUTION_LOOKBACK_SECS: i64 = 86_400;
+
+/// Increase of the rewards reported by a node through its metrics.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NodeRewardsDelta {
+    /// Id of the record in DB, 0 if it's not been stored yet
+    pub id: i64,
+    pub node_id: NodeId,
+    /// Increase of the rewards, in attos
+    pub amount: U256,
+    /// Timestamp (in millis) of the metrics sample the increase was observed in
+    pub timestamp: i64,
+}
+
+/// Share of a reward payment estimated to have been earned by a node.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NodeEarning {
+    pub node_id: NodeId,
+    /// Rewards address the payment was received by
+    pub address: String,
+    /// Amount attributed to the node, in attos
+    pub amount: U256,
+    pub block_number: u64,
+    /// UNIX timestamp (seconds) of the block the payment was included in
+    pub timestamp: i64,
+}
+
+/// Attribute the reward payments received lately to the nodes they were most likely earned
+/// by, correlating them with the increases of the rewards reported by the nodes paying to
+/// the same address. The estimated earnings of each node are then updated in the nodes metrics.
+pub async fn attribute_nodes_earnings(app_ctx: &AppContext) {
+    let db_client = &app_ctx.db_client;
+    let now = Utc::now().timestamp();
+    // payments are attributed only once the window of the rewards increases they can be
+    // correlated with has elapsed, so those reported after the payment are not missed
+    let to = now - ATTRIBUTION_WINDOW_SECS;
+    let from = to - ATTRIBUTION_LOOKBACK_SECS;
+
+    let res = match db_client.get_earnings_in_range(None, from, to).await {
+        Ok(payments) => {
+            let attributed = db_client.get_attributed_payments(from, to).await;
+            let payments = payments
+                .into_iter()
+                .filter(|p| !attributed.contains(&(p.address.clone(), p.block_number)))
+                .collect::<Vec<_>>();
+            db_client
+                .get_nodes_rewards_deltas(
+                    (from - ATTRIBUTION_WINDOW_SECS) * 1_000,
+                    (to + ATTRIBUTION_WINDOW_SECS) * 1_000,
+                )
+                .await
+                .map(|deltas| (payments, deltas))
+        }
+        Err(err) => Err(err),
+    };
+
+    match res {
+        Ok((payments, deltas)) if !payments.is_empty() && !deltas.is_empty() => {
+            let nodes_addrs = db_client
+                .get_nodes_list()
+                .await
+                .into_iter()
+                .filter_map(|(node_id, info)| {
+                    info.rewards_addr
+                        .and_then(|addr| addr.parse::<Address>().ok())
+                        .map(|addr| (node_id, addr))
+                })
+                .collect();
+            let (earnings, consumed) = attribute_payments(&payments, &deltas, &nodes_addrs);
+            if !earnings.is_empty() {
+                logging::log!(
+                    "[BgTask] Attributed {} reward payments shares to nodes.",
+                    earnings.len()
+                );
+            }
+            db_client.store_nodes_earnings(&earnings).await;
+            db_client.delete_nodes_rewards_deltas(&consumed).await;
+        }
+        Ok(_) => {}
+        Err(err) => {
+            logging::error!("[ERROR][BgTask] Failed to attribute reward payments to nodes: {err}")
+        }
+    }
+
+    // rewards increases which can no longer be correlated with any payment are dropped
+    db_client
+        .delete_nodes_rewards_deltas_before((from - ATTRIBUTION_WINDOW_SECS) * 1_000)
+        .await;
+
+    let totals = db_client.get_nodes_earnings_totals().await;
+    app_ctx
+        .nodes_metrics
+        .write()
+        .await
+        .set_nodes_earnings(totals);
+}
+
+// Split each payment among the nodes paying to its address which reported an increase of
+// their rewards around the time it was received. A single increase matching the amount paid
+// gets it all, otherwise it's split in proportion to each increase. Payments no node reported
+// an increase for are left unattributed. It returns the attributions along with the ids of
+// the rewards increases which were correlated with a payment.
+fn attribute_payments(
+    payments: &[EarningsExportRow],
+    deltas: &[NodeRewardsDelta],
+    nodes_addrs: &HashMap<NodeId, Address>,
+) -> (Vec<NodeEarning>, HashSet<i64>) {
+    let mut consumed = HashSet::new();
+    let mut earnings = Vec::new();
+
+    for payment in payments {
+        let Ok(address) = payment.address.parse::<Address>() else {
+            continue;
+        };
+        let candidates = deltas
+            .iter()
+            .filter(|d| {
+                !consumed.contains(&d.id)
+                    && nodes_addrs.get(&d.node_id) == Some(&address)
+                    && (d.timestamp / 1_000 - payment.timestamp).abs() <= ATTRIBUTION_WINDOW_SECS
+            })
+            .collect::<Vec<_>>();
+
+        let exact_match = candidates
+            .iter()
+            .filter(|d| d.amount == payment.amount)
+            .min_by_key(|d| (d.timestamp / 1_000 - payment.timestamp).abs());
+        let shares = match exact_match {
+            Some(d) => vec![(*d, payment.amount)],
+            None => {
+                let total: U256 = candidates.iter().map(|d| d.amount).sum();
+                if total.is_zero() {
+                    continue;
+                }
+                let mut remaining = payment.amount;
+                let mut shares = candidates
+                    .iter()
+                    .map(|d| {
+                        let share = payment.amount * d.amount / total;
+                        remaining -= share;
+                        (*d, share)
+                    })
+                    .collect::<Vec<_>>();
+                // the rounding remainder goes to the largest increase
+                if let Some((_, share)) = shares.iter_mut().max_by_key(|(d, _)| d.amount) {
+                    *share += remaining;
+                }
+                shares
+            }
+        };
+
+        let mut by_node = HashMap::<&NodeId, U256>::new();
+        for (delta, share) in shares {
+            consumed.insert(delta.id);
+            *by_node.entry(&delta.node_id).or_default() += share;
+        }
+        earnings.extend(by_node.into_iter().map(|(node_id, amount)| NodeEarning {
+            node_id: node_id.clone(),
+            address: payment.address.clone(),
+            amount,
+            block_number: payment.block_number,
+            timestamp: payment.timestamp,
+        }));
+    }
+
+    earnings.sort_by(|a, b| (a.block_number, &a.node_id).cmp(&(b.block_number, &b.node_id)));
+    (earnings, consumed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDR_1: &str = "0x1111111111111111111111111111111111111111";
+    const ADDR_2: &str = "0x2222222222222222222222222222222222222222";
+
+    fn node(id: &str) -> NodeId {
+        NodeId::new(id).unwrap()
+    }
+
+    fn payment(address: &str, timestamp: i64, amount: u64) -> EarningsExportRow {
+        EarningsExportRow {
+            address: address.parse::<Address>().unwrap().to_string(),
+            timestamp,
+            block_number: timestamp as u64,
+            amount: U256::from(amount),
+            price: None,
+        }
+    }
+
+    fn delta(id: i64, node_id: &str, timestamp: i64, amount: u64) -> NodeRewardsDelta {
+        NodeRewardsDelta {
+            id,
+            node_id: node(node_id),
+            amount: U256::from(amount),
+            timestamp: timestamp * 1_000,
+        }
+    }
+
+    #[test]
+    fn test_attribute_payments() {
+        let nodes_addrs = HashMap::from([
+            (node("aaaaaaaaaaaa"), ADDR_1.parse().unwrap()),
+            (node("bbbbbbbbbbbb"), ADDR_1.parse().unwrap()),
+            (node("cccccccccccc"), ADDR_2.parse().unwrap()),
+        ]);
+        let payments = [
+            // matches exactly the increase reported by node b
+            payment(ADDR_1, 10_000, 50),
+            // split among nodes a and b in proportion to their increases
+            payment(ADDR_1, 20_000, 100),
+            // the only increase is of a node paying to another address
+            payment(ADDR_1, 30_000, 70),
+        ];
+        let deltas = [
+            delta(1, "aaaaaaaaaaaa", 10_030, 20),
+            delta(2, "bbbbbbbbbbbb", 10_060, 50),
+            delta(3, "aaaaaaaaaaaa", 20_030, 10),
+            delta(4, "bbbbbbbbbbbb", 20_100, 30),
+            delta(5, "cccccccccccc", 30_010, 70),
+            // too late to be correlated with any payment
+            delta(6, "aaaaaaaaaaaa", 40_000, 70),
+        ];
+
+        let (earnings, consumed) = attribute_payments(&payments, &deltas, &nodes_addrs);
+        let summary = earnings
+            .iter()
+            .map(|e| (e.block_number, e.node_id.clone(), e.amount))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                (10_000, node("bbbbbbbbbbbb"), U256::from(50u64)),
+                (20_000, node("aaaaaaaaaaaa"), U256::from(25u64)),
+                (20_000, node("bbbbbbbbbbbb"), U256::from(75u64)),
+            ]
+        );
+        // the increase of node a around the first payment wasn't needed to explain it
+        assert_eq!(consumed, HashSet::from([2, 3, 4]));
+    }
+}
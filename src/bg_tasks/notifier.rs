@@ -0,0 +1,338 @@
+use crate::{
+    db_client::DbClient,
+    types::{
+        NodeInstanceInfo, NodeStatus, NotificationEvent, NotificationEventKind, NotificationTarget,
+    },
+};
+
+use leptos::logging;
+use lettre::{
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+    transport::smtp::authentication::Credentials,
+};
+use serde_json::json;
+use std::collections::{HashMap, hash_map::Entry};
+use thiserror::Error;
+use tokio::{
+    sync::broadcast,
+    time::{Duration, Instant, timeout},
+};
+
+// Base URL of the Telegram bot API.
+const TELEGRAM_API_URL: &str = "https://api.telegram.org";
+// Timeout when sending a notification through a channel.
+const NOTIFICATION_SEND_TIMEOUT: Duration = Duration::from_secs(20);
+// Identical events received within this window are sent only once,
+// e.g. to avoid flooding the channels with a node which keeps exiting.
+const DUPLICATED_EVENTS_WINDOW: Duration = Duration::from_secs(10 * 60);
+
+#[derive(Debug, Error)]
+pub enum NotificationError {
+    #[error(transparent)]
+    HttpError(#[from] reqwest::Error),
+    #[error("{0} responded with status {1}: {2}")]
+    ServiceError(&'static str, reqwest::StatusCode, String),
+    #[error("Invalid email settings: {0}")]
+    InvalidEmail(String),
+    #[error(transparent)]
+    SmtpError(#[from] lettre::transport::smtp::Error),
+    #[error("Timeout ({NOTIFICATION_SEND_TIMEOUT:?}) while sending notification")]
+    Timeout,
+}
+
+// Keeps track of the events recently sent so duplicated ones can be dropped.
+#[derive(Debug, Default)]
+struct RecentEvents(HashMap<(NotificationEventKind, String, String), Instant>);
+
+impl RecentEvents {
+    // Returns true if an identical event was already sent within the window,
+    // otherwise it's recorded as sent at the given instant.
+    fn is_duplicated(&mut self, event: &NotificationEvent, now: Instant) -> bool {
+        self.0
+            .retain(|_, sent_at| now.duration_since(*sent_at) < DUPLICATED_EVENTS_WINDOW);
+        let key = (event.kind, event.title.clone(), event.message.clone());
+        match self.0.entry(key) {
+            Entry::Occupied(_) => true,
+            Entry::Vacant(entry) => {
+                entry.insert(now);
+                false
+            }
+        }
+    }
+}
+
+// Whether the node stopped running without being requested by the user.
+fn is_failed_status(status: &NodeStatus) -> bool {
+    status.is_exited() || status.is_start_failed() || status.is_inactive_unknown()
+}
+
+// Build the event to notify for a node status transition, if the node
+// has just failed, or if it has just recovered from a failure.
+pub fn node_status_event(
+    prev_status: &NodeStatus,
+    info: &NodeInstanceInfo,
+) -> Option<NotificationEvent> {
+    let node = info.short_node_id();
+    let (title, message) = if !is_failed_status(prev_status) && is_failed_status(&info.status) {
+        (
+            format!("Node {node} is down"),
+            format!(
+                "Node {node} status changed from {prev_status} to {}",
+                info.status
+            ),
+        )
+    } else if is_failed_status(prev_status) && info.status.is_active() {
+        (
+            format!("Node {node} recovered"),
+            format!("Node {node} is active again after being {prev_status}"),
+        )
+    } else {
+        return None;
+    };
+
+    Some(
+        NotificationEvent::new(NotificationEventKind::NodeStatus, &title, &message)
+            .for_node(info.node_id.clone()),
+    )
+}
+
+// Send an event through a notification channel, according to the target service's API.
+pub async fn send_notification(
+    client: &reqwest::Client,
+    target: &NotificationTarget,
+    event: &NotificationEvent,
+) -> Result<(), NotificationError> {
+    match timeout(
+        NOTIFICATION_SEND_TIMEOUT,
+        send_to_target(client, target, event),
+    )
+    .await
+    {
+        Ok(res) => res,
+        Err(_) => Err(NotificationError::Timeout),
+    }
+}
+
+async fn send_to_target(
+    client: &reqwest::Client,
+    target: &NotificationTarget,
+    event: &NotificationEvent,
+) -> Result<(), NotificationError> {
+    let title = &event.title;
+    let message = &event.message;
+    let req = match target {
+        NotificationTarget::Webhook { url } => client.post(url).json(event),
+        NotificationTarget::Telegram { bot_token, chat_id } => client
+            .post(format!("{TELEGRAM_API_URL}/bot{bot_token}/sendMessage"))
+            .json(&json!({ "chat_id": chat_id, "text": format!("{title}\n{message}") })),
+        NotificationTarget::Ntfy { url, token } => {
+            let req = client
+                .post(url)
+                .header("Title", title)
+                .body(message.clone());
+            if token.is_empty() {
+                req
+            } else {
+                req.bearer_auth(token)
+            }
+        }
+        NotificationTarget::Gotify { url, token } => client
+            .post(format!("{}/message", url.trim_end_matches('/')))
+            .header("X-Gotify-Key", token)
+            .json(&json!({ "title": title, "message": message })),
+        NotificationTarget::Discord { url } => client
+            .post(url)
+            .json(&json!({ "content": format!("**{title}**\n{message}") })),
+        NotificationTarget::Slack { url } => client
+            .post(url)
+            .json(&json!({ "text": format!("*{title}*\n{message}") })),
+        NotificationTarget::Email {
+            smtp_host,
+            smtp_port,
+            username,
+            password,
+            from,
+            to,
+        } => {
+            return send_email(
+                smtp_host, *smtp_port, username, password, from, to, title, message,
+            )
+            .await;
+        }
+    };
+
+    let resp = req.send().await?;
+    let status = resp.status();
+    if status.is_success() {
+        Ok(())
+    } else {
+        let body = resp.text().await.unwrap_or_default();
+        Err(NotificationError::ServiceError(
+            target.kind_label(),
+            status,
+            body.chars().take(200).collect(),
+        ))
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn send_email(
+    smtp_host: &str,
+    smtp_port: u16,
+    username: &str,
+    password: &str,
+    from: &str,
+    to: &str,
+    subject: &str,
+    body: &str,
+) -> Result<(), NotificationError> {
+    let invalid = |err: &dyn std::fmt::Display| NotificationError::InvalidEmail(err.to_string());
+    let mut builder = Message::builder()
+        .from(from.parse().map_err(|e| invalid(&e))?)
+        .subject(subject);
+    for recipient in to.split(',').map(str::trim).filter(|r| !r.is_empty()) {
+        builder = builder.to(recipient.parse().map_err(|e| invalid(&e))?);
+    }
+    let email = builder.body(body.to_string()).map_err(|e| invalid(&e))?;
+
+    // Port 465 expects TLS from the start, any other port is upgraded with STARTTLS
+    let mut transport = if smtp_port == 465 {
+        AsyncSmtpTransport::<Tokio1Executor>::relay(smtp_host)?
+    } else {
+        AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(smtp_host)?
+    }
+    .port(smtp_port);
+    if !username.is_empty() {
+        transport =
+            transport.credentials(Credentials::new(username.to_string(), password.to_string()));
+    }
+
+    transport.build().send(email).await?;
+    Ok(())
+}
+
+// Task which receives the events from other tasks and sends them
+// through the enabled notification channels subscribed to them.
+pub async fn notifier_task(
+    db_client: DbClient,
+    mut events_rx: broadcast::Receiver<NotificationEvent>,
+) {
+    let client = reqwest::Client::new();
+    let mut recent_events = RecentEvents::default();
+
+    loop {
+        let event = match events_rx.recv().await {
+            Ok(event) => event,
+            Err(broadcast::error::RecvError::Lagged(n)) => {
+                logging::warn!("[WARN][BgTask] Dropped {n} notification events, lagging behind.");
+                continue;
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        };
+
+        if recent_events.is_duplicated(&event, Instant::now()) {
+            continue;
+        }
+
+        for channel in db_client
+            .get_notification_channels()
+            .await
+            .into_iter()
+            .filter(|c| c.is_subscribed_to(event.kind))
+        {
+            let client = client.clone();
+            let event = event.clone();
+            tokio::spawn(async move {
+                if let Err(err) = send_notification(&client, &channel.target, &event).await {
+                    logging::warn!(
+                        "[WARN][BgTask] Failed to send notification '{}' through channel '{}': {err}",
+                        event.title,
+                        channel.name
+                    );
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        bg_tasks::stub_http_server::StubHttpServer,
+        types::{InactiveReason, NodeId},
+    };
+
+    fn test_event() -> NotificationEvent {
+        NotificationEvent::new(
+            NotificationEventKind::BatchFailed,
+            "Batch failed",
+            "Batch 1 finished with 2 failures",
+        )
+    }
+
+    #[test]
+    fn test_duplicated_events_are_dropped_within_window() {
+        let mut recent = RecentEvents::default();
+        let now = Instant::now();
+        let event = test_event();
+        let mut other = test_event();
+        other.message = "Batch 2 finished with 1 failure".to_string();
+
+        assert!(!recent.is_duplicated(&event, now));
+        assert!(recent.is_duplicated(&event, now + Duration::from_secs(60)));
+        assert!(!recent.is_duplicated(&other, now + Duration::from_secs(60)));
+        assert!(!recent.is_duplicated(&event, now + DUPLICATED_EVENTS_WINDOW));
+    }
+
+    #[test]
+    fn test_node_status_events() {
+        let mut info = NodeInstanceInfo {
+            node_id: NodeId::random(),
+            status: NodeStatus::Inactive(InactiveReason::Exited("killed".to_string())),
+            ..Default::default()
+        };
+
+        let event = node_status_event(&NodeStatus::Active, &info).expect("node is down");
+        assert_eq!(event.kind, NotificationEventKind::NodeStatus);
+        assert_eq!(event.node_id, Some(info.node_id.clone()));
+        assert!(event.title.ends_with("is down"));
+
+        let stopped = NodeStatus::Inactive(InactiveReason::Stopped);
+        assert_eq!(node_status_event(&info.status.clone(), &info), None);
+        info.status = stopped.clone();
+        assert_eq!(node_status_event(&NodeStatus::Active, &info), None);
+        info.status = NodeStatus::Active;
+        assert_eq!(node_status_event(&stopped, &info), None);
+
+        let exited = NodeStatus::Inactive(InactiveReason::Unknown);
+        let event = node_status_event(&exited, &info).expect("node recovered");
+        assert!(event.title.ends_with("recovered"));
+    }
+
+    #[tokio::test]
+    async fn test_webhook_receives_event() -> Result<(), Box<dyn std::error::Error>> {
+        let server = StubHttpServer::bind().await?;
+        let target = NotificationTarget::Webhook {
+            url: server.url("/hook"),
+        };
+
+        let client = reqwest::Client::new();
+        let event = test_event();
+        let (sent, request) = tokio::join!(
+            send_notification(&client, &target, &event),
+            server.respond(200, "")
+        );
+        sent?;
+
+        let request = request?;
+        assert_eq!(
+            (request.method.as_str(), request.target.as_str()),
+            ("POST", "/hook")
+        );
+        let body: serde_json::Value = serde_json::from_str(&request.body)?;
+        assert_eq!(body["kind"], "BatchFailed");
+        assert_eq!(body["title"], "Batch failed");
+        Ok(())
+    }
+}
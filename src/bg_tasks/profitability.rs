@@ -0,0 +1,324 @@
+use super::token_prices::{TokenPriceClient, daily_token_prices};
+use crate::{
+    db_client::{DbClient, DbError},
+    types::{
+        AppSettings, EarningsExportRow, NodeId, NodeList, Profitability, ProfitabilityStats,
+        normalized_address,
+    },
+};
+
+use alloy_primitives::U256;
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashMap};
+
+// Bytes in a GB, as disk usage is displayed across the app.
+const BYTES_PER_GB: f64 = 1_073_741_824.0;
+// Average number of hours in a month.
+const HOURS_PER_MONTH: f64 = 730.0;
+// Length of each of the usage samples, as they are hourly rollups of the nodes metrics.
+const SAMPLE_HOURS: f64 = 1.0;
+
+/// Average CPU and memory usage of a node within one hour, rolled up from its metrics.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ResourceUsageSample {
+    pub node_id: String,
+    /// Timestamp (in millis) the hour starts at
+    pub timestamp: i64,
+    pub cpu_usage: Option<f64>,
+    pub mem_used_mb: Option<f64>,
+}
+
+/// Calculate the profitability of the nodes over the last given hours, from the history of
+/// their resources usage and the earnings of their rewards addresses valued at the token price.
+pub async fn profitability(
+    db_client: &DbClient,
+    settings: &AppSettings,
+    nodes: &NodeList,
+    period_hours: u32,
+    now: DateTime<Utc>,
+) -> Result<Profitability, DbError> {
+    let since = now.timestamp() - period_hours as i64 * 3_600;
+    let usage = db_client
+        .get_nodes_resources_usage(since * 1_000, now.timestamp_millis())
+        .await?;
+    let mut payments = db_client
+        .get_earnings_in_range(None, since, now.timestamp())
+        .await?;
+
+    if !settings.token_price_api_url.is_empty() {
+        let price_client =
+            TokenPriceClient::new(&settings.token_price_api_url, &settings.fiat_currency);
+        let days = payments.iter().map(EarningsExportRow::date).collect();
+        let prices = daily_token_prices(db_client, &price_client, &days).await;
+        for payment in payments.iter_mut() {
+            payment.price = prices.get(&payment.date()).copied();
+        }
+    }
+
+    Ok(calc_profitability(
+        settings,
+        period_hours,
+        nodes,
+        &usage,
+        &payments,
+    ))
+}
+
+// Calculate the cost of each node as per the cost model set in the settings, and compare it
+// with the earnings of its rewards address, split evenly among all the nodes sharing it.
+fn calc_profitability(
+    settings: &AppSettings,
+    period_hours: u32,
+    nodes: &NodeList,
+    usage: &[ResourceUsageSample],
+    payments: &[EarningsExportRow],
+) -> Profitability {
+    // usage samples of each node, skipping those of nodes which no longer exist
+    let mut samples_by_id = HashMap::<&str, Vec<&ResourceUsageSample>>::new();
+    for sample in usage {
+        samples_by_id
+            .entry(sample.node_id.as_str())
+            .or_default()
+            .push(sample);
+    }
+    let mut node_samples = HashMap::<&NodeId, Vec<&ResourceUsageSample>>::new();
+    for (id, samples) in samples_by_id {
+        if let Some(node_id) = nodes.keys().find(|n| id.starts_with(&n.to_string())) {
+            node_samples.entry(node_id).or_default().extend(samples);
+        }
+    }
+
+    // total memory used, and number of nodes running, within each hour
+    let mut hours = BTreeMap::<i64, (f64, usize)>::new();
+    for sample in node_samples.values().flatten() {
+        let (mem, count) = hours.entry(sample.timestamp).or_default();
+        *mem += sample.mem_used_mb.unwrap_or_default();
+        *count += 1;
+    }
+
+    // energy (in Wh) consumed by a node within an hour
+    let sample_wh = |sample: &ResourceUsageSample| {
+        if settings.host_power_watts > 0.0 {
+            let (total_mem, count) = hours.get(&sample.timestamp).copied().unwrap_or((0.0, 1));
+            let share = if total_mem > 0.0 {
+                sample.mem_used_mb.unwrap_or_default() / total_mem
+            } else {
+                1.0 / count as f64
+            };
+            settings.host_power_watts * share * SAMPLE_HOURS
+        } else {
+            settings.watts_per_cpu_percent * sample.cpu_usage.unwrap_or_default() * SAMPLE_HOURS
+        }
+    };
+
+    // tokens earned by each address, and their value
+    let priced = !settings.token_price_api_url.is_empty();
+    let mut earned_by_addr = HashMap::<String, (U256, f64)>::new();
+    for payment in payments {
+        if let Some(addr) = normalized_address(&payment.address) {
+            let (earned, value) = earned_by_addr.entry(addr).or_default();
+            *earned += payment.amount;
+            *value += payment.fiat_value().unwrap_or_default();
+        }
+    }
+    let mut nodes_by_addr = HashMap::<String, u64>::new();
+    for info in nodes.values() {
+        if let Some(addr) = info.rewards_addr.as_deref().and_then(normalized_address) {
+            *nodes_by_addr.entry(addr).or_default() += 1;
+        }
+    }
+
+    let mut nodes_stats = nodes
+        .values()
+        .map(|info| {
+            let samples = node_samples.get(&info.node_id).cloned().unwrap_or_default();
+            let hours_running = samples.len() as f64 * SAMPLE_HOURS;
+            let energy_kwh = samples.iter().map(|s| sample_wh(s)).sum::<f64>() / 1_000.0;
+            let disk_usage = info.disk_usage.unwrap_or_default();
+            let (earned, earned_value) = info
+                .rewards_addr
+                .as_deref()
+                .and_then(normalized_address)
+                .and_then(|addr| {
+                    let (earned, value) = earned_by_addr.get(&addr)?;
+                    let n = nodes_by_addr.get(&addr).copied().unwrap_or(1).max(1);
+                    Some((*earned / U256::from(n), value / n as f64))
+                })
+                .unwrap_or_default();
+
+            ProfitabilityStats {
+                node_id: Some(info.node_id.clone()),
+                hours_running,
+                avg_cpu_usage: average(samples.iter().filter_map(|s| s.cpu_usage)),
+                avg_mem_used_mb: average(samples.iter().filter_map(|s| s.mem_used_mb)),
+                disk_usage,
+                records: info.records.unwrap_or_default(),
+                energy_kwh,
+                energy_cost: energy_kwh * settings.electricity_price_per_kwh,
+                disk_cost: disk_cost(settings, disk_usage, hours_running),
+                earned,
+                earned_value: priced.then_some(earned_value),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let host_hours = hours.len() as f64 * SAMPLE_HOURS;
+    let all_samples = node_samples.values().flatten().collect::<Vec<_>>();
+    let per_hour = |total: f64| (host_hours > 0.0).then(|| total / host_hours);
+    let host = ProfitabilityStats {
+        node_id: None,
+        hours_running: host_hours,
+        avg_cpu_usage: per_hour(all_samples.iter().filter_map(|s| s.cpu_usage).sum()),
+        avg_mem_used_mb: per_hour(all_samples.iter().filter_map(|s| s.mem_used_mb).sum()),
+        disk_usage: nodes_stats.iter().map(|s| s.disk_usage).sum(),
+        records: nodes_stats.iter().map(|s| s.records).sum(),
+        energy_kwh: nodes_stats.iter().map(|s| s.energy_kwh).sum(),
+        energy_cost: nodes_stats.iter().map(|s| s.energy_cost).sum(),
+        disk_cost: nodes_stats.iter().map(|s| s.disk_cost).sum(),
+        earned: earned_by_addr.values().map(|(earned, _)| earned).sum(),
+        earned_value: priced.then(|| earned_by_addr.values().map(|(_, value)| value).sum()),
+    };
+
+    nodes_stats.sort_by(|a, b| {
+        b.profit()
+            .unwrap_or(f64::MIN)
+            .total_cmp(&a.profit().unwrap_or(f64::MIN))
+            .then(b.earned.cmp(&a.earned))
+            .then(a.total_cost().total_cmp(&b.total_cost()))
+    });
+
+    Profitability {
+        currency: settings.fiat_currency.to_lowercase(),
+        period_hours,
+        host,
+        nodes: nodes_stats,
+    }
+}
+
+// Cost of keeping the given amount of bytes stored for a number of hours.
+fn disk_cost(settings: &AppSettings, disk_usage: u64, hours: f64) -> f64 {
+    disk_usage as f64 / BYTES_PER_GB * settings.disk_cost_per_gb_month * hours / HOURS_PER_MONTH
+}
+
+fn average(values: impl Iterator<Item = f64>) -> Option<f64> {
+    let (sum, count) = values.fold((0.0, 0), |(sum, count), v| (sum + v, count + 1));
+    (count > 0).then(|| sum / count as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::NodeInstanceInfo;
+
+    const ADDR: &str = "0x1111111111111111111111111111111111111111";
+    const HOUR_MILLIS: i64 = 3_600_000;
+    const A: &str = "aaaaaaaaaaaa";
+    const B: &str = "bbbbbbbbbbbb";
+
+    fn node(id: &str, rewards_addr: &str, disk_usage: u64) -> NodeInstanceInfo {
+        NodeInstanceInfo {
+            node_id: NodeId::new(id).unwrap(),
+            rewards_addr: Some(rewards_addr.to_string()),
+            disk_usage: Some(disk_usage),
+            records: Some(100),
+            ..Default::default()
+        }
+    }
+
+    fn sample(node_id: &str, hour: i64, cpu: f64, mem: f64) -> ResourceUsageSample {
+        ResourceUsageSample {
+            node_id: node_id.to_string(),
+            timestamp: hour * HOUR_MILLIS,
+            cpu_usage: Some(cpu),
+            mem_used_mb: Some(mem),
+        }
+    }
+
+    fn nodes() -> NodeList {
+        [node(A, ADDR, 0), node(B, ADDR, 730 * 1_073_741_824)]
+            .into_iter()
+            .map(|n| (n.node_id.clone(), n))
+            .collect()
+    }
+
+    #[test]
+    fn test_calc_profitability_from_cpu_usage() {
+        let settings = AppSettings {
+            electricity_price_per_kwh: 0.5,
+            host_power_watts: 0.0,
+            watts_per_cpu_percent: 1.0,
+            disk_cost_per_gb_month: 0.01,
+            token_price_api_url: "http://prices".to_string(),
+            ..Default::default()
+        };
+        let usage = vec![
+            sample(A, 0, 100.0, 300.0),
+            sample(A, 1, 300.0, 300.0),
+            sample(B, 1, 100.0, 100.0),
+            // a node which no longer exists
+            sample("cccccccccccc", 1, 1_000.0, 100.0),
+        ];
+        let payments = vec![EarningsExportRow {
+            address: ADDR.to_lowercase(),
+            timestamp: 0,
+            block_number: 1,
+            amount: U256::from(4_000_000_000_000_000_000u128),
+            price: Some(0.25),
+        }];
+
+        let p = calc_profitability(&settings, 24, &nodes(), &usage, &payments);
+        assert_eq!(p.nodes.len(), 2);
+        let a = p
+            .nodes
+            .iter()
+            .find(|n| n.node_id == NodeId::new(A).ok())
+            .unwrap();
+        assert_eq!(a.hours_running, 2.0);
+        assert_eq!(a.avg_cpu_usage, Some(200.0));
+        assert_eq!(a.energy_kwh, 0.4);
+        assert_eq!(a.energy_cost, 0.2);
+        assert_eq!(a.disk_cost, 0.0);
+        assert_eq!(a.earned, U256::from(2_000_000_000_000_000_000u128));
+        assert_eq!(a.earned_value, Some(0.5));
+
+        let b = &p.nodes[0];
+        assert_eq!(b.node_id, NodeId::new(B).ok());
+        assert_eq!(b.energy_cost, 0.05);
+        assert!((b.disk_cost - 0.01).abs() < 1e-9);
+
+        assert_eq!(p.host.hours_running, 2.0);
+        assert_eq!(p.host.avg_cpu_usage, Some(250.0));
+        assert_eq!(p.host.records, 200);
+        assert!((p.host.total_cost() - 0.26).abs() < 1e-9);
+        assert_eq!(p.host.earned_value, Some(1.0));
+    }
+
+    #[test]
+    fn test_calc_profitability_from_host_power() {
+        let settings = AppSettings {
+            electricity_price_per_kwh: 1.0,
+            host_power_watts: 100.0,
+            token_price_api_url: String::new(),
+            ..Default::default()
+        };
+        let usage = vec![
+            sample(A, 0, 0.0, 0.0),
+            sample(A, 1, 10.0, 300.0),
+            sample(B, 1, 10.0, 100.0),
+        ];
+
+        let p = calc_profitability(&settings, 24, &nodes(), &usage, &[]);
+        let energy = |id: &str| {
+            p.nodes
+                .iter()
+                .find(|n| n.node_id == Some(NodeId::new(id).unwrap()))
+                .map(|n| n.energy_kwh)
+        };
+        // alone on the first hour, and 3/4 of the memory used on the second one
+        assert_eq!(energy(A), Some(0.175));
+        assert_eq!(energy(B), Some(0.025));
+        assert!((p.host.energy_kwh - 0.2).abs() < 1e-9);
+        assert_eq!(p.host.earned_value, None);
+        assert_eq!(p.host.roi(), None);
+    }
+}
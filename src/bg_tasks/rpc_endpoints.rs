@@ -0,0 +1,578 @@
+use crate::types::RpcEndpointHealth;
+
+use alloy::{
+    providers::{Provider, RootProvider},
+    rpc::{
+        client::RpcClient,
+        json_rpc::{RequestPacket, ResponsePacket, RpcError},
+    },
+    transports::{
+        TransportError, TransportErrorKind, TransportFut, http::reqwest, utils::guess_local_url,
+    },
+};
+use chrono::{DateTime, Utc};
+use leptos::logging;
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+use thiserror::Error;
+use tokio::{
+    sync::RwLock,
+    time::{Duration, Instant, timeout},
+};
+use tower::Service;
+use url::Url;
+
+// Time an endpoint is not used for after it rate-limited us without telling us how long to wait
+// for, doubled on each consecutive rate-limit.
+const RATE_LIMIT_BACKOFF: Duration = Duration::from_secs(30);
+// Maximum time an endpoint is not used for after it rate-limited us.
+const MAX_RATE_LIMIT_BACKOFF: Duration = Duration::from_secs(15 * 60);
+// Timeout when checking the health of an endpoint.
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Error)]
+pub enum RpcEndpointsError {
+    #[error("No L2 network RPC endpoint available, they are all invalid or rate-limiting us")]
+    NoEndpointAvailable,
+    #[error("RPC endpoint {url} failed: {msg}")]
+    RequestFailed { url: String, msg: String },
+    #[error("RPC endpoint {url} rejected the request: {msg}")]
+    RequestRejected { url: String, msg: String },
+}
+
+/// Error of an operation sent to the RPC endpoints.
+#[derive(Debug, Error)]
+pub enum RpcCallError {
+    #[error(transparent)]
+    Transport(#[from] TransportError),
+    #[error(transparent)]
+    Contract(#[from] alloy::contract::Error),
+    #[error("timeout after {0:?}")]
+    Timeout(Duration),
+}
+
+impl RpcCallError {
+    // How the failure of the operation is to be handled.
+    fn failure(&self) -> Failure {
+        match self {
+            Self::Transport(err) | Self::Contract(alloy::contract::Error::TransportError(err)) => {
+                transport_failure(err)
+            }
+            // the contract response couldn't be decoded, e.g. it reverted or doesn't exist
+            Self::Contract(_) => Failure::Request,
+            Self::Timeout(_) => Failure::Endpoint,
+        }
+    }
+}
+
+// How a failed request is handled.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Failure {
+    // The request itself was rejected, e.g. for exceeding the block range or result size limits
+    // of the endpoint, or for having invalid params, so it's returned to the caller as is.
+    Request,
+    // The endpoint couldn't serve the request, so it's failed over to the next one.
+    Endpoint,
+    // The endpoint is rate-limiting us, so it's failed over to the next one and not used for the
+    // time it asked us to wait for, if any, or otherwise an exponential backoff.
+    RateLimited(Option<Duration>),
+}
+
+// Error returned by the transport when the endpoint responds with HTTP status 429.
+#[derive(Debug, Error)]
+#[error("HTTP error 429 with body: {body}")]
+struct TooManyRequests {
+    retry_after: Option<Duration>,
+    body: String,
+}
+
+#[derive(Debug)]
+struct EndpointState {
+    url: Url,
+    health: RpcEndpointHealth,
+    // Number of consecutive times it rate-limited us
+    rate_limits: u32,
+    backoff_until: Option<Instant>,
+}
+
+impl EndpointState {
+    fn is_backing_off(&self, now: Instant) -> bool {
+        self.backoff_until.is_some_and(|until| until > now)
+    }
+}
+
+/// L2 network RPC endpoints in priority order, shared by all the tasks querying the chain.
+/// Requests are sent to the endpoint with the highest priority which is healthy and not
+/// rate-limiting us, failing over to the next ones when they fail, and back to it once it's
+/// found healthy again by the periodic health checks.
+#[derive(Clone, Debug, Default)]
+pub struct RpcEndpoints {
+    endpoints: Arc<RwLock<Vec<EndpointState>>>,
+    client: reqwest::Client,
+}
+
+impl RpcEndpoints {
+    pub fn new(urls: &[String]) -> Self {
+        Self {
+            endpoints: Arc::new(RwLock::new(endpoints_states(urls, Vec::new()))),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Replace the list of endpoints, keeping the health of those which were already in it.
+    pub async fn set_urls(&self, urls: &[String]) {
+        let mut endpoints = self.endpoints.write().await;
+        let current = std::mem::take(&mut *endpoints);
+        *endpoints = endpoints_states(urls, current);
+    }
+
+    /// Health of each of the endpoints, in priority order.
+    pub async fn health(&self) -> Vec<RpcEndpointHealth> {
+        let endpoints = self.endpoints.read().await;
+        let active = select_endpoint(&endpoints, &[], Instant::now());
+        endpoints
+            .iter()
+            .map(|e| RpcEndpointHealth {
+                active: Some(&e.url) == active.as_ref(),
+                ..e.health.clone()
+            })
+            .collect()
+    }
+
+    /// Send a request with the given operation, failing over to the next available endpoint
+    /// as long as the endpoints fail to serve it, until it succeeds or all of them have been
+    /// tried. If the request is rejected by an endpoint, e.g. for exceeding its limits,
+    /// the error is returned right away without trying the other endpoints.
+    pub async fn call<T, E, F, Fut>(&self, op: F) -> Result<T, RpcEndpointsError>
+    where
+        E: Into<RpcCallError>,
+        F: Fn(RootProvider) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let mut tried = Vec::new();
+        let mut last_err = RpcEndpointsError::NoEndpointAvailable;
+        loop {
+            let next = select_endpoint(&self.endpoints.read().await, &tried, Instant::now());
+            let Some(url) = next else {
+                return Err(last_err);
+            };
+
+            match op(self.provider(&url)).await.map_err(Into::into) {
+                Ok(res) => {
+                    self.report_success(&url, None).await;
+                    return Ok(res);
+                }
+                Err(err) => {
+                    let msg = err.to_string();
+                    let failure = err.failure();
+                    if failure == Failure::Request {
+                        return Err(RpcEndpointsError::RequestRejected {
+                            url: url.to_string(),
+                            msg,
+                        });
+                    }
+                    logging::warn!("[WARN][RPC] Request to RPC endpoint {url} failed: {msg}");
+                    self.report_failure(&url, &msg, failure).await;
+                    last_err = RpcEndpointsError::RequestFailed {
+                        url: url.to_string(),
+                        msg,
+                    };
+                }
+            }
+            tried.push(url);
+        }
+    }
+
+    /// Check the health of all endpoints which are not rate-limiting us
+    /// by querying their latest block number.
+    pub async fn check_health(&self) {
+        let now = Instant::now();
+        let urls = self
+            .endpoints
+            .read()
+            .await
+            .iter()
+            .filter(|e| !e.is_backing_off(now))
+            .map(|e| e.url.clone())
+            .collect::<Vec<_>>();
+
+        for url in urls {
+            let provider = self.provider(&url);
+            let start = Instant::now();
+            match timeout(HEALTH_CHECK_TIMEOUT, provider.get_block_number()).await {
+                Ok(Ok(block_number)) => {
+                    self.report_success(&url, Some((start.elapsed(), block_number)))
+                        .await
+                }
+                Ok(Err(err)) => {
+                    let failure = transport_failure(&err);
+                    self.report_failure(&url, &err.to_string(), failure).await
+                }
+                Err(_) => {
+                    let err = RpcCallError::Timeout(HEALTH_CHECK_TIMEOUT);
+                    self.report_failure(&url, &err.to_string(), err.failure())
+                        .await
+                }
+            }
+        }
+    }
+
+    // Provider sending the requests to the given endpoint.
+    fn provider(&self, url: &Url) -> RootProvider {
+        let transport = HttpTransport {
+            client: self.client.clone(),
+            url: url.clone(),
+        };
+        RootProvider::new(RpcClient::new(transport, guess_local_url(url.as_str())))
+    }
+
+    async fn report_success(&self, url: &Url, check: Option<(Duration, u64)>) {
+        let mut endpoints = self.endpoints.write().await;
+        if let Some(e) = endpoints.iter_mut().find(|e| &e.url == url) {
+            if e.health.healthy == Some(false) {
+                logging::log!("[RPC] RPC endpoint {url} is healthy again.");
+            }
+            e.health.healthy = Some(true);
+            e.health.last_error = None;
+            e.health.last_checked = Some(Utc::now().timestamp());
+            e.health.backoff_until = None;
+            e.rate_limits = 0;
+            e.backoff_until = None;
+            if let Some((latency, block_number)) = check {
+                e.health.latency_millis = Some(latency.as_millis() as u64);
+                e.health.block_number = Some(block_number);
+            }
+        }
+    }
+
+    async fn report_failure(&self, url: &Url, err: &str, failure: Failure) {
+        let mut endpoints = self.endpoints.write().await;
+        if let Some(e) = endpoints.iter_mut().find(|e| &e.url == url) {
+            e.health.healthy = Some(false);
+            e.health.last_error = Some(err.to_string());
+            e.health.last_checked = Some(Utc::now().timestamp());
+            if let Failure::RateLimited(retry_after) = failure {
+                let backoff = retry_after
+                    .unwrap_or_else(|| rate_limit_backoff(e.rate_limits))
+                    .min(MAX_RATE_LIMIT_BACKOFF);
+                e.rate_limits += 1;
+                e.backoff_until = Some(Instant::now() + backoff);
+                e.health.backoff_until = Some(Utc::now().timestamp() + backoff.as_secs() as i64);
+                logging::warn!(
+                    "[WARN][RPC] RPC endpoint {url} is rate-limiting us, not using it for the next {backoff:?}."
+                );
+            }
+        }
+    }
+}
+
+// HTTP transport for the requests sent to an endpoint, which, unlike the default one,
+// keeps the time the endpoint asks us to wait for when it's rate-limiting us.
+#[derive(Clone, Debug)]
+struct HttpTransport {
+    client: reqwest::Client,
+    url: Url,
+}
+
+impl HttpTransport {
+    async fn send(self, req: RequestPacket) -> Result<ResponsePacket, TransportError> {
+        let resp = self
+            .client
+            .post(self.url)
+            .json(&req)
+            .send()
+            .await
+            .map_err(TransportErrorKind::custom)?;
+        let status = resp.status();
+        let retry_after = resp
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| parse_retry_after(value, Utc::now()));
+        let body = resp.bytes().await.map_err(TransportErrorKind::custom)?;
+
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(TransportErrorKind::custom(TooManyRequests {
+                retry_after,
+                body: String::from_utf8_lossy(&body).into_owned(),
+            }));
+        }
+        if !status.is_success() {
+            return Err(TransportErrorKind::http_error(
+                status.as_u16(),
+                String::from_utf8_lossy(&body).into_owned(),
+            ));
+        }
+
+        serde_json::from_slice(&body)
+            .map_err(|err| TransportError::deser_err(err, String::from_utf8_lossy(&body)))
+    }
+}
+
+impl Service<RequestPacket> for HttpTransport {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: RequestPacket) -> Self::Future {
+        Box::pin(self.clone().send(req))
+    }
+}
+
+// How the failure of a request with the given transport error is to be handled.
+fn transport_failure(err: &TransportError) -> Failure {
+    match err {
+        RpcError::Transport(TransportErrorKind::HttpError(err)) if err.status >= 500 => {
+            Failure::Endpoint
+        }
+        RpcError::Transport(TransportErrorKind::HttpError(_)) => Failure::Request,
+        RpcError::Transport(TransportErrorKind::Custom(err)) => {
+            match err.downcast_ref::<TooManyRequests>() {
+                Some(err) => Failure::RateLimited(err.retry_after),
+                None => Failure::Endpoint,
+            }
+        }
+        // the endpoint couldn't be reached, or its response is not a JSON-RPC one
+        RpcError::Transport(_) | RpcError::DeserError { .. } => Failure::Endpoint,
+        RpcError::ErrorResp(_)
+        | RpcError::NullResp
+        | RpcError::UnsupportedFeature(_)
+        | RpcError::LocalUsageError(_)
+        | RpcError::SerError(_) => Failure::Request,
+    }
+}
+
+// Time to wait for set in a Retry-After header, either as a number of seconds or as a date.
+fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    if let Ok(secs) = value.trim().parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let date = DateTime::parse_from_rfc2822(value.trim()).ok()?;
+    (date.with_timezone(&Utc) - now).to_std().ok()
+}
+
+// States of the endpoints with the given URLs, reusing the current state of those already known.
+fn endpoints_states(urls: &[String], mut current: Vec<EndpointState>) -> Vec<EndpointState> {
+    let mut endpoints = Vec::new();
+    for url in urls {
+        let url = match url.parse::<Url>() {
+            Ok(url) => url,
+            Err(err) => {
+                logging::warn!("[WARN][RPC] Ignoring invalid RPC endpoint URL '{url}': {err}");
+                continue;
+            }
+        };
+        if endpoints.iter().any(|e: &EndpointState| e.url == url) {
+            continue;
+        }
+        let mut state = match current.iter().position(|e| e.url == url) {
+            Some(i) => current.swap_remove(i),
+            None => EndpointState {
+                health: RpcEndpointHealth {
+                    url: url.to_string(),
+                    ..Default::default()
+                },
+                url,
+                rate_limits: 0,
+                backoff_until: None,
+            },
+        };
+        state.health.priority = endpoints.len();
+        endpoints.push(state);
+    }
+    endpoints
+}
+
+// Endpoint with the highest priority among those not tried yet and not rate-limiting us,
+// preferring the ones which haven't failed on their last request.
+fn select_endpoint(endpoints: &[EndpointState], tried: &[Url], now: Instant) -> Option<Url> {
+    let mut available = endpoints
+        .iter()
+        .filter(|e| !tried.contains(&e.url) && !e.is_backing_off(now));
+    let first = available.clone().next();
+    available
+        .find(|e| e.health.healthy != Some(false))
+        .or(first)
+        .map(|e| e.url.clone())
+}
+
+// Time to back off after the endpoint rate-limited us the given number of consecutive times before.
+fn rate_limit_backoff(prev_rate_limits: u32) -> Duration {
+    RATE_LIMIT_BACKOFF
+        .saturating_mul(2u32.saturating_pow(prev_rate_limits))
+        .min(MAX_RATE_LIMIT_BACKOFF)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::rpc::json_rpc::ErrorPayload;
+
+    fn urls() -> Vec<String> {
+        vec![
+            "https://primary.example/rpc".to_string(),
+            "not a url".to_string(),
+            "https://fallback.example/rpc".to_string(),
+        ]
+    }
+
+    fn too_many_requests(retry_after: Option<Duration>) -> TransportError {
+        TransportErrorKind::custom(TooManyRequests {
+            retry_after,
+            body: "Too Many Requests".to_string(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_failover_and_back() {
+        let rpc = RpcEndpoints::new(&urls());
+        let primary = "https://primary.example/rpc".parse::<Url>().unwrap();
+        let fallback = "https://fallback.example/rpc".parse::<Url>().unwrap();
+
+        let health = rpc.health().await;
+        assert_eq!(health.len(), 2);
+        assert_eq!(health[1].priority, 1);
+        assert!(health[0].active);
+
+        // fails over when the primary fails, and back once it's healthy again
+        rpc.report_failure(&primary, "connection refused", Failure::Endpoint)
+            .await;
+        let health = rpc.health().await;
+        assert_eq!(health[0].healthy, Some(false));
+        assert!(health[1].active);
+        rpc.report_success(&primary, Some((Duration::from_millis(80), 123)))
+            .await;
+        let health = rpc.health().await;
+        assert!(health[0].active);
+        assert_eq!(health[0].block_number, Some(123));
+
+        // all of them unhealthy, the one with the highest priority is still tried
+        rpc.report_failure(&primary, "connection refused", Failure::Endpoint)
+            .await;
+        rpc.report_failure(&fallback, "connection refused", Failure::Endpoint)
+            .await;
+        assert!(rpc.health().await[0].active);
+
+        // rate-limited ones are not used until their backoff expires
+        rpc.report_failure(&primary, "HTTP error 429", Failure::RateLimited(None))
+            .await;
+        let health = rpc.health().await;
+        assert!(health[0].backoff_until.is_some());
+        assert!(health[1].active);
+        let retry_after = Some(Duration::from_secs(5));
+        rpc.report_failure(
+            &fallback,
+            "HTTP error 429",
+            Failure::RateLimited(retry_after),
+        )
+        .await;
+        let health = rpc.health().await;
+        assert!(!health.iter().any(|e| e.active));
+        let backoff = health[1].backoff_until.unwrap() - Utc::now().timestamp();
+        assert!((4..=5).contains(&backoff));
+        let res = rpc.call(|_| async { Ok::<_, RpcCallError>(()) }).await;
+        assert!(matches!(res, Err(RpcEndpointsError::NoEndpointAvailable)));
+
+        // health is kept when the list is updated
+        rpc.set_urls(&["https://fallback.example/rpc".to_string()])
+            .await;
+        let health = rpc.health().await;
+        assert_eq!(health.len(), 1);
+        assert_eq!(health[0].priority, 0);
+        assert!(health[0].backoff_until.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_rejected_requests_are_not_failed_over() {
+        let rpc = RpcEndpoints::new(&urls());
+
+        let res = rpc
+            .call(|_| async {
+                Err::<(), _>(TransportError::ErrorResp(ErrorPayload {
+                    code: -32602,
+                    message: "query exceeds max block range 10000".into(),
+                    data: None,
+                }))
+            })
+            .await;
+        assert!(matches!(
+            res,
+            Err(RpcEndpointsError::RequestRejected { .. })
+        ));
+        assert!(rpc.health().await.iter().all(|e| e.healthy.is_none()));
+
+        let res = rpc
+            .call(|_| async { Err::<(), _>(TransportErrorKind::http_error(503, String::new())) })
+            .await;
+        assert!(matches!(res, Err(RpcEndpointsError::RequestFailed { .. })));
+        assert!(rpc.health().await.iter().all(|e| e.healthy == Some(false)));
+    }
+
+    #[test]
+    fn test_failures_classification() {
+        let failure = |err: TransportError| RpcCallError::from(err).failure();
+        assert_eq!(
+            failure(TransportErrorKind::http_error(503, String::new())),
+            Failure::Endpoint
+        );
+        assert_eq!(
+            failure(TransportErrorKind::http_error(413, String::new())),
+            Failure::Request
+        );
+        assert_eq!(
+            failure(TransportErrorKind::custom_str("connection refused")),
+            Failure::Endpoint
+        );
+        assert_eq!(failure(too_many_requests(None)), Failure::RateLimited(None));
+        assert_eq!(
+            failure(too_many_requests(Some(Duration::from_secs(7)))),
+            Failure::RateLimited(Some(Duration::from_secs(7)))
+        );
+        assert_eq!(
+            failure(TransportError::ErrorResp(ErrorPayload {
+                code: -32005,
+                message: "query returned more than 10000 results".into(),
+                data: None,
+            })),
+            Failure::Request
+        );
+        assert_eq!(
+            RpcCallError::Contract(too_many_requests(None).into()).failure(),
+            Failure::RateLimited(None)
+        );
+        assert_eq!(
+            RpcCallError::Timeout(HEALTH_CHECK_TIMEOUT).failure(),
+            Failure::Endpoint
+        );
+    }
+
+    #[test]
+    fn test_rate_limit_backoff() {
+        let now = DateTime::parse_from_rfc3339("2025-10-21T07:28:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            parse_retry_after("120", now),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            parse_retry_after("Tue, 21 Oct 2025 07:29:30 GMT", now),
+            Some(Duration::from_secs(90))
+        );
+        assert_eq!(
+            parse_retry_after("Tue, 21 Oct 2025 07:27:00 GMT", now),
+            None
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+        assert_eq!(rate_limit_backoff(0), Duration::from_secs(30));
+        assert_eq!(rate_limit_backoff(2), Duration::from_secs(120));
+        assert_eq!(rate_limit_backoff(10), MAX_RATE_LIMIT_BACKOFF);
+        assert_eq!(rate_limit_backoff(u32::MAX), MAX_RATE_LIMIT_BACKOFF);
+    }
+}
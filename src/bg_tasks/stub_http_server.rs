@@ -0,0 +1,137 @@
+use http::StatusCode;
+use std::{io, net::SocketAddr};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+
+/// HTTP request received by the stub server.
+#[derive(Debug)]
+pub struct StubRequest {
+    pub method: String,
+    /// Path and query of the request
+    pub target: String,
+    /// Headers with their names in lowercase
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl StubRequest {
+    /// Value of the header with the given name, if it was sent.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Stub HTTP server, listening on a random local port, used by tests to check
+/// the requests sent to external services and reply to them.
+pub struct StubHttpServer {
+    listener: TcpListener,
+    addr: SocketAddr,
+}
+
+impl StubHttpServer {
+    pub async fn bind() -> io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        Ok(Self { listener, addr })
+    }
+
+    /// Port the stub server is listening on.
+    pub fn port(&self) -> u16 {
+        self.addr.port()
+    }
+
+    /// URL of the given path and query on the stub server.
+    pub fn url(&self, path_and_query: &str) -> String {
+        format!("http://{}{path_and_query}", self.addr)
+    }
+
+    /// Accept a single request, reading it whole before replying with the given
+    /// status and body, and closing the connection afterwards.
+    pub async fn respond(&self, status: u16, body: &str) -> io::Result<StubRequest> {
+        self.accept().await?.reply(status, body).await
+    }
+
+    /// Accept a single request, reading it whole, leaving it pending to be replied.
+    pub async fn accept(&self) -> io::Result<PendingRequest> {
+        let (mut stream, _) = self.listener.accept().await?;
+
+        // read until the end of the headers, and then the body as long as its length says
+        let mut received = Vec::new();
+        let mut buf = [0u8; 4096];
+        let headers_end = loop {
+            if let Some(pos) = received.windows(4).position(|w| w == b"\r\n\r\n") {
+                break pos + 4;
+            }
+            let n = stream.read(&mut buf).await?;
+            if n == 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "connection closed before the end of the request headers",
+                ));
+            }
+            received.extend_from_slice(&buf[..n]);
+        };
+        let head = String::from_utf8_lossy(&received[..headers_end]).to_string();
+        let mut lines = head.lines();
+        let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+        let method = request_line.next().unwrap_or_default().to_string();
+        let target = request_line.next().unwrap_or_default().to_string();
+        let headers = lines
+            .filter_map(|line| line.split_once(':'))
+            .map(|(name, value)| (name.trim().to_lowercase(), value.trim().to_string()))
+            .collect::<Vec<_>>();
+        let content_length = headers
+            .iter()
+            .find(|(name, _)| name == "content-length")
+            .and_then(|(_, value)| value.parse::<usize>().ok())
+            .unwrap_or(0);
+        while received.len() < headers_end + content_length {
+            let n = stream.read(&mut buf).await?;
+            if n == 0 {
+                break;
+            }
+            received.extend_from_slice(&buf[..n]);
+        }
+        let request_body = String::from_utf8_lossy(&received[headers_end..]).to_string();
+
+        Ok(PendingRequest {
+            request: StubRequest {
+                method,
+                target,
+                headers,
+                body: request_body,
+            },
+            stream,
+        })
+    }
+}
+
+/// Request accepted by the stub server which hasn't been replied yet.
+pub struct PendingRequest {
+    pub request: StubRequest,
+    stream: TcpStream,
+}
+
+impl PendingRequest {
+    /// Reply with the given status and body, closing the connection afterwards.
+    pub async fn reply(mut self, status: u16, body: &str) -> io::Result<StubRequest> {
+        let status = StatusCode::from_u16(status)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+        let response = format!(
+            "HTTP/1.1 {} {}\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\
+            connection: close\r\n\r\n{body}",
+            status.as_u16(),
+            status.canonical_reason().unwrap_or_default(),
+            body.len()
+        );
+        self.stream.write_all(response.as_bytes()).await?;
+        self.stream.shutdown().await?;
+
+        Ok(self.request)
+    }
+}
@@ -1,25 +1,33 @@
 use crate::{
-    app::{AppContext, METRICS_MAX_SIZE_PER_NODE},
+    app::AppContext,
     db_client::DbClient,
     node_mgr::NodeManager,
     types::{
-        AppSettings, BatchType, MetricsMode, NodeInstanceInfo, NodeStatus,
+        AppSettings, BatchType, EarningsHistoryStart, MetricsMode, MetricsResolution, NodeId,
+        NodeInstanceInfo, NodeStatus, OtherBalances, ServerEvent, TokenBalance,
         metrics::{METRIC_KEY_CPU_USAGE, METRIC_KEY_MEM_USED_MB, METRIC_KEY_RECORDS, NodeMetric},
     },
     views::truncated_balance_str,
 };
 
 use super::{
-    BgTasksCmds, ImmutableNodeStatus, TokenContract, arbitrum_client::ArbitrumClient,
-    earnings::calc_earnings_stats, metrics_client::NodeMetricsClient, prepare_node_action_batch,
+    BgTasksCmds, ImmutableNodeStatus, RpcEndpoints, TokenContract,
+    arbitrum_client::ArbitrumClient,
+    earnings::calc_earnings_stats,
+    metrics_client::{MetricsClientError, NodeMetricsClient},
+    metrics_push::{balances_points, node_point, stats_point},
+    nodes_earnings::attribute_nodes_earnings,
+    notifier::node_status_event,
+    prepare_node_action_batch,
+    rpc_endpoints::RpcCallError,
 };
 
 use alloy::{
-    network::Network,
     primitives::{Address, U256},
-    providers::{Provider, ProviderBuilder},
+    providers::Provider,
 };
 use chrono::Utc;
+use futures_util::{StreamExt, stream};
 use leptos::logging;
 use semver::Version;
 use std::{
@@ -30,15 +38,17 @@ use std::{
 };
 use tokio::{
     sync::RwLock,
-    time::{Duration, timeout},
+    time::{Duration, Instant, error::Elapsed, timeout},
 };
-use url::Url;
 
 // Timeout duration when querying for each rewards balance.
 const BALANCE_QUERY_TIMEOUT: Duration = Duration::from_secs(10);
 // Timeout duration when querying metrics from each node.
 const NODE_METRICS_QUERY_TIMEOUT: Duration = Duration::from_secs(3);
 
+// How long (in days) the stats snapshots are kept in the DB.
+const STATS_HISTORY_MAX_AGE_DAYS: i64 = 31;
+
 const LCD_LABEL_NET_SIZE: &str = "Network size:";
 const LCD_LABEL_ACTIVE_NODES: &str = "Active nodes:";
 const LCD_LABEL_STORED_RECORDS: &str = "Stored records:";
@@ -148,26 +158,32 @@ async fn update_node_metadata(
     node_info: &NodeInstanceInfo,
     db_client: &DbClient,
     node_status_locked: &ImmutableNodeStatus,
-) {
+) -> bool {
     let update_status = !node_status_locked.is_still_locked(&node_info.node_id).await
         && !node_info.is_status_locked;
     db_client
         .update_node_metadata(node_info, update_status)
         .await;
+    update_status
 }
 
 // Fetch up to date information for each active node instance
 // from nodes' exposed metrics server caching them in global context.
+// The nodes whose status changed since the last cycle are published onto the events bus.
 pub async fn update_nodes_info(
     node_manager: &NodeManager,
     app_ctx: AppContext,
     query_bin_version: bool,
-    metrics_mode: MetricsMode,
+    settings: &AppSettings,
+    http_client: &reqwest::Client,
+    nodes_last_status: &mut HashMap<NodeId, NodeStatus>,
     lcd_stats: &Arc<RwLock<HashMap<String, String>>>,
 ) {
+    let metrics_mode = settings.metrics_mode;
     let ts = Utc::now();
+    let started = Instant::now();
     let nodes = node_manager
-        .get_nodes_list(metrics_mode.clone())
+        .get_nodes_list(metrics_mode)
         .await
         .unwrap_or_else(|err| {
             logging::warn!("[{ts}] [WARN][BgTask] Failed to get nodes list: {err}");
@@ -193,6 +209,21 @@ pub async fn update_nodes_info(
     let mut connected_peers = 0;
     let mut shunned_count = 0;
     let mut bin_version = HashSet::<String>::new();
+    let mut points = Vec::new();
+    // nodes which status is locked by an ongoing action are left out,
+    // so transitions requested by the user are not notified.
+    let mut nodes_status = HashMap::new();
+
+    let mut fetched_metrics = if metrics_mode == MetricsMode::Http {
+        fetch_nodes_metrics(
+            &nodes,
+            http_client,
+            settings.nodes_metrics_polling_concurrency as usize,
+        )
+        .await
+    } else {
+        HashMap::new()
+    };
 
     for mut node_info in nodes.into_iter() {
         if node_info.status.is_active() {
@@ -246,28 +277,26 @@ pub async fn update_nodes_info(
                         node_info.set_status_to_unknown();
                     } else {
                         let mut node_metrics = app_ctx.nodes_metrics.write().await;
-                        node_metrics.store(&node_info.node_id, &metrics).await;
+                        node_metrics.store(&node_info.node_id, &metrics, node_info.rewards);
                         node_metrics.update_node_info(&mut node_info);
                         node_info.status = NodeStatus::Active;
                     }
                 }
                 MetricsMode::Http => {
-                    if let Some(metrics_port) = node_info.metrics_port {
-                        // Fetch all metrics from the node's HTTP metrics endpoint.
-                        let metrics_client = NodeMetricsClient::new(metrics_port);
+                    // Metrics were fetched from the node's HTTP metrics endpoint before the loop.
+                    if let Some(fetched) = fetched_metrics.remove(&node_info.node_id) {
                         let node_short_id = node_info.short_node_id();
 
-                        match timeout(NODE_METRICS_QUERY_TIMEOUT, metrics_client.fetch_metrics())
-                            .await
-                        {
+                        match fetched {
                             Ok(Ok(metrics)) => {
                                 let mut node_metrics = app_ctx.nodes_metrics.write().await;
-                                node_metrics.store(&node_info.node_id, &metrics).await;
+                                node_metrics.store(&node_info.node_id, &metrics, node_info.rewards);
                                 node_metrics.update_node_info(&mut node_info);
                                 node_info.status = NodeStatus::Active;
                             }
                             Ok(Err(err)) => {
                                 node_info.set_status_to_unknown();
+                                app_ctx.nodes_metrics.write().await.record_fetch_failure();
                                 logging::log!(
                                     "[BgTask] Failed to fetch metrics from node {node_short_id}: {}",
                                     err.source().map_or_else(
@@ -278,6 +307,7 @@ pub async fn update_nodes_info(
                             }
                             Err(_) => {
                                 node_info.set_status_to_unknown();
+                                app_ctx.nodes_metrics.write().await.record_fetch_failure();
                                 logging::warn!(
                                     "[WARN][BgTask] Timeout ({NODE_METRICS_QUERY_TIMEOUT:?}) while fetching metrics from node {node_short_id}."
                                 );
@@ -288,17 +318,50 @@ pub async fn update_nodes_info(
                 MetricsMode::Disabled => {}
             }
 
+            // traffic counters are read by the node manager, when the node's traffic can be told apart
+            if let (Some(rx_bytes), Some(tx_bytes)) =
+                (node_info.net_rx_bytes, node_info.net_tx_bytes)
+                && let Some((rx_rate, tx_rate)) = app_ctx
+                    .nodes_metrics
+                    .write()
+                    .await
+                    .store_network_usage(&node_info.node_id, rx_bytes, tx_bytes)
+            {
+                node_info.net_rx_rate = Some(rx_rate);
+                node_info.net_tx_rate = Some(tx_rate);
+            }
+
             net_size += node_info.net_size.unwrap_or_default();
             records += node_info.records.unwrap_or_default();
             relevant_records += node_info.relevant_records.unwrap_or_default();
             connected_peers += node_info.connected_peers.unwrap_or_default();
             shunned_count += node_info.shunned_count.unwrap_or_default();
+            points.extend(node_point(&node_info, ts.timestamp_millis()));
         } else if node_info.status.is_inactive() {
             num_inactive_nodes += 1;
         }
 
         // store up to date metadata and status onto local DB cache
-        update_node_metadata(&node_info, &app_ctx.db_client, &app_ctx.node_status_locked).await;
+        if update_node_metadata(&node_info, &app_ctx.db_client, &app_ctx.node_status_locked).await {
+            let prev_status = nodes_last_status.get(&node_info.node_id);
+            if let Some(event) =
+                prev_status.and_then(|prev_status| node_status_event(prev_status, &node_info))
+            {
+                let _ = app_ctx.notifications_tx.send(event);
+            }
+            // any other change of a node is published by the node manager when acting on it,
+            // and there is no point in publishing it if nobody is listening.
+            if prev_status.is_some_and(|prev_status| *prev_status != node_info.status)
+                && app_ctx.events_tx.receiver_count() > 0
+            {
+                let mut published = node_info.clone();
+                node_manager.complete_node_info(&mut published).await;
+                let _ = app_ctx
+                    .events_tx
+                    .send(ServerEvent::NodeUpdated(Box::new(published)));
+            }
+            nodes_status.insert(node_info.node_id.clone(), node_info.status.clone());
+        }
 
         if query_bin_version
             && let Some(ref version) = app_ctx
@@ -310,6 +373,20 @@ pub async fn update_nodes_info(
         }
     }
 
+    *nodes_last_status = nodes_status;
+
+    // store all historic metrics collected in this cycle onto local DB cache at once
+    let pending_metrics = app_ctx.nodes_metrics.write().await.take_pending();
+    app_ctx
+        .db_client
+        .store_nodes_metrics(&pending_metrics)
+        .await;
+    let pending_rewards = app_ctx.nodes_metrics.write().await.take_pending_rewards();
+    app_ctx
+        .db_client
+        .store_nodes_rewards_deltas(&pending_rewards)
+        .await;
+
     let mut updated_vals = vec![(
         LCD_LABEL_ACTIVE_NODES,
         format!("{num_active_nodes}/{num_nodes}"),
@@ -340,15 +417,57 @@ pub async fn update_nodes_info(
 
     update_lcd_stats(lcd_stats, &updated_vals).await;
 
-    let mut guard = app_ctx.stats.write().await;
-    guard.total_nodes = num_nodes;
-    guard.active_nodes = num_active_nodes;
-    guard.inactive_nodes = num_inactive_nodes;
-    guard.connected_peers = connected_peers;
-    guard.shunned_count = shunned_count;
-    guard.estimated_net_size = estimated_net_size;
-    guard.stored_records = records;
-    guard.relevant_records = relevant_records;
+    let cycle_duration = started.elapsed();
+    app_ctx
+        .nodes_metrics
+        .write()
+        .await
+        .record_polling_cycle(cycle_duration);
+
+    let point = app_ctx
+        .update_stats(|stats| {
+            stats.metrics_polling_cycle_millis = cycle_duration.as_millis() as u64;
+            stats.total_nodes = num_nodes;
+            stats.active_nodes = num_active_nodes;
+            stats.inactive_nodes = num_inactive_nodes;
+            stats.connected_peers = connected_peers;
+            stats.shunned_count = shunned_count;
+            stats.estimated_net_size = estimated_net_size;
+            stats.stored_records = records;
+            stats.relevant_records = relevant_records;
+            stats_point(stats, ts.timestamp_millis())
+        })
+        .await;
+    points.push(point);
+
+    // send the points collected in this cycle to be pushed to the metrics sinks
+    let _ = app_ctx.metrics_push_tx.send(points);
+}
+
+// Fetch metrics from the HTTP metrics endpoint of all active nodes concurrently,
+// querying no more than 'concurrency' nodes at a time.
+async fn fetch_nodes_metrics(
+    nodes: &[NodeInstanceInfo],
+    http_client: &reqwest::Client,
+    concurrency: usize,
+) -> HashMap<NodeId, Result<Result<Vec<NodeMetric>, MetricsClientError>, Elapsed>> {
+    let targets = nodes
+        .iter()
+        .filter(|info| info.status.is_active())
+        .filter_map(|info| info.metrics_port.map(|port| (info.node_id.clone(), port)))
+        .collect::<Vec<_>>();
+
+    stream::iter(targets)
+        .map(|(node_id, port)| {
+            let metrics_client = NodeMetricsClient::new(http_client.clone(), port);
+            async move {
+                let res = timeout(NODE_METRICS_QUERY_TIMEOUT, metrics_client.fetch_metrics()).await;
+                (node_id, res)
+            }
+        })
+        .buffer_unordered(concurrency.max(1))
+        .collect()
+        .await
 }
 
 // Check current nodes disks usage
@@ -398,34 +517,59 @@ pub async fn update_disks_usage(
         None => (0, 0),
     };
 
-    let mut guard = app_ctx.stats.write().await;
-    guard.total_disk_space = total_space;
-    guard.used_disk_space = used_disk_space;
-    guard.available_disk_space = available_space;
+    app_ctx
+        .update_stats(|stats| {
+            stats.total_disk_space = total_space;
+            stats.used_disk_space = used_disk_space;
+            stats.available_disk_space = available_space;
+        })
+        .await;
 }
 
-// Prune metrics records from the cache DB to always keep the number of records within a limit.
-pub async fn prune_metrics(node_manager: NodeManager, db_client: DbClient) {
-    let nodes = match node_manager.get_nodes_list(MetricsMode::Disabled).await {
-        Ok(nodes) if !nodes.is_empty() => nodes,
-        Err(err) => {
-            logging::log!("[BgTask] Failed to get nodes list: {err}");
-            return;
-        }
-        _ => return,
-    };
+// Average the nodes metrics collected into 1-minute and 1-hour buckets.
+pub async fn rollup_metrics(db_client: DbClient) {
+    let now = Utc::now().timestamp_millis();
+    db_client
+        .rollup_metrics(MetricsResolution::Minute, now)
+        .await;
+    db_client.rollup_metrics(MetricsResolution::Hour, now).await;
+}
 
-    for node_info in nodes.into_iter() {
+// Prune metrics records from the cache DB which are older than the retention set for each resolution.
+pub async fn prune_metrics(db_client: DbClient, settings: AppSettings) {
+    let now = Utc::now().timestamp_millis();
+    for resolution in [
+        MetricsResolution::Raw,
+        MetricsResolution::Minute,
+        MetricsResolution::Hour,
+    ] {
         logging::log!(
-            "[BgTask] Removing oldest metrics from DB for node {} ...",
-            node_info.short_node_id()
+            "[BgTask] Removing {resolution:?} metrics from DB beyond their retention ..."
         );
         db_client
-            .remove_oldest_metrics(node_info.node_id.clone(), METRICS_MAX_SIZE_PER_NODE)
+            .remove_metrics_older_than(resolution, now - resolution.retention_millis(&settings))
             .await;
     }
 }
 
+// Store a snapshot of the current aggregated stats in the DB to keep track of their history.
+pub async fn snapshot_stats(app_ctx: AppContext) {
+    let stats = app_ctx.stats.read().await.clone();
+    app_ctx
+        .db_client
+        .store_stats_snapshot(&stats, Utc::now().timestamp_millis())
+        .await;
+}
+
+// Prune stats snapshots from the cache DB which are older than the max age.
+pub async fn prune_stats_history(db_client: DbClient) {
+    logging::log!(
+        "[BgTask] Removing stats snapshots older than {STATS_HISTORY_MAX_AGE_DAYS} days from DB..."
+    );
+    let cutoff = Utc::now().timestamp_millis() - STATS_HISTORY_MAX_AGE_DAYS * 24 * 3_600_000;
+    db_client.remove_stats_history_older_than(cutoff).await;
+}
+
 pub async fn balance_checker_task(
     settings: AppSettings,
     node_manager: NodeManager,
@@ -435,6 +579,9 @@ pub async fn balance_checker_task(
     // cache retrieved rewards balances to not query more than once per address,
     // as well as how many nodes have each address set for rewards.
     let mut updated_balances = HashMap::<Address, (U256, u64)>::new();
+    // cache of the native and additional tokens balances retrieved for each rewards address.
+    let mut other_balances = HashMap::<Address, OtherBalances>::new();
+    let mut additional_tokens = AdditionalTokens::new(&settings.additional_token_contracts);
     let mut rewards_monitoring_enabled = settings.rewards_monitoring_enabled;
 
     // Let's trigger a first check now
@@ -446,51 +593,37 @@ pub async fn balance_checker_task(
             );
         }
     } else {
-        clear_rewards_stats(&app_ctx, &lcd_stats, &mut updated_balances).await;
+        clear_rewards_stats(
+            &app_ctx,
+            &lcd_stats,
+            &mut updated_balances,
+            &mut other_balances,
+        )
+        .await;
         logging::log!(
             "[BgTask] Rewards monitoring is disabled in settings. Skipping automatic checks."
         );
     }
 
-    // helper which creates a new contract if the new configured values are valid.
-    let update_token_contract = |contract_addr: &str, rpc_url: &str| {
-        let addr = match contract_addr.parse::<Address>() {
-            Err(err) => {
-                logging::log!(
-                    "[BgTask] Rewards balance check disabled. Invalid configured token contract address: {err}"
-                );
-                None
-            }
-            Ok(token_address) => Some(token_address),
-        };
-        let url = match rpc_url.parse::<Url>() {
-            Err(err) => {
-                logging::log!(
-                    "[BgTask] Rewards balance check disabled. Invalid configured RPC URL: {err}"
-                );
-                None
-            }
-            Ok(rpc_url) => Some(rpc_url),
-        };
-
-        match (addr, url) {
-            (Some(token_address), Some(rpc_url)) => {
-                let provider = ProviderBuilder::new().connect_http(rpc_url);
-                let token_contract = TokenContract::new(token_address, provider);
-                Some(token_contract)
-            }
-            _ => None,
+    // helper which parses the token contract address, disabling balance checks if it's not valid.
+    let parse_token_address = |contract_addr: &str| match contract_addr.parse::<Address>() {
+        Err(err) => {
+            logging::log!(
+                "[BgTask] Rewards balance check disabled. Invalid configured token contract address: {err}"
+            );
+            None
         }
+        Ok(token_address) => Some(token_address),
     };
 
     // Token contract used to query rewards balances.
-    let mut token_contract = update_token_contract(
-        &settings.token_contract_address,
-        &settings.l2_network_rpc_url,
-    );
+    let mut token_address = parse_token_address(&settings.token_contract_address);
 
-    let mut current_addr = settings.token_contract_address;
-    let mut current_url = settings.l2_network_rpc_url;
+    let mut current_addr = settings.token_contract_address.clone();
+    let mut current_urls = settings.l2_network_rpc_urls();
+    let mut earnings_periods = settings.earnings_periods_hours;
+    let mut earnings_retention_days = settings.earnings_retention_days;
+    let mut earnings_history_start = settings.earnings_history_start;
 
     loop {
         let mut perform_earnings_stats_update = false;
@@ -502,22 +635,46 @@ pub async fn balance_checker_task(
                     check_balances = true;
                 }
 
-                if current_addr != s.token_contract_address || current_url != s.l2_network_rpc_url {
-                    token_contract =
-                        update_token_contract(&s.token_contract_address, &s.l2_network_rpc_url);
-                    current_addr = s.token_contract_address;
-                    current_url = s.l2_network_rpc_url;
+                if current_addr != s.token_contract_address {
+                    token_address = parse_token_address(&s.token_contract_address);
+                    current_addr = s.token_contract_address.clone();
+                    check_balances = true;
+                }
+
+                if additional_tokens.contracts_changed(&s.additional_token_contracts) {
+                    additional_tokens = AdditionalTokens::new(&s.additional_token_contracts);
+                    check_balances = true;
+                }
+
+                if current_urls != s.l2_network_rpc_urls() {
+                    current_urls = s.l2_network_rpc_urls();
+                    app_ctx.rpc_endpoints.set_urls(&current_urls).await;
                     check_balances = true;
                 }
 
+                if earnings_periods != s.earnings_periods_hours
+                    || earnings_retention_days != s.earnings_retention_days
+                    || earnings_history_start != s.earnings_history_start
+                {
+                    earnings_periods = s.earnings_periods_hours;
+                    earnings_retention_days = s.earnings_retention_days;
+                    earnings_history_start = s.earnings_history_start;
+                    perform_earnings_stats_update = rewards_monitoring_enabled && !check_balances;
+                }
+
                 if check_balances {
                     let _ = app_ctx.bg_tasks_cmds_tx.send(BgTasksCmds::CheckAllBalances);
                 }
             }
             Ok(BgTasksCmds::PruneEarningsHistory) => {
                 logging::log!("[BgTask] Removing old earnings history records from DB ...");
-                if let Err(err) =
-                    ArbitrumClient::prune_history(&current_url, &app_ctx.db_client).await
+                if let Err(err) = ArbitrumClient::prune_history(
+                    &app_ctx.rpc_endpoints,
+                    &app_ctx.db_client,
+                    earnings_retention_days,
+                    earnings_history_start,
+                )
+                .await
                 {
                     logging::error!(
                         "[ERROR][BgTask] Failed to prune old earnings history records: {err}"
@@ -525,30 +682,41 @@ pub async fn balance_checker_task(
                 }
             }
             Ok(BgTasksCmds::CheckBalanceFor(node_info)) => {
-                if rewards_monitoring_enabled && let Some(ref token_contract) = token_contract {
+                if rewards_monitoring_enabled && let Some(token_address) = token_address {
                     retrieve_current_balances(
                         [node_info],
-                        token_contract,
+                        token_address,
+                        &mut additional_tokens,
+                        &app_ctx.rpc_endpoints,
                         &app_ctx.db_client,
                         &mut updated_balances,
+                        &mut other_balances,
                     )
                     .await;
 
-                    let total_balance: U256 = updated_balances.values().map(|(b, _)| b).sum();
+                    let total_balance =
+                        update_balances_stats(&app_ctx, &updated_balances, &other_balances).await;
                     update_balance_lcd_stats(&lcd_stats, total_balance).await;
-                    app_ctx.stats.write().await.total_balance = total_balance;
-                    app_ctx.stats.write().await.balances = updated_balances
-                        .iter()
-                        .map(|(addr, (balance, _))| (addr.to_string(), *balance))
-                        .collect();
                     perform_earnings_stats_update = true;
                 } else if !rewards_monitoring_enabled {
-                    clear_rewards_stats(&app_ctx, &lcd_stats, &mut updated_balances).await;
+                    clear_rewards_stats(
+                        &app_ctx,
+                        &lcd_stats,
+                        &mut updated_balances,
+                        &mut other_balances,
+                    )
+                    .await;
                 }
             }
             Ok(BgTasksCmds::DeleteBalanceFor(node_info)) => {
                 if !rewards_monitoring_enabled {
-                    clear_rewards_stats(&app_ctx, &lcd_stats, &mut updated_balances).await;
+                    clear_rewards_stats(
+                        &app_ctx,
+                        &lcd_stats,
+                        &mut updated_balances,
+                        &mut other_balances,
+                    )
+                    .await;
                 } else if let Some(Ok(address)) = node_info
                     .rewards_addr
                     .as_ref()
@@ -558,39 +726,44 @@ pub async fn balance_checker_task(
                         *num_nodes -= 1;
                         if *num_nodes == 0 {
                             let _ = updated_balances.remove(&address);
+                            let _ = other_balances.remove(&address);
                         }
                     }
-                    let total_balance: U256 = updated_balances.values().map(|(b, _)| b).sum();
+                    let total_balance =
+                        update_balances_stats(&app_ctx, &updated_balances, &other_balances).await;
                     update_balance_lcd_stats(&lcd_stats, total_balance).await;
-                    app_ctx.stats.write().await.total_balance = total_balance;
-                    app_ctx.stats.write().await.balances = updated_balances
-                        .iter()
-                        .map(|(addr, (balance, _))| (addr.to_string(), *balance))
-                        .collect();
                     perform_earnings_stats_update = true;
                 }
             }
             Ok(BgTasksCmds::CheckAllBalances) => {
                 if !rewards_monitoring_enabled {
-                    clear_rewards_stats(&app_ctx, &lcd_stats, &mut updated_balances).await;
+                    clear_rewards_stats(
+                        &app_ctx,
+                        &lcd_stats,
+                        &mut updated_balances,
+                        &mut other_balances,
+                    )
+                    .await;
                     continue;
                 }
                 updated_balances.clear();
-                let mut total_balance = U256::from(0u64);
-                if let Some(ref token_contract) = token_contract {
+                other_balances.clear();
+                if let Some(token_address) = token_address {
                     match node_manager.get_nodes_list(MetricsMode::Disabled).await {
                         Ok(nodes) if !nodes.is_empty() => {
                             retrieve_current_balances(
                                 nodes,
-                                token_contract,
+                                token_address,
+                                &mut additional_tokens,
+                                &app_ctx.rpc_endpoints,
                                 &app_ctx.db_client,
                                 &mut updated_balances,
+                                &mut other_balances,
                             )
                             .await;
 
                             let new_balance: U256 = updated_balances.values().map(|(b, _)| b).sum();
                             update_balance_lcd_stats(&lcd_stats, new_balance).await;
-                            total_balance = new_balance
                         }
                         Err(err) => {
                             logging::warn!("[WARN][BgTask] Failed to get nodes list: {err}");
@@ -601,11 +774,7 @@ pub async fn balance_checker_task(
                         }
                     }
                 }
-                app_ctx.stats.write().await.total_balance = total_balance;
-                app_ctx.stats.write().await.balances = updated_balances
-                    .iter()
-                    .map(|(addr, (balance, _))| (addr.to_string(), *balance))
-                    .collect();
+                update_balances_stats(&app_ctx, &updated_balances, &other_balances).await;
                 perform_earnings_stats_update = true;
             }
             Err(_) | Ok(BgTasksCmds::AgentAutonomousModeToggled(_)) => {}
@@ -613,23 +782,45 @@ pub async fn balance_checker_task(
 
         if perform_earnings_stats_update {
             if updated_balances.is_empty() {
-                let mut guard = app_ctx.stats.write().await;
-                guard.earnings.clear();
-                guard.earnings_syncing = false;
+                app_ctx
+                    .update_stats(|stats| {
+                        stats.earnings.clear();
+                        stats.earnings_sync_progress = None;
+                    })
+                    .await;
             } else {
-                app_ctx.stats.write().await.earnings_syncing = true;
-                update_earnings_stats(&app_ctx, &current_url, &current_addr, &updated_balances)
+                // flag it as syncing until the first fetch reports its progress
+                app_ctx
+                    .update_stats(|stats| {
+                        if stats.earnings.is_empty() && stats.earnings_sync_progress.is_none() {
+                            stats.earnings_sync_progress = Some(0);
+                        }
+                    })
                     .await;
+                let history = EarningsHistorySettings {
+                    periods_hours: &earnings_periods,
+                    retention_days: earnings_retention_days,
+                    start: earnings_history_start,
+                };
+                update_earnings_stats(&app_ctx, &current_addr, &updated_balances, history).await;
+                attribute_nodes_earnings(&app_ctx).await;
             }
+
+            let points =
+                balances_points(&*app_ctx.stats.read().await, Utc::now().timestamp_millis());
+            let _ = app_ctx.metrics_push_tx.send(points);
         }
     }
 }
 
-async fn retrieve_current_balances<P: Provider<N>, N: Network>(
+async fn retrieve_current_balances(
     nodes: impl IntoIterator<Item = NodeInstanceInfo>,
-    token_contract: &TokenContract::TokenContractInstance<P, N>,
+    token_address: Address,
+    additional_tokens: &mut AdditionalTokens,
+    rpc: &RpcEndpoints,
     db_client: &DbClient,
     updated_balances: &mut HashMap<Address, (U256, u64)>,
+    other_balances: &mut HashMap<Address, OtherBalances>,
 ) {
     for node_info in nodes.into_iter() {
         let node_short_id = node_info.short_node_id();
@@ -645,28 +836,25 @@ async fn retrieve_current_balances<P: Provider<N>, N: Network>(
             } else {
                 // query the balance to the ERC20 contract
                 logging::log!("[BgTask] Querying rewards balance for node {node_short_id} ...");
-                match timeout(
-                    BALANCE_QUERY_TIMEOUT,
-                    token_contract.balanceOf(address).call(),
-                )
-                .await
-                {
-                    Ok(Ok(balance)) => {
+                let res = rpc
+                    .call(|provider| async move {
+                        let token_contract = TokenContract::new(token_address, provider);
+                        with_query_timeout(token_contract.balanceOf(address).call()).await
+                    })
+                    .await;
+                let others = additional_tokens.balances_of(address, rpc).await;
+                other_balances.insert(address, others);
+                match res {
+                    Ok(balance) => {
                         updated_balances.insert(address, (balance, 1));
                         balance.to_string()
                     }
-                    Ok(Err(err)) => {
+                    Err(err) => {
                         logging::error!(
                             "[ERROR][BgTask] Failed to query rewards balance for node {node_short_id}: {err}"
                         );
                         "".to_string()
                     }
-                    Err(_) => {
-                        logging::warn!(
-                            "[WARN][BgTask] Timeout ({BALANCE_QUERY_TIMEOUT:?}) while querying rewards balance for node {node_short_id}."
-                        );
-                        "".to_string()
-                    }
                 }
             };
 
@@ -679,6 +867,134 @@ async fn retrieve_current_balances<P: Provider<N>, N: Network>(
     }
 }
 
+// Await an on-chain query, failing if it's not responded within BALANCE_QUERY_TIMEOUT.
+async fn with_query_timeout<T, E: Into<RpcCallError>>(
+    query: impl IntoFuture<Output = Result<T, E>>,
+) -> Result<T, RpcCallError> {
+    match timeout(BALANCE_QUERY_TIMEOUT, query).await {
+        Ok(res) => res.map_err(Into::into),
+        Err(_) => Err(RpcCallError::Timeout(BALANCE_QUERY_TIMEOUT)),
+    }
+}
+
+// ERC-20 tokens, other than the rewards token, which balances are tracked for each
+// rewards address, with their symbol and decimals cached once retrieved from the contracts.
+#[derive(Debug, Default)]
+struct AdditionalTokens {
+    contracts: Vec<String>,
+    metadata: Vec<(Address, Option<(String, u8)>)>,
+}
+
+impl AdditionalTokens {
+    fn new(contracts: &[String]) -> Self {
+        let metadata = contracts
+            .iter()
+            .filter_map(|contract| match contract.parse::<Address>() {
+                Ok(addr) => Some((addr, None)),
+                Err(err) => {
+                    logging::warn!(
+                        "[WARN][BgTask] Ignoring invalid additional token contract address '{contract}': {err}"
+                    );
+                    None
+                }
+            })
+            .collect();
+        Self {
+            contracts: contracts.to_vec(),
+            metadata,
+        }
+    }
+
+    fn contracts_changed(&self, contracts: &[String]) -> bool {
+        self.contracts != contracts
+    }
+
+    // Query the native gas token balance, and the balance of each of the
+    // additional tokens, held by the given address.
+    async fn balances_of(&mut self, address: Address, rpc: &RpcEndpoints) -> OtherBalances {
+        let eth = match rpc
+            .call(|provider| async move { with_query_timeout(provider.get_balance(address)).await })
+            .await
+        {
+            Ok(balance) => Some(balance),
+            Err(err) => {
+                logging::error!("[ERROR][BgTask] Failed to query ETH balance of {address}: {err}");
+                None
+            }
+        };
+
+        let mut tokens = Vec::new();
+        for (contract, metadata) in self.metadata.iter_mut() {
+            let contract = *contract;
+            if metadata.is_none() {
+                let res = rpc
+                    .call(|provider| async move {
+                        let token_contract = TokenContract::new(contract, provider);
+                        let symbol = with_query_timeout(token_contract.symbol().call()).await?;
+                        let decimals = with_query_timeout(token_contract.decimals().call()).await?;
+                        Ok::<_, RpcCallError>((symbol, decimals))
+                    })
+                    .await;
+                match res {
+                    Ok(m) => *metadata = Some(m),
+                    Err(err) => {
+                        logging::error!(
+                            "[ERROR][BgTask] Failed to query symbol and decimals of token {contract}: {err}"
+                        );
+                        continue;
+                    }
+                }
+            }
+            let Some((symbol, decimals)) = metadata.clone() else {
+                continue;
+            };
+
+            let res = rpc
+                .call(|provider| async move {
+                    let token_contract = TokenContract::new(contract, provider);
+                    with_query_timeout(token_contract.balanceOf(address).call()).await
+                })
+                .await;
+            match res {
+                Ok(balance) => tokens.push(TokenBalance {
+                    contract: contract.to_string(),
+                    symbol,
+                    decimals,
+                    balance,
+                }),
+                Err(err) => logging::error!(
+                    "[ERROR][BgTask] Failed to query {symbol} balance of {address}: {err}"
+                ),
+            }
+        }
+
+        OtherBalances { eth, tokens }
+    }
+}
+
+// Set the balances stats from the ones currently cached, returning the new total balance.
+async fn update_balances_stats(
+    app_ctx: &AppContext,
+    updated_balances: &HashMap<Address, (U256, u64)>,
+    other_balances: &HashMap<Address, OtherBalances>,
+) -> U256 {
+    let total_balance: U256 = updated_balances.values().map(|(b, _)| b).sum();
+    app_ctx
+        .update_stats(|stats| {
+            stats.total_balance = total_balance;
+            stats.balances = updated_balances
+                .iter()
+                .map(|(addr, (balance, _))| (addr.to_string(), *balance))
+                .collect();
+            stats.other_balances = other_balances
+                .iter()
+                .map(|(addr, balances)| (addr.to_string(), balances.clone()))
+                .collect();
+        })
+        .await;
+    total_balance
+}
+
 // Helper to update total balance stat to be disaplyed on external LCD device
 async fn update_balance_lcd_stats(
     lcd_stats: &Arc<RwLock<HashMap<String, String>>>,
@@ -692,14 +1008,20 @@ async fn clear_rewards_stats(
     app_ctx: &AppContext,
     lcd_stats: &Arc<RwLock<HashMap<String, String>>>,
     updated_balances: &mut HashMap<Address, (U256, u64)>,
+    other_balances: &mut HashMap<Address, OtherBalances>,
 ) {
     updated_balances.clear();
+    other_balances.clear();
     remove_lcd_stats(lcd_stats, &[LCD_LABEL_BALANCE]).await;
-    let mut guard = app_ctx.stats.write().await;
-    guard.total_balance = U256::ZERO;
-    guard.balances.clear();
-    guard.earnings.clear();
-    guard.earnings_syncing = false;
+    app_ctx
+        .update_stats(|stats| {
+            stats.total_balance = U256::ZERO;
+            stats.balances.clear();
+            stats.other_balances.clear();
+            stats.earnings.clear();
+            stats.earnings_sync_progress = None;
+        })
+        .await;
 }
 
 // Helper to add/update stats to be disaplyed on external LCD device
@@ -762,19 +1084,28 @@ fn get_total_and_free_disk_space(
     (total_space, available_space)
 }
 
+// Settings of the earnings history tracked and analysed.
+struct EarningsHistorySettings<'a> {
+    periods_hours: &'a [u32],
+    retention_days: u64,
+    start: Option<EarningsHistoryStart>,
+}
+
 // Fetch incoming payment data from Arbitrum L2 for rewards addresses.
 async fn update_earnings_stats(
     app_ctx: &AppContext,
-    arbitrum_rpc: &str,
     contract_address: &str,
     updated_balances: &HashMap<Address, (U256, u64)>,
+    history: EarningsHistorySettings<'_>,
 ) {
     // Create the Arbitrum client with the provided configuration
     let client = match ArbitrumClient::new(
-        arbitrum_rpc,
+        app_ctx.rpc_endpoints.clone(),
         contract_address,
         updated_balances.keys(),
         app_ctx.db_client.clone(),
+        history.retention_days,
+        history.start,
     ) {
         Ok(client) => client,
         Err(e) => {
@@ -785,9 +1116,9 @@ async fn update_earnings_stats(
 
     // Fetch payments from Arbitrum
     match client.fetch_incoming_payments().await {
-        Ok((payments, fully_synced)) => {
+        Ok((payments, sync_progress)) => {
             logging::log!(
-                "[BgTask] Successfully updated earnings stats for {} addresses (fully synced: {fully_synced}).",
+                "[BgTask] Successfully updated earnings stats for {} addresses (synced: {sync_progress}%).",
                 payments.len()
             );
             let now = Utc::now().timestamp();
@@ -795,7 +1126,7 @@ async fn update_earnings_stats(
             let mut earnings = payments
                 .into_iter()
                 .map(|p| {
-                    let earnings = calc_earnings_stats(now, &p.payments);
+                    let earnings = calc_earnings_stats(now, history.periods_hours, &p.payments);
                     aggregated_payments.extend(p.payments);
                     (p.address.clone(), earnings)
                 })
@@ -803,18 +1134,282 @@ async fn update_earnings_stats(
 
             earnings.push((
                 "".to_string(),
-                calc_earnings_stats(now, &aggregated_payments),
+                calc_earnings_stats(now, history.periods_hours, &aggregated_payments),
             ));
 
-            let mut guard = app_ctx.stats.write().await;
-            guard.earnings = earnings;
-            guard.earnings_syncing = !fully_synced;
+            app_ctx
+                .update_stats(|stats| {
+                    stats.earnings = earnings;
+                    stats.earnings_sync_progress = (sync_progress < 100).then_some(sync_progress);
+                })
+                .await;
         }
         Err(err) => {
-            let mut guard = app_ctx.stats.write().await;
-            guard.earnings.clear();
-            guard.earnings_syncing = false;
+            app_ctx
+                .update_stats(|stats| {
+                    stats.earnings.clear();
+                    stats.earnings_sync_progress = None;
+                })
+                .await;
             logging::error!("[ERROR][BgTask] Failed to fetch rewards payments: {err}");
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bg_tasks::{
+        TokenContract::{balanceOfCall, decimalsCall, symbolCall},
+        stub_http_server::StubHttpServer,
+    };
+    use alloy::sol_types::{SolCall, SolValue};
+
+    const METRICS_BODY: &str = "# TYPE ant_networking_connected_peers gauge\n\
+        ant_networking_connected_peers 5\n# EOF\n";
+
+    fn active_node(server: &StubHttpServer) -> NodeInstanceInfo {
+        NodeInstanceInfo {
+            node_id: NodeId::random(),
+            status: NodeStatus::Active,
+            metrics_port: Some(server.port()),
+            ..Default::default()
+        }
+    }
+
+    async fn stub_servers(count: usize) -> Vec<StubHttpServer> {
+        let mut servers = Vec::new();
+        for _ in 0..count {
+            servers.push(StubHttpServer::bind().await.unwrap());
+        }
+        servers
+    }
+
+    fn assert_fetched(
+        fetched: &HashMap<NodeId, Result<Result<Vec<NodeMetric>, MetricsClientError>, Elapsed>>,
+        node: &NodeInstanceInfo,
+    ) {
+        match fetched.get(&node.node_id) {
+            Some(Ok(Ok(metrics))) => {
+                assert_eq!(metrics.len(), 1);
+                assert_eq!(metrics[0].value, "5");
+            }
+            other => panic!("unexpected metrics fetched: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_nodes_metrics_within_concurrency_limit() {
+        let servers = stub_servers(3).await;
+        let nodes = servers.iter().map(active_node).collect::<Vec<_>>();
+        let fetch = {
+            let nodes = nodes.clone();
+            tokio::spawn(
+                async move { fetch_nodes_metrics(&nodes, &reqwest::Client::new(), 2).await },
+            )
+        };
+
+        let first = servers[0].accept().await.unwrap();
+        let second = servers[1].accept().await.unwrap();
+        // the third node is not queried until one of the first two replies
+        assert!(
+            timeout(Duration::from_millis(300), servers[2].accept())
+                .await
+                .is_err()
+        );
+        first.reply(200, METRICS_BODY).await.unwrap();
+        servers[2].respond(200, METRICS_BODY).await.unwrap();
+        second.reply(200, METRICS_BODY).await.unwrap();
+
+        let fetched = fetch.await.unwrap();
+        assert_eq!(fetched.len(), 3);
+        nodes.iter().for_each(|node| assert_fetched(&fetched, node));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_nodes_metrics_slow_node_does_not_block_others() {
+        let servers = stub_servers(3).await;
+        let nodes = servers.iter().map(active_node).collect::<Vec<_>>();
+        let started = Instant::now();
+        let fetch = {
+            let nodes = nodes.clone();
+            tokio::spawn(
+                async move { fetch_nodes_metrics(&nodes, &reqwest::Client::new(), 2).await },
+            )
+        };
+
+        // the first node never replies, while the others are queried and reply meanwhile
+        let _slow = servers[0].accept().await.unwrap();
+        servers[1].respond(200, METRICS_BODY).await.unwrap();
+        servers[2].respond(200, METRICS_BODY).await.unwrap();
+        assert!(started.elapsed() < NODE_METRICS_QUERY_TIMEOUT);
+
+        let fetched = fetch.await.unwrap();
+        assert_eq!(fetched.len(), 3);
+        assert!(matches!(fetched.get(&nodes[0].node_id), Some(Err(_))));
+        assert_fetched(&fetched, &nodes[1]);
+        assert_fetched(&fetched, &nodes[2]);
+    }
+
+    const REWARDS_BALANCE: u64 = 100;
+    const ETH_BALANCE: u64 = 7;
+    const EXTRA_TOKEN_BALANCE: u64 = 42;
+
+    // Serve JSON-RPC requests querying the balances of the rewards token, an additional token
+    // and ETH, failing to provide the rewards balance of the given address. The method, or the
+    // contract function selector, of each request received is recorded.
+    fn spawn_rpc_stub(
+        server: StubHttpServer,
+        failing_addr: Address,
+    ) -> Arc<std::sync::Mutex<Vec<String>>> {
+        let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let log = received.clone();
+        tokio::spawn(async move {
+            while let Ok(pending) = server.accept().await {
+                let req: serde_json::Value = serde_json::from_str(&pending.request.body).unwrap();
+                let method = req["method"].as_str().unwrap_or_default().to_string();
+                let result = match method.as_str() {
+                    "eth_getBalance" => Ok(serde_json::json!(U256::from(ETH_BALANCE))),
+                    "eth_call" => {
+                        let call = &req["params"][0];
+                        let input = call["input"].as_str().or(call["data"].as_str()).unwrap();
+                        let input = hex::decode(input.trim_start_matches("0x")).unwrap();
+                        let selector: [u8; 4] = input[..4].try_into().unwrap();
+                        log.lock().unwrap().push(hex::encode(selector));
+                        let output = if selector == symbolCall::SELECTOR {
+                            Ok(("USDC".to_string(),).abi_encode_params())
+                        } else if selector == decimalsCall::SELECTOR {
+                            Ok(U256::from(6).abi_encode())
+                        } else if selector == balanceOfCall::SELECTOR {
+                            let addr = Address::from_slice(&input[16..36]);
+                            let is_rewards_token = call["to"]
+                                .as_str()
+                                .is_some_and(|to| to.parse::<Address>() == Ok(rewards_token()));
+                            if !is_rewards_token {
+                                Ok(U256::from(EXTRA_TOKEN_BALANCE).abi_encode())
+                            } else if addr == failing_addr {
+                                Err("execution reverted")
+                            } else {
+                                Ok(U256::from(REWARDS_BALANCE).abi_encode())
+                            }
+                        } else {
+                            Err("unexpected call")
+                        };
+                        output.map(|o| serde_json::json!(format!("0x{}", hex::encode(o))))
+                    }
+                    _ => Err("unexpected method"),
+                };
+                log.lock().unwrap().push(method);
+                let body = match result {
+                    Ok(result) => {
+                        serde_json::json!({"jsonrpc": "2.0", "id": req["id"], "result": result})
+                    }
+                    Err(msg) => serde_json::json!({
+                        "jsonrpc": "2.0",
+                        "id": req["id"],
+                        "error": {"code": -32000, "message": msg}
+                    }),
+                };
+                let _ = pending.reply(200, &body.to_string()).await;
+            }
+        });
+        received
+    }
+
+    fn rewards_token() -> Address {
+        Address::repeat_byte(0x11)
+    }
+
+    #[tokio::test]
+    async fn test_retrieve_and_report_current_balances() {
+        let server = StubHttpServer::bind().await.unwrap();
+        let rpc = RpcEndpoints::new(&[server.url("/")]);
+        let (addr_a, addr_b) = (Address::repeat_byte(0xaa), Address::repeat_byte(0xbb));
+        let received = spawn_rpc_stub(server, addr_b);
+        let extra_token = Address::repeat_byte(0x22);
+        let mut additional_tokens = AdditionalTokens::new(&[extra_token.to_string()]);
+
+        let app_ctx = AppContext::new(crate::db_client::test_db_client().await).await;
+        let rewards_addrs = [Some(addr_a), Some(addr_a), Some(addr_b), None];
+        let mut nodes = Vec::new();
+        for rewards_addr in rewards_addrs {
+            let node_info = NodeInstanceInfo {
+                node_id: NodeId::random(),
+                rewards_addr: rewards_addr.map(|addr| addr.to_string()),
+                ..Default::default()
+            };
+            app_ctx.db_client.insert_node_metadata(&node_info).await;
+            nodes.push(node_info);
+        }
+
+        let mut updated_balances = HashMap::new();
+        let mut other_balances = HashMap::new();
+        retrieve_current_balances(
+            nodes.clone(),
+            rewards_token(),
+            &mut additional_tokens,
+            &rpc,
+            &app_ctx.db_client,
+            &mut updated_balances,
+            &mut other_balances,
+        )
+        .await;
+
+        // the balances of each address are queried once, the tokens metadata once for all
+        assert_eq!(
+            updated_balances,
+            HashMap::from([(addr_a, (U256::from(REWARDS_BALANCE), 2))])
+        );
+        let others = OtherBalances {
+            eth: Some(U256::from(ETH_BALANCE)),
+            tokens: vec![TokenBalance {
+                contract: extra_token.to_string(),
+                symbol: "USDC".to_string(),
+                decimals: 6,
+                balance: U256::from(EXTRA_TOKEN_BALANCE),
+            }],
+        };
+        assert_eq!(
+            other_balances,
+            HashMap::from([(addr_a, others.clone()), (addr_b, others.clone())])
+        );
+        let received = received.lock().unwrap().clone();
+        let count = |method: &str| received.iter().filter(|m| *m == method).count();
+        assert_eq!(count("eth_getBalance"), 2);
+        assert_eq!(count(&hex::encode(symbolCall::SELECTOR)), 1);
+        assert_eq!(count(&hex::encode(balanceOfCall::SELECTOR)), 4);
+
+        // each node's balance is stored, the ones which couldn't be retrieved cleared
+        let stored = app_ctx.db_client.get_nodes_list().await;
+        let balances = nodes
+            .iter()
+            .map(|node| stored[&node.node_id].balance)
+            .collect::<Vec<_>>();
+        let rewards = Some(U256::from(REWARDS_BALANCE));
+        assert_eq!(balances, vec![rewards, rewards, None, None]);
+
+        let mut events_rx = app_ctx.events_tx.subscribe();
+        let total_balance =
+            update_balances_stats(&app_ctx, &updated_balances, &other_balances).await;
+        assert_eq!(total_balance, U256::from(REWARDS_BALANCE));
+        let stats = app_ctx.stats.read().await.clone();
+        assert_eq!(stats.total_balance, U256::from(REWARDS_BALANCE));
+        assert_eq!(
+            stats.balances,
+            vec![(addr_a.to_string(), U256::from(REWARDS_BALANCE))]
+        );
+        let mut reported_others = stats.other_balances.clone();
+        reported_others.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            reported_others,
+            vec![
+                (addr_a.to_string(), others.clone()),
+                (addr_b.to_string(), others)
+            ]
+        );
+        assert!(matches!(
+            events_rx.try_recv(),
+            Ok(ServerEvent::StatsUpdated(reported)) if *reported == stats
+        ));
+    }
+}
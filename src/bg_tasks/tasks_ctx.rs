@@ -5,12 +5,27 @@ use tokio::time::{Duration, Interval, interval};
 
 // How often to perform a metrics pruning in the DB.
 const METRICS_PRUNING_FREQ: Duration = Duration::from_secs(60 * 60); // every hour.
+// How often to average the collected metrics into 1-minute and 1-hour buckets.
+const METRICS_ROLLUP_FREQ: Duration = Duration::from_secs(60); // every minute.
+// How often to store a snapshot of the aggregated stats in the DB.
+const STATS_SNAPSHOT_FREQ: Duration = Duration::from_secs(5 * 60); // every 5 minutes.
+// How often to evaluate the alert rules.
+const ALERTS_EVALUATION_FREQ: Duration = Duration::from_secs(30); // every 30 seconds.
+// How often to check the health of the L2 network RPC endpoints.
+const RPC_HEALTH_CHECK_FREQ: Duration = Duration::from_secs(60); // every minute.
+// How often to sample the host resources usage.
+const HOST_METRICS_SAMPLING_FREQ: Duration = Duration::from_secs(15); // every 15 seconds.
 
 // App settings and set of intervals used to schedule each of the tasks.
 pub struct TasksContext {
     pub node_bin_version_check: Interval,
     pub balances_retrieval: Interval,
     pub metrics_pruning: Interval,
+    pub metrics_rollup: Interval,
+    pub stats_snapshot: Interval,
+    pub alerts_evaluation: Interval,
+    pub rpc_health_check: Interval,
+    pub host_metrics_sampling: Interval,
     pub nodes_metrics_polling: Interval,
     pub disks_usage_check: Interval,
     pub app_settings: AppSettings,
@@ -20,11 +35,20 @@ impl TasksContext {
     pub fn from(settings: AppSettings) -> Self {
         let mut balances_retrieval = interval(settings.rewards_balances_retrieval_freq);
         balances_retrieval.reset(); // the task will trigger the first check by itself
+        let mut stats_snapshot = interval(STATS_SNAPSHOT_FREQ);
+        stats_snapshot.reset(); // let the stats be collected before taking the first snapshot
+        let mut alerts_evaluation = interval(ALERTS_EVALUATION_FREQ);
+        alerts_evaluation.reset(); // same for evaluating the alert rules
 
         Self {
             node_bin_version_check: interval(settings.node_bin_version_polling_freq),
             balances_retrieval,
             metrics_pruning: interval(METRICS_PRUNING_FREQ),
+            metrics_rollup: interval(METRICS_ROLLUP_FREQ),
+            stats_snapshot,
+            alerts_evaluation,
+            rpc_health_check: interval(RPC_HEALTH_CHECK_FREQ),
+            host_metrics_sampling: interval(HOST_METRICS_SAMPLING_FREQ),
             nodes_metrics_polling: interval(settings.nodes_metrics_polling_freq),
             disks_usage_check: interval(settings.disks_usage_check_freq),
             app_settings: settings,
@@ -0,0 +1,368 @@
+use crate::{
+    db_client::{DbClient, DbError},
+    types::{AppSettings, EarningsExport, EarningsExportRow},
+};
+
+use alloy_primitives::Address;
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use leptos::logging;
+use std::{
+    collections::{BTreeSet, HashMap},
+    time::Duration,
+};
+use thiserror::Error;
+
+// Timeout when retrieving a price from the price API.
+const PRICE_API_TIMEOUT: Duration = Duration::from_secs(20);
+// Number of seconds in a day.
+const SECS_PER_DAY: i64 = 24 * 3_600;
+
+#[derive(Debug, Error)]
+pub enum TokenPriceError {
+    #[error(transparent)]
+    HttpError(#[from] reqwest::Error),
+    #[error("Price API responded with status {0}")]
+    ServiceError(reqwest::StatusCode),
+    #[error("No '{0}' price found in the price API response")]
+    PriceNotFound(String),
+}
+
+/// Client of the HTTP API the daily price of the rewards token is retrieved from.
+pub struct TokenPriceClient {
+    client: reqwest::Client,
+    url_template: String,
+    currency: String,
+}
+
+impl TokenPriceClient {
+    pub fn new(url_template: &str, currency: &str) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url_template: url_template.to_string(),
+            currency: currency.to_lowercase(),
+        }
+    }
+
+    // URL to query the price on a day, with the placeholders of the template replaced
+    fn url_for(&self, date: NaiveDate) -> String {
+        self.url_template
+            .replace("{date}", &date.format("%d-%m-%Y").to_string())
+            .replace("{currency}", &self.currency)
+    }
+
+    // Whether the API returns the prices within a range of time, with '{from}' and '{to}'
+    // placeholders, rather than the price on a single day with a '{date}' placeholder.
+    fn is_range_api(&self) -> bool {
+        self.url_template.contains("{from}") && self.url_template.contains("{to}")
+    }
+
+    // URL to query the prices within a range of days, both inclusive, with the placeholders
+    // of the template replaced by the UNIX timestamps (seconds) the range starts and ends at.
+    fn range_url_for(&self, from: NaiveDate, to: NaiveDate) -> String {
+        let start = from.and_time(NaiveTime::MIN).and_utc().timestamp();
+        let end = to.and_time(NaiveTime::MIN).and_utc().timestamp() + SECS_PER_DAY - 1;
+        self.url_template
+            .replace("{from}", &start.to_string())
+            .replace("{to}", &end.to_string())
+            .replace("{currency}", &self.currency)
+    }
+
+    async fn get_json(&self, url: &str) -> Result<serde_json::Value, TokenPriceError> {
+        let res = self
+            .client
+            .get(url)
+            .timeout(PRICE_API_TIMEOUT)
+            .send()
+            .await?;
+        if !res.status().is_success() {
+            return Err(TokenPriceError::ServiceError(res.status()));
+        }
+        Ok(res.json::<serde_json::Value>().await?)
+    }
+
+    /// Retrieve the token price on the given day from the price API.
+    pub async fn daily_price(&self, date: NaiveDate) -> Result<f64, TokenPriceError> {
+        let json = self.get_json(&self.url_for(date)).await?;
+        parse_price(&json, &self.currency)
+            .ok_or_else(|| TokenPriceError::PriceNotFound(self.currency.clone()))
+    }
+
+    /// Retrieve the token prices on each day within the given range, both inclusive,
+    /// from the price API with a single request.
+    pub async fn range_prices(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<HashMap<NaiveDate, f64>, TokenPriceError> {
+        let json = self.get_json(&self.range_url_for(from, to)).await?;
+        parse_range_prices(&json)
+            .ok_or_else(|| TokenPriceError::PriceNotFound(self.currency.clone()))
+    }
+}
+
+// Price found in a response of the API, either in the format of CoinGecko's history endpoint,
+// i.e. '{"market_data":{"current_price":{"usd":0.1}}}', or in a plain '{"price":0.1}' object.
+fn parse_price(json: &serde_json::Value, currency: &str) -> Option<f64> {
+    json.pointer(&format!("/market_data/current_price/{currency}"))
+        .or_else(|| json.get(currency))
+        .or_else(|| json.get("price"))
+        .and_then(|v| v.as_f64())
+}
+
+// Price on each day found in a response of the API in the format of CoinGecko's range endpoint,
+// i.e. '{"prices":[[<timestamp millis>,0.1],...]}', taking the first price sampled each day.
+fn parse_range_prices(json: &serde_json::Value) -> Option<HashMap<NaiveDate, f64>> {
+    let mut prices = HashMap::new();
+    for sample in json.get("prices")?.as_array()? {
+        let (Some(millis), Some(price)) = (
+            sample.get(0).and_then(|v| v.as_i64()),
+            sample.get(1).and_then(|v| v.as_f64()),
+        ) else {
+            continue;
+        };
+        if let Some(time) = DateTime::from_timestamp_millis(millis) {
+            prices.entry(time.date_naive()).or_insert(price);
+        }
+    }
+    Some(prices)
+}
+
+/// Token prices on each of the given days, using the cached ones and retrieving the rest from
+/// the price API, with a single request if it supports ranges of time. Only prices of past
+/// days are cached since today's one may still change. Days whose price couldn't be
+/// retrieved are left out.
+pub async fn daily_token_prices(
+    db_client: &DbClient,
+    price_client: &TokenPriceClient,
+    days: &BTreeSet<NaiveDate>,
+) -> HashMap<NaiveDate, f64> {
+    let (Some(first), Some(last)) = (days.first(), days.last()) else {
+        return HashMap::new();
+    };
+    let mut prices = db_client
+        .get_token_prices(
+            &price_client.url_template,
+            &price_client.currency,
+            *first,
+            *last,
+        )
+        .await;
+    let today = Utc::now().date_naive();
+    let missing = days
+        .iter()
+        .filter(|d| !prices.contains_key(d))
+        .copied()
+        .collect::<Vec<_>>();
+    let (Some(first_missing), Some(last_missing)) = (missing.first(), missing.last()) else {
+        return prices;
+    };
+
+    let mut retrieved = HashMap::new();
+    if price_client.is_range_api() {
+        match price_client
+            .range_prices(*first_missing, *last_missing)
+            .await
+        {
+            Ok(range_prices) => retrieved = range_prices,
+            Err(err) => logging::warn!(
+                "[WARN] Failed to retrieve token prices from {first_missing} to {last_missing}: {err}"
+            ),
+        }
+    } else {
+        for day in missing.iter() {
+            match price_client.daily_price(*day).await {
+                Ok(price) => {
+                    retrieved.insert(*day, price);
+                }
+                Err(err) => {
+                    // most likely we are being rate limited, so don't keep trying for the rest of days
+                    logging::warn!("[WARN] Failed to retrieve token price on {day}: {err}");
+                    break;
+                }
+            }
+        }
+    }
+
+    for day in missing {
+        if let Some(price) = retrieved.get(&day) {
+            if day < today {
+                db_client
+                    .store_token_price(
+                        day,
+                        &price_client.url_template,
+                        &price_client.currency,
+                        *price,
+                    )
+                    .await;
+            }
+            prices.insert(day, *price);
+        }
+    }
+
+    prices
+}
+
+/// Build the export of the reward payments received within the given range of days,
+/// valued in the fiat currency and with the price source set in the settings.
+pub async fn earnings_export(
+    db_client: &DbClient,
+    settings: &AppSettings,
+    address: Option<Address>,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<EarningsExport, DbError> {
+    let from_ts = from.and_time(NaiveTime::MIN).and_utc().timestamp();
+    let to_ts = to
+        .succ_opt()
+        .unwrap_or(to)
+        .and_time(NaiveTime::MIN)
+        .and_utc()
+        .timestamp()
+        - 1;
+    let mut payments = db_client
+        .get_earnings_in_range(address.as_ref(), from_ts, to_ts)
+        .await?;
+
+    let mut missing_prices = 0;
+    if !settings.token_price_api_url.is_empty() {
+        let price_client =
+            TokenPriceClient::new(&settings.token_price_api_url, &settings.fiat_currency);
+        let days = payments.iter().map(EarningsExportRow::date).collect();
+        let prices = daily_token_prices(db_client, &price_client, &days).await;
+        for payment in payments.iter_mut() {
+            payment.price = prices.get(&payment.date()).copied();
+        }
+        let missing = payments.iter().filter(|p| p.price.is_none()).count();
+        if missing > 0 {
+            logging::warn!(
+                "[WARN] Token price unknown for {missing} of the {} payments exported",
+                payments.len()
+            );
+        }
+        missing_prices = missing;
+    }
+
+    Ok(EarningsExport {
+        currency: settings.fiat_currency.to_lowercase(),
+        from,
+        to,
+        payments,
+        missing_prices,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bg_tasks::stub_http_server::StubHttpServer;
+
+    #[test]
+    fn test_parse_price() {
+        let coingecko = serde_json::json!({
+            "id": "autonomi",
+            "market_data": { "current_price": { "usd": 0.25, "eur": 0.23 } }
+        });
+        assert_eq!(parse_price(&coingecko, "eur"), Some(0.23));
+        assert_eq!(parse_price(&coingecko, "gbp"), None);
+        assert_eq!(
+            parse_price(&serde_json::json!({ "price": 1.5 }), "usd"),
+            Some(1.5)
+        );
+        assert_eq!(
+            parse_price(&serde_json::json!({ "usd": "n/a" }), "usd"),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_daily_price_from_stub_api() -> Result<(), Box<dyn std::error::Error>> {
+        let server = StubHttpServer::bind().await?;
+        let url = server.url("/history?date={date}&vs={currency}");
+        let price_client = TokenPriceClient::new(&url, "EUR");
+        let date = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+
+        let (price, request) = tokio::join!(
+            price_client.daily_price(date),
+            server.respond(200, r#"{"market_data":{"current_price":{"eur":0.42}}}"#)
+        );
+
+        assert_eq!(price?, 0.42);
+        assert_eq!(request?.target, "/history?date=02-03-2026&vs=eur");
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_range_prices() {
+        let json = serde_json::json!({
+            "prices": [
+                // 2026-03-01 00:00 and 12:00 UTC
+                [1_772_323_200_000i64, 0.2],
+                [1_772_366_400_000i64, 0.3],
+                // 2026-03-02 00:00 UTC
+                [1_772_409_600_000i64, 0.4],
+                ["invalid", 0.5]
+            ]
+        });
+        let prices = parse_range_prices(&json).unwrap();
+        assert_eq!(prices.len(), 2);
+        assert_eq!(prices[&NaiveDate::from_ymd_opt(2026, 3, 1).unwrap()], 0.2);
+        assert_eq!(prices[&NaiveDate::from_ymd_opt(2026, 3, 2).unwrap()], 0.4);
+        assert_eq!(
+            parse_range_prices(&serde_json::json!({ "error": "limit" })),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_range_prices_from_stub_api() -> Result<(), Box<dyn std::error::Error>> {
+        let server = StubHttpServer::bind().await?;
+        let url = server.url("/range?vs={currency}&from={from}&to={to}");
+        let price_client = TokenPriceClient::new(&url, "EUR");
+        assert!(price_client.is_range_api());
+        let from = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        let to = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+
+        let (prices, request) = tokio::join!(
+            price_client.range_prices(from, to),
+            server.respond(
+                200,
+                r#"{"prices":[[1772323200000,0.2],[1772409600000,0.4]]}"#
+            )
+        );
+
+        assert_eq!(prices?.len(), 2);
+        assert_eq!(
+            request?.target,
+            "/range?vs=eur&from=1772323200&to=1772495999"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_token_prices_cached_per_source() -> Result<(), Box<dyn std::error::Error>> {
+        let db_client = crate::db_client::test_db_client().await;
+        let day = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        let days = BTreeSet::from([day]);
+        let mut sources = Vec::new();
+        // each source is queried even if the day's price was already cached from another one
+        for price in [0.42, 0.9] {
+            let server = StubHttpServer::bind().await?;
+            let url = server.url("/history?date={date}&vs={currency}");
+            let price_client = TokenPriceClient::new(&url, "EUR");
+            let body = format!(r#"{{"market_data":{{"current_price":{{"eur":{price}}}}}}}"#);
+            let responder = tokio::spawn(async move { server.respond(200, &body).await });
+            let prices = daily_token_prices(&db_client, &price_client, &days).await;
+            responder.abort();
+            assert_eq!(prices, HashMap::from([(day, price)]));
+            sources.push((url, price));
+        }
+
+        for (url, price) in sources {
+            assert_eq!(
+                db_client.get_token_prices(&url, "eur", day, day).await,
+                HashMap::from([(day, price)])
+            );
+        }
+        Ok(())
+    }
+}
@@ -1,10 +1,16 @@
 use crate::{
     server_api::*,
     types::{
-        AppSettings, BatchOnMatch, BatchType, LogLevel, NodeFilter, NodeId, NodeInstanceInfo,
-        NodeOpts, NodeSortField, NodeStatusFilter, NodesActionsBatch, NodesSortStrategy, Stats,
+        AddressBook, AddressBookEntry, AppSettings, BatchOnMatch, BatchType, DEFAULT_ADDRESS_COLOR,
+        EarningsExport, ExportFormat, HostStats, LogLevel, MonthlyTraffic, NodeConfigUpdate,
+        NodeFilter, NodeId, NodeInstanceInfo, NodeOpts, NodeSortField, NodeStatusFilter,
+        NodeUpdated, NodesActionsBatch, NodesSortStrategy, RewardsAddrWeight,
+        RewardsAddressSummary, Stats, format_earnings_periods,
+    },
+    views::{
+        format_disk_usage, format_other_balances, format_rate, format_timestamp,
+        truncated_balance_str,
     },
-    views::{format_disk_usage, truncated_balance_str},
 };
 
 #[cfg(feature = "ssr")]
@@ -16,11 +22,11 @@ use eyre::{Result, WrapErr};
 use url::form_urlencoded;
 
 use alloy_primitives::{Address, utils::format_units};
-use chrono::{DateTime, Local, Utc};
+use chrono::{DateTime, Local, NaiveDate, Utc};
 use eyre::eyre;
 use leptos::prelude::ServerFnError;
-use prettytable::{Table, format, row};
-use std::{io::Write, net::SocketAddr, path::PathBuf};
+use prettytable::{Cell, Row, Table, format, row};
+use std::{io::Write, net::SocketAddr, path::PathBuf, str::FromStr};
 use structopt::StructOpt;
 
 #[derive(StructOpt, Debug)]
@@ -29,6 +35,10 @@ pub struct CliCmds {
     /// Backend IP address and port.
     #[structopt(long, global = true)]
     pub addr: Option<SocketAddr>,
+    /// Session token to authenticate with the backend, instead of the one
+    /// stored by the 'login' command.
+    #[structopt(long, global = true, env = "FORMICAIO_TOKEN", hide_env_values = true)]
+    pub token: Option<String>,
     #[structopt(subcommand)]
     pub sub_cmds: CliSubCmds,
 }
@@ -40,10 +50,24 @@ pub enum CliSubCmds {
     Start,
     #[cfg(feature = "native")]
     Start(StartSubcommands),
+    /// Log in to the backend, storing the session token for the following commands
+    Login(LoginSubcommands),
+    /// Log out from the backend, ending the stored session
+    Logout,
     #[structopt(flatten)]
     CliCommands(CliCommands),
 }
 
+#[derive(Debug, PartialEq, StructOpt)]
+pub struct LoginSubcommands {
+    /// Username to log in with.
+    #[structopt(long)]
+    pub username: String,
+    /// Password of the user. It's prompted for, without echoing it, when not provided.
+    #[structopt(long)]
+    pub password: Option<String>,
+}
+
 #[derive(Debug, PartialEq, StructOpt)]
 pub struct StartSubcommands {
     /// Do not start nodes which were previously active.
@@ -74,10 +98,16 @@ pub enum CliCommands {
     Nodes(NodesSubcommands),
     /// Stats commands
     Stats,
+    /// Show the current resources usage of the host
+    Host,
     /// Batches commands
     Batches(BatchesSubcommands),
     /// Settings commands
     Settings(SettingsSubcommands),
+    /// Rewards addresses commands
+    Addresses(AddressesSubcommands),
+    /// Earnings commands
+    Earnings(EarningsSubcommands),
 }
 
 #[derive(Debug, PartialEq, StructOpt)]
@@ -171,6 +201,54 @@ pub enum NodesSubcommands {
         #[structopt(long, default_value = "0")]
         interval: u64,
     },
+    /// Edit the configuration of existing nodes, keeping their data and peer-id.
+    /// Only the settings provided are changed, e.g. '--id 726d63514a6d --log-level debug'.
+    Edit {
+        /// Edit nodes with the provided id/s (a batch will be created if more than one is provided).
+        /// Multiple ids can be provided, e.g. '--id 726d63514a6d --id 59566d447968'.
+        #[structopt(long, required = true)]
+        id: Vec<NodeId>,
+        #[structopt(flatten)]
+        changes: NodeConfigCmd,
+        /// Interval (in seconds) between each action.
+        #[structopt(long, default_value = "0")]
+        interval: u64,
+    },
+}
+
+#[derive(Debug, PartialEq, StructOpt)]
+pub struct NodeConfigCmd {
+    /// Enable or disable IPv4-only mode, e.g. '--ipv4-only true'.
+    #[structopt(long)]
+    ipv4_only: Option<bool>,
+    /// New node port number
+    #[structopt(long)]
+    port: Option<u16>,
+    /// New node metrics port number
+    #[structopt(long)]
+    metrics_port: Option<u16>,
+    /// New rewards address
+    #[structopt(long, parse(try_from_str = parse_and_validate_addr))]
+    rewards_addr: Option<Address>,
+    /// Enable or disable node logs, e.g. '--node-logs false'.
+    #[structopt(long)]
+    node_logs: Option<bool>,
+    /// New log level of node logs
+    #[structopt(long, parse(try_from_str = parse_log_level), possible_values = &["error", "warn", "info", "debug", "trace"])]
+    log_level: Option<LogLevel>,
+}
+
+impl From<&NodeConfigCmd> for NodeConfigUpdate {
+    fn from(cmd: &NodeConfigCmd) -> Self {
+        Self {
+            ipv4_only: cmd.ipv4_only,
+            port: cmd.port,
+            metrics_port: cmd.metrics_port,
+            rewards_addr: cmd.rewards_addr.map(|addr| addr.to_string()),
+            node_logs: cmd.node_logs,
+            log_level: cmd.log_level.clone(),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, StructOpt)]
@@ -184,9 +262,11 @@ pub struct NodeOptsCmd {
     /// Node metrics port number (range start when creating multiple nodes)
     #[structopt(long)]
     metrics_port: u16,
-    /// Rewards address
-    #[structopt(long, parse(try_from_str = parse_and_validate_addr))]
-    rewards_addr: Address,
+    /// Rewards address. It can be set several times, optionally followed by ':<weight>', to
+    /// distribute the nodes of a batch across them in proportion to their weights (default 1),
+    /// which requires creating more than one node with '--count'.
+    #[structopt(long, required = true, number_of_values = 1, parse(try_from_str = parse_rewards_addr_weight))]
+    rewards_addr: Vec<RewardsAddrWeight>,
     /// Automatically starts nodes upon creation.
     #[structopt(long)]
     auto_start: bool,
@@ -212,6 +292,29 @@ fn parse_node_status_filter(src: &str) -> eyre::Result<NodeStatusFilter> {
     Ok(status_filter)
 }
 
+// Parser for the rewards address CLI args, with an optional weight, e.g. '0x1234...:3'
+fn parse_rewards_addr_weight(src: &str) -> eyre::Result<RewardsAddrWeight> {
+    let (addr, weight) = match src.split_once(':') {
+        Some((addr, weight)) => (
+            addr,
+            weight
+                .parse::<u16>()
+                .map_err(|_| eyre!("Not a valid weight: {weight}"))?,
+        ),
+        None => (src, 1),
+    };
+    let address = parse_and_validate_addr(addr).map_err(|err| eyre!(err))?;
+    Ok(RewardsAddrWeight {
+        address: address.to_string(),
+        weight,
+    })
+}
+
+// Parser for the node log level CLI args
+fn parse_log_level(src: &str) -> eyre::Result<LogLevel> {
+    LogLevel::from_str(src).map_err(|_| eyre!("Not a valid log level: {src}"))
+}
+
 // Parser for the node sort strategy CLI args
 fn parse_sort_strategy(src: &str) -> eyre::Result<NodesSortStrategy> {
     NodesSortStrategy::from_arg_str(src).ok_or(eyre!("Not a valid sort option: {src}"))
@@ -220,12 +323,38 @@ fn parse_sort_strategy(src: &str) -> eyre::Result<NodesSortStrategy> {
 #[derive(Debug, PartialEq, StructOpt)]
 pub enum BatchesSubcommands {
     /// List running and scheduled nodes actions batches
-    Ls,
+    Ls {
+        /// Include also the history of finished batches, with the outcome of each node action
+        #[structopt(long)]
+        all: bool,
+    },
     /// Cancel batch
     Cancel {
         /// Batch Id to cancel
         batch_id: u16,
     },
+    /// Pause a running batch
+    Pause {
+        /// Batch Id to pause
+        batch_id: u16,
+    },
+    /// Resume a paused batch
+    Resume {
+        /// Batch Id to resume
+        batch_id: u16,
+    },
+    /// Change the delay between each node action of a batch not finished yet
+    Interval {
+        /// Batch Id to update
+        batch_id: u16,
+        /// New delay (in seconds) between each node action
+        interval: u64,
+    },
+    /// Create a new batch retrying only the failed node actions of a finished batch
+    Retry {
+        /// Batch Id to retry
+        batch_id: u16,
+    },
 }
 
 #[derive(Debug, PartialEq, StructOpt)]
@@ -234,13 +363,62 @@ pub enum SettingsSubcommands {
     Ls,
 }
 
+#[derive(Debug, PartialEq, StructOpt)]
+pub enum AddressesSubcommands {
+    /// List rewards addresses with their label, the nodes paying to them, balance and earnings
+    Ls,
+    /// Set the label, note and colour of a rewards address in the address book
+    Set {
+        /// Rewards address to set the label of
+        address: Address,
+        /// Label to display instead of the address
+        #[structopt(long)]
+        label: String,
+        /// Optional note about the address
+        #[structopt(long, default_value = "")]
+        note: String,
+        /// Colour in '#rrggbb' format used to tell the address apart in the UI
+        #[structopt(long, default_value = DEFAULT_ADDRESS_COLOR)]
+        color: String,
+    },
+    /// Remove a rewards address from the address book
+    Rm {
+        /// Rewards address to remove
+        address: Address,
+    },
+}
+
+#[derive(Debug, PartialEq, StructOpt)]
+pub enum EarningsSubcommands {
+    /// Export the reward payments received within a range of days, valued in the fiat
+    /// currency set in the settings, e.g. 'earnings export --from 2026-01-01 > earnings.csv'
+    Export {
+        /// First day (UTC) of the range, in YYYY-MM-DD format
+        #[structopt(long)]
+        from: NaiveDate,
+        /// Last day (UTC) of the range, in YYYY-MM-DD format (default = today)
+        #[structopt(long)]
+        to: Option<NaiveDate>,
+        /// Export only the payments received by this rewards address
+        #[structopt(long)]
+        address: Option<Address>,
+        /// Format of the export: 'csv' or 'json'
+        #[structopt(long, default_value = "csv")]
+        format: ExportFormat,
+    },
+}
+
 #[derive(Debug)]
 pub enum CliCmdResponse {
-    Nodes(Vec<NodeInstanceInfo>, bool),
+    Nodes(Vec<NodeInstanceInfo>, bool, AddressBook),
     NodeCreated(Box<NodeInstanceInfo>),
-    Stats(Box<Stats>),
-    Batches(Vec<NodesActionsBatch>),
+    NodeUpdated(Box<NodeUpdated>, AddressBook),
+    Stats(Box<Stats>, AddressBook),
+    Host(Option<Box<HostStats>>, Option<MonthlyTraffic>),
+    Addresses(Vec<RewardsAddressSummary>),
+    Batches(Vec<NodesActionsBatch>, bool),
     Settings(Box<AppSettings>),
+    EarningsExport(String),
     BatchCreated(u16),
     Success,
 }
@@ -267,14 +445,20 @@ impl CliCommands {
                 let sort_strategy =
                     sort.unwrap_or(NodesSortStrategy::new(NodeSortField::NodeId, false));
                 sort_strategy.sort_items(&mut sorted_nodes);
-                CliCmdResponse::Nodes(sorted_nodes, *extended)
+                // labels of rewards addresses are only displayed in the extended list
+                let book = if *extended {
+                    address_book().await?
+                } else {
+                    AddressBook::default()
+                };
+                CliCmdResponse::Nodes(sorted_nodes, *extended, book)
             }
             CliCommands::Nodes(NodesSubcommands::Create(node_opts_cmd)) => {
                 let node_opts = NodeOpts {
                     ipv4_only: node_opts_cmd.ipv4_only,
                     port: node_opts_cmd.port,
                     metrics_port: node_opts_cmd.metrics_port,
-                    rewards_addr: node_opts_cmd.rewards_addr.to_string(),
+                    rewards_addr: node_opts_cmd.rewards_addr[0].address.clone(),
                     node_logs: true,
                     log_level: LogLevel::default(),
                     auto_start: node_opts_cmd.auto_start,
@@ -285,6 +469,7 @@ impl CliCommands {
                         BatchType::Create {
                             node_opts,
                             count: node_opts_cmd.count,
+                            rewards_addrs: weighted_rewards_addrs(&node_opts_cmd.rewards_addr),
                         },
                         node_opts_cmd.interval,
                     )
@@ -405,24 +590,107 @@ impl CliCommands {
                 }
                 CliCmdResponse::Success
             }
-            CliCommands::Stats => CliCmdResponse::Stats(Box::new(fetch_stats().await?)),
-            CliCommands::Batches(BatchesSubcommands::Ls) => {
-                CliCmdResponse::Batches(nodes_actions_batches().await?)
+            CliCommands::Nodes(NodesSubcommands::Edit {
+                id,
+                changes,
+                interval,
+            }) => {
+                let changes = NodeConfigUpdate::from(changes);
+                if let [node_id] = id.as_slice() {
+                    let info = update_node_instance(node_id.clone(), changes).await?;
+                    CliCmdResponse::NodeUpdated(Box::new(info), address_book().await?)
+                } else {
+                    let batch_id = nodes_actions_batch_create(
+                        BatchType::Update {
+                            node_ids: id.clone(),
+                            changes,
+                        },
+                        *interval,
+                    )
+                    .await?;
+                    CliCmdResponse::BatchCreated(batch_id)
+                }
+            }
+            CliCommands::Stats => {
+                CliCmdResponse::Stats(Box::new(fetch_stats().await?), address_book().await?)
+            }
+            CliCommands::Host => CliCmdResponse::Host(
+                host_stats().await?.map(Box::new),
+                fetch_stats().await?.monthly_traffic,
+            ),
+            CliCommands::Batches(BatchesSubcommands::Ls { all }) => {
+                let mut batches = nodes_actions_batches().await?;
+                if *all {
+                    batches.extend(nodes_actions_batches_history().await?);
+                }
+                CliCmdResponse::Batches(batches, *all)
             }
             CliCommands::Batches(BatchesSubcommands::Cancel { batch_id }) => {
                 cancel_batch(*batch_id).await?;
                 CliCmdResponse::Success
             }
+            CliCommands::Batches(BatchesSubcommands::Pause { batch_id }) => {
+                pause_batch(*batch_id).await?;
+                CliCmdResponse::Success
+            }
+            CliCommands::Batches(BatchesSubcommands::Resume { batch_id }) => {
+                resume_batch(*batch_id).await?;
+                CliCmdResponse::Success
+            }
+            CliCommands::Batches(BatchesSubcommands::Interval { batch_id, interval }) => {
+                set_batch_interval(*batch_id, *interval).await?;
+                CliCmdResponse::Success
+            }
+            CliCommands::Batches(BatchesSubcommands::Retry { batch_id }) => {
+                CliCmdResponse::BatchCreated(retry_batch(*batch_id).await?)
+            }
             CliCommands::Settings(SettingsSubcommands::Ls) => {
                 CliCmdResponse::Settings(Box::new(get_settings().await?))
             }
+            CliCommands::Addresses(AddressesSubcommands::Ls) => {
+                CliCmdResponse::Addresses(rewards_addresses().await?)
+            }
+            CliCommands::Addresses(AddressesSubcommands::Set {
+                address,
+                label,
+                note,
+                color,
+            }) => {
+                save_address_book_entry(AddressBookEntry {
+                    address: address.to_string(),
+                    label: label.clone(),
+                    note: note.clone(),
+                    color: color.clone(),
+                })
+                .await?;
+                CliCmdResponse::Success
+            }
+            CliCommands::Addresses(AddressesSubcommands::Rm { address }) => {
+                delete_address_book_entry(address.to_string()).await?;
+                CliCmdResponse::Success
+            }
+            CliCommands::Earnings(EarningsSubcommands::Export {
+                from,
+                to,
+                address,
+                format,
+            }) => {
+                let to = to.unwrap_or_else(|| Utc::now().date_naive());
+                let export =
+                    export_earnings(*from, to, address.map(|addr| addr.to_string())).await?;
+                earnings_export_response(&export, *format)
+            }
         };
 
         Ok(res)
     }
 
     #[cfg(feature = "ssr")]
-    pub async fn send_request(&self, addr: SocketAddr) -> Result<CliCmdResponse> {
+    pub async fn send_request(
+        &self,
+        addr: SocketAddr,
+        token: Option<&str>,
+    ) -> Result<CliCmdResponse> {
         let api_url = format!("http://{addr}/api");
 
         match &self {
@@ -451,28 +719,38 @@ impl CliCommands {
                     }
                 }
 
-                send_req(&format!("{api_url}/nodes/list"), Some(body))
-                    .await
-                    .map(|res: NodesInstancesInfo| {
-                        let mut sorted_nodes = res.nodes.values().cloned().collect::<Vec<_>>();
-                        let sort_strategy =
-                            sort.unwrap_or(NodesSortStrategy::new(NodeSortField::NodeId, false));
-                        sort_strategy.sort_items(&mut sorted_nodes);
+                let res: NodesInstancesInfo =
+                    send_req(&format!("{api_url}/nodes/list"), Some(body), token).await?;
+                let mut sorted_nodes = res.nodes.values().cloned().collect::<Vec<_>>();
+                let sort_strategy =
+                    sort.unwrap_or(NodesSortStrategy::new(NodeSortField::NodeId, false));
+                sort_strategy.sort_items(&mut sorted_nodes);
 
-                        CliCmdResponse::Nodes(sorted_nodes, *extended)
-                    })
+                // labels of rewards addresses are only displayed in the extended list
+                let book = if *extended {
+                    send_req(&format!("{api_url}/addresses/book/list"), None, token).await?
+                } else {
+                    AddressBook::default()
+                };
+                Ok(CliCmdResponse::Nodes(sorted_nodes, *extended, book))
             }
             CliCommands::Nodes(NodesSubcommands::Create(opts)) => {
                 let ipv4_only = opts.ipv4_only;
+                // a single node cannot be distributed across several addresses
+                if opts.count <= 1 && opts.rewards_addr.len() > 1 {
+                    return Err(eyre!(
+                        "Several rewards addresses can only be set when creating more than one node with --count"
+                    ));
+                }
 
                 if opts.count > 1 {
                     // TODO: use some crate which performs this serialisation
-                    let body = format!(
+                    let mut body = format!(
                         "batch_type[Create][node_opts][ipv4_only]={}&batch_type[Create][node_opts][port]={}&batch_type[Create][node_opts][metrics_port]={}&batch_type[Create][node_opts][rewards_addr]={}&batch_type[Create][node_opts][node_logs]={}&batch_type[Create][node_opts][log_level]=info&batch_type[Create][node_opts][auto_start]={}&batch_type[Create][node_opts][data_dir_path]={}&batch_type[Create][count]={}&interval_secs={}",
                         ipv4_only,
                         opts.port,
                         opts.metrics_port,
-                        opts.rewards_addr,
+                        opts.rewards_addr[0].address,
                         true,
                         opts.auto_start,
                         form_urlencoded::byte_serialize(
@@ -482,9 +760,19 @@ impl CliCommands {
                         opts.count,
                         opts.interval
                     );
+                    for (i, addr) in weighted_rewards_addrs(&opts.rewards_addr)
+                        .iter()
+                        .enumerate()
+                    {
+                        body.push_str(&format!(
+                            "&batch_type[Create][rewards_addrs][{i}][address]={}&batch_type[Create][rewards_addrs][{i}][weight]={}",
+                            addr.address, addr.weight
+                        ));
+                    }
 
                     let batch_id =
-                        send_req::<u16>(&format!("{api_url}/batch/create"), Some(body)).await?;
+                        send_req::<u16>(&format!("{api_url}/batch/create"), Some(body), token)
+                            .await?;
                     Ok(CliCmdResponse::BatchCreated(batch_id))
                 } else {
                     // TODO: use some crate which performs this serialisation
@@ -493,7 +781,7 @@ impl CliCommands {
                         ipv4_only,
                         opts.port,
                         opts.metrics_port,
-                        opts.rewards_addr,
+                        opts.rewards_addr[0].address,
                         true,
                         opts.auto_start,
                         form_urlencoded::byte_serialize(
@@ -505,6 +793,7 @@ impl CliCommands {
                     match send_req::<NodeInstanceInfo>(
                         &format!("{api_url}/nodes/create"),
                         Some(body),
+                        token,
                     )
                     .await
                     {
@@ -526,6 +815,7 @@ impl CliCommands {
                     &status.clone().unwrap_or_default(),
                     *interval,
                     "RemoveOnMatch",
+                    token,
                 )
                 .await
             }
@@ -542,6 +832,7 @@ impl CliCommands {
                     &status.clone().unwrap_or_default(),
                     *interval,
                     "StartOnMatch",
+                    token,
                 )
                 .await
             }
@@ -558,6 +849,7 @@ impl CliCommands {
                     &status.clone().unwrap_or_default(),
                     *interval,
                     "StopOnMatch",
+                    token,
                 )
                 .await
             }
@@ -574,6 +866,7 @@ impl CliCommands {
                     &status.clone().unwrap_or_default(),
                     *interval,
                     "RecycleOnMatch",
+                    token,
                 )
                 .await
             }
@@ -590,31 +883,196 @@ impl CliCommands {
                     &status.clone().unwrap_or_default(),
                     *interval,
                     "UpgradeOnMatch",
+                    token,
                 )
                 .await
             }
-            CliCommands::Stats => send_req(&format!("{api_url}/stats"), None)
-                .await
-                .map(|res: Stats| CliCmdResponse::Stats(Box::new(res))),
-            CliCommands::Batches(BatchesSubcommands::Ls) => {
-                send_req(&format!("{api_url}/batch/list"), None)
+            CliCommands::Nodes(NodesSubcommands::Edit {
+                id,
+                changes,
+                interval,
+            }) => {
+                let changes = NodeConfigUpdate::from(changes);
+                if let [node_id] = id.as_slice() {
+                    let mut params = vec![("node_id".to_string(), node_id.to_string())];
+                    params.extend(config_changes_params("changes", &changes));
+                    let info: NodeUpdated = send_req(
+                        &format!("{api_url}/nodes/update"),
+                        Some(to_req_body(&params)),
+                        token,
+                    )
+                    .await?;
+                    let book =
+                        send_req(&format!("{api_url}/addresses/book/list"), None, token).await?;
+                    Ok(CliCmdResponse::NodeUpdated(Box::new(info), book))
+                } else {
+                    let mut params = id
+                        .iter()
+                        .enumerate()
+                        .map(|(i, node_id)| {
+                            (
+                                format!("batch_type[Update][node_ids][{i}]"),
+                                node_id.to_string(),
+                            )
+                        })
+                        .collect::<Vec<_>>();
+                    params.extend(config_changes_params(
+                        "batch_type[Update][changes]",
+                        &changes,
+                    ));
+                    params.push(("interval_secs".to_string(), interval.to_string()));
+                    send_req(
+                        &format!("{api_url}/batch/create"),
+                        Some(to_req_body(&params)),
+                        token,
+                    )
                     .await
-                    .map(|res: Vec<NodesActionsBatch>| CliCmdResponse::Batches(res))
+                    .map(CliCmdResponse::BatchCreated)
+                }
+            }
+            CliCommands::Stats => {
+                let stats: Stats = send_req(&format!("{api_url}/stats"), None, token).await?;
+                let book = send_req(&format!("{api_url}/addresses/book/list"), None, token).await?;
+                Ok(CliCmdResponse::Stats(Box::new(stats), book))
+            }
+            CliCommands::Host => {
+                let host: Option<HostStats> =
+                    send_req(&format!("{api_url}/host/stats"), None, token).await?;
+                let stats: Stats = send_req(&format!("{api_url}/stats"), None, token).await?;
+                Ok(CliCmdResponse::Host(
+                    host.map(Box::new),
+                    stats.monthly_traffic,
+                ))
+            }
+            CliCommands::Batches(BatchesSubcommands::Ls { all }) => {
+                let mut batches: Vec<NodesActionsBatch> =
+                    send_req(&format!("{api_url}/batch/list"), None, token).await?;
+                if *all {
+                    let history: Vec<NodesActionsBatch> =
+                        send_req(&format!("{api_url}/batch/history"), None, token).await?;
+                    batches.extend(history);
+                }
+                Ok(CliCmdResponse::Batches(batches, *all))
             }
             CliCommands::Batches(BatchesSubcommands::Cancel { batch_id }) => {
                 let body = format!("batch_id={batch_id}");
-                send_req::<()>(&format!("{api_url}/batch/cancel"), Some(body)).await?;
+                send_req::<()>(&format!("{api_url}/batch/cancel"), Some(body), token).await?;
+                Ok(CliCmdResponse::Success)
+            }
+            CliCommands::Batches(BatchesSubcommands::Pause { batch_id }) => {
+                let body = format!("batch_id={batch_id}");
+                send_req::<()>(&format!("{api_url}/batch/pause"), Some(body), token).await?;
+                Ok(CliCmdResponse::Success)
+            }
+            CliCommands::Batches(BatchesSubcommands::Resume { batch_id }) => {
+                let body = format!("batch_id={batch_id}");
+                send_req::<()>(&format!("{api_url}/batch/resume"), Some(body), token).await?;
+                Ok(CliCmdResponse::Success)
+            }
+            CliCommands::Batches(BatchesSubcommands::Interval { batch_id, interval }) => {
+                let body = format!("batch_id={batch_id}&interval_secs={interval}");
+                send_req::<()>(&format!("{api_url}/batch/interval"), Some(body), token).await?;
                 Ok(CliCmdResponse::Success)
             }
+            CliCommands::Batches(BatchesSubcommands::Retry { batch_id }) => {
+                let body = format!("batch_id={batch_id}");
+                send_req(&format!("{api_url}/batch/retry"), Some(body), token)
+                    .await
+                    .map(CliCmdResponse::BatchCreated)
+            }
             CliCommands::Settings(SettingsSubcommands::Ls) => {
-                send_req(&format!("{api_url}/settings/get"), None)
+                send_req(&format!("{api_url}/settings/get"), None, token)
                     .await
                     .map(|settings: AppSettings| CliCmdResponse::Settings(Box::new(settings)))
             }
+            CliCommands::Addresses(AddressesSubcommands::Ls) => {
+                send_req(&format!("{api_url}/addresses/summaries"), None, token)
+                    .await
+                    .map(CliCmdResponse::Addresses)
+            }
+            CliCommands::Addresses(AddressesSubcommands::Set {
+                address,
+                label,
+                note,
+                color,
+            }) => {
+                let params = [
+                    ("entry[address]", address.to_string()),
+                    ("entry[label]", label.clone()),
+                    ("entry[note]", note.clone()),
+                    ("entry[color]", color.clone()),
+                ]
+                .map(|(key, value)| (key.to_string(), value));
+                send_req::<()>(
+                    &format!("{api_url}/addresses/book/save"),
+                    Some(to_req_body(&params)),
+                    token,
+                )
+                .await?;
+                Ok(CliCmdResponse::Success)
+            }
+            CliCommands::Addresses(AddressesSubcommands::Rm { address }) => {
+                let body = format!("address={address}");
+                send_req::<()>(
+                    &format!("{api_url}/addresses/book/delete"),
+                    Some(body),
+                    token,
+                )
+                .await?;
+                Ok(CliCmdResponse::Success)
+            }
+            CliCommands::Earnings(EarningsSubcommands::Export {
+                from,
+                to,
+                address,
+                format,
+            }) => {
+                let to = to.unwrap_or_else(|| Utc::now().date_naive());
+                let mut body = format!("from={from}&to={to}");
+                if let Some(addr) = address {
+                    body.push_str(&format!("&address={addr}"));
+                }
+                send_req(&format!("{api_url}/earnings/export"), Some(body), token)
+                    .await
+                    .map(|export: EarningsExport| earnings_export_response(&export, *format))
+            }
         }
     }
 }
 
+// Response with the content of an export, warning on stderr, so it's not mixed with
+// the content when redirected to a file, if some payments lack their fiat value.
+fn earnings_export_response(export: &EarningsExport, format: ExportFormat) -> CliCmdResponse {
+    if export.is_incomplete() {
+        eprintln!(
+            "WARNING: the export is incomplete, the token price couldn't be retrieved for {} \
+            payment/s, which were exported without their fiat value.",
+            export.missing_prices
+        );
+    }
+    CliCmdResponse::EarningsExport(export.render(format))
+}
+
+// Addresses to distribute the nodes of a batch across, none if only one was given
+fn weighted_rewards_addrs(addrs: &[RewardsAddrWeight]) -> Vec<RewardsAddrWeight> {
+    if addrs.len() > 1 {
+        addrs.to_vec()
+    } else {
+        vec![]
+    }
+}
+
+// Helper which formats a rewards address prefixed with its label, if it's in the address book
+fn format_rewards_addr(book: &AddressBook, addr: &Option<String>) -> String {
+    match addr {
+        Some(addr) => match book.get(addr) {
+            Some(entry) => format!("{} ({addr})", entry.label),
+            None => addr.clone(),
+        },
+        None => "-".to_string(),
+    }
+}
+
 // Helper which converts a value to string or a dash sign if it's None
 fn value_or_dash<T: ToString>(val: Option<T>) -> String {
     val.map_or("-".to_string(), |v| v.to_string())
@@ -622,6 +1080,11 @@ fn value_or_dash<T: ToString>(val: Option<T>) -> String {
 
 impl CliCmdResponse {
     pub fn print<T: Write + ?Sized>(&self, out: &mut T) -> eyre::Result<()> {
+        // exports are printed as they are so they can be redirected to a file
+        if let CliCmdResponse::EarningsExport(content) = self {
+            write!(out, "{content}")?;
+            return Ok(());
+        }
         let tables = self.gen_print_table();
         for t in tables {
             t.print(out)?;
@@ -631,6 +1094,10 @@ impl CliCmdResponse {
     }
 
     pub fn printstd(&self) {
+        if let CliCmdResponse::EarningsExport(content) = self {
+            print!("{content}");
+            return;
+        }
         let tables = self.gen_print_table();
         tables.iter().for_each(|t| {
             t.printstd();
@@ -641,7 +1108,7 @@ impl CliCmdResponse {
     fn gen_print_table(&self) -> Vec<Table> {
         let mut tables = vec![];
         match self {
-            CliCmdResponse::Nodes(nodes, extended) => {
+            CliCmdResponse::Nodes(nodes, extended, book) => {
                 if *extended {
                     for info in nodes {
                         let mut table = Table::new();
@@ -681,6 +1148,26 @@ impl CliCmdResponse {
                             "Disk usage",
                             value_or_dash(info.disk_usage.map(format_disk_usage))
                         ]);
+                        table.add_row(row![
+                            "Network rate",
+                            value_or_dash(info.net_rx_rate.zip(info.net_tx_rate).map(
+                                |(rx, tx)| format!(
+                                    "in {} / out {}",
+                                    format_rate(rx),
+                                    format_rate(tx)
+                                )
+                            ))
+                        ]);
+                        table.add_row(row![
+                            "Network traffic",
+                            value_or_dash(info.net_rx_bytes.zip(info.net_tx_bytes).map(
+                                |(rx, tx)| format!(
+                                    "in {} / out {}",
+                                    format_disk_usage(rx),
+                                    format_disk_usage(tx)
+                                )
+                            ))
+                        ]);
                         table.add_row(row!["Connected peers", value_or_dash(info.connected_peers)]);
                         table.add_row(row!["Shunned by", value_or_dash(info.shunned_count)]);
                         table.add_row(row!["kBuckets peers", value_or_dash(info.kbuckets_peers)]);
@@ -700,11 +1187,18 @@ impl CliCmdResponse {
                                     .map(|b| format_units(b, "ether").unwrap_or_default(),)
                             )
                         ]);
+                        table.add_row(row![
+                            "Estimated earnings",
+                            value_or_dash(
+                                info.estimated_earnings
+                                    .map(|b| format_units(b, "ether").unwrap_or_default(),)
+                            )
+                        ]);
                         table.add_row(row!["Port", value_or_dash(info.port)]);
                         table.add_row(row!["Metrics port", value_or_dash(info.metrics_port)]);
                         table.add_row(row![
                             "Rewards address",
-                            value_or_dash(info.rewards_addr.clone())
+                            format_rewards_addr(book, &info.rewards_addr)
                         ]);
                         table.add_row(row!["Peer Id", value_or_dash(info.peer_id.clone())]);
                         tables.push(table);
@@ -718,6 +1212,7 @@ impl CliCmdResponse {
                         "Records",
                         "Disk usage",
                         "Conn. peers",
+                        "Earned",
                         "Status"
                     ]);
                     for info in nodes {
@@ -728,6 +1223,10 @@ impl CliCmdResponse {
                             value_or_dash(info.records),
                             value_or_dash(info.disk_usage.map(format_disk_usage)),
                             value_or_dash(info.connected_peers),
+                            value_or_dash(
+                                info.estimated_earnings
+                                    .map(|b| format_units(b, "ether").unwrap_or_default())
+                            ),
                             format_node_status(info)
                         ]);
                     }
@@ -755,7 +1254,44 @@ impl CliCmdResponse {
 
                 tables.push(table);
             }
-            CliCmdResponse::Stats(stats) => {
+            CliCmdResponse::NodeUpdated(updated, book) => {
+                let info = &updated.info;
+                let mut table = Table::new();
+                table.set_titles(row![
+                    "Node Id",
+                    "Port",
+                    "Metrics port",
+                    "Rewards address",
+                    "IPv4-only",
+                    "Node logs",
+                    "Status"
+                ]);
+                table.add_row(row![
+                    info.node_id,
+                    value_or_dash(info.port),
+                    value_or_dash(info.metrics_port),
+                    format_rewards_addr(book, &info.rewards_addr),
+                    info.ipv4_only,
+                    if info.node_logs {
+                        info.log_level.to_string()
+                    } else {
+                        "disabled".to_string()
+                    },
+                    format_node_status(info)
+                ]);
+
+                tables.push(table);
+                if let Some(previous_node_id) = &updated.previous_node_id {
+                    let mut table = Table::new();
+                    table.set_format(*prettytable::format::consts::FORMAT_NO_BORDER);
+                    table.add_row(row![format!(
+                        "The node's container was recreated, its ID changed from {previous_node_id} to {}.",
+                        info.node_id
+                    )]);
+                    tables.push(table);
+                }
+            }
+            CliCmdResponse::Stats(stats, book) => {
                 let mut table = Table::new();
                 table.set_titles(row![
                     "Total balance",
@@ -775,13 +1311,158 @@ impl CliCmdResponse {
                     format_disk_usage(stats.used_disk_space)
                 ]);
                 tables.push(table);
+
+                if !stats.balances.is_empty() {
+                    let mut table = Table::new();
+                    table.set_titles(row!["Rewards address", "Balance", "Other balances"]);
+                    for (addr, balance) in stats.balances.iter() {
+                        let others = stats
+                            .other_balances
+                            .iter()
+                            .find(|(a, _)| a == addr)
+                            .map(|(_, others)| format_other_balances(others))
+                            .filter(|others| !others.is_empty());
+                        table.add_row(row![
+                            format_rewards_addr(book, &Some(addr.clone())),
+                            truncated_balance_str(*balance),
+                            value_or_dash(others)
+                        ]);
+                    }
+                    tables.push(table);
+                }
+            }
+            CliCmdResponse::Host(None, _) => {
+                let mut table = Table::new();
+                table.add_row(row!["Host resources usage not sampled yet."]);
+                tables.push(table);
             }
-            CliCmdResponse::Batches(batches) => {
+            CliCmdResponse::Host(Some(host), monthly_traffic) => {
+                let percentage = |v: Option<f64>| v.map(|v| format!("{v:.1}%"));
                 let mut table = Table::new();
-                table.set_titles(row!["Batch Id", "Action", "Status", "Interval", "Progress"]);
+                table.set_titles(row!["Resource", "Usage", "Details"]);
+                table.add_row(row![
+                    "CPU",
+                    format!("{:.1}%", host.cpu_usage),
+                    format!("{} cores", host.cpus)
+                ]);
+                table.add_row(row![
+                    "Memory",
+                    value_or_dash(percentage(host.mem_usage())),
+                    format!(
+                        "{} / {}",
+                        format_disk_usage(host.mem_used),
+                        format_disk_usage(host.mem_total)
+                    )
+                ]);
+                table.add_row(row![
+                    "Swap",
+                    value_or_dash(percentage(host.swap_usage())),
+                    format!(
+                        "{} / {}",
+                        format_disk_usage(host.swap_used),
+                        format_disk_usage(host.swap_total)
+                    )
+                ]);
+                table.add_row(row![
+                    "Load average",
+                    format!("{:.2}", host.load_avg[0]),
+                    format!(
+                        "{:.2} / {:.2} (5m / 15m)",
+                        host.load_avg[1], host.load_avg[2]
+                    )
+                ]);
+                table.add_row(row![
+                    "Disk I/O",
+                    format_rate(host.disk_read_rate + host.disk_write_rate),
+                    format!(
+                        "read {} / written {}",
+                        format_rate(host.disk_read_rate),
+                        format_rate(host.disk_write_rate)
+                    )
+                ]);
+                tables.push(table);
+
+                if !host.networks.is_empty() {
+                    let mut table = Table::new();
+                    table.set_titles(row!["Interface", "Received", "Transmitted"]);
+                    for net in host.networks.iter() {
+                        table.add_row(row![
+                            net.interface,
+                            format_rate(net.received_rate),
+                            format_rate(net.transmitted_rate)
+                        ]);
+                    }
+                    tables.push(table);
+                }
+
+                if let Some(traffic) = monthly_traffic {
+                    let mut table = Table::new();
+                    table.set_titles(row!["Month", "Received", "Transmitted", "Total", "Cap"]);
+                    table.add_row(row![
+                        traffic.month,
+                        format_disk_usage(traffic.received),
+                        format_disk_usage(traffic.transmitted),
+                        format_disk_usage(traffic.total()),
+                        value_or_dash(traffic.cap.zip(traffic.cap_usage()).map(
+                            |(cap, usage)| format!("{} ({usage:.1}% used)", format_disk_usage(cap))
+                        ))
+                    ]);
+                    tables.push(table);
+                }
+            }
+            CliCmdResponse::Addresses(summaries) => {
+                // all addresses are analysed over the same periods
+                let periods = summaries
+                    .iter()
+                    .find_map(|s| s.earnings.clone())
+                    .unwrap_or_default()
+                    .periods;
+                let mut table = Table::new();
+                let mut titles = ["Label", "Address", "Nodes", "Records", "Balance"]
+                    .into_iter()
+                    .map(Cell::new)
+                    .collect::<Vec<_>>();
+                titles.extend(
+                    periods
+                        .iter()
+                        .map(|p| Cell::new(&format!("Earned {}h", p.length_hours))),
+                );
+                table.set_titles(Row::new(titles));
+                for summary in summaries {
+                    let mut row = row![
+                        value_or_dash(summary.entry.as_ref().map(|e| e.label.clone())),
+                        summary.address,
+                        format!("{}/{}", summary.active_nodes, summary.nodes.len()),
+                        summary.records,
+                        value_or_dash(summary.balance.map(truncated_balance_str))
+                    ];
+                    for i in 0..periods.len() {
+                        let earned = summary
+                            .earnings
+                            .as_ref()
+                            .and_then(|e| e.periods.get(i))
+                            .map(|p| truncated_balance_str(p.total_earned));
+                        row.add_cell(Cell::new(&value_or_dash(earned)));
+                    }
+                    table.add_row(row);
+                }
+                tables.push(table);
+            }
+            CliCmdResponse::Batches(batches, all) => {
+                let mut table = Table::new();
+                if *all {
+                    table.set_titles(row![
+                        "Batch Id", "Action", "Status", "Interval", "Progress", "Started",
+                        "Finished"
+                    ]);
+                } else {
+                    table.set_titles(row!["Batch Id", "Action", "Status", "Interval", "Progress"]);
+                }
                 for batch in batches {
                     let (count, extra_detail) = match &batch.batch_type {
-                        BatchType::Create { node_opts, count } => (
+                        BatchType::Create {
+                            node_opts, count, ..
+                        } => (
                             *count,
                             format!(
                                 " (auto-start: {})",
@@ -794,15 +1475,44 @@ impl CliCmdResponse {
                         .checked_div(count)
                         .unwrap_or_default();
 
-                    table.add_row(row![
+                    let mut row = row![
                         batch.id,
                         format!("{}{}", batch.batch_type, extra_detail),
                         batch.status,
                         format!("{}s", batch.interval_secs),
                         format!("{}/{} ({}%)", batch.complete, count, progress)
-                    ]);
+                    ];
+                    if *all {
+                        row.add_cell(Cell::new(&value_or_dash(
+                            batch.started_at.map(format_timestamp),
+                        )));
+                        row.add_cell(Cell::new(&value_or_dash(
+                            batch.finished_at.map(format_timestamp),
+                        )));
+                    }
+                    table.add_row(row);
                 }
                 tables.push(table);
+
+                if *all {
+                    for batch in batches.iter().filter(|b| !b.items.is_empty()) {
+                        let mut table = Table::new();
+                        table.set_format(*format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+                        table.set_titles(row![
+                            format!("Batch {} ({})", batch.id, batch.batch_type),
+                            "Node Id",
+                            "Outcome"
+                        ]);
+                        for item in batch.items.iter() {
+                            table.add_row(row![
+                                format_timestamp(item.timestamp),
+                                value_or_dash(item.node_id.as_ref().map(|id| id.short_node_id())),
+                                item.error.as_deref().unwrap_or("OK")
+                            ]);
+                        }
+                        tables.push(table);
+                    }
+                }
             }
             CliCmdResponse::Settings(settings) => {
                 let mut table = Table::new();
@@ -820,6 +1530,22 @@ impl CliCmdResponse {
                     "Nodes metrics polling freq.",
                     format!("{:?}", settings.nodes_metrics_polling_freq)
                 ]);
+                table.add_row(row![
+                    "Nodes metrics polling concurrency",
+                    settings.nodes_metrics_polling_concurrency
+                ]);
+                table.add_row(row![
+                    "Raw metrics retention",
+                    format!("{} hours", settings.metrics_raw_retention_hours)
+                ]);
+                table.add_row(row![
+                    "1-minute metrics retention",
+                    format!("{} days", settings.metrics_minute_retention_days)
+                ]);
+                table.add_row(row![
+                    "1-hour metrics retention",
+                    format!("{} days", settings.metrics_hourly_retention_days)
+                ]);
                 table.add_row(row![
                     "Disk usage check freq.",
                     format!("{:?}", settings.disks_usage_check_freq)
@@ -833,15 +1559,60 @@ impl CliCmdResponse {
                     settings.rewards_monitoring_enabled
                 ]);
                 table.add_row(row!["L2 network RPC URL", settings.l2_network_rpc_url]);
+                table.add_row(row![
+                    "Fallback L2 network RPC URLs",
+                    settings.l2_network_rpc_fallback_urls.join(", ")
+                ]);
                 table.add_row(row![
                     "Token contract address",
                     settings.token_contract_address
                 ]);
+                table.add_row(row![
+                    "Additional token contracts",
+                    settings.additional_token_contracts.join(", ")
+                ]);
+                table.add_row(row!["Fiat currency", settings.fiat_currency]);
+                table.add_row(row!["Token price API URL", settings.token_price_api_url]);
+                table.add_row(row![
+                    "Earnings periods (hours)",
+                    format_earnings_periods(&settings.earnings_periods_hours)
+                ]);
+                table.add_row(row![
+                    "Earnings history retention (days)",
+                    settings.earnings_retention_days
+                ]);
+                table.add_row(row![
+                    "Earnings history start",
+                    value_or_dash(settings.earnings_history_start)
+                ]);
+                table.add_row(row![
+                    "Electricity price per kWh",
+                    settings.electricity_price_per_kwh
+                ]);
+                table.add_row(row!["Host power draw (watts)", settings.host_power_watts]);
+                table.add_row(row![
+                    "Power per CPU percentage (watts)",
+                    settings.watts_per_cpu_percent
+                ]);
+                table.add_row(row![
+                    "Disk cost per GB-month",
+                    settings.disk_cost_per_gb_month
+                ]);
+                table.add_row(row![
+                    "Monthly traffic cap (GB)",
+                    settings.monthly_traffic_cap_gb
+                ]);
                 table.add_row(row!["LCD display enabled", settings.lcd_display_enabled]);
                 table.add_row(row!["LCD device", settings.lcd_device]);
                 table.add_row(row!["LCD address", settings.lcd_addr]);
+                table.add_row(row!["InfluxDB push enabled", settings.influxdb_enabled]);
+                table.add_row(row!["InfluxDB write URL", settings.influxdb_url]);
+                table.add_row(row!["Graphite push enabled", settings.graphite_enabled]);
+                table.add_row(row!["Graphite address", settings.graphite_addr]);
                 tables.push(table);
             }
+            // exports are printed as they are rather than in a table
+            CliCmdResponse::EarningsExport(_) => {}
             CliCmdResponse::BatchCreated(batch_id) => {
                 let mut table = Table::new();
                 table.set_format(*prettytable::format::consts::FORMAT_NO_BORDER);
@@ -875,13 +1646,20 @@ fn format_node_status(info: &NodeInstanceInfo) -> String {
 
 // Helper to send request and parse response
 #[cfg(feature = "ssr")]
-async fn send_req<T: serde::de::DeserializeOwned>(url: &str, body: Option<String>) -> Result<T> {
+async fn send_req<T: serde::de::DeserializeOwned>(
+    url: &str,
+    body: Option<String>,
+    token: Option<&str>,
+) -> Result<T> {
     let client = reqwest::Client::new();
     let mut req_builder = client.post(url);
 
     if let Some(body) = body {
         req_builder = req_builder.body(body);
     }
+    if let Some(token) = token {
+        req_builder = req_builder.bearer_auth(token);
+    }
 
     let res = req_builder
         .send()
@@ -890,6 +1668,10 @@ async fn send_req<T: serde::de::DeserializeOwned>(url: &str, body: Option<String
 
     if res.status().is_success() {
         Ok(res.json::<T>().await?)
+    } else if res.status() == reqwest::StatusCode::UNAUTHORIZED {
+        Err(eyre!(
+            "Authentication required, log in with the 'login' command or provide a session token with '--token'"
+        ))
     } else {
         let err = res
             .text()
@@ -899,6 +1681,180 @@ async fn send_req<T: serde::de::DeserializeOwned>(url: &str, body: Option<String
     }
 }
 
+// Name of the file, within the user's config directory, where the session tokens are stored.
+#[cfg(feature = "ssr")]
+const CREDENTIALS_FILE: &str = "formicaio/credentials.json";
+
+// Path of the file where the session tokens obtained by the 'login' command are stored.
+#[cfg(feature = "ssr")]
+fn credentials_path() -> Result<PathBuf> {
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .ok_or_else(|| eyre!("Failed to find the user's config directory"))?;
+    Ok(config_dir.join(CREDENTIALS_FILE))
+}
+
+// Read the stored session tokens, by backend address.
+#[cfg(feature = "ssr")]
+fn read_credentials() -> std::collections::HashMap<String, String> {
+    credentials_path()
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+#[cfg(feature = "ssr")]
+fn write_credentials(credentials: &std::collections::HashMap<String, String>) -> Result<()> {
+    let path = credentials_path()?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .wrap_err_with(|| format!("Failed to create directory {}", dir.display()))?;
+    }
+    // the file is created only readable by the user, so the tokens are never exposed
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(&path)
+        .wrap_err_with(|| format!("Failed to open credentials file {}", path.display()))?;
+    // files created by previous versions may still be readable by others
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    }
+    file.write_all(serde_json::to_string_pretty(credentials)?.as_bytes())
+        .wrap_err_with(|| format!("Failed to write credentials to {}", path.display()))?;
+    Ok(())
+}
+
+/// Session token stored by the 'login' command for the backend at the given address.
+#[cfg(feature = "ssr")]
+pub fn stored_token(addr: SocketAddr) -> Option<String> {
+    read_credentials().remove(&addr.to_string())
+}
+
+/// Log in to the backend at the given address, storing the session token obtained
+/// so it's used by the following commands sent to it.
+/// The password is read from the terminal, without echoing it, when not provided.
+#[cfg(feature = "ssr")]
+pub async fn login_request(
+    addr: SocketAddr,
+    sub_cmds: &LoginSubcommands,
+) -> Result<CliCmdResponse> {
+    let password = match &sub_cmds.password {
+        Some(password) => password.clone(),
+        None => rpassword::prompt_password("Password: ")
+            .wrap_err("Failed to read password from the terminal")?,
+    };
+
+    let body = form_urlencoded::Serializer::new(String::new())
+        .append_pair("username", &sub_cmds.username)
+        .append_pair("password", &password)
+        .finish();
+    let res = reqwest::Client::new()
+        .post(format!("http://{addr}/api/auth/login"))
+        .header(
+            reqwest::header::CONTENT_TYPE,
+            "application/x-www-form-urlencoded",
+        )
+        .body(body)
+        .send()
+        .await
+        .map_err(|err| eyre!("Failed to send request: {err:?}"))?;
+
+    if !res.status().is_success() {
+        let err = res.text().await.wrap_err("Failed to decode response")?;
+        return Err(eyre!(err));
+    }
+
+    let cookie_prefix = format!("{}=", crate::types::SESSION_COOKIE_NAME);
+    let token = res
+        .headers()
+        .get_all(reqwest::header::SET_COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .find_map(|cookie| {
+            cookie
+                .split(';')
+                .next()
+                .and_then(|v| v.strip_prefix(&cookie_prefix))
+        })
+        .filter(|token| !token.is_empty())
+        .ok_or_else(|| eyre!("No session token was returned by the backend"))?
+        .to_string();
+
+    let mut credentials = read_credentials();
+    credentials.insert(addr.to_string(), token);
+    write_credentials(&credentials)?;
+
+    Ok(CliCmdResponse::Success)
+}
+
+/// Log out from the backend at the given address, ending the session and
+/// removing its stored token.
+#[cfg(feature = "ssr")]
+pub async fn logout_request(addr: SocketAddr, token: Option<&str>) -> Result<CliCmdResponse> {
+    // the stored token is removed even if the backend fails to end the session, e.g. if already expired
+    let mut credentials = read_credentials();
+    if credentials.remove(&addr.to_string()).is_some() {
+        write_credentials(&credentials)?;
+    }
+
+    if let Some(token) = token {
+        send_req::<()>(&format!("http://{addr}/api/auth/logout"), None, Some(token)).await?;
+    }
+
+    Ok(CliCmdResponse::Success)
+}
+
+// Helper to build the request params, under the given prefix, with the node configuration changes
+#[cfg(feature = "ssr")]
+fn config_changes_params(prefix: &str, changes: &NodeConfigUpdate) -> Vec<(String, String)> {
+    let mut params = vec![];
+    let mut push = |field: &str, value: String| params.push((format!("{prefix}[{field}]"), value));
+    if let Some(ipv4_only) = changes.ipv4_only {
+        push("ipv4_only", ipv4_only.to_string());
+    }
+    if let Some(port) = changes.port {
+        push("port", port.to_string());
+    }
+    if let Some(metrics_port) = changes.metrics_port {
+        push("metrics_port", metrics_port.to_string());
+    }
+    if let Some(rewards_addr) = &changes.rewards_addr {
+        push("rewards_addr", rewards_addr.clone());
+    }
+    if let Some(node_logs) = changes.node_logs {
+        push("node_logs", node_logs.to_string());
+    }
+    if let Some(log_level) = &changes.log_level {
+        push("log_level", format!("{log_level:?}"));
+    }
+    params
+}
+
+// Helper to serialise request params into a request body, encoding only their values
+#[cfg(feature = "ssr")]
+fn to_req_body(params: &[(String, String)]) -> String {
+    params
+        .iter()
+        .map(|(key, value)| {
+            format!(
+                "{key}={}",
+                form_urlencoded::byte_serialize(value.as_bytes()).collect::<String>()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
 // Helper to send node action request
 #[cfg(feature = "ssr")]
 async fn send_node_action_req(
@@ -908,6 +1864,7 @@ async fn send_node_action_req(
     status: &[NodeStatusFilter],
     interval: u64,
     action_type: &str,
+    token: Option<&str>,
 ) -> Result<CliCmdResponse> {
     if node_ids.len() > 1 || !status.is_empty() {
         // create batch for multiple ids and status
@@ -920,14 +1877,14 @@ async fn send_node_action_req(
             body = format!("{body}batch_on_match[{action_type}][status][{i}]={s:?}&");
         }
         let body = format!("{body}interval_secs={interval}");
-        let batch_id = send_req::<u16>(batch_url, Some(body)).await?;
+        let batch_id = send_req::<u16>(batch_url, Some(body), token).await?;
         Ok(CliCmdResponse::BatchCreated(batch_id))
     } else if let Some(node_id) = node_ids.first() {
         let body = format!("node_id={node_id}");
-        send_req::<()>(url, Some(body)).await?;
+        send_req::<()>(url, Some(body), token).await?;
         Ok(CliCmdResponse::Success)
     } else {
-        send_req::<()>(url, None).await?;
+        send_req::<()>(url, None, token).await?;
         Ok(CliCmdResponse::Success)
     }
 }
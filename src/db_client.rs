@@ -1,11 +1,15 @@
 use super::types::{
-    AgentEvent, AgentEventType, AppSettings, Metrics, MetricsMode, NodeId, NodeInstanceInfo,
-    NodeMetric, NodePid, NodeStatus,
+    AddressBook, AddressBookEntry, AgentEvent, AgentEventType, Alert, AlertRule, AlertState,
+    AppSettings, CurrentUser, DEFAULT_EARNINGS_PERIODS_HOURS, EarningsExportRow, HOST_METRICS_ID,
+    METRIC_KEY_CPU_USAGE, METRIC_KEY_MEM_USED_MB, Metrics, MetricsMode, MetricsResolution, NodeId,
+    NodeInstanceInfo, NodeMetric, NodePid, NodeStatus, NodesActionsBatch, NotificationChannel,
+    Stats, StatsSnapshot, UserInfo, UserRole, format_earnings_periods, parse_earnings_periods,
+    parse_rpc_urls, parse_token_addresses,
 };
-use crate::bg_tasks::PaymentRecord;
+use crate::bg_tasks::{NodeEarning, NodeRewardsDelta, PaymentRecord, ResourceUsageSample};
 
 use alloy_primitives::{Address, U256};
-use chrono::{TimeZone, Utc};
+use chrono::{NaiveDate, TimeZone, Utc};
 use leptos::logging;
 use semver::Version;
 use serde::{Deserialize, Serialize};
@@ -34,6 +38,8 @@ pub enum DbError {
     NodeIsBatched,
     #[error("Invalid node id")]
     InvalidNodeId,
+    #[error("At least one admin user is required")]
+    LastAdmin,
 }
 
 // Sqlite DB filename.
@@ -42,6 +48,12 @@ const SQLITE_DB_FILENAME: &str = "formicaio.db";
 const DB_PATH: &str = "DB_PATH";
 // Default path for the DB file.
 const DEFAULT_DB_PATH: &str = "./";
+// Maximum number of metrics records inserted with a single statement, keeping
+// the number of bound parameters within SQLite's limits.
+const METRICS_INSERT_CHUNK_SIZE: usize = 1_000;
+// Time (in millis) we wait after a bucket closes before rolling it up, so samples
+// collected by an ongoing polling cycle can still make it into the bucket.
+const METRICS_ROLLUP_GRACE_MILLIS: i64 = 10_000;
 
 // Struct stored on the DB with application settings.
 #[derive(Clone, Debug, Deserialize, FromRow, Serialize)]
@@ -54,7 +66,19 @@ struct CachedSettings {
     rewards_balances_retrieval_freq_secs: u64,
     rewards_monitoring_enabled: bool,
     l2_network_rpc_url: String,
+    l2_network_rpc_fallback_urls: String,
     token_contract_address: String,
+    additional_token_contracts: String,
+    fiat_currency: String,
+    token_price_api_url: String,
+    earnings_periods_hours: String,
+    earnings_retention_days: i64,
+    earnings_history_start: Option<String>,
+    electricity_price_per_kwh: f64,
+    host_power_watts: f64,
+    watts_per_cpu_percent: f64,
+    disk_cost_per_gb_month: f64,
+    monthly_traffic_cap_gb: i64,
     lcd_display_enabled: bool,
     lcd_device: String,
     lcd_addr: String,
@@ -71,6 +95,15 @@ struct CachedSettings {
     autonomous_max_actions_per_cycle: i64,
     metrics_mode: i64,
     node_bin_download_url: Option<String>,
+    metrics_raw_retention_hours: i64,
+    metrics_minute_retention_days: i64,
+    metrics_hourly_retention_days: i64,
+    influxdb_enabled: bool,
+    influxdb_url: String,
+    influxdb_token: String,
+    graphite_enabled: bool,
+    graphite_addr: String,
+    nodes_metrics_polling_concurrency: i64,
 }
 
 // Struct stored on the DB caching nodes metadata.
@@ -196,6 +229,106 @@ struct CachedEarnings {
     timestamp: i64,
 }
 
+// Struct stored on the DB with an alert rule.
+#[derive(Clone, Debug, Deserialize, FromRow, Serialize)]
+struct CachedAlertRule {
+    id: i64,
+    name: String,
+    metric: String,
+    operator: String,
+    threshold: f64,
+    hysteresis: f64,
+    for_secs: i64,
+    severity: String,
+    enabled: bool,
+    silenced_until: Option<i64>,
+}
+
+// Struct stored on the DB with an alert fired by a rule.
+#[derive(Clone, Debug, Deserialize, FromRow, Serialize)]
+struct CachedAlert {
+    id: i64,
+    rule_id: i64,
+    rule_name: String,
+    severity: String,
+    node_id: Option<String>,
+    value: f64,
+    message: String,
+    fired_at: i64,
+    resolved_at: Option<i64>,
+    acked_at: Option<i64>,
+    silenced: bool,
+}
+
+// Struct stored on the DB with the settings of a notification channel.
+#[derive(Clone, Debug, Deserialize, FromRow, Serialize)]
+struct CachedNotificationChannel {
+    id: i64,
+    name: String,
+    // JSON-encoded target service and its connection details
+    target: String,
+    // Comma-separated list of the kinds of events sent through the channel
+    events: String,
+    enabled: bool,
+}
+
+// Struct stored on the DB with a nodes actions batch and its progress.
+#[derive(Clone, Debug, Deserialize, FromRow, Serialize)]
+struct CachedNodesActionsBatch {
+    id: i64,
+    // JSON-encoded type of batch with the nodes it acts on
+    batch_type: String,
+    interval_secs: i64,
+    // JSON-encoded status of the batch
+    status: String,
+    complete: i64,
+    // JSON-encoded list of the outcome of each action performed
+    items: String,
+    created_at: i64,
+    started_at: Option<i64>,
+    finished_at: Option<i64>,
+}
+
+impl CachedNodesActionsBatch {
+    fn into_batch(self) -> Option<NodesActionsBatch> {
+        Some(NodesActionsBatch {
+            id: self.id as u16,
+            status: serde_json::from_str(&self.status).ok()?,
+            batch_type: serde_json::from_str(&self.batch_type).ok()?,
+            interval_secs: self.interval_secs as u64,
+            complete: self.complete as u16,
+            items: serde_json::from_str(&self.items).unwrap_or_default(),
+            created_at: self.created_at,
+            started_at: self.started_at,
+            finished_at: self.finished_at,
+        })
+    }
+}
+
+// Struct stored on the DB with the label, note and colour of a rewards address.
+#[derive(Clone, Debug, Deserialize, FromRow, Serialize)]
+struct CachedAddressBookEntry {
+    address: String,
+    label: String,
+    note: String,
+    color: String,
+}
+
+// Struct retrieved from the DB with the stats snapshots averaged over a bucket.
+#[derive(Clone, Debug, Deserialize, FromRow, Serialize)]
+struct CachedStatsSnapshot {
+    timestamp: i64,
+    total_nodes: f64,
+    active_nodes: f64,
+    connected_peers: f64,
+    shunned_count: f64,
+    estimated_net_size: f64,
+    stored_records: f64,
+    relevant_records: f64,
+    used_disk_space: f64,
+    available_disk_space: f64,
+}
+
 // Client to interface with the local Sqlite database
 #[derive(Clone, Debug)]
 pub struct DbClient {
@@ -751,115 +884,575 @@ impl DbClient {
         .await
     }
 
-    // Retrieve node metrics from local cache DB
-    pub async fn get_node_metrics(&self, node_id: NodeId, since: Option<i64>) -> Metrics {
-        let db_lock = self.db.lock().await;
-        let mut node_metrics = Metrics::new();
+    // Convenient method to update the configuration fields of a node,
+    // i.e. the ones used as arguments when launching the node.
+    pub async fn update_node_config(&self, info: &NodeInstanceInfo) {
+        let bool_str = |v: bool| if v { "1" } else { "0" };
+        let port = info.port.map(|p| p.to_string()).unwrap_or_default();
+        let metrics_port = info.metrics_port.map(|p| p.to_string()).unwrap_or_default();
+        let log_level = info.log_level.to_string();
+        self.update_node_metadata_fields(
+            &info.node_id,
+            &[
+                ("ipv4_only", bool_str(info.ipv4_only)),
+                ("port", &port),
+                ("metrics_port", &metrics_port),
+                (
+                    "rewards_addr",
+                    info.rewards_addr.as_deref().unwrap_or_default(),
+                ),
+                ("node_logs", bool_str(info.node_logs)),
+                ("log_level", &log_level),
+            ],
+        )
+        .await
+    }
 
-        match sqlx::query(
-            "SELECT * FROM nodes_metrics WHERE node_id LIKE ? || '%' AND timestamp > ? ORDER BY timestamp",
+    // Move all records of a node, including its metrics, to a new node id.
+    pub async fn update_node_id(&self, node_id: &NodeId, new_node_id: &NodeId) {
+        let db_lock = self.db.lock().await;
+        if let Err(err) = sqlx::query("UPDATE nodes SET node_id = ? WHERE node_id LIKE ? || '%'")
+            .bind(new_node_id.to_string())
+            .bind(node_id.to_string())
+            .execute(&*db_lock)
+            .await
+        {
+            logging::error!("[ERROR][DB] Database update error while updating node id: {err}");
+        }
+        for table in [
+            "nodes_metrics",
+            "nodes_metrics_rollups",
+            "nodes_rewards_deltas",
+            "nodes_earnings",
+        ] {
+            let query_str = format!("UPDATE {table} SET node_id = ? WHERE node_id GLOB ?");
+            if let Err(err) = sqlx::query(sqlx::AssertSqlSafe(query_str))
+                .bind(new_node_id.to_string())
+                .bind(format!("{node_id}*"))
+                .execute(&*db_lock)
+                .await
+            {
+                logging::error!(
+                    "[ERROR][DB] Database update error while updating node id in {table}: {err}"
+                )
+            }
+        }
+        // batches reference the nodes by their full id within their JSON-encoded fields
+        if let Err(err) = sqlx::query(
+            "UPDATE nodes_actions_batches SET \
+            batch_type = REPLACE(batch_type, ?1, ?2), items = REPLACE(items, ?1, ?2)",
         )
         .bind(node_id.to_string())
-        .bind(since.unwrap_or_default())
-        .fetch_all(&*db_lock)
+        .bind(new_node_id.to_string())
+        .execute(&*db_lock)
         .await
         {
+            logging::error!(
+                "[ERROR][DB] Database update error while updating node id in batches: {err}"
+            )
+        }
+    }
+
+    // Retrieve node metrics from local cache DB with the given resolution, and
+    // whose timestamp is within the 'from' (exclusive) and 'to' (exclusive) range.
+    pub async fn get_node_metrics(
+        &self,
+        node_id: NodeId,
+        resolution: MetricsResolution,
+        from: i64,
+        to: i64,
+    ) -> Metrics {
+        self.get_metrics(format!("{node_id}*"), resolution, from, to)
+            .await
+    }
+
+    // Retrieve host metrics from local cache DB with the given resolution, and
+    // whose timestamp is within the 'from' (exclusive) and 'to' (exclusive) range.
+    pub async fn get_host_metrics(
+        &self,
+        resolution: MetricsResolution,
+        from: i64,
+        to: i64,
+    ) -> Metrics {
+        self.get_metrics(HOST_METRICS_ID.to_string(), resolution, from, to)
+            .await
+    }
+
+    // Retrieve the metrics stored with an id matching the given glob pattern.
+    async fn get_metrics(
+        &self,
+        id_pattern: String,
+        resolution: MetricsResolution,
+        from: i64,
+        to: i64,
+    ) -> Metrics {
+        let db_lock = self.db.lock().await;
+        let mut node_metrics = Metrics::new();
+
+        let res = match resolution {
+            MetricsResolution::Raw => {
+                sqlx::query(
+                    "SELECT timestamp, key, value FROM nodes_metrics \
+                    WHERE node_id GLOB ? AND timestamp > ? AND timestamp < ? \
+                    ORDER BY timestamp",
+                )
+                .bind(&id_pattern)
+                .bind(from)
+                .bind(to)
+                .fetch_all(&*db_lock)
+                .await
+            }
+            MetricsResolution::Minute | MetricsResolution::Hour => {
+                sqlx::query(
+                    "SELECT timestamp, key, value FROM nodes_metrics_rollups \
+                    WHERE node_id GLOB ? AND resolution = ? AND timestamp > ? AND timestamp < ? \
+                    ORDER BY timestamp",
+                )
+                .bind(&id_pattern)
+                .bind(resolution.bucket_secs())
+                .bind(from)
+                .bind(to)
+                .fetch_all(&*db_lock)
+                .await
+            }
+        };
+
+        match res {
             Ok(metrics) => {
                 metrics.into_iter().for_each(|m| {
                     let key: String = m.get("key");
+                    let value: f64 = m.get("value");
                     let entry = node_metrics.entry(key.clone()).or_default();
                     entry.push(NodeMetric {
                         timestamp: m.get("timestamp"),
                         key,
-                        value: m.get("value"),
+                        value: value.to_string(),
                     });
                 });
             }
             Err(err) => {
-                logging::error!("[ERROR][DB] Database query error while retrieving node metrics: {err}")
+                logging::error!(
+                    "[ERROR][DB] Database query error while retrieving node metrics: {err}"
+                )
             }
         }
 
         node_metrics
     }
 
-    // Store node metrics onto local cache DB
-    pub async fn store_node_metrics(
+    // Retrieve the hourly averages of CPU and memory usage of all nodes, whose
+    // timestamp (in millis) is within the 'from' (exclusive) and 'to' (exclusive) range.
+    pub async fn get_nodes_resources_usage(
         &self,
-        node_id: &NodeId,
-        metrics: impl IntoIterator<Item = &NodeMetric>,
-    ) {
-        let metrics = metrics.into_iter().collect::<Vec<_>>();
-        if metrics.is_empty() {
-            return;
+        from: i64,
+        to: i64,
+    ) -> Result<Vec<ResourceUsageSample>, DbError> {
+        let db_lock = self.db.lock().await;
+        let res = sqlx::query(
+            "SELECT node_id, timestamp, \
+                MAX(CASE WHEN key = ?1 THEN value END) AS cpu_usage, \
+                MAX(CASE WHEN key = ?2 THEN value END) AS mem_used_mb \
+            FROM nodes_metrics_rollups \
+            WHERE resolution = ?3 AND key IN (?1, ?2) AND timestamp > ?4 AND timestamp < ?5 \
+            GROUP BY node_id, timestamp ORDER BY timestamp",
+        )
+        .bind(METRIC_KEY_CPU_USAGE)
+        .bind(METRIC_KEY_MEM_USED_MB)
+        .bind(MetricsResolution::Hour.bucket_secs())
+        .bind(from)
+        .bind(to)
+        .fetch_all(&*db_lock)
+        .await;
+
+        match res {
+            Ok(rows) => Ok(rows
+                .into_iter()
+                .map(|row| ResourceUsageSample {
+                    node_id: row.get("node_id"),
+                    timestamp: row.get("timestamp"),
+                    cpu_usage: row.get("cpu_usage"),
+                    mem_used_mb: row.get("mem_used_mb"),
+                })
+                .collect()),
+            Err(err) => {
+                logging::error!(
+                    "[ERROR][DB] Database query error while retrieving nodes resources usage: {err}"
+                );
+                Err(err.into())
+            }
         }
+    }
 
-        let mut query_builder =
-            QueryBuilder::new("INSERT INTO nodes_metrics (node_id, timestamp, key, value) ");
+    // Store the metrics collected from all nodes during a polling cycle, within a single transaction.
+    // Samples whose value is not numeric are discarded.
+    pub async fn store_nodes_metrics(&self, metrics: &[(NodeId, NodeMetric)]) {
+        let samples = metrics
+            .iter()
+            .filter_map(|(node_id, m)| {
+                m.value
+                    .parse::<f64>()
+                    .ok()
+                    .map(|value| (node_id.to_string(), m.timestamp, &m.key, value))
+            })
+            .collect::<Vec<_>>();
+        self.insert_metrics(&samples, "nodes").await;
+    }
 
-        query_builder.push_values(metrics, |mut b, metric| {
-            b.push_bind(node_id.to_string())
-                .push_bind(metric.timestamp)
-                .push_bind(metric.key.clone())
-                .push_bind(metric.value.clone());
-        });
+    // Store the host metrics sampled, alongside the nodes metrics so they are
+    // rolled up and pruned with the same retention.
+    pub async fn store_host_metrics(&self, metrics: &[NodeMetric]) {
+        let samples = metrics
+            .iter()
+            .filter_map(|m| {
+                m.value
+                    .parse::<f64>()
+                    .ok()
+                    .map(|value| (HOST_METRICS_ID.to_string(), m.timestamp, &m.key, value))
+            })
+            .collect::<Vec<_>>();
+        self.insert_metrics(&samples, "host").await;
+    }
+
+    // Insert the (id, timestamp, key, value) metrics samples within a single transaction.
+    async fn insert_metrics(&self, samples: &[(String, i64, &String, f64)], kind: &str) {
+        if samples.is_empty() {
+            return;
+        }
 
         let db_lock = self.db.lock().await;
-        match query_builder.build().execute(&*db_lock).await {
-            Ok(_) => {}
+        let mut tx = match db_lock.begin().await {
+            Ok(tx) => tx,
             Err(err) => {
                 logging::error!(
-                    "[ERROR][DB] Database insert error while storing node metrics: {err}."
+                    "[ERROR][DB] Database transaction error while storing {kind} metrics: {err}"
+                );
+                return;
+            }
+        };
+
+        for chunk in samples.chunks(METRICS_INSERT_CHUNK_SIZE) {
+            let mut query_builder =
+                QueryBuilder::new("INSERT INTO nodes_metrics (node_id, timestamp, key, value) ");
+            query_builder.push_values(chunk, |mut b, (id, timestamp, key, value)| {
+                b.push_bind(id.clone())
+                    .push_bind(*timestamp)
+                    .push_bind(key.to_string())
+                    .push_bind(*value);
+            });
+
+            if let Err(err) = query_builder.build().execute(&mut *tx).await {
+                logging::error!(
+                    "[ERROR][DB] Database insert error while storing {kind} metrics: {err}"
+                );
+                return;
+            }
+        }
+
+        // samples may land in buckets already rolled up, e.g. if the polling cycle took long
+        let oldest = samples.iter().map(|(_, ts, _, _)| *ts).min();
+        if let Err(err) = sqlx::query(
+            "UPDATE metrics_rollups_state SET unrolled_from = MIN(unrolled_from, ?) \
+            WHERE resolution = ?",
+        )
+        .bind(oldest)
+        .bind(MetricsResolution::Minute.bucket_secs())
+        .execute(&mut *tx)
+        .await
+        {
+            logging::error!(
+                "[ERROR][DB] Database update error while storing {kind} metrics rollup state: {err}"
+            );
+            return;
+        }
+
+        match tx.commit().await {
+            Ok(()) => {}
+            Err(err) => logging::error!(
+                "[ERROR][DB] Database commit error while storing {kind} metrics: {err}"
+            ),
+        }
+    }
+
+    // Add the bytes received/transmitted by the host to the traffic accounted for the given month
+    pub async fn add_network_traffic(&self, month: &str, received: u64, transmitted: u64) {
+        let db_lock = self.db.lock().await;
+        if let Err(err) = sqlx::query(
+            "INSERT INTO network_traffic (month, received_bytes, transmitted_bytes) VALUES (?, ?, ?) \
+            ON CONFLICT(month) DO UPDATE SET \
+            received_bytes = received_bytes + excluded.received_bytes, \
+            transmitted_bytes = transmitted_bytes + excluded.transmitted_bytes",
+        )
+        .bind(month)
+        .bind(received as i64)
+        .bind(transmitted as i64)
+        .execute(&*db_lock)
+        .await
+        {
+            logging::error!(
+                "[ERROR][DB] Database insert error while storing network traffic: {err}"
+            );
+        }
+    }
+
+    // Retrieve the (received, transmitted) bytes accounted for the given month
+    pub async fn get_network_traffic(&self, month: &str) -> (u64, u64) {
+        let db_lock = self.db.lock().await;
+        match sqlx::query(
+            "SELECT received_bytes, transmitted_bytes FROM network_traffic WHERE month = ?",
+        )
+        .bind(month)
+        .fetch_optional(&*db_lock)
+        .await
+        {
+            Ok(row) => row.map_or((0, 0), |r| {
+                (
+                    r.get::<i64, _>("received_bytes") as u64,
+                    r.get::<i64, _>("transmitted_bytes") as u64,
                 )
+            }),
+            Err(err) => {
+                logging::error!(
+                    "[ERROR][DB] Database error while retrieving network traffic: {err}"
+                );
+                (0, 0)
             }
         }
     }
 
-    // Remove node metrics from local cache DB
+    // Remove node metrics, including their rollups, from local cache DB
     pub async fn delete_node_metrics(&self, node_id: &NodeId) {
         let db_lock = self.db.lock().await;
-        match sqlx::query("DELETE FROM nodes_metrics WHERE node_id LIKE ? || '%'")
-            .bind(node_id.to_string())
-            .execute(&*db_lock)
-            .await
-        {
-            Ok(_) => {}
+        for table in [
+            "nodes_metrics",
+            "nodes_metrics_rollups",
+            "nodes_rewards_deltas",
+            "nodes_earnings",
+        ] {
+            let query_str = format!("DELETE FROM {table} WHERE node_id GLOB ?");
+            if let Err(err) = sqlx::query(sqlx::AssertSqlSafe(query_str))
+                .bind(format!("{node_id}*"))
+                .execute(&*db_lock)
+                .await
+            {
+                logging::error!(
+                    "[ERROR][DB] Database delete error while removing node metrics from {table}: {err}"
+                )
+            }
+        }
+    }
+
+    // Average all metrics samples, for every node, over buckets of the given resolution.
+    // Only buckets which are complete and haven't been rolled up since they last got samples
+    // are computed: 1-minute buckets are computed from raw samples, while 1-hour buckets
+    // from 1-minute buckets, thus recomputing a 1-minute bucket flags its 1-hour one as well.
+    pub async fn rollup_metrics(&self, resolution: MetricsResolution, now: i64) {
+        let (source, source_resolution) = match resolution {
+            MetricsResolution::Raw => return,
+            MetricsResolution::Minute => ("nodes_metrics", None),
+            MetricsResolution::Hour => (
+                "nodes_metrics_rollups",
+                Some(MetricsResolution::Minute.bucket_secs()),
+            ),
+        };
+        let bucket_millis = resolution.bucket_secs() * 1_000;
+
+        let db_lock = self.db.lock().await;
+        let res: Result<Option<u64>, sqlx::Error> = async {
+            let mut tx = db_lock.begin().await?;
+            let unrolled_from: Option<i64> = sqlx::query_scalar(
+                "SELECT unrolled_from FROM metrics_rollups_state WHERE resolution = ?",
+            )
+            .bind(resolution.bucket_secs())
+            .fetch_optional(&mut *tx)
+            .await?;
+
+            let from = (unrolled_from.unwrap_or_default() / bucket_millis) * bucket_millis;
+            let to = ((now - METRICS_ROLLUP_GRACE_MILLIS) / bucket_millis) * bucket_millis;
+            if from >= to {
+                return Ok(None);
+            }
+
+            let query_str = format!(
+                "INSERT OR REPLACE INTO nodes_metrics_rollups (node_id, resolution, timestamp, key, value) \
+                    SELECT node_id, ?, (timestamp / ?) * ? AS bucket, key, AVG(value) \
+                    FROM {source} WHERE timestamp >= ? AND timestamp < ? {} \
+                    GROUP BY node_id, bucket, key",
+                if source_resolution.is_some() {
+                    "AND resolution = ?"
+                } else {
+                    ""
+                }
+            );
+            let mut query = sqlx::query(sqlx::AssertSqlSafe(query_str))
+                .bind(resolution.bucket_secs())
+                .bind(bucket_millis)
+                .bind(bucket_millis)
+                .bind(from)
+                .bind(to);
+            if let Some(source_resolution) = source_resolution {
+                query = query.bind(source_resolution);
+            }
+            let rows = query.execute(&mut *tx).await?.rows_affected();
+
+            sqlx::query(
+                "INSERT OR REPLACE INTO metrics_rollups_state (resolution, unrolled_from) \
+                VALUES (?, ?)",
+            )
+            .bind(resolution.bucket_secs())
+            .bind(to)
+            .execute(&mut *tx)
+            .await?;
+            if resolution == MetricsResolution::Minute {
+                sqlx::query(
+                    "UPDATE metrics_rollups_state SET unrolled_from = MIN(unrolled_from, ?) \
+                    WHERE resolution = ?",
+                )
+                .bind(from)
+                .bind(MetricsResolution::Hour.bucket_secs())
+                .execute(&mut *tx)
+                .await?;
+            }
+
+            tx.commit().await?;
+            Ok(Some(rows))
+        }
+        .await;
+
+        match res {
+            Ok(Some(rows)) => logging::log!(
+                "[DB] Rolled up {rows} metrics records with {resolution:?} resolution"
+            ),
+            Ok(None) => {}
+            Err(err) => {
+                logging::error!("[ERROR][DB] Database insert error while rolling up metrics: {err}")
+            }
+        }
+    }
+
+    // Remove all nodes metrics of the given resolution older than the provided timestamp (in millis)
+    pub async fn remove_metrics_older_than(&self, resolution: MetricsResolution, timestamp: i64) {
+        let db_lock = self.db.lock().await;
+        let res = match resolution {
+            MetricsResolution::Raw => {
+                sqlx::query("DELETE FROM nodes_metrics WHERE timestamp < ?")
+                    .bind(timestamp)
+                    .execute(&*db_lock)
+                    .await
+            }
+            MetricsResolution::Minute | MetricsResolution::Hour => {
+                sqlx::query(
+                    "DELETE FROM nodes_metrics_rollups WHERE resolution = ? AND timestamp < ?",
+                )
+                .bind(resolution.bucket_secs())
+                .bind(timestamp)
+                .execute(&*db_lock)
+                .await
+            }
+        };
+
+        match res {
+            Ok(res) => logging::log!(
+                "[DB] Removed {} metrics records with {resolution:?} resolution",
+                res.rows_affected()
+            ),
             Err(err) => {
                 logging::error!(
-                    "[ERROR][DB] Database delete error while removing node metrics: {err}"
+                    "[ERROR][DB] Database delete error while pruning old metrics: {err}"
                 )
             }
         }
     }
 
-    // Remove metrics for a node so there are no more than max_size records
-    pub async fn remove_oldest_metrics(&self, node_id: NodeId, max_size: usize) {
+    // Store a snapshot of the aggregated stats of all nodes
+    pub async fn store_stats_snapshot(&self, stats: &Stats, timestamp: i64) {
         let db_lock = self.db.lock().await;
         match sqlx::query(
-            "DELETE FROM nodes_metrics WHERE \
-                node_id LIKE ? || '%' AND timestamp <= \
-                    (SELECT DISTINCT timestamp \
-                        FROM nodes_metrics \
-                        WHERE node_id LIKE ? || '%' \
-                        ORDER BY timestamp DESC \
-                        LIMIT 1 OFFSET ? \
-                    )",
+            "INSERT OR REPLACE INTO stats_history (\
+                timestamp, total_nodes, active_nodes, connected_peers, shunned_count, \
+                estimated_net_size, stored_records, relevant_records, used_disk_space, \
+                available_disk_space\
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
         )
-        .bind(node_id.to_string())
-        .bind(node_id.to_string())
-        .bind(max_size as i64)
+        .bind(timestamp)
+        .bind(stats.total_nodes as i64)
+        .bind(stats.active_nodes as i64)
+        .bind(stats.connected_peers as i64)
+        .bind(stats.shunned_count as i64)
+        .bind(stats.estimated_net_size as i64)
+        .bind(stats.stored_records as i64)
+        .bind(stats.relevant_records as i64)
+        .bind(stats.used_disk_space as i64)
+        .bind(stats.available_disk_space as i64)
         .execute(&*db_lock)
         .await
         {
-            Ok(res) => logging::log!("[DB] Removed {} metrics records", res.rows_affected()),
+            Ok(_) => {}
+            Err(err) => logging::error!(
+                "[ERROR][DB] Database insert error while storing stats snapshot: {err}"
+            ),
+        }
+    }
+
+    // Retrieve the stats snapshots taken after the given timestamp, averaged into buckets of given size
+    pub async fn get_stats_history(&self, since: i64, bucket_millis: i64) -> Vec<StatsSnapshot> {
+        let db_lock = self.db.lock().await;
+        let res = sqlx::query_as::<_, CachedStatsSnapshot>(
+            "SELECT (timestamp / ?1) * ?1 AS timestamp, \
+                AVG(total_nodes) AS total_nodes, \
+                AVG(active_nodes) AS active_nodes, \
+                AVG(connected_peers) AS connected_peers, \
+                AVG(shunned_count) AS shunned_count, \
+                AVG(estimated_net_size) AS estimated_net_size, \
+                AVG(stored_records) AS stored_records, \
+                AVG(relevant_records) AS relevant_records, \
+                AVG(used_disk_space) AS used_disk_space, \
+                AVG(available_disk_space) AS available_disk_space \
+            FROM stats_history WHERE timestamp > ?2 \
+            GROUP BY timestamp / ?1 ORDER BY timestamp",
+        )
+        .bind(bucket_millis.max(1))
+        .bind(since)
+        .fetch_all(&*db_lock)
+        .await;
+
+        match res {
+            Ok(snapshots) => snapshots
+                .into_iter()
+                .map(|s| StatsSnapshot {
+                    timestamp: s.timestamp,
+                    total_nodes: s.total_nodes,
+                    active_nodes: s.active_nodes,
+                    connected_peers: s.connected_peers,
+                    shunned_count: s.shunned_count,
+                    estimated_net_size: s.estimated_net_size,
+                    stored_records: s.stored_records,
+                    relevant_records: s.relevant_records,
+                    used_disk_space: s.used_disk_space,
+                    available_disk_space: s.available_disk_space,
+                })
+                .collect(),
             Err(err) => {
-                logging::error!(
-                    "[ERROR][DB] Database delete error while pruning old metrics: {err}"
-                )
+                logging::error!("[ERROR][DB] Database error while retrieving stats history: {err}");
+                vec![]
             }
         }
     }
 
+    // Remove the stats snapshots older than the given timestamp
+    pub async fn remove_stats_history_older_than(&self, timestamp: i64) {
+        let db_lock = self.db.lock().await;
+        match sqlx::query("DELETE FROM stats_history WHERE timestamp < ?")
+            .bind(timestamp)
+            .execute(&*db_lock)
+            .await
+        {
+            Ok(res) => logging::log!("[DB] Removed {} stats history records", res.rows_affected()),
+            Err(err) => logging::error!(
+                "[ERROR][DB] Database delete error while pruning stats history: {err}"
+            ),
+        }
+    }
+
     // Store earnings (reward payment) for an address with block number and timestamp
     pub async fn store_earnings(
         &self,
@@ -955,38 +1548,384 @@ impl DbClient {
                 )
             }
         }
+
+        if let Err(err) = sqlx::query("DELETE FROM nodes_earnings WHERE block_number <= ?")
+            .bind(max_block_number as i64)
+            .execute(&*db_lock)
+            .await
+        {
+            logging::error!(
+                "[ERROR][DB] Database delete error while pruning old nodes earnings: {err}"
+            )
+        }
+
+        // history of all addresses now starts after the pruned blocks
+        if let Err(err) =
+            sqlx::query("UPDATE earnings_sync SET from_block = ? WHERE from_block <= ?")
+                .bind(max_block_number as i64 + 1)
+                .bind(max_block_number as i64)
+                .execute(&*db_lock)
+                .await
+        {
+            logging::error!(
+                "[ERROR][DB] Database update error while pruning earnings sync status: {err}"
+            )
+        }
     }
 
-    // Retrieve the settings values
-    pub async fn get_settings(&self) -> AppSettings {
+    // Retrieve the earliest block the earnings history of an address has been fetched from
+    pub async fn get_earnings_synced_from(&self, address: &Address) -> Option<u64> {
         let db_lock = self.db.lock().await;
-        match sqlx::query_as::<_, CachedSettings>("SELECT * FROM settings")
-            .fetch_all(&*db_lock)
+        match sqlx::query("SELECT from_block FROM earnings_sync WHERE address = ?")
+            .bind(address.to_string())
+            .fetch_optional(&*db_lock)
             .await
-            .map(|s| s.first().cloned())
         {
-            Ok(Some(s)) => AppSettings {
-                nodes_auto_upgrade: s.nodes_auto_upgrade,
-                nodes_auto_upgrade_delay: Duration::from_secs(s.nodes_auto_upgrade_delay_secs),
-                node_bin_version_polling_freq: Duration::from_secs(
-                    s.node_bin_version_polling_freq_secs,
-                ),
-                nodes_metrics_polling_freq: Duration::from_secs(s.nodes_metrics_polling_freq_secs),
-                disks_usage_check_freq: Duration::from_secs(s.disks_usage_check_freq),
-                rewards_balances_retrieval_freq: Duration::from_secs(
-                    s.rewards_balances_retrieval_freq_secs,
-                ),
-                rewards_monitoring_enabled: s.rewards_monitoring_enabled,
-                l2_network_rpc_url: s.l2_network_rpc_url.clone(),
-                token_contract_address: s.token_contract_address.clone(),
-                lcd_display_enabled: s.lcd_display_enabled,
-                lcd_device: s.lcd_device.clone(),
-                lcd_addr: s.lcd_addr.clone(),
-                node_list_page_size: s.node_list_page_size,
-                node_list_mode: s.node_list_mode,
-                llm_base_url: s.llm_base_url,
-                llm_model: s.llm_model,
-                llm_api_key: s.llm_api_key,
+            Ok(row) => row.map(|r| r.get::<i64, _>("from_block") as u64),
+            Err(err) => {
+                logging::error!(
+                    "[ERROR][DB] Database error while retrieving earnings sync status: {err}"
+                );
+                None
+            }
+        }
+    }
+
+    // Set the earliest block the earnings history of an address has been fetched from
+    pub async fn set_earnings_synced_from(&self, address: &Address, from_block: u64) {
+        let db_lock = self.db.lock().await;
+        if let Err(err) =
+            sqlx::query("INSERT OR REPLACE INTO earnings_sync (address, from_block) VALUES (?, ?)")
+                .bind(address.to_string())
+                .bind(from_block as i64)
+                .execute(&*db_lock)
+                .await
+        {
+            logging::error!(
+                "[ERROR][DB] Database insert error while storing earnings sync status: {err}"
+            );
+        }
+    }
+
+    // Retrieve the non-zero reward payments received within a time range, oldest first,
+    // optionally only those received by the given address
+    pub async fn get_earnings_in_range(
+        &self,
+        address: Option<&Address>,
+        from_ts: i64,
+        to_ts: i64,
+    ) -> Result<Vec<EarningsExportRow>, DbError> {
+        let db_lock = self.db.lock().await;
+        let res = sqlx::query_as::<_, CachedEarnings>(
+            "SELECT * FROM earnings WHERE address LIKE ? AND timestamp >= ? AND timestamp <= ? \
+             ORDER BY timestamp, block_number",
+        )
+        .bind(address.map_or("%".to_string(), |addr| addr.to_string()))
+        .bind(from_ts)
+        .bind(to_ts)
+        .fetch_all(&*db_lock)
+        .await;
+
+        match res {
+            Ok(earnings) => Ok(earnings
+                .into_iter()
+                .filter_map(|e| {
+                    let amount = U256::from_str(&e.amount).unwrap_or(U256::ZERO);
+                    (amount > U256::ZERO).then_some(EarningsExportRow {
+                        address: e.address,
+                        timestamp: e.timestamp,
+                        block_number: e.block_number as u64,
+                        amount,
+                        price: None,
+                    })
+                })
+                .collect()),
+            Err(err) => {
+                logging::error!(
+                    "[ERROR][DB] Database error while retrieving earnings in range: {err}"
+                );
+                Err(err.into())
+            }
+        }
+    }
+
+    // Store the increases of the rewards reported by nodes, pending to be attributed payments
+    pub async fn store_nodes_rewards_deltas(&self, deltas: &[NodeRewardsDelta]) {
+        if deltas.is_empty() {
+            return;
+        }
+        let db_lock = self.db.lock().await;
+        let mut query_builder =
+            QueryBuilder::new("INSERT INTO nodes_rewards_deltas (node_id, amount, timestamp) ");
+        query_builder.push_values(deltas, |mut b, d| {
+            b.push_bind(d.node_id.to_string())
+                .push_bind(d.amount.to_string())
+                .push_bind(d.timestamp);
+        });
+        if let Err(err) = query_builder.build().execute(&*db_lock).await {
+            logging::error!(
+                "[ERROR][DB] Database insert error while storing nodes rewards increases: {err}"
+            );
+        }
+    }
+
+    // Retrieve the increases of the rewards reported by nodes within a time range (in millis)
+    pub async fn get_nodes_rewards_deltas(
+        &self,
+        from: i64,
+        to: i64,
+    ) -> Result<Vec<NodeRewardsDelta>, DbError> {
+        let db_lock = self.db.lock().await;
+        let res = sqlx::query(
+            "SELECT id, node_id, amount, timestamp FROM nodes_rewards_deltas \
+             WHERE timestamp >= ? AND timestamp <= ? ORDER BY timestamp",
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_all(&*db_lock)
+        .await;
+
+        match res {
+            Ok(rows) => Ok(rows
+                .into_iter()
+                .filter_map(|row| {
+                    Some(NodeRewardsDelta {
+                        id: row.get("id"),
+                        node_id: NodeId::new(row.get::<String, _>("node_id")).ok()?,
+                        amount: U256::from_str(&row.get::<String, _>("amount")).ok()?,
+                        timestamp: row.get("timestamp"),
+                    })
+                })
+                .collect()),
+            Err(err) => {
+                logging::error!(
+                    "[ERROR][DB] Database error while retrieving nodes rewards increases: {err}"
+                );
+                Err(err.into())
+            }
+        }
+    }
+
+    // Delete the increases of the rewards reported by nodes with the given ids
+    pub async fn delete_nodes_rewards_deltas(&self, ids: &HashSet<i64>) {
+        if ids.is_empty() {
+            return;
+        }
+        let db_lock = self.db.lock().await;
+        let mut query_builder = QueryBuilder::new("DELETE FROM nodes_rewards_deltas WHERE id IN ");
+        query_builder.push_tuples(ids, |mut b, id| {
+            b.push_bind(*id);
+        });
+        if let Err(err) = query_builder.build().execute(&*db_lock).await {
+            logging::error!(
+                "[ERROR][DB] Database delete error while removing nodes rewards increases: {err}"
+            );
+        }
+    }
+
+    // Delete the increases of the rewards reported by nodes before the given timestamp (in millis)
+    pub async fn delete_nodes_rewards_deltas_before(&self, timestamp: i64) {
+        let db_lock = self.db.lock().await;
+        if let Err(err) = sqlx::query("DELETE FROM nodes_rewards_deltas WHERE timestamp < ?")
+            .bind(timestamp)
+            .execute(&*db_lock)
+            .await
+        {
+            logging::error!(
+                "[ERROR][DB] Database delete error while pruning nodes rewards increases: {err}"
+            );
+        }
+    }
+
+    // Store the shares of reward payments attributed to nodes
+    pub async fn store_nodes_earnings(&self, earnings: &[NodeEarning]) {
+        if earnings.is_empty() {
+            return;
+        }
+        let db_lock = self.db.lock().await;
+        let mut query_builder = QueryBuilder::new(
+            "INSERT INTO nodes_earnings (node_id, address, amount, block_number, timestamp) ",
+        );
+        query_builder.push_values(earnings, |mut b, e| {
+            b.push_bind(e.node_id.to_string())
+                .push_bind(e.address.clone())
+                .push_bind(e.amount.to_string())
+                .push_bind(e.block_number as i64)
+                .push_bind(e.timestamp);
+        });
+        if let Err(err) = query_builder.build().execute(&*db_lock).await {
+            logging::error!(
+                "[ERROR][DB] Database insert error while storing nodes earnings: {err}"
+            );
+        }
+    }
+
+    // Retrieve the address and block number of the payments received within a time range
+    // (in seconds) which have already been attributed to nodes
+    pub async fn get_attributed_payments(
+        &self,
+        from_ts: i64,
+        to_ts: i64,
+    ) -> HashSet<(String, u64)> {
+        let db_lock = self.db.lock().await;
+        match sqlx::query(
+            "SELECT DISTINCT address, block_number FROM nodes_earnings \
+             WHERE timestamp >= ? AND timestamp <= ?",
+        )
+        .bind(from_ts)
+        .bind(to_ts)
+        .fetch_all(&*db_lock)
+        .await
+        {
+            Ok(rows) => rows
+                .into_iter()
+                .map(|row| {
+                    (
+                        row.get::<String, _>("address"),
+                        row.get::<i64, _>("block_number") as u64,
+                    )
+                })
+                .collect(),
+            Err(err) => {
+                logging::error!(
+                    "[ERROR][DB] Database error while retrieving attributed payments: {err}"
+                );
+                HashSet::new()
+            }
+        }
+    }
+
+    // Retrieve the total earnings attributed to each node
+    pub async fn get_nodes_earnings_totals(&self) -> HashMap<NodeId, U256> {
+        let db_lock = self.db.lock().await;
+        let mut totals = HashMap::<NodeId, U256>::new();
+        match sqlx::query("SELECT node_id, amount FROM nodes_earnings")
+            .fetch_all(&*db_lock)
+            .await
+        {
+            Ok(rows) => {
+                for row in rows {
+                    if let (Ok(node_id), Ok(amount)) = (
+                        NodeId::new(row.get::<String, _>("node_id")),
+                        U256::from_str(&row.get::<String, _>("amount")),
+                    ) {
+                        *totals.entry(node_id).or_default() += amount;
+                    }
+                }
+            }
+            Err(err) => {
+                logging::error!("[ERROR][DB] Database error while retrieving nodes earnings: {err}")
+            }
+        }
+        totals
+    }
+
+    // Retrieve the cached daily token prices in the given currency within a range of days,
+    // as retrieved from the given price source
+    pub async fn get_token_prices(
+        &self,
+        source: &str,
+        currency: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> HashMap<NaiveDate, f64> {
+        let db_lock = self.db.lock().await;
+        match sqlx::query(
+            "SELECT date, price FROM token_prices \
+            WHERE source = ? AND currency = ? AND date >= ? AND date <= ?",
+        )
+        .bind(source)
+        .bind(currency)
+        .bind(from.to_string())
+        .bind(to.to_string())
+        .fetch_all(&*db_lock)
+        .await
+        {
+            Ok(rows) => rows
+                .into_iter()
+                .filter_map(|row| {
+                    let date = row.get::<String, _>("date").parse::<NaiveDate>().ok()?;
+                    Some((date, row.get::<f64, _>("price")))
+                })
+                .collect(),
+            Err(err) => {
+                logging::error!(
+                    "[ERROR][DB] Database error while retrieving cached token prices: {err}"
+                );
+                HashMap::new()
+            }
+        }
+    }
+
+    // Cache the token price in the given currency on a day, as retrieved from the given price source
+    pub async fn store_token_price(
+        &self,
+        date: NaiveDate,
+        source: &str,
+        currency: &str,
+        price: f64,
+    ) {
+        let db_lock = self.db.lock().await;
+        if let Err(err) = sqlx::query(
+            "INSERT OR REPLACE INTO token_prices (date, currency, source, price) VALUES (?, ?, ?, ?)",
+        )
+        .bind(date.to_string())
+        .bind(currency)
+        .bind(source)
+        .bind(price)
+        .execute(&*db_lock)
+        .await
+        {
+            logging::error!("[ERROR][DB] Database insert error while storing token price: {err}");
+        }
+    }
+
+    // Retrieve the settings values
+    pub async fn get_settings(&self) -> AppSettings {
+        let db_lock = self.db.lock().await;
+        match sqlx::query_as::<_, CachedSettings>("SELECT * FROM settings")
+            .fetch_all(&*db_lock)
+            .await
+            .map(|s| s.first().cloned())
+        {
+            Ok(Some(s)) => AppSettings {
+                nodes_auto_upgrade: s.nodes_auto_upgrade,
+                nodes_auto_upgrade_delay: Duration::from_secs(s.nodes_auto_upgrade_delay_secs),
+                node_bin_version_polling_freq: Duration::from_secs(
+                    s.node_bin_version_polling_freq_secs,
+                ),
+                nodes_metrics_polling_freq: Duration::from_secs(s.nodes_metrics_polling_freq_secs),
+                disks_usage_check_freq: Duration::from_secs(s.disks_usage_check_freq),
+                rewards_balances_retrieval_freq: Duration::from_secs(
+                    s.rewards_balances_retrieval_freq_secs,
+                ),
+                rewards_monitoring_enabled: s.rewards_monitoring_enabled,
+                l2_network_rpc_url: s.l2_network_rpc_url.clone(),
+                l2_network_rpc_fallback_urls: parse_rpc_urls(&s.l2_network_rpc_fallback_urls),
+                token_contract_address: s.token_contract_address.clone(),
+                additional_token_contracts: parse_token_addresses(&s.additional_token_contracts)
+                    .unwrap_or_default(),
+                fiat_currency: s.fiat_currency,
+                token_price_api_url: s.token_price_api_url,
+                earnings_periods_hours: parse_earnings_periods(&s.earnings_periods_hours)
+                    .unwrap_or_else(|_| DEFAULT_EARNINGS_PERIODS_HOURS.to_vec()),
+                earnings_retention_days: s.earnings_retention_days as u64,
+                earnings_history_start: s
+                    .earnings_history_start
+                    .and_then(|start| start.parse().ok()),
+                electricity_price_per_kwh: s.electricity_price_per_kwh,
+                host_power_watts: s.host_power_watts,
+                watts_per_cpu_percent: s.watts_per_cpu_percent,
+                disk_cost_per_gb_month: s.disk_cost_per_gb_month,
+                monthly_traffic_cap_gb: s.monthly_traffic_cap_gb as u64,
+                lcd_display_enabled: s.lcd_display_enabled,
+                lcd_device: s.lcd_device.clone(),
+                lcd_addr: s.lcd_addr.clone(),
+                node_list_page_size: s.node_list_page_size,
+                node_list_mode: s.node_list_mode,
+                llm_base_url: s.llm_base_url,
+                llm_model: s.llm_model,
+                llm_api_key: s.llm_api_key,
                 system_prompt: s.system_prompt,
                 max_context_messages: s.max_context_messages as u64,
                 autonomous_enabled: s.autonomous_enabled,
@@ -994,6 +1933,15 @@ impl DbClient {
                 autonomous_max_actions_per_cycle: s.autonomous_max_actions_per_cycle as u64,
                 metrics_mode: MetricsMode::from_db(s.metrics_mode),
                 node_bin_download_url: s.node_bin_download_url,
+                metrics_raw_retention_hours: s.metrics_raw_retention_hours as u64,
+                metrics_minute_retention_days: s.metrics_minute_retention_days as u64,
+                metrics_hourly_retention_days: s.metrics_hourly_retention_days as u64,
+                influxdb_enabled: s.influxdb_enabled,
+                influxdb_url: s.influxdb_url,
+                influxdb_token: s.influxdb_token,
+                graphite_enabled: s.graphite_enabled,
+                graphite_addr: s.graphite_addr,
+                nodes_metrics_polling_concurrency: s.nodes_metrics_polling_concurrency as u64,
             },
             Ok(None) => {
                 logging::log!("[DB] No settings found in DB, we'll be using defaults.");
@@ -1021,7 +1969,19 @@ impl DbClient {
             rewards_balances_retrieval_freq_secs = ?, \
             rewards_monitoring_enabled = ?, \
             l2_network_rpc_url = ?, \
+            l2_network_rpc_fallback_urls = ?, \
             token_contract_address = ?, \
+            additional_token_contracts = ?, \
+            fiat_currency = ?, \
+            token_price_api_url = ?, \
+            earnings_periods_hours = ?, \
+            earnings_retention_days = ?, \
+            earnings_history_start = ?, \
+            electricity_price_per_kwh = ?, \
+            host_power_watts = ?, \
+            watts_per_cpu_percent = ?, \
+            disk_cost_per_gb_month = ?, \
+            monthly_traffic_cap_gb = ?, \
             lcd_display_enabled = ?, \
             lcd_device = ?, \
             lcd_addr = ?, \
@@ -1036,7 +1996,16 @@ impl DbClient {
             autonomous_check_interval_secs = ?, \
             autonomous_max_actions_per_cycle = ?, \
             metrics_mode = ?, \
-            node_bin_download_url = ?",
+            node_bin_download_url = ?, \
+            metrics_raw_retention_hours = ?, \
+            metrics_minute_retention_days = ?, \
+            metrics_hourly_retention_days = ?, \
+            influxdb_enabled = ?, \
+            influxdb_url = ?, \
+            influxdb_token = ?, \
+            graphite_enabled = ?, \
+            graphite_addr = ?, \
+            nodes_metrics_polling_concurrency = ?",
         )
         .bind(settings.nodes_auto_upgrade)
         .bind(settings.nodes_auto_upgrade_delay.as_secs() as i64)
@@ -1046,7 +2015,23 @@ impl DbClient {
         .bind(settings.rewards_balances_retrieval_freq.as_secs() as i64)
         .bind(settings.rewards_monitoring_enabled)
         .bind(settings.l2_network_rpc_url.clone())
+        .bind(settings.l2_network_rpc_fallback_urls.join(","))
         .bind(settings.token_contract_address.clone())
+        .bind(settings.additional_token_contracts.join(","))
+        .bind(settings.fiat_currency.clone())
+        .bind(settings.token_price_api_url.clone())
+        .bind(format_earnings_periods(&settings.earnings_periods_hours))
+        .bind(settings.earnings_retention_days as i64)
+        .bind(
+            settings
+                .earnings_history_start
+                .map(|start| start.to_string()),
+        )
+        .bind(settings.electricity_price_per_kwh)
+        .bind(settings.host_power_watts)
+        .bind(settings.watts_per_cpu_percent)
+        .bind(settings.disk_cost_per_gb_month)
+        .bind(settings.monthly_traffic_cap_gb as i64)
         .bind(settings.lcd_display_enabled)
         .bind(settings.lcd_device.clone())
         .bind(settings.lcd_addr.clone())
@@ -1062,6 +2047,15 @@ impl DbClient {
         .bind(settings.autonomous_max_actions_per_cycle as i64)
         .bind(settings.metrics_mode.to_db())
         .bind(settings.node_bin_download_url.clone())
+        .bind(settings.metrics_raw_retention_hours as i64)
+        .bind(settings.metrics_minute_retention_days as i64)
+        .bind(settings.metrics_hourly_retention_days as i64)
+        .bind(settings.influxdb_enabled)
+        .bind(settings.influxdb_url.clone())
+        .bind(settings.influxdb_token.clone())
+        .bind(settings.graphite_enabled)
+        .bind(settings.graphite_addr.clone())
+        .bind(settings.nodes_metrics_polling_concurrency as i64)
         .execute(&*db_lock)
         .await
         {
@@ -1078,10 +2072,15 @@ impl DbClient {
 
     // ─── Agent events ────────────────────────────────────────────────────────────
 
-    pub async fn insert_agent_event(&self, event_type: &AgentEventType, description: &str) {
+    // Insert an agent event, returning it with the id it was stored with
+    pub async fn insert_agent_event(
+        &self,
+        event_type: &AgentEventType,
+        description: &str,
+    ) -> Option<AgentEvent> {
         let timestamp = chrono::Utc::now().timestamp();
         let db_lock = self.db.lock().await;
-        if let Err(err) = sqlx::query(
+        match sqlx::query(
             "INSERT INTO agent_events (event_type, description, timestamp) \
              VALUES (?, ?, ?)",
         )
@@ -1091,7 +2090,16 @@ impl DbClient {
         .execute(&*db_lock)
         .await
         {
-            logging::error!("[ERROR][DB] Database error while inserting agent event: {err}");
+            Ok(res) => Some(AgentEvent {
+                id: res.last_insert_rowid(),
+                event_type: event_type.clone(),
+                description: description.to_string(),
+                timestamp,
+            }),
+            Err(err) => {
+                logging::error!("[ERROR][DB] Database error while inserting agent event: {err}");
+                None
+            }
         }
     }
 
@@ -1172,4 +2180,1252 @@ impl DbClient {
             }
         }
     }
+
+    // Retrieve all alert rules, skipping those which cannot be parsed
+    pub async fn get_alert_rules(&self) -> Vec<AlertRule> {
+        let db_lock = self.db.lock().await;
+        match sqlx::query_as::<_, CachedAlertRule>("SELECT * FROM alert_rules ORDER BY id")
+            .fetch_all(&*db_lock)
+            .await
+        {
+            Ok(rows) => rows
+                .into_iter()
+                .filter_map(|r| {
+                    Some(AlertRule {
+                        id: r.id,
+                        name: r.name,
+                        metric: r.metric.parse().ok()?,
+                        operator: r.operator.parse().ok()?,
+                        threshold: r.threshold,
+                        hysteresis: r.hysteresis,
+                        for_secs: r.for_secs as u64,
+                        severity: r.severity.parse().ok()?,
+                        enabled: r.enabled,
+                        silenced_until: r.silenced_until,
+                    })
+                })
+                .collect(),
+            Err(err) => {
+                logging::error!("[ERROR][DB] Database error while retrieving alert rules: {err}");
+                vec![]
+            }
+        }
+    }
+
+    // Insert a new alert rule if its id is 0, or update the existing one otherwise
+    pub async fn store_alert_rule(&self, rule: &AlertRule) -> Result<(), DbError> {
+        let db_lock = self.db.lock().await;
+        let query = if rule.id == 0 {
+            sqlx::query(
+                "INSERT INTO alert_rules (\
+                    name, metric, operator, threshold, hysteresis, for_secs, severity, enabled, \
+                    silenced_until\
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+        } else {
+            sqlx::query(
+                "UPDATE alert_rules SET \
+                    name = ?, metric = ?, operator = ?, threshold = ?, hysteresis = ?, \
+                    for_secs = ?, severity = ?, enabled = ?, silenced_until = ? \
+                WHERE id = ?",
+            )
+        };
+
+        let mut query = query
+            .bind(rule.name.clone())
+            .bind(rule.metric.as_str())
+            .bind(rule.operator.as_str())
+            .bind(rule.threshold)
+            .bind(rule.hysteresis)
+            .bind(rule.for_secs as i64)
+            .bind(rule.severity.as_str())
+            .bind(rule.enabled)
+            .bind(rule.silenced_until);
+        if rule.id != 0 {
+            query = query.bind(rule.id);
+        }
+
+        match query.execute(&*db_lock).await {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                logging::error!("[ERROR][DB] Database error while storing alert rule: {err}");
+                Err(err.into())
+            }
+        }
+    }
+
+    // Set the timestamp until which the alerts of a rule are silenced
+    pub async fn silence_alert_rule(
+        &self,
+        rule_id: i64,
+        silenced_until: Option<i64>,
+    ) -> Result<(), DbError> {
+        let db_lock = self.db.lock().await;
+        match sqlx::query("UPDATE alert_rules SET silenced_until = ? WHERE id = ?")
+            .bind(silenced_until)
+            .bind(rule_id)
+            .execute(&*db_lock)
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                logging::error!("[ERROR][DB] Database error while silencing alert rule: {err}");
+                Err(err.into())
+            }
+        }
+    }
+
+    // Delete an alert rule
+    pub async fn delete_alert_rule(&self, rule_id: i64) -> Result<(), DbError> {
+        let db_lock = self.db.lock().await;
+        match sqlx::query("DELETE FROM alert_rules WHERE id = ?")
+            .bind(rule_id)
+            .execute(&*db_lock)
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                logging::error!("[ERROR][DB] Database error while deleting alert rule: {err}");
+                Err(err.into())
+            }
+        }
+    }
+
+    // Retrieve the alerts fired by the alert rules, skipping those which cannot be parsed
+    pub async fn get_alerts(&self) -> Vec<Alert> {
+        let db_lock = self.db.lock().await;
+        match sqlx::query_as::<_, CachedAlert>("SELECT * FROM alerts ORDER BY id")
+            .fetch_all(&*db_lock)
+            .await
+        {
+            Ok(rows) => rows
+                .into_iter()
+                .filter_map(|a| {
+                    let node_id = match a.node_id {
+                        Some(id) => Some(NodeId::new(&id).ok()?),
+                        None => None,
+                    };
+                    Some(Alert {
+                        id: a.id as u64,
+                        rule_id: a.rule_id,
+                        rule_name: a.rule_name,
+                        severity: a.severity.parse().ok()?,
+                        node_id,
+                        value: a.value,
+                        message: a.message,
+                        state: if a.resolved_at.is_some() {
+                            AlertState::Resolved
+                        } else {
+                            AlertState::Firing
+                        },
+                        fired_at: a.fired_at,
+                        resolved_at: a.resolved_at,
+                        acked_at: a.acked_at,
+                        silenced: a.silenced,
+                    })
+                })
+                .collect(),
+            Err(err) => {
+                logging::error!("[ERROR][DB] Database error while retrieving alerts: {err}");
+                vec![]
+            }
+        }
+    }
+
+    // Insert or update the given alerts, and delete those with the given ids, within a
+    // single transaction. An acknowledgement already stored is never cleared.
+    pub async fn store_alerts(&self, alerts: &[Alert], removed: &[u64]) -> Result<(), DbError> {
+        if alerts.is_empty() && removed.is_empty() {
+            return Ok(());
+        }
+
+        let db_lock = self.db.lock().await;
+        let res: Result<(), sqlx::Error> = async {
+            let mut tx = db_lock.begin().await?;
+            for alert in alerts {
+                sqlx::query(
+                    "INSERT INTO alerts (\
+                        id, rule_id, rule_name, severity, node_id, value, message, fired_at, \
+                        resolved_at, acked_at, silenced\
+                    ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?) \
+                    ON CONFLICT(id) DO UPDATE SET \
+                        rule_name = excluded.rule_name, severity = excluded.severity, \
+                        value = excluded.value, message = excluded.message, \
+                        resolved_at = excluded.resolved_at, \
+                        acked_at = COALESCE(alerts.acked_at, excluded.acked_at), \
+                        silenced = excluded.silenced",
+                )
+                .bind(alert.id as i64)
+                .bind(alert.rule_id)
+                .bind(alert.rule_name.clone())
+                .bind(alert.severity.as_str())
+                .bind(alert.node_id.as_ref().map(|id| id.to_string()))
+                .bind(alert.value)
+                .bind(alert.message.clone())
+                .bind(alert.fired_at)
+                .bind(alert.resolved_at)
+                .bind(alert.acked_at)
+                .bind(alert.silenced)
+                .execute(&mut *tx)
+                .await?;
+            }
+            for id in removed {
+                sqlx::query("DELETE FROM alerts WHERE id = ?")
+                    .bind(*id as i64)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            tx.commit().await
+        }
+        .await;
+
+        match res {
+            Ok(()) => Ok(()),
+            Err(err) => {
+                logging::error!("[ERROR][DB] Database error while storing alerts: {err}");
+                Err(err.into())
+            }
+        }
+    }
+
+    // Set the timestamp when an alert was acknowledged, unless it already was
+    pub async fn acknowledge_alert(&self, alert_id: u64, acked_at: i64) -> Result<(), DbError> {
+        let db_lock = self.db.lock().await;
+        match sqlx::query("UPDATE alerts SET acked_at = ? WHERE id = ? AND acked_at IS NULL")
+            .bind(acked_at)
+            .bind(alert_id as i64)
+            .execute(&*db_lock)
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                logging::error!("[ERROR][DB] Database error while acknowledging alert: {err}");
+                Err(err.into())
+            }
+        }
+    }
+
+    // Retrieve all notification channels, skipping those which cannot be parsed
+    pub async fn get_notification_channels(&self) -> Vec<NotificationChannel> {
+        let db_lock = self.db.lock().await;
+        match sqlx::query_as::<_, CachedNotificationChannel>(
+            "SELECT * FROM notification_channels ORDER BY id",
+        )
+        .fetch_all(&*db_lock)
+        .await
+        {
+            Ok(rows) => rows
+                .into_iter()
+                .filter_map(|c| {
+                    Some(NotificationChannel {
+                        id: c.id,
+                        name: c.name,
+                        target: serde_json::from_str(&c.target).ok()?,
+                        events: c.events.split(',').filter_map(|e| e.parse().ok()).collect(),
+                        enabled: c.enabled,
+                    })
+                })
+                .collect(),
+            Err(err) => {
+                logging::error!(
+                    "[ERROR][DB] Database error while retrieving notification channels: {err}"
+                );
+                vec![]
+            }
+        }
+    }
+
+    // Insert a new notification channel if its id is 0, or update the existing one otherwise
+    pub async fn store_notification_channel(
+        &self,
+        channel: &NotificationChannel,
+    ) -> Result<(), DbError> {
+        let db_lock = self.db.lock().await;
+        let query = if channel.id == 0 {
+            sqlx::query(
+                "INSERT INTO notification_channels (name, target, events, enabled) \
+                VALUES (?, ?, ?, ?)",
+            )
+        } else {
+            sqlx::query(
+                "UPDATE notification_channels SET name = ?, target = ?, events = ?, enabled = ? \
+                WHERE id = ?",
+            )
+        };
+
+        let events = channel
+            .events
+            .iter()
+            .map(|e| e.as_str())
+            .collect::<Vec<_>>()
+            .join(",");
+        let mut query = query
+            .bind(channel.name.clone())
+            .bind(json!(channel.target).to_string())
+            .bind(events)
+            .bind(channel.enabled);
+        if channel.id != 0 {
+            query = query.bind(channel.id);
+        }
+
+        match query.execute(&*db_lock).await {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                logging::error!(
+                    "[ERROR][DB] Database error while storing notification channel: {err}"
+                );
+                Err(err.into())
+            }
+        }
+    }
+
+    // Delete a notification channel
+    pub async fn delete_notification_channel(&self, channel_id: i64) -> Result<(), DbError> {
+        let db_lock = self.db.lock().await;
+        match sqlx::query("DELETE FROM notification_channels WHERE id = ?")
+            .bind(channel_id)
+            .execute(&*db_lock)
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                logging::error!(
+                    "[ERROR][DB] Database error while deleting notification channel: {err}"
+                );
+                Err(err.into())
+            }
+        }
+    }
+
+    // Retrieve all entries of the rewards addresses book, sorted by label
+    pub async fn get_address_book(&self) -> AddressBook {
+        let db_lock = self.db.lock().await;
+        match sqlx::query_as::<_, CachedAddressBookEntry>(
+            "SELECT * FROM address_book ORDER BY label COLLATE NOCASE",
+        )
+        .fetch_all(&*db_lock)
+        .await
+        {
+            Ok(rows) => AddressBook(
+                rows.into_iter()
+                    .map(|e| AddressBookEntry {
+                        address: e.address,
+                        label: e.label,
+                        note: e.note,
+                        color: e.color,
+                    })
+                    .collect(),
+            ),
+            Err(err) => {
+                logging::error!("[ERROR][DB] Database error while retrieving address book: {err}");
+                AddressBook::default()
+            }
+        }
+    }
+
+    // Insert a new entry in the rewards addresses book, or update it if the address is already there
+    pub async fn store_address_book_entry(&self, entry: &AddressBookEntry) -> Result<(), DbError> {
+        let db_lock = self.db.lock().await;
+        match sqlx::query(
+            "INSERT INTO address_book (address, label, note, color) VALUES (?, ?, ?, ?) \
+            ON CONFLICT(address) DO UPDATE SET label = excluded.label, note = excluded.note, \
+            color = excluded.color",
+        )
+        .bind(entry.address.clone())
+        .bind(entry.label.clone())
+        .bind(entry.note.clone())
+        .bind(entry.color.clone())
+        .execute(&*db_lock)
+        .await
+        {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                logging::error!(
+                    "[ERROR][DB] Database error while storing address book entry: {err}"
+                );
+                Err(err.into())
+            }
+        }
+    }
+
+    // Delete the entry of an address from the rewards addresses book
+    pub async fn delete_address_book_entry(&self, address: &str) -> Result<(), DbError> {
+        let db_lock = self.db.lock().await;
+        match sqlx::query("DELETE FROM address_book WHERE address = ?")
+            .bind(address)
+            .execute(&*db_lock)
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                logging::error!(
+                    "[ERROR][DB] Database error while deleting address book entry: {err}"
+                );
+                Err(err.into())
+            }
+        }
+    }
+
+    // Retrieve the timestamp of the most recent reward payment received by any address
+    pub async fn get_last_earning_timestamp(&self) -> Option<i64> {
+        let db_lock = self.db.lock().await;
+        match sqlx::query_scalar::<_, Option<i64>>(
+            "SELECT MAX(timestamp) FROM earnings WHERE amount != '0'",
+        )
+        .fetch_one(&*db_lock)
+        .await
+        {
+            Ok(timestamp) => timestamp,
+            Err(err) => {
+                logging::error!(
+                    "[ERROR][DB] Database error while retrieving last earning timestamp: {err}"
+                );
+                None
+            }
+        }
+    }
+
+    // Retrieve the nodes actions batches which were not finished, in the order they were created
+    pub async fn get_unfinished_batches(&self) -> Vec<NodesActionsBatch> {
+        let db_lock = self.db.lock().await;
+        match sqlx::query_as::<_, CachedNodesActionsBatch>(
+            "SELECT * FROM nodes_actions_batches WHERE finished_at IS NULL ORDER BY created_at",
+        )
+        .fetch_all(&*db_lock)
+        .await
+        {
+            Ok(rows) => rows
+                .into_iter()
+                .filter_map(CachedNodesActionsBatch::into_batch)
+                .collect(),
+            Err(err) => {
+                logging::error!(
+                    "[ERROR][DB] Database error while retrieving unfinished batches: {err}"
+                );
+                vec![]
+            }
+        }
+    }
+
+    // Retrieve the nodes actions batches already finished, most recent first
+    pub async fn get_batches_history(&self) -> Vec<NodesActionsBatch> {
+        let db_lock = self.db.lock().await;
+        match sqlx::query_as::<_, CachedNodesActionsBatch>(
+            "SELECT * FROM nodes_actions_batches WHERE finished_at IS NOT NULL \
+            ORDER BY finished_at DESC, created_at DESC",
+        )
+        .fetch_all(&*db_lock)
+        .await
+        {
+            Ok(rows) => rows
+                .into_iter()
+                .filter_map(CachedNodesActionsBatch::into_batch)
+                .collect(),
+            Err(err) => {
+                logging::error!(
+                    "[ERROR][DB] Database error while retrieving batches history: {err}"
+                );
+                vec![]
+            }
+        }
+    }
+
+    // Retrieve a nodes actions batch by its id
+    pub async fn get_batch(&self, batch_id: u16) -> Option<NodesActionsBatch> {
+        let db_lock = self.db.lock().await;
+        match sqlx::query_as::<_, CachedNodesActionsBatch>(
+            "SELECT * FROM nodes_actions_batches WHERE id = ?",
+        )
+        .bind(batch_id)
+        .fetch_optional(&*db_lock)
+        .await
+        {
+            Ok(batch) => batch.and_then(CachedNodesActionsBatch::into_batch),
+            Err(err) => {
+                logging::error!("[ERROR][DB] Database error while retrieving batch: {err}");
+                None
+            }
+        }
+    }
+
+    // Check if a batch with the given id is already stored
+    pub async fn is_batch_stored(&self, batch_id: u16) -> bool {
+        let db_lock = self.db.lock().await;
+        match sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM nodes_actions_batches WHERE id = ?",
+        )
+        .bind(batch_id)
+        .fetch_one(&*db_lock)
+        .await
+        {
+            Ok(count) => count > 0,
+            Err(err) => {
+                logging::error!("[ERROR][DB] Database error while looking up batch: {err}");
+                false
+            }
+        }
+    }
+
+    // Insert or update a nodes actions batch along with its progress
+    pub async fn store_batch(&self, batch: &NodesActionsBatch) -> Result<(), DbError> {
+        let db_lock = self.db.lock().await;
+        match sqlx::query(
+            "INSERT OR REPLACE INTO nodes_actions_batches (\
+                id, batch_type, interval_secs, status, complete, items, \
+                created_at, started_at, finished_at\
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(batch.id)
+        .bind(json!(batch.batch_type).to_string())
+        .bind(batch.interval_secs as i64)
+        .bind(json!(batch.status).to_string())
+        .bind(batch.complete)
+        .bind(json!(batch.items).to_string())
+        .bind(batch.created_at)
+        .bind(batch.started_at)
+        .bind(batch.finished_at)
+        .execute(&*db_lock)
+        .await
+        {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                logging::error!("[ERROR][DB] Database error while storing batch: {err}");
+                Err(err.into())
+            }
+        }
+    }
+
+    // Remove the oldest finished batches, keeping only the given number of most recent ones
+    pub async fn prune_batches_history(&self, max_len: u32) {
+        let db_lock = self.db.lock().await;
+        match sqlx::query(
+            "DELETE FROM nodes_actions_batches WHERE finished_at IS NOT NULL AND id NOT IN (\
+                SELECT id FROM nodes_actions_batches WHERE finished_at IS NOT NULL \
+                ORDER BY finished_at DESC LIMIT ?\
+            )",
+        )
+        .bind(max_len)
+        .execute(&*db_lock)
+        .await
+        {
+            Ok(res) if res.rows_affected() > 0 => logging::log!(
+                "[DB] Pruned {} finished batches from history",
+                res.rows_affected()
+            ),
+            Ok(_) => {}
+            Err(err) => {
+                logging::error!("[ERROR][DB] Database error while pruning batches history: {err}")
+            }
+        }
+    }
+
+    // Whether at least one user has been set up
+    pub async fn has_users(&self) -> bool {
+        let db_lock = self.db.lock().await;
+        match sqlx::query("SELECT EXISTS(SELECT 1 FROM users) AS has_users")
+            .fetch_one(&*db_lock)
+            .await
+        {
+            Ok(row) => row.get::<bool, _>("has_users"),
+            Err(err) => {
+                logging::error!("[ERROR][DB] Database error while checking users: {err}");
+                // assume there are users so the setup cannot be used to bypass authentication
+                true
+            }
+        }
+    }
+
+    // Create the first user, as an admin, only if there is no user set up yet.
+    // Returns whether the user was created.
+    pub async fn create_first_user(
+        &self,
+        username: &str,
+        password_hash: &str,
+    ) -> Result<bool, DbError> {
+        let db_lock = self.db.lock().await;
+        match sqlx::query(
+            "INSERT INTO users (username, password_hash, role, created) \
+            SELECT ?, ?, ?, ? WHERE NOT EXISTS (SELECT 1 FROM users)",
+        )
+        .bind(username)
+        .bind(password_hash)
+        .bind(UserRole::Admin.as_str())
+        .bind(Utc::now().timestamp())
+        .execute(&*db_lock)
+        .await
+        {
+            Ok(res) => Ok(res.rows_affected() > 0),
+            Err(err) => {
+                logging::error!("[ERROR][DB] Database error while creating first user: {err}");
+                Err(err.into())
+            }
+        }
+    }
+
+    // Retrieve the id and password hash of a user
+    pub async fn get_user_credentials(&self, username: &str) -> Option<(i64, String)> {
+        let db_lock = self.db.lock().await;
+        match sqlx::query("SELECT id, password_hash FROM users WHERE username = ?")
+            .bind(username)
+            .fetch_optional(&*db_lock)
+            .await
+        {
+            Ok(row) => row.map(|r| (r.get("id"), r.get("password_hash"))),
+            Err(err) => {
+                logging::error!("[ERROR][DB] Database error while retrieving user: {err}");
+                None
+            }
+        }
+    }
+
+    // Store a new session of a user, identified by the hash of its token
+    pub async fn store_session(
+        &self,
+        token_hash: &str,
+        user_id: i64,
+        expires: i64,
+    ) -> Result<(), DbError> {
+        let db_lock = self.db.lock().await;
+        match sqlx::query(
+            "INSERT INTO sessions (token_hash, user_id, created, expires) VALUES (?, ?, ?, ?)",
+        )
+        .bind(token_hash)
+        .bind(user_id)
+        .bind(Utc::now().timestamp())
+        .bind(expires)
+        .execute(&*db_lock)
+        .await
+        {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                logging::error!("[ERROR][DB] Database error while storing session: {err}");
+                Err(err.into())
+            }
+        }
+    }
+
+    // Retrieve the user of the session with the given token hash, along with
+    // the time (seconds) it expires at, if it hasn't expired yet
+    pub async fn get_session(&self, token_hash: &str) -> Option<(CurrentUser, i64)> {
+        let db_lock = self.db.lock().await;
+        match sqlx::query(
+            "SELECT users.username, users.role, sessions.expires FROM sessions \
+            INNER JOIN users ON users.id = sessions.user_id \
+            WHERE sessions.token_hash = ? AND sessions.expires > ?",
+        )
+        .bind(token_hash)
+        .bind(Utc::now().timestamp())
+        .fetch_optional(&*db_lock)
+        .await
+        {
+            Ok(row) => row.map(|r| {
+                let user = CurrentUser {
+                    username: r.get("username"),
+                    // an unknown role is given the least privileges
+                    role: r.get::<String, _>("role").parse().unwrap_or_default(),
+                };
+                (user, r.get("expires"))
+            }),
+            Err(err) => {
+                logging::error!("[ERROR][DB] Database error while retrieving session: {err}");
+                None
+            }
+        }
+    }
+
+    // Delete the session with the given token hash
+    pub async fn delete_session(&self, token_hash: &str) {
+        let db_lock = self.db.lock().await;
+        if let Err(err) = sqlx::query("DELETE FROM sessions WHERE token_hash = ?")
+            .bind(token_hash)
+            .execute(&*db_lock)
+            .await
+        {
+            logging::error!("[ERROR][DB] Database error while deleting session: {err}");
+        }
+    }
+
+    // Remove all sessions which have already expired
+    pub async fn prune_expired_sessions(&self) {
+        let db_lock = self.db.lock().await;
+        match sqlx::query("DELETE FROM sessions WHERE expires <= ?")
+            .bind(Utc::now().timestamp())
+            .execute(&*db_lock)
+            .await
+        {
+            Ok(res) if res.rows_affected() > 0 => {
+                logging::log!("[DB] Removed {} expired sessions", res.rows_affected())
+            }
+            Ok(_) => {}
+            Err(err) => {
+                logging::error!("[ERROR][DB] Database error while pruning expired sessions: {err}")
+            }
+        }
+    }
+
+    // Retrieve all users, sorted by username
+    pub async fn get_users(&self) -> Vec<UserInfo> {
+        let db_lock = self.db.lock().await;
+        match sqlx::query("SELECT id, username, role, created FROM users ORDER BY username")
+            .fetch_all(&*db_lock)
+            .await
+        {
+            Ok(rows) => rows
+                .into_iter()
+                .map(|r| UserInfo {
+                    id: r.get("id"),
+                    username: r.get("username"),
+                    role: r.get::<String, _>("role").parse().unwrap_or_default(),
+                    created: r.get("created"),
+                })
+                .collect(),
+            Err(err) => {
+                logging::error!("[ERROR][DB] Database error while retrieving users: {err}");
+                vec![]
+            }
+        }
+    }
+
+    // Create a new user, unless there is already one with the same username.
+    // Returns whether the user was created.
+    pub async fn create_user(
+        &self,
+        username: &str,
+        password_hash: &str,
+        role: UserRole,
+    ) -> Result<bool, DbError> {
+        let db_lock = self.db.lock().await;
+        match sqlx::query(
+            "INSERT OR IGNORE INTO users (username, password_hash, role, created) \
+            VALUES (?, ?, ?, ?)",
+        )
+        .bind(username)
+        .bind(password_hash)
+        .bind(role.as_str())
+        .bind(Utc::now().timestamp())
+        .execute(&*db_lock)
+        .await
+        {
+            Ok(res) => Ok(res.rows_affected() > 0),
+            Err(err) => {
+                logging::error!("[ERROR][DB] Database error while creating user: {err}");
+                Err(err.into())
+            }
+        }
+    }
+
+    // Change the role of a user, failing if it's the last admin user and it'd be demoted
+    pub async fn update_user_role(&self, user_id: i64, role: UserRole) -> Result<(), DbError> {
+        let db_lock = self.db.lock().await;
+        // the last admin is checked in the same statement so concurrent changes can't remove it
+        let query = if role == UserRole::Admin {
+            sqlx::query("UPDATE users SET role = ? WHERE id = ?")
+        } else {
+            sqlx::query(
+                "UPDATE users SET role = ? WHERE id = ? AND (role != 'admin' \
+                OR (SELECT COUNT(*) FROM users WHERE role = 'admin') > 1)",
+            )
+        };
+        match query
+            .bind(role.as_str())
+            .bind(user_id)
+            .execute(&*db_lock)
+            .await
+        {
+            Ok(res) if res.rows_affected() == 0 => Err(DbError::LastAdmin),
+            Ok(_) => Ok(()),
+            Err(err) => {
+                logging::error!("[ERROR][DB] Database error while updating user role: {err}");
+                Err(err.into())
+            }
+        }
+    }
+
+    // Change the password of a user, ending all its sessions
+    pub async fn update_user_password(
+        &self,
+        user_id: i64,
+        password_hash: &str,
+    ) -> Result<(), DbError> {
+        let db_lock = self.db.lock().await;
+        let res = async {
+            sqlx::query("UPDATE users SET password_hash = ? WHERE id = ?")
+                .bind(password_hash)
+                .bind(user_id)
+                .execute(&*db_lock)
+                .await?;
+            sqlx::query("DELETE FROM sessions WHERE user_id = ?")
+                .bind(user_id)
+                .execute(&*db_lock)
+                .await
+        }
+        .await;
+        match res {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                logging::error!("[ERROR][DB] Database error while updating user password: {err}");
+                Err(err.into())
+            }
+        }
+    }
+
+    // Delete a user along with all its sessions, failing if it's the last admin user
+    pub async fn delete_user(&self, user_id: i64) -> Result<(), DbError> {
+        let db_lock = self.db.lock().await;
+        let res: Result<bool, sqlx::Error> = async {
+            let deleted = sqlx::query(
+                "DELETE FROM users WHERE id = ? AND (role != 'admin' \
+                OR (SELECT COUNT(*) FROM users WHERE role = 'admin') > 1)",
+            )
+            .bind(user_id)
+            .execute(&*db_lock)
+            .await?;
+            if deleted.rows_affected() == 0 {
+                return Ok(false);
+            }
+            sqlx::query("DELETE FROM sessions WHERE user_id = ?")
+                .bind(user_id)
+                .execute(&*db_lock)
+                .await?;
+            Ok(true)
+        }
+        .await;
+        match res {
+            Ok(false) => Err(DbError::LastAdmin),
+            Ok(true) => Ok(()),
+            Err(err) => {
+                logging::error!("[ERROR][DB] Database error while deleting user: {err}");
+                Err(err.into())
+            }
+        }
+    }
+}
+
+// Client connected to a new DB, with all migrations applied, in a temporary directory.
+#[cfg(test)]
+pub(crate) async fn test_db_client() -> DbClient {
+    let dir = env::temp_dir().join(format!(
+        "formicaio-test-{}",
+        hex::encode(rand::random::<[u8; 8]>())
+    ));
+    std::fs::create_dir_all(&dir).expect("Failed to create test DB directory");
+    DbClient::connect(Some(dir))
+        .await
+        .expect("Failed to create test DB")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_last_admin_cannot_be_demoted_or_removed() {
+        let db_client = test_db_client().await;
+        for username in ["admin1", "admin2"] {
+            assert!(
+                db_client
+                    .create_user(username, "hash", UserRole::Admin)
+                    .await
+                    .unwrap()
+            );
+        }
+        let users = db_client.get_users().await;
+        let (admin1, admin2) = (users[0].id, users[1].id);
+
+        db_client
+            .update_user_role(admin1, UserRole::Operator)
+            .await
+            .unwrap();
+        assert!(matches!(
+            db_client.update_user_role(admin2, UserRole::Viewer).await,
+            Err(DbError::LastAdmin)
+        ));
+        assert!(matches!(
+            db_client.delete_user(admin2).await,
+            Err(DbError::LastAdmin)
+        ));
+        // non-admin users can still be removed, and admins promoted
+        db_client.delete_user(admin1).await.unwrap();
+        let users = db_client.get_users().await;
+        assert_eq!(users.len(), 1);
+        assert_eq!(users[0].role, UserRole::Admin);
+    }
+
+    fn metric_sample(key: &str, value: f64, timestamp: i64) -> NodeMetric {
+        NodeMetric {
+            key: key.to_string(),
+            value: value.to_string(),
+            timestamp,
+        }
+    }
+
+    async fn host_metric_values(
+        db_client: &DbClient,
+        resolution: MetricsResolution,
+        key: &str,
+    ) -> Vec<(i64, f64)> {
+        db_client
+            .get_host_metrics(resolution, -1, i64::MAX)
+            .await
+            .remove(key)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|m| (m.timestamp, m.value.parse().unwrap()))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_rollup_metrics_redoes_buckets_with_late_samples() {
+        let db_client = test_db_client().await;
+        let hour = MetricsResolution::Hour.bucket_secs() * 1_000;
+        db_client
+            .store_host_metrics(&[
+                metric_sample("cpu", 10.0, 1_000),
+                metric_sample("cpu", 20.0, 2_000),
+            ])
+            .await;
+        db_client
+            .rollup_metrics(MetricsResolution::Minute, hour + 60_000)
+            .await;
+        db_client
+            .rollup_metrics(MetricsResolution::Hour, 2 * hour)
+            .await;
+        assert_eq!(
+            host_metric_values(&db_client, MetricsResolution::Minute, "cpu").await,
+            vec![(0, 15.0)]
+        );
+        assert_eq!(
+            host_metric_values(&db_client, MetricsResolution::Hour, "cpu").await,
+            vec![(0, 15.0)]
+        );
+
+        // a sample stored after its buckets were already rolled up
+        db_client
+            .store_host_metrics(&[metric_sample("cpu", 60.0, 3_000)])
+            .await;
+        db_client
+            .rollup_metrics(MetricsResolution::Minute, hour + 60_000)
+            .await;
+        db_client
+            .rollup_metrics(MetricsResolution::Hour, 2 * hour)
+            .await;
+        assert_eq!(
+            host_metric_values(&db_client, MetricsResolution::Minute, "cpu").await,
+            vec![(0, 30.0)]
+        );
+        assert_eq!(
+            host_metric_values(&db_client, MetricsResolution::Hour, "cpu").await,
+            vec![(0, 30.0)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_typed_nodes_metrics_migration_discards_non_numeric_values() {
+        let db = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let mut migrator = Migrator::new(current_dir().unwrap().join("migrations"))
+            .await
+            .unwrap();
+        migrator.set_ignore_missing(true);
+        migrator.run_to(20261018000000, &db).await.unwrap();
+
+        for (timestamp, key, value) in [
+            (Some(1), Some("cpu"), Some("12.5")),
+            (Some(2), Some("cpu"), Some(" 7 ")),
+            (Some(3), Some("cpu"), Some("1e3")),
+            (Some(4), Some("cpu"), Some("")),
+            (Some(5), Some("cpu"), Some("n/a")),
+            (Some(6), Some("cpu"), None),
+            (None, Some("cpu"), Some("1")),
+            (Some(7), None, Some("1")),
+        ] {
+            sqlx::query(
+                "INSERT INTO nodes_metrics (node_id, timestamp, key, value) VALUES (?, ?, ?, ?)",
+            )
+            .bind("node1")
+            .bind(timestamp)
+            .bind(key)
+            .bind(value)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+        migrator.run(&db).await.unwrap();
+
+        let rows: Vec<(i64, f64)> =
+            sqlx::query_as("SELECT timestamp, value FROM nodes_metrics ORDER BY timestamp")
+                .fetch_all(&db)
+                .await
+                .unwrap();
+        assert_eq!(rows, vec![(1, 12.5), (2, 7.0), (3, 1_000.0)]);
+    }
+
+    #[tokio::test]
+    async fn test_store_nodes_metrics_in_batches() {
+        let db_client = test_db_client().await;
+        let node_id = NodeId::random();
+        let mut metrics = (0..METRICS_INSERT_CHUNK_SIZE as i64 * 2 + 1)
+            .map(|ts| {
+                let metric = NodeMetric {
+                    key: "cpu".to_string(),
+                    value: ts.to_string(),
+                    timestamp: ts,
+                };
+                (node_id.clone(), metric)
+            })
+            .collect::<Vec<_>>();
+        metrics.push((
+            node_id.clone(),
+            NodeMetric {
+                key: "version".to_string(),
+                value: "0.1.0".to_string(),
+                timestamp: 1,
+            },
+        ));
+        db_client.store_nodes_metrics(&metrics).await;
+
+        let stored = db_client
+            .get_node_metrics(node_id, MetricsResolution::Raw, -1, i64::MAX)
+            .await;
+        assert_eq!(stored.len(), 1, "non-numeric values are not stored");
+        let cpu = &stored["cpu"];
+        assert_eq!(cpu.len(), METRICS_INSERT_CHUNK_SIZE * 2 + 1);
+        assert!(
+            cpu.iter()
+                .enumerate()
+                .all(|(i, m)| m.timestamp == i as i64 && m.value.parse::<f64>() == Ok(i as f64))
+        );
+    }
+
+    const MINUTE: i64 = 60 * 1_000;
+    const HOUR: i64 = 60 * MINUTE;
+    const DAY: i64 = 24 * HOUR;
+
+    #[tokio::test]
+    async fn test_rollup_metrics_averages_minute_and_hour_buckets() {
+        let db_client = test_db_client().await;
+        let (node_a, node_b) = (NodeId::random(), NodeId::random());
+        let samples = [
+            (&node_a, 10.0, 1_000),
+            (&node_a, 20.0, 30_000),
+            (&node_a, 40.0, MINUTE + 1_000),
+            (&node_a, 100.0, HOUR + 5_000),
+            (&node_b, 1.0, 2_000),
+        ]
+        .map(|(node_id, value, ts)| (node_id.clone(), metric_sample("cpu", value, ts)));
+        db_client.store_nodes_metrics(&samples).await;
+
+        let now = 2 * HOUR + MINUTE;
+        db_client
+            .rollup_metrics(MetricsResolution::Minute, now)
+            .await;
+        db_client.rollup_metrics(MetricsResolution::Hour, now).await;
+
+        let values = async |node_id: &NodeId, resolution| {
+            db_client
+                .get_node_metrics(node_id.clone(), resolution, -1, i64::MAX)
+                .await
+                .remove("cpu")
+                .unwrap_or_default()
+                .into_iter()
+                .map(|m| (m.timestamp, m.value.parse::<f64>().unwrap()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            values(&node_a, MetricsResolution::Minute).await,
+            vec![(0, 15.0), (MINUTE, 40.0), (HOUR, 100.0)]
+        );
+        assert_eq!(
+            values(&node_b, MetricsResolution::Minute).await,
+            vec![(0, 1.0)]
+        );
+        // hour buckets are averaged from the minute ones
+        assert_eq!(
+            values(&node_a, MetricsResolution::Hour).await,
+            vec![(0, 27.5), (HOUR, 100.0)]
+        );
+        assert_eq!(
+            values(&node_b, MetricsResolution::Hour).await,
+            vec![(0, 1.0)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_remove_metrics_older_than_per_resolution() {
+        let db_client = test_db_client().await;
+        db_client
+            .store_host_metrics(&[
+                metric_sample("cpu", 10.0, 1_000),
+                metric_sample("cpu", 20.0, HOUR + 1_000),
+            ])
+            .await;
+        let now = 2 * HOUR + MINUTE;
+        db_client
+            .rollup_metrics(MetricsResolution::Minute, now)
+            .await;
+        db_client.rollup_metrics(MetricsResolution::Hour, now).await;
+
+        db_client
+            .remove_metrics_older_than(MetricsResolution::Raw, HOUR)
+            .await;
+        let timestamps = async |resolution| {
+            host_metric_values(&db_client, resolution, "cpu")
+                .await
+                .into_iter()
+                .map(|(ts, _)| ts)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(timestamps(MetricsResolution::Raw).await, vec![HOUR + 1_000]);
+        assert_eq!(timestamps(MetricsResolution::Minute).await, vec![0, HOUR]);
+        assert_eq!(timestamps(MetricsResolution::Hour).await, vec![0, HOUR]);
+
+        db_client
+            .remove_metrics_older_than(MetricsResolution::Minute, HOUR)
+            .await;
+        assert_eq!(timestamps(MetricsResolution::Minute).await, vec![HOUR]);
+        assert_eq!(timestamps(MetricsResolution::Hour).await, vec![0, HOUR]);
+
+        db_client
+            .remove_metrics_older_than(MetricsResolution::Hour, 2 * HOUR)
+            .await;
+        assert!(timestamps(MetricsResolution::Hour).await.is_empty());
+        assert_eq!(timestamps(MetricsResolution::Raw).await, vec![HOUR + 1_000]);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_range_is_read_across_resolutions() {
+        let db_client = test_db_client().await;
+        let settings = AppSettings::default();
+        let now = 10 * DAY;
+        let (hour_ts, minute_ts, raw_ts) = (now - 8 * DAY, now - 2 * DAY, now - HOUR + 123);
+        db_client
+            .store_host_metrics(&[
+                metric_sample("cpu", 1.0, hour_ts),
+                metric_sample("cpu", 2.0, minute_ts),
+                metric_sample("cpu", 3.0, raw_ts),
+            ])
+            .await;
+        db_client
+            .rollup_metrics(MetricsResolution::Minute, now)
+            .await;
+        db_client.rollup_metrics(MetricsResolution::Hour, now).await;
+        for resolution in [
+            MetricsResolution::Raw,
+            MetricsResolution::Minute,
+            MetricsResolution::Hour,
+        ] {
+            db_client
+                .remove_metrics_older_than(resolution, now - resolution.retention_millis(&settings))
+                .await;
+        }
+
+        let mut stitched = Vec::new();
+        for (resolution, from, to) in MetricsResolution::windows_for_range(None, now, &settings) {
+            let metrics = db_client.get_host_metrics(resolution, from, to).await;
+            stitched.extend(
+                metrics
+                    .get("cpu")
+                    .into_iter()
+                    .flatten()
+                    .map(|m| (resolution, m.timestamp, m.value.parse::<f64>().unwrap())),
+            );
+        }
+        assert_eq!(
+            stitched,
+            vec![
+                (MetricsResolution::Hour, hour_ts, 1.0),
+                (MetricsResolution::Minute, minute_ts, 2.0),
+                (MetricsResolution::Raw, raw_ts, 3.0),
+            ]
+        );
+    }
+
+    fn nodes_stats(total_nodes: usize, active_nodes: usize) -> Stats {
+        Stats {
+            total_nodes,
+            active_nodes,
+            connected_peers: 3,
+            shunned_count: 4,
+            estimated_net_size: 5,
+            stored_records: 6,
+            relevant_records: 7,
+            used_disk_space: 8,
+            available_disk_space: 9,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_store_stats_snapshot() {
+        let db_client = test_db_client().await;
+        db_client
+            .store_stats_snapshot(&nodes_stats(1, 2), 1_000)
+            .await;
+        // a snapshot taken at the same time replaces the previous one
+        db_client
+            .store_stats_snapshot(&nodes_stats(10, 2), 2_000)
+            .await;
+        db_client
+            .store_stats_snapshot(&nodes_stats(20, 2), 2_000)
+            .await;
+
+        assert_eq!(
+            db_client.get_stats_history(0, 1).await,
+            vec![
+                StatsSnapshot {
+                    timestamp: 1_000,
+                    total_nodes: 1.0,
+                    active_nodes: 2.0,
+                    connected_peers: 3.0,
+                    shunned_count: 4.0,
+                    estimated_net_size: 5.0,
+                    stored_records: 6.0,
+                    relevant_records: 7.0,
+                    used_disk_space: 8.0,
+                    available_disk_space: 9.0,
+                },
+                StatsSnapshot {
+                    timestamp: 2_000,
+                    total_nodes: 20.0,
+                    active_nodes: 2.0,
+                    connected_peers: 3.0,
+                    shunned_count: 4.0,
+                    estimated_net_size: 5.0,
+                    stored_records: 6.0,
+                    relevant_records: 7.0,
+                    used_disk_space: 8.0,
+                    available_disk_space: 9.0,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stats_history_is_averaged_into_buckets() {
+        let db_client = test_db_client().await;
+        for (timestamp, total_nodes, active_nodes) in [
+            (MINUTE - 1_000, 100, 100),
+            (MINUTE, 2, 1),
+            (MINUTE + 30_000, 4, 2),
+            (2 * MINUTE + 1_000, 10, 5),
+        ] {
+            db_client
+                .store_stats_snapshot(&nodes_stats(total_nodes, active_nodes), timestamp)
+                .await;
+        }
+
+        // snapshots taken before 'since' are left out
+        let history = db_client.get_stats_history(MINUTE - 1_000, MINUTE).await;
+        let buckets = history
+            .iter()
+            .map(|s| (s.timestamp, s.total_nodes, s.active_nodes, s.stored_records))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            buckets,
+            vec![(MINUTE, 3.0, 1.5, 6.0), (2 * MINUTE, 10.0, 5.0, 6.0)]
+        );
+    }
 }
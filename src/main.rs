@@ -4,7 +4,6 @@
 #[tokio::main]
 async fn main() -> eyre::Result<()> {
     use formicaio::cli_cmds::*;
-    use std::net::{IpAddr, Ipv4Addr, SocketAddr};
     use structopt::StructOpt;
 
     let cmds = CliCmds::from_args();
@@ -13,19 +12,36 @@ async fn main() -> eyre::Result<()> {
         CliSubCmds::Start => start_backend(cmds.addr).await?,
         #[cfg(feature = "native")]
         CliSubCmds::Start(sub_cmds) => start_backend(cmds.addr, sub_cmds).await?,
+        CliSubCmds::Login(sub_cmds) => {
+            login_request(backend_addr(cmds.addr), &sub_cmds)
+                .await?
+                .printstd();
+        }
+        CliSubCmds::Logout => {
+            let addr = backend_addr(cmds.addr);
+            let token = cmds.token.or_else(|| stored_token(addr));
+            logout_request(addr, token.as_deref()).await?.printstd();
+        }
         CliSubCmds::CliCommands(cmd) => {
-            let res = cmd
-                .send_request(cmds.addr.unwrap_or(SocketAddr::new(
-                    IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)),
-                    52100,
-                )))
-                .await?;
+            let addr = backend_addr(cmds.addr);
+            let token = cmds.token.or_else(|| stored_token(addr));
+            let res = cmd.send_request(addr, token.as_deref()).await?;
             res.printstd();
         }
     }
     Ok(())
 }
 
+// Address of the backend the CLI commands are sent to, unless one is provided.
+#[cfg(feature = "ssr")]
+fn backend_addr(addr: Option<std::net::SocketAddr>) -> std::net::SocketAddr {
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+    addr.unwrap_or(SocketAddr::new(
+        IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)),
+        52100,
+    ))
+}
+
 #[cfg(feature = "ssr")]
 async fn start_backend(
     listen_addr: Option<std::net::SocketAddr>,
@@ -35,7 +51,7 @@ async fn start_backend(
     use eyre::{WrapErr, bail};
     use formicaio::{
         app::{App, AppContext, ServerGlobalState, shell},
-        bg_tasks::spawn_bg_tasks,
+        bg_tasks::{events_stream, metrics_exporter, require_auth, spawn_bg_tasks},
         db_client::DbClient,
         node_mgr::NodeManager,
     };
@@ -124,12 +140,19 @@ async fn start_backend(
     }
 
     let app = Router::new()
+        .route("/metrics", axum::routing::get(metrics_exporter))
+        .route("/api/events", axum::routing::get(events_stream))
         .leptos_routes(&app_state, routes, {
             move || shell(leptos_options.clone())
         })
         .fallback(leptos_axum::file_and_error_handler::<ServerGlobalState, _>(
             shell,
         ))
+        // all the server API requires an authenticated session
+        .layer(axum::middleware::from_fn_with_state(
+            app_state.clone(),
+            require_auth,
+        ))
         .with_state(app_state);
 
     let listener = tokio::net::TcpListener::bind(&listen_addr)
@@ -1,10 +1,10 @@
 use crate::{
     app::AppContext,
-    bg_tasks::{BgTasksCmds, NodesMetrics},
+    bg_tasks::{BgTasksCmds, NodesMetrics, replace_batched_node_id},
     db_client::DbError,
     types::{
-        InactiveReason, MetricsMode, NodeFilter, NodeId, NodeInstanceInfo, NodeList, NodeOpts,
-        NodeStatus,
+        InactiveReason, MetricsMode, NodeConfigUpdate, NodeFilter, NodeId, NodeInstanceInfo,
+        NodeList, NodeOpts, NodeStatus, ServerEvent,
     },
 };
 
@@ -78,10 +78,23 @@ impl NodeManager {
             logging::error!("[ERROR][NodeMgr] Failed to pull node image: {err}");
             return Err(err.into());
         }
-        *self.app_ctx.latest_bin_version.write().await = version.cloned();
+        self.app_ctx.set_latest_bin_version(version.cloned()).await;
         Ok(())
     }
 
+    // Set the node image to create containers with, as configured in the settings
+    async fn set_node_image(&self) {
+        let settings = self.app_ctx.db_client.get_settings().await;
+        let (name, tag) = match settings.node_bin_download_url.as_deref() {
+            Some(custom) => parse_image(custom),
+            None => (
+                DEFAULT_NODE_CONTAINER_IMAGE_NAME,
+                DEFAULT_NODE_CONTAINER_IMAGE_TAG,
+            ),
+        };
+        self.docker_client.set_image(name, tag).await;
+    }
+
     // Create a node instance
     pub async fn create_node_instance(
         &self,
@@ -92,16 +105,11 @@ impl NodeManager {
             node_opts.port
         );
         let auto_start = node_opts.auto_start;
-        let settings = self.app_ctx.db_client.get_settings().await;
-        let (name, tag) = match settings.node_bin_download_url.as_deref() {
-            Some(custom) => parse_image(custom),
-            None => (
-                DEFAULT_NODE_CONTAINER_IMAGE_NAME,
-                DEFAULT_NODE_CONTAINER_IMAGE_TAG,
-            ),
-        };
-        self.docker_client.set_image(name, tag).await;
-        let node_id = self.docker_client.create_new_container(node_opts).await?;
+        self.set_node_image().await;
+        let node_id = self
+            .docker_client
+            .create_new_container(node_opts, None)
+            .await?;
         logging::log!("[NodeMgr] New node ID: {node_id} ...");
 
         let mut node_info = self.docker_client.get_container_info(&node_id).await?;
@@ -111,6 +119,7 @@ impl NodeManager {
             .db_client
             .insert_node_metadata(&node_info)
             .await;
+        self.publish_node_updated(&node_id).await;
 
         if auto_start {
             self.start_node_instance(node_id.clone()).await?;
@@ -139,6 +148,7 @@ impl NodeManager {
             .db_client
             .update_node_status(&node_id, &NodeStatus::Restarting)
             .await;
+        self.publish_node_updated(&node_id).await;
 
         let (bin_version, peer_id, ips) =
             self.docker_client.start_container(&node_id, true).await?;
@@ -156,6 +166,7 @@ impl NodeManager {
             .db_client
             .update_node_metadata(&node_info, false)
             .await;
+        self.publish_node_updated(&node_info.node_id).await;
 
         Ok(())
     }
@@ -176,6 +187,7 @@ impl NodeManager {
             .db_client
             .update_node_status(&node_id, &NodeStatus::Stopping)
             .await;
+        self.publish_node_updated(&node_id).await;
 
         let res = self.docker_client.stop_container(&node_id).await;
 
@@ -199,6 +211,7 @@ impl NodeManager {
         }
 
         self.app_ctx.node_status_locked.remove(&node_id).await;
+        self.publish_node_updated(&node_id).await;
 
         Ok(res?)
     }
@@ -206,7 +219,18 @@ impl NodeManager {
     // Delete a node instance with given id
     pub async fn delete_node_instance(&self, node_id: NodeId) -> Result<(), NodeManagerError> {
         let node_info = self.docker_client.get_container_info(&node_id).await?;
+        let data_volume = self
+            .docker_client
+            .get_container_data_volume(&node_id)
+            .await?;
         self.docker_client.delete_container(&node_id).await?;
+        if let Some(volume) = data_volume
+            && let Err(err) = self.docker_client.delete_volume(&volume).await
+        {
+            logging::error!(
+                "[ERROR][NodeMgr] Failed to remove data volume of node {node_id}: {err}"
+            );
+        }
         self.app_ctx.db_client.delete_node_metadata(&node_id).await;
         self.app_ctx
             .nodes_metrics
@@ -214,6 +238,10 @@ impl NodeManager {
             .await
             .remove_node_metrics(&node_id)
             .await;
+        let _ = self
+            .app_ctx
+            .events_tx
+            .send(ServerEvent::NodeRemoved(node_id.clone()));
 
         self.app_ctx
             .bg_tasks_cmds_tx
@@ -243,6 +271,7 @@ impl NodeManager {
             .db_client
             .update_node_status(node_id, &NodeStatus::Upgrading)
             .await;
+        self.publish_node_updated(node_id).await;
 
         let res = self
             .docker_client
@@ -272,6 +301,7 @@ impl NodeManager {
         }
 
         self.app_ctx.node_status_locked.remove(node_id).await;
+        self.publish_node_updated(node_id).await;
 
         let _ = res?;
 
@@ -294,6 +324,7 @@ impl NodeManager {
             .db_client
             .update_node_status(&node_id, &NodeStatus::Recycling)
             .await;
+        self.publish_node_updated(&node_id).await;
 
         let (bin_version, peer_id, ips) = self
             .docker_client
@@ -315,10 +346,141 @@ impl NodeManager {
             .await;
 
         self.app_ctx.node_status_locked.remove(&node_id).await;
+        self.publish_node_updated(&node_id).await;
 
         Ok(())
     }
 
+    // Update the configuration of a node instance with given id. The configuration is set
+    // upon creating the container, thus a new container is created with the new configuration,
+    // and the node's data copied onto it, so the node keeps its peer-id but gets a new node id.
+    pub async fn update_node_instance(
+        &self,
+        node_id: NodeId,
+        changes: NodeConfigUpdate,
+    ) -> Result<NodeInstanceInfo, NodeManagerError> {
+        let _ = self
+            .app_ctx
+            .db_client
+            .check_node_is_not_batched(&node_id)
+            .await?;
+        let mut node_info = self.docker_client.get_container_info(&node_id).await?;
+
+        logging::log!("[NodeMgr] Updating configuration of node {node_id}: {changes} ...");
+        let was_active = node_info.status.is_active();
+        if was_active {
+            self.stop_node_instance(node_id.clone()).await?;
+        }
+
+        let prev_node_info = node_info.clone();
+        changes.apply_to(&mut node_info);
+        self.set_node_image().await;
+        // the container is recreated on the same volume, so its data is kept as is. Containers
+        // created before nodes had their own volume get their data copied onto a new one.
+        let new_container = async {
+            let data_volume = self
+                .docker_client
+                .get_container_data_volume(&node_id)
+                .await?;
+            let with_data = data_volume.is_none();
+            let new_node_id = self
+                .docker_client
+                .create_new_container(NodeOpts::from(&node_info), data_volume)
+                .await?;
+            Ok::<_, DockerClientError>((new_node_id, with_data))
+        }
+        .await;
+        let (new_node_id, with_data) = match new_container {
+            Ok(new_container) => new_container,
+            Err(err) => {
+                logging::error!(
+                    "[ERROR][NodeMgr] Failed to create new container for node {node_id}: {err}"
+                );
+                // leave the node as it was before the update
+                if was_active {
+                    let _ = self.start_node_instance(node_id).await;
+                }
+                return Err(err.into());
+            }
+        };
+
+        let moved = match self
+            .docker_client
+            .copy_node_data(&node_id, &new_node_id, with_data)
+            .await
+        {
+            Ok(()) => self.docker_client.delete_container(&node_id).await,
+            Err(err) => Err(err),
+        };
+        if let Err(err) = moved {
+            logging::error!(
+                "[ERROR][NodeMgr] Failed to move node {node_id} onto its new container: {err}"
+            );
+            // leave the node as it was before the update
+            let new_volume = if with_data {
+                self.docker_client
+                    .get_container_data_volume(&new_node_id)
+                    .await
+                    .ok()
+                    .flatten()
+            } else {
+                None
+            };
+            let _ = self.docker_client.delete_container(&new_node_id).await;
+            if let Some(volume) = new_volume {
+                let _ = self.docker_client.delete_volume(&volume).await;
+            }
+            if was_active {
+                let _ = self.start_node_instance(node_id).await;
+            }
+            return Err(err.into());
+        }
+
+        // the node id is the container's id, so everything referencing the node is moved to the new one
+        self.app_ctx
+            .db_client
+            .update_node_id(&prev_node_info.node_id, &new_node_id)
+            .await;
+        replace_batched_node_id(&self.app_ctx, &node_id, &new_node_id).await;
+        node_info.node_id = new_node_id.clone();
+        self.app_ctx.db_client.update_node_config(&node_info).await;
+        self.app_ctx
+            .nodes_metrics
+            .write()
+            .await
+            .remove_node_metrics(&node_id)
+            .await;
+        let _ = self
+            .app_ctx
+            .events_tx
+            .send(ServerEvent::NodeRemoved(node_id.clone()));
+        self.publish_node_updated(&new_node_id).await;
+
+        // the old container is gone at this point, so the node's new id is returned even
+        // if it cannot be started, otherwise the caller would be left with a stale id
+        if was_active && let Err(err) = self.start_node_instance(new_node_id.clone()).await {
+            logging::error!(
+                "[ERROR][NodeMgr] Failed to start node {new_node_id} after updating its configuration: {err}"
+            );
+        }
+
+        if changes.rewards_addr.is_some() {
+            self.app_ctx
+                .bg_tasks_cmds_tx
+                .send(BgTasksCmds::DeleteBalanceFor(prev_node_info))
+                .map_err(|err| NodeManagerError::BgTasks(err.to_string()))?;
+            self.app_ctx
+                .bg_tasks_cmds_tx
+                .send(BgTasksCmds::CheckBalanceFor(node_info.clone()))
+                .map_err(|err| NodeManagerError::BgTasks(err.to_string()))?;
+        }
+
+        logging::log!(
+            "[NodeMgr] Configuration of node {node_id} updated successfully, new node ID: {new_node_id}"
+        );
+        Ok(node_info)
+    }
+
     // Obtain a non-filtered list of existing nodes.
     pub async fn get_nodes_list(
         &self,
@@ -367,6 +529,51 @@ impl NodeManager {
         Ok(nodes)
     }
 
+    // Obtain the up to date info of a node instance, as it's presented to the user.
+    pub async fn node_instance_info(
+        &self,
+        node_id: &NodeId,
+    ) -> Result<NodeInstanceInfo, NodeManagerError> {
+        let mut node_info = self.docker_client.get_container_info(node_id).await?;
+        self.complete_node_info(&mut node_info).await;
+        Ok(node_info)
+    }
+
+    // Complete the info of a node, as obtained from its container, with the
+    // metadata cached in the DB and its latest metrics.
+    pub async fn complete_node_info(&self, node_info: &mut NodeInstanceInfo) {
+        self.app_ctx
+            .db_client
+            .get_node_metadata(node_info, false)
+            .await;
+        if node_info.status.is_active() {
+            self.app_ctx
+                .nodes_metrics
+                .read()
+                .await
+                .update_node_info(node_info);
+        }
+    }
+
+    // Publish the up to date info of a node onto the events bus, e.g. after its status changed.
+    async fn publish_node_updated(&self, node_id: &NodeId) {
+        // there is no point in querying the node if nobody is listening
+        if self.app_ctx.events_tx.receiver_count() == 0 {
+            return;
+        }
+        match self.node_instance_info(node_id).await {
+            Ok(info) => {
+                let _ = self
+                    .app_ctx
+                    .events_tx
+                    .send(ServerEvent::NodeUpdated(Box::new(info)));
+            }
+            Err(err) => logging::warn!(
+                "[WARN][NodeMgr] Failed to get info of node {node_id} to publish it: {err}"
+            ),
+        }
+    }
+
     // Return a node logs stream.
     pub async fn get_node_logs_stream(
         &self,
@@ -41,11 +41,22 @@ pub const LABEL_KEY_REWARDS_ADDR: &str = "rewards_addr";
 pub const LABEL_KEY_NODE_LOGS_DISABLED: &str = "node_logs_disabled";
 // Label's key to cache the log level for the node
 pub const LABEL_KEY_LOG_LEVEL: &str = "log_level";
+// Label's key to cache the IPv4-only mode set for the node
+pub const LABEL_KEY_IPV4_ONLY: &str = "ipv4_only";
+// Label's key to keep the name of the volume the node's data is stored in
+const LABEL_KEY_DATA_VOLUME: &str = "data_volume";
+
+// Paths of the node's data directory and binary within its container
+const NODE_DATA_PATH: &str = "/app/node_data";
+const NODE_BIN_PATH: &str = "/app/ant-node";
+// Prefix of the name of the volumes created to store the nodes' data
+const DATA_VOLUME_NAME_PREFIX: &str = "formicaio_node_";
 
 // Docker API base paths
 const DOCKER_CONTAINERS_API: &str = "/containers";
 const DOCKER_EXEC_API: &str = "/exec";
 const DOCKER_IMAGES_API: &str = "/images";
+const DOCKER_VOLUMES_API: &str = "/volumes";
 
 // Env var name to set the path of the Docker socket.
 const DOCKER_SOCKET_PATH: &str = "DOCKER_SOCKET_PATH";
@@ -112,32 +123,7 @@ impl ReqMethod {
         query_params: &[(&str, &str)],
         socket_path: &Path,
     ) -> Result<Response<Incoming>, DockerClientError> {
-        let unix_stream = UnixStream::connect(socket_path).await.map_err(|err| {
-            DockerClientError::ClientConnError(format!(
-                "Failed to connect to Docker socket at {socket_path:?}: {err:?}"
-            ))
-        })?;
-        let io = TokioIo::new(unix_stream);
-        let (mut docker_reqs_sender, connection) = conn::http1::handshake(io).await?;
-        tokio::spawn(async move {
-            if let Err(err) = connection.await {
-                logging::error!("[ERROR] Docker connection error: {err:?}");
-            }
-        });
-
-        // Construct the query string using url::form_urlencoded
-        let query_string = form_urlencoded::Serializer::new(String::new())
-            .extend_pairs(query_params)
-            .finish();
-
-        // Construct the full URL with query parameters
-        let full_url = format!("{base_url}?{query_string}");
-
-        let req_builder = Request::builder()
-            .uri(full_url)
-            // Host added just because http1 requires it
-            .header("Host", "localhost");
-
+        let req_builder = request_builder(base_url, query_params);
         let req = match self {
             ReqMethod::Post(body_str) => req_builder
                 .method(Method::POST)
@@ -152,33 +138,70 @@ impl ReqMethod {
             ReqMethod::Delete => req_builder.method(Method::DELETE).body(Body::from(()))?,
         };
 
-        let resp = docker_reqs_sender.send_request(req).await?;
-
-        match resp.status() {
-            StatusCode::NO_CONTENT | StatusCode::CREATED | StatusCode::OK => Ok(resp),
-            StatusCode::NOT_FOUND => {
-                let resp_bytes = get_response_bytes(resp).await?;
-                let msg: ServerErrorMessage = serde_json::from_slice(&resp_bytes)?;
-                // unfortunatelly the API returns different error msgs
-                // instead of different error codes to handle them
-                if msg.message.starts_with("No such image") {
-                    Err(DockerClientError::ImageNotFound)
-                } else {
-                    Err(DockerClientError::DockerServerError(
-                        StatusCode::NOT_FOUND.into(),
-                        msg.message,
-                    ))
-                }
-            }
-            other => {
-                let resp_bytes = get_response_bytes(resp).await?;
-                let msg = match serde_json::from_slice::<ServerErrorMessage>(&resp_bytes) {
-                    Ok(msg) => msg.message,
-                    Err(_) => String::from_utf8_lossy(&resp_bytes).to_string(),
-                };
-                Err(DockerClientError::DockerServerError(other.into(), msg))
+        send_docker_request(req, socket_path).await
+    }
+}
+
+// Build a request to the Docker server with the given url and query parameters.
+fn request_builder(base_url: &str, query_params: &[(&str, &str)]) -> http::request::Builder {
+    // Construct the query string using url::form_urlencoded
+    let query_string = form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(query_params)
+        .finish();
+
+    // Construct the full URL with query parameters
+    let full_url = format!("{base_url}?{query_string}");
+
+    Request::builder()
+        .uri(full_url)
+        // Host added just because http1 requires it
+        .header("Host", "localhost")
+}
+
+// Send a request to the Docker server through its socket.
+async fn send_docker_request(
+    req: Request<Body>,
+    socket_path: &Path,
+) -> Result<Response<Incoming>, DockerClientError> {
+    let unix_stream = UnixStream::connect(socket_path).await.map_err(|err| {
+        DockerClientError::ClientConnError(format!(
+            "Failed to connect to Docker socket at {socket_path:?}: {err:?}"
+        ))
+    })?;
+    let io = TokioIo::new(unix_stream);
+    let (mut docker_reqs_sender, connection) = conn::http1::handshake(io).await?;
+    tokio::spawn(async move {
+        if let Err(err) = connection.await {
+            logging::error!("[ERROR] Docker connection error: {err:?}");
+        }
+    });
+
+    let resp = docker_reqs_sender.send_request(req).await?;
+
+    match resp.status() {
+        StatusCode::NO_CONTENT | StatusCode::CREATED | StatusCode::OK => Ok(resp),
+        StatusCode::NOT_FOUND => {
+            let resp_bytes = get_response_bytes(resp).await?;
+            let msg: ServerErrorMessage = serde_json::from_slice(&resp_bytes)?;
+            // unfortunatelly the API returns different error msgs
+            // instead of different error codes to handle them
+            if msg.message.starts_with("No such image") {
+                Err(DockerClientError::ImageNotFound)
+            } else {
+                Err(DockerClientError::DockerServerError(
+                    StatusCode::NOT_FOUND.into(),
+                    msg.message,
+                ))
             }
         }
+        other => {
+            let resp_bytes = get_response_bytes(resp).await?;
+            let msg = match serde_json::from_slice::<ServerErrorMessage>(&resp_bytes) {
+                Ok(msg) => msg.message,
+                Err(_) => String::from_utf8_lossy(&resp_bytes).to_string(),
+            };
+            Err(DockerClientError::DockerServerError(other.into(), msg))
+        }
     }
 }
 
@@ -235,6 +258,23 @@ impl DockerClient {
             .ok_or(DockerClientError::CointainerNotFound(id.clone()))
     }
 
+    // Query the Docker server to return the name of the volume where the data of the
+    // container matching the given id is stored, if it was created with its own volume.
+    pub async fn get_container_data_volume(
+        &self,
+        id: &NodeId,
+    ) -> Result<Option<String>, DockerClientError> {
+        let mut filters = HashMap::default();
+        filters.insert("id".to_string(), vec![id.to_string()]);
+        let container = self
+            .query_containers(&filters, true)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or(DockerClientError::CointainerNotFound(id.clone()))?;
+        Ok(container.Labels.get(LABEL_KEY_DATA_VOLUME).cloned())
+    }
+
     // Query the Docker server to return the list of ALL existing containers,
     // unless 'all' argument is set to false in which case only running containers are returned.
     pub async fn get_containers_list(
@@ -254,22 +294,20 @@ impl DockerClient {
         all: bool,
         read_system_metrics: bool,
     ) -> Result<Vec<NodeInstanceInfo>, DockerClientError> {
-        let url = format!("{DOCKER_CONTAINERS_API}/json");
-        let all_str = all.to_string();
-        let query = &[
-            ("all", all_str.as_str()),
-            ("filters", &serde_json::to_string(filters)?),
-        ];
-        let resp_bytes = self.send_request(ReqMethod::Get, &url, query).await?;
-        let containers: Vec<Container> = serde_json::from_slice(&resp_bytes)?;
+        let containers = self.query_containers(filters, all).await?;
         let mut nodes: Vec<NodeInstanceInfo> = containers.into_iter().map(|c| c.into()).collect();
 
         if read_system_metrics {
             for node in nodes.iter_mut() {
-                if node.status.is_active() {
-                    if let Ok((mem_mb, cpu_pct)) = self.get_container_stats(&node.node_id).await {
-                        node.mem_used = Some(mem_mb);
-                        node.cpu_usage = Some(cpu_pct);
+                if node.status.is_active()
+                    && let Ok((mem_mb, cpu_pct, net_io)) =
+                        self.get_container_stats(&node.node_id).await
+                {
+                    node.mem_used = Some(mem_mb);
+                    node.cpu_usage = Some(cpu_pct);
+                    if let Some((rx_bytes, tx_bytes)) = net_io {
+                        node.net_rx_bytes = Some(rx_bytes);
+                        node.net_tx_bytes = Some(tx_bytes);
                     }
                 }
             }
@@ -293,7 +331,24 @@ impl DockerClient {
         Ok(nodes)
     }
 
-    // Request the Docker server to DELETE a container matching the given id
+    // Query the Docker server to return the raw LIST of existing containers using the given filter.
+    async fn query_containers(
+        &self,
+        filters: &HashMap<String, Vec<String>>,
+        all: bool,
+    ) -> Result<Vec<Container>, DockerClientError> {
+        let url = format!("{DOCKER_CONTAINERS_API}/json");
+        let all_str = all.to_string();
+        let query = &[
+            ("all", all_str.as_str()),
+            ("filters", &serde_json::to_string(filters)?),
+        ];
+        let resp_bytes = self.send_request(ReqMethod::Get, &url, query).await?;
+        Ok(serde_json::from_slice(&resp_bytes)?)
+    }
+
+    // Request the Docker server to DELETE a container matching the given id.
+    // The volume where its data is stored, if any, is not removed.
     pub async fn delete_container(&self, id: &NodeId) -> Result<(), DockerClientError> {
         let url = format!("{DOCKER_CONTAINERS_API}/{id}");
         logging::log!("[DELETE] Sending Docker request to delete containers: {url} ...");
@@ -302,6 +357,14 @@ impl DockerClient {
         Ok(())
     }
 
+    // Request the Docker server to DELETE a volume matching the given name
+    pub async fn delete_volume(&self, name: &str) -> Result<(), DockerClientError> {
+        let url = format!("{DOCKER_VOLUMES_API}/{name}");
+        logging::log!("[DELETE] Sending Docker request to delete volume: {url} ...");
+        self.send_request(ReqMethod::Delete, &url, &[]).await?;
+        Ok(())
+    }
+
     // Request the Docker server to START a container matching the given id
     pub async fn start_container(
         &self,
@@ -333,9 +396,11 @@ impl DockerClient {
     }
 
     // Request the Docker server to CREATE a new node container, returning the container info.
+    // The node's data is stored in the given volume, or in a new one if none is provided.
     pub async fn create_new_container(
         &self,
         node_opts: NodeOpts,
+        data_volume: Option<String>,
     ) -> Result<NodeId, DockerClientError> {
         let (image_name, image_tag) = {
             let img = self.node_image.read().await;
@@ -374,6 +439,7 @@ impl DockerClient {
         }
         if node_opts.ipv4_only {
             env_vars.push("IPV4_ONLY_ARG=--ipv4-only".to_string());
+            labels.push((LABEL_KEY_IPV4_ONLY.to_string(), "true".to_string()));
         } else {
             env_vars.push("IPV4_ONLY_ARG=".to_string());
         }
@@ -389,6 +455,11 @@ impl DockerClient {
             labels.push((LABEL_KEY_NODE_LOGS_DISABLED.to_string(), "true".to_string()));
         }
 
+        let random_name = hex::encode(rand::random::<[u8; 10]>());
+        let data_volume =
+            data_volume.unwrap_or_else(|| format!("{DATA_VOLUME_NAME_PREFIX}{random_name}"));
+        labels.push((LABEL_KEY_DATA_VOLUME.to_string(), data_volume.clone()));
+
         let container_create_req = ContainerCreate {
             Image: format!("{image_name}:{image_tag}"),
             // we use a label so we can then filter them when fetching a list of containers
@@ -419,10 +490,15 @@ impl DockerClient {
                         })
                         .collect::<PortBindings>(),
                 ),
+                // Docker creates the volume if it doesn't exist yet
+                Mounts: Some(vec![Mount {
+                    Type: "volume".to_string(),
+                    Source: data_volume,
+                    Target: NODE_DATA_PATH.to_string(),
+                }]),
             }),
         };
 
-        let random_name = hex::encode(rand::random::<[u8; 10]>());
         logging::log!(
             "[CREATE] Sending Docker request to create a new container (named: {random_name}): {url} ..."
         );
@@ -604,8 +680,13 @@ impl DockerClient {
         Ok(usage)
     }
 
-    // Return CPU usage (%) and memory usage (MB) for the given container.
-    pub async fn get_container_stats(&self, id: &NodeId) -> Result<(f64, f64), DockerClientError> {
+    // Return memory usage (MB), CPU usage (%), and the bytes received and transmitted, for the
+    // given container. The traffic counters are only available if the container has its own network,
+    // otherwise its traffic cannot be told apart from the host's.
+    pub async fn get_container_stats(
+        &self,
+        id: &NodeId,
+    ) -> Result<(f64, f64, Option<(u64, u64)>), DockerClientError> {
         let url = format!("{DOCKER_CONTAINERS_API}/{id}/stats");
         let resp_bytes = self
             .send_request(ReqMethod::Get, &url, &[("stream", "false")])
@@ -632,7 +713,13 @@ impl DockerClient {
             0.0
         };
 
-        Ok((mem_mb, cpu_pct))
+        let net_io = stats.networks.map(|networks| {
+            networks
+                .values()
+                .fold((0, 0), |(rx, tx), n| (rx + n.rx_bytes, tx + n.tx_bytes))
+        });
+
+        Ok((mem_mb, cpu_pct, net_io))
     }
 
     // Clears the node's PeerId within the containver and restarts it
@@ -667,6 +754,38 @@ impl DockerClient {
         self.get_node_version_and_peer_id(id, get_ips).await
     }
 
+    // Copy the node's binary, along with its data if requested, from a container onto another one.
+    // The archives are streamed from one container to the other so they are not loaded in memory.
+    pub async fn copy_node_data(
+        &self,
+        from: &NodeId,
+        to: &NodeId,
+        with_data: bool,
+    ) -> Result<(), DockerClientError> {
+        logging::log!("[COPY] Copying node files from container {from} to container {to} ...");
+        let paths = if with_data {
+            vec![NODE_BIN_PATH, NODE_DATA_PATH]
+        } else {
+            vec![NODE_BIN_PATH]
+        };
+        for path in paths {
+            let url = format!("{DOCKER_CONTAINERS_API}/{from}/archive");
+            let archive = self
+                .send_request_and_return_stream(ReqMethod::Get, &url, &[("path", path)])
+                .await?;
+
+            let url = format!("{DOCKER_CONTAINERS_API}/{to}/archive");
+            let req = request_builder(&url, &[("path", "/app")])
+                .header(CONTENT_TYPE, "application/x-tar")
+                .method(Method::PUT)
+                .body(Body::from_stream(archive))?;
+            let resp = send_docker_request(req, &self.socket_path).await?;
+            get_response_bytes(resp).await?;
+        }
+
+        Ok(())
+    }
+
     // Restart the container wich has given id
     async fn restart_container(&self, id: &NodeId) -> Result<(), DockerClientError> {
         let url = format!("{DOCKER_CONTAINERS_API}/{id}/restart");
@@ -2,8 +2,8 @@ use crate::types::{InactiveReason, NodeId, NodeInstanceInfo};
 
 #[cfg(feature = "ssr")]
 use super::docker_client::{
-    LABEL_KEY_LOG_LEVEL, LABEL_KEY_METRICS_PORT, LABEL_KEY_NODE_LOGS_DISABLED, LABEL_KEY_NODE_PORT,
-    LABEL_KEY_REWARDS_ADDR,
+    LABEL_KEY_IPV4_ONLY, LABEL_KEY_LOG_LEVEL, LABEL_KEY_METRICS_PORT, LABEL_KEY_NODE_LOGS_DISABLED,
+    LABEL_KEY_NODE_PORT, LABEL_KEY_REWARDS_ADDR,
 };
 #[cfg(feature = "ssr")]
 use crate::types::NodeStatus;
@@ -51,6 +51,7 @@ impl From<Container> for NodeInstanceInfo {
             port: val.port(),
             metrics_port: val.metrics_port(),
             rewards_addr: val.Labels.get(LABEL_KEY_REWARDS_ADDR).cloned(),
+            ipv4_only: val.Labels.contains_key(LABEL_KEY_IPV4_ONLY),
             node_logs: !val.Labels.contains_key(LABEL_KEY_NODE_LOGS_DISABLED),
             log_level: val
                 .Labels
@@ -125,6 +126,15 @@ pub struct HostConfigCreate {
     pub NetworkMode: Option<String>,
     pub PublishAllPorts: Option<bool>,
     pub PortBindings: Option<PortBindings>,
+    pub Mounts: Option<Vec<Mount>>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[allow(non_snake_case)]
+pub struct Mount {
+    pub Type: String,
+    pub Source: String,
+    pub Target: String,
 }
 
 pub type ExposedPorts = HashMap<String, HashMap<i32, i32>>;
@@ -196,6 +206,17 @@ pub struct ContainerStats {
     pub memory_stats: MemoryStats,
     pub cpu_stats: CpuStats,
     pub precpu_stats: CpuStats,
+    // Not reported for containers sharing the host network
+    #[serde(default)]
+    pub networks: Option<HashMap<String, NetworkStats>>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct NetworkStats {
+    #[serde(default)]
+    pub rx_bytes: u64,
+    #[serde(default)]
+    pub tx_bytes: u64,
 }
 
 #[derive(Deserialize, Debug, Default)]
@@ -10,9 +10,9 @@ mod native;
 mod native_nodes;
 
 #[cfg(not(feature = "native"))]
-pub use docker::NodeManager;
+pub use docker::{NodeManager, NodeManagerError};
 #[cfg(feature = "native")]
-pub use native::NodeManager;
+pub use native::{NodeManager, NodeManagerError};
 
 // Number of seconds before timing out an attempt to upgrade the node binary.
 pub const UPGRADE_NODE_BIN_TIMEOUT_SECS: u64 = 8 * 60; // 8 mins
@@ -4,8 +4,8 @@ use crate::{
     db_client::DbError,
     server_api::parse_and_validate_addr,
     types::{
-        BatchType, InactiveReason, MetricsMode, NodeFilter, NodeId, NodeInstanceInfo, NodeList,
-        NodeOpts, NodeStatus,
+        BatchType, InactiveReason, MetricsMode, NodeConfigUpdate, NodeFilter, NodeId,
+        NodeInstanceInfo, NodeList, NodeOpts, NodeStatus, ServerEvent,
     },
 };
 
@@ -159,7 +159,7 @@ impl NodeManager {
             .native_nodes
             .upgrade_master_node_binary(version, settings.node_bin_download_url.as_deref())
             .await?;
-        *self.app_ctx.latest_bin_version.write().await = Some(v);
+        self.app_ctx.set_latest_bin_version(Some(v)).await;
         Ok(())
     }
 
@@ -222,6 +222,7 @@ impl NodeManager {
             .db_client
             .insert_node_metadata(&node_info)
             .await;
+        self.publish_node_updated(&node_id).await;
         logging::log!("[NodeMgr] New node created successfully with ID: {node_id}");
 
         if node_opts.auto_start {
@@ -258,6 +259,7 @@ impl NodeManager {
             .db_client
             .update_node_status(&node_id, &node_info.status)
             .await;
+        self.publish_node_updated(&node_id).await;
         let res = self.native_nodes.spawn_new_node(&mut node_info).await;
 
         node_info.status = match &res {
@@ -280,6 +282,7 @@ impl NodeManager {
             .update_node_metadata(&node_info, true)
             .await;
         self.app_ctx.node_status_locked.remove(&node_id).await;
+        self.publish_node_updated(&node_id).await;
 
         res?;
         Ok(())
@@ -301,6 +304,7 @@ impl NodeManager {
             .db_client
             .update_node_status(&node_id, &NodeStatus::Stopping)
             .await;
+        self.publish_node_updated(&node_id).await;
 
         self.native_nodes.kill_node(&node_id).await;
 
@@ -322,6 +326,7 @@ impl NodeManager {
             .await;
         self.app_ctx.db_client.update_node_pid(&node_id, None).await;
         self.app_ctx.node_status_locked.remove(&node_id).await;
+        self.publish_node_updated(&node_id).await;
 
         Ok(())
     }
@@ -351,6 +356,10 @@ impl NodeManager {
             .await
             .remove_node_metrics(&node_info.node_id)
             .await;
+        let _ = self
+            .app_ctx
+            .events_tx
+            .send(ServerEvent::NodeRemoved(node_info.node_id.clone()));
 
         self.app_ctx
             .bg_tasks_cmds_tx
@@ -381,6 +390,7 @@ impl NodeManager {
             .db_client
             .update_node_status(node_id, &node_info.status)
             .await;
+        self.publish_node_updated(node_id).await;
 
         let res = self.native_nodes.upgrade_node(&mut node_info).await;
 
@@ -408,6 +418,7 @@ impl NodeManager {
             .update_node_metadata(&node_info, true)
             .await;
         self.app_ctx.node_status_locked.remove(node_id).await;
+        self.publish_node_updated(node_id).await;
 
         res?;
         Ok(())
@@ -431,6 +442,7 @@ impl NodeManager {
             .db_client
             .update_node_status(&node_id, &node_info.status)
             .await;
+        self.publish_node_updated(&node_id).await;
 
         let res = self.native_nodes.regenerate_peer_id(&mut node_info).await;
 
@@ -454,11 +466,58 @@ impl NodeManager {
             .update_node_metadata(&node_info, true)
             .await;
         self.app_ctx.node_status_locked.remove(&node_id).await;
+        self.publish_node_updated(&node_id).await;
 
         res?;
         Ok(())
     }
 
+    // Update the configuration of a node instance with given id, re-spawning
+    // its process with the new arguments if it was running.
+    pub async fn update_node_instance(
+        &self,
+        node_id: NodeId,
+        changes: NodeConfigUpdate,
+    ) -> Result<NodeInstanceInfo, NodeManagerError> {
+        let mut node_info = self
+            .app_ctx
+            .db_client
+            .check_node_is_not_batched(&node_id)
+            .await?;
+        if let Some(addr) = &changes.rewards_addr {
+            let _ = parse_and_validate_addr(addr).map_err(NodeManagerError::RewardsAddressError)?;
+        }
+
+        logging::log!("[NodeMgr] Updating configuration of node {node_id}: {changes} ...");
+        let was_active = node_info.status.is_active();
+        if was_active {
+            self.stop_node_instance(node_id.clone()).await?;
+        }
+
+        let prev_node_info = node_info.clone();
+        changes.apply_to(&mut node_info);
+        self.app_ctx.db_client.update_node_config(&node_info).await;
+        self.publish_node_updated(&node_id).await;
+
+        if was_active {
+            self.start_node_instance(node_id.clone()).await?;
+        }
+
+        if changes.rewards_addr.is_some() {
+            self.app_ctx
+                .bg_tasks_cmds_tx
+                .send(BgTasksCmds::DeleteBalanceFor(prev_node_info))
+                .map_err(|err| NodeManagerError::BgTasks(err.to_string()))?;
+            self.app_ctx
+                .bg_tasks_cmds_tx
+                .send(BgTasksCmds::CheckBalanceFor(node_info.clone()))
+                .map_err(|err| NodeManagerError::BgTasks(err.to_string()))?;
+        }
+
+        logging::log!("[NodeMgr] Configuration of node {node_id} updated successfully.");
+        Ok(node_info)
+    }
+
     // Obtain a non-filtered list of existing nodes.
     pub async fn get_nodes_list(
         &self,
@@ -512,6 +571,52 @@ impl NodeManager {
         Ok(nodes)
     }
 
+    // Obtain the up to date info of a node instance, as it's presented to the user.
+    pub async fn node_instance_info(
+        &self,
+        node_id: &NodeId,
+    ) -> Result<NodeInstanceInfo, NodeManagerError> {
+        let mut node_info = NodeInstanceInfo::new(node_id.clone());
+        self.complete_node_info(&mut node_info).await;
+        Ok(node_info)
+    }
+
+    // Complete the info of a node, as obtained from its process, with the
+    // metadata cached in the DB and its latest metrics.
+    pub async fn complete_node_info(&self, node_info: &mut NodeInstanceInfo) {
+        self.app_ctx
+            .db_client
+            .get_node_metadata(node_info, true)
+            .await;
+        helper_gen_status_info(node_info);
+        if node_info.status.is_active() {
+            self.app_ctx
+                .nodes_metrics
+                .read()
+                .await
+                .update_node_info(node_info);
+        }
+    }
+
+    // Publish the up to date info of a node onto the events bus, e.g. after its status changed.
+    async fn publish_node_updated(&self, node_id: &NodeId) {
+        // there is no point in querying the node if nobody is listening
+        if self.app_ctx.events_tx.receiver_count() == 0 {
+            return;
+        }
+        match self.node_instance_info(node_id).await {
+            Ok(info) => {
+                let _ = self
+                    .app_ctx
+                    .events_tx
+                    .send(ServerEvent::NodeUpdated(Box::new(info)));
+            }
+            Err(err) => logging::warn!(
+                "[WARN][NodeMgr] Failed to get info of node {node_id} to publish it: {err}"
+            ),
+        }
+    }
+
     // Return a node logs stream.
     pub async fn get_node_logs_stream(
         &self,
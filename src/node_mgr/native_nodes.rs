@@ -87,6 +87,42 @@ impl NodeProcess {
     }
 }
 
+// Bytes received and transmitted by a node process, read from the network interfaces of its
+// network namespace. They can only be told apart when the process runs in its own namespace,
+// otherwise the counters would be those of the whole host.
+#[cfg(target_os = "linux")]
+fn read_process_net_io(pid: u32) -> Option<(u64, u64)> {
+    let process_ns = std::fs::read_link(format!("/proc/{pid}/ns/net")).ok()?;
+    if std::fs::read_link("/proc/self/ns/net").ok()? == process_ns {
+        return None;
+    }
+
+    // the first two lines are headers, then each interface is listed as:
+    // '<iface>: <rx bytes> <rx packets> ... (8 rx fields) <tx bytes> <tx packets> ...'
+    let net_dev = std::fs::read_to_string(format!("/proc/{pid}/net/dev")).ok()?;
+    let net_io = net_dev
+        .lines()
+        .skip(2)
+        .filter_map(|line| line.split_once(':'))
+        .filter(|(iface, _)| iface.trim() != "lo")
+        .filter_map(|(_, counters)| {
+            let counters = counters.split_whitespace().collect::<Vec<_>>();
+            Some((
+                counters.first()?.parse::<u64>().ok()?,
+                counters.get(8)?.parse::<u64>().ok()?,
+            ))
+        })
+        .fold((0, 0), |(rx, tx), (iface_rx, iface_tx)| {
+            (rx + iface_rx, tx + iface_tx)
+        });
+    Some(net_io)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_process_net_io(_pid: u32) -> Option<(u64, u64)> {
+    None
+}
+
 // Determine the platform-specific archive name for downloading ant-node
 fn get_platform_archive_name() -> Result<String, NativeNodesError> {
     let arch = std::env::consts::ARCH;
@@ -464,6 +500,10 @@ impl NativeNodes {
                     node_info.set_status_active();
                     node_info.mem_used = Some(process.memory() as f64 / 1_048_576.0);
                     node_info.cpu_usage = Some(process.cpu_usage() as f64 / num_cpus);
+                    if let Some((rx_bytes, tx_bytes)) = read_process_net_io(pid) {
+                        node_info.net_rx_bytes = Some(rx_bytes);
+                        node_info.net_tx_bytes = Some(tx_bytes);
+                    }
 
                     if read_lmdb {
                         let count_from = |env: &heed::Env| -> Option<usize> {
@@ -1,30 +1,46 @@
 use crate::types::{
-    AgentEvent, BatchOnMatch, BatchType, ChatMessage, NodeFilter, NodeId, NodeInstanceInfo,
-    NodeOpts, NodesActionsBatch, NodesInstancesInfo, Stats, WidgetFourStats,
+    AddressBook, AddressBookEntry, AgentEvent, Alert, AlertRule, AuthStatus, BatchOnMatch,
+    BatchType, ChatMessage, EarningsBucket, EarningsExport, EarningsHistory, HostSnapshot,
+    HostStats, NodeConfigUpdate, NodeFilter, NodeId, NodeInstanceInfo, NodeOpts, NodeUpdated,
+    NodesActionsBatch, NodesInstancesInfo, NotificationChannel, Profitability,
+    RewardsAddressSummary, Stats, StatsHistoryRange, StatsSnapshot, UserInfo, UserRole,
+    WidgetFourStats,
 };
 
 use alloy_primitives::Address;
+use chrono::NaiveDate;
 use leptos::{
     prelude::*,
-    server_fn::codec::{ByteStream, GetUrl, Streaming},
+    server_fn::codec::{ByteStream, GetUrl, Json, Streaming},
 };
 use std::{collections::HashMap, str::FromStr};
 
 #[cfg(feature = "ssr")]
 mod ssr_imports_and_defs {
     pub use crate::{
-        app::ServerGlobalState,
+        app::{AppContext, ServerGlobalState},
         bg_tasks::{
-            BgTasksCmds,
+            BgTasksCmds, SESSION_DURATION_SECS,
             agent::{LlmClient, OpenAiCompatClient, process_chat_turn},
-            prepare_node_action_batch,
+            authenticated_user, calc_earnings_history, cancel_node_action_batch, earnings_export,
+            find_node_action_batch, hash_password, hash_session_token, host_metrics_history,
+            new_session_token, nodes_instances_info, pause_node_action_batch,
+            prepare_node_action_batch, profitability, resume_node_action_batch,
+            retry_node_action_batch, rewards_addrs_permission, send_notification, session_cookie,
+            session_token, set_node_action_batch_interval, verify_password,
+        },
+        types::{
+            CurrentUser, MetricsMode, NotificationEvent, NotificationEventKind, Permission,
+            WidgetStat, normalized_address, rewards_addresses_summaries, validate_credentials,
         },
-        types::{MetricsMode, WidgetStat},
         views::truncated_balance_str,
     };
     pub use bytes::Bytes;
     pub use futures_util::StreamExt;
+    pub use http::{HeaderMap, header::SET_COOKIE};
     pub use leptos::logging;
+    pub use leptos_axum::ResponseOptions;
+    pub use std::time::Duration;
     pub use tokio_stream::wrappers::ReceiverStream;
 }
 
@@ -33,6 +49,141 @@ use ssr_imports_and_defs::*;
 
 // Expected length of entered hex-encoded rewards address.
 const REWARDS_ADDR_LENGTH: usize = 40;
+// Delay before replying to a failed login attempt, to slow down guessing passwords.
+#[cfg(feature = "ssr")]
+const FAILED_LOGIN_DELAY: Duration = Duration::from_secs(1);
+
+/// Return the authentication status of the client, i.e. whether it's logged in
+#[server(name = GetAuthStatus, prefix = "/api", endpoint = "/auth/status")]
+pub async fn auth_status() -> Result<AuthStatus, ServerFnError> {
+    let context = expect_context::<ServerGlobalState>();
+    let headers: HeaderMap = leptos_axum::extract().await?;
+    let status = if let Some(user) = authenticated_user(&context.app_ctx, &headers).await {
+        AuthStatus::Authenticated(user)
+    } else if context.app_ctx.db_client.has_users().await {
+        AuthStatus::LoginRequired
+    } else {
+        AuthStatus::SetupRequired
+    };
+    Ok(status)
+}
+
+/// Create the first user, only allowed when no user has been set up yet, and log in with it
+#[server(name = SetupFirstUser, prefix = "/api", endpoint = "/auth/setup")]
+pub async fn setup_first_user(username: String, password: String) -> Result<(), ServerFnError> {
+    let context = expect_context::<ServerGlobalState>();
+    validate_credentials(&username, &password).map_err(ServerFnError::new)?;
+    if context.app_ctx.db_client.has_users().await {
+        return Err(ServerFnError::new("A user has already been set up"));
+    }
+
+    let password_hash = hash_password(&password)
+        .map_err(|err| ServerFnError::new(format!("Failed to hash password: {err}")))?;
+    if !context
+        .app_ctx
+        .db_client
+        .create_first_user(&username, &password_hash)
+        .await?
+    {
+        return Err(ServerFnError::new("A user has already been set up"));
+    }
+    logging::log!("[AUTH] First user '{username}' has been set up");
+
+    let (user_id, _) = context
+        .app_ctx
+        .db_client
+        .get_user_credentials(&username)
+        .await
+        .ok_or_else(|| ServerFnError::new("Failed to retrieve the user created"))?;
+    start_session(&context.app_ctx, user_id).await
+}
+
+/// Log in with the user's credentials, setting a session cookie which authenticates
+/// the following requests. The same session token can be sent as a bearer token instead.
+#[server(name = Login, prefix = "/api", endpoint = "/auth/login")]
+pub async fn login(username: String, password: String) -> Result<(), ServerFnError> {
+    let context = expect_context::<ServerGlobalState>();
+    match context
+        .app_ctx
+        .db_client
+        .get_user_credentials(&username)
+        .await
+    {
+        Some((user_id, password_hash)) if verify_password(&password, &password_hash) => {
+            logging::log!("[AUTH] User '{username}' logged in");
+            context.app_ctx.db_client.prune_expired_sessions().await;
+            start_session(&context.app_ctx, user_id).await
+        }
+        _ => {
+            logging::warn!("[AUTH] Failed login attempt for user '{username}'");
+            tokio::time::sleep(FAILED_LOGIN_DELAY).await;
+            Err(ServerFnError::new("Invalid username or password"))
+        }
+    }
+}
+
+/// Log out, ending the current session
+#[server(name = Logout, prefix = "/api", endpoint = "/auth/logout")]
+pub async fn logout() -> Result<(), ServerFnError> {
+    let context = expect_context::<ServerGlobalState>();
+    let headers: HeaderMap = leptos_axum::extract().await?;
+    if let Some(token) = session_token(&headers) {
+        context
+            .app_ctx
+            .db_client
+            .delete_session(&hash_session_token(&token))
+            .await;
+    }
+    set_session_cookie(&session_cookie("", 0))
+}
+
+// Create a new session for the user, setting its token in the session cookie of the response.
+#[cfg(feature = "ssr")]
+async fn start_session(app_ctx: &AppContext, user_id: i64) -> Result<(), ServerFnError> {
+    let token = new_session_token();
+    let expires = chrono::Utc::now().timestamp() + SESSION_DURATION_SECS;
+    app_ctx
+        .db_client
+        .store_session(&hash_session_token(&token), user_id, expires)
+        .await?;
+    set_session_cookie(&session_cookie(&token, SESSION_DURATION_SECS))
+}
+
+// Retrieve the user the request is made by.
+#[cfg(feature = "ssr")]
+async fn current_user() -> Result<CurrentUser, ServerFnError> {
+    let context = expect_context::<ServerGlobalState>();
+    let headers: HeaderMap = leptos_axum::extract().await?;
+    authenticated_user(&context.app_ctx, &headers)
+        .await
+        .ok_or_else(|| ServerFnError::new("Authentication required"))
+}
+
+// Retrieve the user the request is made by, failing if its role isn't allowed the permission.
+// Permissions are already checked for each endpoint by the authentication middleware, this is
+// only needed when the permission depends on the arguments.
+#[cfg(feature = "ssr")]
+async fn require_permission(permission: Permission) -> Result<CurrentUser, ServerFnError> {
+    let user = current_user().await?;
+    if user.can(permission) {
+        Ok(user)
+    } else {
+        Err(ServerFnError::new(format!(
+            "Permission denied, the '{}' role is not allowed to perform this action",
+            user.role.as_str()
+        )))
+    }
+}
+
+#[cfg(feature = "ssr")]
+fn set_session_cookie(cookie: &str) -> Result<(), ServerFnError> {
+    let response = expect_context::<ResponseOptions>();
+    let value = cookie
+        .parse()
+        .map_err(|err| ServerFnError::new(format!("Invalid session cookie: {err}")))?;
+    response.append_header(SET_COOKIE, value);
+    Ok(())
+}
 
 /// Return a set of stats
 #[server(name = FetchStats, prefix = "/api", endpoint = "/stats")]
@@ -84,29 +235,8 @@ pub async fn nodes_instances(
     filter: Option<NodeFilter>,
 ) -> Result<NodesInstancesInfo, ServerFnError> {
     let context = expect_context::<ServerGlobalState>();
-
-    let latest_bin_version = context
-        .app_ctx
-        .latest_bin_version
-        .read()
-        .await
-        .clone()
-        .map(|v| v.to_string());
-    let stats = context.app_ctx.stats.read().await.clone();
-
-    let nodes = context
-        .node_manager
-        .filtered_nodes_list(filter, context.app_ctx.nodes_metrics)
-        .await?;
-
-    let scheduled_batches = context.app_ctx.node_action_batches.read().await.1.clone();
-
-    Ok(NodesInstancesInfo {
-        latest_bin_version,
-        nodes,
-        stats,
-        scheduled_batches,
-    })
+    let info = nodes_instances_info(&context.app_ctx, &context.node_manager, filter).await?;
+    Ok(info)
 }
 
 /// Create and add a new node instance returning its info
@@ -116,6 +246,14 @@ pub async fn create_node_instance(node_opts: NodeOpts) -> Result<NodeInstanceInf
 
     // validate rewards address before proceeding
     parse_and_validate_addr(&node_opts.rewards_addr).map_err(ServerFnError::new)?;
+    require_permission(
+        rewards_addrs_permission(
+            &context.app_ctx,
+            std::slice::from_ref(&node_opts.rewards_addr),
+        )
+        .await,
+    )
+    .await?;
 
     let info = context.node_manager.create_node_instance(node_opts).await?;
     Ok(info)
@@ -165,6 +303,35 @@ pub async fn recycle_node_instance(node_id: NodeId) -> Result<(), ServerFnError>
     Ok(())
 }
 
+/// Update the configuration of a node instance with given id, returning its up to date info.
+/// The node keeps its data and peer-id, although it gets a new node id in Docker mode,
+/// in which case the response also tells the id it had before the update.
+#[server(name = UpdateNodeInstance, prefix = "/api", endpoint = "/nodes/update")]
+pub async fn update_node_instance(
+    node_id: NodeId,
+    changes: NodeConfigUpdate,
+) -> Result<NodeUpdated, ServerFnError> {
+    let context = expect_context::<ServerGlobalState>();
+    if changes.is_empty() {
+        return Err(ServerFnError::new("No configuration changes provided"));
+    }
+    if let Some(addr) = &changes.rewards_addr {
+        parse_and_validate_addr(addr).map_err(ServerFnError::new)?;
+    }
+    require_permission(changes.required_permission()).await?;
+
+    logging::log!("[API] Updating configuration of node with Id: {node_id} ...");
+    let info = context
+        .node_manager
+        .update_node_instance(node_id.clone(), changes)
+        .await?;
+    let previous_node_id = (info.node_id != node_id).then_some(node_id);
+    Ok(NodeUpdated {
+        info,
+        previous_node_id,
+    })
+}
+
 /// Start streaming logs from a node instance with given id
 #[server(output = Streaming, name = StartNodeLogsStream, prefix = "/api", endpoint = "/nodes/logs_stream")]
 pub async fn start_node_logs_stream(node_id: NodeId) -> Result<ByteStream, ServerFnError> {
@@ -195,11 +362,48 @@ pub async fn node_metrics(
     Ok(metrics)
 }
 
+/// Retrieve the history of the aggregated stats within given time range
+#[server(name = GetStatsHistory, prefix = "/api", endpoint = "/stats/history")]
+pub async fn stats_history(range: StatsHistoryRange) -> Result<Vec<StatsSnapshot>, ServerFnError> {
+    let context = expect_context::<ServerGlobalState>();
+    let since = chrono::Utc::now().timestamp_millis() - range.span_millis();
+    let history = context
+        .app_ctx
+        .db_client
+        .get_stats_history(since, range.bucket_millis())
+        .await;
+    Ok(history)
+}
+
+/// Retrieve the latest resources usage sampled from the host
+#[server(name = GetHostStats, prefix = "/api", endpoint = "/host/stats")]
+pub async fn host_stats() -> Result<Option<HostStats>, ServerFnError> {
+    let context = expect_context::<ServerGlobalState>();
+    let host_stats = context.app_ctx.stats.read().await.host.clone();
+    Ok(host_stats)
+}
+
+/// Retrieve the history of the host resources usage within given time range
+#[server(name = GetHostHistory, prefix = "/api", endpoint = "/host/history")]
+pub async fn host_history(range: StatsHistoryRange) -> Result<Vec<HostSnapshot>, ServerFnError> {
+    let context = expect_context::<ServerGlobalState>();
+    let settings = context.app_ctx.db_client.get_settings().await;
+    let history = host_metrics_history(&context.app_ctx.db_client, &settings, range).await;
+    Ok(history)
+}
+
 /// Retrieve the settings
 #[server(name = GetSettings, prefix = "/api", endpoint = "/settings/get")]
 pub async fn get_settings() -> Result<super::types::AppSettings, ServerFnError> {
     let context = expect_context::<ServerGlobalState>();
-    let settings = context.app_ctx.db_client.get_settings().await;
+    let mut settings = context.app_ctx.db_client.get_settings().await;
+    // credentials of external services are only revealed to those who can change them
+    if !current_user()
+        .await
+        .is_ok_and(|user| user.can(Permission::ManageSettings))
+    {
+        settings.redact_secrets();
+    }
     Ok(settings)
 }
 
@@ -219,6 +423,22 @@ pub async fn update_settings(settings: super::types::AppSettings) -> Result<(),
     Ok(())
 }
 
+/// Retrieve the health of the L2 network RPC endpoints, in priority order
+#[server(name = GetRpcEndpointsHealth, prefix = "/api", endpoint = "/settings/rpc_health")]
+pub async fn get_rpc_endpoints_health()
+-> Result<Vec<super::types::RpcEndpointHealth>, ServerFnError> {
+    let context = expect_context::<ServerGlobalState>();
+    let mut health = context.app_ctx.rpc_endpoints.health().await;
+    // the URLs usually embed the providers API keys
+    if !current_user()
+        .await
+        .is_ok_and(|user| user.can(Permission::ManageSettings))
+    {
+        health.iter_mut().for_each(|endpoint| endpoint.redact_url());
+    }
+    Ok(health)
+}
+
 /// Retrieve the MCP server info (e.g., endpoint) if active
 #[server(name = GetMcpInfo, prefix = "/api", endpoint = "/mcp/info")]
 pub async fn get_mcp_info() -> Result<Option<String>, ServerFnError> {
@@ -235,6 +455,14 @@ pub async fn nodes_actions_batches() -> Result<Vec<NodesActionsBatch>, ServerFnE
     Ok(batches)
 }
 
+/// Return the history of finished nodes actions batches, most recent first
+#[server(name = NodesActionsBatchesHistory, prefix = "/api", endpoint = "/batch/history")]
+pub async fn nodes_actions_batches_history() -> Result<Vec<NodesActionsBatch>, ServerFnError> {
+    let context = expect_context::<ServerGlobalState>();
+    let batches = context.app_ctx.db_client.get_batches_history().await;
+    Ok(batches)
+}
+
 /// Prepare a new nodes actions batch
 #[server(name = CreateNodesActionsBatch, prefix = "/api", endpoint = "/batch/create")]
 pub async fn nodes_actions_batch_create(
@@ -242,6 +470,12 @@ pub async fn nodes_actions_batch_create(
     interval_secs: u64,
 ) -> Result<u16, ServerFnError> {
     let context = expect_context::<ServerGlobalState>();
+    require_permission(batch_type.required_permission()).await?;
+    let rewards_addrs = batch_type.created_nodes_rewards_addrs();
+    if !rewards_addrs.is_empty() {
+        require_permission(rewards_addrs_permission(&context.app_ctx, &rewards_addrs).await)
+            .await?;
+    }
     let batch_id = prepare_node_action_batch(
         batch_type,
         interval_secs,
@@ -258,6 +492,9 @@ pub async fn nodes_actions_batch_on_match(
     batch_on_match: BatchOnMatch,
     interval_secs: u64,
 ) -> Result<u16, ServerFnError> {
+    if matches!(batch_on_match, BatchOnMatch::RemoveOnMatch(_)) {
+        require_permission(Permission::RemoveNodes).await?;
+    }
     let context = expect_context::<ServerGlobalState>();
     let nodes_list = context
         .node_manager
@@ -290,25 +527,338 @@ pub async fn nodes_actions_batch_on_match(
     Ok(batch_id)
 }
 
-/// Cancel all node instances creation batches
+/// Cancel a scheduled or running nodes actions batch, or dismiss it if it already finished
 #[server(name = CancelNodesActionsBatch, prefix = "/api", endpoint = "/batch/cancel")]
 pub async fn cancel_batch(batch_id: u16) -> Result<(), ServerFnError> {
     let context = expect_context::<ServerGlobalState>();
     logging::log!("[API] Cancelling node action batch {batch_id} ...");
+    cancel_node_action_batch(&context.app_ctx, batch_id).await?;
+    Ok(())
+}
 
-    let mut guard = context.app_ctx.node_action_batches.write().await;
-    if let Some(index) = guard.1.iter().position(|b| b.id == batch_id) {
-        if guard.1[index].status.is_failed() {
-            // failed batch — just remove it (dismiss)
-            guard.1.remove(index);
-        } else {
-            // still running/scheduled — signal the runner to cancel
-            guard.0.send(batch_id)?;
-        }
+/// Pause a running nodes actions batch
+#[server(name = PauseNodesActionsBatch, prefix = "/api", endpoint = "/batch/pause")]
+pub async fn pause_batch(batch_id: u16) -> Result<(), ServerFnError> {
+    let context = expect_context::<ServerGlobalState>();
+    logging::log!("[API] Pausing node action batch {batch_id} ...");
+    pause_node_action_batch(&context.app_ctx, batch_id).await?;
+    Ok(())
+}
+
+/// Resume a paused nodes actions batch
+#[server(name = ResumeNodesActionsBatch, prefix = "/api", endpoint = "/batch/resume")]
+pub async fn resume_batch(batch_id: u16) -> Result<(), ServerFnError> {
+    let context = expect_context::<ServerGlobalState>();
+    logging::log!("[API] Resuming node action batch {batch_id} ...");
+    resume_node_action_batch(&context.app_ctx, batch_id).await?;
+    Ok(())
+}
+
+/// Change the interval between the actions of a scheduled, running or paused nodes actions batch
+#[server(name = SetNodesActionsBatchInterval, prefix = "/api", endpoint = "/batch/interval")]
+pub async fn set_batch_interval(batch_id: u16, interval_secs: u64) -> Result<(), ServerFnError> {
+    let context = expect_context::<ServerGlobalState>();
+    logging::log!("[API] Setting interval of node action batch {batch_id} to {interval_secs}s ...");
+    set_node_action_batch_interval(&context.app_ctx, batch_id, interval_secs).await?;
+    Ok(())
+}
+
+/// Create a new nodes actions batch retrying only the failed actions of a finished batch
+#[server(name = RetryNodesActionsBatch, prefix = "/api", endpoint = "/batch/retry")]
+pub async fn retry_batch(batch_id: u16) -> Result<u16, ServerFnError> {
+    let context = expect_context::<ServerGlobalState>();
+    let batch = find_node_action_batch(&context.app_ctx, batch_id).await?;
+    if matches!(batch.batch_type, BatchType::Remove(_)) {
+        require_permission(Permission::RemoveNodes).await?;
+    }
+    logging::log!("[API] Retrying failed actions of node action batch {batch_id} ...");
+    let new_batch_id =
+        retry_node_action_batch(&context.app_ctx, &context.node_manager, batch_id).await?;
+    Ok(new_batch_id)
+}
+
+// ─── Alerts server functions ──────────────────────────────────────────────────
+
+/// Retrieve the alerts fired by the alert rules, firing ones first.
+#[server(name = ListAlerts, prefix = "/api", endpoint = "/alerts/list")]
+pub async fn alerts_list() -> Result<Vec<Alert>, ServerFnError> {
+    let context = expect_context::<ServerGlobalState>();
+    let alerts = context.app_ctx.alerts.read().await.alerts();
+    Ok(alerts)
+}
+
+/// Acknowledge an alert
+#[server(name = AcknowledgeAlert, prefix = "/api", endpoint = "/alerts/ack")]
+pub async fn acknowledge_alert(alert_id: u64) -> Result<(), ServerFnError> {
+    let context = expect_context::<ServerGlobalState>();
+    let now = chrono::Utc::now().timestamp();
+    if context
+        .app_ctx
+        .alerts
+        .write()
+        .await
+        .acknowledge(alert_id, now)
+    {
+        context
+            .app_ctx
+            .db_client
+            .acknowledge_alert(alert_id, now)
+            .await?;
+        Ok(())
+    } else {
+        Err(ServerFnError::new(format!("Alert {alert_id} not found")))
+    }
+}
+
+/// Retrieve the list of alert rules
+#[server(name = ListAlertRules, prefix = "/api", endpoint = "/alerts/rules/list")]
+pub async fn alert_rules() -> Result<Vec<AlertRule>, ServerFnError> {
+    let context = expect_context::<ServerGlobalState>();
+    Ok(context.app_ctx.db_client.get_alert_rules().await)
+}
+
+/// Create a new alert rule if its id is 0, or update the existing one otherwise
+#[server(name = SaveAlertRule, prefix = "/api", endpoint = "/alerts/rules/save")]
+pub async fn save_alert_rule(rule: AlertRule) -> Result<(), ServerFnError> {
+    let context = expect_context::<ServerGlobalState>();
+    if rule.name.trim().is_empty() {
+        return Err(ServerFnError::new("Alert rule name cannot be empty"));
+    }
+    if !rule.threshold.is_finite() || !rule.hysteresis.is_finite() || rule.hysteresis < 0.0 {
+        return Err(ServerFnError::new(
+            "Alert rule threshold and hysteresis must be valid numbers, and hysteresis cannot be negative",
+        ));
     }
+
+    logging::log!("[API] Saving alert rule: {rule:?} ...");
+    context.app_ctx.db_client.store_alert_rule(&rule).await?;
+    Ok(())
+}
+
+/// Silence the alerts of a rule for the given number of seconds, or unsilence them if it's zero
+#[server(name = SilenceAlertRule, prefix = "/api", endpoint = "/alerts/rules/silence")]
+pub async fn silence_alert_rule(rule_id: i64, secs: u64) -> Result<(), ServerFnError> {
+    let context = expect_context::<ServerGlobalState>();
+    let silenced_until = (secs > 0).then(|| chrono::Utc::now().timestamp() + secs as i64);
+    logging::log!("[API] Silencing alert rule {rule_id} for {secs} seconds ...");
+    context
+        .app_ctx
+        .db_client
+        .silence_alert_rule(rule_id, silenced_until)
+        .await?;
+    Ok(())
+}
+
+/// Delete an alert rule
+#[server(name = DeleteAlertRule, prefix = "/api", endpoint = "/alerts/rules/delete")]
+pub async fn delete_alert_rule(rule_id: i64) -> Result<(), ServerFnError> {
+    let context = expect_context::<ServerGlobalState>();
+    logging::log!("[API] Deleting alert rule {rule_id} ...");
+    context.app_ctx.db_client.delete_alert_rule(rule_id).await?;
+    Ok(())
+}
+
+// ─── Rewards addresses book server functions ──────────────────────────────────
+
+/// Retrieve the labels, notes and colours assigned to rewards addresses
+#[server(name = GetAddressBook, prefix = "/api", endpoint = "/addresses/book/list")]
+pub async fn address_book() -> Result<AddressBook, ServerFnError> {
+    let context = expect_context::<ServerGlobalState>();
+    Ok(context.app_ctx.db_client.get_address_book().await)
+}
+
+/// Add a rewards address to the address book, or update its entry if it's already there
+#[server(name = SaveAddressBookEntry, prefix = "/api", endpoint = "/addresses/book/save")]
+pub async fn save_address_book_entry(entry: AddressBookEntry) -> Result<(), ServerFnError> {
+    let context = expect_context::<ServerGlobalState>();
+    let mut entry = entry;
+    entry.validate().map_err(ServerFnError::new)?;
+    logging::log!(
+        "[API] Saving address book entry '{}' for {} ...",
+        entry.label,
+        entry.address
+    );
+    context
+        .app_ctx
+        .db_client
+        .store_address_book_entry(&entry)
+        .await?;
+    Ok(())
+}
+
+/// Remove a rewards address from the address book
+#[server(name = DeleteAddressBookEntry, prefix = "/api", endpoint = "/addresses/book/delete")]
+pub async fn delete_address_book_entry(address: String) -> Result<(), ServerFnError> {
+    let context = expect_context::<ServerGlobalState>();
+    let address = normalized_address(&address)
+        .ok_or_else(|| ServerFnError::new(format!("Invalid rewards address: '{address}'")))?;
+    logging::log!("[API] Deleting address book entry for {address} ...");
+    context
+        .app_ctx
+        .db_client
+        .delete_address_book_entry(&address)
+        .await?;
     Ok(())
 }
 
+/// Retrieve the nodes, records, balance and earnings of each rewards address
+#[server(name = RewardsAddressesSummaries, prefix = "/api", endpoint = "/addresses/summaries")]
+pub async fn rewards_addresses() -> Result<Vec<RewardsAddressSummary>, ServerFnError> {
+    let context = expect_context::<ServerGlobalState>();
+    let nodes = context
+        .node_manager
+        .filtered_nodes_list(None, context.app_ctx.nodes_metrics.clone())
+        .await?;
+    let stats = context.app_ctx.stats.read().await.clone();
+    let book = context.app_ctx.db_client.get_address_book().await;
+    Ok(rewards_addresses_summaries(&nodes, &stats, &book))
+}
+
+/// Export the reward payments received within a range of days (both inclusive), valued
+/// in the fiat currency set in the settings, optionally only those of the given address
+#[server(name = ExportEarnings, prefix = "/api", endpoint = "/earnings/export")]
+pub async fn export_earnings(
+    from: NaiveDate,
+    to: NaiveDate,
+    address: Option<String>,
+) -> Result<EarningsExport, ServerFnError> {
+    let context = expect_context::<ServerGlobalState>();
+    if from > to {
+        return Err(ServerFnError::new(format!(
+            "Invalid range of days, {from} is after {to}"
+        )));
+    }
+    let address = match address.filter(|a| !a.is_empty()) {
+        Some(addr) => Some(
+            addr.parse::<Address>()
+                .map_err(|_| ServerFnError::new(format!("Invalid rewards address: '{addr}'")))?,
+        ),
+        None => None,
+    };
+    logging::log!("[API] Exporting earnings from {from} to {to} ...");
+    let settings = context.app_ctx.db_client.get_settings().await;
+    let export = earnings_export(&context.app_ctx.db_client, &settings, address, from, to).await?;
+    Ok(export)
+}
+
+/// Retrieve the earnings over time, grouped into buckets of the given size, of all
+/// the rewards addresses together (first item in the list), and of each of them
+#[server(name = FetchEarningsHistory, prefix = "/api", endpoint = "/earnings/history")]
+pub async fn earnings_history(
+    bucket: EarningsBucket,
+) -> Result<Vec<EarningsHistory>, ServerFnError> {
+    let context = expect_context::<ServerGlobalState>();
+    let now = chrono::Utc::now();
+    let today = now.date_naive();
+    let since = bucket
+        .first_bucket_start(today)
+        .and_time(chrono::NaiveTime::MIN)
+        .and_utc()
+        .timestamp();
+    let payments = context
+        .app_ctx
+        .db_client
+        .get_earnings_in_range(None, since, now.timestamp())
+        .await?;
+    Ok(calc_earnings_history(today, bucket, &payments))
+}
+
+/// Retrieve the costs of running each node, and all of them in the host, over the
+/// last given hours compared with their earnings, as per the cost model in the settings
+#[server(name = FetchProfitability, prefix = "/api", endpoint = "/earnings/profitability")]
+pub async fn nodes_profitability(period_hours: u32) -> Result<Profitability, ServerFnError> {
+    let context = expect_context::<ServerGlobalState>();
+    if period_hours == 0 {
+        return Err(ServerFnError::new("Period cannot be 0 hours long"));
+    }
+    let nodes = context
+        .node_manager
+        .filtered_nodes_list(None, context.app_ctx.nodes_metrics.clone())
+        .await?;
+    let settings = context.app_ctx.db_client.get_settings().await;
+    let res = profitability(
+        &context.app_ctx.db_client,
+        &settings,
+        &nodes,
+        period_hours,
+        chrono::Utc::now(),
+    )
+    .await?;
+    Ok(res)
+}
+
+// ─── Notification channels server functions ───────────────────────────────────
+
+/// Retrieve the list of notification channels
+#[server(name = ListNotificationChannels, prefix = "/api", endpoint = "/notifications/channels/list")]
+pub async fn notification_channels() -> Result<Vec<NotificationChannel>, ServerFnError> {
+    let context = expect_context::<ServerGlobalState>();
+    Ok(context.app_ctx.db_client.get_notification_channels().await)
+}
+
+/// Create a new notification channel if its id is 0, or update the existing one otherwise
+#[server(name = SaveNotificationChannel, input = Json, prefix = "/api", endpoint = "/notifications/channels/save")]
+pub async fn save_notification_channel(channel: NotificationChannel) -> Result<(), ServerFnError> {
+    let context = expect_context::<ServerGlobalState>();
+    if channel.name.trim().is_empty() {
+        return Err(ServerFnError::new(
+            "Notification channel name cannot be empty",
+        ));
+    }
+    channel.target.validate().map_err(ServerFnError::new)?;
+
+    logging::log!(
+        "[API] Saving {} notification channel '{}' ...",
+        channel.target.kind_label(),
+        channel.name
+    );
+    context
+        .app_ctx
+        .db_client
+        .store_notification_channel(&channel)
+        .await?;
+    Ok(())
+}
+
+/// Delete a notification channel
+#[server(name = DeleteNotificationChannel, prefix = "/api", endpoint = "/notifications/channels/delete")]
+pub async fn delete_notification_channel(channel_id: i64) -> Result<(), ServerFnError> {
+    let context = expect_context::<ServerGlobalState>();
+    logging::log!("[API] Deleting notification channel {channel_id} ...");
+    context
+        .app_ctx
+        .db_client
+        .delete_notification_channel(channel_id)
+        .await?;
+    Ok(())
+}
+
+/// Send a test notification through a channel, which doesn't need to be stored yet
+#[server(name = TestNotificationChannel, input = Json, prefix = "/api", endpoint = "/notifications/channels/test")]
+pub async fn test_notification_channel(channel: NotificationChannel) -> Result<(), ServerFnError> {
+    channel.target.validate().map_err(ServerFnError::new)?;
+    logging::log!(
+        "[API] Sending test notification through {} channel '{}' ...",
+        channel.target.kind_label(),
+        channel.name
+    );
+    let event = NotificationEvent::new(
+        channel
+            .events
+            .first()
+            .copied()
+            .unwrap_or(NotificationEventKind::NodeStatus),
+        "Formicaio test notification",
+        &format!(
+            "This is a test notification sent through the '{}' channel.",
+            channel.name
+        ),
+    );
+    send_notification(&reqwest::Client::new(), &channel.target, &event)
+        .await
+        .map_err(|err| ServerFnError::new(format!("Failed to send test notification: {err}")))
+}
+
 // ─── Agent server functions ───────────────────────────────────────────────────
 
 /// Stream an AI agent chat turn. Returns a ByteStream of NDJSON `StreamChunk` objects.
@@ -319,6 +869,7 @@ pub async fn send_chat_message(
     user_message: String,
 ) -> Result<ByteStream, ServerFnError> {
     let context = expect_context::<ServerGlobalState>();
+    let user = current_user().await?;
     let (tx, rx) = tokio::sync::mpsc::channel::<Bytes>(64);
 
     tokio::spawn(process_chat_turn(
@@ -326,6 +877,7 @@ pub async fn send_chat_message(
         session_id,
         context.app_ctx.clone(),
         context.node_manager.clone(),
+        user.role,
         tx,
     ));
 
@@ -418,6 +970,108 @@ pub async fn test_llm_connection(
         .unwrap_or_else(|| "connected (no models listed)".to_string()))
 }
 
+// ─── Users server functions ───────────────────────────────────────────────────
+
+/// Retrieve all users, sorted by username
+#[server(name = ListUsers, prefix = "/api", endpoint = "/users/list")]
+pub async fn users_list() -> Result<Vec<UserInfo>, ServerFnError> {
+    let context = expect_context::<ServerGlobalState>();
+    Ok(context.app_ctx.db_client.get_users().await)
+}
+
+/// Create a new user with the given role
+#[server(name = CreateUser, prefix = "/api", endpoint = "/users/create")]
+pub async fn create_user(
+    username: String,
+    password: String,
+    role: UserRole,
+) -> Result<(), ServerFnError> {
+    let context = expect_context::<ServerGlobalState>();
+    validate_credentials(&username, &password).map_err(ServerFnError::new)?;
+    let password_hash = hash_password(&password)
+        .map_err(|err| ServerFnError::new(format!("Failed to hash password: {err}")))?;
+    if !context
+        .app_ctx
+        .db_client
+        .create_user(&username, &password_hash, role)
+        .await?
+    {
+        return Err(ServerFnError::new(format!(
+            "A user named '{username}' already exists"
+        )));
+    }
+    logging::log!(
+        "[AUTH] User '{username}' created with role '{}'",
+        role.as_str()
+    );
+    Ok(())
+}
+
+/// Change the role of a user, and its password if provided, which ends all its sessions
+#[server(name = UpdateUser, prefix = "/api", endpoint = "/users/update")]
+pub async fn update_user(
+    user_id: i64,
+    role: UserRole,
+    password: Option<String>,
+) -> Result<(), ServerFnError> {
+    let context = expect_context::<ServerGlobalState>();
+    let users = context.app_ctx.db_client.get_users().await;
+    let user = users
+        .iter()
+        .find(|u| u.id == user_id)
+        .ok_or_else(|| ServerFnError::new(format!("User with id {user_id} not found")))?;
+
+    if let Some(password) = password.as_deref().filter(|p| !p.is_empty()) {
+        validate_credentials(&user.username, password).map_err(ServerFnError::new)?;
+    }
+    // the DB refuses to demote the last admin user within the same statement
+    if user.role != role {
+        context
+            .app_ctx
+            .db_client
+            .update_user_role(user_id, role)
+            .await?;
+        logging::log!(
+            "[AUTH] Role of user '{}' changed to '{}'",
+            user.username,
+            role.as_str()
+        );
+    }
+    if let Some(password) = password.filter(|p| !p.is_empty()) {
+        let password_hash = hash_password(&password)
+            .map_err(|err| ServerFnError::new(format!("Failed to hash password: {err}")))?;
+        context
+            .app_ctx
+            .db_client
+            .update_user_password(user_id, &password_hash)
+            .await?;
+        logging::log!("[AUTH] Password of user '{}' changed", user.username);
+    }
+    Ok(())
+}
+
+/// Remove a user, ending all its sessions. Users cannot remove themselves,
+/// nor the last admin user.
+#[server(name = DeleteUser, prefix = "/api", endpoint = "/users/delete")]
+pub async fn delete_user(user_id: i64) -> Result<(), ServerFnError> {
+    let context = expect_context::<ServerGlobalState>();
+    let current_user = current_user().await?;
+    let user = context
+        .app_ctx
+        .db_client
+        .get_users()
+        .await
+        .into_iter()
+        .find(|u| u.id == user_id)
+        .ok_or_else(|| ServerFnError::new(format!("User with id {user_id} not found")))?;
+    if user.username == current_user.username {
+        return Err(ServerFnError::new("You cannot remove your own user"));
+    }
+    context.app_ctx.db_client.delete_user(user_id).await?;
+    logging::log!("[AUTH] User '{}' removed", user.username);
+    Ok(())
+}
+
 // Helper to parse and validate the rewards address
 pub fn parse_and_validate_addr(input_str: &str) -> Result<Address, String> {
     let value = input_str
@@ -0,0 +1,267 @@
+use super::{EarningsStats, NodeId, NodeList, Stats, shortened_address};
+
+use alloy_primitives::{Address, U256};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Colour assigned to addresses the user didn't choose one for.
+pub const DEFAULT_ADDRESS_COLOR: &str = "#6366f1";
+
+/// Label, note and colour the user assigned to a rewards address.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct AddressBookEntry {
+    /// Rewards address, in its checksummed form with '0x' prefix
+    pub address: String,
+    pub label: String,
+    pub note: String,
+    /// Colour in '#rrggbb' format used to tell the address apart in the UI
+    pub color: String,
+}
+
+impl Default for AddressBookEntry {
+    fn default() -> Self {
+        Self {
+            address: String::new(),
+            label: String::new(),
+            note: String::new(),
+            color: DEFAULT_ADDRESS_COLOR.to_string(),
+        }
+    }
+}
+
+impl AddressBookEntry {
+    /// Check the entry is complete and well-formed, normalising its address.
+    pub fn validate(&mut self) -> Result<(), String> {
+        self.address = normalized_address(&self.address)
+            .ok_or_else(|| format!("Invalid rewards address: '{}'", self.address))?;
+        if self.label.trim().is_empty() {
+            return Err("Address label cannot be empty".to_string());
+        }
+        let is_hex_color = self.color.len() == 7
+            && self.color.starts_with('#')
+            && self.color[1..].chars().all(|c| c.is_ascii_hexdigit());
+        if !is_hex_color {
+            return Err(format!("Invalid colour: '{}'", self.color));
+        }
+        Ok(())
+    }
+}
+
+/// Entries of the address book, used to display labels instead of raw addresses.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct AddressBook(pub Vec<AddressBookEntry>);
+
+impl AddressBook {
+    /// Find the entry of an address, which may or may not have the '0x' prefix,
+    /// nor be in its checksummed form, as rewards addresses of nodes.
+    pub fn get(&self, addr: &str) -> Option<&AddressBookEntry> {
+        let addr = addr.parse::<Address>().ok()?;
+        self.0
+            .iter()
+            .find(|e| e.address.parse::<Address>().ok() == Some(addr))
+    }
+
+    /// Label of the address if it's in the book, or its shortened form otherwise.
+    pub fn display_name(&self, addr: &str) -> String {
+        self.get(addr)
+            .map(|e| e.label.clone())
+            .unwrap_or_else(|| shortened_address(&addr.to_string()))
+    }
+}
+
+/// Checksummed form with '0x' prefix of an address, if it's a valid one.
+pub fn normalized_address(addr: &str) -> Option<String> {
+    addr.trim().parse::<Address>().ok().map(|a| a.to_string())
+}
+
+/// Summary of the nodes paying rewards to an address, with its balance and earnings.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct RewardsAddressSummary {
+    /// Rewards address, in its checksummed form with '0x' prefix
+    pub address: String,
+    /// Entry in the address book, if the user added the address to it
+    pub entry: Option<AddressBookEntry>,
+    /// Nodes which pay rewards to this address
+    pub nodes: Vec<NodeId>,
+    /// Number of those nodes currently active
+    pub active_nodes: usize,
+    /// Total number of records stored by those nodes
+    pub records: usize,
+    /// Current balance, if it was already retrieved
+    pub balance: Option<U256>,
+    /// Earnings analytics, if the history was already retrieved
+    pub earnings: Option<EarningsStats>,
+}
+
+impl RewardsAddressSummary {
+    /// Label of the address if it's in the book, or its shortened form otherwise.
+    pub fn display_name(&self) -> String {
+        self.entry
+            .as_ref()
+            .map(|e| e.label.clone())
+            .unwrap_or_else(|| shortened_address(&self.address))
+    }
+}
+
+/// Build the summary of every address known, either because nodes pay rewards to it,
+/// its balance was retrieved, or it's in the address book. Labelled addresses are
+/// listed first, in the same order as in the book.
+pub fn rewards_addresses_summaries(
+    nodes: &NodeList,
+    stats: &Stats,
+    book: &AddressBook,
+) -> Vec<RewardsAddressSummary> {
+    let mut summaries = HashMap::<String, RewardsAddressSummary>::new();
+    fn summary_for<'a>(
+        summaries: &'a mut HashMap<String, RewardsAddressSummary>,
+        book: &AddressBook,
+        addr: &str,
+    ) -> Option<&'a mut RewardsAddressSummary> {
+        let address = normalized_address(addr)?;
+        Some(
+            summaries
+                .entry(address.clone())
+                .or_insert_with(|| RewardsAddressSummary {
+                    entry: book.get(&address).cloned(),
+                    address,
+                    ..RewardsAddressSummary::default()
+                }),
+        )
+    }
+
+    for entry in book.0.iter() {
+        summary_for(&mut summaries, book, &entry.address);
+    }
+    for (addr, balance) in stats.balances.iter() {
+        if let Some(summary) = summary_for(&mut summaries, book, addr) {
+            summary.balance = Some(*balance);
+        }
+    }
+    for (addr, earnings) in stats.earnings.iter() {
+        if let Some(summary) = summary_for(&mut summaries, book, addr) {
+            summary.earnings = Some(earnings.clone());
+        }
+    }
+    for info in nodes.values() {
+        if let Some(summary) = info
+            .rewards_addr
+            .as_deref()
+            .and_then(|addr| summary_for(&mut summaries, book, addr))
+        {
+            summary.nodes.push(info.node_id.clone());
+            if info.status.is_active() {
+                summary.active_nodes += 1;
+            }
+            summary.records += info.records.unwrap_or_default();
+        }
+    }
+
+    let mut list = summaries.into_values().collect::<Vec<_>>();
+    list.iter_mut().for_each(|s| s.nodes.sort());
+    list.sort_by_key(|s| {
+        (
+            book.0
+                .iter()
+                .position(|e| Some(e) == s.entry.as_ref())
+                .unwrap_or(usize::MAX),
+            s.address.clone(),
+        )
+    });
+    list
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDR: &str = "0x52e6FCF5c2b1C1ea3B7fEA4a5BfF1e3C3f2E5a1b";
+
+    #[test]
+    fn test_address_book_lookup() {
+        let book = AddressBook(vec![AddressBookEntry {
+            address: normalized_address(ADDR).unwrap(),
+            label: "Cold wallet".to_string(),
+            ..AddressBookEntry::default()
+        }]);
+
+        // nodes store their rewards address without prefix and not checksummed
+        let node_addr = ADDR.strip_prefix("0x").unwrap().to_lowercase();
+        assert_eq!(book.display_name(&node_addr), "Cold wallet");
+        assert_eq!(
+            book.display_name(&ADDR.to_uppercase().replace("0X", "0x")),
+            "Cold wallet"
+        );
+
+        let other = "0x1111111111111111111111111111111111111111";
+        assert!(book.get(other).is_none());
+        assert_eq!(book.display_name(other), "0x11111111...11111111");
+    }
+
+    #[test]
+    fn test_rewards_addresses_summaries() {
+        use crate::types::{NodeInstanceInfo, NodeStatus};
+
+        let other = normalized_address("0x1111111111111111111111111111111111111111").unwrap();
+        let book = AddressBook(vec![AddressBookEntry {
+            address: normalized_address(ADDR).unwrap(),
+            label: "Cold wallet".to_string(),
+            ..AddressBookEntry::default()
+        }]);
+        let stats = Stats {
+            balances: vec![(other.clone(), U256::from(7))],
+            ..Stats::default()
+        };
+        let mut nodes = NodeList::new();
+        for (i, (status, records)) in [
+            (NodeStatus::Active, 10),
+            (NodeStatus::Inactive(Default::default()), 5),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let node_id = NodeId::new(format!("{i}0a1b2c3d4e5")).unwrap();
+            nodes.insert(
+                node_id.clone(),
+                NodeInstanceInfo {
+                    node_id,
+                    status,
+                    records: Some(records),
+                    rewards_addr: Some(ADDR.strip_prefix("0x").unwrap().to_lowercase()),
+                    ..NodeInstanceInfo::default()
+                },
+            );
+        }
+
+        let summaries = rewards_addresses_summaries(&nodes, &stats, &book);
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[0].display_name(), "Cold wallet");
+        assert_eq!(summaries[0].nodes.len(), 2);
+        assert_eq!(summaries[0].active_nodes, 1);
+        assert_eq!(summaries[0].records, 15);
+        assert_eq!(summaries[0].balance, None);
+        assert_eq!(summaries[1].address, other);
+        assert_eq!(summaries[1].entry, None);
+        assert!(summaries[1].nodes.is_empty());
+        assert_eq!(summaries[1].balance, Some(U256::from(7)));
+    }
+
+    #[test]
+    fn test_address_book_entry_validation() {
+        let mut entry = AddressBookEntry {
+            address: ADDR.strip_prefix("0x").unwrap().to_lowercase(),
+            label: "Hot wallet".to_string(),
+            ..AddressBookEntry::default()
+        };
+        assert_eq!(entry.validate(), Ok(()));
+        assert_eq!(entry.address, normalized_address(ADDR).unwrap());
+
+        entry.color = "red".to_string();
+        assert!(entry.validate().is_err());
+        entry.color = "#12ab3f".to_string();
+        entry.label = " ".to_string();
+        assert!(entry.validate().is_err());
+        entry.label = "Hot wallet".to_string();
+        entry.address = "0x1234".to_string();
+        assert!(entry.validate().is_err());
+    }
+}
@@ -0,0 +1,270 @@
+use super::NodeId;
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Severity of the alerts fired by a rule.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+pub enum AlertSeverity {
+    Info,
+    #[default]
+    Warning,
+    Critical,
+}
+
+impl AlertSeverity {
+    pub const ALL: [Self; 3] = [Self::Info, Self::Warning, Self::Critical];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Critical => "critical",
+        }
+    }
+}
+
+impl std::str::FromStr for AlertSeverity {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|v| v.as_str() == s)
+            .ok_or_else(|| format!("Unknown alert severity: {s}"))
+    }
+}
+
+/// Value an alert rule is evaluated against, either for each node or for the aggregated stats.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum AlertMetric {
+    /// Number of peers connected to an active node.
+    #[default]
+    NodeConnectedPeers,
+    /// Number of times an active node has been shunned by peers.
+    NodeShunnedCount,
+    /// Number of records stored by an active node.
+    NodeStoredRecords,
+    /// CPU usage (%) of an active node.
+    NodeCpuUsage,
+    /// Memory (MB) used by an active node.
+    NodeMemUsedMb,
+    /// 1 if the node exited or failed to start, 0 otherwise.
+    NodeExited,
+    /// Number of active nodes.
+    ActiveNodes,
+    /// Number of inactive nodes.
+    InactiveNodes,
+    /// Total number of peers connected across all nodes.
+    TotalConnectedPeers,
+    /// Estimated network size.
+    EstimatedNetSize,
+    /// Available disk space, as a percentage of the total disk space.
+    FreeDiskPercent,
+    /// Hours elapsed since the last reward payment received by any of the rewards addresses.
+    HoursSinceLastEarning,
+    /// Traffic of the host during the current month, as a percentage of the monthly cap.
+    MonthlyTrafficCapPercent,
+}
+
+impl AlertMetric {
+    pub const ALL: [Self; 13] = [
+        Self::NodeConnectedPeers,
+        Self::NodeShunnedCount,
+        Self::NodeStoredRecords,
+        Self::NodeCpuUsage,
+        Self::NodeMemUsedMb,
+        Self::NodeExited,
+        Self::ActiveNodes,
+        Self::InactiveNodes,
+        Self::TotalConnectedPeers,
+        Self::EstimatedNetSize,
+        Self::FreeDiskPercent,
+        Self::HoursSinceLastEarning,
+        Self::MonthlyTrafficCapPercent,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::NodeConnectedPeers => "node_connected_peers",
+            Self::NodeShunnedCount => "node_shunned_count",
+            Self::NodeStoredRecords => "node_stored_records",
+            Self::NodeCpuUsage => "node_cpu_usage",
+            Self::NodeMemUsedMb => "node_mem_used_mb",
+            Self::NodeExited => "node_exited",
+            Self::ActiveNodes => "active_nodes",
+            Self::InactiveNodes => "inactive_nodes",
+            Self::TotalConnectedPeers => "total_connected_peers",
+            Self::EstimatedNetSize => "estimated_net_size",
+            Self::FreeDiskPercent => "free_disk_percent",
+            Self::HoursSinceLastEarning => "hours_since_last_earning",
+            Self::MonthlyTrafficCapPercent => "monthly_traffic_cap_percent",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::NodeConnectedPeers => "Node connected peers",
+            Self::NodeShunnedCount => "Node shunned count",
+            Self::NodeStoredRecords => "Node stored records",
+            Self::NodeCpuUsage => "Node CPU usage (%)",
+            Self::NodeMemUsedMb => "Node memory used (MB)",
+            Self::NodeExited => "Node exited",
+            Self::ActiveNodes => "Active nodes",
+            Self::InactiveNodes => "Inactive nodes",
+            Self::TotalConnectedPeers => "Total connected peers",
+            Self::EstimatedNetSize => "Estimated network size",
+            Self::FreeDiskPercent => "Free disk space (%)",
+            Self::HoursSinceLastEarning => "Hours since last earning",
+            Self::MonthlyTrafficCapPercent => "Monthly traffic (% of cap)",
+        }
+    }
+
+    /// Whether the rule is evaluated on each node rather than on the aggregated stats.
+    pub fn is_node_metric(&self) -> bool {
+        matches!(
+            self,
+            Self::NodeConnectedPeers
+                | Self::NodeShunnedCount
+                | Self::NodeStoredRecords
+                | Self::NodeCpuUsage
+                | Self::NodeMemUsedMb
+                | Self::NodeExited
+        )
+    }
+}
+
+impl std::str::FromStr for AlertMetric {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|v| v.as_str() == s)
+            .ok_or_else(|| format!("Unknown alert metric: {s}"))
+    }
+}
+
+/// How the value is compared with the rule's threshold.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum AlertOperator {
+    #[default]
+    Below,
+    Above,
+}
+
+impl AlertOperator {
+    pub const ALL: [Self; 2] = [Self::Below, Self::Above];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Below => "<",
+            Self::Above => ">",
+        }
+    }
+
+    /// Whether the value breaches the threshold.
+    pub fn is_breached(&self, value: f64, threshold: f64) -> bool {
+        match self {
+            Self::Below => value < threshold,
+            Self::Above => value > threshold,
+        }
+    }
+
+    /// Whether the value is back beyond the threshold by at least the hysteresis margin.
+    pub fn is_recovered(&self, value: f64, threshold: f64, hysteresis: f64) -> bool {
+        match self {
+            Self::Below => value >= threshold + hysteresis,
+            Self::Above => value <= threshold - hysteresis,
+        }
+    }
+}
+
+impl std::str::FromStr for AlertOperator {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|v| v.as_str() == s)
+            .ok_or_else(|| format!("Unknown alert operator: {s}"))
+    }
+}
+
+/// User-defined rule which fires an alert when a value breaches a threshold for some time.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct AlertRule {
+    /// Unique identifier of the rule, 0 for a rule not stored yet
+    pub id: i64,
+    pub name: String,
+    pub metric: AlertMetric,
+    pub operator: AlertOperator,
+    pub threshold: f64,
+    /// Margin the value needs to recover beyond the threshold before a firing alert is resolved
+    pub hysteresis: f64,
+    /// Number of seconds the threshold needs to be breached before the alert fires
+    pub for_secs: u64,
+    pub severity: AlertSeverity,
+    pub enabled: bool,
+    /// UNIX timestamp (seconds) until which the alerts of this rule are silenced
+    pub silenced_until: Option<i64>,
+}
+
+impl AlertRule {
+    pub fn is_silenced(&self, now: i64) -> bool {
+        self.silenced_until.is_some_and(|until| until > now)
+    }
+}
+
+impl fmt::Display for AlertRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.metric.label(),
+            self.operator.as_str(),
+            self.threshold
+        )?;
+        if self.for_secs > 0 {
+            write!(f, " for {}s", self.for_secs)?;
+        }
+        Ok(())
+    }
+}
+
+/// State of an alert fired by a rule.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum AlertState {
+    Firing,
+    Resolved,
+}
+
+/// Alert fired by a rule, for a node or for the aggregated stats.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Alert {
+    pub id: u64,
+    pub rule_id: i64,
+    pub rule_name: String,
+    pub severity: AlertSeverity,
+    /// Node which breached the rule, None if it was breached by the aggregated stats
+    pub node_id: Option<NodeId>,
+    /// Last value evaluated against the rule
+    pub value: f64,
+    pub message: String,
+    pub state: AlertState,
+    /// UNIX timestamp (seconds) when the alert fired
+    pub fired_at: i64,
+    /// UNIX timestamp (seconds) when the alert was resolved
+    pub resolved_at: Option<i64>,
+    /// UNIX timestamp (seconds) when the alert was acknowledged
+    pub acked_at: Option<i64>,
+    /// Whether the rule was silenced when the alert was last evaluated
+    pub silenced: bool,
+}
+
+impl Alert {
+    pub fn is_firing(&self) -> bool {
+        self.state == AlertState::Firing
+    }
+
+    pub fn is_acknowledged(&self) -> bool {
+        self.acked_at.is_some()
+    }
+}
@@ -0,0 +1,175 @@
+use serde::{Deserialize, Serialize};
+
+/// Name of the cookie the session token is kept in by the browser.
+pub const SESSION_COOKIE_NAME: &str = "formicaio_session";
+/// Minimum number of characters of a password.
+pub const MIN_PASSWORD_LENGTH: usize = 8;
+// Maximum number of characters of a username.
+const MAX_USERNAME_LENGTH: usize = 32;
+
+/// Authentication status of the client a request is made from.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum AuthStatus {
+    /// No user has been set up yet, the first one needs to be created.
+    SetupRequired,
+    /// The client needs to log in.
+    LoginRequired,
+    /// The client is logged in as the given user.
+    Authenticated(CurrentUser),
+}
+
+/// Role of a user, which determines the actions it's allowed to perform.
+/// Each role is allowed everything the previous ones are.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize,
+)]
+pub enum UserRole {
+    /// Can only see the nodes, stats, settings and history.
+    #[default]
+    Viewer,
+    /// Can also create, start, stop, upgrade, recycle and edit nodes, and run batches of actions.
+    Operator,
+    /// Can also remove nodes, change the settings and manage the users.
+    Admin,
+}
+
+impl UserRole {
+    pub const ALL: [Self; 3] = [Self::Viewer, Self::Operator, Self::Admin];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Viewer => "viewer",
+            Self::Operator => "operator",
+            Self::Admin => "admin",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Viewer => "Viewer",
+            Self::Operator => "Operator",
+            Self::Admin => "Admin",
+        }
+    }
+
+    /// Whether users with this role are allowed the given permission.
+    pub fn can(&self, permission: Permission) -> bool {
+        *self >= permission.required_role()
+    }
+}
+
+impl std::str::FromStr for UserRole {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|v| v.as_str() == s)
+            .ok_or_else(|| format!("Unknown user role: {s}"))
+    }
+}
+
+/// Permission required to call a server function or MCP tool.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Permission {
+    /// See the nodes, stats, settings, alerts, earnings and history.
+    View,
+    /// Create, start, stop, upgrade, recycle and edit nodes, run batches of
+    /// actions, acknowledge alerts and chat with the AI agent.
+    OperateNodes,
+    /// Remove nodes, either one by one or in batches.
+    RemoveNodes,
+    /// Change the settings, alert rules, notification channels and address book.
+    ManageSettings,
+    /// Create, edit and remove users.
+    ManageUsers,
+}
+
+impl Permission {
+    /// Least privileged role which is allowed this permission.
+    pub fn required_role(&self) -> UserRole {
+        match self {
+            Self::View => UserRole::Viewer,
+            Self::OperateNodes => UserRole::Operator,
+            Self::RemoveNodes | Self::ManageSettings | Self::ManageUsers => UserRole::Admin,
+        }
+    }
+}
+
+/// User a client is logged in as.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CurrentUser {
+    pub username: String,
+    pub role: UserRole,
+}
+
+impl CurrentUser {
+    /// Whether the user is allowed the given permission.
+    pub fn can(&self, permission: Permission) -> bool {
+        self.role.can(permission)
+    }
+}
+
+/// User of the application, as listed for its management.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct UserInfo {
+    pub id: i64,
+    pub username: String,
+    pub role: UserRole,
+    /// Timestamp (seconds) the user was created at.
+    pub created: i64,
+}
+
+/// Check the username and password chosen for a new user are acceptable.
+pub fn validate_credentials(username: &str, password: &str) -> Result<(), String> {
+    if username.is_empty() || username.len() > MAX_USERNAME_LENGTH {
+        return Err(format!(
+            "Username must be between 1 and {MAX_USERNAME_LENGTH} characters long"
+        ));
+    }
+    if !username
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+    {
+        return Err(
+            "Username can only contain letters, digits, and the '_', '-' and '.' characters"
+                .to_string(),
+        );
+    }
+    if password.chars().count() < MIN_PASSWORD_LENGTH {
+        return Err(format!(
+            "Password must be at least {MIN_PASSWORD_LENGTH} characters long"
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_credentials() {
+        assert!(validate_credentials("admin", "s3cret-pass").is_ok());
+        assert!(validate_credentials("node.op_1", "12345678").is_ok());
+        assert!(validate_credentials("", "s3cret-pass").is_err());
+        assert!(validate_credentials("admin user", "s3cret-pass").is_err());
+        assert!(validate_credentials(&"a".repeat(33), "s3cret-pass").is_err());
+        assert!(validate_credentials("admin", "short").is_err());
+    }
+
+    #[test]
+    fn test_role_permissions() {
+        assert!(UserRole::Viewer.can(Permission::View));
+        assert!(!UserRole::Viewer.can(Permission::OperateNodes));
+        assert!(UserRole::Operator.can(Permission::OperateNodes));
+        assert!(!UserRole::Operator.can(Permission::RemoveNodes));
+        assert!(!UserRole::Operator.can(Permission::ManageSettings));
+        assert!(UserRole::Admin.can(Permission::RemoveNodes));
+        assert!(UserRole::Admin.can(Permission::ManageUsers));
+
+        for role in UserRole::ALL {
+            assert_eq!(role.as_str().parse::<UserRole>(), Ok(role));
+        }
+        assert!("root".parse::<UserRole>().is_err());
+    }
+}
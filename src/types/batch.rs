@@ -1,10 +1,10 @@
-use super::{LogLevel, NodeFilter, NodeId};
+use super::{LogLevel, NodeFilter, NodeId, NodeInstanceInfo, Permission};
 
 use serde::{Deserialize, Serialize};
 use std::{fmt, path::PathBuf};
 
 /// Represents the current status of a batch operation on nodes.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum BatchStatus {
     /// The batch is scheduled and waiting to be processed.
     Scheduled,
@@ -13,8 +13,14 @@ pub enum BatchStatus {
     /// The batch is currently being processed, but some actions have failed.
     /// Contains the count of failed actions and the last error encountered.
     InProgressWithFailures(u16, String),
+    /// The batch was paused by the user, no more actions are performed till it's resumed.
+    Paused,
     /// The batch has completed with failures. Contains the last error encountered.
     Failed(String),
+    /// The batch has completed without failures.
+    Completed,
+    /// The batch was cancelled by the user before completing all its actions.
+    Cancelled,
 }
 
 impl fmt::Display for BatchStatus {
@@ -25,7 +31,10 @@ impl fmt::Display for BatchStatus {
             BatchStatus::InProgressWithFailures(count, msg) => {
                 write!(f, "In progress with {count} failures, last error: {msg}")
             }
+            BatchStatus::Paused => write!(f, "Paused"),
             BatchStatus::Failed(msg) => write!(f, "Failed, last error: {msg}"),
+            BatchStatus::Completed => write!(f, "Completed"),
+            BatchStatus::Cancelled => write!(f, "Cancelled"),
         }
     }
 }
@@ -34,14 +43,17 @@ impl BatchStatus {
     pub fn is_failed(&self) -> bool {
         matches!(self, Self::Failed(_) | Self::InProgressWithFailures(_, _))
     }
+    pub fn is_running(&self) -> bool {
+        matches!(self, Self::InProgress | Self::InProgressWithFailures(_, _))
+    }
     pub fn is_finished(&self) -> bool {
-        matches!(self, Self::Failed(_))
+        matches!(self, Self::Failed(_) | Self::Completed | Self::Cancelled)
     }
 }
 
 /// Represents a batch of actions to be performed on node instances, such as creation, start, stop, etc.
 /// Used to track the progress and status of bulk node operations.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct NodesActionsBatch {
     /// Unique identifier for the batch.
     pub id: u16,
@@ -53,6 +65,14 @@ pub struct NodesActionsBatch {
     pub interval_secs: u64,
     /// Number of actions completed successfully in the batch.
     pub complete: u16,
+    /// Outcome of each action already performed, in the order they were performed.
+    pub items: Vec<BatchItemOutcome>,
+    /// UNIX timestamp (seconds) when the batch was created.
+    pub created_at: i64,
+    /// UNIX timestamp (seconds) when the first action of the batch was started.
+    pub started_at: Option<i64>,
+    /// UNIX timestamp (seconds) when the batch completed, failed or was cancelled.
+    pub finished_at: Option<i64>,
 }
 
 impl NodesActionsBatch {
@@ -64,12 +84,131 @@ impl NodesActionsBatch {
             batch_type,
             interval_secs,
             complete: 0,
+            items: vec![],
+            created_at: chrono::Utc::now().timestamp(),
+            started_at: None,
+            finished_at: None,
+        }
+    }
+
+    /// Total number of actions to be performed by the batch.
+    pub fn total_actions(&self) -> usize {
+        match &self.batch_type {
+            BatchType::Create { count, .. } => *count as usize,
+            other => other.ids().len(),
         }
     }
+
+    /// Number of actions which failed so far.
+    pub fn failures(&self) -> usize {
+        self.items.iter().filter(|i| i.error.is_some()).count()
+    }
+
+    /// Status of the batch while its actions are being performed, based on their outcomes.
+    pub fn running_status(&self) -> BatchStatus {
+        match self.items.iter().rev().find_map(|i| i.error.clone()) {
+            Some(err) => BatchStatus::InProgressWithFailures(self.failures() as u16, err),
+            None => BatchStatus::InProgress,
+        }
+    }
+
+    /// IDs of the nodes the batch hasn't performed its action on yet.
+    pub fn pending_node_ids(&self) -> Vec<NodeId> {
+        self.batch_type
+            .ids()
+            .into_iter()
+            .skip(self.items.len())
+            .collect()
+    }
+
+    /// Type of batch which retries only the actions which failed, if there was any.
+    /// It fails if the nodes to create cannot be assigned ports following this batch's ones.
+    pub fn retry_batch_type(&self) -> Result<Option<BatchType>, String> {
+        let failures = self.failures();
+        if failures == 0 {
+            return Ok(None);
+        }
+        let failed_ids = || {
+            self.items
+                .iter()
+                .filter(|i| i.error.is_some())
+                .filter_map(|i| i.node_id.clone())
+                .collect()
+        };
+
+        let batch_type = match &self.batch_type {
+            BatchType::Create {
+                node_opts,
+                count,
+                rewards_addrs,
+            } => {
+                // nodes are created with the ports following the ones assigned by this batch
+                let mut node_opts = node_opts.clone();
+                let (Some(port), Some(metrics_port)) = (
+                    node_opts.port.checked_add(*count),
+                    node_opts.metrics_port.checked_add(*count),
+                ) else {
+                    return Err(format!(
+                        "the ports following the ones assigned by the batch exceed the maximum port number {}",
+                        u16::MAX
+                    ));
+                };
+                node_opts.port = port;
+                node_opts.metrics_port = metrics_port;
+
+                // and paying to the same addresses the failed ones were assigned
+                let assigned = distribute_rewards_addrs(rewards_addrs, *count);
+                let mut retry_addrs = Vec::<RewardsAddrWeight>::new();
+                let failed_addrs = self
+                    .items
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, item)| item.error.is_some())
+                    .filter_map(|(index, _)| assigned.get(index));
+                for address in failed_addrs {
+                    match retry_addrs.iter_mut().find(|a| &a.address == address) {
+                        Some(a) => a.weight += 1,
+                        None => retry_addrs.push(RewardsAddrWeight {
+                            address: address.clone(),
+                            weight: 1,
+                        }),
+                    }
+                }
+
+                BatchType::Create {
+                    node_opts,
+                    count: failures as u16,
+                    rewards_addrs: retry_addrs,
+                }
+            }
+            BatchType::Start(_) => BatchType::Start(failed_ids()),
+            BatchType::Stop(_) => BatchType::Stop(failed_ids()),
+            BatchType::Upgrade(_) => BatchType::Upgrade(failed_ids()),
+            BatchType::Recycle(_) => BatchType::Recycle(failed_ids()),
+            BatchType::Remove(_) => BatchType::Remove(failed_ids()),
+            BatchType::Update { changes, .. } => BatchType::Update {
+                node_ids: failed_ids(),
+                changes: changes.clone(),
+            },
+        };
+        Ok(Some(batch_type))
+    }
+}
+
+/// Outcome of the action performed on a single node as part of a batch.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BatchItemOutcome {
+    /// Node the action was performed on. For creation batches, the node
+    /// created, thus not available if the creation failed.
+    pub node_id: Option<NodeId>,
+    /// Error returned by the action, if it failed.
+    pub error: Option<String>,
+    /// UNIX timestamp (seconds) when the action finished.
+    pub timestamp: i64,
 }
 
 /// Describes the type of batch operation to perform on nodes, along with any required data.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum BatchType {
     /// Create a batch of new node instances with the given options and count.
     Create {
@@ -77,6 +216,10 @@ pub enum BatchType {
         node_opts: NodeOpts,
         /// Number of nodes to create.
         count: u16,
+        /// Rewards addresses the nodes are distributed across, in proportion to their weights,
+        /// instead of all of them paying to the address set in the options.
+        #[serde(default)]
+        rewards_addrs: Vec<RewardsAddrWeight>,
     },
     /// Start the specified node instances.
     Start(Vec<NodeId>),
@@ -88,6 +231,13 @@ pub enum BatchType {
     Recycle(Vec<NodeId>),
     /// Remove (delete) the specified node instances.
     Remove(Vec<NodeId>),
+    /// Apply the same configuration changes to the specified node instances.
+    Update {
+        /// Nodes to apply the changes to.
+        node_ids: Vec<NodeId>,
+        /// Configuration changes to apply to each of the nodes.
+        changes: NodeConfigUpdate,
+    },
 }
 
 impl BatchType {
@@ -95,6 +245,35 @@ impl BatchType {
         !matches!(self, Self::Create { .. })
     }
 
+    /// Permission required to run the batch. Creating nodes may also require the permission
+    /// to manage settings, depending on the rewards addresses they'd pay to.
+    pub fn required_permission(&self) -> Permission {
+        match self {
+            Self::Remove(_) => Permission::RemoveNodes,
+            Self::Update { changes, .. } => changes.required_permission(),
+            Self::Create { .. }
+            | Self::Start(_)
+            | Self::Stop(_)
+            | Self::Upgrade(_)
+            | Self::Recycle(_) => Permission::OperateNodes,
+        }
+    }
+
+    /// Rewards addresses the nodes created by the batch are set to pay to.
+    pub fn created_nodes_rewards_addrs(&self) -> Vec<String> {
+        match self {
+            Self::Create {
+                node_opts,
+                rewards_addrs,
+                ..
+            } if rewards_addrs.is_empty() => vec![node_opts.rewards_addr.clone()],
+            Self::Create { rewards_addrs, .. } => {
+                rewards_addrs.iter().map(|a| a.address.clone()).collect()
+            }
+            _ => vec![],
+        }
+    }
+
     pub fn ids(&self) -> Vec<NodeId> {
         match self {
             Self::Create { .. } => vec![],
@@ -102,7 +281,25 @@ impl BatchType {
             | Self::Stop(ids)
             | Self::Upgrade(ids)
             | Self::Recycle(ids)
-            | Self::Remove(ids) => ids.clone(),
+            | Self::Remove(ids)
+            | Self::Update { node_ids: ids, .. } => ids.clone(),
+        }
+    }
+
+    /// Replace the id of a node the batch actions on, e.g. after it got a new one.
+    pub fn replace_node_id(&mut self, node_id: &NodeId, new_node_id: &NodeId) {
+        match self {
+            Self::Create { .. } => {}
+            Self::Start(ids)
+            | Self::Stop(ids)
+            | Self::Upgrade(ids)
+            | Self::Recycle(ids)
+            | Self::Remove(ids)
+            | Self::Update { node_ids: ids, .. } => {
+                for id in ids.iter_mut().filter(|id| *id == node_id) {
+                    *id = new_node_id.clone();
+                }
+            }
         }
     }
 }
@@ -116,6 +313,7 @@ impl fmt::Display for BatchType {
             BatchType::Upgrade(_) => write!(f, "UPGRADE"),
             BatchType::Recycle(_) => write!(f, "RECYCLE"),
             BatchType::Remove(_) => write!(f, "REMOVE"),
+            BatchType::Update { .. } => write!(f, "UPDATE"),
         }
     }
 }
@@ -142,8 +340,55 @@ impl BatchOnMatch {
     }
 }
 
+/// Rewards address along with its share of the nodes created by a batch.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RewardsAddrWeight {
+    /// Hex-encoded rewards address
+    pub address: String,
+    /// Share of the nodes paying rewards to this address, relative to the other addresses
+    pub weight: u16,
+}
+
+/// Rewards address assigned to each of the nodes created by a batch, spreading them across the
+/// addresses in proportion to their weights, and interleaving them as in a smooth weighted
+/// round-robin so nodes paying to the same address are not all created one after the other.
+pub fn distribute_rewards_addrs(addrs: &[RewardsAddrWeight], count: u16) -> Vec<String> {
+    let total: i64 = addrs.iter().map(|a| a.weight as i64).sum();
+    if total == 0 {
+        return vec![];
+    }
+    let mut current = vec![0i64; addrs.len()];
+    (0..count)
+        .map(|_| {
+            let mut selected = 0;
+            for (i, addr) in addrs.iter().enumerate() {
+                current[i] += addr.weight as i64;
+                if current[i] > current[selected] {
+                    selected = i;
+                }
+            }
+            current[selected] -= total;
+            addrs[selected].address.clone()
+        })
+        .collect()
+}
+
+/// Number of nodes created by a batch which pay rewards to each of the addresses,
+/// in the same order as the addresses are given.
+pub fn rewards_addrs_distribution(addrs: &[RewardsAddrWeight], count: u16) -> Vec<(String, u16)> {
+    let assigned = distribute_rewards_addrs(addrs, count);
+    let mut distribution = Vec::<(String, u16)>::new();
+    for addr in addrs.iter() {
+        if !distribution.iter().any(|(a, _)| a == &addr.address) {
+            let n = assigned.iter().filter(|a| *a == &addr.address).count();
+            distribution.push((addr.address.clone(), n as u16));
+        }
+    }
+    distribution
+}
+
 /// Options when creating a new node instance.
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct NodeOpts {
     /// Whether to force IPv4-only mode (disable dual-stack)
     pub ipv4_only: bool,
@@ -162,3 +407,338 @@ pub struct NodeOpts {
     /// Custom data directory path for this node instance
     pub data_dir_path: PathBuf,
 }
+
+impl From<&NodeInstanceInfo> for NodeOpts {
+    fn from(info: &NodeInstanceInfo) -> Self {
+        Self {
+            ipv4_only: info.ipv4_only,
+            port: info.port.unwrap_or_default(),
+            metrics_port: info.metrics_port.unwrap_or_default(),
+            rewards_addr: info.rewards_addr.clone().unwrap_or_default(),
+            node_logs: info.node_logs,
+            log_level: info.log_level.clone(),
+            auto_start: false,
+            data_dir_path: info.data_dir_path.clone().unwrap_or_default(),
+        }
+    }
+}
+
+/// Changes to the configuration of an existing node instance.
+/// Settings left unset keep their current value.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct NodeConfigUpdate {
+    /// Whether to force IPv4-only mode (disable dual-stack)
+    pub ipv4_only: Option<bool>,
+    /// TCP port used by the node for main operations
+    pub port: Option<u16>,
+    /// TCP port used by the node for metrics reporting
+    pub metrics_port: Option<u16>,
+    /// Hex-encoded rewards address for the node
+    pub rewards_addr: Option<String>,
+    /// Whether node logs are enabled for the node
+    pub node_logs: Option<bool>,
+    /// Log level for node logging output
+    pub log_level: Option<LogLevel>,
+}
+
+impl NodeConfigUpdate {
+    /// Whether there is no change to apply at all.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Permission required to apply the changes. Only admins can change the rewards
+    /// address, since it redirects the node's earnings to another wallet.
+    pub fn required_permission(&self) -> Permission {
+        if self.rewards_addr.is_some() {
+            Permission::ManageSettings
+        } else {
+            Permission::OperateNodes
+        }
+    }
+
+    /// Whether the ports of the node are changed, which cannot be
+    /// set to the same values on more than one node.
+    pub fn changes_ports(&self) -> bool {
+        self.port.is_some() || self.metrics_port.is_some()
+    }
+
+    /// Set the changes onto the given node info.
+    pub fn apply_to(&self, info: &mut NodeInstanceInfo) {
+        if let Some(ipv4_only) = self.ipv4_only {
+            info.ipv4_only = ipv4_only;
+        }
+        if let Some(port) = self.port {
+            info.port = Some(port);
+        }
+        if let Some(metrics_port) = self.metrics_port {
+            info.metrics_port = Some(metrics_port);
+        }
+        if let Some(rewards_addr) = &self.rewards_addr {
+            info.rewards_addr = Some(rewards_addr.clone());
+        }
+        if let Some(node_logs) = self.node_logs {
+            info.node_logs = node_logs;
+        }
+        if let Some(log_level) = &self.log_level {
+            info.log_level = log_level.clone();
+        }
+    }
+}
+
+/// Outcome of updating the configuration of a node instance.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NodeUpdated {
+    /// Up to date info of the node.
+    pub info: NodeInstanceInfo,
+    /// Id the node had before the update, only set if it got a new one, which happens in
+    /// Docker mode where its container is recreated. Its history is moved to the new id.
+    pub previous_node_id: Option<NodeId>,
+}
+
+impl fmt::Display for NodeConfigUpdate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut changes = vec![];
+        if let Some(ipv4_only) = self.ipv4_only {
+            changes.push(format!("IPv4-only: {ipv4_only}"));
+        }
+        if let Some(port) = self.port {
+            changes.push(format!("port: {port}"));
+        }
+        if let Some(metrics_port) = self.metrics_port {
+            changes.push(format!("metrics port: {metrics_port}"));
+        }
+        if let Some(rewards_addr) = &self.rewards_addr {
+            changes.push(format!("rewards address: {rewards_addr}"));
+        }
+        if let Some(node_logs) = self.node_logs {
+            changes.push(format!("node logs: {node_logs}"));
+        }
+        if let Some(log_level) = &self.log_level {
+            changes.push(format!("log level: {log_level}"));
+        }
+        write!(f, "{}", changes.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::UserRole;
+
+    #[test]
+    fn test_batch_progress() {
+        let ids = vec![NodeId::random(), NodeId::random(), NodeId::random()];
+        let mut batch = NodesActionsBatch::new(1, BatchType::Upgrade(ids.clone()), 10);
+        assert_eq!(batch.total_actions(), 3);
+        assert_eq!(batch.failures(), 0);
+        assert!(!batch.status.is_finished());
+
+        batch.items = vec![
+            BatchItemOutcome {
+                node_id: Some(ids[0].clone()),
+                error: None,
+                timestamp: 1,
+            },
+            BatchItemOutcome {
+                node_id: Some(ids[1].clone()),
+                error: Some("failed".to_string()),
+                timestamp: 2,
+            },
+        ];
+        assert_eq!(batch.failures(), 1);
+        assert_eq!(
+            batch.running_status(),
+            BatchStatus::InProgressWithFailures(1, "failed".to_string())
+        );
+        assert_eq!(batch.pending_node_ids(), vec![ids[2].clone()]);
+        assert_eq!(
+            batch.retry_batch_type(),
+            Ok(Some(BatchType::Upgrade(vec![ids[1].clone()])))
+        );
+
+        let create = BatchType::Create {
+            node_opts: NodeOpts::default(),
+            count: 5,
+            rewards_addrs: vec![],
+        };
+        let mut create_batch = NodesActionsBatch::new(2, create, 0);
+        assert_eq!(create_batch.total_actions(), 5);
+        assert_eq!(create_batch.retry_batch_type(), Ok(None));
+        create_batch.items.push(BatchItemOutcome {
+            node_id: None,
+            error: Some("port in use".to_string()),
+            timestamp: 3,
+        });
+        let Ok(Some(BatchType::Create {
+            node_opts, count, ..
+        })) = create_batch.retry_batch_type()
+        else {
+            panic!("expected a creation batch to retry");
+        };
+        assert_eq!((node_opts.port, count), (5, 1));
+
+        // ports following the batch's ones would overflow
+        let create = BatchType::Create {
+            node_opts: NodeOpts {
+                port: u16::MAX - 2,
+                ..Default::default()
+            },
+            count: 3,
+            rewards_addrs: vec![],
+        };
+        let mut create_batch = NodesActionsBatch::new(3, create, 0);
+        create_batch.items = vec![
+            BatchItemOutcome {
+                node_id: None,
+                error: Some("port in use".to_string()),
+                timestamp: 4,
+            };
+            3
+        ];
+        assert!(create_batch.retry_batch_type().is_err());
+
+        assert!(BatchStatus::Completed.is_finished());
+        assert!(BatchStatus::Cancelled.is_finished());
+        assert!(!BatchStatus::Cancelled.is_failed());
+        assert!(BatchStatus::Failed("err".to_string()).is_finished());
+        assert!(!BatchStatus::InProgressWithFailures(1, "err".to_string()).is_finished());
+    }
+
+    #[test]
+    fn test_rewards_addrs_distribution() {
+        let weight = |address: &str, weight| RewardsAddrWeight {
+            address: address.to_string(),
+            weight,
+        };
+        let addrs = vec![weight("a", 2), weight("b", 1), weight("c", 0)];
+
+        assert_eq!(
+            distribute_rewards_addrs(&addrs, 6),
+            vec!["a", "b", "a", "a", "b", "a"]
+        );
+        assert_eq!(
+            rewards_addrs_distribution(&addrs, 7),
+            vec![
+                ("a".to_string(), 5),
+                ("b".to_string(), 2),
+                ("c".to_string(), 0)
+            ]
+        );
+        assert!(distribute_rewards_addrs(&[weight("a", 0)], 3).is_empty());
+        assert!(distribute_rewards_addrs(&[], 3).is_empty());
+
+        // failed nodes are retried with the addresses they were assigned
+        let create = BatchType::Create {
+            node_opts: NodeOpts::default(),
+            count: 3,
+            rewards_addrs: addrs,
+        };
+        let mut batch = NodesActionsBatch::new(1, create, 0);
+        for error in [None, Some("err"), Some("err")] {
+            batch.items.push(BatchItemOutcome {
+                node_id: None,
+                error: error.map(|e| e.to_string()),
+                timestamp: 1,
+            });
+        }
+        let Ok(Some(BatchType::Create { rewards_addrs, .. })) = batch.retry_batch_type() else {
+            panic!("expected a creation batch to retry");
+        };
+        assert_eq!(rewards_addrs, vec![weight("b", 1), weight("a", 1)]);
+    }
+
+    #[test]
+    fn test_node_config_update() {
+        let mut info = NodeInstanceInfo {
+            port: Some(12000),
+            metrics_port: Some(14000),
+            rewards_addr: Some("0x1234".to_string()),
+            node_logs: true,
+            ..Default::default()
+        };
+        assert!(NodeConfigUpdate::default().is_empty());
+
+        let changes = NodeConfigUpdate {
+            node_logs: Some(false),
+            log_level: Some(LogLevel::Debug),
+            rewards_addr: Some("0x5678".to_string()),
+            ..Default::default()
+        };
+        assert!(!changes.is_empty());
+        assert!(!changes.changes_ports());
+        changes.apply_to(&mut info);
+        assert!(!info.node_logs);
+        assert_eq!(info.log_level, LogLevel::Debug);
+        assert_eq!(info.rewards_addr.as_deref(), Some("0x5678"));
+        assert_eq!((info.port, info.metrics_port), (Some(12000), Some(14000)));
+
+        let changes = NodeConfigUpdate {
+            port: Some(13000),
+            ..Default::default()
+        };
+        assert!(changes.changes_ports());
+        changes.apply_to(&mut info);
+        let node_opts = NodeOpts::from(&info);
+        assert_eq!((node_opts.port, node_opts.metrics_port), (13000, 14000));
+
+        let ids = vec![NodeId::random()];
+        let update = BatchType::Update {
+            node_ids: ids.clone(),
+            changes,
+        };
+        let mut batch = NodesActionsBatch::new(3, update, 0);
+        batch.items.push(BatchItemOutcome {
+            node_id: Some(ids[0].clone()),
+            error: Some("failed".to_string()),
+            timestamp: 1,
+        });
+        assert!(matches!(
+            batch.retry_batch_type(),
+            Ok(Some(BatchType::Update { node_ids, changes })) if node_ids == ids && changes.port == Some(13000)
+        ));
+    }
+
+    #[test]
+    fn test_rewards_addr_changes_require_admin() {
+        let changes = NodeConfigUpdate {
+            log_level: Some(LogLevel::Debug),
+            ..Default::default()
+        };
+        assert!(UserRole::Operator.can(changes.required_permission()));
+
+        let changes = NodeConfigUpdate {
+            rewards_addr: Some("0x1234".to_string()),
+            ..changes
+        };
+        assert!(!UserRole::Operator.can(changes.required_permission()));
+        assert!(UserRole::Admin.can(changes.required_permission()));
+        let update = BatchType::Update {
+            node_ids: vec![NodeId::random()],
+            changes,
+        };
+        assert!(!UserRole::Operator.can(update.required_permission()));
+        assert!(UserRole::Operator.can(BatchType::Start(vec![]).required_permission()));
+        assert!(!UserRole::Operator.can(BatchType::Remove(vec![]).required_permission()));
+
+        let create = |rewards_addrs: Vec<&str>| BatchType::Create {
+            node_opts: NodeOpts {
+                rewards_addr: "0xaaaa".to_string(),
+                ..Default::default()
+            },
+            count: 2,
+            rewards_addrs: rewards_addrs
+                .into_iter()
+                .map(|address| RewardsAddrWeight {
+                    address: address.to_string(),
+                    weight: 1,
+                })
+                .collect(),
+        };
+        assert_eq!(create(vec![]).created_nodes_rewards_addrs(), vec!["0xaaaa"]);
+        assert_eq!(
+            create(vec!["0xbbbb", "0xcccc"]).created_nodes_rewards_addrs(),
+            vec!["0xbbbb", "0xcccc"]
+        );
+    }
+}
@@ -0,0 +1,260 @@
+use alloy_primitives::{U256, utils::format_units};
+use chrono::{DateTime, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// Number of decimals of the rewards token.
+const TOKEN_DECIMALS: u8 = 18;
+
+/// File format reward payments can be exported to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Json => "json",
+        }
+    }
+
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv",
+            Self::Json => "application/json",
+        }
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.extension())
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            other => Err(format!("Not a valid export format: {other}")),
+        }
+    }
+}
+
+/// Reward payment received by an address, along with the token price on the day it was received.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct EarningsExportRow {
+    /// Rewards address the payment was received by
+    pub address: String,
+    /// UNIX timestamp (seconds) of the block the payment was included in
+    pub timestamp: i64,
+    pub block_number: u64,
+    /// Amount paid, in attos
+    pub amount: U256,
+    /// Token price in fiat currency on the day of the payment, if it could be retrieved
+    pub price: Option<f64>,
+}
+
+impl EarningsExportRow {
+    /// Day (UTC) the payment was received on.
+    pub fn date(&self) -> NaiveDate {
+        DateTime::from_timestamp(self.timestamp, 0)
+            .unwrap_or_default()
+            .date_naive()
+    }
+
+    /// Amount paid, in tokens.
+    pub fn amount_tokens(&self) -> String {
+        format_units(self.amount, TOKEN_DECIMALS).unwrap_or_default()
+    }
+
+    /// Value of the payment in fiat currency, if the token price is known.
+    pub fn fiat_value(&self) -> Option<f64> {
+        let tokens = self.amount_tokens().parse::<f64>().ok()?;
+        self.price.map(|price| tokens * price)
+    }
+}
+
+/// Reward payments received within a range of days, valued in a fiat currency for accounting.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct EarningsExport {
+    /// Fiat currency payments are valued in, e.g. 'usd'
+    pub currency: String,
+    /// First day (UTC) of the range
+    pub from: NaiveDate,
+    /// Last day (UTC) of the range, inclusive
+    pub to: NaiveDate,
+    /// Payments sorted from the oldest to the newest
+    pub payments: Vec<EarningsExportRow>,
+    /// Number of payments the token price couldn't be retrieved for, e.g. due to
+    /// the price API rate-limiting us, which are exported without their fiat value
+    #[serde(default)]
+    pub missing_prices: usize,
+}
+
+// Payment as written to a JSON export, with its amounts already formatted.
+#[derive(Serialize)]
+struct JsonPayment<'a> {
+    date: String,
+    timestamp: i64,
+    address: &'a str,
+    block_number: u64,
+    amount_attos: String,
+    amount: String,
+    price: Option<f64>,
+    value: Option<f64>,
+}
+
+impl EarningsExport {
+    /// Name of the file the export is saved to, e.g. 'earnings_2026-01-01_2026-03-31.csv'.
+    pub fn file_name(&self, format: ExportFormat) -> String {
+        format!("earnings_{}_{}.{}", self.from, self.to, format.extension())
+    }
+
+    /// Whether some payments are exported without their fiat value since their
+    /// token price couldn't be retrieved.
+    pub fn is_incomplete(&self) -> bool {
+        self.missing_prices > 0
+    }
+
+    /// Total value of the payments whose token price is known.
+    pub fn total_fiat_value(&self) -> f64 {
+        self.payments.iter().filter_map(|p| p.fiat_value()).sum()
+    }
+
+    /// Content of the export in the given format.
+    pub fn render(&self, format: ExportFormat) -> String {
+        match format {
+            ExportFormat::Csv => self.to_csv(),
+            ExportFormat::Json => self.to_json(),
+        }
+    }
+
+    fn to_csv(&self) -> String {
+        let mut csv = format!(
+            "date,timestamp,address,block_number,amount_attos,amount,price_{0},value_{0}\n",
+            self.currency
+        );
+        for p in self.payments.iter() {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{},{}\n",
+                p.date(),
+                p.timestamp,
+                p.address,
+                p.block_number,
+                p.amount,
+                p.amount_tokens(),
+                p.price.map(|v| v.to_string()).unwrap_or_default(),
+                p.fiat_value()
+                    .map(|v| format!("{v:.6}"))
+                    .unwrap_or_default()
+            ));
+        }
+        csv
+    }
+
+    fn to_json(&self) -> String {
+        let payments = self
+            .payments
+            .iter()
+            .map(|p| JsonPayment {
+                date: p.date().to_string(),
+                timestamp: p.timestamp,
+                address: &p.address,
+                block_number: p.block_number,
+                amount_attos: p.amount.to_string(),
+                amount: p.amount_tokens(),
+                price: p.price,
+                value: p.fiat_value(),
+            })
+            .collect::<Vec<_>>();
+        let total: U256 = self.payments.iter().map(|p| p.amount).sum();
+        let json = serde_json::json!({
+            "currency": self.currency,
+            "from": self.from,
+            "to": self.to,
+            "total_amount": format_units(total, TOKEN_DECIMALS).unwrap_or_default(),
+            "total_value": self.total_fiat_value(),
+            "incomplete": self.is_incomplete(),
+            "missing_prices": self.missing_prices,
+            "payments": payments,
+        });
+        serde_json::to_string_pretty(&json).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_export() -> EarningsExport {
+        EarningsExport {
+            currency: "usd".to_string(),
+            from: NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+            to: NaiveDate::from_ymd_opt(2026, 3, 31).unwrap(),
+            payments: vec![
+                EarningsExportRow {
+                    address: "0x52e6FCF5c2b1C1ea3B7fEA4a5BfF1e3C3f2E5a1b".to_string(),
+                    // 2026-03-02 10:00:00 UTC
+                    timestamp: 1_772_445_600,
+                    block_number: 100,
+                    amount: U256::from(1_500_000_000_000_000_000u128),
+                    price: Some(0.2),
+                },
+                EarningsExportRow {
+                    address: "0x52e6FCF5c2b1C1ea3B7fEA4a5BfF1e3C3f2E5a1b".to_string(),
+                    timestamp: 1_772_532_000,
+                    block_number: 200,
+                    amount: U256::from(500_000_000_000_000_000u128),
+                    price: None,
+                },
+            ],
+            missing_prices: 1,
+        }
+    }
+
+    #[test]
+    fn test_earnings_export_csv() {
+        let export = test_export();
+        assert_eq!(
+            export.file_name(ExportFormat::Csv),
+            "earnings_2026-03-01_2026-03-31.csv"
+        );
+
+        let csv = export.render(ExportFormat::Csv);
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[0],
+            "date,timestamp,address,block_number,amount_attos,amount,price_usd,value_usd"
+        );
+        assert_eq!(
+            lines[1],
+            "2026-03-02,1772445600,0x52e6FCF5c2b1C1ea3B7fEA4a5BfF1e3C3f2E5a1b,100,1500000000000000000,1.500000000000000000,0.2,0.300000"
+        );
+        // payments without a known price have no fiat value
+        assert!(lines[2].ends_with(",0.500000000000000000,,"));
+    }
+
+    #[test]
+    fn test_earnings_export_json() -> Result<(), serde_json::Error> {
+        let export = test_export();
+        let json: serde_json::Value = serde_json::from_str(&export.render(ExportFormat::Json))?;
+        assert_eq!(json["currency"], "usd");
+        assert_eq!(json["total_amount"], "2.000000000000000000");
+        assert!((json["total_value"].as_f64().unwrap() - 0.3).abs() < 1e-9);
+        assert_eq!(json["payments"].as_array().map(|p| p.len()), Some(2));
+        assert_eq!(json["payments"][0]["date"], "2026-03-02");
+        assert_eq!(json["payments"][1]["value"], serde_json::Value::Null);
+        assert_eq!(json["incomplete"], true);
+        assert_eq!(json["missing_prices"], 1);
+        assert_eq!("JSON".parse::<ExportFormat>(), Ok(ExportFormat::Json));
+        Ok(())
+    }
+}
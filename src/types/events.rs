@@ -0,0 +1,25 @@
+use super::{AgentEvent, NodeId, NodeInstanceInfo, NodesActionsBatch, NodesInstancesInfo, Stats};
+
+use serde::{Deserialize, Serialize};
+
+/// Event pushed by the backend to the subscribers of its live events stream.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data")]
+pub enum ServerEvent {
+    /// Full state sent upon subscription, any further event is an incremental update to it.
+    Snapshot(Box<NodesInstancesInfo>),
+    /// A node was added, or its status, metadata or metrics changed.
+    NodeUpdated(Box<NodeInstanceInfo>),
+    /// A node was removed.
+    NodeRemoved(NodeId),
+    /// Aggregated stats changed.
+    StatsUpdated(Box<Stats>),
+    /// List of scheduled and running batches changed, including the progress of any of them.
+    BatchesUpdated(Vec<NodesActionsBatch>),
+    /// The AI agent recorded a new event.
+    AgentEvent(AgentEvent),
+    /// A new version of the node binary is available.
+    NewBinVersion(String),
+    /// The app settings were changed, they need to be retrieved again.
+    SettingsUpdated,
+}
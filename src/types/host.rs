@@ -0,0 +1,263 @@
+use super::{Metrics, NodeMetric};
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Id the host metrics are stored with, alongside the nodes metrics, so they are rolled up
+/// and pruned with the same retention.
+pub const HOST_METRICS_ID: &str = "host";
+
+// Percentage of CPU used across all cores. Value is from 0-100.
+pub const HOST_METRIC_KEY_CPU_USAGE: &str = "host_cpu_usage_percentage";
+// Memory used in bytes.
+pub const HOST_METRIC_KEY_MEM_USED: &str = "host_memory_used_bytes";
+// Swap used in bytes.
+pub const HOST_METRIC_KEY_SWAP_USED: &str = "host_swap_used_bytes";
+// System load average over the last minute.
+pub const HOST_METRIC_KEY_LOAD_AVG: &str = "host_load_average_1m";
+// Bytes read from all disks per second.
+pub const HOST_METRIC_KEY_DISK_READ: &str = "host_disk_read_bytes_per_sec";
+// Bytes written to all disks per second.
+pub const HOST_METRIC_KEY_DISK_WRITE: &str = "host_disk_written_bytes_per_sec";
+// Bytes received per second by a network interface, the key is suffixed with ':<interface>'.
+pub const HOST_METRIC_KEY_NET_RECEIVED: &str = "host_network_received_bytes_per_sec";
+// Bytes transmitted per second by a network interface, the key is suffixed with ':<interface>'.
+pub const HOST_METRIC_KEY_NET_TRANSMITTED: &str = "host_network_transmitted_bytes_per_sec";
+
+/// Throughput of a network interface of the host.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct HostNetworkStats {
+    /// Name of the network interface
+    pub interface: String,
+    /// Bytes received per second
+    pub received_rate: f64,
+    /// Bytes transmitted per second
+    pub transmitted_rate: f64,
+}
+
+/// Resources usage of the host Formicaio and its nodes are running on.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct HostStats {
+    /// Timestamp (in millis) the stats were sampled at
+    pub timestamp: i64,
+    /// Number of logical CPUs
+    pub cpus: usize,
+    /// Percentage of CPU used across all cores
+    pub cpu_usage: f64,
+    /// Memory used in bytes
+    pub mem_used: u64,
+    /// Total memory in bytes
+    pub mem_total: u64,
+    /// Swap used in bytes
+    pub swap_used: u64,
+    /// Total swap in bytes
+    pub swap_total: u64,
+    /// System load average over the last 1, 5 and 15 minutes
+    pub load_avg: [f64; 3],
+    /// Bytes read from all disks per second
+    pub disk_read_rate: f64,
+    /// Bytes written to all disks per second
+    pub disk_write_rate: f64,
+    /// Throughput of each network interface
+    pub networks: Vec<HostNetworkStats>,
+}
+
+impl HostStats {
+    /// Percentage of the total memory in use.
+    pub fn mem_usage(&self) -> Option<f64> {
+        (self.mem_total > 0).then(|| self.mem_used as f64 * 100.0 / self.mem_total as f64)
+    }
+
+    /// Percentage of the total swap in use.
+    pub fn swap_usage(&self) -> Option<f64> {
+        (self.swap_total > 0).then(|| self.swap_used as f64 * 100.0 / self.swap_total as f64)
+    }
+
+    /// Metrics data points to be stored as the history of the host resources usage.
+    pub fn metrics(&self) -> Vec<NodeMetric> {
+        let metric = |key: String, value: f64| NodeMetric {
+            key,
+            value: value.to_string(),
+            timestamp: self.timestamp,
+        };
+        let mut metrics = vec![
+            metric(HOST_METRIC_KEY_CPU_USAGE.to_string(), self.cpu_usage),
+            metric(HOST_METRIC_KEY_MEM_USED.to_string(), self.mem_used as f64),
+            metric(HOST_METRIC_KEY_SWAP_USED.to_string(), self.swap_used as f64),
+            metric(HOST_METRIC_KEY_LOAD_AVG.to_string(), self.load_avg[0]),
+            metric(HOST_METRIC_KEY_DISK_READ.to_string(), self.disk_read_rate),
+            metric(HOST_METRIC_KEY_DISK_WRITE.to_string(), self.disk_write_rate),
+        ];
+        for net in self.networks.iter() {
+            metrics.push(metric(
+                format!("{HOST_METRIC_KEY_NET_RECEIVED}:{}", net.interface),
+                net.received_rate,
+            ));
+            metrics.push(metric(
+                format!("{HOST_METRIC_KEY_NET_TRANSMITTED}:{}", net.interface),
+                net.transmitted_rate,
+            ));
+        }
+        metrics
+    }
+}
+
+/// Host resources usage averaged over the bucket starting at its timestamp.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct HostSnapshot {
+    /// Timestamp (in millis) of the snapshot
+    pub timestamp: i64,
+    /// Percentage of CPU used across all cores
+    pub cpu_usage: Option<f64>,
+    /// Memory used in bytes
+    pub mem_used: Option<f64>,
+    /// Swap used in bytes
+    pub swap_used: Option<f64>,
+    /// System load average over one minute
+    pub load_avg: Option<f64>,
+    /// Bytes read from all disks per second
+    pub disk_read_rate: Option<f64>,
+    /// Bytes written to all disks per second
+    pub disk_write_rate: Option<f64>,
+    /// Bytes received and transmitted per second by each network interface
+    pub networks: BTreeMap<String, (f64, f64)>,
+}
+
+impl HostSnapshot {
+    /// Average the host metrics data points over buckets of the given length, oldest first.
+    pub fn from_metrics(metrics: &Metrics, bucket_millis: i64) -> Vec<Self> {
+        let mut buckets = BTreeMap::<i64, HashMap<&str, (f64, usize)>>::new();
+        for (key, points) in metrics.iter() {
+            for point in points {
+                let Ok(value) = point.value.parse::<f64>() else {
+                    continue;
+                };
+                let bucket = (point.timestamp / bucket_millis) * bucket_millis;
+                let (sum, count) = buckets
+                    .entry(bucket)
+                    .or_default()
+                    .entry(key.as_str())
+                    .or_default();
+                *sum += value;
+                *count += 1;
+            }
+        }
+
+        buckets
+            .into_iter()
+            .map(|(timestamp, values)| {
+                let avg = |key: &str| values.get(key).map(|(sum, count)| sum / *count as f64);
+                let mut networks = BTreeMap::<String, (f64, f64)>::new();
+                for key in values.keys() {
+                    if let Some((base, interface)) = key.split_once(':') {
+                        let entry = networks.entry(interface.to_string()).or_default();
+                        match base {
+                            HOST_METRIC_KEY_NET_RECEIVED => entry.0 = avg(key).unwrap_or_default(),
+                            HOST_METRIC_KEY_NET_TRANSMITTED => {
+                                entry.1 = avg(key).unwrap_or_default()
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                Self {
+                    timestamp,
+                    cpu_usage: avg(HOST_METRIC_KEY_CPU_USAGE),
+                    mem_used: avg(HOST_METRIC_KEY_MEM_USED),
+                    swap_used: avg(HOST_METRIC_KEY_SWAP_USED),
+                    load_avg: avg(HOST_METRIC_KEY_LOAD_AVG),
+                    disk_read_rate: avg(HOST_METRIC_KEY_DISK_READ),
+                    disk_write_rate: avg(HOST_METRIC_KEY_DISK_WRITE),
+                    networks,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Traffic received and transmitted by the host's network interfaces during a calendar month.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct MonthlyTraffic {
+    /// Month (UTC) the traffic was accounted in, in 'YYYY-MM' format
+    pub month: String,
+    /// Bytes received during the month
+    pub received: u64,
+    /// Bytes transmitted during the month
+    pub transmitted: u64,
+    /// Traffic allowed per month in bytes, None if it's not capped
+    pub cap: Option<u64>,
+}
+
+impl MonthlyTraffic {
+    /// Total bytes received and transmitted during the month.
+    pub fn total(&self) -> u64 {
+        self.received + self.transmitted
+    }
+
+    /// Percentage of the monthly cap already used, None if it's not capped.
+    pub fn cap_usage(&self) -> Option<f64> {
+        self.cap
+            .filter(|cap| *cap > 0)
+            .map(|cap| self.total() as f64 * 100.0 / cap as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_host_snapshots_from_metrics() {
+        let stats = |timestamp, cpu_usage, received_rate| HostStats {
+            timestamp,
+            cpu_usage,
+            mem_used: 1_000,
+            load_avg: [0.5, 0.4, 0.3],
+            networks: vec![HostNetworkStats {
+                interface: "eth0".to_string(),
+                received_rate,
+                transmitted_rate: 10.0,
+            }],
+            ..Default::default()
+        };
+        let mut metrics = Metrics::new();
+        for s in [
+            stats(0, 10.0, 100.0),
+            stats(30_000, 20.0, 300.0),
+            stats(60_000, 50.0, 0.0),
+        ] {
+            for m in s.metrics() {
+                metrics.entry(m.key.clone()).or_default().push(m);
+            }
+        }
+
+        let snapshots = HostSnapshot::from_metrics(&metrics, 60_000);
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(snapshots[0].timestamp, 0);
+        assert_eq!(snapshots[0].cpu_usage, Some(15.0));
+        assert_eq!(snapshots[0].mem_used, Some(1_000.0));
+        assert_eq!(snapshots[0].load_avg, Some(0.5));
+        assert_eq!(snapshots[0].networks.get("eth0"), Some(&(200.0, 10.0)));
+        assert_eq!(snapshots[1].timestamp, 60_000);
+        assert_eq!(snapshots[1].cpu_usage, Some(50.0));
+        assert_eq!(snapshots[1].disk_read_rate, Some(0.0));
+    }
+
+    #[test]
+    fn test_monthly_traffic_cap_usage() {
+        let mut traffic = MonthlyTraffic {
+            month: "2026-10".to_string(),
+            received: 300,
+            transmitted: 100,
+            cap: None,
+        };
+        assert_eq!(traffic.total(), 400);
+        assert_eq!(traffic.cap_usage(), None);
+
+        traffic.cap = Some(1_000);
+        assert_eq!(traffic.cap_usage(), Some(40.0));
+
+        traffic.cap = Some(0);
+        assert_eq!(traffic.cap_usage(), None);
+    }
+}
@@ -1,3 +1,5 @@
+use super::AppSettings;
+
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -14,6 +16,61 @@ pub struct NodeMetric {
 // Set of metrics collected for a node, indexed by metric name/key.
 pub type Metrics = HashMap<String, Vec<NodeMetric>>;
 
+const MILLIS_PER_HOUR: i64 = 60 * 60 * 1_000;
+const MILLIS_PER_DAY: i64 = 24 * MILLIS_PER_HOUR;
+
+/// Resolution of the historic metrics data points kept in the DB.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MetricsResolution {
+    /// Samples as they were collected from the nodes.
+    Raw,
+    /// Samples averaged over 1-minute buckets.
+    Minute,
+    /// Samples averaged over 1-hour buckets.
+    Hour,
+}
+
+impl MetricsResolution {
+    /// Length (in seconds) of the buckets samples are averaged over, zero for raw samples.
+    pub fn bucket_secs(&self) -> i64 {
+        match self {
+            Self::Raw => 0,
+            Self::Minute => 60,
+            Self::Hour => 60 * 60,
+        }
+    }
+
+    /// How long (in millis) data points of this resolution are kept as per the given settings.
+    pub fn retention_millis(&self, settings: &AppSettings) -> i64 {
+        match self {
+            Self::Raw => settings.metrics_raw_retention_hours as i64 * MILLIS_PER_HOUR,
+            Self::Minute => settings.metrics_minute_retention_days as i64 * MILLIS_PER_DAY,
+            Self::Hour => settings.metrics_hourly_retention_days as i64 * MILLIS_PER_DAY,
+        }
+    }
+
+    /// Split the range starting at 'since' (timestamp in millis, exclusive) until 'now'
+    /// into the windows to be read with each resolution, coarsest first, so the most
+    /// recent data points are always provided with the finest resolution available.
+    pub fn windows_for_range(
+        since: Option<i64>,
+        now: i64,
+        settings: &AppSettings,
+    ) -> Vec<(Self, i64, i64)> {
+        let since = since.unwrap_or_default();
+        let minute_start = now - Self::Minute.retention_millis(settings);
+        let raw_start = now - Self::Raw.retention_millis(settings);
+        [
+            (Self::Hour, since, minute_start),
+            (Self::Minute, since.max(minute_start), raw_start),
+            (Self::Raw, since.max(raw_start), i64::MAX),
+        ]
+        .into_iter()
+        .filter(|(_, from, to)| from < to)
+        .collect()
+    }
+}
+
 // The number of Nanos in the node reward wallet.
 pub const METRIC_KEY_BALANCE: &str = "ant_node_current_reward_wallet_balance";
 // Memory used by the process in MegaBytes.
@@ -37,3 +94,53 @@ pub const METRIC_KEY_REACHABILITY: &str = "ant_networking_reachability_adapter";
 // Progress indicator for reachability check. 0 = not run, 1-99 = in progress, 100 = completed.
 pub const METRIC_KEY_REACHABILITY_CHECK_PROGRESS: &str =
     "ant_networking_reachability_check_progress";
+// Bytes received per second by the node, calculated by Formicaio from the node's traffic counters.
+pub const METRIC_KEY_NET_RX_RATE: &str = "formicaio_node_network_received_bytes_per_sec";
+// Bytes transmitted per second by the node, calculated by Formicaio from the node's traffic counters.
+pub const METRIC_KEY_NET_TX_RATE: &str = "formicaio_node_network_transmitted_bytes_per_sec";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000_000;
+
+    #[test]
+    fn test_recent_range_is_read_raw() {
+        let settings = AppSettings::default();
+        let since = NOW - 10 * 60 * 1_000;
+        assert_eq!(
+            MetricsResolution::windows_for_range(Some(since), NOW, &settings),
+            vec![(MetricsResolution::Raw, since, i64::MAX)]
+        );
+    }
+
+    #[test]
+    fn test_range_beyond_raw_retention_is_split() {
+        let settings = AppSettings::default();
+        let raw_start = NOW - MetricsResolution::Raw.retention_millis(&settings);
+        let since = raw_start - MILLIS_PER_HOUR;
+        assert_eq!(
+            MetricsResolution::windows_for_range(Some(since), NOW, &settings),
+            vec![
+                (MetricsResolution::Minute, since, raw_start),
+                (MetricsResolution::Raw, raw_start, i64::MAX)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_whole_history_uses_all_resolutions() {
+        let settings = AppSettings::default();
+        let minute_start = NOW - MetricsResolution::Minute.retention_millis(&settings);
+        let raw_start = NOW - MetricsResolution::Raw.retention_millis(&settings);
+        assert_eq!(
+            MetricsResolution::windows_for_range(None, NOW, &settings),
+            vec![
+                (MetricsResolution::Hour, 0, minute_start),
+                (MetricsResolution::Minute, minute_start, raw_start),
+                (MetricsResolution::Raw, raw_start, i64::MAX)
+            ]
+        );
+    }
+}
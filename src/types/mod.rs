@@ -1,26 +1,68 @@
+mod address_book;
 mod agent;
+mod alerts;
+mod auth;
 mod batch;
+mod earnings_export;
+mod events;
 mod filters;
+mod host;
 pub mod metrics;
 mod node_id;
 mod node_instance;
 mod node_status;
+mod notifications;
+mod profitability;
+mod rpc_endpoints;
 mod settings;
 mod sort_nodes;
 mod stats;
 
+pub use address_book::{
+    AddressBook, AddressBookEntry, DEFAULT_ADDRESS_COLOR, RewardsAddressSummary,
+    normalized_address, rewards_addresses_summaries,
+};
 pub use agent::{AgentEvent, AgentEventType, ChatMessage, ChatRole, StreamChunk};
-pub use batch::{BatchOnMatch, BatchStatus, BatchType, NodeOpts, NodesActionsBatch};
+pub use alerts::{Alert, AlertMetric, AlertOperator, AlertRule, AlertSeverity, AlertState};
+pub use auth::{
+    AuthStatus, CurrentUser, MIN_PASSWORD_LENGTH, Permission, SESSION_COOKIE_NAME, UserInfo,
+    UserRole, validate_credentials,
+};
+pub use batch::{
+    BatchItemOutcome, BatchOnMatch, BatchStatus, BatchType, NodeConfigUpdate, NodeOpts,
+    NodeUpdated, NodesActionsBatch, RewardsAddrWeight, distribute_rewards_addrs,
+    rewards_addrs_distribution,
+};
+pub use earnings_export::{EarningsExport, EarningsExportRow, ExportFormat};
+pub use events::ServerEvent;
 pub use filters::{NodeFilter, NodeStatusFilter};
+pub use host::{
+    HOST_METRIC_KEY_CPU_USAGE, HOST_METRIC_KEY_DISK_READ, HOST_METRIC_KEY_DISK_WRITE,
+    HOST_METRIC_KEY_LOAD_AVG, HOST_METRIC_KEY_MEM_USED, HOST_METRIC_KEY_NET_RECEIVED,
+    HOST_METRIC_KEY_NET_TRANSMITTED, HOST_METRIC_KEY_SWAP_USED, HOST_METRICS_ID, HostNetworkStats,
+    HostSnapshot, HostStats, MonthlyTraffic,
+};
 pub use metrics::*;
 pub use node_id::NodeId;
 pub use node_instance::{
     LogLevel, NodeInstanceInfo, NodePid, ReachabilityCheckStatus, shortened_address,
 };
 pub use node_status::{InactiveReason, NodeStatus};
-pub use settings::{AppSettings, MetricsMode};
+pub use notifications::{
+    NotificationChannel, NotificationEvent, NotificationEventKind, NotificationTarget,
+};
+pub use profitability::{DEFAULT_PROFITABILITY_PERIOD_HOURS, Profitability, ProfitabilityStats};
+pub use rpc_endpoints::{RpcEndpointHealth, parse_rpc_urls};
+pub use settings::{
+    AppSettings, EarningsHistoryStart, MetricsMode, format_earnings_periods, mask_url,
+    parse_earnings_periods, parse_token_addresses,
+};
 pub use sort_nodes::{NodeSortField, NodesSortStrategy};
-pub use stats::{EarningsStats, PeriodStats, Stats, WidgetFourStats, WidgetStat};
+pub use stats::{
+    DEFAULT_EARNINGS_PERIODS_HOURS, EarningsBucket, EarningsBucketStats, EarningsHistory,
+    EarningsStats, OtherBalances, PeriodStats, Stats, StatsHistoryRange, StatsSnapshot,
+    TokenBalance, WidgetFourStats, WidgetStat,
+};
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -29,7 +71,7 @@ use std::collections::HashMap;
 pub type NodeList = HashMap<NodeId, NodeInstanceInfo>;
 
 /// List of nodes, stats and currently running batch.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct NodesInstancesInfo {
     /// Latest available version of the node binary
     pub latest_bin_version: Option<String>,
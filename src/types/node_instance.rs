@@ -130,6 +130,9 @@ pub struct NodeInstanceInfo {
     pub log_level: LogLevel,
     /// Current rewards earned by the node
     pub rewards: Option<U256>,
+    /// Rewards estimated to have been earned by the node, from the payments received by
+    /// its rewards address which correlate with increases of the rewards it reported
+    pub estimated_earnings: Option<U256>,
     /// Total number of records stored by the node
     pub records: Option<usize>,
     /// Number of relevant records for the node
@@ -138,6 +141,14 @@ pub struct NodeInstanceInfo {
     pub mem_used: Option<f64>,
     /// CPU usage percentage for the node (if active)
     pub cpu_usage: Option<f64>,
+    /// Bytes received by the node since it started, when its traffic can be told apart
+    pub net_rx_bytes: Option<u64>,
+    /// Bytes transmitted by the node since it started, when its traffic can be told apart
+    pub net_tx_bytes: Option<u64>,
+    /// Bytes received per second by the node (if active)
+    pub net_rx_rate: Option<f64>,
+    /// Bytes transmitted per second by the node (if active)
+    pub net_tx_rate: Option<f64>,
     /// Node disk usage in bytes
     pub disk_usage: Option<u64>,
     /// Number of peers currently connected to the node
@@ -228,6 +239,8 @@ impl NodeInstanceInfo {
         self.is_status_unknown = true;
         self.mem_used = None;
         self.cpu_usage = None;
+        self.net_rx_rate = None;
+        self.net_tx_rate = None;
         self.disk_usage = None;
         self.records = None;
         self.relevant_records = None;
@@ -0,0 +1,200 @@
+use super::NodeId;
+
+use serde::{Deserialize, Serialize};
+
+/// Kind of events which can be sent through the notification channels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum NotificationEventKind {
+    /// A node exited, failed to start, or recovered from any of those.
+    NodeStatus,
+    /// A batch of node actions finished with failures.
+    BatchFailed,
+    /// The AI agent reported an error.
+    AgentError,
+    /// An alert rule fired.
+    AlertFired,
+}
+
+impl NotificationEventKind {
+    pub const ALL: [Self; 4] = [
+        Self::NodeStatus,
+        Self::BatchFailed,
+        Self::AgentError,
+        Self::AlertFired,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::NodeStatus => "node_status",
+            Self::BatchFailed => "batch_failed",
+            Self::AgentError => "agent_error",
+            Self::AlertFired => "alert_fired",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::NodeStatus => "Node status changes",
+            Self::BatchFailed => "Batch failures",
+            Self::AgentError => "AI agent errors",
+            Self::AlertFired => "Alerts fired",
+        }
+    }
+}
+
+impl std::str::FromStr for NotificationEventKind {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|v| v.as_str() == s)
+            .ok_or_else(|| format!("Unknown notification event kind: {s}"))
+    }
+}
+
+/// Event to be sent through the notification channels subscribed to its kind.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct NotificationEvent {
+    pub kind: NotificationEventKind,
+    pub title: String,
+    pub message: String,
+    /// Node the event refers to, if any
+    pub node_id: Option<NodeId>,
+    /// UNIX timestamp (seconds) when the event occurred
+    pub timestamp: i64,
+}
+
+impl NotificationEvent {
+    pub fn new(kind: NotificationEventKind, title: &str, message: &str) -> Self {
+        Self {
+            kind,
+            title: title.to_string(),
+            message: message.to_string(),
+            node_id: None,
+            timestamp: chrono::Utc::now().timestamp(),
+        }
+    }
+
+    pub fn for_node(mut self, node_id: NodeId) -> Self {
+        self.node_id = Some(node_id);
+        self
+    }
+}
+
+/// Service a notification channel delivers the events to, with its connection details.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "type")]
+pub enum NotificationTarget {
+    /// Generic webhook which receives the event as a JSON object.
+    Webhook { url: String },
+    /// Email sent through an SMTP server.
+    Email {
+        smtp_host: String,
+        smtp_port: u16,
+        username: String,
+        password: String,
+        from: String,
+        to: String,
+    },
+    /// Message sent by a Telegram bot to a chat.
+    Telegram { bot_token: String, chat_id: String },
+    /// Message published on a ntfy topic, the URL includes the topic name.
+    Ntfy { url: String, token: String },
+    /// Message sent to a Gotify server using an application token.
+    Gotify { url: String, token: String },
+    /// Discord-compatible webhook.
+    Discord { url: String },
+    /// Slack-compatible incoming webhook.
+    Slack { url: String },
+}
+
+impl Default for NotificationTarget {
+    fn default() -> Self {
+        Self::Webhook { url: String::new() }
+    }
+}
+
+impl NotificationTarget {
+    pub fn kind_label(&self) -> &'static str {
+        match self {
+            Self::Webhook { .. } => "Webhook",
+            Self::Email { .. } => "Email",
+            Self::Telegram { .. } => "Telegram",
+            Self::Ntfy { .. } => "ntfy",
+            Self::Gotify { .. } => "Gotify",
+            Self::Discord { .. } => "Discord",
+            Self::Slack { .. } => "Slack",
+        }
+    }
+
+    /// Check the connection details are complete and well-formed.
+    pub fn validate(&self) -> Result<(), String> {
+        let check_url = |url: &str| {
+            url.parse::<url::Url>()
+                .map(|_| ())
+                .map_err(|err| format!("Invalid URL '{url}': {err}"))
+        };
+        let check_not_empty = |field: &str, value: &str| {
+            if value.trim().is_empty() {
+                Err(format!("{field} cannot be empty"))
+            } else {
+                Ok(())
+            }
+        };
+        match self {
+            Self::Webhook { url }
+            | Self::Ntfy { url, .. }
+            | Self::Discord { url }
+            | Self::Slack { url } => check_url(url),
+            Self::Gotify { url, token } => {
+                check_url(url)?;
+                check_not_empty("Gotify application token", token)
+            }
+            Self::Telegram { bot_token, chat_id } => {
+                check_not_empty("Telegram bot token", bot_token)?;
+                check_not_empty("Telegram chat ID", chat_id)
+            }
+            Self::Email {
+                smtp_host,
+                from,
+                to,
+                ..
+            } => {
+                check_not_empty("SMTP server", smtp_host)?;
+                check_not_empty("Sender address", from)?;
+                check_not_empty("Recipient address", to)
+            }
+        }
+    }
+
+    /// Short description of where the events are delivered to, without any secrets.
+    pub fn destination(&self) -> String {
+        match self {
+            Self::Webhook { url }
+            | Self::Ntfy { url, .. }
+            | Self::Gotify { url, .. }
+            | Self::Discord { url }
+            | Self::Slack { url } => url.clone(),
+            Self::Email { smtp_host, to, .. } => format!("{to} (via {smtp_host})"),
+            Self::Telegram { chat_id, .. } => format!("chat {chat_id}"),
+        }
+    }
+}
+
+/// User-defined channel which the selected kinds of events are sent through.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct NotificationChannel {
+    /// Unique identifier of the channel, 0 for a channel not stored yet
+    pub id: i64,
+    pub name: String,
+    pub target: NotificationTarget,
+    /// Kinds of events sent through this channel
+    pub events: Vec<NotificationEventKind>,
+    pub enabled: bool,
+}
+
+impl NotificationChannel {
+    pub fn is_subscribed_to(&self, kind: NotificationEventKind) -> bool {
+        self.enabled && self.events.contains(&kind)
+    }
+}
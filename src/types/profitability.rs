@@ -0,0 +1,104 @@
+use super::NodeId;
+
+use alloy_primitives::{U256, utils::format_units};
+use serde::{Deserialize, Serialize};
+
+/// Default length (in hours) of the period the profitability is calculated over.
+pub const DEFAULT_PROFITABILITY_PERIOD_HOURS: u32 = 720;
+
+/// Cost of running a node, or all the nodes of the host, compared with what they earned
+/// over a period. Costs and values are in the fiat currency set in the settings.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProfitabilityStats {
+    /// Node the stats are of, or None if they are of all nodes in the host
+    pub node_id: Option<NodeId>,
+    /// Number of hours the node/s were running within the period
+    pub hours_running: f64,
+    /// Average CPU usage percentage while running
+    pub avg_cpu_usage: Option<f64>,
+    /// Average memory used (in MB) while running
+    pub avg_mem_used_mb: Option<f64>,
+    /// Current disk usage in bytes
+    pub disk_usage: u64,
+    /// Current number of records stored
+    pub records: usize,
+    /// Estimated energy consumed, in kWh
+    pub energy_kwh: f64,
+    /// Cost of the energy consumed
+    pub energy_cost: f64,
+    /// Cost of the disk space used
+    pub disk_cost: f64,
+    /// Tokens earned within the period, in attos
+    pub earned: U256,
+    /// Value of the tokens earned on the day they were received,
+    /// None if the price of the token is not known
+    pub earned_value: Option<f64>,
+}
+
+impl ProfitabilityStats {
+    pub fn total_cost(&self) -> f64 {
+        self.energy_cost + self.disk_cost
+    }
+
+    /// Value earned minus the costs.
+    pub fn profit(&self) -> Option<f64> {
+        self.earned_value.map(|value| value - self.total_cost())
+    }
+
+    /// Return on investment, as a ratio of the profit over the costs.
+    pub fn roi(&self) -> Option<f64> {
+        let cost = self.total_cost();
+        (cost > 0.0).then_some(self.profit()? / cost)
+    }
+
+    /// Cost of storing each of the records currently held.
+    pub fn cost_per_record(&self) -> Option<f64> {
+        (self.records > 0).then(|| self.total_cost() / self.records as f64)
+    }
+
+    /// Token price at which what's earned pays for the costs.
+    pub fn break_even_price(&self) -> Option<f64> {
+        let tokens = format_units(self.earned, 18).ok()?.parse::<f64>().ok()?;
+        (tokens > 0.0).then(|| self.total_cost() / tokens)
+    }
+}
+
+/// Profitability of each node, and of all of them together, over a period.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Profitability {
+    /// Fiat currency costs and values are in
+    pub currency: String,
+    /// Length (in hours) of the period the profitability is calculated over
+    pub period_hours: u32,
+    /// Stats of all the nodes in the host
+    pub host: ProfitabilityStats,
+    /// Stats of each node, most profitable first
+    pub nodes: Vec<ProfitabilityStats>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profitability_ratios() {
+        let stats = ProfitabilityStats {
+            records: 200,
+            energy_cost: 1.5,
+            disk_cost: 0.5,
+            earned: U256::from(4_000_000_000_000_000_000u128),
+            earned_value: Some(3.0),
+            ..Default::default()
+        };
+        assert_eq!(stats.total_cost(), 2.0);
+        assert_eq!(stats.profit(), Some(1.0));
+        assert_eq!(stats.roi(), Some(0.5));
+        assert_eq!(stats.cost_per_record(), Some(0.01));
+        assert_eq!(stats.break_even_price(), Some(0.5));
+
+        let stats = ProfitabilityStats::default();
+        assert_eq!(stats.roi(), None);
+        assert_eq!(stats.cost_per_record(), None);
+        assert_eq!(stats.break_even_price(), None);
+    }
+}
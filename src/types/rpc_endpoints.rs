@@ -0,0 +1,65 @@
+use super::mask_url;
+
+use serde::{Deserialize, Serialize};
+
+/// Health of one of the L2 network RPC endpoints, as last seen by the backend.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RpcEndpointHealth {
+    pub url: String,
+    /// Position in the list of endpoints, 0 being the one with the highest priority
+    pub priority: usize,
+    /// Whether the last request sent to the endpoint succeeded, None if none was sent yet
+    pub healthy: Option<bool>,
+    /// Whether it's the endpoint queries are currently sent to
+    pub active: bool,
+    /// Time (in millis) the last health check took to be responded
+    pub latency_millis: Option<u64>,
+    /// Latest block number reported by the endpoint on the last health check
+    pub block_number: Option<u64>,
+    /// Error returned by the last failed request
+    pub last_error: Option<String>,
+    /// UNIX timestamp (seconds) of the last request sent to the endpoint
+    pub last_checked: Option<i64>,
+    /// UNIX timestamp (seconds) until which it's not used since it rate-limited us
+    pub backoff_until: Option<i64>,
+}
+
+impl RpcEndpointHealth {
+    /// Mask the credentials embedded in the endpoint's URL, also where it's quoted by the last error.
+    pub fn redact_url(&mut self) {
+        let masked = mask_url(&self.url);
+        if let Some(err) = self.last_error.as_mut() {
+            *err = err.replace(&self.url, &masked);
+        }
+        self.url = masked;
+    }
+}
+
+/// Parse a list of RPC endpoints URLs separated by commas and/or whitespaces.
+pub fn parse_rpc_urls(s: &str) -> Vec<String> {
+    s.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|url| !url.is_empty())
+        .map(|url| url.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_url() {
+        let url = "https://arb-mainnet.g.alchemy.com/v2/alchemy-key";
+        let mut health = RpcEndpointHealth {
+            url: url.to_string(),
+            last_error: Some(format!("error sending request for url ({url})")),
+            ..Default::default()
+        };
+        health.redact_url();
+        assert_eq!(health.url, "https://arb-mainnet.g.alchemy.com/***");
+        assert_eq!(
+            health.last_error.as_deref(),
+            Some("error sending request for url (https://arb-mainnet.g.alchemy.com/***)")
+        );
+    }
+}
@@ -12,6 +12,7 @@ pub enum NodeSortField {
     BinaryVersion,
     PortNumber,
     Rewards,
+    EstimatedEarnings,
     ShunnedCount,
     NumRecords,
     NumConnPeers,
@@ -29,6 +30,7 @@ impl fmt::Display for NodeSortField {
             NodeSortField::BinaryVersion => "Version",
             NodeSortField::PortNumber => "Port number",
             NodeSortField::Rewards => "Rewards count",
+            NodeSortField::EstimatedEarnings => "Estimated earnings",
             NodeSortField::ShunnedCount => "Shunned count",
             NodeSortField::NumRecords => "Number of records",
             NodeSortField::NumConnPeers => "Connected peers",
@@ -49,6 +51,7 @@ impl NodeSortField {
             Self::BinaryVersion,
             Self::PortNumber,
             Self::Rewards,
+            Self::EstimatedEarnings,
             Self::ShunnedCount,
             Self::NumRecords,
             Self::NumConnPeers,
@@ -97,6 +100,8 @@ impl NodesSortStrategy {
             Self::new(NodeSortField::PortNumber, false),
             Self::new(NodeSortField::Rewards, true),
             Self::new(NodeSortField::Rewards, false),
+            Self::new(NodeSortField::EstimatedEarnings, true),
+            Self::new(NodeSortField::EstimatedEarnings, false),
             Self::new(NodeSortField::ShunnedCount, true),
             Self::new(NodeSortField::ShunnedCount, false),
             Self::new(NodeSortField::NumRecords, true),
@@ -126,6 +131,8 @@ impl NodesSortStrategy {
             "port-desc" => Self::new(NodeSortField::PortNumber, true),
             "rewards" => Self::new(NodeSortField::Rewards, false),
             "rewards-desc" => Self::new(NodeSortField::Rewards, true),
+            "earnings" => Self::new(NodeSortField::EstimatedEarnings, false),
+            "earnings-desc" => Self::new(NodeSortField::EstimatedEarnings, true),
             "shunned" => Self::new(NodeSortField::ShunnedCount, false),
             "shunned-desc" => Self::new(NodeSortField::ShunnedCount, true),
             "records" => Self::new(NodeSortField::NumRecords, false),
@@ -157,6 +164,8 @@ impl NodesSortStrategy {
             (NodeSortField::PortNumber, true) => "port-desc",
             (NodeSortField::Rewards, false) => "rewards",
             (NodeSortField::Rewards, true) => "rewards-desc",
+            (NodeSortField::EstimatedEarnings, false) => "earnings",
+            (NodeSortField::EstimatedEarnings, true) => "earnings-desc",
             (NodeSortField::ShunnedCount, false) => "shunned",
             (NodeSortField::ShunnedCount, true) => "shunned-desc",
             (NodeSortField::NumRecords, false) => "records",
@@ -205,6 +214,12 @@ impl NodesSortStrategy {
             (NodeSortField::PortNumber, true) => b.port.cmp(&a.port),
             (NodeSortField::Rewards, false) => a.rewards.cmp(&b.rewards),
             (NodeSortField::Rewards, true) => b.rewards.cmp(&a.rewards),
+            (NodeSortField::EstimatedEarnings, false) => {
+                a.estimated_earnings.cmp(&b.estimated_earnings)
+            }
+            (NodeSortField::EstimatedEarnings, true) => {
+                b.estimated_earnings.cmp(&a.estimated_earnings)
+            }
             (NodeSortField::ShunnedCount, false) => a.shunned_count.cmp(&b.shunned_count),
             (NodeSortField::ShunnedCount, true) => b.shunned_count.cmp(&a.shunned_count),
             (NodeSortField::NumConnPeers, false) => a.connected_peers.cmp(&b.connected_peers),
@@ -1,18 +1,25 @@
+use super::{HostStats, MonthlyTraffic};
+
 use alloy_primitives::U256;
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
 use serde::{Deserialize, Serialize};
 
 /// Node stats collected by the backend and retrievable through the public server API.
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct Stats {
     /// Total balance across all nodes
     pub total_balance: U256,
     /// Balances of the addresses assigned to nodes for rewards
     pub balances: Vec<(String, U256)>,
+    /// Balances of the native gas token and additional ERC-20 tokens held by the rewards addresses
+    #[serde(default)]
+    pub other_balances: Vec<(String, OtherBalances)>,
     /// Earnings analytics for rewards addresses
     pub earnings: Vec<(String, EarningsStats)>,
-    /// Whether earnings history is still being fetched from the chain (not fully synced yet)
+    /// Percentage of the earnings history fetched from the chain so far,
+    /// or None if it's fully synced
     #[serde(default)]
-    pub earnings_syncing: bool,
+    pub earnings_sync_progress: Option<u8>,
     /// Total number of node instances
     pub total_nodes: usize,
     /// Number of currently active nodes
@@ -35,6 +42,98 @@ pub struct Stats {
     pub available_disk_space: u64,
     /// Total disk size/space in bytes used by all nodes
     pub used_disk_space: u64,
+    /// Time (in millis) it took to complete the last nodes metrics polling cycle
+    pub metrics_polling_cycle_millis: u64,
+    /// Latest resources usage sampled from the host
+    #[serde(default)]
+    pub host: Option<HostStats>,
+    /// Traffic of the host's network interfaces during the current month
+    #[serde(default)]
+    pub monthly_traffic: Option<MonthlyTraffic>,
+}
+
+/// Balances held by a rewards address other than the rewards token's.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct OtherBalances {
+    /// Balance of the network's native gas token (ETH), None if it couldn't be retrieved
+    pub eth: Option<U256>,
+    /// Balances of the additional ERC-20 tokens configured in the settings
+    pub tokens: Vec<TokenBalance>,
+}
+
+/// Balance of an ERC-20 token held by an address.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TokenBalance {
+    /// Address of the token contract
+    pub contract: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub balance: U256,
+}
+
+/// Snapshot of the aggregated stats, averaged over the bucket starting at its timestamp.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct StatsSnapshot {
+    /// Timestamp (in millis) of the snapshot
+    pub timestamp: i64,
+    /// Total number of node instances
+    pub total_nodes: f64,
+    /// Number of active nodes
+    pub active_nodes: f64,
+    /// Total number of peers connected across all nodes
+    pub connected_peers: f64,
+    /// Total number of peers which have shunned nodes
+    pub shunned_count: f64,
+    /// Estimated network size
+    pub estimated_net_size: f64,
+    /// Total number of records stored across all nodes
+    pub stored_records: f64,
+    /// Total number of relevant records stored across all nodes
+    pub relevant_records: f64,
+    /// Disk space in bytes used by all nodes
+    pub used_disk_space: f64,
+    /// Available disk space in bytes
+    pub available_disk_space: f64,
+}
+
+/// Time range of the stats history to retrieve.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StatsHistoryRange {
+    #[default]
+    Day,
+    Week,
+    Month,
+}
+
+impl StatsHistoryRange {
+    pub const ALL: [Self; 3] = [Self::Day, Self::Week, Self::Month];
+
+    /// Length of the range in millis.
+    pub fn span_millis(&self) -> i64 {
+        match self {
+            Self::Day => 24 * 3_600_000,
+            Self::Week => 7 * 24 * 3_600_000,
+            Self::Month => 30 * 24 * 3_600_000,
+        }
+    }
+
+    /// Size in millis of the buckets the snapshots are averaged into,
+    /// so the number of points returned is about the same for any range.
+    pub fn bucket_millis(&self) -> i64 {
+        match self {
+            Self::Day => 5 * 60_000,
+            Self::Week => 30 * 60_000,
+            Self::Month => 2 * 3_600_000,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Day => "24 Hours",
+            Self::Week => "Week",
+            Self::Month => "Month",
+        }
+    }
 }
 
 /// Node stats formatted for UmbrelOS widgets.
@@ -55,7 +154,7 @@ pub struct WidgetStat {
 }
 
 /// Detailed statistics for a single earnings period
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct PeriodStats {
     /// Period label (e.g., "24 Hours", "72 Hours")
     pub label: String,
@@ -79,42 +178,143 @@ pub struct PeriodStats {
     pub largest_payment: U256,
 }
 
-/// Aggregated earnings statistics for all periods
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Default length (in hours) of the periods earnings are analysed over:
+/// last 48 hours, last week, last month and last 3 months.
+pub const DEFAULT_EARNINGS_PERIODS_HOURS: [u32; 4] = [48, 168, 720, 2160];
+
+impl PeriodStats {
+    /// Empty stats for a period of the given length, labelled after it, e.g. "Last 2 Weeks".
+    pub fn new(length_hours: u32) -> Self {
+        Self {
+            label: period_label(length_hours),
+            length_hours,
+            ..Self::default()
+        }
+    }
+}
+
+// Human readable label of a period, using the largest unit it's a multiple of.
+fn period_label(hours: u32) -> String {
+    let (num, unit) = if hours >= 720 && hours.is_multiple_of(720) {
+        (hours / 720, "Month")
+    } else if hours >= 168 && hours.is_multiple_of(168) {
+        (hours / 168, "Week")
+    } else if hours > 48 && hours.is_multiple_of(24) {
+        (hours / 24, "Day")
+    } else {
+        (hours, "Hour")
+    };
+
+    match (num, unit) {
+        (1, "Hour") => "Last Hour".to_string(),
+        (1, _) => format!("Last {unit}"),
+        _ => format!("Last {num} {unit}s"),
+    }
+}
+
+/// Aggregated earnings statistics for each of the periods analysed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct EarningsStats {
-    pub period_1: PeriodStats,
-    pub period_2: PeriodStats,
-    pub period_3: PeriodStats,
-    pub period_4: PeriodStats,
+    /// Stats of each period, sorted from the shortest to the longest one
+    pub periods: Vec<PeriodStats>,
+}
+
+impl EarningsStats {
+    /// Empty stats for periods of the given lengths (in hours).
+    pub fn new(periods_hours: &[u32]) -> Self {
+        let mut periods_hours = periods_hours.to_vec();
+        periods_hours.sort_unstable();
+        periods_hours.dedup();
+        Self {
+            periods: periods_hours.into_iter().map(PeriodStats::new).collect(),
+        }
+    }
 }
 
 impl Default for EarningsStats {
     fn default() -> Self {
-        Self {
-            // Statistics for the last 48 hours
-            period_1: PeriodStats {
-                label: "Last 48 Hours".to_string(),
-                length_hours: 48,
-                ..PeriodStats::default()
-            },
-            // Statistics for the last week
-            period_2: PeriodStats {
-                label: "Last Week".to_string(),
-                length_hours: 168,
-                ..PeriodStats::default()
-            },
-            // Statistics for the last month
-            period_3: PeriodStats {
-                label: "Last Month".to_string(),
-                length_hours: 720,
-                ..PeriodStats::default()
-            },
-            // Statistics for the last 3 months
-            period_4: PeriodStats {
-                label: "Last 3 Months".to_string(),
-                length_hours: 2160,
-                ..PeriodStats::default()
-            },
+        Self::new(&DEFAULT_EARNINGS_PERIODS_HOURS)
+    }
+}
+
+/// Size of the buckets reward payments are grouped into when charting earnings over time.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EarningsBucket {
+    #[default]
+    Day,
+    Week,
+    Month,
+}
+
+impl EarningsBucket {
+    pub const ALL: [Self; 3] = [Self::Day, Self::Week, Self::Month];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Day => "Daily",
+            Self::Week => "Weekly",
+            Self::Month => "Monthly",
         }
     }
+
+    /// Number of buckets charted, up to the current one, which all fit
+    /// within the history of earnings retrieved from the L2 network.
+    pub fn num_buckets(&self) -> u32 {
+        match self {
+            Self::Day => 30,
+            Self::Week => 26,
+            Self::Month => 7,
+        }
+    }
+
+    /// First day of the bucket the given day belongs to.
+    pub fn bucket_start(&self, day: NaiveDate) -> NaiveDate {
+        match self {
+            Self::Day => day,
+            Self::Week => day.week(Weekday::Mon).first_day(),
+            Self::Month => day.with_day(1).unwrap_or(day),
+        }
+    }
+
+    /// First day of the bucket following the one starting on the given day.
+    pub fn next_bucket_start(&self, start: NaiveDate) -> NaiveDate {
+        match self {
+            Self::Day => start + Days::new(1),
+            Self::Week => start + Days::new(7),
+            Self::Month => start + Months::new(1),
+        }
+    }
+
+    /// First day of the oldest bucket charted when the current day is the given one.
+    pub fn first_bucket_start(&self, today: NaiveDate) -> NaiveDate {
+        let current = self.bucket_start(today);
+        let back = self.num_buckets() - 1;
+        match self {
+            Self::Day => current - Days::new(back.into()),
+            Self::Week => current - Days::new(7 * u64::from(back)),
+            Self::Month => current - Months::new(back),
+        }
+    }
+}
+
+/// Reward payments received within a bucket of time.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EarningsBucketStats {
+    /// First day (UTC) of the bucket
+    pub start: NaiveDate,
+    /// Total earned within the bucket
+    pub earned: U256,
+    /// Number of payments received within the bucket
+    pub num_payments: usize,
+    /// Total earned since the first bucket charted, up to and including this one
+    pub cumulative: U256,
+}
+
+/// Earnings over time of a rewards address, or of all of them.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EarningsHistory {
+    /// Rewards address, or None for the total of all addresses
+    pub address: Option<String>,
+    /// Buckets sorted from the oldest to the current one, including those without payments
+    pub buckets: Vec<EarningsBucketStats>,
 }
@@ -1,8 +1,14 @@
-use super::{helpers::show_error_alert_msg, icons::IconCancel};
+use super::{
+    helpers::{format_timestamp, show_error_alert_msg, value_or_dash},
+    icons::{IconCancel, IconChevronDown, IconPause, IconRetry, IconStartNode},
+};
 use crate::{
     app::ClientGlobalState,
-    server_api::cancel_batch,
-    types::{BatchStatus, BatchType, NodesActionsBatch},
+    server_api::{
+        cancel_batch, nodes_actions_batches_history, pause_batch, resume_batch, retry_batch,
+        set_batch_interval,
+    },
+    types::{BatchStatus, BatchType, NodesActionsBatch, Permission},
 };
 
 use leptos::{logging, prelude::*, task::spawn_local};
@@ -30,22 +36,23 @@ fn ActionBatchViewNew(batch_info: RwSignal<NodesActionsBatch>) -> impl IntoView
         };
         (batch_type, action_duration)
     };
-    let (count, auto_start) = if let BatchType::Create { count, node_opts } = &batch_type {
+    let (count, auto_start) = if let BatchType::Create {
+        count, node_opts, ..
+    } = &batch_type
+    {
         (*count, node_opts.auto_start)
     } else {
         (batch_type.ids().len() as u16, false)
     };
-    let finished = move || match &batch_info.read().status {
-        BatchStatus::Scheduled => 0,
-        BatchStatus::InProgress => batch_info.read().complete,
-        BatchStatus::InProgressWithFailures(c, _) => *c + batch_info.read().complete,
-        BatchStatus::Failed(_) => count,
-    };
+    let can_operate = move || context.can(Permission::OperateNodes);
+    let retry_permission = retry_permission(&batch_type);
+    let can_retry = move || context.can(retry_permission);
+    let finished = move || batch_info.read().items.len() as u16;
     let progress = move || (finished() * 100).checked_div(count).unwrap_or_default();
     let time_remaining = move || {
         if count > 0 {
-            let remaining = (count - finished()) as u64
-                * (batch_info.get_untracked().interval_secs + action_duration);
+            let remaining =
+                (count - finished()) as u64 * (batch_info.read().interval_secs + action_duration);
             let minutes = remaining / 60;
             let seconds = remaining % 60;
             (minutes, seconds)
@@ -54,18 +61,72 @@ fn ActionBatchViewNew(batch_info: RwSignal<NodesActionsBatch>) -> impl IntoView
         }
     };
     let is_failed = Memo::new(move |_| match &batch_info.read().status {
-        BatchStatus::Failed(reason) => Some((
-            reason.clone(),
-            count - batch_info.read().complete,
-            "Batch failed".to_string(),
-        )),
-        BatchStatus::InProgressWithFailures(c, reason) => Some((
-            reason.clone(),
-            *c,
-            "Batch in progress with failures".to_string(),
-        )),
+        BatchStatus::Failed(_) => Some("Batch failed".to_string()),
+        BatchStatus::InProgressWithFailures(_, _) => {
+            Some("Batch in progress with failures".to_string())
+        }
         _ => None,
     });
+    // errors of each failed action, along with the node it was performed on, if known
+    let failed_actions = Memo::new(move |_| {
+        batch_info
+            .read()
+            .items
+            .iter()
+            .filter_map(|item| {
+                item.error.clone().map(|err| {
+                    let node = item
+                        .node_id
+                        .as_ref()
+                        .map(|id| id.short_node_id())
+                        .unwrap_or_else(|| "-".to_string());
+                    (node, err)
+                })
+            })
+            .collect::<Vec<_>>()
+    });
+    let interval_input = RwSignal::new(batch_info.get_untracked().interval_secs.to_string());
+
+    let on_error = |action: &str, err: ServerFnError| {
+        let msg = format!("Failed to {action} node action batch: {err:?}");
+        logging::log!("{msg}");
+        show_error_alert_msg(msg);
+    };
+    let toggle_pause = move |_| {
+        let is_paused = batch_info.read_untracked().status == BatchStatus::Paused;
+        spawn_local(async move {
+            let res = if is_paused {
+                resume_batch(batch_id).await
+            } else {
+                pause_batch(batch_id).await
+            };
+            if let Err(err) = res {
+                on_error(if is_paused { "resume" } else { "pause" }, err);
+            }
+        })
+    };
+    let retry = move |_| {
+        spawn_local(async move {
+            match retry_batch(batch_id).await {
+                Ok(_) => context
+                    .scheduled_batches
+                    .update(|batches| batches.retain(|b| b.read_untracked().id != batch_id)),
+                Err(err) => on_error("retry", err),
+            }
+        })
+    };
+    let set_interval = move |_| {
+        let Ok(interval_secs) = interval_input.get_untracked().parse::<u64>() else {
+            show_error_alert_msg("Delay must be a number of seconds".to_string());
+            return;
+        };
+        spawn_local(async move {
+            match set_batch_interval(batch_id, interval_secs).await {
+                Ok(()) => batch_info.update(|b| b.interval_secs = interval_secs),
+                Err(err) => on_error("update", err),
+            }
+        })
+    };
 
     view! {
         <div class=move || {
@@ -81,50 +142,102 @@ fn ActionBatchViewNew(batch_info: RwSignal<NodesActionsBatch>) -> impl IntoView
             <div class="flex items-start justify-between">
                 <h4 class="text-base font-bold text-white">
                     {move || {
-                        if let Some((_, _, failure_status)) = is_failed.get() {
+                        if let Some(failure_status) = is_failed.get() {
                             failure_status
                         } else {
                             batch_info.read().status.to_string()
                         }
                     }} ":"
                 </h4>
-                <button
-                    title=move || {
-                        if is_failed.read().is_none() { "Cancel batch" } else { "Dismiss" }
-                    }
-                    class="p-1 text-slate-500 hover:text-white transition-colors"
-                    on:click=move |_| spawn_local({
-                        context
-                            .scheduled_batches
-                            .update(|batches| {
-                                batches.retain(|b| { b.read_untracked().id != batch_id })
-                            });
-                        async move {
-                            if let Err(err) = cancel_batch(batch_id).await {
-                                let msg = format!("Failed to cancel node action batch: {err:?}");
-                                logging::log!("{msg}");
-                                show_error_alert_msg(msg);
+                <div class="flex items-center gap-1">
+                    <Show when=move || {
+                        let status = &batch_info.read().status;
+                        can_operate() && (status.is_running() || *status == BatchStatus::Paused)
+                    }>
+                        <button
+                            title=move || {
+                                if batch_info.read().status == BatchStatus::Paused {
+                                    "Resume batch"
+                                } else {
+                                    "Pause batch"
+                                }
+                            }
+                            class="p-1 text-slate-500 hover:text-white transition-colors"
+                            on:click=toggle_pause
+                        >
+                            <Show
+                                when=move || batch_info.read().status == BatchStatus::Paused
+                                fallback=|| view! { <IconPause /> }
+                            >
+                                <IconStartNode />
+                            </Show>
+                        </button>
+                    </Show>
+                    <Show when=move || {
+                        can_retry() && batch_info.read().status.is_finished()
+                            && !failed_actions.read().is_empty()
+                    }>
+                        <button
+                            title="Retry failed actions"
+                            class="p-1 text-slate-500 hover:text-white transition-colors"
+                            on:click=retry
+                        >
+                            <IconRetry />
+                        </button>
+                    </Show>
+                    <button
+                        hidden=move || !can_operate()
+                        title=move || {
+                            if batch_info.read().status.is_finished() {
+                                "Dismiss"
+                            } else {
+                                "Cancel batch"
                             }
                         }
-                    })
-                >
-                    <IconCancel />
-                </button>
+                        class="p-1 text-slate-500 hover:text-white transition-colors"
+                        on:click=move |_| spawn_local({
+                            context
+                                .scheduled_batches
+                                .update(|batches| {
+                                    batches.retain(|b| { b.read_untracked().id != batch_id })
+                                });
+                            async move {
+                                if let Err(err) = cancel_batch(batch_id).await {
+                                    on_error("cancel", err);
+                                }
+                            }
+                        })
+                    >
+                        <IconCancel />
+                    </button>
+                </div>
             </div>
 
-            <Show when=move || is_failed.read().is_some()>
+            <Show when=move || !failed_actions.read().is_empty()>
                 <div class="bg-rose-500/10 border border-rose-500/20 rounded-xl p-4 animate-in zoom-in-95">
                     <span class="text-[10px] font-bold text-rose-400 uppercase tracking-widest block mb-2">
-                        Last Error
+                        Failed Actions
                     </span>
-                    <p class="text-sm text-rose-100 font-mono leading-relaxed">
+                    <ul class="max-h-32 overflow-y-auto space-y-1 text-sm text-rose-100 font-mono leading-relaxed">
                         {move || {
-                            is_failed.get().map(|(reason, _, _)| reason.clone()).unwrap_or_default()
+                            failed_actions
+                                .get()
+                                .into_iter()
+                                .map(|(node, err)| {
+                                    view! {
+                                        <li>
+                                            <span class="text-rose-300">{node}</span>
+                                            ": "
+                                            {err}
+                                        </li>
+                                    }
+                                })
+                                .collect_view()
                         }}
-                    </p>
+                    </ul>
                     <div class="mt-3 text-xs text-rose-300 flex items-center gap-2">
                         <span class="w-1.5 h-1.5 rounded-full bg-rose-500" />
-                        {move || is_failed.get().map(|(_, count, _)| count).unwrap_or_default()}
+                        {move || failed_actions.read().len()}
                         " failures."
                     </div>
                 </div>
@@ -138,8 +251,29 @@ fn ActionBatchViewNew(batch_info: RwSignal<NodesActionsBatch>) -> impl IntoView
                     </span> ": " {count}
                 </li>
                 <li>
-                    "Delay between each node action: " {batch_info.get_untracked().interval_secs}
-                    " secs."
+                    "Delay between each node action: "
+                    <Show
+                        when=move || can_operate() && !batch_info.read().status.is_finished()
+                        fallback=move || {
+                            view! { {move || batch_info.read().interval_secs} " secs." }
+                        }
+                    >
+                        <input
+                            type="number"
+                            min="0"
+                            class="w-20 mx-1 px-2 py-0.5 bg-slate-800 border border-slate-700 rounded-lg text-white text-sm"
+                            prop:value=move || interval_input.get()
+                            on:input=move |ev| interval_input.set(event_target_value(&ev))
+                        />
+                        " secs. "
+                        <button
+                            title="Apply the new delay to the remaining node actions"
+                            class="text-xs font-bold text-indigo-400 hover:text-indigo-300"
+                            on:click=set_interval
+                        >
+                            Apply
+                        </button>
+                    </Show>
                 </li>
                 <Show when=move || matches!(batch_type, BatchType::Create { .. })>
                     <li>
@@ -180,3 +314,216 @@ fn ActionBatchViewNew(batch_info: RwSignal<NodesActionsBatch>) -> impl IntoView
         </div>
     }
 }
+
+#[component]
+pub(super) fn BatchesHistoryView() -> impl IntoView {
+    let context = expect_context::<ClientGlobalState>();
+    // retrieve the history again whenever a batch is added or removed from the running list
+    let history = Resource::new(
+        move || context.scheduled_batches.read().len(),
+        |_| nodes_actions_batches_history(),
+    );
+
+    view! {
+        <div class="p-4 lg:p-8 max-w-6xl mx-auto space-y-8 animate-in fade-in slide-in-from-bottom-4 duration-500">
+            <section class="space-y-4">
+                <h3 class="text-lg font-bold">Running & Scheduled Batches</h3>
+                <Show
+                    when=move || !context.scheduled_batches.read().is_empty()
+                    fallback=move || {
+                        view! { <p class="text-slate-400">"No batches running or scheduled"</p> }
+                    }
+                >
+                    <div class="grid grid-cols-1 md:grid-cols-2 gap-4">
+                        <NodesActionsBatchesView />
+                    </div>
+                </Show>
+            </section>
+
+            <section class="bg-slate-900 border border-slate-800 rounded-3xl overflow-hidden shadow-xl">
+                <header class="p-6 border-b border-slate-800">
+                    <h3 class="text-lg font-bold">Batches History</h3>
+                    <p class="text-xs text-slate-500 mt-1">
+                        "Batches which completed, failed or were cancelled, with the outcome of the action on each node."
+                    </p>
+                </header>
+                <Transition fallback=move || {
+                    view! { <p class="p-6 text-slate-400">"Retrieving batches history..."</p> }
+                }>
+                    {move || {
+                        history
+                            .get()
+                            .map(|res| match res {
+                                Ok(list) if list.is_empty() => {
+                                    view! {
+                                        <p class="p-6 text-center text-slate-400">
+                                            "No batches have finished yet"
+                                        </p>
+                                    }
+                                        .into_any()
+                                }
+                                Ok(list) => {
+                                    view! {
+                                        <div class="overflow-x-auto">
+                                            <table class="w-full text-left text-sm">
+                                                <thead>
+                                                    <tr class="bg-slate-800/50 text-slate-400 text-xs uppercase tracking-wider">
+                                                        <th class="px-6 py-3 font-semibold">Batch Id</th>
+                                                        <th class="px-6 py-3 font-semibold">Action</th>
+                                                        <th class="px-6 py-3 font-semibold">Status</th>
+                                                        <th class="px-6 py-3 font-semibold">Started</th>
+                                                        <th class="px-6 py-3 font-semibold">Finished</th>
+                                                        <th class="px-6 py-3 font-semibold">Succeeded</th>
+                                                        <th class="px-6 py-3 font-semibold"></th>
+                                                    </tr>
+                                                </thead>
+                                                <tbody class="divide-y divide-slate-800">
+                                                    {list
+                                                        .into_iter()
+                                                        .map(|batch| view! { <BatchHistoryRow batch /> })
+                                                        .collect_view()}
+                                                </tbody>
+                                            </table>
+                                        </div>
+                                    }
+                                        .into_any()
+                                }
+                                Err(err) => {
+                                    view! {
+                                        <p class="p-6 text-rose-400">
+                                            {format!("Failed to retrieve batches history: {err}")}
+                                        </p>
+                                    }
+                                        .into_any()
+                                }
+                            })
+                    }}
+                </Transition>
+            </section>
+        </div>
+    }
+}
+
+#[component]
+fn BatchHistoryRow(batch: NodesActionsBatch) -> impl IntoView {
+    let context = expect_context::<ClientGlobalState>();
+    let expanded = RwSignal::new(false);
+    let status_color = match batch.status {
+        BatchStatus::Completed => "text-emerald-400",
+        BatchStatus::Cancelled => "text-amber-400",
+        _ => "text-rose-400",
+    };
+    let status = match batch.status {
+        BatchStatus::Failed(_) => "Failed".to_string(),
+        ref other => other.to_string(),
+    };
+    let last_error = match batch.status {
+        BatchStatus::Failed(ref err) => Some(err.clone()),
+        _ => None,
+    };
+    let items = StoredValue::new(batch.items.clone());
+    let batch_id = batch.id;
+    let has_failures = batch.failures() > 0;
+    let can_retry = context.can(retry_permission(&batch.batch_type));
+
+    view! {
+        <tr class="hover:bg-slate-800/30">
+            <td class="px-6 py-4 font-mono text-slate-300">{batch.id}</td>
+            <td class="px-6 py-4 font-bold uppercase text-indigo-400">
+                {batch.batch_type.to_string()}
+            </td>
+            <td class=format!("px-6 py-4 font-medium {status_color}") title=last_error>
+                {status}
+            </td>
+            <td class="px-6 py-4 text-slate-400">
+                {value_or_dash(batch.started_at.map(format_timestamp))}
+            </td>
+            <td class="px-6 py-4 text-slate-400">
+                {value_or_dash(batch.finished_at.map(format_timestamp))}
+            </td>
+            <td class="px-6 py-4 text-slate-300">
+                {batch.complete} " / " {batch.total_actions()}
+            </td>
+            <td class="px-6 py-4 text-right whitespace-nowrap">
+                <Show when=move || has_failures && can_retry>
+                    <button
+                        type="button"
+                        title="Retry failed actions"
+                        class="p-1 text-slate-500 hover:text-white"
+                        on:click=move |_| {
+                            spawn_local(async move {
+                                if let Err(err) = retry_batch(batch_id).await {
+                                    show_error_alert_msg(
+                                        format!("Failed to retry node action batch: {err:?}"),
+                                    );
+                                }
+                            })
+                        }
+                    >
+                        <IconRetry />
+                    </button>
+                </Show>
+                <button
+                    type="button"
+                    title="Show the outcome of each action"
+                    class="p-1 text-slate-500 hover:text-white"
+                    on:click=move |_| expanded.update(|e| *e = !*e)
+                >
+                    <IconChevronDown is_down=expanded.into() />
+                </button>
+            </td>
+        </tr>
+        <Show when=move || expanded.get()>
+            <tr class="bg-slate-950/40">
+                <td colspan="7" class="px-6 py-3">
+                    <Show
+                        when=move || !items.read_value().is_empty()
+                        fallback=move || {
+                            view! { <p class="text-xs text-slate-500">"No actions were performed"</p> }
+                        }
+                    >
+                        <ul class="space-y-1 text-xs font-mono">
+                            {items
+                                .get_value()
+                                .into_iter()
+                                .map(|item| {
+                                    let node = item
+                                        .node_id
+                                        .map(|id| id.short_node_id())
+                                        .unwrap_or_else(|| "-".to_string());
+                                    view! {
+                                        <li class="flex gap-4">
+                                            <span class="text-slate-500">
+                                                {format_timestamp(item.timestamp)}
+                                            </span>
+                                            <span class="text-slate-300">{node}</span>
+                                            {match item.error {
+                                                None => {
+                                                    view! { <span class="text-emerald-400">"OK"</span> }
+                                                        .into_any()
+                                                }
+                                                Some(err) => {
+                                                    view! { <span class="text-rose-400">{err}</span> }
+                                                        .into_any()
+                                                }
+                                            }}
+                                        </li>
+                                    }
+                                })
+                                .collect_view()}
+                        </ul>
+                    </Show>
+                </td>
+            </tr>
+        </Show>
+    }
+}
+
+// Permission required to retry the failed actions of a batch, which depends on its type
+fn retry_permission(batch_type: &BatchType) -> Permission {
+    if matches!(batch_type, BatchType::Remove(_)) {
+        Permission::RemoveNodes
+    } else {
+        Permission::OperateNodes
+    }
+}
@@ -1,4 +1,7 @@
-use crate::types::{LogLevel, NodeOpts};
+use crate::{
+    server_api::parse_and_validate_addr,
+    types::{LogLevel, NodeOpts, RewardsAddrWeight, rewards_addrs_distribution},
+};
 
 use super::{
     form_inputs::{
@@ -6,7 +9,8 @@ use super::{
         RewardsAddrInput, TextInput,
     },
     helpers::{add_node_instances, show_error_alert_msg},
-    icons::IconCancel,
+    icons::{IconCancel, IconRemove},
+    rewards_addresses::AddressLabel,
 };
 
 use leptos::{logging, prelude::*};
@@ -61,19 +65,63 @@ fn AddNodeTabs(is_open: RwSignal<bool>, active_tab: RwSignal<u8>) -> impl IntoVi
     let interval = RwSignal::new(Ok(60));
     let data_dir_path = RwSignal::new(PathBuf::default());
 
-    let add_node = Action::new(move |(node_opts, count, interval): &(NodeOpts, u16, u64)| {
-        let node_opts = node_opts.clone();
-        let count = *count;
-        let interval = *interval;
-        async move {
-            if let Err(err) = add_node_instances(node_opts, count, interval).await {
-                let msg = format!("Failed to create node/s: {err}");
-                logging::error!("[ERROR] {msg}");
-                show_error_alert_msg(msg);
-            }
+    let distribute = RwSignal::new(false);
+    let weighted_addrs = RwSignal::new(Vec::<WeightedAddrRow>::new());
+    // rewards addresses are distributed only when creating a batch of nodes
+    let is_distributing = move || distribute.get() && count.get().is_ok_and(|c| c > 1);
+    let rewards_addrs = move || {
+        weighted_addrs
+            .read()
+            .iter()
+            .map(|row| {
+                let addr = row.address.get();
+                RewardsAddrWeight {
+                    address: addr.strip_prefix("0x").unwrap_or(&addr).to_string(),
+                    weight: row.weight.get(),
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+    let rewards_addrs_valid = move || {
+        let addrs = rewards_addrs();
+        addrs.iter().any(|a| a.weight > 0)
+            && addrs
+                .iter()
+                .all(|a| parse_and_validate_addr(&a.address).is_ok())
+    };
+    Effect::new(move |_| {
+        // start the list of addresses with the one already entered
+        if distribute.get() && weighted_addrs.read_untracked().is_empty() {
+            let first = match rewards_addr.get_untracked() {
+                Ok(s) | Err((s, _)) => s,
+            };
+            weighted_addrs.set(vec![WeightedAddrRow::new(0, first)]);
         }
     });
 
+    let add_node = Action::new(
+        move |(node_opts, count, interval, rewards_addrs): &(
+            NodeOpts,
+            u16,
+            u64,
+            Vec<RewardsAddrWeight>,
+        )| {
+            let node_opts = node_opts.clone();
+            let count = *count;
+            let interval = *interval;
+            let rewards_addrs = rewards_addrs.clone();
+            async move {
+                if let Err(err) =
+                    add_node_instances(node_opts, count, rewards_addrs, interval).await
+                {
+                    let msg = format!("Failed to create node/s: {err}");
+                    logging::error!("[ERROR] {msg}");
+                    show_error_alert_msg(msg);
+                }
+            }
+        },
+    );
+
     view! {
         <div class="p-8 space-y-6">
             <span hidden=move || active_tab.read() != 0>
@@ -92,7 +140,15 @@ fn AddNodeTabs(is_open: RwSignal<bool>, active_tab: RwSignal<u8>) -> impl IntoVi
                         label="Node metrics port number (range start):"
                         help_msg="Node metrics port number (range start when creating multiple nodes)."
                     />
-                    <RewardsAddrInput signal=rewards_addr label="Rewards address:" />
+                    <Show when=move || !is_distributing()>
+                        <RewardsAddrInput signal=rewards_addr label="Rewards address:" />
+                    </Show>
+                    <Show when=move || is_distributing()>
+                        <WeightedRewardsAddrs
+                            rows=weighted_addrs
+                            count=Signal::derive(move || count.get().unwrap_or_default())
+                        />
+                    </Show>
                     <div class="grid grid-cols-2 gap-4">
                         <NumberInput
                             id="nodes_count"
@@ -118,6 +174,14 @@ fn AddNodeTabs(is_open: RwSignal<bool>, active_tab: RwSignal<u8>) -> impl IntoVi
                             help_msg="Automatically starts nodes upon creation."
                             help_align="left-0"
                         />
+                        <Show when=move || count.get().is_ok_and(|c| c > 1)>
+                            <CheckboxInput
+                                signal=distribute
+                                id="distribute_rewards_addrs"
+                                label="Several rewards addresses"
+                                help_msg="Distribute the nodes across several rewards addresses, in proportion to the weight given to each of them."
+                            />
+                        </Show>
                     </div>
                 </div>
             </span>
@@ -157,14 +221,25 @@ fn AddNodeTabs(is_open: RwSignal<bool>, active_tab: RwSignal<u8>) -> impl IntoVi
             <button
                 prop:disabled=move || {
                     port.read().is_err() || metrics_port.read().is_err() || count.read().is_err()
-                        || rewards_addr.read().is_err() || interval.read().is_err()
+                        || interval.read().is_err()
+                        || if is_distributing() {
+                            !rewards_addrs_valid()
+                        } else {
+                            rewards_addr.read().is_err()
+                        }
                 }
                 on:click=move |_| {
+                    let (addr, rewards_addrs) = if is_distributing() {
+                        let addrs = rewards_addrs();
+                        (addrs.first().map(|a| a.address.clone()).ok_or(()), addrs)
+                    } else {
+                        (rewards_addr.get().map_err(|_| ()), vec![])
+                    };
                     if let (Ok(p), Ok(m), Ok(c), Ok(addr), Ok(i)) = (
                         port.get(),
                         metrics_port.get(),
                         count.get(),
-                        rewards_addr.get(),
+                        addr,
                         interval.get(),
                     ) {
                         is_open.set(false);
@@ -178,7 +253,7 @@ fn AddNodeTabs(is_open: RwSignal<bool>, active_tab: RwSignal<u8>) -> impl IntoVi
                             auto_start: auto_start.get(),
                             data_dir_path: data_dir_path.get(),
                         };
-                        add_node.dispatch((node_opts, c, i as u64));
+                        add_node.dispatch((node_opts, c, i as u64, rewards_addrs));
                     }
                 }
                 class="bg-indigo-600 hover:bg-indigo-500 text-white px-6 py-2.5 rounded-lg font-bold transition-all shadow-lg shadow-indigo-500/20 flex items-center gap-2 disabled:opacity-75 disabled:bg-slate-600 disabled:text-slate-400 disabled:shadow-none disabled:cursor-not-allowed"
@@ -196,6 +271,121 @@ fn AddNodeTabs(is_open: RwSignal<bool>, active_tab: RwSignal<u8>) -> impl IntoVi
     }
 }
 
+// Rewards address, along with its weight, entered in the list of addresses to distribute nodes across
+#[derive(Clone, Copy)]
+struct WeightedAddrRow {
+    key: usize,
+    address: RwSignal<String>,
+    weight: RwSignal<u16>,
+}
+
+impl WeightedAddrRow {
+    fn new(key: usize, address: String) -> Self {
+        Self {
+            key,
+            address: RwSignal::new(address),
+            weight: RwSignal::new(1),
+        }
+    }
+}
+
+#[component]
+fn WeightedRewardsAddrs(rows: RwSignal<Vec<WeightedAddrRow>>, count: Signal<u16>) -> impl IntoView {
+    let next_key = StoredValue::new(rows.read_untracked().len());
+    let input_class = "bg-slate-800 border rounded-lg px-3 py-2 text-sm focus:ring-1 focus:outline-none border-slate-700 focus:ring-indigo-500";
+
+    // number of nodes which will pay to each address, showing the valid ones only
+    let distribution = move || {
+        let addrs = rows
+            .read()
+            .iter()
+            .map(|row| RewardsAddrWeight {
+                address: row.address.get(),
+                weight: row.weight.get(),
+            })
+            .filter(|a| parse_and_validate_addr(&a.address).is_ok())
+            .collect::<Vec<_>>();
+        rewards_addrs_distribution(&addrs, count.get())
+    };
+
+    view! {
+        <div class="space-y-2">
+            <label class="text-sm font-medium text-slate-300">
+                "Rewards addresses and their weights:"
+            </label>
+            <For each=move || rows.get() key=|row| row.key let:row>
+                <div class="flex items-center gap-2">
+                    <input
+                        type="text"
+                        placeholder="0x"
+                        class=move || {
+                            format!(
+                                "flex-1 min-w-0 {input_class} {}",
+                                if parse_and_validate_addr(&row.address.read()).is_ok() {
+                                    ""
+                                } else {
+                                    "border-rose-500/60"
+                                },
+                            )
+                        }
+                        prop:value=move || row.address.get()
+                        on:input=move |ev| row.address.set(event_target_value(&ev))
+                    />
+                    <input
+                        type="number"
+                        min=0
+                        title="Weight"
+                        class=format!("w-20 {input_class}")
+                        prop:value=move || row.weight.get()
+                        on:input=move |ev| {
+                            row.weight.set(event_target_value(&ev).parse::<u16>().unwrap_or(0))
+                        }
+                    />
+                    <button
+                        type="button"
+                        class="p-2 text-slate-500 hover:text-rose-400 transition-colors"
+                        title="Remove address"
+                        on:click=move |_| rows.update(|list| list.retain(|r| r.key != row.key))
+                    >
+                        <IconRemove />
+                    </button>
+                </div>
+            </For>
+            <button
+                type="button"
+                class="text-xs font-bold text-indigo-400 hover:text-indigo-300"
+                on:click=move |_| {
+                    let key = next_key.get_value();
+                    next_key.set_value(key + 1);
+                    rows.update(|list| list.push(WeightedAddrRow::new(key, String::new())));
+                }
+            >
+                "+ Add address"
+            </button>
+            <Show when=move || !distribution().is_empty()>
+                <div class="bg-slate-950/60 border border-slate-800 rounded-lg p-3 space-y-1">
+                    <p class="text-xs font-bold text-slate-500 uppercase tracking-widest">
+                        Distribution
+                    </p>
+                    {move || {
+                        distribution()
+                            .into_iter()
+                            .map(|(address, n)| {
+                                view! {
+                                    <div class="flex items-center justify-between text-xs text-slate-300">
+                                        <AddressLabel address />
+                                        <span class="font-bold">{format!("{n} node/s")}</span>
+                                    </div>
+                                }
+                            })
+                            .collect_view()
+                    }}
+                </div>
+            </Show>
+        </div>
+    }
+}
+
 #[component]
 fn TabButton(label: &'static str, active_tab: RwSignal<u8>, tab_index: u8) -> impl IntoView {
     let is_active = move || active_tab.get() == tab_index;
@@ -8,7 +8,7 @@ use crate::{
         clear_chat_history, get_agent_events, get_chat_history, get_new_agent_events,
         list_chat_sessions, send_chat_message, toggle_autonomous_mode,
     },
-    types::{AgentEvent, AgentEventType, ChatMessage, ChatRole, StreamChunk},
+    types::{AgentEvent, AgentEventType, ChatMessage, ChatRole, Permission, StreamChunk},
 };
 
 use alloy_primitives::U256;
@@ -387,7 +387,10 @@ pub fn AgentView() -> impl IntoView {
                     </div>
                     <div class="flex items-center gap-2">
                         // Autonomous mode toggle
-                        <label class="flex items-center gap-2 cursor-pointer group">
+                        <label
+                            class="flex items-center gap-2 cursor-pointer group"
+                            hidden=move || !context.can(Permission::ManageSettings)
+                        >
                             <span class="text-xs text-slate-500 group-hover:text-slate-300 transition-colors hidden sm:inline">
                                 Autonomous
                             </span>
@@ -411,6 +414,7 @@ pub fn AgentView() -> impl IntoView {
                         </button>
                         <button
                             on:click=clear_session
+                            hidden=move || !context.can(Permission::OperateNodes)
                             class="text-xs text-slate-500 hover:text-rose-400 px-2 py-1 rounded-lg hover:bg-slate-800 transition-colors"
                         >
                             "Clear"
@@ -508,6 +512,7 @@ pub fn AgentView() -> impl IntoView {
 
                 // Input area — styled to match the terminal prompt
                 <form
+                    hidden=move || !context.can(Permission::OperateNodes)
                     on:submit=move |ev| {
                         ev.prevent_default();
                         send_msg();
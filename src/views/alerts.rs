@@ -1,7 +1,19 @@
-use super::icons::{IconCancel, IconOffline};
-use crate::app::ClientGlobalState;
+use super::{
+    helpers::{format_timestamp, show_error_alert_msg},
+    icons::{IconCancel, IconOffline},
+};
+use crate::{
+    app::ClientGlobalState,
+    server_api::{
+        acknowledge_alert, alert_rules, alerts_list, delete_alert_rule, save_alert_rule,
+        silence_alert_rule,
+    },
+    types::{Alert, AlertMetric, AlertOperator, AlertRule, AlertSeverity, Permission},
+};
 
-use leptos::prelude::*;
+use chrono::Utc;
+use leptos::{prelude::*, task::spawn_local};
+use std::time::Duration;
 
 #[component]
 pub fn AlertMsg() -> impl IntoView {
@@ -79,3 +91,403 @@ pub fn OfflineMsg() -> impl IntoView {
         </Show>
     }
 }
+
+// How often the list of alerts is refreshed while the view is displayed.
+const ALERTS_POLLING_FREQ: Duration = Duration::from_secs(10);
+// How long the alerts of a rule are silenced for when requested by the user.
+const ALERT_RULE_SILENCE_SECS: u64 = 60 * 60;
+
+#[component]
+pub fn AlertsView() -> impl IntoView {
+    let context = expect_context::<ClientGlobalState>();
+    // bumped to retrieve up to date alerts and rules from the backend
+    let refresh = RwSignal::new(0u32);
+    Effect::new(move |_| {
+        if let Ok(handle) =
+            set_interval_with_handle(move || refresh.update(|n| *n += 1), ALERTS_POLLING_FREQ)
+        {
+            on_cleanup(move || handle.clear());
+        }
+    });
+
+    let alerts = Resource::new(move || refresh.get(), |_| alerts_list());
+    let rules = Resource::new(move || refresh.get(), |_| alert_rules());
+
+    view! {
+        <div class="p-4 lg:p-8 max-w-6xl mx-auto space-y-8 animate-in fade-in slide-in-from-bottom-4 duration-500">
+            <section class="bg-slate-900 border border-slate-800 rounded-3xl overflow-hidden shadow-xl">
+                <header class="p-6 border-b border-slate-800">
+                    <h3 class="text-lg font-bold">Alerts</h3>
+                    <p class="text-xs text-slate-500 mt-1">
+                        "Alerts fired by the rules below, evaluated periodically against the nodes metrics, stats and earnings."
+                    </p>
+                </header>
+                <Transition fallback=move || {
+                    view! { <p class="p-6 text-slate-400">"Retrieving alerts..."</p> }
+                }>
+                    {move || {
+                        alerts
+                            .get()
+                            .map(|res| match res {
+                                Ok(list) if list.is_empty() => {
+                                    view! {
+                                        <p class="p-6 text-center text-slate-400">
+                                            "No alerts have fired"
+                                        </p>
+                                    }
+                                        .into_any()
+                                }
+                                Ok(list) => {
+                                    view! {
+                                        <ul class="divide-y divide-slate-800">
+                                            {list
+                                                .into_iter()
+                                                .map(|alert| view! { <AlertItem alert refresh /> })
+                                                .collect_view()}
+                                        </ul>
+                                    }
+                                        .into_any()
+                                }
+                                Err(err) => {
+                                    view! {
+                                        <p class="p-6 text-rose-400">
+                                            {format!("Failed to retrieve alerts: {err}")}
+                                        </p>
+                                    }
+                                        .into_any()
+                                }
+                            })
+                    }}
+                </Transition>
+            </section>
+
+            <section class="bg-slate-900 border border-slate-800 rounded-3xl overflow-hidden shadow-xl">
+                <header class="p-6 border-b border-slate-800">
+                    <h3 class="text-lg font-bold">Alert Rules</h3>
+                </header>
+                <Transition fallback=move || {
+                    view! { <p class="p-6 text-slate-400">"Retrieving alert rules..."</p> }
+                }>
+                    {move || {
+                        rules
+                            .get()
+                            .map(|res| match res {
+                                Ok(list) => {
+                                    view! {
+                                        <div class="overflow-x-auto">
+                                            <table class="w-full text-left text-sm">
+                                                <thead>
+                                                    <tr class="bg-slate-800/50 text-slate-400 text-xs uppercase tracking-wider">
+                                                        <th class="px-6 py-3 font-semibold">Name</th>
+                                                        <th class="px-6 py-3 font-semibold">Condition</th>
+                                                        <th class="px-6 py-3 font-semibold">Severity</th>
+                                                        <th class="px-6 py-3 font-semibold"></th>
+                                                    </tr>
+                                                </thead>
+                                                <tbody class="divide-y divide-slate-800">
+                                                    {list
+                                                        .into_iter()
+                                                        .map(|rule| view! { <AlertRuleRow rule refresh /> })
+                                                        .collect_view()}
+                                                </tbody>
+                                            </table>
+                                        </div>
+                                    }
+                                        .into_any()
+                                }
+                                Err(err) => {
+                                    view! {
+                                        <p class="p-6 text-rose-400">
+                                            {format!("Failed to retrieve alert rules: {err}")}
+                                        </p>
+                                    }
+                                        .into_any()
+                                }
+                            })
+                    }}
+                </Transition>
+                <Show when=move || context.can(Permission::ManageSettings)>
+                    <NewAlertRuleForm refresh />
+                </Show>
+            </section>
+        </div>
+    }
+}
+
+#[component]
+fn AlertItem(alert: Alert, refresh: RwSignal<u32>) -> impl IntoView {
+    let context = expect_context::<ClientGlobalState>();
+    let alert_id = alert.id;
+    let is_firing = alert.is_firing();
+    let acknowledged = alert.is_acknowledged();
+    let status = match (is_firing, alert.silenced, acknowledged) {
+        (false, _, _) => "Resolved",
+        (true, true, _) => "Silenced",
+        (true, false, true) => "Acknowledged",
+        (true, false, false) => "Firing",
+    };
+    let when = match alert.resolved_at {
+        Some(ts) => format!(
+            "Fired {} | Resolved {}",
+            format_timestamp(alert.fired_at),
+            format_timestamp(ts)
+        ),
+        None => format!("Fired {}", format_timestamp(alert.fired_at)),
+    };
+
+    view! {
+        <li class=format!(
+            "px-6 py-4 flex items-start gap-4 {}",
+            if is_firing && !acknowledged && !alert.silenced { "" } else { "opacity-60" },
+        )>
+            <span class=format!(
+                "mt-0.5 px-2 py-0.5 rounded-full text-[10px] font-bold uppercase tracking-wider border {}",
+                severity_color(alert.severity),
+            )>{alert.severity.as_str()}</span>
+            <div class="flex-1 min-w-0">
+                <p class="text-sm text-slate-200 font-medium">{alert.message}</p>
+                <p class="text-xs text-slate-500 mt-1">{status} " | " {when}</p>
+            </div>
+            <Show when=move || {
+                is_firing && !acknowledged && context.can(Permission::OperateNodes)
+            }>
+                <button
+                    class="text-xs font-bold text-indigo-400 hover:text-indigo-300"
+                    on:click=move |_| {
+                        spawn_local(async move {
+                            if let Err(err) = acknowledge_alert(alert_id).await {
+                                show_error_alert_msg(format!("Failed to acknowledge alert: {err}"));
+                            }
+                            refresh.update(|n| *n += 1);
+                        })
+                    }
+                >
+                    Acknowledge
+                </button>
+            </Show>
+        </li>
+    }
+}
+
+#[component]
+fn AlertRuleRow(rule: AlertRule, refresh: RwSignal<u32>) -> impl IntoView {
+    let context = expect_context::<ClientGlobalState>();
+    let rule_id = rule.id;
+    let now = Utc::now().timestamp();
+    let is_silenced = rule.is_silenced(now);
+    let enabled = rule.enabled;
+    let condition = rule.to_string();
+    let severity = rule.severity;
+    let name = rule.name.clone();
+
+    let run =
+        move |action: &'static str,
+              f: std::pin::Pin<Box<dyn Future<Output = Result<(), ServerFnError>>>>| {
+            spawn_local(async move {
+                if let Err(err) = f.await {
+                    show_error_alert_msg(format!("Failed to {action} alert rule: {err}"));
+                }
+                refresh.update(|n| *n += 1);
+            })
+        };
+
+    let toggled = AlertRule {
+        enabled: !enabled,
+        ..rule
+    };
+
+    view! {
+        <tr class=if enabled { "" } else { "opacity-60" }>
+            <td class="px-6 py-3 font-medium">{name}</td>
+            <td class="px-6 py-3 font-mono text-xs text-slate-300">{condition}</td>
+            <td class="px-6 py-3">
+                <span class=format!(
+                    "px-2 py-0.5 rounded-full text-[10px] font-bold uppercase tracking-wider border {}",
+                    severity_color(severity),
+                )>{severity.as_str()}</span>
+            </td>
+            <td class="px-6 py-3">
+                <div
+                    class="flex items-center justify-end gap-3 text-xs font-bold"
+                    hidden=move || !context.can(Permission::ManageSettings)
+                >
+                    <button
+                        class="text-indigo-400 hover:text-indigo-300"
+                        on:click=move |_| {
+                            let rule = toggled.clone();
+                            run("update", Box::pin(save_alert_rule(rule)))
+                        }
+                    >
+                        {if enabled { "Disable" } else { "Enable" }}
+                    </button>
+                    <button
+                        class="text-amber-400 hover:text-amber-300"
+                        on:click=move |_| {
+                            let secs = if is_silenced { 0 } else { ALERT_RULE_SILENCE_SECS };
+                            run("silence", Box::pin(silence_alert_rule(rule_id, secs)))
+                        }
+                    >
+                        {if is_silenced { "Unsilence" } else { "Silence 1h" }}
+                    </button>
+                    <button
+                        class="text-rose-400 hover:text-rose-300"
+                        on:click=move |_| run("delete", Box::pin(delete_alert_rule(rule_id)))
+                    >
+                        Delete
+                    </button>
+                </div>
+            </td>
+        </tr>
+    }
+}
+
+#[component]
+fn NewAlertRuleForm(refresh: RwSignal<u32>) -> impl IntoView {
+    let new_rule = || AlertRule {
+        enabled: true,
+        ..Default::default()
+    };
+    let rule = RwSignal::new(new_rule());
+    let parse_f64 = |ev| event_target_value(&ev).parse::<f64>().unwrap_or_default();
+
+    let input_class = "bg-slate-800 border border-slate-700 rounded-md px-3 py-2 text-sm focus:outline-none focus:ring-1 focus:ring-indigo-500";
+
+    view! {
+        <div class="p-6 border-t border-slate-800 bg-slate-800/10 space-y-4">
+            <h4 class="text-sm font-bold text-slate-300">New Rule</h4>
+            <div class="grid grid-cols-1 md:grid-cols-4 gap-3">
+                <input
+                    type="text"
+                    placeholder="Name"
+                    class=input_class
+                    prop:value=move || rule.read().name.clone()
+                    on:input=move |ev| rule.update(|r| r.name = event_target_value(&ev))
+                />
+                <select
+                    class=input_class
+                    on:change=move |ev| {
+                        if let Ok(metric) = event_target_value(&ev).parse() {
+                            rule.update(|r| r.metric = metric);
+                        }
+                    }
+                >
+                    {AlertMetric::ALL
+                        .into_iter()
+                        .map(|m| {
+                            view! {
+                                <option
+                                    value=m.as_str()
+                                    selected=move || rule.read().metric == m
+                                >
+                                    {m.label()}
+                                </option>
+                            }
+                        })
+                        .collect_view()}
+                </select>
+                <select
+                    class=input_class
+                    on:change=move |ev| {
+                        if let Ok(operator) = event_target_value(&ev).parse() {
+                            rule.update(|r| r.operator = operator);
+                        }
+                    }
+                >
+                    {AlertOperator::ALL
+                        .into_iter()
+                        .map(|op| {
+                            view! {
+                                <option
+                                    value=op.as_str()
+                                    selected=move || rule.read().operator == op
+                                >
+                                    {match op {
+                                        AlertOperator::Below => "Below",
+                                        AlertOperator::Above => "Above",
+                                    }}
+                                </option>
+                            }
+                        })
+                        .collect_view()}
+                </select>
+                <input
+                    type="number"
+                    placeholder="Threshold"
+                    title="Threshold"
+                    class=input_class
+                    prop:value=move || rule.read().threshold.to_string()
+                    on:input=move |ev| rule.update(|r| r.threshold = parse_f64(ev))
+                />
+                <input
+                    type="number"
+                    min=0
+                    placeholder="For (seconds)"
+                    title="Seconds the threshold needs to be breached before firing"
+                    class=input_class
+                    prop:value=move || rule.read().for_secs.to_string()
+                    on:input=move |ev| {
+                        rule.update(|r| {
+                            r.for_secs = event_target_value(&ev).parse().unwrap_or_default()
+                        })
+                    }
+                />
+                <input
+                    type="number"
+                    min=0
+                    placeholder="Hysteresis"
+                    title="Margin the value needs to recover beyond the threshold before resolving"
+                    class=input_class
+                    prop:value=move || rule.read().hysteresis.to_string()
+                    on:input=move |ev| rule.update(|r| r.hysteresis = parse_f64(ev))
+                />
+                <select
+                    class=input_class
+                    on:change=move |ev| {
+                        if let Ok(severity) = event_target_value(&ev).parse() {
+                            rule.update(|r| r.severity = severity);
+                        }
+                    }
+                >
+                    {AlertSeverity::ALL
+                        .into_iter()
+                        .map(|s| {
+                            view! {
+                                <option
+                                    value=s.as_str()
+                                    selected=move || rule.read().severity == s
+                                >
+                                    {s.as_str()}
+                                </option>
+                            }
+                        })
+                        .collect_view()}
+                </select>
+                <button
+                    class="bg-indigo-600 hover:bg-indigo-500 transition-colors text-white px-4 py-2 rounded-lg font-medium text-sm"
+                    on:click=move |_| {
+                        let to_save = rule.get();
+                        spawn_local(async move {
+                            match save_alert_rule(to_save).await {
+                                Ok(()) => rule.set(new_rule()),
+                                Err(err) => {
+                                    show_error_alert_msg(format!("Failed to add alert rule: {err}"))
+                                }
+                            }
+                            refresh.update(|n| *n += 1);
+                        })
+                    }
+                >
+                    Add Rule
+                </button>
+            </div>
+        </div>
+    }
+}
+
+fn severity_color(severity: AlertSeverity) -> &'static str {
+    match severity {
+        AlertSeverity::Info => "bg-sky-500/10 text-sky-400 border-sky-500/20",
+        AlertSeverity::Warning => "bg-amber-500/10 text-amber-400 border-amber-500/20",
+        AlertSeverity::Critical => "bg-rose-500/10 text-rose-400 border-rose-500/20",
+    }
+}
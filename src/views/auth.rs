@@ -0,0 +1,174 @@
+use crate::{
+    app::ClientGlobalState,
+    server_api::{login, logout, setup_first_user},
+    types::validate_credentials,
+};
+
+use super::{
+    helpers::show_error_alert_msg,
+    icons::{IconLogout, IconShield},
+};
+
+use leptos::{logging, prelude::*};
+use wasm_bindgen_futures::spawn_local;
+
+const INPUT_CLASS: &str = "w-full bg-slate-800 border border-slate-700 rounded-md px-3 py-2 text-sm focus:outline-none font-mono transition-colors focus:ring-1 focus:ring-indigo-500";
+
+// Reload the app so it's rendered again with the new session, or without it.
+fn reload_app() {
+    if let Err(err) = window().location().reload() {
+        logging::error!("[ERROR] Failed to reload the app: {err:?}");
+    }
+}
+
+// Form to log in, or to create the first user when none has been set up yet.
+#[component]
+pub fn LoginView(setup: bool) -> impl IntoView {
+    let username = RwSignal::new(String::new());
+    let password = RwSignal::new(String::new());
+    let password_confirm = RwSignal::new(String::new());
+    let error = RwSignal::new(None::<String>);
+    let submitting = RwSignal::new(false);
+
+    let submit = move || {
+        let (username, password) = (username.get_untracked(), password.get_untracked());
+        if setup {
+            if let Err(err) = validate_credentials(&username, &password) {
+                error.set(Some(err));
+                return;
+            }
+            if password != password_confirm.get_untracked() {
+                error.set(Some("Passwords don't match".to_string()));
+                return;
+            }
+        }
+
+        submitting.set(true);
+        spawn_local(async move {
+            let res = if setup {
+                setup_first_user(username, password).await
+            } else {
+                login(username, password).await
+            };
+            match res {
+                Ok(()) => reload_app(),
+                Err(err) => {
+                    error.set(Some(err.to_string()));
+                    submitting.set(false);
+                }
+            }
+        });
+    };
+
+    view! {
+        <div class="flex min-h-screen items-center justify-center bg-slate-950 text-slate-100 p-4">
+            <form
+                class="w-full max-w-sm bg-slate-900 border border-slate-800 rounded-3xl shadow-2xl p-8 flex flex-col gap-5"
+                on:submit=move |e| {
+                    e.prevent_default();
+                    submit();
+                }
+            >
+                <div class="flex items-center gap-4">
+                    <div class="p-3 bg-indigo-500/10 rounded-2xl text-indigo-400">
+                        <IconShield class="w-6 h-6" />
+                    </div>
+                    <div>
+                        <h2 class="text-xl font-bold text-white tracking-tight">"Formicaio"</h2>
+                        <p class="text-xs text-slate-500 font-medium uppercase tracking-widest mt-1">
+                            {if setup { "Create the first user" } else { "Log in" }}
+                        </p>
+                    </div>
+                </div>
+                <Show when=move || setup>
+                    <p class="text-sm text-slate-400">
+                        "No user has been set up yet. Choose the credentials required to access this Formicaio instance from now on."
+                    </p>
+                </Show>
+
+                <input
+                    type="text"
+                    class=INPUT_CLASS
+                    placeholder="Username"
+                    autocomplete="username"
+                    prop:value=move || username.get()
+                    on:input=move |ev| username.set(event_target_value(&ev))
+                />
+                <input
+                    type="password"
+                    class=INPUT_CLASS
+                    placeholder="Password"
+                    autocomplete=if setup { "new-password" } else { "current-password" }
+                    prop:value=move || password.get()
+                    on:input=move |ev| password.set(event_target_value(&ev))
+                />
+                <Show when=move || setup>
+                    <input
+                        type="password"
+                        class=INPUT_CLASS
+                        placeholder="Confirm password"
+                        autocomplete="new-password"
+                        prop:value=move || password_confirm.get()
+                        on:input=move |ev| password_confirm.set(event_target_value(&ev))
+                    />
+                </Show>
+
+                {move || {
+                    error
+                        .get()
+                        .map(|err| view! { <p class="text-sm text-rose-400">{err}</p> })
+                }}
+
+                <button
+                    type="submit"
+                    disabled=move || submitting.get()
+                    class="bg-indigo-600 hover:bg-indigo-500 disabled:opacity-50 transition-colors text-white px-4 py-2 rounded-lg font-medium text-sm shadow-lg shadow-indigo-500/20"
+                >
+                    {if setup { "Create user" } else { "Log in" }}
+                </button>
+            </form>
+        </div>
+    }
+}
+
+// Username and role of the user logged in, along with a button to log out.
+#[component]
+pub fn LogoutButton() -> impl IntoView {
+    let context = expect_context::<ClientGlobalState>();
+
+    let on_logout = move |_| {
+        spawn_local(async {
+            match logout().await {
+                Ok(()) => reload_app(),
+                Err(err) => show_error_alert_msg(format!("Failed to log out: {err}")),
+            }
+        });
+    };
+
+    view! {
+        <div class="flex items-center gap-2">
+            {move || {
+                context
+                    .current_user
+                    .get()
+                    .map(|user| {
+                        view! {
+                            <span class="hidden md:inline text-sm text-slate-400 font-mono">
+                                {user.username}
+                            </span>
+                            <span class="hidden md:inline text-[10px] uppercase tracking-widest font-bold px-2 py-0.5 rounded-full bg-slate-800 text-slate-400">
+                                {user.role.label()}
+                            </span>
+                        }
+                    })
+            }}
+            <button
+                on:click=on_logout
+                title="Log out"
+                class="p-2 text-slate-400 hover:text-white transition-all rounded-lg hover:bg-slate-800"
+            >
+                <IconLogout />
+            </button>
+        </div>
+    }
+}
@@ -1,7 +1,10 @@
 use crate::{
     app::{ClientGlobalState, METRICS_MAX_SIZE_PER_NODE},
     server_api::{get_settings, node_metrics},
-    types::{METRIC_KEY_CPU_USAGE, METRIC_KEY_MEM_USED_MB, NodeId},
+    types::{
+        METRIC_KEY_CPU_USAGE, METRIC_KEY_MEM_USED_MB, METRIC_KEY_NET_RX_RATE,
+        METRIC_KEY_NET_TX_RATE, NodeId, NodeMetric,
+    },
 };
 
 use super::icons::IconCancel;
@@ -19,25 +22,40 @@ use charming::{
 use gloo_timers::future::TimeoutFuture;
 use leptos::{logging, prelude::*};
 
-pub type ChartSeriesData = (Vec<(i64, f64)>, Vec<(i64, f64)>);
+/// Data points of each of the series charted for a node.
+#[derive(Clone, Debug, Default)]
+pub struct ChartSeriesData {
+    pub mem: Vec<(i64, f64)>,
+    pub cpu: Vec<(i64, f64)>,
+    /// Bytes received per second
+    pub net_rx: Vec<(i64, f64)>,
+    /// Bytes transmitted per second
+    pub net_tx: Vec<(i64, f64)>,
+}
 
 const CHART_MEM_SERIES_NAME: &str = "Memory (MB)";
 const CHART_CPU_SERIES_NAME: &str = "CPU (%)";
+const CHART_NET_RX_SERIES_NAME: &str = "Received (KB/s)";
+const CHART_NET_TX_SERIES_NAME: &str = "Transmitted (KB/s)";
 const CHART_MEM_COLOR: &str = "#F98080";
 const CHART_CPU_COLOR: &str = "#3F83F8";
+const CHART_NET_RX_COLOR: &str = "#22D3EE";
+const CHART_NET_TX_COLOR: &str = "#A3E635";
+const KB_CONVERTION: f64 = 1_024.0;
 
-fn build_metrics_chart(mem: &[(i64, f64)], cpu: &[(i64, f64)]) -> Chart {
-    let to_dataframe = |pts: &[(i64, f64)]| -> Vec<DataPoint> {
-        pts.iter()
-            .map(|(ts, v)| {
-                DataPoint::from(CompositeValue::from(vec![
-                    CompositeValue::from(*ts),
-                    CompositeValue::from(*v),
-                ]))
-            })
-            .collect()
-    };
+fn to_dataframe(pts: &[(i64, f64)]) -> Vec<DataPoint> {
+    pts.iter()
+        .map(|(ts, v)| {
+            DataPoint::from(CompositeValue::from(vec![
+                CompositeValue::from(*ts),
+                CompositeValue::from(*v),
+            ]))
+        })
+        .collect()
+}
 
+// Chart with a time axis and a tooltip listing the value of each series.
+fn build_time_chart() -> Chart {
     Chart::new()
         .tooltip(
             Tooltip::new().trigger(Trigger::Axis).formatter(Formatter::Function(
@@ -66,6 +84,10 @@ fn build_metrics_chart(mem: &[(i64, f64)], cpu: &[(i64, f64)]) -> Chart {
                         .formatter(Formatter::String("{HH}:{mm}:{ss}".to_string())),
                 ),
         )
+}
+
+fn build_metrics_chart(mem: &[(i64, f64)], cpu: &[(i64, f64)]) -> Chart {
+    build_time_chart()
         .y_axis(
             Axis::new()
                 .type_(AxisType::Value)
@@ -105,6 +127,35 @@ fn build_metrics_chart(mem: &[(i64, f64)], cpu: &[(i64, f64)]) -> Chart {
         )
 }
 
+fn build_network_chart(rx: &[(i64, f64)], tx: &[(i64, f64)]) -> Chart {
+    let to_kb = |pts: &[(i64, f64)]| {
+        pts.iter()
+            .map(|(ts, v)| (*ts, v / KB_CONVERTION))
+            .collect::<Vec<_>>()
+    };
+    let series = |name: &str, color: &str, pts: &[(i64, f64)]| {
+        Line::new()
+            .name(name)
+            .data(to_dataframe(&to_kb(pts)))
+            .smooth(true)
+            .show_symbol(false)
+            .item_style(ItemStyle::new().color(color))
+    };
+
+    build_time_chart()
+        .y_axis(
+            Axis::new()
+                .type_(AxisType::Value)
+                .name("KB/s")
+                .name_text_style(TextStyle::new().color("#9CA3AF"))
+                .axis_label(AxisLabel::new().color("#9CA3AF"))
+                .axis_line(AxisLine::new().show(false))
+                .split_line(SplitLine::new().show(false)),
+        )
+        .series(series(CHART_NET_RX_SERIES_NAME, CHART_NET_RX_COLOR, rx))
+        .series(series(CHART_NET_TX_SERIES_NAME, CHART_NET_TX_COLOR, tx))
+}
+
 #[component]
 pub fn MetricsViewerModal(
     set_render_chart: RwSignal<bool>,
@@ -133,7 +184,7 @@ pub fn MetricsViewerModal(
                 <header class="p-4 border-b border-slate-800 flex items-center justify-between bg-slate-800/30 shrink-0">
                     <div class="flex items-center gap-3">
                         <h3 class="text-lg font-bold">
-                            "Real-time Mem & CPU Metrics: "
+                            "Real-time Mem, CPU & Network Metrics: "
                             <span class="text-indigo-400 font-mono">
                                 {move || {
                                     context
@@ -188,10 +239,12 @@ pub fn NodeChartView(
     chart_data: ReadSignal<ChartSeriesData>,
 ) -> impl IntoView {
     let chart_id = "metrics_chart";
+    let net_chart_id = "net_metrics_chart";
 
     use charming::WasmRenderer;
 
     let echarts = RwSignal::new_local(None::<charming::renderer::wasm_renderer::Echarts>);
+    let net_echarts = RwSignal::new_local(None::<charming::renderer::wasm_renderer::Echarts>);
 
     Effect::new(move |_| {
         if !*is_render_chart.read() {
@@ -201,24 +254,32 @@ pub fn NodeChartView(
             Ok(e) => echarts.update(|h| *h = Some(e)),
             Err(err) => logging::error!("[ERROR] Failed to render chart: {err}"),
         }
+        match WasmRenderer::new_opt(None, None).render(net_chart_id, &build_network_chart(&[], &[]))
+        {
+            Ok(e) => net_echarts.update(|h| *h = Some(e)),
+            Err(err) => logging::error!("[ERROR] Failed to render network chart: {err}"),
+        }
     });
 
     Effect::new(move |_| {
         if !*is_render_chart.read() {
             return;
         }
+        let data = chart_data.get();
         echarts.with(|h| {
             if let Some(e) = h {
-                let (mem, cpu) = chart_data.get();
-                WasmRenderer::update(e, &build_metrics_chart(&mem, &cpu));
+                WasmRenderer::update(e, &build_metrics_chart(&data.mem, &data.cpu));
+            }
+        });
+        net_echarts.with(|h| {
+            if let Some(e) = h {
+                WasmRenderer::update(e, &build_network_chart(&data.net_rx, &data.net_tx));
             }
         });
     });
 
-    let no_data = move || {
-        let (mem, cpu) = chart_data.get();
-        mem.is_empty() && cpu.is_empty()
-    };
+    let no_data = move || chart_data.with(|d| d.mem.is_empty() && d.cpu.is_empty());
+    let no_net_data = move || chart_data.with(|d| d.net_rx.is_empty() && d.net_tx.is_empty());
 
     view! {
         <div class="relative" style="width: 100%; height: 380px;">
@@ -229,6 +290,16 @@ pub fn NodeChartView(
                 </div>
             </Show>
         </div>
+        <div class="relative border-t border-slate-800" style="width: 100%; height: 240px;">
+            <div id=net_chart_id style="width: 100%; height: 240px;" />
+            <Show when=no_net_data>
+                <div class="absolute inset-0 flex items-center justify-center pointer-events-none">
+                    <p class="text-slate-400 text-sm">
+                        "No network traffic data available, nodes sharing the host's network can't be accounted separately"
+                    </p>
+                </div>
+            </Show>
+        </div>
     }
 }
 
@@ -245,7 +316,7 @@ pub async fn node_metrics_update(
     // use context to check if we should stop retrieving the metrics
     let context = expect_context::<ClientGlobalState>();
     let mut since = None;
-    set_chart_data.update(|data| *data = (vec![], vec![]));
+    set_chart_data.set(ChartSeriesData::default());
 
     while let Some(true) = context
         .metrics_update_on_for
@@ -260,22 +331,15 @@ pub async fn node_metrics_update(
         ) {
             (Some(mem), Some(cpu)) if !mem.is_empty() && !cpu.is_empty() => {
                 since = mem.last().map(|m| m.timestamp);
-                set_chart_data.update(|(m, c)| {
-                    m.extend(
-                        mem.iter()
-                            .map(|v| (v.timestamp, v.value.parse::<f64>().unwrap_or_default())),
-                    );
-                    c.extend(
-                        cpu.iter()
-                            .map(|v| (v.timestamp, v.value.parse::<f64>().unwrap_or_default())),
-                    );
-
-                    // remove items if they exceed the max size
-                    if let Some(delta) = m.len().checked_sub(METRICS_MAX_SIZE_PER_NODE) {
-                        m.drain(0..delta);
+                set_chart_data.update(|data| {
+                    extend_series(&mut data.mem, mem);
+                    extend_series(&mut data.cpu, cpu);
+                    // network traffic is only available for nodes with their own network
+                    if let Some(rx) = update.get(METRIC_KEY_NET_RX_RATE) {
+                        extend_series(&mut data.net_rx, rx);
                     }
-                    if let Some(delta) = c.len().checked_sub(METRICS_MAX_SIZE_PER_NODE) {
-                        c.drain(0..delta);
+                    if let Some(tx) = update.get(METRIC_KEY_NET_TX_RATE) {
+                        extend_series(&mut data.net_tx, tx);
                     }
                 });
             }
@@ -289,3 +353,15 @@ pub async fn node_metrics_update(
     logging::log!("Stopped node metrics update from node {node_id}.");
     Ok(())
 }
+
+// Add the new data points to a series, removing the oldest ones if it exceeds the max size
+fn extend_series(series: &mut Vec<(i64, f64)>, metrics: &[NodeMetric]) {
+    series.extend(
+        metrics
+            .iter()
+            .map(|v| (v.timestamp, v.value.parse::<f64>().unwrap_or_default())),
+    );
+    if let Some(delta) = series.len().checked_sub(METRICS_MAX_SIZE_PER_NODE) {
+        series.drain(0..delta);
+    }
+}
@@ -1,13 +1,17 @@
-use crate::{app::ClientGlobalState, types::shortened_address};
+use crate::{app::ClientGlobalState, types::OtherBalances};
 
 use super::{
     GB_CONVERTION,
     earnings::RewardsEarningsCard,
     format_disk_usage,
-    helpers::{truncated_balance_str, value_or_dash},
+    helpers::{format_other_balances, truncated_balance_str, value_or_dash},
+    host::HostCard,
     icons::{
         IconActivity, IconArrowUpRight, IconDisk, IconFile, IconPeers, IconServer, IconWallet,
     },
+    profitability::ProfitabilityCard,
+    rewards_addresses::AddressLabel,
+    stats_history::StatsHistoryCard,
 };
 
 use alloy_primitives::{U256, utils::format_units};
@@ -47,9 +51,10 @@ pub fn DashboardView(on_nodes_click: Callback<()>) -> impl IntoView {
                                 .stats
                                 .with(|s| {
                                     format!(
-                                        "{} Active | {} Inactive",
+                                        "{} Active | {} Inactive | Polled in {:.1}s",
                                         s.active_nodes,
                                         s.inactive_nodes,
+                                        s.metrics_polling_cycle_millis as f64 / 1_000.0,
                                     )
                                 })
                         })
@@ -83,6 +88,9 @@ pub fn DashboardView(on_nodes_click: Callback<()>) -> impl IntoView {
                             )
                         })
                         balances=Signal::derive(move || context.stats.read().balances.clone())
+                        other_balances=Signal::derive(move || {
+                            context.stats.read().other_balances.clone()
+                        })
                     />
                 </Show>
                 <StatCard
@@ -116,6 +124,12 @@ pub fn DashboardView(on_nodes_click: Callback<()>) -> impl IntoView {
 
             </div>
 
+            // Stats History Card - Aggregated stats over time
+            <StatsHistoryCard />
+
+            // Host Card - Resources usage of the host machine
+            <HostCard />
+
             // Analytics Card - Comprehensive Rewards Breakdown
             <div class="grid grid-cols-1 gap-6">
                 <Show
@@ -131,6 +145,7 @@ pub fn DashboardView(on_nodes_click: Callback<()>) -> impl IntoView {
                     }
                 >
                     <RewardsEarningsCard />
+                    <ProfitabilityCard />
                 </Show>
             </div>
 
@@ -338,7 +353,17 @@ fn BalanceCard(
     sub_value: Signal<String>,
     base_url: Signal<String>,
     balances: Signal<Vec<(String, U256)>>,
+    other_balances: Signal<Vec<(String, OtherBalances)>>,
 ) -> impl IntoView {
+    let others_of = move |addr: &str| {
+        other_balances
+            .read()
+            .iter()
+            .find(|(a, _)| a == addr)
+            .map(|(_, others)| format_other_balances(others))
+            .unwrap_or_default()
+    };
+
     view! {
         <div class="relative group">
             <div class="bg-slate-900 border border-slate-800 p-6 rounded-2xl hover:border-emerald-500/50 transition-all duration-300 group-hover:border-emerald-500/50 shadow-lg flex flex-col justify-between min-h-[140px]">
@@ -378,11 +403,19 @@ fn BalanceCard(
                                         rel="noopener noreferrer"
                                         class="flex items-center justify-between text-slate-400 hover:text-white group/link p-1 rounded-md hover:bg-slate-800/50"
                                     >
-                                        <span>{shortened_address(&child.0)}</span>
+                                        <AddressLabel address=child.0.clone() />
                                         <div class="flex items-center gap-2">
-                                            <span class="text-emerald-400 font-sans font-bold">
-                                                {truncated_balance_str(child.1)}
-                                            </span>
+                                            <div class="flex flex-col items-end">
+                                                <span class="text-emerald-400 font-sans font-bold">
+                                                    {truncated_balance_str(child.1)}
+                                                </span>
+                                                <span class="text-slate-500 font-sans text-[10px]">
+                                                    {
+                                                        let addr = child.0.clone();
+                                                        move || others_of(&addr)
+                                                    }
+                                                </span>
+                                            </div>
                                             <IconArrowUpRight class="h-4 w-4 opacity-0 group-hover/link:opacity-100 transition-opacity" />
                                         </div>
                                     </a>
@@ -1,22 +1,38 @@
 use crate::{
     app::ClientGlobalState,
-    types::{PeriodStats, shortened_address},
+    server_api::{earnings_history, export_earnings},
+    types::{EarningsBucket, EarningsHistory, ExportFormat, PeriodStats},
 };
 
 use super::{
-    helpers::{human_readable_percent, truncated_balance_str},
+    helpers::{
+        download_file, human_readable_percent, show_error_alert_msg, show_warning_alert_msg,
+        truncated_balance_str,
+    },
     icons::{IconChevronDown, IconTrendingUp, IconWallet},
 };
 
-use alloy_primitives::U256;
-use leptos::prelude::*;
+use alloy_primitives::{Address, U256};
+use charming::{
+    Chart, WasmRenderer,
+    component::{Axis, Legend},
+    element::{AxisLabel, AxisLine, AxisType, ItemStyle, SplitLine, TextStyle, Tooltip, Trigger},
+    series::{Bar, Line},
+};
+use chrono::{Days, NaiveDate, Utc};
+use leptos::{logging, prelude::*, task::spawn_local};
+
+// Number of days exported by default, up to today.
+const DEFAULT_EXPORT_DAYS: u64 = 30;
+
+const EARNINGS_CHART_ID: &str = "earnings_history_chart";
 
 #[component]
 pub fn RewardsEarningsCard() -> impl IntoView {
     let context = expect_context::<ClientGlobalState>();
     let selected_address = RwSignal::<Option<String>>::new(None);
     let balances = move || context.stats.read().balances.clone();
-    let earnings_syncing = move || context.stats.read().earnings_syncing;
+    let earnings_sync_progress = move || context.stats.read().earnings_sync_progress;
     Effect::new(move |_| {
         if let Some(addr) = selected_address.get() {
             let addr_upper = addr.to_uppercase();
@@ -57,12 +73,15 @@ pub fn RewardsEarningsCard() -> impl IntoView {
                                 Earnings Stats
                             </h3>
                             {move || {
-                                earnings_syncing()
-                                    .then(|| {
+                                earnings_sync_progress()
+                                    .map(|pct| {
                                         view! {
-                                            <span class="inline-flex items-center gap-1.5 px-2 py-0.5 rounded-full text-[10px] font-bold uppercase tracking-wider bg-amber-500/10 text-amber-400 border border-amber-500/20 animate-pulse">
+                                            <span
+                                                class="inline-flex items-center gap-1.5 px-2 py-0.5 rounded-full text-[10px] font-bold uppercase tracking-wider bg-amber-500/10 text-amber-400 border border-amber-500/20 animate-pulse"
+                                                title="Percentage of the earnings history fetched from the chain so far"
+                                            >
                                                 <span class="w-1.5 h-1.5 bg-amber-400 rounded-full"></span>
-                                                "Syncing"
+                                                {format!("Syncing {pct}%")}
                                             </span>
                                         }
                                     })
@@ -95,7 +114,10 @@ pub fn RewardsEarningsCard() -> impl IntoView {
                             <option prop:value="All Reward Addresses">All Reward Addresses</option>
                             <For each=move || balances() key=|(addr, _)| addr.clone() let:child>
                                 <option prop:key=child.0.clone() value=child.0.clone()>
-                                    {shortened_address(&child.0)}
+                                    {
+                                        let addr = child.0.clone();
+                                        move || context.address_book.read().display_name(&addr)
+                                    }
                                 </option>
                             </For>
                         </select>
@@ -111,10 +133,12 @@ pub fn RewardsEarningsCard() -> impl IntoView {
                 Some(stats) => {
                     view! {
                         <div class="p-6 grid grid-cols-1 md:grid-cols-2 xl:grid-cols-4 gap-6 bg-slate-950/20">
-                            <PeriodStatCard stats=stats.1.period_1 />
-                            <PeriodStatCard stats=stats.1.period_2 />
-                            <PeriodStatCard stats=stats.1.period_3 />
-                            <PeriodStatCard stats=stats.1.period_4 />
+                            {stats
+                                .1
+                                .periods
+                                .into_iter()
+                                .map(|period| view! { <PeriodStatCard stats=period /> })
+                                .collect_view()}
                         </div>
                     }
                         .into_any()
@@ -136,12 +160,317 @@ pub fn RewardsEarningsCard() -> impl IntoView {
                         .into_any()
                 }
             }}
+
+            <EarningsHistoryChart selected_address />
+            <EarningsExportBar selected_address />
         </div>
     }
 }
 
+// Convert an amount in attos into tokens, for charting.
+fn attos_to_tokens(v: U256) -> f64 {
+    f64::from(v) / 1e18
+}
+
+fn build_earnings_chart(history: &EarningsHistory, bucket: EarningsBucket) -> Chart {
+    let labels = history
+        .buckets
+        .iter()
+        .map(|b| match bucket {
+            EarningsBucket::Day | EarningsBucket::Week => b.start.format("%m-%d").to_string(),
+            EarningsBucket::Month => b.start.format("%Y-%m").to_string(),
+        })
+        .collect::<Vec<_>>();
+    let value_axis = |show_labels: bool| {
+        Axis::new()
+            .type_(AxisType::Value)
+            .axis_label(AxisLabel::new().show(show_labels).color("#9CA3AF"))
+            .axis_line(AxisLine::new().show(false))
+            .split_line(SplitLine::new().show(false))
+    };
+
+    Chart::new()
+        .legend(Legend::new().text_style(TextStyle::new().color("#9CA3AF")))
+        .tooltip(Tooltip::new().trigger(Trigger::Axis))
+        .x_axis(
+            Axis::new()
+                .type_(AxisType::Category)
+                .data(labels)
+                .axis_line(AxisLine::new().show(false))
+                .axis_label(AxisLabel::new().color("#9CA3AF")),
+        )
+        // earned per bucket, cumulative earnings, and number of payments, each on its own scale
+        .y_axis(value_axis(true))
+        .y_axis(value_axis(true))
+        .y_axis(value_axis(false))
+        .series(
+            Bar::new()
+                .name("Earned")
+                .data(
+                    history
+                        .buckets
+                        .iter()
+                        .map(|b| attos_to_tokens(b.earned))
+                        .collect(),
+                )
+                .item_style(ItemStyle::new().color("#818CF8")),
+        )
+        .series(
+            Line::new()
+                .name("Cumulative")
+                .y_axis_index(1)
+                .data(
+                    history
+                        .buckets
+                        .iter()
+                        .map(|b| attos_to_tokens(b.cumulative))
+                        .collect(),
+                )
+                .smooth(true)
+                .show_symbol(false)
+                .item_style(ItemStyle::new().color("#34D399")),
+        )
+        .series(
+            Line::new()
+                .name("Payments")
+                .y_axis_index(2)
+                .data(
+                    history
+                        .buckets
+                        .iter()
+                        .map(|b| b.num_payments as f64)
+                        .collect(),
+                )
+                .show_symbol(false)
+                .item_style(ItemStyle::new().color("#FBBF24")),
+        )
+}
+
+// Earnings over time, of all addresses or only of the selected one, grouped by day, week or month.
+#[component]
+fn EarningsHistoryChart(selected_address: RwSignal<Option<String>>) -> impl IntoView {
+    let context = expect_context::<ClientGlobalState>();
+    let bucket = RwSignal::new(EarningsBucket::default());
+    let history = RwSignal::new(Vec::<EarningsHistory>::new());
+    let echarts = RwSignal::new_local(None::<charming::renderer::wasm_renderer::Echarts>);
+    // number of payments known, so the history is retrieved again when new ones are received
+    let num_payments = Memo::new(move |_| {
+        context.stats.with(|s| {
+            s.earnings
+                .iter()
+                .filter_map(|(_, e)| e.periods.last().map(|p| p.num_payments))
+                .sum::<usize>()
+        })
+    });
+
+    Effect::new(move |_| {
+        let bucket = bucket.get();
+        num_payments.track();
+        spawn_local(async move {
+            match earnings_history(bucket).await {
+                Ok(list) => history.set(list),
+                Err(err) => logging::error!("[ERROR] Failed to retrieve earnings history: {err}"),
+            }
+        });
+    });
+
+    let selected_history = move || {
+        let selected = selected_address
+            .get()
+            .and_then(|addr| addr.parse::<Address>().ok());
+        history.with(|list| match selected {
+            None => list.first().cloned(),
+            Some(addr) => list
+                .iter()
+                .find(|h| h.address.as_ref().and_then(|a| a.parse::<Address>().ok()) == Some(addr))
+                .cloned(),
+        })
+    };
+
+    Effect::new(move |_| {
+        // addresses without any payment within the charted buckets are not in the history
+        let h = selected_history().unwrap_or(EarningsHistory {
+            address: None,
+            buckets: vec![],
+        });
+        let chart = build_earnings_chart(&h, bucket.get());
+        if echarts.with(|e| e.is_none()) {
+            match WasmRenderer::new_opt(None, None).render(EARNINGS_CHART_ID, &chart) {
+                Ok(e) => echarts.set(Some(e)),
+                Err(err) => logging::error!("[ERROR] Failed to render earnings chart: {err}"),
+            }
+        } else {
+            echarts.with(|e| {
+                if let Some(e) = e {
+                    WasmRenderer::update(e, &chart);
+                }
+            });
+        }
+    });
+
+    let tab_class = |selected: bool| {
+        format!(
+            "px-3 py-1.5 text-xs font-bold rounded-lg transition-colors {}",
+            if selected {
+                "bg-indigo-500/20 text-indigo-300"
+            } else {
+                "text-slate-500 hover:text-slate-300"
+            },
+        )
+    };
+
+    view! {
+        <div class="px-6 pt-6 border-t border-slate-800">
+            <div class="flex items-center justify-between gap-4">
+                <h4 class="text-xs font-bold text-slate-500 uppercase tracking-widest">
+                    Earnings Over Time
+                </h4>
+                <div class="flex items-center gap-1 bg-slate-950 border border-slate-800 rounded-xl p-1">
+                    {EarningsBucket::ALL
+                        .into_iter()
+                        .map(|b| {
+                            view! {
+                                <button
+                                    class=move || tab_class(bucket.get() == b)
+                                    on:click=move |_| bucket.set(b)
+                                >
+                                    {b.label()}
+                                </button>
+                            }
+                        })
+                        .collect_view()}
+                </div>
+            </div>
+            <div class="relative" style="width: 100%; height: 320px;">
+                <div id=EARNINGS_CHART_ID style="width: 100%; height: 100%;" />
+                <Show when=move || {
+                    selected_history().is_none_or(|h| h.buckets.iter().all(|b| b.num_payments == 0))
+                }>
+                    <div class="absolute inset-0 flex items-center justify-center pointer-events-none">
+                        <p class="text-slate-400 text-sm">"No reward payments received yet"</p>
+                    </div>
+                </Show>
+            </div>
+        </div>
+    }
+}
+
+// Download of the reward payments received within a range of days, by all addresses or
+// only by the selected one, with their value in the fiat currency set in the settings.
+#[component]
+fn EarningsExportBar(selected_address: RwSignal<Option<String>>) -> impl IntoView {
+    let today = Utc::now().date_naive();
+    let from = RwSignal::new(
+        today
+            .checked_sub_days(Days::new(DEFAULT_EXPORT_DAYS))
+            .unwrap_or(today),
+    );
+    let to = RwSignal::new(today);
+    let format = RwSignal::new(ExportFormat::Csv);
+    let export = Action::new(
+        move |(from, to, address, format): &(
+            NaiveDate,
+            NaiveDate,
+            Option<String>,
+            ExportFormat,
+        )| {
+            let (from, to, address, format) = (*from, *to, address.clone(), *format);
+            async move {
+                let res = export_earnings(from, to, address)
+                    .await
+                    .map_err(|err| err.to_string())
+                    .and_then(|export| {
+                        download_file(
+                            &export.file_name(format),
+                            format.mime_type(),
+                            &export.render(format),
+                        )?;
+                        Ok(export.missing_prices)
+                    });
+                match res {
+                    Ok(0) => {}
+                    Ok(missing) => show_warning_alert_msg(format!(
+                        "Earnings export is incomplete: the token price couldn't be retrieved \
+                        for {missing} payment/s, which were exported without their fiat value"
+                    )),
+                    Err(err) => {
+                        let msg = format!("Failed to export earnings: {err}");
+                        logging::error!("[ERROR] {msg}");
+                        show_error_alert_msg(msg);
+                    }
+                }
+            }
+        },
+    );
+    let input_class = "bg-slate-950 border border-slate-700 text-xs text-slate-300 py-2 px-3 rounded-xl focus:outline-none focus:ring-2 focus:ring-indigo-500/50";
+    let on_date = move |signal: RwSignal<NaiveDate>| {
+        move |ev| {
+            if let Ok(date) = event_target_value(&ev).parse::<NaiveDate>() {
+                signal.set(date);
+            }
+        }
+    };
+
+    view! {
+        <footer class="px-6 py-4 border-t border-slate-800 bg-slate-800/10 flex flex-wrap items-center gap-3">
+            <span class="text-[11px] font-bold text-slate-500 uppercase tracking-widest mr-auto">
+                {move || {
+                    if selected_address.read().is_some() {
+                        "Export payments of this address"
+                    } else {
+                        "Export payments of all addresses"
+                    }
+                }}
+            </span>
+            <input
+                type="date"
+                title="From"
+                class=input_class
+                prop:value=move || from.get().to_string()
+                on:change=on_date(from)
+            />
+            <span class="text-slate-500 text-xs">"to"</span>
+            <input
+                type="date"
+                title="To"
+                class=input_class
+                prop:value=move || to.get().to_string()
+                on:change=on_date(to)
+            />
+            <select
+                class=input_class
+                prop:value=move || format.get().to_string()
+                on:change=move |ev| {
+                    if let Ok(f) = event_target_value(&ev).parse() {
+                        format.set(f);
+                    }
+                }
+            >
+                <option value="csv">CSV</option>
+                <option value="json">JSON</option>
+            </select>
+            <button
+                type="button"
+                class="bg-indigo-600 hover:bg-indigo-500 transition-colors text-white px-4 py-2 rounded-xl font-medium text-xs disabled:opacity-75 disabled:bg-slate-600 disabled:cursor-not-allowed"
+                prop:disabled=move || export.pending().get() || (from.get() > to.get())
+                on:click=move |_| {
+                    export
+                        .dispatch((
+                            from.get_untracked(),
+                            to.get_untracked(),
+                            selected_address.get_untracked(),
+                            format.get_untracked(),
+                        ));
+                }
+            >
+                {move || if export.pending().get() { "Exporting..." } else { "Download" }}
+            </button>
+        </footer>
+    }
+}
+
 #[component]
-fn PeriodStatCard(stats: PeriodStats) -> impl IntoView {
+pub(super) fn PeriodStatCard(stats: PeriodStats) -> impl IntoView {
     let is_positive = move || {
         stats.change_percent.is_none() || matches!(stats.change_percent, Some(v) if v >= 0.0)
     };
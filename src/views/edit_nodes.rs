@@ -0,0 +1,263 @@
+use crate::{
+    app::ClientGlobalState,
+    server_api::update_node_instance,
+    types::{BatchType, NodeConfigUpdate, NodeId, NodeStatus, NodeUpdated, Permission},
+};
+
+use super::{
+    form_inputs::{
+        CheckboxInput, Ipv4OnlySelect, LogLevelSelect, NumberInput, PortNumberInput,
+        RewardsAddrInput,
+    },
+    helpers::{show_error_alert_msg, show_success_alert_msg},
+    icons::{IconCancel, IconEdit},
+    node_actions::create_batch_on_selected,
+};
+
+use leptos::{logging, prelude::*, task::spawn_local};
+
+// Form to edit the configuration of a single node, or to create a batch
+// applying the same configuration changes to several nodes.
+#[component]
+pub fn EditNodesForm(node_ids: RwSignal<Option<Vec<NodeId>>>) -> impl IntoView {
+    let context = expect_context::<ClientGlobalState>();
+    let ids = StoredValue::new(node_ids.get_untracked().unwrap_or_default());
+    let is_single = ids.read_value().len() == 1;
+
+    // the form is pre-filled with the current configuration of the first node
+    let current = ids
+        .read_value()
+        .first()
+        .and_then(|id| context.nodes.read_untracked().1.get(id).cloned())
+        .map(|info| info.get_untracked())
+        .unwrap_or_default();
+
+    let change_ports = RwSignal::new(false);
+    let port = RwSignal::new(Ok(current.port.unwrap_or_default()));
+    let metrics_port = RwSignal::new(Ok(current.metrics_port.unwrap_or_default()));
+    let change_rewards_addr = RwSignal::new(false);
+    let rewards_addr = RwSignal::new(Ok(current.rewards_addr.clone().unwrap_or_default()));
+    let change_ipv4_only = RwSignal::new(false);
+    let ipv4_only = RwSignal::new(current.ipv4_only);
+    let change_logs = RwSignal::new(false);
+    let node_logs = RwSignal::new(current.node_logs);
+    let log_level = RwSignal::new(current.log_level.clone());
+    let interval = RwSignal::new(Ok(60));
+
+    // only the settings the user chose to change are sent,
+    // None is returned if there is none or any of them is invalid.
+    let changes = move || {
+        let mut changes = NodeConfigUpdate::default();
+        if change_ports.get() {
+            changes.port = Some(port.get().ok()?);
+            changes.metrics_port = Some(metrics_port.get().ok()?);
+        }
+        if change_rewards_addr.get() {
+            let addr = rewards_addr.get().ok()?;
+            changes.rewards_addr = Some(addr.strip_prefix("0x").unwrap_or(&addr).to_string());
+        }
+        if change_ipv4_only.get() {
+            changes.ipv4_only = Some(ipv4_only.get());
+        }
+        if change_logs.get() {
+            changes.node_logs = Some(node_logs.get());
+            changes.log_level = Some(log_level.get());
+        }
+        (!changes.is_empty()).then_some(changes)
+    };
+
+    let apply_changes = move || {
+        let (Some(changes), Ok(interval)) = (changes(), interval.get_untracked()) else {
+            return;
+        };
+        node_ids.set(None);
+        let ids = ids.get_value();
+        if let [node_id] = ids.as_slice() {
+            if let Some(info) = context.nodes.read_untracked().1.get(node_id)
+                && info.read_untracked().status.is_active()
+            {
+                info.update(|node| node.status = NodeStatus::Restarting);
+            }
+            let node_id = node_id.clone();
+            spawn_local(async move {
+                match update_node_instance(node_id.clone(), changes).await {
+                    Ok(NodeUpdated {
+                        info,
+                        previous_node_id: Some(_),
+                    }) => {
+                        show_success_alert_msg(format!(
+                            "Node {} updated, its container was recreated with the new ID {}",
+                            node_id.short_node_id(),
+                            info.node_id.short_node_id()
+                        ));
+                    }
+                    Ok(_) => {}
+                    Err(err) => {
+                        let msg = format!(
+                            "Failed to update configuration of node {}: {err}",
+                            node_id.short_node_id()
+                        );
+                        logging::error!("[ERROR] {msg}");
+                        show_error_alert_msg(msg);
+                    }
+                }
+            });
+        } else {
+            let batch_type = BatchType::Update {
+                node_ids: ids,
+                changes,
+            };
+            create_batch_on_selected(batch_type, interval as u64, context);
+        }
+    };
+
+    let section_class = move |enabled: RwSignal<bool>| {
+        move || {
+            format!(
+                "space-y-4 transition-opacity {}",
+                if enabled.get() {
+                    ""
+                } else {
+                    "opacity-40 pointer-events-none"
+                }
+            )
+        }
+    };
+
+    view! {
+        <div class="fixed inset-0 z-[100] flex items-center justify-center p-4 bg-black/90 backdrop-blur-sm animate-in fade-in duration-300">
+            <div class="bg-slate-900 border border-slate-800 w-full max-w-lg rounded-2xl overflow-hidden shadow-2xl animate-in zoom-in-95 duration-300">
+                <div class="p-6 border-b border-slate-800 flex items-center justify-between">
+                    <div class="flex items-center gap-4">
+                        <div class="text-indigo-400">
+                            <IconEdit />
+                        </div>
+                        <h3 class="text-xl font-bold">
+                            {if is_single {
+                                format!("Edit node {}", current.short_node_id())
+                            } else {
+                                format!("Edit {} nodes", ids.read_value().len())
+                            }}
+                        </h3>
+                    </div>
+                    <button
+                        on:click=move |_| node_ids.set(None)
+                        class="p-2 text-slate-500 hover:text-white transition-colors"
+                    >
+                        <IconCancel />
+                    </button>
+                </div>
+
+                <div class="p-8 space-y-6 max-h-[70vh] overflow-y-auto">
+                    <p class="text-sm text-slate-400">
+                        "Only the settings checked below are changed. Nodes keep their data and peer-id, running nodes are restarted with the new configuration."
+                    </p>
+
+                    <Show when=move || is_single>
+                        <CheckboxInput
+                            signal=change_ports
+                            id="change_ports"
+                            label="Change ports"
+                            help_msg="Ports can only be changed one node at a time."
+                            help_align="left-0"
+                        />
+                        <div class=section_class(change_ports)>
+                            <PortNumberInput
+                                id="port"
+                                signal=port
+                                default=port.get_untracked().unwrap_or_default()
+                                label="Port number:"
+                                help_msg="Node port number."
+                            />
+                            <PortNumberInput
+                                id="metrics_port"
+                                signal=metrics_port
+                                default=metrics_port.get_untracked().unwrap_or_default()
+                                label="Node metrics port number:"
+                                help_msg="Node metrics port number."
+                            />
+                        </div>
+                    </Show>
+
+                    // only admins can redirect the nodes earnings to another address
+                    <Show when=move || context.can(Permission::ManageSettings)>
+                        <CheckboxInput
+                            signal=change_rewards_addr
+                            id="change_rewards_addr"
+                            label="Change rewards address"
+                            help_msg="Set the address where the network rewards earned by the node/s will be sent."
+                            help_align="left-0"
+                        />
+                        <div class=section_class(change_rewards_addr)>
+                            <RewardsAddrInput signal=rewards_addr label="Rewards address:" />
+                        </div>
+                    </Show>
+
+                    <CheckboxInput
+                        signal=change_ipv4_only
+                        id="change_ipv4_only"
+                        label="Change IP version"
+                        help_msg="Switch the node/s between dual-stack and IPv4-only modes."
+                        help_align="left-0"
+                    />
+                    <div class=section_class(change_ipv4_only)>
+                        <Ipv4OnlySelect
+                            signal=ipv4_only
+                            label="IP version:"
+                            help_msg="'Dual-stack' uses both IPv4 and IPv6. 'IPv4 only' disables dual-stack, use on hosts without working IPv6."
+                        />
+                    </div>
+
+                    <CheckboxInput
+                        signal=change_logs
+                        id="change_logs"
+                        label="Change logging"
+                        help_msg="Enable or disable node logs, and set their verbosity level."
+                        help_align="left-0"
+                    />
+                    <div class=section_class(change_logs)>
+                        <CheckboxInput
+                            signal=node_logs
+                            id="node_logs"
+                            label="Enable logging"
+                            help_msg="Enable logging output for the node/s. When disabled, no log records are emitted."
+                            help_align="left-0"
+                        />
+                        <LogLevelSelect
+                            logs_enabled=node_logs
+                            signal=log_level
+                            label="Log level:"
+                            help_msg="Select the log verbosity level. Only applies when logging is enabled."
+                        />
+                    </div>
+
+                    <Show when=move || !is_single>
+                        <NumberInput
+                            id="update_interval"
+                            signal=interval
+                            min=0
+                            label="Delay (in seconds):"
+                            help_msg="The time to wait between updating each node in the batch."
+                        />
+                    </Show>
+                </div>
+
+                <div class="p-6 bg-slate-950 border-t border-slate-800 flex items-center justify-end gap-4">
+                    <button
+                        class="px-6 py-2.5 rounded-xl font-bold text-slate-400 hover:bg-slate-800 transition-colors"
+                        on:click=move |_| node_ids.set(None)
+                    >
+                        Cancel
+                    </button>
+                    <button
+                        prop:disabled=move || changes().is_none() || interval.read().is_err()
+                        on:click=move |_| apply_changes()
+                        class="bg-indigo-600 hover:bg-indigo-500 text-white px-6 py-2.5 rounded-lg font-bold transition-all shadow-lg shadow-indigo-500/20 disabled:opacity-75 disabled:bg-slate-600 disabled:text-slate-400 disabled:shadow-none disabled:cursor-not-allowed"
+                    >
+                        {if is_single { "Apply changes" } else { "Create batch" }}
+                    </button>
+                </div>
+            </div>
+        </div>
+    }
+}
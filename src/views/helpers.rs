@@ -5,10 +5,11 @@ use crate::{
         create_node_instance, delete_node_instance, nodes_actions_batch_create,
         start_node_logs_stream,
     },
-    types::{BatchType, NodeId, NodeOpts},
+    types::{BatchType, NodeId, NodeOpts, OtherBalances, RewardsAddrWeight},
 };
 
-use alloy_primitives::U256;
+use alloy_primitives::{U256, utils::format_units};
+use chrono::{DateTime, Local, Utc};
 use gloo_timers::future::TimeoutFuture;
 use leptos::{logging, prelude::*, task::spawn_local};
 
@@ -19,6 +20,15 @@ pub fn value_or_dash<T: ToString>(val: Option<T>) -> String {
     val.map_or("-".to_string(), |v| v.to_string())
 }
 
+// Format a UNIX timestamp (seconds) as a local date and time.
+pub fn format_timestamp(ts: i64) -> String {
+    DateTime::<Utc>::from_timestamp(ts, 0)
+        .unwrap_or_default()
+        .with_timezone(&Local)
+        .format("%Y-%m-%d %H:%M:%S")
+        .to_string()
+}
+
 // Format a U256 value truncating it to only 4 decimals if it's too large in attos.
 pub fn truncated_balance_str(v: U256) -> String {
     if v > U256::from(1_000_000u128) {
@@ -31,6 +41,32 @@ pub fn truncated_balance_str(v: U256) -> String {
     }
 }
 
+// Format a token amount with the given decimals, truncating it to only 4 decimals.
+pub fn truncated_units_str(v: U256, decimals: u8) -> String {
+    let units = format_units(v, decimals).unwrap_or_else(|_| v.to_string());
+    match units.split_once('.') {
+        Some((int, frac)) => format!("{int}.{}", &frac[..frac.len().min(4)]),
+        None => units,
+    }
+}
+
+// Format the ETH and additional tokens balances of an address, e.g. "0.0012 ETH, 5.0000 USDC".
+pub fn format_other_balances(balances: &OtherBalances) -> String {
+    balances
+        .eth
+        .map(|eth| format!("{} ETH", truncated_units_str(eth, 18)))
+        .into_iter()
+        .chain(balances.tokens.iter().map(|t| {
+            format!(
+                "{} {}",
+                truncated_units_str(t.balance, t.decimals),
+                t.symbol
+            )
+        }))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 // Shows an error alert message in the UI.
 pub fn show_error_alert_msg(msg: String) {
     let notif = Notification::new_error(msg.clone());
@@ -38,12 +74,17 @@ pub fn show_error_alert_msg(msg: String) {
 }
 
 // Shows a warning alert message in the UI.
-#[cfg(feature = "hydrate")]
 pub fn show_warning_alert_msg(msg: String) {
     let notif = Notification::new_warning(msg.clone());
     show_alert_msg(notif);
 }
 
+// Shows a success alert message in the UI.
+pub fn show_success_alert_msg(msg: String) {
+    let notif = Notification::new_success(msg.clone());
+    show_alert_msg(notif);
+}
+
 // Helper to show an alert message in the UI.
 fn show_alert_msg(notif: Notification) {
     let context = expect_context::<ClientGlobalState>();
@@ -60,10 +101,12 @@ fn show_alert_msg(notif: Notification) {
     });
 }
 
-// Creates and add new node instances
+// Creates and add new node instances, distributing them across
+// the given rewards addresses if more than one node is created
 pub async fn add_node_instances(
     node_opts: NodeOpts,
     count: u16,
+    rewards_addrs: Vec<RewardsAddrWeight>,
     interval_secs: u64,
 ) -> Result<(), ServerFnError> {
     let context = expect_context::<ClientGlobalState>();
@@ -72,7 +115,11 @@ pub async fn add_node_instances(
         context
             .is_action_triggered
             .set(ActionTriggered::BatchCreatingNodes);
-        let batch_type = BatchType::Create { node_opts, count };
+        let batch_type = BatchType::Create {
+            node_opts,
+            count,
+            rewards_addrs,
+        };
         match nodes_actions_batch_create(batch_type, interval_secs).await {
             Ok(_batch_id) => Ok(()),
             Err(err) => {
@@ -94,6 +141,28 @@ pub async fn add_node_instances(
     }
 }
 
+// Lets the user download the given content as a file with the given name
+pub fn download_file(file_name: &str, mime_type: &str, content: &str) -> Result<(), String> {
+    use web_sys::{
+        Blob, BlobPropertyBag, HtmlAnchorElement, Url, js_sys::Array, wasm_bindgen::JsCast,
+    };
+
+    let parts = Array::of1(&content.into());
+    let props = BlobPropertyBag::new();
+    props.set_type(mime_type);
+    let blob = Blob::new_with_str_sequence_and_options(&parts, &props)
+        .map_err(|err| format!("{err:?}"))?;
+    let url = Url::create_object_url_with_blob(&blob).map_err(|err| format!("{err:?}"))?;
+    let anchor = document()
+        .create_element("a")
+        .map_err(|err| format!("{err:?}"))?
+        .unchecked_into::<HtmlAnchorElement>();
+    anchor.set_href(&url);
+    anchor.set_download(file_name);
+    anchor.click();
+    Url::revoke_object_url(&url).map_err(|err| format!("{err:?}"))
+}
+
 // Removes a node instance with given id and updates given signal
 pub async fn remove_node_instance(node_id: NodeId) -> Result<(), ServerFnError> {
     let context = expect_context::<ClientGlobalState>();
@@ -0,0 +1,400 @@
+use crate::{
+    app::ClientGlobalState,
+    server_api::host_history,
+    types::{HostSnapshot, HostStats, MonthlyTraffic, StatsHistoryRange},
+};
+
+use super::{
+    GB_CONVERTION, format_disk_usage,
+    icons::IconCpu,
+    stats_history::{StatsSeries, build_stats_chart},
+};
+
+use charming::WasmRenderer;
+use leptos::{logging, prelude::*, task::spawn_local};
+
+const HOST_CHART_ID: &str = "host_history_chart";
+const KB_CONVERTION: f64 = 1_024.0;
+
+// Set of host resources which can be charted.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum HostChartKind {
+    Cpu,
+    Memory,
+    Load,
+    Network,
+    DiskIo,
+}
+
+impl HostChartKind {
+    const ALL: [Self; 5] = [
+        Self::Cpu,
+        Self::Memory,
+        Self::Load,
+        Self::Network,
+        Self::DiskIo,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Cpu => "CPU",
+            Self::Memory => "Memory",
+            Self::Load => "Load",
+            Self::Network => "Network",
+            Self::DiskIo => "Disk I/O",
+        }
+    }
+
+    // Name, color and data points of each series to chart.
+    fn series(&self, history: &[HostSnapshot]) -> Vec<StatsSeries> {
+        let points = |f: &dyn Fn(&HostSnapshot) -> Option<f64>| {
+            history
+                .iter()
+                .filter_map(|s| f(s).map(|v| (s.timestamp, v)))
+                .collect::<Vec<_>>()
+        };
+        match self {
+            Self::Cpu => vec![("CPU usage (%)", "#818CF8", points(&|s| s.cpu_usage))],
+            Self::Memory => vec![
+                (
+                    "Memory used (GB)",
+                    "#34D399",
+                    points(&|s| s.mem_used.map(|v| v / GB_CONVERTION)),
+                ),
+                (
+                    "Swap used (GB)",
+                    "#F87171",
+                    points(&|s| s.swap_used.map(|v| v / GB_CONVERTION)),
+                ),
+            ],
+            Self::Load => vec![("Load average (1m)", "#FBBF24", points(&|s| s.load_avg))],
+            // throughput of all interfaces is aggregated, the breakdown is shown with current values
+            Self::Network => vec![
+                (
+                    "Received (KB/s)",
+                    "#22D3EE",
+                    points(&|s| {
+                        (!s.networks.is_empty()).then(|| {
+                            s.networks.values().map(|(rx, _)| rx).sum::<f64>() / KB_CONVERTION
+                        })
+                    }),
+                ),
+                (
+                    "Transmitted (KB/s)",
+                    "#A3E635",
+                    points(&|s| {
+                        (!s.networks.is_empty()).then(|| {
+                            s.networks.values().map(|(_, tx)| tx).sum::<f64>() / KB_CONVERTION
+                        })
+                    }),
+                ),
+            ],
+            Self::DiskIo => vec![
+                (
+                    "Read (KB/s)",
+                    "#818CF8",
+                    points(&|s| s.disk_read_rate.map(|v| v / KB_CONVERTION)),
+                ),
+                (
+                    "Written (KB/s)",
+                    "#FB7185",
+                    points(&|s| s.disk_write_rate.map(|v| v / KB_CONVERTION)),
+                ),
+            ],
+        }
+    }
+}
+
+/// Format a throughput given in bytes per second.
+pub fn format_rate(bytes_per_sec: f64) -> String {
+    format!("{}/s", format_disk_usage(bytes_per_sec as u64))
+}
+
+// Current resources usage of the host along with its history.
+#[component]
+pub fn HostCard() -> impl IntoView {
+    let context = expect_context::<ClientGlobalState>();
+    let range = RwSignal::new(StatsHistoryRange::default());
+    let kind = RwSignal::new(HostChartKind::Cpu);
+    let history = RwSignal::new(Vec::<HostSnapshot>::new());
+    let echarts = RwSignal::new_local(None::<charming::renderer::wasm_renderer::Echarts>);
+
+    // Retrieve the history from the backend every time a different range is selected
+    Effect::new(move |_| {
+        let range = range.get();
+        spawn_local(async move {
+            match host_history(range).await {
+                Ok(snapshots) => history.set(snapshots),
+                Err(err) => logging::error!("[ERROR] Failed to retrieve host history: {err}"),
+            }
+        });
+    });
+
+    Effect::new(move |_| {
+        let chart = build_stats_chart(kind.get().series(&history.read()), range.get());
+        if echarts.with(|h| h.is_none()) {
+            match WasmRenderer::new_opt(None, None).render(HOST_CHART_ID, &chart) {
+                Ok(e) => echarts.set(Some(e)),
+                Err(err) => logging::error!("[ERROR] Failed to render host chart: {err}"),
+            }
+        } else {
+            echarts.with(|h| {
+                if let Some(e) = h {
+                    WasmRenderer::update(e, &chart);
+                }
+            });
+        }
+    });
+
+    let tab_class = |selected: bool| {
+        format!(
+            "px-3 py-1.5 text-xs font-bold rounded-lg transition-colors {}",
+            if selected {
+                "bg-indigo-500/20 text-indigo-300"
+            } else {
+                "text-slate-500 hover:text-slate-300"
+            },
+        )
+    };
+
+    view! {
+        <div class="bg-slate-900 border border-slate-800 rounded-3xl overflow-hidden shadow-2xl">
+            <header class="p-6 border-b border-slate-800 bg-slate-800/10 backdrop-blur-md flex flex-col md:flex-row md:items-center justify-between gap-4">
+                <div class="flex items-center gap-4">
+                    <div class="p-3 bg-cyan-500/10 rounded-2xl text-cyan-400">
+                        <IconCpu />
+                    </div>
+                    <div>
+                        <h3 class="text-xl font-bold text-white tracking-tight">Host</h3>
+                        <p class="text-xs text-slate-500 font-medium uppercase tracking-widest mt-1">
+                            Resources Usage of the Host Machine
+                        </p>
+                    </div>
+                </div>
+
+                <div class="flex flex-wrap items-center gap-2">
+                    <div class="flex items-center gap-1 bg-slate-950 border border-slate-800 rounded-xl p-1">
+                        {HostChartKind::ALL
+                            .into_iter()
+                            .map(|k| {
+                                view! {
+                                    <button
+                                        class=move || tab_class(kind.get() == k)
+                                        on:click=move |_| kind.set(k)
+                                    >
+                                        {k.label()}
+                                    </button>
+                                }
+                            })
+                            .collect_view()}
+                    </div>
+                    <div class="flex items-center gap-1 bg-slate-950 border border-slate-800 rounded-xl p-1">
+                        {StatsHistoryRange::ALL
+                            .into_iter()
+                            .map(|r| {
+                                view! {
+                                    <button
+                                        class=move || tab_class(range.get() == r)
+                                        on:click=move |_| range.set(r)
+                                    >
+                                        {r.label()}
+                                    </button>
+                                }
+                            })
+                            .collect_view()}
+                    </div>
+                </div>
+            </header>
+
+            {move || match context.stats.read().host.clone() {
+                Some(host) => view! { <HostCurrentStats host /> }.into_any(),
+                None => {
+                    view! {
+                        <div class="p-6 text-sm text-slate-500 italic">
+                            "Sampling host resources usage..."
+                        </div>
+                    }
+                        .into_any()
+                }
+            }}
+
+            {move || {
+                context
+                    .stats
+                    .read()
+                    .monthly_traffic
+                    .clone()
+                    .map(|traffic| view! { <MonthlyTrafficView traffic /> })
+            }}
+
+            <div class="relative p-4 border-t border-slate-800" style="width: 100%; height: 340px;">
+                <div id=HOST_CHART_ID style="width: 100%; height: 100%;" />
+                <Show when=move || history.read().is_empty()>
+                    <div class="absolute inset-0 flex items-center justify-center pointer-events-none">
+                        <p class="text-slate-400 text-sm">"No host history available yet"</p>
+                    </div>
+                </Show>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn HostCurrentStats(host: HostStats) -> impl IntoView {
+    let percentage = |v: Option<f64>| v.map_or("-".to_string(), |v| format!("{v:.1}%"));
+    let summary = [
+        (
+            "CPU",
+            format!("{:.1}%", host.cpu_usage),
+            format!("{} cores", host.cpus),
+        ),
+        (
+            "Memory",
+            percentage(host.mem_usage()),
+            format!(
+                "{} / {}",
+                format_disk_usage(host.mem_used),
+                format_disk_usage(host.mem_total)
+            ),
+        ),
+        (
+            "Swap",
+            percentage(host.swap_usage()),
+            format!(
+                "{} / {}",
+                format_disk_usage(host.swap_used),
+                format_disk_usage(host.swap_total)
+            ),
+        ),
+        (
+            "Load Average",
+            format!("{:.2}", host.load_avg[0]),
+            format!(
+                "{:.2} / {:.2} (5m / 15m)",
+                host.load_avg[1], host.load_avg[2]
+            ),
+        ),
+        (
+            "Disk I/O",
+            format_rate(host.disk_read_rate + host.disk_write_rate),
+            format!(
+                "R {} / W {}",
+                format_rate(host.disk_read_rate),
+                format_rate(host.disk_write_rate)
+            ),
+        ),
+    ];
+
+    view! {
+        <div class="p-6 grid grid-cols-2 md:grid-cols-5 gap-4 bg-slate-950/20">
+            {summary
+                .into_iter()
+                .map(|(label, value, detail)| {
+                    view! {
+                        <div class="bg-slate-900/60 border border-slate-800/80 rounded-2xl p-4 flex flex-col gap-1">
+                            <span class="text-[11px] font-bold text-slate-500 uppercase tracking-widest">
+                                {label}
+                            </span>
+                            <span class="text-lg font-bold font-mono tracking-tight text-white">
+                                {value}
+                            </span>
+                            <span class="text-xs text-slate-500 font-mono">{detail}</span>
+                        </div>
+                    }
+                })
+                .collect_view()}
+        </div>
+        {(!host.networks.is_empty())
+            .then(|| {
+                view! {
+            <div class="overflow-x-auto border-t border-slate-800">
+                <table class="w-full text-left">
+                    <thead>
+                        <tr class="bg-slate-800/50 text-slate-400 text-xs uppercase tracking-wider">
+                            <th class="px-6 py-3 font-semibold">Interface</th>
+                            <th class="px-6 py-3 font-semibold text-center">Received</th>
+                            <th class="px-6 py-3 font-semibold text-center">Transmitted</th>
+                        </tr>
+                    </thead>
+                    <tbody class="divide-y divide-slate-800 text-sm">
+                        {host
+                            .networks
+                            .into_iter()
+                            .map(|net| {
+                                view! {
+                                    <tr class="hover:bg-slate-800/30 transition-colors">
+                                        <td class="px-6 py-3 font-mono text-indigo-400">
+                                            {net.interface}
+                                        </td>
+                                        <td class="px-6 py-3 text-center font-mono">
+                                            {format_rate(net.received_rate)}
+                                        </td>
+                                        <td class="px-6 py-3 text-center font-mono">
+                                            {format_rate(net.transmitted_rate)}
+                                        </td>
+                                    </tr>
+                                }
+                            })
+                            .collect_view()}
+                    </tbody>
+                </table>
+            </div>
+                }
+            })}
+    }
+}
+
+// Traffic of the host during the current month, along with the usage of the cap if there is one.
+#[component]
+fn MonthlyTrafficView(traffic: MonthlyTraffic) -> impl IntoView {
+    let usage = traffic.cap_usage();
+    let (bar_color, text_color) = match usage {
+        Some(u) if u >= 90.0 => ("bg-rose-500", "text-rose-400"),
+        Some(u) if u >= 75.0 => ("bg-amber-500", "text-amber-400"),
+        _ => ("bg-cyan-500", "text-cyan-400"),
+    };
+
+    view! {
+        <div class="px-6 py-4 border-t border-slate-800 flex flex-col gap-3">
+            <div class="flex flex-wrap items-baseline justify-between gap-2">
+                <span class="text-[11px] font-bold text-slate-500 uppercase tracking-widest">
+                    {format!("Traffic in {}", traffic.month)}
+                </span>
+                <span class="text-xs text-slate-500 font-mono">
+                    {format!(
+                        "In {} / Out {}",
+                        format_disk_usage(traffic.received),
+                        format_disk_usage(traffic.transmitted),
+                    )}
+                </span>
+            </div>
+            <div class="flex items-baseline gap-2">
+                <span class=format!("text-lg font-bold font-mono tracking-tight {text_color}")>
+                    {format_disk_usage(traffic.total())}
+                </span>
+                <span class="text-xs text-slate-500 font-mono">
+                    {traffic
+                        .cap
+                        .zip(usage)
+                        .map_or(
+                            "no monthly cap set".to_string(),
+                            |(cap, usage)| {
+                                format!("of {} cap ({usage:.1}%)", format_disk_usage(cap))
+                            },
+                        )}
+                </span>
+            </div>
+            {usage
+                .map(|usage| {
+                    view! {
+                        <div class="w-full h-2 bg-slate-800 rounded-full overflow-hidden">
+                            <div
+                                class=format!("h-full rounded-full {bar_color}")
+                                style=format!("width: {:.1}%", usage.min(100.0))
+                            />
+                        </div>
+                    }
+                })}
+        </div>
+    }
+}
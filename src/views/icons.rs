@@ -197,6 +197,26 @@ pub fn IconRecycle() -> impl IntoView {
     }
 }
 
+#[component]
+pub fn IconEdit() -> impl IntoView {
+    view! {
+        <svg
+            class="w-5 h-5"
+            xmlns="http://www.w3.org/2000/svg"
+            fill="none"
+            viewBox="0 0 24 24"
+            stroke="currentColor"
+        >
+            <path
+                stroke-linecap="round"
+                stroke-linejoin="round"
+                stroke-width="2"
+                d="M16.862 4.487l1.687-1.688a1.875 1.875 0 112.652 2.652L7.5 19.153 3 21l1.847-4.5L16.862 4.487z"
+            />
+        </svg>
+    }
+}
+
 #[component]
 pub fn IconRemove() -> impl IntoView {
     view! {
@@ -233,6 +253,41 @@ pub fn IconStartNode() -> impl IntoView {
     }
 }
 
+#[component]
+pub fn IconPause() -> impl IntoView {
+    view! {
+        <svg
+            class="w-5 h-5"
+            xmlns="http://www.w3.org/2000/svg"
+            fill="none"
+            viewBox="0 0 24 24"
+            stroke="currentColor"
+        >
+            <rect x="6" y="5" width="4" height="14" rx="1" stroke-width="2" />
+            <rect x="14" y="5" width="4" height="14" rx="1" stroke-width="2" />
+        </svg>
+    }
+}
+
+#[component]
+pub fn IconRetry() -> impl IntoView {
+    view! {
+        <svg
+            class="w-5 h-5"
+            xmlns="http://www.w3.org/2000/svg"
+            fill="none"
+            viewBox="0 0 24 24"
+            stroke="currentColor"
+            stroke-width="2"
+            stroke-linecap="round"
+            stroke-linejoin="round"
+        >
+            <path d="M3 12a9 9 0 1 0 9-9 9.75 9.75 0 0 0-6.74 2.74L3 8" />
+            <path d="M3 3v5h5" />
+        </svg>
+    }
+}
+
 #[component]
 pub fn IconStopNode() -> impl IntoView {
     view! {
@@ -857,6 +912,26 @@ pub fn IconActivity(#[prop(default = "w-6 h-6")] class: &'static str) -> impl In
     }
 }
 
+#[component]
+pub fn IconHistory(#[prop(default = "w-6 h-6")] class: &'static str) -> impl IntoView {
+    view! {
+        <svg
+            class=class.to_string()
+            xmlns="http://www.w3.org/2000/svg"
+            viewBox="0 0 24 24"
+            fill="none"
+            stroke="currentColor"
+            stroke-width="2"
+            stroke-linecap="round"
+            stroke-linejoin="round"
+        >
+            <path d="M3 12a9 9 0 1 0 9-9 9.75 9.75 0 0 0-6.74 2.74L3 8" />
+            <path d="M3 3v5h5" />
+            <path d="M12 7v5l4 2" />
+        </svg>
+    }
+}
+
 #[component]
 pub fn IconPeers(#[prop(default = "w-6 h-6")] class: &'static str) -> impl IntoView {
     view! {
@@ -1110,3 +1185,23 @@ pub fn IconTrendingUp(#[prop(default = "w-5 h-5")] class: &'static str) -> impl
         </svg>
     }
 }
+
+#[component]
+pub fn IconLogout(#[prop(default = "w-5 h-5")] class: &'static str) -> impl IntoView {
+    view! {
+        <svg
+            class=class.to_string()
+            xmlns="http://www.w3.org/2000/svg"
+            viewBox="0 0 24 24"
+            fill="none"
+            stroke="currentColor"
+            stroke-width="2"
+            stroke-linecap="round"
+            stroke-linejoin="round"
+        >
+            <path d="M9 21H5a2 2 0 0 1-2-2V5a2 2 0 0 1 2-2h4" />
+            <polyline points="16 17 21 12 16 7" />
+            <line x1="21" y1="12" x2="9" y2="12" />
+        </svg>
+    }
+}
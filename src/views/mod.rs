@@ -3,46 +3,59 @@ mod actions_batch;
 mod add_nodes;
 mod agent_view;
 mod alerts;
+mod auth;
 mod chart;
 mod dashboard;
 mod earnings;
+mod edit_nodes;
 mod form_inputs;
 mod helpers;
+mod host;
 mod icons;
 mod logs_viewer;
 mod mcp_view;
 mod node_actions;
 mod node_instance;
 mod nodes_list;
+mod notification_channels;
 mod notifications;
 mod pagination;
+mod profitability;
+mod rewards_addresses;
 mod settings;
 mod sidebar;
 mod sort_nodes;
+mod stats_history;
 pub mod terminal;
+mod users;
 
-pub use helpers::truncated_balance_str;
+pub use auth::LoginView;
+pub use helpers::{format_other_balances, format_timestamp, truncated_balance_str};
 #[cfg(feature = "hydrate")]
 pub use helpers::{show_error_alert_msg, show_warning_alert_msg};
+pub use host::format_rate;
 pub use notifications::{Notification, NotificationsView};
 
 use self::{
     about::AboutView,
+    actions_batch::BatchesHistoryView,
     add_nodes::AddNodesForm,
     agent_view::AgentView,
-    alerts::{AlertMsg, OfflineMsg},
-    chart::MetricsViewerModal,
+    alerts::{AlertMsg, AlertsView, OfflineMsg},
+    auth::LogoutButton,
+    chart::{ChartSeriesData, MetricsViewerModal},
     dashboard::DashboardView,
     icons::{IconAddNode, IconBell, IconHamburguer},
     logs_viewer::LogViewerModal,
     mcp_view::McpView,
     nodes_list::NodesListView,
+    rewards_addresses::RewardsAddressesView,
     settings::SettingsView,
     sidebar::Sidebar,
     terminal::TerminalView,
 };
 
-use crate::app::ClientGlobalState;
+use crate::{app::ClientGlobalState, types::Permission};
 
 use leptos::prelude::*;
 use std::fmt;
@@ -63,6 +76,9 @@ pub fn format_disk_usage(v: u64) -> String {
 enum ViewType {
     Dashboard,
     Nodes,
+    Addresses,
+    Batches,
+    Alerts,
     Terminal,
     Mcp,
     Agent,
@@ -75,6 +91,9 @@ impl fmt::Display for ViewType {
         let label = match self {
             ViewType::Dashboard => "Dashboard",
             ViewType::Nodes => "Nodes",
+            ViewType::Addresses => "Rewards Addresses",
+            ViewType::Batches => "Batches",
+            ViewType::Alerts => "Alerts",
             ViewType::Terminal => "Terminal",
             ViewType::Mcp => "MCP Server",
             ViewType::Agent => "AI Agent",
@@ -96,7 +115,7 @@ pub fn HomeScreenView() -> impl IntoView {
     // this signal keeps the reactive list of log entries
     let (logs, set_logs) = signal(Vec::new());
 
-    let (chart_data, set_chart_data) = signal((vec![], vec![]));
+    let (chart_data, set_chart_data) = signal(ChartSeriesData::default());
     let set_render_chart = RwSignal::new(false);
 
     view! {
@@ -156,13 +175,17 @@ pub fn HomeScreenView() -> impl IntoView {
                             <NotificationsView is_open=notifications_open />
                         </div>
 
-                        <button
-                            on:click=move |_| add_nodes_modal_open.set(true)
-                            class="flex items-center gap-2 bg-indigo-600 hover:bg-indigo-500 transition-colors text-white px-4 py-1.5 rounded-lg font-medium text-sm shadow-lg shadow-indigo-500/20"
-                        >
-                            <IconAddNode />
-                            <span class="hidden sm:inline">Add Node</span>
-                        </button>
+                        <Show when=move || context.can(Permission::OperateNodes)>
+                            <button
+                                on:click=move |_| add_nodes_modal_open.set(true)
+                                class="flex items-center gap-2 bg-indigo-600 hover:bg-indigo-500 transition-colors text-white px-4 py-1.5 rounded-lg font-medium text-sm shadow-lg shadow-indigo-500/20"
+                            >
+                                <IconAddNode />
+                                <span class="hidden sm:inline">Add Node</span>
+                            </button>
+                        </Show>
+
+                        <LogoutButton />
                     </div>
                 </header>
 
@@ -187,6 +210,9 @@ pub fn HomeScreenView() -> impl IntoView {
                             view! { <NodesListView set_logs set_render_chart set_chart_data /> }
                                 .into_any()
                         }
+                        ViewType::Addresses => view! { <RewardsAddressesView /> }.into_any(),
+                        ViewType::Batches => view! { <BatchesHistoryView /> }.into_any(),
+                        ViewType::Alerts => view! { <AlertsView /> }.into_any(),
                         ViewType::Terminal => view! { <TerminalView /> }.into_any(),
                         ViewType::Settings => view! { <SettingsView /> }.into_any(),
                         ViewType::About => view! { <AboutView /> }.into_any(),
@@ -4,7 +4,7 @@ use crate::{
         nodes_actions_batch_create, recycle_node_instance, start_node_instance, stop_node_instance,
         upgrade_node_instance,
     },
-    types::{BatchType, NodeInstanceInfo, NodeStatus, NodesActionsBatch, Stats},
+    types::{BatchType, NodeInstanceInfo, NodeStatus, NodesActionsBatch, Permission, Stats},
 };
 
 use super::{
@@ -25,6 +25,14 @@ pub enum NodeAction {
 }
 
 impl NodeAction {
+    // Permission required to apply the action
+    pub fn permission(&self) -> Permission {
+        match self {
+            Self::Remove => Permission::RemoveNodes,
+            Self::Start | Self::Stop | Self::Upgrade | Self::Recycle => Permission::OperateNodes,
+        }
+    }
+
     // Apply the action to the given node instance
     pub async fn apply(&self, info: &RwSignal<NodeInstanceInfo>, stats: &RwSignal<Stats>) {
         let node_id = info.read_untracked().node_id.clone();
@@ -313,6 +321,16 @@ fn apply_on_selected(action: NodeAction, interval: u64, context: ClientGlobalSta
         NodeAction::Remove => BatchType::Remove(selected),
     };
 
+    create_batch_on_selected(batch_type, interval, context);
+}
+
+// Helper to create a batch to action on the set of nodes selected by the user, marking
+// them as locked till the running batch actions on them.
+pub(super) fn create_batch_on_selected(
+    batch_type: BatchType,
+    interval: u64,
+    context: ClientGlobalState,
+) {
     spawn_local(async move {
         match nodes_actions_batch_create(batch_type.clone(), interval).await {
             Ok(batch_id) => {
@@ -333,7 +351,7 @@ fn apply_on_selected(action: NodeAction, interval: u64, context: ClientGlobalSta
                 });
             }
             Err(err) => {
-                let msg = format!("Failed to schedule batch of {action:?}: {err:?}");
+                let msg = format!("Failed to schedule batch of {batch_type}: {err:?}");
                 logging::error!("[ERROR] {msg}");
                 show_error_alert_msg(msg);
             }
@@ -1,16 +1,17 @@
 use super::{
     chart::{ChartSeriesData, node_metrics_update},
-    format_disk_usage,
+    format_disk_usage, format_rate,
     helpers::{node_logs_stream, show_error_alert_msg, truncated_balance_str, value_or_dash},
     icons::{
-        IconChevronDown, IconRecycle, IconRemove, IconShowChart, IconShowLogs, IconStartNode,
-        IconStopNode, IconUpgradeNode,
+        IconChevronDown, IconEdit, IconRecycle, IconRemove, IconShowChart, IconShowLogs,
+        IconStartNode, IconStopNode, IconUpgradeNode,
     },
     node_actions::NodeAction,
+    rewards_addresses::AddressLabel,
 };
 use crate::{
     app::ClientGlobalState,
-    types::{InactiveReason, NodeId, NodeInstanceInfo, NodeStatus},
+    types::{InactiveReason, NodeId, NodeInstanceInfo, NodeStatus, Permission},
 };
 
 use alloy_primitives::utils::format_units;
@@ -26,6 +27,7 @@ pub(super) fn NodeInstanceView(
     set_logs: WriteSignal<Vec<String>>,
     set_render_chart: RwSignal<bool>,
     set_chart_data: WriteSignal<ChartSeriesData>,
+    modal_edit_nodes: RwSignal<Option<Vec<NodeId>>>,
 ) -> impl IntoView {
     let context = expect_context::<ClientGlobalState>();
     let is_selected = move || {
@@ -38,7 +40,10 @@ pub(super) fn NodeInstanceView(
     };
     let node_card_clicked = move |always: bool| {
         let (is_selecting, _) = *context.selecting_nodes.read_untracked();
-        if (always || is_selecting) && !info.read_untracked().is_status_locked {
+        if (always || is_selecting)
+            && !info.read_untracked().is_status_locked
+            && context.can(Permission::OperateNodes)
+        {
             let node_id = &info.read_untracked().node_id;
             context.selecting_nodes.update(|(is_selecting, selected)| {
                 if selected.contains(node_id) {
@@ -147,7 +152,7 @@ pub(super) fn NodeInstanceView(
                                     {info.read_untracked().short_node_id().to_string()}
                                 </div>
                             </div>
-                            <div class="md:col-span-5 flex items-center justify-between md:justify-start gap-4">
+                            <div class="md:col-span-4 flex items-center justify-between md:justify-start gap-4">
                                 <span class="md:hidden text-xs font-bold text-slate-500 uppercase w-20">
                                     Status
                                 </span>
@@ -203,6 +208,16 @@ pub(super) fn NodeInstanceView(
                                     }
                                 }>{move || value_or_dash(info.read().connected_peers)}</span>
                             </div>
+                            <div class="md:col-span-1 flex items-center justify-between md:justify-center gap-4">
+                                <span class="md:hidden text-xs font-bold text-slate-500 uppercase w-20">
+                                    Earned
+                                </span>
+                                <span class="font-mono text-emerald-400">
+                                    {move || value_or_dash(
+                                        info.read().estimated_earnings.map(truncated_balance_str),
+                                    )}
+                                </span>
+                            </div>
                             <div
                                 class="md:col-span-3 flex flex-wrap items-center justify-center gap-1 text-slate-400"
                                 on:click=move |e| e.stop_propagation()
@@ -212,6 +227,7 @@ pub(super) fn NodeInstanceView(
                                 <Show when=move || info.read().upgradeable()>
                                     <ButtonUpgrade info />
                                 </Show>
+                                <ButtonEdit info modal_edit_nodes />
                                 <button
                                     on:click=move |_| {
                                         if is_expanded() {
@@ -292,6 +308,7 @@ pub(super) fn NodeInstanceView(
                             <ButtonUpgrade info />
                         </Show>
                         <ButtonStopStart info />
+                        <ButtonEdit info modal_edit_nodes />
                         <ButtonRecycle info />
                         <ButtonRemove info />
                     </div>
@@ -456,6 +473,12 @@ fn ExpandedNodeDetails(info: RwSignal<NodeInstanceInfo>) -> impl IntoView {
                     </div>
                 </div>
             </DetailItemView>
+            <DetailItemView
+                label="Estimated Earnings"
+                children_class=Signal::stored("text-emerald-400")
+            >
+                {move || value_or_dash(info.read().estimated_earnings.map(truncated_balance_str))}
+            </DetailItemView>
             <DetailItemView label="kBuckets Peers">
                 {move || value_or_dash(info.read().kbuckets_peers)}
             </DetailItemView>
@@ -465,6 +488,32 @@ fn ExpandedNodeDetails(info: RwSignal<NodeInstanceInfo>) -> impl IntoView {
             <DetailItemView label="Relevant Chunks">
                 {move || value_or_dash(info.read().relevant_records)}
             </DetailItemView>
+            <DetailItemView label="Network In" children_class=Signal::stored("text-cyan-400")>
+                {move || {
+                    let info = info.read();
+                    value_or_dash(
+                        info
+                            .net_rx_rate
+                            .zip(info.net_rx_bytes)
+                            .map(|(rate, bytes)| {
+                                format!("{} ({})", format_rate(rate), format_disk_usage(bytes))
+                            }),
+                    )
+                }}
+            </DetailItemView>
+            <DetailItemView label="Network Out" children_class=Signal::stored("text-lime-400")>
+                {move || {
+                    let info = info.read();
+                    value_or_dash(
+                        info
+                            .net_tx_rate
+                            .zip(info.net_tx_bytes)
+                            .map(|(rate, bytes)| {
+                                format!("{} ({})", format_rate(rate), format_disk_usage(bytes))
+                            }),
+                    )
+                }}
+            </DetailItemView>
 
             <DetailItemView label="IPv4 only">
                 {move || info.read().ipv4_only.to_string()}
@@ -497,7 +546,10 @@ fn ExpandedNodeDetails(info: RwSignal<NodeInstanceInfo>) -> impl IntoView {
             </div>
 
             <DetailItemView label="Rewards Address" full_width=true>
-                {value_or_dash(info.read_untracked().short_rewards_addr())}
+                {match info.read_untracked().rewards_addr.clone() {
+                    Some(address) => view! { <AddressLabel address /> }.into_any(),
+                    None => "-".into_any(),
+                }}
             </DetailItemView>
             <Show when=move || { !custom_data_dir().is_none_or(|p| p.is_empty()) }>
                 <div class="col-span-full">
@@ -555,7 +607,9 @@ fn NodeSelection(info: RwSignal<NodeInstanceInfo>) -> impl IntoView {
             type="checkbox"
             prop:checked=is_selected
             prop:disabled=move || info.read().is_status_locked
-            prop:hidden=move || info.read().status.is_transitioning()
+            prop:hidden=move || {
+                info.read().status.is_transitioning() || !context.can(Permission::OperateNodes)
+            }
             on:click=move |event| event.stop_propagation()
             on:change=move |_| select_node_clicked()
             class="w-5 h-5 mt-0.5 rounded-md border-slate-700 bg-slate-800 text-indigo-600 focus:ring-indigo-500/20 shrink-0"
@@ -621,7 +675,7 @@ fn NodeChartShow(
 
     // action to trigger the update of nodes metrics charts
     let start_metrics_update = move |id: NodeId| {
-        set_chart_data.set((vec![], vec![]));
+        set_chart_data.set(ChartSeriesData::default());
         set_render_chart.set(true);
         context.metrics_update_on_for.set(Some(info));
         leptos::task::spawn_local(async move {
@@ -665,34 +719,36 @@ fn ButtonStopStart(info: RwSignal<NodeInstanceInfo>) -> impl IntoView {
     };
 
     view! {
-        <button
-            title=tip
-            prop:disabled=is_btn_disabled
-            class=move || {
-                format!(
-                    "p-2 {} rounded-lg transition-colors disabled:opacity-50 disabled:cursor-not-allowed disabled:hover:bg-transparent",
-                    if info.read().status.is_inactive() {
-                        "hover:bg-emerald-500/10 text-emerald-500"
-                    } else {
-                        "hover:bg-rose-500/10 text-rose-700"
-                    },
-                )
-            }
-            on:click=move |_| spawn_local(async move {
-                if info.read_untracked().status.is_inactive() {
-                    NodeAction::Start.apply(&info, &context.stats).await;
-                } else {
-                    NodeAction::Stop.apply(&info, &context.stats).await;
+        <Show when=move || context.can(Permission::OperateNodes)>
+            <button
+                title=tip
+                prop:disabled=is_btn_disabled
+                class=move || {
+                    format!(
+                        "p-2 {} rounded-lg transition-colors disabled:opacity-50 disabled:cursor-not-allowed disabled:hover:bg-transparent",
+                        if info.read().status.is_inactive() {
+                            "hover:bg-emerald-500/10 text-emerald-500"
+                        } else {
+                            "hover:bg-rose-500/10 text-rose-700"
+                        },
+                    )
                 }
-            })
-        >
-            <Show
-                when=move || info.read().status.is_inactive()
-                fallback=|| view! { <IconStopNode /> }
+                on:click=move |_| spawn_local(async move {
+                    if info.read_untracked().status.is_inactive() {
+                        NodeAction::Start.apply(&info, &context.stats).await;
+                    } else {
+                        NodeAction::Stop.apply(&info, &context.stats).await;
+                    }
+                })
             >
-                <IconStartNode />
-            </Show>
-        </button>
+                <Show
+                    when=move || info.read().status.is_inactive()
+                    fallback=|| view! { <IconStopNode /> }
+                >
+                    <IconStartNode />
+                </Show>
+            </button>
+        </Show>
     }
 }
 
@@ -714,21 +770,23 @@ fn ButtonUpgrade(info: RwSignal<NodeInstanceInfo>) -> impl IntoView {
     };
 
     view! {
-        <div class="relative group flex items-center">
-            <button
-                type="button"
-                prop:disabled=is_btn_disabled
-                class="p-2 hover:bg-cyan-500/10 text-cyan-500 rounded-lg transition-colors disabled:opacity-50 disabled:cursor-not-allowed disabled:hover:bg-transparent"
-                on:click=move |_| spawn_local(async move {
-                    NodeAction::Upgrade.apply(&info, &context.stats).await;
-                })
-            >
-                <IconUpgradeNode />
-            </button>
-            <div class="absolute bottom-full mb-2 right-0 max-w-xs w-max bg-cyan-950 border-cyan-700 text-cyan-200 text-xs px-2 py-1 rounded-md border border-slate-700 opacity-0 group-hover:opacity-100 transition-opacity pointer-events-none z-10 shadow-lg">
-                {move || tip()}
+        <Show when=move || context.can(NodeAction::Upgrade.permission())>
+            <div class="relative group flex items-center">
+                <button
+                    type="button"
+                    prop:disabled=is_btn_disabled
+                    class="p-2 hover:bg-cyan-500/10 text-cyan-500 rounded-lg transition-colors disabled:opacity-50 disabled:cursor-not-allowed disabled:hover:bg-transparent"
+                    on:click=move |_| spawn_local(async move {
+                        NodeAction::Upgrade.apply(&info, &context.stats).await;
+                    })
+                >
+                    <IconUpgradeNode />
+                </button>
+                <div class="absolute bottom-full mb-2 right-0 max-w-xs w-max bg-cyan-950 border-cyan-700 text-cyan-200 text-xs px-2 py-1 rounded-md border border-slate-700 opacity-0 group-hover:opacity-100 transition-opacity pointer-events-none z-10 shadow-lg">
+                    {move || tip()}
+                </div>
             </div>
-        </div>
+        </Show>
     }
 }
 
@@ -744,16 +802,47 @@ fn ButtonRecycle(info: RwSignal<NodeInstanceInfo>) -> impl IntoView {
     };
 
     view! {
-        <button
-            prop:disabled=move || !is_btn_enabled()
-            class="p-2 hover:bg-slate-800 rounded-lg transition-colors disabled:opacity-50 disabled:cursor-not-allowed disabled:hover:bg-transparent"
-            title="recycle"
-            on:click=move |_| spawn_local(async move {
-                NodeAction::Recycle.apply(&info, &context.stats).await;
-            })
-        >
-            <IconRecycle />
-        </button>
+        <Show when=move || context.can(NodeAction::Recycle.permission())>
+            <button
+                prop:disabled=move || !is_btn_enabled()
+                class="p-2 hover:bg-slate-800 rounded-lg transition-colors disabled:opacity-50 disabled:cursor-not-allowed disabled:hover:bg-transparent"
+                title="recycle"
+                on:click=move |_| spawn_local(async move {
+                    NodeAction::Recycle.apply(&info, &context.stats).await;
+                })
+            >
+                <IconRecycle />
+            </button>
+        </Show>
+    }
+}
+
+#[component]
+fn ButtonEdit(
+    info: RwSignal<NodeInstanceInfo>,
+    modal_edit_nodes: RwSignal<Option<Vec<NodeId>>>,
+) -> impl IntoView {
+    let context = expect_context::<ClientGlobalState>();
+    let is_selecting_nodes = move || context.selecting_nodes.read().0;
+    let is_btn_disabled = move || {
+        is_selecting_nodes()
+            || info.read().is_status_locked
+            || info.read().status.is_transitioning()
+    };
+
+    view! {
+        <Show when=move || context.can(Permission::OperateNodes)>
+            <button
+                prop:disabled=is_btn_disabled
+                class="p-2 hover:bg-slate-800 rounded-lg transition-colors disabled:opacity-50 disabled:cursor-not-allowed disabled:hover:bg-transparent"
+                title="edit configuration"
+                on:click=move |_| {
+                    modal_edit_nodes.set(Some(vec![info.read_untracked().node_id.clone()]))
+                }
+            >
+                <IconEdit />
+            </button>
+        </Show>
     }
 }
 
@@ -762,15 +851,17 @@ fn ButtonRemove(info: RwSignal<NodeInstanceInfo>) -> impl IntoView {
     let context = expect_context::<ClientGlobalState>();
 
     view! {
-        <button
-            prop:disabled=move || context.selecting_nodes.read().0
-            class="p-2 hover:bg-rose-500/10 text-rose-700 rounded-lg transition-colors disabled:opacity-50 disabled:cursor-not-allowed disabled:hover:bg-transparent"
-            title="remove"
-            on:click=move |_| spawn_local(async move {
-                NodeAction::Remove.apply(&info, &context.stats).await;
-            })
-        >
-            <IconRemove />
-        </button>
+        <Show when=move || context.can(NodeAction::Remove.permission())>
+            <button
+                prop:disabled=move || context.selecting_nodes.read().0
+                class="p-2 hover:bg-rose-500/10 text-rose-700 rounded-lg transition-colors disabled:opacity-50 disabled:cursor-not-allowed disabled:hover:bg-transparent"
+                title="remove"
+                on:click=move |_| spawn_local(async move {
+                    NodeAction::Remove.apply(&info, &context.stats).await;
+                })
+            >
+                <IconRemove />
+            </button>
+        </Show>
     }
 }
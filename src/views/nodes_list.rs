@@ -1,9 +1,10 @@
 use super::{
     actions_batch::NodesActionsBatchesView,
     chart::ChartSeriesData,
+    edit_nodes::EditNodesForm,
     icons::{
-        IconChevronDown, IconCollapse, IconExpand, IconLayoutList, IconLayoutTile, IconRecycle,
-        IconRemove, IconStartNode, IconStopNode,
+        IconChevronDown, IconCollapse, IconEdit, IconExpand, IconLayoutList, IconLayoutTile,
+        IconRecycle, IconRemove, IconStartNode, IconStopNode,
     },
     node_actions::{BatchActionModal, NodeAction},
     node_instance::NodeInstanceView,
@@ -12,6 +13,7 @@ use super::{
 };
 use crate::{
     app::{ActionTriggered, ClientGlobalState},
+    types::{NodeId, Permission},
     views::icons::IconUpgradeNode,
 };
 
@@ -44,6 +46,8 @@ pub fn NodesListView(
 
     // signal to toggle the panel to confirm actions to nodes
     let modal_apply_action = RwSignal::new(None);
+    // signal to toggle the form to edit the configuration of nodes
+    let modal_edit_nodes = RwSignal::new(None);
 
     view! {
         <div>
@@ -51,6 +55,7 @@ pub fn NodesListView(
             <NodeListToolbarView
                 num_nodes=Memo::new(move |_| sorted_nodes.read().len())
                 modal_apply_action
+                modal_edit_nodes
             />
 
             // Nodes Grid/List
@@ -64,12 +69,18 @@ pub fn NodesListView(
                                 <div class="hidden md:grid grid-cols-18 gap-4 items-center px-6 py-3 text-xs font-bold text-slate-500 uppercase tracking-wider border-b border-slate-800 bg-slate-900 rounded-t-lg">
                                     <div class="col-span-1"></div>
                                     <div class="col-span-2 flex items-center gap-4">Node ID</div>
-                                    <div class="col-span-5">Status</div>
+                                    <div class="col-span-4">Status</div>
                                     <div class="col-span-1 text-center">CPU</div>
                                     <div class="col-span-2 text-center">Memory</div>
                                     <div class="col-span-1 text-center">Chunks</div>
                                     <div class="col-span-2 text-center">Disk Usage</div>
                                     <div class="col-span-1 text-center">Peers</div>
+                                    <div
+                                        class="col-span-1 text-center"
+                                        title="Rewards estimated to have been earned by the node"
+                                    >
+                                        Earned
+                                    </div>
                                     <div class="col-span-3 text-center">Actions</div>
                                 </div>
 
@@ -90,6 +101,7 @@ pub fn NodesListView(
                                         set_logs
                                         set_render_chart
                                         set_chart_data
+                                        modal_edit_nodes
                                     />
                                 </For>
 
@@ -114,6 +126,7 @@ pub fn NodesListView(
                                 set_logs
                                 set_render_chart
                                 set_chart_data
+                                modal_edit_nodes
                             />
                         </For>
                     </div>
@@ -125,6 +138,12 @@ pub fn NodesListView(
                     <BatchActionModal action=modal_apply_action />
                 </Show>
 
+                <Show when=move || {
+                    modal_edit_nodes.read().is_some() && *context.is_online.read()
+                }>
+                    <EditNodesForm node_ids=modal_edit_nodes />
+                </Show>
+
             </div>
         </div>
     }
@@ -134,6 +153,7 @@ pub fn NodesListView(
 fn NodeListToolbarView(
     num_nodes: Memo<usize>,
     modal_apply_action: RwSignal<Option<NodeAction>>,
+    modal_edit_nodes: RwSignal<Option<Vec<NodeId>>>,
 ) -> impl IntoView {
     let context = expect_context::<ClientGlobalState>();
     let is_selection_open = RwSignal::new(false);
@@ -222,48 +242,51 @@ fn NodeListToolbarView(
         <div class="sticky top-0 z-20 bg-slate-950/80 backdrop-blur-md border-b border-slate-800 px-4 lg:px-8">
             <div class="flex flex-col sm:flex-row sm:items-center justify-between gap-4 py-3">
                 <div class="flex items-center gap-4">
-                    <div class="relative">
-                        <button
-                            on:click=move |_| is_selection_open.update(|prev| *prev = !*prev)
-                            class="flex items-center gap-2 px-3 py-1.5 text-sm font-medium text-slate-400 hover:text-white bg-slate-800 border border-slate-700 rounded-lg transition-all"
-                        >
-                            Selection
-                            <IconChevronDown is_down=Signal::derive(move || {
-                                is_selection_open.get()
-                            }) />
-                        </button>
-                        <Show when=move || is_selection_open.get() fallback=|| ()>
-                            <div class="absolute top-full mt-2 w-48 bg-slate-800 border border-slate-700 rounded-lg shadow-xl z-10 animate-in fade-in duration-150 py-1">
-                                <a
-                                    on:click=move |_| select_all()
-                                    class="block w-full text-left px-4 py-2 text-sm text-slate-300 hover:bg-slate-700/50 cursor-pointer"
-                                >
-                                    Select All
-                                </a>
-                                <a
-                                    on:click=move |_| select_none()
-                                    class="block w-full text-left px-4 py-2 text-sm text-slate-300 hover:bg-slate-700/50 cursor-pointer"
-                                >
-                                    Select None
-                                </a>
-                                <div class="h-px bg-slate-700 my-1" />
-                                <a
-                                    on:click=move |_| select_active()
-                                    class="block w-full text-left px-4 py-2 text-sm text-slate-300 hover:bg-slate-700/50 cursor-pointer"
-                                >
-                                    Select Active
-                                </a>
-                                <a
-                                    on:click=move |_| select_inactive()
-                                    class="block w-full text-left px-4 py-2 text-sm text-slate-300 hover:bg-slate-700/50 cursor-pointer"
-                                >
-                                    Select Inactive
-                                </a>
-                            </div>
-                        </Show>
-                    </div>
+                    // nodes are only selected to apply actions on them
+                    <Show when=move || context.can(Permission::OperateNodes)>
+                        <div class="relative">
+                            <button
+                                on:click=move |_| is_selection_open.update(|prev| *prev = !*prev)
+                                class="flex items-center gap-2 px-3 py-1.5 text-sm font-medium text-slate-400 hover:text-white bg-slate-800 border border-slate-700 rounded-lg transition-all"
+                            >
+                                Selection
+                                <IconChevronDown is_down=Signal::derive(move || {
+                                    is_selection_open.get()
+                                }) />
+                            </button>
+                            <Show when=move || is_selection_open.get() fallback=|| ()>
+                                <div class="absolute top-full mt-2 w-48 bg-slate-800 border border-slate-700 rounded-lg shadow-xl z-10 animate-in fade-in duration-150 py-1">
+                                    <a
+                                        on:click=move |_| select_all()
+                                        class="block w-full text-left px-4 py-2 text-sm text-slate-300 hover:bg-slate-700/50 cursor-pointer"
+                                    >
+                                        Select All
+                                    </a>
+                                    <a
+                                        on:click=move |_| select_none()
+                                        class="block w-full text-left px-4 py-2 text-sm text-slate-300 hover:bg-slate-700/50 cursor-pointer"
+                                    >
+                                        Select None
+                                    </a>
+                                    <div class="h-px bg-slate-700 my-1" />
+                                    <a
+                                        on:click=move |_| select_active()
+                                        class="block w-full text-left px-4 py-2 text-sm text-slate-300 hover:bg-slate-700/50 cursor-pointer"
+                                    >
+                                        Select Active
+                                    </a>
+                                    <a
+                                        on:click=move |_| select_inactive()
+                                        class="block w-full text-left px-4 py-2 text-sm text-slate-300 hover:bg-slate-700/50 cursor-pointer"
+                                    >
+                                        Select Inactive
+                                    </a>
+                                </div>
+                            </Show>
+                        </div>
 
-                    <div class="h-4 w-px bg-slate-800" />
+                        <div class="h-4 w-px bg-slate-800" />
+                    </Show>
 
                     <Show
                         when=move || context.expanded_nodes.read().len() < num_nodes.get()
@@ -326,6 +349,26 @@ fn NodeListToolbarView(
                             >
                                 <IconRecycle />
                             </button>
+                            <button
+                                on:click=move |_| {
+                                    modal_edit_nodes
+                                        .set(
+                                            Some(
+                                                context
+                                                    .selecting_nodes
+                                                    .read_untracked()
+                                                    .1
+                                                    .iter()
+                                                    .cloned()
+                                                    .collect(),
+                                            ),
+                                        )
+                                }
+                                class="p-1.5 hover:bg-indigo-500/10 text-indigo-400 rounded-lg transition-colors"
+                                title="Edit Selected"
+                            >
+                                <IconEdit />
+                            </button>
                             <button
                                 on:click=move |_| apply_action_on_selected(NodeAction::Start)
                                 class="p-1.5 hover:bg-emerald-500/10 text-emerald-500 rounded-lg transition-colors"
@@ -340,13 +383,15 @@ fn NodeListToolbarView(
                             >
                                 <IconStopNode />
                             </button>
-                            <button
-                                on:click=move |_| apply_action_on_selected(NodeAction::Remove)
-                                class="p-1.5 hover:bg-rose-500/10 text-rose-700 rounded-lg transition-colors"
-                                title="Remove Selected"
-                            >
-                                <IconRemove />
-                            </button>
+                            <Show when=move || context.can(NodeAction::Remove.permission())>
+                                <button
+                                    on:click=move |_| apply_action_on_selected(NodeAction::Remove)
+                                    class="p-1.5 hover:bg-rose-500/10 text-rose-700 rounded-lg transition-colors"
+                                    title="Remove Selected"
+                                >
+                                    <IconRemove />
+                                </button>
+                            </Show>
                         </div>
                     </Show>
                 </div>
@@ -0,0 +1,476 @@
+use super::helpers::{show_error_alert_msg, show_success_alert_msg};
+use crate::{
+    server_api::{
+        delete_notification_channel, notification_channels, save_notification_channel,
+        test_notification_channel,
+    },
+    types::{NotificationChannel, NotificationEventKind, NotificationTarget},
+};
+
+use leptos::{prelude::*, task::spawn_local};
+
+// Default port of SMTP servers accepting STARTTLS connections.
+const DEFAULT_SMTP_PORT: u16 = 587;
+
+// Label of each kind of target, as listed in the form.
+const TARGET_KINDS: [&str; 7] = [
+    "Webhook", "Email", "Telegram", "ntfy", "Gotify", "Discord", "Slack",
+];
+
+// Target of the given kind with empty connection details.
+fn empty_target(kind: &str) -> NotificationTarget {
+    match kind {
+        "Email" => NotificationTarget::Email {
+            smtp_host: String::new(),
+            smtp_port: DEFAULT_SMTP_PORT,
+            username: String::new(),
+            password: String::new(),
+            from: String::new(),
+            to: String::new(),
+        },
+        "Telegram" => NotificationTarget::Telegram {
+            bot_token: String::new(),
+            chat_id: String::new(),
+        },
+        "ntfy" => NotificationTarget::Ntfy {
+            url: String::new(),
+            token: String::new(),
+        },
+        "Gotify" => NotificationTarget::Gotify {
+            url: String::new(),
+            token: String::new(),
+        },
+        "Discord" => NotificationTarget::Discord { url: String::new() },
+        "Slack" => NotificationTarget::Slack { url: String::new() },
+        _ => NotificationTarget::default(),
+    }
+}
+
+fn new_channel() -> NotificationChannel {
+    NotificationChannel {
+        enabled: true,
+        events: NotificationEventKind::ALL.to_vec(),
+        ..Default::default()
+    }
+}
+
+// Send a test notification through the channel, reporting the outcome in the UI.
+fn send_test(channel: NotificationChannel) {
+    spawn_local(async move {
+        match test_notification_channel(channel).await {
+            Ok(()) => show_success_alert_msg("Test notification sent".to_string()),
+            Err(err) => show_error_alert_msg(err.to_string()),
+        }
+    });
+}
+
+#[component]
+pub fn NotificationChannelsCard() -> impl IntoView {
+    // bumped to retrieve up to date channels from the backend
+    let refresh = RwSignal::new(0u32);
+    let channels = Resource::new(move || refresh.get(), |_| notification_channels());
+    // channel being added, or edited if its id is not 0
+    let editing = RwSignal::new(new_channel());
+
+    view! {
+        <Transition fallback=move || {
+            view! { <p class="p-6 text-slate-400">"Retrieving notification channels..."</p> }
+        }>
+            {move || {
+                channels
+                    .get()
+                    .map(|res| match res {
+                        Ok(list) if list.is_empty() => {
+                            view! {
+                                <p class="p-6 text-center text-slate-400">
+                                    "No notification channels have been set up"
+                                </p>
+                            }
+                                .into_any()
+                        }
+                        Ok(list) => {
+                            view! {
+                                <ul class="divide-y divide-slate-800">
+                                    {list
+                                        .into_iter()
+                                        .map(|channel| {
+                                            view! { <NotificationChannelItem channel editing refresh /> }
+                                        })
+                                        .collect_view()}
+                                </ul>
+                            }
+                                .into_any()
+                        }
+                        Err(err) => {
+                            view! {
+                                <p class="p-6 text-rose-400">
+                                    {format!("Failed to retrieve notification channels: {err}")}
+                                </p>
+                            }
+                                .into_any()
+                        }
+                    })
+            }}
+        </Transition>
+        <NotificationChannelForm channel=editing refresh />
+    }
+}
+
+#[component]
+fn NotificationChannelItem(
+    channel: NotificationChannel,
+    editing: RwSignal<NotificationChannel>,
+    refresh: RwSignal<u32>,
+) -> impl IntoView {
+    let channel_id = channel.id;
+    let enabled = channel.enabled;
+    let events = channel
+        .events
+        .iter()
+        .map(|e| e.label())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let stored = StoredValue::new(channel.clone());
+
+    let run =
+        move |action: &'static str,
+              f: std::pin::Pin<Box<dyn Future<Output = Result<(), ServerFnError>>>>| {
+            spawn_local(async move {
+                if let Err(err) = f.await {
+                    show_error_alert_msg(format!("Failed to {action} notification channel: {err}"));
+                }
+                refresh.update(|n| *n += 1);
+            })
+        };
+
+    view! {
+        <li class=format!(
+            "px-6 py-4 flex items-start gap-4 {}",
+            if enabled { "" } else { "opacity-60" },
+        )>
+            <span class="mt-0.5 px-2 py-0.5 rounded-full text-[10px] font-bold uppercase tracking-wider border bg-indigo-500/10 text-indigo-400 border-indigo-500/20">
+                {channel.target.kind_label()}
+            </span>
+            <div class="flex-1 min-w-0">
+                <p class="text-sm text-slate-200 font-medium">{channel.name}</p>
+                <p class="text-xs text-slate-500 mt-1 truncate">
+                    {channel.target.destination()} " | " {events}
+                </p>
+            </div>
+            <div class="flex items-center gap-3 text-xs font-bold">
+                <button
+                    type="button"
+                    class="text-indigo-400 hover:text-indigo-300"
+                    on:click=move |_| {
+                        let channel = NotificationChannel {
+                            enabled: !enabled,
+                            ..stored.get_value()
+                        };
+                        run("update", Box::pin(save_notification_channel(channel)))
+                    }
+                >
+                    {if enabled { "Disable" } else { "Enable" }}
+                </button>
+                <button
+                    type="button"
+                    class="text-emerald-400 hover:text-emerald-300"
+                    on:click=move |_| send_test(stored.get_value())
+                >
+                    Send test
+                </button>
+                <button
+                    type="button"
+                    class="text-slate-300 hover:text-white"
+                    on:click=move |_| editing.set(stored.get_value())
+                >
+                    Edit
+                </button>
+                <button
+                    type="button"
+                    class="text-rose-400 hover:text-rose-300"
+                    on:click=move |_| {
+                        run("delete", Box::pin(delete_notification_channel(channel_id)))
+                    }
+                >
+                    Delete
+                </button>
+            </div>
+        </li>
+    }
+}
+
+// Text input bound to one of the string fields of the channel's target,
+// the accessor returns None if the field doesn't belong to the current kind of target.
+#[component]
+fn TargetField(
+    channel: RwSignal<NotificationChannel>,
+    placeholder: &'static str,
+    #[prop(optional)] secret: bool,
+    field: fn(&mut NotificationTarget) -> Option<&mut String>,
+) -> impl IntoView {
+    view! {
+        <input
+            type=if secret { "password" } else { "text" }
+            placeholder=placeholder
+            title=placeholder
+            class="bg-slate-800 border border-slate-700 rounded-md px-3 py-2 text-sm focus:outline-none focus:ring-1 focus:ring-indigo-500"
+            prop:value=move || {
+                let mut target = channel.read().target.clone();
+                field(&mut target).cloned().unwrap_or_default()
+            }
+            on:input=move |ev| {
+                channel
+                    .update(|c| {
+                        if let Some(value) = field(&mut c.target) {
+                            *value = event_target_value(&ev);
+                        }
+                    })
+            }
+        />
+    }
+}
+
+#[component]
+fn NotificationChannelForm(
+    channel: RwSignal<NotificationChannel>,
+    refresh: RwSignal<u32>,
+) -> impl IntoView {
+    let input_class = "bg-slate-800 border border-slate-700 rounded-md px-3 py-2 text-sm focus:outline-none focus:ring-1 focus:ring-indigo-500";
+    let is_editing = move || channel.read().id != 0;
+    let kind = Memo::new(move |_| channel.read().target.kind_label());
+
+    let target_fields = move || {
+        match kind.get() {
+        "Email" => view! {
+            <TargetField
+                channel
+                placeholder="SMTP server"
+                field=|t| match t {
+                    NotificationTarget::Email { smtp_host, .. } => Some(smtp_host),
+                    _ => None,
+                }
+            />
+            <input
+                type="number"
+                min=1
+                placeholder="SMTP port"
+                title="SMTP port: 465 for TLS, any other port is upgraded with STARTTLS"
+                class=input_class
+                prop:value=move || match &channel.read().target {
+                    NotificationTarget::Email { smtp_port, .. } => smtp_port.to_string(),
+                    _ => String::new(),
+                }
+                on:input=move |ev| {
+                    channel
+                        .update(|c| {
+                            if let NotificationTarget::Email { smtp_port, .. } = &mut c.target {
+                                *smtp_port = event_target_value(&ev)
+                                    .parse()
+                                    .unwrap_or(DEFAULT_SMTP_PORT);
+                            }
+                        })
+                }
+            />
+            <TargetField
+                channel
+                placeholder="Username (optional)"
+                field=|t| match t {
+                    NotificationTarget::Email { username, .. } => Some(username),
+                    _ => None,
+                }
+            />
+            <TargetField
+                channel
+                placeholder="Password (optional)"
+                secret=true
+                field=|t| match t {
+                    NotificationTarget::Email { password, .. } => Some(password),
+                    _ => None,
+                }
+            />
+            <TargetField
+                channel
+                placeholder="From, e.g. Formicaio <formicaio@example.com>"
+                field=|t| match t {
+                    NotificationTarget::Email { from, .. } => Some(from),
+                    _ => None,
+                }
+            />
+            <TargetField
+                channel
+                placeholder="To (comma-separated)"
+                field=|t| match t {
+                    NotificationTarget::Email { to, .. } => Some(to),
+                    _ => None,
+                }
+            />
+        }
+            .into_any(),
+        "Telegram" => view! {
+            <TargetField
+                channel
+                placeholder="Bot token"
+                secret=true
+                field=|t| match t {
+                    NotificationTarget::Telegram { bot_token, .. } => Some(bot_token),
+                    _ => None,
+                }
+            />
+            <TargetField
+                channel
+                placeholder="Chat ID"
+                field=|t| match t {
+                    NotificationTarget::Telegram { chat_id, .. } => Some(chat_id),
+                    _ => None,
+                }
+            />
+        }
+            .into_any(),
+        "ntfy" | "Gotify" => view! {
+            <TargetField
+                channel
+                placeholder=if kind.get_untracked() == "ntfy" {
+                    "Topic URL, e.g. https://ntfy.sh/my-topic"
+                } else {
+                    "Server URL, e.g. https://gotify.example.com"
+                }
+                field=|t| match t {
+                    NotificationTarget::Ntfy { url, .. } | NotificationTarget::Gotify { url, .. } => {
+                        Some(url)
+                    }
+                    _ => None,
+                }
+            />
+            <TargetField
+                channel
+                placeholder=if kind.get_untracked() == "ntfy" {
+                    "Access token (optional)"
+                } else {
+                    "Application token"
+                }
+                secret=true
+                field=|t| match t {
+                    NotificationTarget::Ntfy { token, .. }
+                    | NotificationTarget::Gotify { token, .. } => Some(token),
+                    _ => None,
+                }
+            />
+        }
+            .into_any(),
+        _ => view! {
+            <TargetField
+                channel
+                placeholder="Webhook URL"
+                field=|t| match t {
+                    NotificationTarget::Webhook { url }
+                    | NotificationTarget::Discord { url }
+                    | NotificationTarget::Slack { url } => Some(url),
+                    _ => None,
+                }
+            />
+        }
+            .into_any(),
+    }
+    };
+
+    view! {
+        <div class="p-6 border-t border-slate-800 bg-slate-800/10 space-y-4">
+            <h4 class="text-sm font-bold text-slate-300">
+                {move || if is_editing() { "Edit Channel" } else { "New Channel" }}
+            </h4>
+            <div class="grid grid-cols-1 md:grid-cols-3 gap-3">
+                <input
+                    type="text"
+                    placeholder="Name"
+                    class=input_class
+                    prop:value=move || channel.read().name.clone()
+                    on:input=move |ev| channel.update(|c| c.name = event_target_value(&ev))
+                />
+                <select
+                    class=input_class
+                    on:change=move |ev| {
+                        let target = empty_target(&event_target_value(&ev));
+                        channel.update(|c| c.target = target);
+                    }
+                >
+                    {TARGET_KINDS
+                        .into_iter()
+                        .map(|k| {
+                            view! {
+                                <option value=k selected=move || kind.get() == k>
+                                    {k}
+                                </option>
+                            }
+                        })
+                        .collect_view()}
+                </select>
+                {target_fields}
+            </div>
+            <div class="flex flex-wrap items-center gap-4">
+                {NotificationEventKind::ALL
+                    .into_iter()
+                    .map(|e| {
+                        view! {
+                            <label class="flex items-center gap-2 text-sm text-slate-300">
+                                <input
+                                    type="checkbox"
+                                    class="accent-indigo-500"
+                                    prop:checked=move || channel.read().events.contains(&e)
+                                    on:change=move |ev| {
+                                        let checked = event_target_checked(&ev);
+                                        channel
+                                            .update(|c| {
+                                                c.events.retain(|k| *k != e);
+                                                if checked {
+                                                    c.events.push(e);
+                                                }
+                                            })
+                                    }
+                                />
+                                {e.label()}
+                            </label>
+                        }
+                    })
+                    .collect_view()}
+            </div>
+            <div class="flex items-center gap-3">
+                <button
+                    type="button"
+                    class="bg-indigo-600 hover:bg-indigo-500 transition-colors text-white px-4 py-2 rounded-lg font-medium text-sm"
+                    on:click=move |_| {
+                        let to_save = channel.get();
+                        spawn_local(async move {
+                            match save_notification_channel(to_save).await {
+                                Ok(()) => channel.set(new_channel()),
+                                Err(err) => {
+                                    show_error_alert_msg(
+                                        format!("Failed to save notification channel: {err}"),
+                                    )
+                                }
+                            }
+                            refresh.update(|n| *n += 1);
+                        })
+                    }
+                >
+                    {move || if is_editing() { "Save Channel" } else { "Add Channel" }}
+                </button>
+                <button
+                    type="button"
+                    class="px-4 py-2 text-sm font-bold bg-slate-800 hover:bg-slate-700 border border-slate-600 text-slate-200 rounded-lg transition-colors"
+                    on:click=move |_| send_test(channel.get())
+                >
+                    Send test
+                </button>
+                <Show when=is_editing>
+                    <button
+                        type="button"
+                        class="px-4 py-2 text-sm font-bold text-slate-400 hover:bg-slate-800 rounded-lg transition-colors"
+                        on:click=move |_| channel.set(new_channel())
+                    >
+                        Cancel
+                    </button>
+                </Show>
+            </div>
+        </div>
+    }
+}
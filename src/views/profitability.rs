@@ -0,0 +1,213 @@
+use crate::{
+    server_api::nodes_profitability,
+    types::{DEFAULT_PROFITABILITY_PERIOD_HOURS, Profitability, ProfitabilityStats},
+};
+
+use super::{format_disk_usage, helpers::truncated_balance_str, icons::IconZap};
+
+use leptos::{logging, prelude::*, task::spawn_local};
+
+// Periods (in hours) the profitability can be calculated over.
+const PROFITABILITY_PERIODS: [(u32, &str); 3] = [(24, "24H"), (168, "7D"), (720, "30D")];
+
+// Estimated costs of running the nodes compared with what they earned over a period.
+#[component]
+pub fn ProfitabilityCard() -> impl IntoView {
+    let period_hours = RwSignal::new(DEFAULT_PROFITABILITY_PERIOD_HOURS);
+    let profitability = RwSignal::new(None::<Profitability>);
+
+    Effect::new(move |_| {
+        let hours = period_hours.get();
+        spawn_local(async move {
+            match nodes_profitability(hours).await {
+                Ok(p) => profitability.set(Some(p)),
+                Err(err) => {
+                    logging::error!("[ERROR] Failed to retrieve nodes profitability: {err}")
+                }
+            }
+        });
+    });
+
+    let tab_class = |selected: bool| {
+        format!(
+            "px-3 py-1.5 text-xs font-bold rounded-lg transition-colors {}",
+            if selected {
+                "bg-indigo-500/20 text-indigo-300"
+            } else {
+                "text-slate-500 hover:text-slate-300"
+            },
+        )
+    };
+
+    view! {
+        <div class="bg-slate-900 border border-slate-800 rounded-3xl overflow-hidden shadow-2xl">
+            <header class="p-6 border-b border-slate-800 bg-slate-800/10 flex flex-col md:flex-row md:items-center justify-between gap-4">
+                <div class="flex items-center gap-4">
+                    <div class="p-3 bg-amber-500/10 rounded-2xl text-amber-400">
+                        <IconZap />
+                    </div>
+                    <div>
+                        <h3 class="text-xl font-bold text-white tracking-tight">Profitability</h3>
+                        <p class="text-xs text-slate-500 font-medium uppercase tracking-widest mt-1">
+                            Estimated Running Costs vs Earnings
+                        </p>
+                    </div>
+                </div>
+                <div class="flex items-center gap-1 bg-slate-950 border border-slate-800 rounded-xl p-1">
+                    {PROFITABILITY_PERIODS
+                        .into_iter()
+                        .map(|(hours, label)| {
+                            view! {
+                                <button
+                                    class=move || tab_class(period_hours.get() == hours)
+                                    on:click=move |_| period_hours.set(hours)
+                                >
+                                    {label}
+                                </button>
+                            }
+                        })
+                        .collect_view()}
+                </div>
+            </header>
+
+            {move || match profitability.get() {
+                Some(p) => view! { <ProfitabilityDetails profitability=p /> }.into_any(),
+                None => {
+                    view! {
+                        <div class="p-6 text-sm text-slate-500 italic">
+                            "Calculating profitability..."
+                        </div>
+                    }
+                        .into_any()
+                }
+            }}
+        </div>
+    }
+}
+
+#[component]
+fn ProfitabilityDetails(profitability: Profitability) -> impl IntoView {
+    let Profitability {
+        currency,
+        host,
+        nodes,
+        ..
+    } = profitability;
+    let money = {
+        let currency = currency.to_uppercase();
+        move |v: Option<f64>| v.map_or("-".to_string(), |v| format!("{v:.2} {currency}"))
+    };
+    let profit_class = |stats: &ProfitabilityStats| match stats.profit() {
+        Some(p) if p >= 0.0 => "text-emerald-400",
+        Some(_) => "text-rose-400",
+        None => "text-slate-400",
+    };
+    let summary = [
+        ("Total Cost", money(Some(host.total_cost()))),
+        ("Earned Value", money(host.earned_value)),
+        ("Profit", money(host.profit())),
+        ("ROI", format_roi(host.roi())),
+        (
+            "Cost per Record",
+            host.cost_per_record().map_or("-".to_string(), |v| {
+                format!("{v:.6} {}", currency.to_uppercase())
+            }),
+        ),
+        (
+            "Break-even Price",
+            host.break_even_price().map_or("-".to_string(), |v| {
+                format!("{v:.4} {}", currency.to_uppercase())
+            }),
+        ),
+        ("Energy", format!("{:.2} kWh", host.energy_kwh)),
+        ("Earned", truncated_balance_str(host.earned)),
+    ];
+    let host_profit_class = profit_class(&host);
+
+    view! {
+        <div class="p-6 grid grid-cols-2 md:grid-cols-4 gap-4 bg-slate-950/20">
+            {summary
+                .into_iter()
+                .map(|(label, value)| {
+                    let class = if label == "Profit" || label == "ROI" {
+                        host_profit_class
+                    } else {
+                        "text-white"
+                    };
+                    view! {
+                        <div class="bg-slate-900/60 border border-slate-800/80 rounded-2xl p-4 flex flex-col gap-1">
+                            <span class="text-[11px] font-bold text-slate-500 uppercase tracking-widest">
+                                {label}
+                            </span>
+                            <span class=format!(
+                                "text-lg font-bold font-mono tracking-tight {class}",
+                            )>{value}</span>
+                        </div>
+                    }
+                })
+                .collect_view()}
+        </div>
+        <div class="overflow-x-auto border-t border-slate-800">
+            <table class="w-full text-left">
+                <thead>
+                    <tr class="bg-slate-800/50 text-slate-400 text-xs uppercase tracking-wider">
+                        <th class="px-6 py-3 font-semibold">Node Id</th>
+                        <th class="px-6 py-3 font-semibold text-center">Hours</th>
+                        <th class="px-6 py-3 font-semibold text-center">Avg CPU</th>
+                        <th class="px-6 py-3 font-semibold text-center">Disk</th>
+                        <th class="px-6 py-3 font-semibold text-center">Cost</th>
+                        <th class="px-6 py-3 font-semibold text-center">Earned</th>
+                        <th class="px-6 py-3 font-semibold text-center">Profit</th>
+                        <th class="px-6 py-3 font-semibold text-center">ROI</th>
+                    </tr>
+                </thead>
+                <tbody class="divide-y divide-slate-800 text-sm">
+                    {nodes
+                        .into_iter()
+                        .map(|stats| {
+                            let class = profit_class(&stats);
+                            view! {
+                                <tr class="hover:bg-slate-800/30 transition-colors">
+                                    <td class="px-6 py-3 font-mono text-indigo-400">
+                                        {stats
+                                            .node_id
+                                            .as_ref()
+                                            .map(|id| id.short_node_id())
+                                            .unwrap_or_default()}
+                                    </td>
+                                    <td class="px-6 py-3 text-center font-mono">
+                                        {format!("{:.0}", stats.hours_running)}
+                                    </td>
+                                    <td class="px-6 py-3 text-center font-mono">
+                                        {stats
+                                            .avg_cpu_usage
+                                            .map_or("-".to_string(), |v| format!("{v:.1}%"))}
+                                    </td>
+                                    <td class="px-6 py-3 text-center font-mono">
+                                        {format_disk_usage(stats.disk_usage)}
+                                    </td>
+                                    <td class="px-6 py-3 text-center font-mono">
+                                        {money(Some(stats.total_cost()))}
+                                    </td>
+                                    <td class="px-6 py-3 text-center font-mono">
+                                        {truncated_balance_str(stats.earned)}
+                                    </td>
+                                    <td class=format!(
+                                        "px-6 py-3 text-center font-mono {class}",
+                                    )>{money(stats.profit())}</td>
+                                    <td class=format!(
+                                        "px-6 py-3 text-center font-mono {class}",
+                                    )>{format_roi(stats.roi())}</td>
+                                </tr>
+                            }
+                        })
+                        .collect_view()}
+                </tbody>
+            </table>
+        </div>
+    }
+}
+
+fn format_roi(roi: Option<f64>) -> String {
+    roi.map_or("-".to_string(), |v| format!("{:+.1}%", v * 100.0))
+}
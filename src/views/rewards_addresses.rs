@@ -0,0 +1,382 @@
+use super::{
+    earnings::PeriodStatCard,
+    helpers::{show_error_alert_msg, truncated_balance_str},
+    icons::{IconArrowUpRight, IconWallet},
+};
+use crate::{
+    app::{ClientGlobalState, refresh_address_book},
+    server_api::{delete_address_book_entry, rewards_addresses, save_address_book_entry},
+    types::{AddressBookEntry, Permission, RewardsAddressSummary},
+};
+
+use leptos::{prelude::*, task::spawn_local};
+
+// Label and colour of an address if it's in the address book, or its shortened form otherwise.
+#[component]
+pub(super) fn AddressLabel(address: String) -> impl IntoView {
+    let context = expect_context::<ClientGlobalState>();
+    let address = StoredValue::new(address);
+    let entry = move || {
+        context
+            .address_book
+            .read()
+            .get(&address.read_value())
+            .cloned()
+    };
+
+    view! {
+        <span
+            class="inline-flex items-center gap-1.5 min-w-0"
+            title=move || {
+                entry()
+                    .map(|e| format!("{}\n{}", e.address, e.note))
+                    .unwrap_or_else(|| address.get_value())
+            }
+        >
+            {move || {
+                entry()
+                    .map(|e| {
+                        view! {
+                            <span
+                                class="w-2 h-2 rounded-full shrink-0"
+                                style:background-color=e.color
+                            ></span>
+                        }
+                    })
+            }}
+            <span class="truncate">
+                {move || context.address_book.read().display_name(&address.read_value())}
+            </span>
+        </span>
+    }
+}
+
+#[component]
+pub(super) fn RewardsAddressesView() -> impl IntoView {
+    let context = expect_context::<ClientGlobalState>();
+    // retrieve the summaries again whenever the address book, the balances or the nodes change
+    let summaries = Resource::new(
+        move || {
+            context.address_book.track();
+            context
+                .stats
+                .with(|s| (s.total_balance, s.total_nodes, s.active_nodes))
+        },
+        |_| rewards_addresses(),
+    );
+    let selected = RwSignal::<Option<String>>::new(None);
+
+    view! {
+        <div class="p-4 lg:p-8 max-w-6xl mx-auto space-y-8 animate-in fade-in slide-in-from-bottom-4 duration-500">
+            <Transition fallback=move || {
+                view! { <p class="text-slate-400">"Retrieving rewards addresses..."</p> }
+            }>
+                {move || {
+                    summaries
+                        .get()
+                        .map(|res| match res {
+                            Ok(list) if list.is_empty() => {
+                                view! {
+                                    <p class="p-6 text-center text-slate-400">
+                                        "No nodes are paying rewards to any address yet"
+                                    </p>
+                                }
+                                    .into_any()
+                            }
+                            Ok(list) => {
+                                let current = selected
+                                    .get()
+                                    .and_then(|addr| list.iter().find(|s| s.address == addr))
+                                    .or(list.first())
+                                    .cloned();
+                                view! {
+                                    <div class="grid grid-cols-1 lg:grid-cols-3 gap-6">
+                                        <ul class="space-y-3">
+                                            {list
+                                                .into_iter()
+                                                .map(|summary| {
+                                                    let is_selected = current
+                                                        .as_ref()
+                                                        .is_some_and(|c| c.address == summary.address);
+                                                    view! {
+                                                        <RewardsAddressItem summary selected is_selected />
+                                                    }
+                                                })
+                                                .collect_view()}
+                                        </ul>
+                                        <div class="lg:col-span-2">
+                                            {current.map(|summary| view! { <RewardsAddressDashboard summary /> })}
+                                        </div>
+                                    </div>
+                                }
+                                    .into_any()
+                            }
+                            Err(err) => {
+                                view! {
+                                    <p class="p-6 text-rose-400">
+                                        {format!("Failed to retrieve rewards addresses: {err}")}
+                                    </p>
+                                }
+                                    .into_any()
+                            }
+                        })
+                }}
+            </Transition>
+        </div>
+    }
+}
+
+#[component]
+fn RewardsAddressItem(
+    summary: RewardsAddressSummary,
+    selected: RwSignal<Option<String>>,
+    is_selected: bool,
+) -> impl IntoView {
+    let address = summary.address.clone();
+    let color = summary
+        .entry
+        .as_ref()
+        .map(|e| e.color.clone())
+        .unwrap_or("transparent".to_string());
+
+    view! {
+        <li>
+            <button
+                type="button"
+                on:click=move |_| selected.set(Some(address.clone()))
+                class=format!(
+                    "w-full text-left bg-slate-900 border rounded-2xl p-4 border-l-4 transition-colors {}",
+                    if is_selected {
+                        "border-indigo-500/60 bg-slate-800/60"
+                    } else {
+                        "border-slate-800 hover:border-slate-600"
+                    },
+                )
+                style:border-left-color=color
+            >
+                <div class="flex items-center justify-between gap-2">
+                    <span class="font-bold text-white truncate">{summary.display_name()}</span>
+                    <span class="text-emerald-400 text-sm font-bold shrink-0">
+                        {summary.balance.map(truncated_balance_str).unwrap_or("-".to_string())}
+                    </span>
+                </div>
+                <div class="text-xs text-slate-500 font-mono truncate mt-1">{summary.address}</div>
+                <div class="text-xs text-slate-400 mt-2">
+                    {format!("{} node/s, {} active", summary.nodes.len(), summary.active_nodes)}
+                </div>
+            </button>
+        </li>
+    }
+}
+
+#[component]
+fn RewardsAddressDashboard(summary: RewardsAddressSummary) -> impl IntoView {
+    let context = expect_context::<ClientGlobalState>();
+    let explorer_url = format!(
+        "https://arbiscan.io/token/{}?a={}",
+        context.app_settings.read_untracked().token_contract_address,
+        summary.address
+    );
+    let nodes = summary
+        .nodes
+        .iter()
+        .map(|id| id.short_node_id())
+        .collect::<Vec<_>>();
+
+    view! {
+        <div class="bg-slate-900 border border-slate-800 rounded-3xl overflow-hidden shadow-2xl">
+            <header class="p-6 border-b border-slate-800 bg-slate-800/10 flex items-center justify-between gap-4">
+                <div class="flex items-center gap-4 min-w-0">
+                    <div class="p-3 bg-emerald-500/10 rounded-2xl text-emerald-400">
+                        <IconWallet />
+                    </div>
+                    <div class="min-w-0">
+                        <h3 class="text-xl font-bold text-white tracking-tight truncate">
+                            {summary.display_name()}
+                        </h3>
+                        <p class="text-xs text-slate-500 font-mono truncate">
+                            {summary.address.clone()}
+                        </p>
+                    </div>
+                </div>
+                <a
+                    href=explorer_url
+                    target="_blank"
+                    rel="noopener noreferrer"
+                    class="text-slate-400 hover:text-white"
+                    title="Open in block explorer"
+                >
+                    <IconArrowUpRight class="h-5 w-5" />
+                </a>
+            </header>
+
+            <div class="p-6 grid grid-cols-1 sm:grid-cols-3 gap-4">
+                <SummaryTile
+                    label="Nodes"
+                    value=summary.nodes.len().to_string()
+                    sub_value=format!("{} active", summary.active_nodes)
+                />
+                <SummaryTile label="Stored Records" value=summary.records.to_string() />
+                <SummaryTile
+                    label="Balance"
+                    value=summary.balance.map(truncated_balance_str).unwrap_or("-".to_string())
+                />
+            </div>
+
+            <div class="px-6 pb-6">
+                <h4 class="text-xs font-bold text-slate-500 uppercase tracking-widest mb-2">
+                    "Nodes paying to this address"
+                </h4>
+                <Show
+                    when={
+                        let is_empty = nodes.is_empty();
+                        move || !is_empty
+                    }
+                    fallback=|| view! { <p class="text-sm text-slate-400">"None"</p> }
+                >
+                    <div class="flex flex-wrap gap-2">
+                        {nodes
+                            .clone()
+                            .into_iter()
+                            .map(|id| {
+                                view! {
+                                    <span class="px-2 py-0.5 rounded bg-slate-800 text-xs font-mono text-slate-300">
+                                        {id}
+                                    </span>
+                                }
+                            })
+                            .collect_view()}
+                    </div>
+                </Show>
+            </div>
+
+            {match summary.earnings.clone() {
+                Some(earnings) => {
+                    view! {
+                        <div class="p-6 grid grid-cols-1 md:grid-cols-2 gap-6 bg-slate-950/20 border-t border-slate-800">
+                            {earnings
+                                .periods
+                                .into_iter()
+                                .map(|period| view! { <PeriodStatCard stats=period /> })
+                                .collect_view()}
+                        </div>
+                    }
+                        .into_any()
+                }
+                None => {
+                    view! {
+                        <p class="p-6 text-center text-amber-400 border-t border-slate-800">
+                            "Earnings history not retrieved yet for this address..."
+                        </p>
+                    }
+                        .into_any()
+                }
+            }}
+
+            <Show when=move || context.can(Permission::ManageSettings)>
+                <AddressBookEntryForm
+                    address=summary.address.clone()
+                    entry=summary.entry.clone()
+                />
+            </Show>
+        </div>
+    }
+}
+
+#[component]
+fn SummaryTile(
+    label: &'static str,
+    value: String,
+    #[prop(optional)] sub_value: Option<String>,
+) -> impl IntoView {
+    view! {
+        <div class="bg-slate-950/40 border border-slate-800 rounded-2xl p-4">
+            <div class="text-[11px] font-bold text-slate-500 uppercase tracking-widest">
+                {label}
+            </div>
+            <div class="text-2xl font-bold text-white mt-1">{value}</div>
+            {sub_value.map(|v| view! { <div class="text-xs text-slate-400 mt-1">{v}</div> })}
+        </div>
+    }
+}
+
+// Form to set the label, note and colour of an address, or remove it from the address book.
+#[component]
+fn AddressBookEntryForm(address: String, entry: Option<AddressBookEntry>) -> impl IntoView {
+    let context = expect_context::<ClientGlobalState>();
+    let in_book = entry.is_some();
+    let entry = RwSignal::new(entry.unwrap_or(AddressBookEntry {
+        address,
+        ..AddressBookEntry::default()
+    }));
+    let input_class = "bg-slate-800 border border-slate-700 rounded-md px-3 py-2 text-sm focus:outline-none focus:ring-1 focus:ring-indigo-500";
+
+    let save = move |_| {
+        let to_save = entry.get_untracked();
+        spawn_local(async move {
+            match save_address_book_entry(to_save).await {
+                Ok(()) => refresh_address_book(context).await,
+                Err(err) => show_error_alert_msg(format!("Failed to save address label: {err}")),
+            }
+        });
+    };
+    let remove = move |_| {
+        let address = entry.read_untracked().address.clone();
+        spawn_local(async move {
+            match delete_address_book_entry(address).await {
+                Ok(()) => refresh_address_book(context).await,
+                Err(err) => show_error_alert_msg(format!("Failed to remove address label: {err}")),
+            }
+        });
+    };
+
+    view! {
+        <div class="p-6 border-t border-slate-800 bg-slate-800/10 space-y-4">
+            <h4 class="text-sm font-bold text-slate-300">
+                {if in_book { "Address Book Entry" } else { "Add to Address Book" }}
+            </h4>
+            <div class="grid grid-cols-1 md:grid-cols-[1fr_2fr_auto] gap-3">
+                <input
+                    type="text"
+                    placeholder="Label"
+                    class=input_class
+                    prop:value=move || entry.read().label.clone()
+                    on:input=move |ev| entry.update(|e| e.label = event_target_value(&ev))
+                />
+                <input
+                    type="text"
+                    placeholder="Note (optional)"
+                    class=input_class
+                    prop:value=move || entry.read().note.clone()
+                    on:input=move |ev| entry.update(|e| e.note = event_target_value(&ev))
+                />
+                <input
+                    type="color"
+                    title="Colour"
+                    class="h-10 w-14 bg-slate-800 border border-slate-700 rounded-md cursor-pointer"
+                    prop:value=move || entry.read().color.clone()
+                    on:input=move |ev| entry.update(|e| e.color = event_target_value(&ev))
+                />
+            </div>
+            <div class="flex items-center gap-3">
+                <button
+                    type="button"
+                    prop:disabled=move || entry.read().label.trim().is_empty()
+                    class="bg-indigo-600 hover:bg-indigo-500 transition-colors text-white px-4 py-2 rounded-lg font-medium text-sm disabled:opacity-75 disabled:bg-slate-600 disabled:text-slate-400 disabled:cursor-not-allowed"
+                    on:click=save
+                >
+                    {if in_book { "Save" } else { "Add Label" }}
+                </button>
+                <Show when=move || in_book>
+                    <button
+                        type="button"
+                        class="px-4 py-2 text-sm font-bold text-rose-400 hover:bg-slate-800 rounded-lg transition-colors"
+                        on:click=remove
+                    >
+                        Remove Label
+                    </button>
+                </Show>
+            </div>
+        </div>
+    }
+}
@@ -1,16 +1,24 @@
 use crate::{
-    server_api::{get_settings, test_llm_connection, update_settings},
-    types::{AppSettings, MetricsMode},
+    app::{ClientGlobalState, apply_app_settings},
+    server_api::{get_rpc_endpoints_health, get_settings, test_llm_connection, update_settings},
+    types::{
+        AppSettings, EarningsHistoryStart, MetricsMode, Permission, RpcEndpointHealth,
+        format_earnings_periods, parse_earnings_periods, parse_rpc_urls, parse_token_addresses,
+    },
 };
 
 use super::{
-    helpers::show_error_alert_msg,
+    helpers::{format_timestamp, show_error_alert_msg},
     icons::{
-        IconBot, IconCheck, IconLayoutDashboard, IconLcdSettings, IconSave, IconServer, IconWallet,
+        IconBell, IconBot, IconCheck, IconLayoutDashboard, IconLcdSettings, IconSave, IconServer,
+        IconShare, IconShield, IconWallet,
     },
+    notification_channels::NotificationChannelsCard,
+    users::UsersCard,
 };
 
 use alloy_primitives::Address;
+use chrono::Utc;
 use gloo_timers::future::TimeoutFuture;
 use leptos::{logging, prelude::*};
 use std::time::Duration;
@@ -23,6 +31,9 @@ const SETTINGS_TAB_INTERFACE: u8 = 1;
 const SETTINGS_TAB_REWARDS: u8 = 2;
 const SETTINGS_TAB_LCD_DEVICE: u8 = 3;
 const SETTINGS_TAB_AGENT: u8 = 4;
+const SETTINGS_TAB_METRICS_EXPORT: u8 = 5;
+const SETTINGS_TAB_NOTIFICATIONS: u8 = 6;
+const SETTINGS_TAB_USERS: u8 = 7;
 
 struct FormContent {
     saved_settings: RwSignal<AppSettings>,
@@ -32,9 +43,22 @@ struct FormContent {
     balances_retrieval_freq: RwSignal<Result<u64, (String, String)>>,
     rewards_monitoring_enabled: RwSignal<bool>,
     metrics_polling_freq: RwSignal<Result<u64, (String, String)>>,
+    metrics_polling_concurrency: RwSignal<Result<u64, (String, String)>>,
     disks_usage_check_freq: RwSignal<Result<u64, (String, String)>>,
     l2_network_rpc_url: RwSignal<Result<String, (String, String)>>,
+    l2_network_rpc_fallback_urls: RwSignal<Result<String, (String, String)>>,
     token_contract_address: RwSignal<Result<String, (String, String)>>,
+    additional_token_contracts: RwSignal<Result<String, (String, String)>>,
+    fiat_currency: RwSignal<Result<String, (String, String)>>,
+    token_price_api_url: RwSignal<String>,
+    earnings_periods: RwSignal<Result<String, (String, String)>>,
+    earnings_retention: RwSignal<Result<u64, (String, String)>>,
+    earnings_history_start: RwSignal<Result<String, (String, String)>>,
+    electricity_price: RwSignal<Result<f64, (String, String)>>,
+    host_power_watts: RwSignal<Result<f64, (String, String)>>,
+    watts_per_cpu_percent: RwSignal<Result<f64, (String, String)>>,
+    disk_cost_per_gb_month: RwSignal<Result<f64, (String, String)>>,
+    monthly_traffic_cap: RwSignal<Result<u64, (String, String)>>,
     lcd_enabled: RwSignal<bool>,
     lcd_device: RwSignal<Result<String, (String, String)>>,
     lcd_addr: RwSignal<Result<String, (String, String)>>,
@@ -49,7 +73,15 @@ struct FormContent {
     autonomous_check_interval: RwSignal<Result<u64, (String, String)>>,
     autonomous_max_actions: RwSignal<Result<u64, (String, String)>>,
     metrics_mode: RwSignal<MetricsMode>,
+    metrics_raw_retention: RwSignal<Result<u64, (String, String)>>,
+    metrics_minute_retention: RwSignal<Result<u64, (String, String)>>,
+    metrics_hourly_retention: RwSignal<Result<u64, (String, String)>>,
     node_bin_download_url: RwSignal<Result<String, (String, String)>>,
+    influxdb_enabled: RwSignal<bool>,
+    influxdb_url: RwSignal<Result<String, (String, String)>>,
+    influxdb_token: RwSignal<String>,
+    graphite_enabled: RwSignal<bool>,
+    graphite_addr: RwSignal<Result<String, (String, String)>>,
 }
 
 impl FormContent {
@@ -66,9 +98,33 @@ impl FormContent {
                 .as_secs())),
             rewards_monitoring_enabled: RwSignal::new(settings.rewards_monitoring_enabled),
             metrics_polling_freq: RwSignal::new(Ok(settings.nodes_metrics_polling_freq.as_secs())),
+            metrics_polling_concurrency: RwSignal::new(Ok(
+                settings.nodes_metrics_polling_concurrency
+            )),
             disks_usage_check_freq: RwSignal::new(Ok(settings.disks_usage_check_freq.as_secs())),
             l2_network_rpc_url: RwSignal::new(Ok(settings.l2_network_rpc_url.clone())),
+            l2_network_rpc_fallback_urls: RwSignal::new(Ok(settings
+                .l2_network_rpc_fallback_urls
+                .join(", "))),
             token_contract_address: RwSignal::new(Ok(settings.token_contract_address.clone())),
+            additional_token_contracts: RwSignal::new(Ok(settings
+                .additional_token_contracts
+                .join(", "))),
+            fiat_currency: RwSignal::new(Ok(settings.fiat_currency.clone())),
+            token_price_api_url: RwSignal::new(settings.token_price_api_url.clone()),
+            earnings_periods: RwSignal::new(Ok(format_earnings_periods(
+                &settings.earnings_periods_hours,
+            ))),
+            earnings_retention: RwSignal::new(Ok(settings.earnings_retention_days)),
+            earnings_history_start: RwSignal::new(Ok(settings
+                .earnings_history_start
+                .map(|start| start.to_string())
+                .unwrap_or_default())),
+            electricity_price: RwSignal::new(Ok(settings.electricity_price_per_kwh)),
+            host_power_watts: RwSignal::new(Ok(settings.host_power_watts)),
+            watts_per_cpu_percent: RwSignal::new(Ok(settings.watts_per_cpu_percent)),
+            disk_cost_per_gb_month: RwSignal::new(Ok(settings.disk_cost_per_gb_month)),
+            monthly_traffic_cap: RwSignal::new(Ok(settings.monthly_traffic_cap_gb)),
             lcd_enabled: RwSignal::new(settings.lcd_display_enabled),
             lcd_device: RwSignal::new(Ok(settings.lcd_device.clone())),
             lcd_addr: RwSignal::new(Ok(settings.lcd_addr.clone())),
@@ -83,10 +139,18 @@ impl FormContent {
             autonomous_check_interval: RwSignal::new(Ok(settings.autonomous_check_interval_secs)),
             autonomous_max_actions: RwSignal::new(Ok(settings.autonomous_max_actions_per_cycle)),
             metrics_mode: RwSignal::new(settings.metrics_mode),
+            metrics_raw_retention: RwSignal::new(Ok(settings.metrics_raw_retention_hours)),
+            metrics_minute_retention: RwSignal::new(Ok(settings.metrics_minute_retention_days)),
+            metrics_hourly_retention: RwSignal::new(Ok(settings.metrics_hourly_retention_days)),
             node_bin_download_url: RwSignal::new(Ok(settings
                 .node_bin_download_url
                 .clone()
                 .unwrap_or_default())),
+            influxdb_enabled: RwSignal::new(settings.influxdb_enabled),
+            influxdb_url: RwSignal::new(Ok(settings.influxdb_url)),
+            influxdb_token: RwSignal::new(settings.influxdb_token),
+            graphite_enabled: RwSignal::new(settings.graphite_enabled),
+            graphite_addr: RwSignal::new(Ok(settings.graphite_addr)),
         }
     }
 
@@ -102,11 +166,46 @@ impl FormContent {
             || self.rewards_monitoring_enabled.get() != saved_settings.rewards_monitoring_enabled
             || self.metrics_polling_freq.get()
                 != Ok(saved_settings.nodes_metrics_polling_freq.as_secs())
+            || self.metrics_polling_concurrency.get()
+                != Ok(saved_settings.nodes_metrics_polling_concurrency)
             || self.disks_usage_check_freq.get()
                 != Ok(saved_settings.disks_usage_check_freq.as_secs())
             || self.l2_network_rpc_url.get() != Ok(saved_settings.l2_network_rpc_url.clone())
+            || self
+                .l2_network_rpc_fallback_urls
+                .get()
+                .map(|v| parse_rpc_urls(&v))
+                .as_ref()
+                != Ok(&saved_settings.l2_network_rpc_fallback_urls)
             || self.token_contract_address.get()
                 != Ok(saved_settings.token_contract_address.clone())
+            || self
+                .additional_token_contracts
+                .get()
+                .ok()
+                .and_then(|v| parse_token_addresses(&v).ok())
+                .as_ref()
+                != Some(&saved_settings.additional_token_contracts)
+            || self.fiat_currency.get() != Ok(saved_settings.fiat_currency.clone())
+            || self.token_price_api_url.get() != saved_settings.token_price_api_url
+            || self
+                .earnings_periods
+                .get()
+                .ok()
+                .and_then(|v| parse_earnings_periods(&v).ok())
+                .as_ref()
+                != Some(&saved_settings.earnings_periods_hours)
+            || self.earnings_retention.get() != Ok(saved_settings.earnings_retention_days)
+            || self.earnings_history_start.get()
+                != Ok(saved_settings
+                    .earnings_history_start
+                    .map(|start| start.to_string())
+                    .unwrap_or_default())
+            || self.electricity_price.get() != Ok(saved_settings.electricity_price_per_kwh)
+            || self.host_power_watts.get() != Ok(saved_settings.host_power_watts)
+            || self.watts_per_cpu_percent.get() != Ok(saved_settings.watts_per_cpu_percent)
+            || self.disk_cost_per_gb_month.get() != Ok(saved_settings.disk_cost_per_gb_month)
+            || self.monthly_traffic_cap.get() != Ok(saved_settings.monthly_traffic_cap_gb)
             || self.lcd_enabled.get() != saved_settings.lcd_display_enabled
             || self.lcd_device.get() != Ok(saved_settings.lcd_device.clone())
             || self.lcd_addr.get() != Ok(saved_settings.lcd_addr.clone())
@@ -123,11 +222,21 @@ impl FormContent {
             || self.autonomous_max_actions.get()
                 != Ok(saved_settings.autonomous_max_actions_per_cycle)
             || self.metrics_mode.get() != saved_settings.metrics_mode
+            || self.metrics_raw_retention.get() != Ok(saved_settings.metrics_raw_retention_hours)
+            || self.metrics_minute_retention.get()
+                != Ok(saved_settings.metrics_minute_retention_days)
+            || self.metrics_hourly_retention.get()
+                != Ok(saved_settings.metrics_hourly_retention_days)
             || self.node_bin_download_url.get()
                 != Ok(saved_settings
                     .node_bin_download_url
                     .clone()
                     .unwrap_or_default())
+            || self.influxdb_enabled.get() != saved_settings.influxdb_enabled
+            || self.influxdb_url.get() != Ok(saved_settings.influxdb_url.clone())
+            || self.influxdb_token.get() != saved_settings.influxdb_token
+            || self.graphite_enabled.get() != saved_settings.graphite_enabled
+            || self.graphite_addr.get() != Ok(saved_settings.graphite_addr.clone())
     }
 
     pub fn get_valid_changes(&self) -> Option<AppSettings> {
@@ -148,6 +257,28 @@ impl FormContent {
         let v14 = self.autonomous_check_interval.get().ok()?;
         let v15 = self.autonomous_max_actions.get().ok()?;
         let v16 = self.node_bin_download_url.get().ok()?;
+        let v17 = self.metrics_raw_retention.get().ok()?;
+        let v18 = self.metrics_minute_retention.get().ok()?;
+        let v19 = self.metrics_hourly_retention.get().ok()?;
+        let v20 = self.influxdb_url.get().ok()?;
+        let v21 = self.graphite_addr.get().ok()?;
+        let v22 = self.metrics_polling_concurrency.get().ok()?;
+        let v23 = self.fiat_currency.get().ok()?;
+        let v24 = parse_earnings_periods(&self.earnings_periods.get().ok()?).ok()?;
+        let v25 = self.earnings_retention.get().ok()?;
+        let v26 = self.earnings_history_start.get().ok()?;
+        let v26 = if v26.is_empty() {
+            None
+        } else {
+            Some(v26.parse::<EarningsHistoryStart>().ok()?)
+        };
+        let v27 = parse_rpc_urls(&self.l2_network_rpc_fallback_urls.get().ok()?);
+        let v28 = parse_token_addresses(&self.additional_token_contracts.get().ok()?).ok()?;
+        let v29 = self.electricity_price.get().ok()?;
+        let v30 = self.host_power_watts.get().ok()?;
+        let v31 = self.watts_per_cpu_percent.get().ok()?;
+        let v32 = self.disk_cost_per_gb_month.get().ok()?;
+        let v33 = self.monthly_traffic_cap.get().ok()?;
 
         Some(AppSettings {
             nodes_auto_upgrade: self.auto_upgrade.get(),
@@ -156,9 +287,22 @@ impl FormContent {
             rewards_balances_retrieval_freq: Duration::from_secs(v3),
             rewards_monitoring_enabled: self.rewards_monitoring_enabled.get(),
             nodes_metrics_polling_freq: Duration::from_secs(v4),
+            nodes_metrics_polling_concurrency: v22,
             disks_usage_check_freq: Duration::from_secs(v5),
             l2_network_rpc_url: v6,
+            l2_network_rpc_fallback_urls: v27,
             token_contract_address: v7,
+            additional_token_contracts: v28,
+            fiat_currency: v23,
+            token_price_api_url: self.token_price_api_url.get(),
+            earnings_periods_hours: v24,
+            earnings_retention_days: v25,
+            earnings_history_start: v26,
+            electricity_price_per_kwh: v29,
+            host_power_watts: v30,
+            watts_per_cpu_percent: v31,
+            disk_cost_per_gb_month: v32,
+            monthly_traffic_cap_gb: v33,
             lcd_display_enabled: self.lcd_enabled.get(),
             lcd_device: v8,
             lcd_addr: v9,
@@ -173,7 +317,15 @@ impl FormContent {
             autonomous_check_interval_secs: v14,
             autonomous_max_actions_per_cycle: v15,
             metrics_mode: self.metrics_mode.get(),
+            metrics_raw_retention_hours: v17,
+            metrics_minute_retention_days: v18,
+            metrics_hourly_retention_days: v19,
             node_bin_download_url: (!v16.is_empty()).then_some(v16),
+            influxdb_enabled: self.influxdb_enabled.get(),
+            influxdb_url: v20,
+            influxdb_token: self.influxdb_token.get(),
+            graphite_enabled: self.graphite_enabled.get(),
+            graphite_addr: v21,
         })
     }
 
@@ -190,12 +342,41 @@ impl FormContent {
             .set(saved_settings.rewards_monitoring_enabled);
         self.metrics_polling_freq
             .set(Ok(saved_settings.nodes_metrics_polling_freq.as_secs()));
+        self.metrics_polling_concurrency
+            .set(Ok(saved_settings.nodes_metrics_polling_concurrency));
         self.disks_usage_check_freq
             .set(Ok(saved_settings.disks_usage_check_freq.as_secs()));
         self.l2_network_rpc_url
             .set(Ok(saved_settings.l2_network_rpc_url.clone()));
+        self.l2_network_rpc_fallback_urls
+            .set(Ok(saved_settings.l2_network_rpc_fallback_urls.join(", ")));
         self.token_contract_address
             .set(Ok(saved_settings.token_contract_address.clone()));
+        self.additional_token_contracts
+            .set(Ok(saved_settings.additional_token_contracts.join(", ")));
+        self.fiat_currency
+            .set(Ok(saved_settings.fiat_currency.clone()));
+        self.token_price_api_url
+            .set(saved_settings.token_price_api_url.clone());
+        self.earnings_periods.set(Ok(format_earnings_periods(
+            &saved_settings.earnings_periods_hours,
+        )));
+        self.earnings_retention
+            .set(Ok(saved_settings.earnings_retention_days));
+        self.earnings_history_start.set(Ok(saved_settings
+            .earnings_history_start
+            .map(|start| start.to_string())
+            .unwrap_or_default()));
+        self.electricity_price
+            .set(Ok(saved_settings.electricity_price_per_kwh));
+        self.host_power_watts
+            .set(Ok(saved_settings.host_power_watts));
+        self.watts_per_cpu_percent
+            .set(Ok(saved_settings.watts_per_cpu_percent));
+        self.disk_cost_per_gb_month
+            .set(Ok(saved_settings.disk_cost_per_gb_month));
+        self.monthly_traffic_cap
+            .set(Ok(saved_settings.monthly_traffic_cap_gb));
         self.lcd_enabled.set(saved_settings.lcd_display_enabled);
         self.lcd_device.set(Ok(saved_settings.lcd_device.clone()));
         self.lcd_addr.set(Ok(saved_settings.lcd_addr.clone()));
@@ -216,10 +397,24 @@ impl FormContent {
         self.autonomous_max_actions
             .set(Ok(saved_settings.autonomous_max_actions_per_cycle));
         self.metrics_mode.set(saved_settings.metrics_mode);
+        self.metrics_raw_retention
+            .set(Ok(saved_settings.metrics_raw_retention_hours));
+        self.metrics_minute_retention
+            .set(Ok(saved_settings.metrics_minute_retention_days));
+        self.metrics_hourly_retention
+            .set(Ok(saved_settings.metrics_hourly_retention_days));
         self.node_bin_download_url.set(Ok(saved_settings
             .node_bin_download_url
             .clone()
             .unwrap_or_default()));
+        self.influxdb_enabled.set(saved_settings.influxdb_enabled);
+        self.influxdb_url
+            .set(Ok(saved_settings.influxdb_url.clone()));
+        self.influxdb_token
+            .set(saved_settings.influxdb_token.clone());
+        self.graphite_enabled.set(saved_settings.graphite_enabled);
+        self.graphite_addr
+            .set(Ok(saved_settings.graphite_addr.clone()));
     }
 }
 
@@ -228,8 +423,14 @@ fn SettingsForm(form: RwSignal<FormContent>, active_tab: RwSignal<u8>) -> impl I
     // Status message for the "Test Connection" button
     let test_status = RwSignal::new(Option::<Result<String, String>>::None);
     let is_testing = RwSignal::new(false);
+    let context = expect_context::<ClientGlobalState>();
 
     view! {
+        <Show when=move || !context.can(Permission::ManageSettings)>
+            <p class="mb-4 px-4 py-3 text-sm text-amber-300 bg-amber-500/10 border border-amber-500/20 rounded-xl">
+                "Settings can only be changed by admin users."
+            </p>
+        </Show>
         <span hidden=move || active_tab.read() != SETTINGS_TAB_NODE_MGMT>
             <SettingsCard
                 icon=view! { <IconServer /> }.into_any()
@@ -281,12 +482,64 @@ fn SettingsForm(form: RwSignal<FormContent>, active_tab: RwSignal<u8>) -> impl I
                         min=5
                     />
                 </SettingRow>
+                <SettingRow
+                    label="Metrics Fetch Concurrency"
+                    description="Maximum number of nodes to fetch metrics from at the same time."
+                    error=Signal::derive(move || {
+                        form.read().metrics_polling_concurrency.read().clone().err()
+                    })
+                >
+                    <NumberInput
+                        name="metricsConcurrency"
+                        signal=form.read_untracked().metrics_polling_concurrency
+                        min=1
+                    />
+                </SettingRow>
                 <SettingRow
                     label="Metrics Source"
                     description="Choose where CPU and memory metrics are read from: the node's HTTP endpoint, the OS/Docker stats, or disabled entirely."
                 >
                     <MetricsModeSelect signal=form.read_untracked().metrics_mode />
                 </SettingRow>
+                <SettingRow
+                    label="Raw Metrics Retention"
+                    description="How long (in hours) to keep every metrics sample collected from nodes."
+                    error=Signal::derive(move || {
+                        form.read().metrics_raw_retention.read().clone().err()
+                    })
+                >
+                    <NumberInput
+                        name="metricsRawRetention"
+                        signal=form.read_untracked().metrics_raw_retention
+                        min=1
+                    />
+                </SettingRow>
+                <SettingRow
+                    label="1-Minute Metrics Retention"
+                    description="How long (in days) to keep metrics averaged over 1-minute intervals."
+                    error=Signal::derive(move || {
+                        form.read().metrics_minute_retention.read().clone().err()
+                    })
+                >
+                    <NumberInput
+                        name="metricsMinuteRetention"
+                        signal=form.read_untracked().metrics_minute_retention
+                        min=1
+                    />
+                </SettingRow>
+                <SettingRow
+                    label="1-Hour Metrics Retention"
+                    description="How long (in days) to keep metrics averaged over 1-hour intervals."
+                    error=Signal::derive(move || {
+                        form.read().metrics_hourly_retention.read().clone().err()
+                    })
+                >
+                    <NumberInput
+                        name="metricsHourlyRetention"
+                        signal=form.read_untracked().metrics_hourly_retention
+                        min=1
+                    />
+                </SettingRow>
                 <SettingRow
                     label="Disks Usage Check Frequency"
                     description="How often (in seconds) to check the nodes disk usage."
@@ -300,6 +553,19 @@ fn SettingsForm(form: RwSignal<FormContent>, active_tab: RwSignal<u8>) -> impl I
                         min=10
                     />
                 </SettingRow>
+                <SettingRow
+                    label="Monthly Traffic Cap"
+                    description="Traffic (in GB) allowed per month by the host's connection. A warning is raised when most of it has been used. Set it to 0 if the traffic is not capped."
+                    error=Signal::derive(move || {
+                        form.read().monthly_traffic_cap.read().clone().err()
+                    })
+                >
+                    <NumberInput
+                        name="monthlyTrafficCap"
+                        signal=form.read_untracked().monthly_traffic_cap
+                        min=0
+                    />
+                </SettingRow>
                 <SettingRow
                     label={
                         #[cfg(feature = "native")] { "Binary Download URL" }
@@ -413,6 +679,36 @@ fn SettingsForm(form: RwSignal<FormContent>, active_tab: RwSignal<u8>) -> impl I
                         validator=|v| { v.parse::<Url>().map_err(|err| err.to_string()).map(|_| v) }
                     />
                 </SettingRow>
+                <SettingRow
+                    label="Fallback L2 RPC URLs"
+                    description="Comma-separated RPC endpoints, in priority order, to fail over to when the ones above fail or rate-limit requests. The highest priority healthy endpoint is always used."
+                    full_width=true
+                    error=Signal::derive(move || {
+                        form.read().l2_network_rpc_fallback_urls.read().clone().err()
+                    })
+                >
+                    <TextInputNew
+                        name="rpcFallbackUrls"
+                        signal=form.read_untracked().l2_network_rpc_fallback_urls
+                        validator=|v| {
+                            parse_rpc_urls(&v)
+                                .iter()
+                                .try_for_each(|url| {
+                                    url.parse::<Url>()
+                                        .map(|_| ())
+                                        .map_err(|err| format!("'{url}': {err}"))
+                                })
+                                .map(|_| v)
+                        }
+                    />
+                </SettingRow>
+                <SettingRow
+                    label="L2 RPC Endpoints Health"
+                    description="Status of each RPC endpoint as seen by the last requests and periodic health checks. Changes take effect once settings are saved."
+                    full_width=true
+                >
+                    <RpcEndpointsHealth />
+                </SettingRow>
                 <SettingRow
                     label="ERC20 Token Contract Address"
                     description="The smart contract address for the network's rewards token."
@@ -429,6 +725,134 @@ fn SettingsForm(form: RwSignal<FormContent>, active_tab: RwSignal<u8>) -> impl I
                         }
                     />
                 </SettingRow>
+                <SettingRow
+                    label="Additional ERC20 Tokens"
+                    description="Comma-separated contract addresses of other ERC20 tokens which balances are tracked for each rewards address, along with their ETH balance."
+                    full_width=true
+                    error=Signal::derive(move || {
+                        form.read().additional_token_contracts.read().clone().err()
+                    })
+                >
+                    <TextInputNew
+                        name="additionalTokens"
+                        signal=form.read_untracked().additional_token_contracts
+                        validator=|v| parse_token_addresses(&v).map(|_| v)
+                    />
+                </SettingRow>
+                <SettingRow
+                    label="Fiat Currency"
+                    description="Currency reward payments are valued in when exporting earnings, e.g. usd or eur."
+                    error=Signal::derive(move || form.read().fiat_currency.read().clone().err())
+                >
+                    <TextInputNew
+                        name="fiatCurrency"
+                        signal=form.read_untracked().fiat_currency
+                        validator=|v| {
+                            if !v.is_empty() && v.chars().all(|c| c.is_ascii_alphabetic()) {
+                                Ok(v.to_lowercase())
+                            } else {
+                                Err("expected a currency code, e.g. usd".to_string())
+                            }
+                        }
+                    />
+                </SettingRow>
+                <SettingRow
+                    label="Token Price API URL"
+                    description="HTTP API the daily token prices are retrieved from, with '{from}' and '{to}' (UNIX timestamps) placeholders to retrieve them all with one request, or a '{date}' (dd-mm-yyyy) placeholder to retrieve each day's price, and a '{currency}' placeholder. Leave empty to export earnings without their fiat value."
+                    full_width=true
+                    error=Signal::derive(|| None)
+                >
+                    <input
+                        type="text"
+                        class="w-full bg-slate-800 border border-slate-700 rounded-md px-3 py-2 text-sm focus:outline-none font-mono transition-colors focus:ring-1 focus:ring-indigo-500"
+                        prop:value=move || form.read().token_price_api_url.get()
+                        on:input=move |ev| {
+                            form.read().token_price_api_url.set(event_target_value(&ev))
+                        }
+                        placeholder="(disabled)"
+                    />
+                </SettingRow>
+                <SettingRow
+                    label="Earnings Analysis Periods"
+                    description="Comma-separated lengths, in hours, of the periods earnings are analysed over and compared with their previous period, e.g. 24,168,720."
+                    error=Signal::derive(move || form.read().earnings_periods.read().clone().err())
+                >
+                    <TextInputNew
+                        name="earningsPeriods"
+                        signal=form.read_untracked().earnings_periods
+                        validator=|v| parse_earnings_periods(&v).map(|_| v)
+                    />
+                </SettingRow>
+                <SettingRow
+                    label="Earnings History Retention"
+                    description="How long (in days) reward payments history is kept. It should be at least twice the longest analysis period so it can be compared with the previous one."
+                    error=Signal::derive(move || {
+                        form.read().earnings_retention.read().clone().err()
+                    })
+                >
+                    <NumberInput
+                        name="earningsRetention"
+                        signal=form.read_untracked().earnings_retention
+                        min=1
+                    />
+                </SettingRow>
+                <SettingRow
+                    label="Earnings History Start"
+                    description="Block number or date (YYYY-MM-DD) to backfill and keep the full earnings history since, instead of only the retention period. Leave empty to disable."
+                    error=Signal::derive(move || {
+                        form.read().earnings_history_start.read().clone().err()
+                    })
+                >
+                    <TextInputNew
+                        name="earningsHistoryStart"
+                        signal=form.read_untracked().earnings_history_start
+                        validator=|v| {
+                            if v.trim().is_empty() {
+                                Ok(String::new())
+                            } else {
+                                v.parse::<EarningsHistoryStart>().map(|_| v)
+                            }
+                        }
+                    />
+                </SettingRow>
+                <SettingRow
+                    label="Electricity Price"
+                    description="Price of the electricity per kWh, in the fiat currency, used to estimate the cost of running the nodes."
+                    error=Signal::derive(move || form.read().electricity_price.read().clone().err())
+                >
+                    <DecimalInput name="electricityPrice" signal=form.read_untracked().electricity_price />
+                </SettingRow>
+                <SettingRow
+                    label="Host Power Draw"
+                    description="Power (in watts) drawn by the host, attributed to each node by its share of the memory used. Set it to 0 to estimate it from the nodes CPU usage instead."
+                    error=Signal::derive(move || form.read().host_power_watts.read().clone().err())
+                >
+                    <DecimalInput name="hostPowerWatts" signal=form.read_untracked().host_power_watts />
+                </SettingRow>
+                <SettingRow
+                    label="Power per CPU Percentage"
+                    description="Estimated power (in watts) drawn per each CPU usage percentage point of a node, when the host power draw is not set."
+                    error=Signal::derive(move || {
+                        form.read().watts_per_cpu_percent.read().clone().err()
+                    })
+                >
+                    <DecimalInput
+                        name="wattsPerCpuPercent"
+                        signal=form.read_untracked().watts_per_cpu_percent
+                    />
+                </SettingRow>
+                <SettingRow
+                    label="Disk Cost"
+                    description="Cost of each GB of disk space used by the nodes per month, in the fiat currency."
+                    error=Signal::derive(move || {
+                        form.read().disk_cost_per_gb_month.read().clone().err()
+                    })
+                >
+                    <DecimalInput
+                        name="diskCostPerGbMonth"
+                        signal=form.read_untracked().disk_cost_per_gb_month
+                    />
+                </SettingRow>
             </SettingsCard>
         </span>
         <span hidden=move || active_tab.read() != SETTINGS_TAB_AGENT>
@@ -538,7 +962,10 @@ fn SettingsForm(form: RwSignal<FormContent>, active_tab: RwSignal<u8>) -> impl I
                 </SettingRow>
             </SettingsCard>
             // Test Connection + Save row
-            <div class="mt-3 flex items-center gap-4 flex-wrap">
+            <div
+                class="mt-3 flex items-center gap-4 flex-wrap"
+                hidden=move || !context.can(Permission::ManageSettings)
+            >
                 <button
                     type="button"
                     prop:disabled=move || {
@@ -591,6 +1018,99 @@ fn SettingsForm(form: RwSignal<FormContent>, active_tab: RwSignal<u8>) -> impl I
                 </Show>
             </div>
         </span>
+        <span hidden=move || active_tab.read() != SETTINGS_TAB_METRICS_EXPORT>
+            <SettingsCard
+                icon=view! { <IconShare class="w-6 h-6" /> }.into_any()
+                title="Metrics Export"
+                description="Push nodes metrics, stats and balances to external monitoring systems."
+            >
+                <SettingRow
+                    label="Push to InfluxDB"
+                    description="Send metrics to an InfluxDB server using the line protocol over HTTP."
+                >
+                    <ToggleSwitch
+                        name="influxdbEnabled"
+                        checked=form.read_untracked().influxdb_enabled
+                    />
+                </SettingRow>
+                <SettingRow
+                    label="InfluxDB Write URL"
+                    description="Write endpoint including the target bucket/database, e.g. http://localhost:8086/api/v2/write?org=myorg&bucket=formicaio, or http://localhost:8086/write?db=formicaio for InfluxDB v1."
+                    full_width=true
+                    error=Signal::derive(move || form.read().influxdb_url.read().clone().err())
+                >
+                    <TextInputNew
+                        name="influxdbUrl"
+                        signal=form.read_untracked().influxdb_url
+                        validator=|v| { v.parse::<Url>().map_err(|e| e.to_string()).map(|_| v) }
+                    />
+                </SettingRow>
+                <SettingRow
+                    label="InfluxDB API Token"
+                    description="Optional token for authentication. Leave empty if your server requires no token."
+                    full_width=true
+                    error=Signal::derive(|| None)
+                >
+                    <input
+                        type="password"
+                        class="w-full bg-slate-800 border border-slate-700 rounded-md px-3 py-2 text-sm focus:outline-none font-mono transition-colors focus:ring-1 focus:ring-indigo-500"
+                        prop:value=move || form.read().influxdb_token.get()
+                        on:input=move |ev| form.read().influxdb_token.set(event_target_value(&ev))
+                        placeholder="(optional)"
+                    />
+                </SettingRow>
+                <SettingRow
+                    label="Push to Graphite"
+                    description="Send metrics to a Graphite server using the plaintext protocol over TCP."
+                >
+                    <ToggleSwitch
+                        name="graphiteEnabled"
+                        checked=form.read_untracked().graphite_enabled
+                    />
+                </SettingRow>
+                <SettingRow
+                    label="Graphite Address"
+                    description="Host and port of the Graphite plaintext protocol listener, e.g. localhost:2003."
+                    full_width=true
+                    error=Signal::derive(move || form.read().graphite_addr.read().clone().err())
+                >
+                    <TextInputNew
+                        name="graphiteAddr"
+                        signal=form.read_untracked().graphite_addr
+                        validator=|v| {
+                            match v.rsplit_once(':') {
+                                Some((host, port)) if !host.is_empty() => {
+                                    port.parse::<u16>().map_err(|err| err.to_string()).map(|_| v)
+                                }
+                                _ => Err("expected an address in the form host:port".to_string()),
+                            }
+                        }
+                    />
+                </SettingRow>
+            </SettingsCard>
+        </span>
+        <span hidden=move || active_tab.read() != SETTINGS_TAB_NOTIFICATIONS>
+            <Show when=move || context.can(Permission::ManageSettings)>
+                <SettingsCard
+                    icon=view! { <IconBell /> }.into_any()
+                    title="Notifications"
+                    description="Send node status changes, batch failures, AI agent errors and fired alerts to external services."
+                >
+                    <NotificationChannelsCard />
+                </SettingsCard>
+            </Show>
+        </span>
+        <span hidden=move || active_tab.read() != SETTINGS_TAB_USERS>
+            <Show when=move || context.can(Permission::ManageUsers)>
+                <SettingsCard
+                    icon=view! { <IconShield class="w-6 h-6" /> }.into_any()
+                    title="Users"
+                    description="Manage the users allowed to access this instance, and the role each of them has."
+                >
+                    <UsersCard />
+                </SettingsCard>
+            </Show>
+        </span>
         <span hidden=move || active_tab.read() != SETTINGS_TAB_LCD_DEVICE>
             <SettingsCard
                 icon=IconLcdSettings.into_any()
@@ -650,6 +1170,7 @@ pub fn SettingsView() -> impl IntoView {
     #[cfg(not(feature = "lcd-disabled"))]
     let lcd_disabled = false;
 
+    let context = expect_context::<ClientGlobalState>();
     let update_settings_action = Action::new(move |settings: &AppSettings| {
         let settings_clone = settings.clone();
         async move {
@@ -659,6 +1180,8 @@ pub fn SettingsView() -> impl IntoView {
                 show_error_alert_msg(msg);
             } else {
                 is_saved.set(true);
+                // settings are not pushed by the server, apply them to the context right away
+                apply_app_settings(&context, settings_clone.clone());
                 form_content.update(|f| f.saved_settings.set(settings_clone));
                 spawn_local(async move {
                     TimeoutFuture::new(3000).await;
@@ -702,6 +1225,28 @@ pub fn SettingsView() -> impl IntoView {
                             active_tab
                             tab_index=SETTINGS_TAB_AGENT
                         />
+                        <SideNavLink
+                            icon=view! { <IconShare class="w-5 h-5" /> }.into_any()
+                            label="Metrics Export"
+                            active_tab
+                            tab_index=SETTINGS_TAB_METRICS_EXPORT
+                        />
+                        <Show when=move || context.can(Permission::ManageSettings)>
+                            <SideNavLink
+                                icon=view! { <IconBell /> }.into_any()
+                                label="Notifications"
+                                active_tab
+                                tab_index=SETTINGS_TAB_NOTIFICATIONS
+                            />
+                        </Show>
+                        <Show when=move || context.can(Permission::ManageUsers)>
+                            <SideNavLink
+                                icon=view! { <IconShield class="w-5 h-5" /> }.into_any()
+                                label="Users"
+                                active_tab
+                                tab_index=SETTINGS_TAB_USERS
+                            />
+                        </Show>
                         <Show when=move || !lcd_disabled>
                             <SideNavLink
                                 icon=IconLcdSettings.into_any()
@@ -723,7 +1268,7 @@ pub fn SettingsView() -> impl IntoView {
                     </main>
                 </div>
 
-                <Show when=move || is_dirty()>
+                <Show when=move || is_dirty() && context.can(Permission::ManageSettings)>
                     <div class="sticky bottom-6 mt-6 z-50 animate-in fade-in slide-in-from-bottom-4 duration-300">
                         <div class="max-w-4xl mx-auto bg-slate-900/80 backdrop-blur-lg border border-slate-700 rounded-2xl shadow-2xl p-4 flex items-center justify-between">
                             <p class="text-sm font-medium text-slate-300">
@@ -811,6 +1356,104 @@ fn SettingsCard(
     }
 }
 
+// How often the health of the RPC endpoints is refreshed while displayed.
+const RPC_HEALTH_POLLING_FREQ: Duration = Duration::from_secs(15);
+
+#[component]
+fn RpcEndpointsHealth() -> impl IntoView {
+    let refresh = RwSignal::new(0u32);
+    Effect::new(move |_| {
+        if let Ok(handle) =
+            set_interval_with_handle(move || refresh.update(|n| *n += 1), RPC_HEALTH_POLLING_FREQ)
+        {
+            on_cleanup(move || handle.clear());
+        }
+    });
+    let health = Resource::new(move || refresh.get(), |_| get_rpc_endpoints_health());
+
+    view! {
+        <Transition fallback=move || {
+            view! { <p class="text-sm text-slate-400">"Retrieving endpoints health..."</p> }
+        }>
+            {move || {
+                health
+                    .get()
+                    .map(|res| match res {
+                        Ok(list) if list.is_empty() => {
+                            view! {
+                                <p class="text-sm text-slate-400">"No valid RPC endpoint is set"</p>
+                            }
+                                .into_any()
+                        }
+                        Ok(list) => {
+                            view! {
+                                <ul class="space-y-2">
+                                    {list
+                                        .into_iter()
+                                        .map(|endpoint| view! { <RpcEndpointHealthItem endpoint /> })
+                                        .collect_view()}
+                                </ul>
+                            }
+                                .into_any()
+                        }
+                        Err(err) => {
+                            view! {
+                                <p class="text-sm text-rose-400">
+                                    {format!("Failed to retrieve endpoints health: {err}")}
+                                </p>
+                            }
+                                .into_any()
+                        }
+                    })
+            }}
+        </Transition>
+    }
+}
+
+#[component]
+fn RpcEndpointHealthItem(endpoint: RpcEndpointHealth) -> impl IntoView {
+    let active = endpoint.active;
+    let status_class = match endpoint.healthy {
+        Some(true) => "bg-emerald-500",
+        Some(false) => "bg-rose-500",
+        None => "bg-slate-500",
+    };
+    let details = [
+        endpoint.latency_millis.map(|ms| format!("{ms} ms")),
+        endpoint.block_number.map(|b| format!("block #{b}")),
+        endpoint
+            .last_checked
+            .map(|ts| format!("checked {}", format_timestamp(ts))),
+        endpoint
+            .backoff_until
+            .filter(|until| *until > Utc::now().timestamp())
+            .map(|until| format!("rate-limited until {}", format_timestamp(until))),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(" · ");
+
+    view! {
+        <li class="bg-slate-800/50 border border-slate-700 rounded-md px-3 py-2 text-sm">
+            <div class="flex items-center gap-3">
+                <span class=format!("w-2.5 h-2.5 rounded-full shrink-0 {status_class}") />
+                <span class="text-xs text-slate-500">{format!("#{}", endpoint.priority + 1)}</span>
+                <span class="font-mono truncate text-slate-200">{endpoint.url}</span>
+                <Show when=move || active>
+                    <span class="ml-auto px-2 py-0.5 rounded-full text-xs font-semibold bg-indigo-500/20 text-indigo-300">
+                        "Active"
+                    </span>
+                </Show>
+            </div>
+            <p class="text-xs text-slate-500 mt-1">{details}</p>
+            {endpoint
+                .last_error
+                .map(|err| view! { <p class="text-xs text-rose-400 mt-1 break-all">{err}</p> })}
+        </li>
+    }
+}
+
 #[component]
 fn SettingRow(
     label: &'static str,
@@ -884,6 +1527,46 @@ pub fn NumberInput(
     }
 }
 
+#[component]
+pub fn DecimalInput(
+    signal: RwSignal<Result<f64, (String, String)>>,
+    name: &'static str,
+) -> impl IntoView {
+    let on_input = move |ev| {
+        let orig_val = event_target_value(&ev);
+        let val = match orig_val.parse::<f64>() {
+            Ok(v) if !v.is_finite() || v < 0.0 => {
+                Err((orig_val, "Value must be a positive number.".to_string()))
+            }
+            Ok(v) => Ok(v),
+            Err(err) => Err((orig_val, format!("Invalid value, {err}"))),
+        };
+        signal.set(val);
+    };
+
+    view! {
+        <input
+            type="number"
+            step="any"
+            min="0"
+            name=name
+            value=signal.get_untracked().unwrap_or_default()
+            prop:value=move || signal.get().map_or_else(|(v, _)| v, |v| v.to_string())
+            on:input=on_input
+            class=move || {
+                format!(
+                    "w-full bg-slate-800 border rounded-md px-3 py-2 text-sm focus:outline-none font-mono transition-colors {}",
+                    if signal.read().is_err() {
+                        "border-rose-500 ring-1 ring-rose-500/50"
+                    } else {
+                        "border-slate-700 focus:ring-1 focus:ring-indigo-500"
+                    },
+                )
+            }
+        />
+    }
+}
+
 #[component]
 pub fn TextInputNew(
     signal: RwSignal<Result<String, (String, String)>>,
@@ -1,6 +1,9 @@
 use super::{
     ViewType,
-    icons::{IconAbout, IconBot, IconDashboard, IconNodes, IconPlug, IconSettings, IconTerminal},
+    icons::{
+        IconAbout, IconBot, IconDashboard, IconHistory, IconNodes, IconPlug, IconSettings,
+        IconTerminal, IconWallet, IconWarning,
+    },
 };
 
 use leptos::prelude::*;
@@ -11,6 +14,17 @@ pub fn Sidebar(active_view: RwSignal<ViewType>, is_open: RwSignal<bool>) -> impl
     let nav_items = vec![
         (ViewType::Dashboard, "Dashboard", IconDashboard.into_any()),
         (ViewType::Nodes, "Nodes", IconNodes.into_any()),
+        (
+            ViewType::Addresses,
+            "Rewards Addresses",
+            view! { <IconWallet class="w-5 h-5" /> }.into_any(),
+        ),
+        (
+            ViewType::Batches,
+            "Batches",
+            view! { <IconHistory class="w-5 h-5" /> }.into_any(),
+        ),
+        (ViewType::Alerts, "Alerts", IconWarning.into_any()),
         (
             ViewType::Terminal,
             "Terminal",
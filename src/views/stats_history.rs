@@ -0,0 +1,266 @@
+use crate::{
+    server_api::stats_history,
+    types::{StatsHistoryRange, StatsSnapshot},
+};
+
+use super::{GB_CONVERTION, icons::IconActivity};
+
+use charming::{
+    Chart, WasmRenderer,
+    component::{Axis, Legend},
+    datatype::{CompositeValue, DataPoint},
+    element::{
+        AxisLabel, AxisLine, AxisType, Formatter, ItemStyle, JsFunction, SplitLine, TextStyle,
+        Tooltip, Trigger,
+    },
+    series::Line,
+};
+use leptos::{logging, prelude::*, task::spawn_local};
+
+const STATS_CHART_ID: &str = "stats_history_chart";
+
+// Set of stats which can be charted, each of them with one or two series.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum StatsChartKind {
+    Nodes,
+    Peers,
+    NetworkSize,
+    Records,
+    DiskUsage,
+}
+
+pub(super) type StatsSeries = (&'static str, &'static str, Vec<(i64, f64)>);
+
+impl StatsChartKind {
+    const ALL: [Self; 5] = [
+        Self::Nodes,
+        Self::Peers,
+        Self::NetworkSize,
+        Self::Records,
+        Self::DiskUsage,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Nodes => "Nodes",
+            Self::Peers => "Peers",
+            Self::NetworkSize => "Network Size",
+            Self::Records => "Records",
+            Self::DiskUsage => "Disk Usage",
+        }
+    }
+
+    // Name, color and data points of each series to chart.
+    fn series(&self, history: &[StatsSnapshot]) -> Vec<StatsSeries> {
+        let points = |f: fn(&StatsSnapshot) -> f64| {
+            history
+                .iter()
+                .map(|s| (s.timestamp, f(s)))
+                .collect::<Vec<_>>()
+        };
+        match self {
+            Self::Nodes => vec![
+                ("Total", "#818CF8", points(|s| s.total_nodes)),
+                ("Active", "#34D399", points(|s| s.active_nodes)),
+            ],
+            Self::Peers => vec![
+                ("Connected peers", "#22D3EE", points(|s| s.connected_peers)),
+                ("Shunned by", "#F87171", points(|s| s.shunned_count)),
+            ],
+            Self::NetworkSize => vec![(
+                "Estimated network size",
+                "#FB7185",
+                points(|s| s.estimated_net_size),
+            )],
+            Self::Records => vec![
+                ("Stored", "#FBBF24", points(|s| s.stored_records)),
+                ("Relevant", "#A3E635", points(|s| s.relevant_records)),
+            ],
+            Self::DiskUsage => vec![
+                (
+                    "Used by nodes (GB)",
+                    "#818CF8",
+                    points(|s| s.used_disk_space / GB_CONVERTION),
+                ),
+                (
+                    "Available (GB)",
+                    "#34D399",
+                    points(|s| s.available_disk_space / GB_CONVERTION),
+                ),
+            ],
+        }
+    }
+}
+
+pub(super) fn build_stats_chart(series: Vec<StatsSeries>, range: StatsHistoryRange) -> Chart {
+    let x_label_format = match range {
+        StatsHistoryRange::Day => "{HH}:{mm}",
+        StatsHistoryRange::Week | StatsHistoryRange::Month => "{MM}-{dd}",
+    };
+
+    let mut chart = Chart::new()
+        .legend(Legend::new().text_style(TextStyle::new().color("#9CA3AF")))
+        .tooltip(
+            Tooltip::new().trigger(Trigger::Axis).formatter(Formatter::Function(
+                JsFunction::new_with_args(
+                    "params",
+                    "var d = new Date(params[0].axisValue);
+                     var pad = function(n) { return n < 10 ? '0' + n : '' + n; };
+                     var date = d.getFullYear() + '-' + pad(d.getMonth()+1) + '-' + pad(d.getDate());
+                     var time = pad(d.getHours()) + ':' + pad(d.getMinutes());
+                     var out = '<b>' + date + ' ' + time + '</b><br/>';
+                     params.forEach(function(p) {
+                         out += p.marker + ' ' + p.seriesName + ': <b>' + (Math.round(p.value[1] * 100) / 100) + '</b><br/>';
+                     });
+                     return out;",
+                ),
+            )),
+        )
+        .x_axis(
+            Axis::new()
+                .type_(AxisType::Time)
+                .axis_line(AxisLine::new().show(false))
+                .split_line(SplitLine::new().show(false))
+                .axis_label(
+                    AxisLabel::new()
+                        .color("#9CA3AF")
+                        .formatter(Formatter::String(x_label_format.to_string())),
+                ),
+        )
+        .y_axis(
+            Axis::new()
+                .type_(AxisType::Value)
+                .scale(true)
+                .axis_label(AxisLabel::new().color("#9CA3AF"))
+                .axis_line(AxisLine::new().show(false))
+                .split_line(SplitLine::new().show(false)),
+        );
+
+    for (name, color, points) in series {
+        let data = points
+            .into_iter()
+            .map(|(ts, v)| {
+                DataPoint::from(CompositeValue::from(vec![
+                    CompositeValue::from(ts),
+                    CompositeValue::from(v),
+                ]))
+            })
+            .collect::<Vec<_>>();
+        chart = chart.series(
+            Line::new()
+                .name(name)
+                .data(data)
+                .smooth(true)
+                .show_symbol(false)
+                .item_style(ItemStyle::new().color(color)),
+        );
+    }
+
+    chart
+}
+
+#[component]
+pub fn StatsHistoryCard() -> impl IntoView {
+    let range = RwSignal::new(StatsHistoryRange::default());
+    let kind = RwSignal::new(StatsChartKind::Nodes);
+    let history = RwSignal::new(Vec::<StatsSnapshot>::new());
+    let echarts = RwSignal::new_local(None::<charming::renderer::wasm_renderer::Echarts>);
+
+    // Retrieve the history from the backend every time a different range is selected
+    Effect::new(move |_| {
+        let range = range.get();
+        spawn_local(async move {
+            match stats_history(range).await {
+                Ok(snapshots) => history.set(snapshots),
+                Err(err) => logging::error!("[ERROR] Failed to retrieve stats history: {err}"),
+            }
+        });
+    });
+
+    Effect::new(move |_| {
+        let chart = build_stats_chart(kind.get().series(&history.read()), range.get());
+        if echarts.with(|h| h.is_none()) {
+            match WasmRenderer::new_opt(None, None).render(STATS_CHART_ID, &chart) {
+                Ok(e) => echarts.set(Some(e)),
+                Err(err) => logging::error!("[ERROR] Failed to render stats chart: {err}"),
+            }
+        } else {
+            echarts.with(|h| {
+                if let Some(e) = h {
+                    WasmRenderer::update(e, &chart);
+                }
+            });
+        }
+    });
+
+    let tab_class = |selected: bool| {
+        format!(
+            "px-3 py-1.5 text-xs font-bold rounded-lg transition-colors {}",
+            if selected {
+                "bg-indigo-500/20 text-indigo-300"
+            } else {
+                "text-slate-500 hover:text-slate-300"
+            },
+        )
+    };
+
+    view! {
+        <div class="bg-slate-900 border border-slate-800 rounded-3xl overflow-hidden shadow-2xl">
+            <header class="p-6 border-b border-slate-800 bg-slate-800/10 backdrop-blur-md flex flex-col md:flex-row md:items-center justify-between gap-4">
+                <div class="flex items-center gap-4">
+                    <div class="p-3 bg-indigo-500/10 rounded-2xl text-indigo-400">
+                        <IconActivity class="w-5 h-5" />
+                    </div>
+                    <div>
+                        <h3 class="text-xl font-bold text-white tracking-tight">Stats History</h3>
+                        <p class="text-xs text-slate-500 font-medium uppercase tracking-widest mt-1">
+                            Fleet and Network Trends
+                        </p>
+                    </div>
+                </div>
+
+                <div class="flex flex-wrap items-center gap-2">
+                    <div class="flex items-center gap-1 bg-slate-950 border border-slate-800 rounded-xl p-1">
+                        {StatsChartKind::ALL
+                            .into_iter()
+                            .map(|k| {
+                                view! {
+                                    <button
+                                        class=move || tab_class(kind.get() == k)
+                                        on:click=move |_| kind.set(k)
+                                    >
+                                        {k.label()}
+                                    </button>
+                                }
+                            })
+                            .collect_view()}
+                    </div>
+                    <div class="flex items-center gap-1 bg-slate-950 border border-slate-800 rounded-xl p-1">
+                        {StatsHistoryRange::ALL
+                            .into_iter()
+                            .map(|r| {
+                                view! {
+                                    <button
+                                        class=move || tab_class(range.get() == r)
+                                        on:click=move |_| range.set(r)
+                                    >
+                                        {r.label()}
+                                    </button>
+                                }
+                            })
+                            .collect_view()}
+                    </div>
+                </div>
+            </header>
+
+            <div class="relative p-4" style="width: 100%; height: 340px;">
+                <div id=STATS_CHART_ID style="width: 100%; height: 100%;" />
+                <Show when=move || history.read().is_empty()>
+                    <div class="absolute inset-0 flex items-center justify-center pointer-events-none">
+                        <p class="text-slate-400 text-sm">"No stats history available yet"</p>
+                    </div>
+                </Show>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,211 @@
+use super::helpers::{format_timestamp, show_error_alert_msg, show_success_alert_msg};
+use crate::{
+    app::ClientGlobalState,
+    server_api::{create_user, delete_user, update_user, users_list},
+    types::{UserInfo, UserRole, validate_credentials},
+};
+
+use leptos::{prelude::*, task::spawn_local};
+
+const INPUT_CLASS: &str = "bg-slate-800 border border-slate-700 rounded-md px-3 py-2 text-sm focus:outline-none focus:ring-1 focus:ring-indigo-500";
+
+// Select of the role to assign to a user.
+#[component]
+fn RoleSelect(role: RwSignal<UserRole>) -> impl IntoView {
+    view! {
+        <select
+            class=INPUT_CLASS
+            on:change=move |ev| {
+                if let Ok(selected) = event_target_value(&ev).parse() {
+                    role.set(selected);
+                }
+            }
+        >
+            {UserRole::ALL
+                .into_iter()
+                .map(|r| {
+                    view! {
+                        <option value=r.as_str() selected=move || role.get() == r>
+                            {r.label()}
+                        </option>
+                    }
+                })
+                .collect_view()}
+        </select>
+    }
+}
+
+#[component]
+pub fn UsersCard() -> impl IntoView {
+    // bumped to retrieve up to date users from the backend
+    let refresh = RwSignal::new(0u32);
+    let users = Resource::new(move || refresh.get(), |_| users_list());
+
+    view! {
+        <Transition fallback=move || {
+            view! { <p class="p-6 text-slate-400">"Retrieving users..."</p> }
+        }>
+            {move || {
+                users
+                    .get()
+                    .map(|res| match res {
+                        Ok(list) => {
+                            view! {
+                                <ul class="divide-y divide-slate-800">
+                                    {list
+                                        .into_iter()
+                                        .map(|user| view! { <UserItem user refresh /> })
+                                        .collect_view()}
+                                </ul>
+                            }
+                                .into_any()
+                        }
+                        Err(err) => {
+                            view! {
+                                <p class="p-6 text-rose-400">
+                                    {format!("Failed to retrieve users: {err}")}
+                                </p>
+                            }
+                                .into_any()
+                        }
+                    })
+            }}
+        </Transition>
+        <NewUserForm refresh />
+    }
+}
+
+#[component]
+fn UserItem(user: UserInfo, refresh: RwSignal<u32>) -> impl IntoView {
+    let context = expect_context::<ClientGlobalState>();
+    let user_id = user.id;
+    let is_current = context
+        .current_user
+        .read_untracked()
+        .as_ref()
+        .is_some_and(|u| u.username == user.username);
+    let role = RwSignal::new(user.role);
+    let password = RwSignal::new(String::new());
+    let is_dirty = move || role.get() != user.role || !password.read().is_empty();
+
+    let on_save = move |_| {
+        let new_password = Some(password.get_untracked()).filter(|p| !p.is_empty());
+        spawn_local(async move {
+            match update_user(user_id, role.get_untracked(), new_password).await {
+                Ok(()) => show_success_alert_msg("User updated".to_string()),
+                Err(err) => show_error_alert_msg(format!("Failed to update user: {err}")),
+            }
+            refresh.update(|n| *n += 1);
+        });
+    };
+    let on_remove = move |_| {
+        spawn_local(async move {
+            if let Err(err) = delete_user(user_id).await {
+                show_error_alert_msg(format!("Failed to remove user: {err}"));
+            }
+            refresh.update(|n| *n += 1);
+        });
+    };
+
+    view! {
+        <li class="px-6 py-4 flex flex-wrap items-center gap-4">
+            <div class="flex-1 min-w-40">
+                <p class="text-sm text-slate-200 font-medium font-mono">
+                    {user.username} {is_current.then_some(" (you)")}
+                </p>
+                <p class="text-xs text-slate-500 mt-1">
+                    "Created " {format_timestamp(user.created)}
+                </p>
+            </div>
+            <RoleSelect role />
+            <input
+                type="password"
+                placeholder="New password"
+                autocomplete="new-password"
+                class=INPUT_CLASS
+                prop:value=move || password.get()
+                on:input=move |ev| password.set(event_target_value(&ev))
+            />
+            <div class="flex items-center gap-3 text-xs font-bold">
+                <button
+                    type="button"
+                    class="text-indigo-400 hover:text-indigo-300 disabled:text-slate-600 disabled:cursor-not-allowed"
+                    prop:disabled=move || !is_dirty()
+                    on:click=on_save
+                >
+                    Save
+                </button>
+                <Show when=move || !is_current>
+                    <button
+                        type="button"
+                        class="text-rose-400 hover:text-rose-300"
+                        on:click=on_remove
+                    >
+                        Remove
+                    </button>
+                </Show>
+            </div>
+        </li>
+    }
+}
+
+#[component]
+fn NewUserForm(refresh: RwSignal<u32>) -> impl IntoView {
+    let username = RwSignal::new(String::new());
+    let password = RwSignal::new(String::new());
+    let role = RwSignal::new(UserRole::default());
+
+    let on_create = move |_| {
+        let (new_username, new_password) = (username.get_untracked(), password.get_untracked());
+        if let Err(err) = validate_credentials(&new_username, &new_password) {
+            show_error_alert_msg(err);
+            return;
+        }
+        spawn_local(async move {
+            match create_user(new_username, new_password, role.get_untracked()).await {
+                Ok(()) => {
+                    username.set(String::new());
+                    password.set(String::new());
+                    role.set(UserRole::default());
+                }
+                Err(err) => show_error_alert_msg(format!("Failed to create user: {err}")),
+            }
+            refresh.update(|n| *n += 1);
+        });
+    };
+
+    view! {
+        <div class="p-6 border-t border-slate-800 bg-slate-800/10 space-y-4">
+            <h4 class="text-sm font-bold text-slate-300">"New User"</h4>
+            <div class="grid grid-cols-1 md:grid-cols-3 gap-3">
+                <input
+                    type="text"
+                    placeholder="Username"
+                    autocomplete="off"
+                    class=INPUT_CLASS
+                    prop:value=move || username.get()
+                    on:input=move |ev| username.set(event_target_value(&ev))
+                />
+                <input
+                    type="password"
+                    placeholder="Password"
+                    autocomplete="new-password"
+                    class=INPUT_CLASS
+                    prop:value=move || password.get()
+                    on:input=move |ev| password.set(event_target_value(&ev))
+                />
+                <RoleSelect role />
+            </div>
+            <p class="text-xs text-slate-500">
+                "Viewers can only monitor nodes, operators can also create, start, stop, upgrade and recycle them, while admins can additionally remove nodes, change the settings and manage users."
+            </p>
+            <button
+                type="button"
+                class="bg-indigo-600 hover:bg-indigo-500 transition-colors text-white px-4 py-2 rounded-lg font-medium text-sm"
+                on:click=on_create
+            >
+                Add User
+            </button>
+        </div>
+    }
+}